@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::HashMap;
+use whitehole::utils::CharMap;
+
+/// ASCII-heavy text with a few non-ASCII chars, like most source code.
+fn text() -> String {
+  "fn main() { let x = [1, 2, 3]; println!(\"{}\", x.len() * 2 - 1); } // 好\n".repeat(10000)
+}
+
+fn entries() -> Vec<(char, u8)> {
+  "(){}[];,.+-*/=!<>\""
+    .chars()
+    .chain(['好', '→'])
+    .zip(0..)
+    .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+  let text = text();
+  let char_map: CharMap<u8> = entries().into_iter().collect();
+  let hash_map: HashMap<char, u8> = entries().into_iter().collect();
+
+  c.bench_function("lookup with `CharMap`", |b| {
+    b.iter(|| {
+      black_box(&text)
+        .chars()
+        .map(|c| *char_map.get_or(c, &0) as usize)
+        .sum::<usize>()
+    })
+  });
+
+  c.bench_function("lookup with `HashMap`", |b| {
+    b.iter(|| {
+      black_box(&text)
+        .chars()
+        .map(|c| hash_map.get(&c).copied().unwrap_or(0) as usize)
+        .sum::<usize>()
+    })
+  });
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
@@ -2,7 +2,7 @@ use crate::common::{number, string, whitespaces};
 use in_str::in_str;
 use whitehole::{
   action::Action,
-  combinator::{next, Combinator},
+  combinator::{dispatch, eat, next, Combinator},
 };
 
 pub fn lexer_entry() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>> {
@@ -10,3 +10,15 @@ pub fn lexer_entry() -> Combinator<impl Action<Text = str, State = (), Heap = ()
 
   whitespaces() | boundary | number() | string() | "true" | "false" | "null"
 }
+
+pub fn lexer_entry_with_dispatch(
+) -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>> {
+  dispatch()
+    .on(" \t\r\n", whitespaces())
+    .on("[]{}:,", next(in_str!("[]{}:,")))
+    .on("-0123456789", number())
+    .on('"', string())
+    .on('t', eat("true"))
+    .on('f', eat("false"))
+    .on('n', eat("null"))
+}
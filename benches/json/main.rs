@@ -3,7 +3,7 @@ mod lexer;
 mod parser;
 
 use criterion::{criterion_group, criterion_main, Criterion};
-use lexer::lexer_entry;
+use lexer::{lexer_entry, lexer_entry_with_dispatch};
 use parser::{parser_entry_with_recur, parser_entry_with_static};
 use std::fs::read_to_string;
 use whitehole::{action::Action, combinator::Combinator, parser::Parser};
@@ -54,6 +54,13 @@ fn lex_json(c: &mut Criterion) {
   bench_with("lex_json", lex, c);
 }
 
+fn lex_json_with_dispatch(c: &mut Criterion) {
+  fn lex(s: &str) {
+    process(lexer_entry_with_dispatch(), s);
+  }
+  bench_with("lex_json_with_dispatch", lex, c);
+}
+
 fn parse_json_with_recur(c: &mut Criterion) {
   fn parse_with_recur(s: &str) {
     process(parser_entry_with_recur(), s);
@@ -71,6 +78,6 @@ fn parse_json_with_static(c: &mut Criterion) {
 criterion_group! {
   name = benches;
   config = Criterion::default();
-  targets = lex_json, parse_json_with_recur, parse_json_with_static
+  targets = lex_json, lex_json_with_dispatch, parse_json_with_recur, parse_json_with_static
 }
 criterion_main!(benches);
@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use whitehole::{
+  action::Action,
+  combinator::{contextual, Combinator},
+  memo::MemoTable,
+  parser::Parser,
+};
+
+contextual!((), MemoTable);
+
+// expr = term '+' expr | term '-' expr | term
+// term = '(' expr ')' | digit
+// without memoization, `term` is executed 3 times at the same offset per nesting level
+fn plain() -> Combinator<impl Action<Text = str, State = (), Heap = MemoTable, Value = ()>> {
+  let (expr, set_expr) = recur::<()>();
+  let (term, set_term) = recur::<()>();
+  set_expr.boxed((term() + '+' + expr()) | (term() + '-' + expr()) | term());
+  set_term.boxed((eat('(') + expr() + ')') | next(|c| c.is_ascii_digit()));
+  expr()
+}
+
+fn memoized() -> Combinator<impl Action<Text = str, State = (), Heap = MemoTable, Value = ()>> {
+  let (expr, set_expr) = recur::<()>();
+  let (term, set_term) = recur::<()>();
+  let term = || term().memo(0);
+  set_expr.boxed((term() + '+' + expr()) | (term() + '-' + expr()) | term());
+  set_term.boxed((eat('(') + expr() + ')') | next(|c| c.is_ascii_digit()));
+  expr()
+}
+
+fn nested_text(depth: usize) -> String {
+  format!("{}1{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+fn process(entry: impl Action<Text = str, State = (), Heap = MemoTable, Value = ()>, s: &str) {
+  let mut parser = Parser::builder()
+    .heap(MemoTable::new())
+    .entry(entry)
+    .build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn backtracking(c: &mut Criterion) {
+  let text = nested_text(10);
+
+  c.bench_function("nested parentheses without `memo`", |b| {
+    let entry = plain();
+    b.iter(|| process(&entry, black_box(&text)))
+  });
+
+  c.bench_function("nested parentheses with `memo`", |b| {
+    let entry = memoized();
+    b.iter(|| process(&entry, black_box(&text)))
+  });
+}
+
+criterion_group!(benches, backtracking);
+criterion_main!(benches);
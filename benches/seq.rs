@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use whitehole::{
+  action::Action,
+  combinator::{eat, next, seq, Combinator},
+  parser::Parser,
+};
+
+// a fixed-field record with 12 elements: 6 fields and 6 separators
+fn field() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>> {
+  next(|c| c.is_ascii_alphanumeric()) * (1..)
+}
+
+fn record_text() -> String {
+  "abc,12345,x,hello,42,zz\n".repeat(10000)
+}
+
+fn process(entry: impl Action<Text = str, State = (), Heap = (), Value = ()>, s: &str) {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn fixed_fields(c: &mut Criterion) {
+  let text = record_text();
+
+  c.bench_function("12 elements with `+`", |b| {
+    b.iter(|| {
+      process(
+        field()
+          + ','
+          + field()
+          + ','
+          + field()
+          + ','
+          + field()
+          + ','
+          + field()
+          + ','
+          + field()
+          + '\n',
+        black_box(&text),
+      )
+    })
+  });
+
+  c.bench_function("12 elements with `seq`", |b| {
+    b.iter(|| {
+      process(
+        seq((
+          field(),
+          eat(','),
+          field(),
+          eat(','),
+          field(),
+          eat(','),
+          field(),
+          eat(','),
+          field(),
+          eat(','),
+          field(),
+          eat('\n'),
+        )),
+        black_box(&text),
+      )
+    })
+  });
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default();
+  targets = fixed_fields
+}
+criterion_main!(benches);
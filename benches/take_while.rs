@@ -0,0 +1,110 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use whitehole::{
+  action::Action,
+  combinator::{bytes, eat, next, take_while_min_max},
+  parser::Parser,
+};
+
+fn ident_text() -> String {
+  let mut text = "a_very_long_identifier_".repeat(1000);
+  text.push(' ');
+  text.repeat(100)
+}
+
+fn process(entry: impl Action<Text = str, State = (), Heap = (), Value = ()>, s: &str) {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn process_bytes(entry: impl Action<Text = [u8], State = (), Heap = (), Value = ()>, s: &[u8]) {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn scan_idents(c: &mut Criterion) {
+  let text = ident_text();
+  let ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+  let ident_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+
+  c.bench_function("ident with `next * (1..)`", |b| {
+    b.iter(|| process((next(ident) * (1..)) + eat(' '), black_box(&text)))
+  });
+
+  c.bench_function("ident with `take_while_min_max`", |b| {
+    b.iter(|| {
+      process(
+        take_while_min_max(ident, 1, usize::MAX) + eat(' '),
+        black_box(&text),
+      )
+    })
+  });
+
+  c.bench_function("ident with `bytes::next * (1..)`", |b| {
+    b.iter(|| {
+      process_bytes(
+        (bytes::next(ident_byte) * (1..)) + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+
+  c.bench_function("ident with `bytes::take_while_min_max`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::take_while_min_max(ident_byte, 1, usize::MAX) + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+}
+
+fn scan_digits(c: &mut Criterion) {
+  let mut text = "1234567890".repeat(100);
+  text.push(' ');
+  let text = text.repeat(1000);
+
+  c.bench_function("digits with `bytes::take_while_min_max`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::take_while_min_max(|b| b.is_ascii_digit(), 1, usize::MAX) + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+
+  c.bench_function("digits with `bytes::ascii_digits1`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::ascii_digits1() + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+
+  c.bench_function("alnums with `bytes::take_while_min_max`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::take_while_min_max(|b| b.is_ascii_alphanumeric(), 1, usize::MAX) + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+
+  c.bench_function("alnums with `bytes::ascii_alnums1`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::ascii_alnums1() + bytes::eat(b' '),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default();
+  targets = scan_idents, scan_digits
+}
+criterion_main!(benches);
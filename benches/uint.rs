@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use whitehole::{
+  action::Action,
+  combinator::{bounded_uint, eat, next},
+  parser::Parser,
+};
+
+fn process(entry: impl Action<Text = str, State = (), Heap = (), Value = u64>, s: &str) -> u64 {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  let sum = (&mut parser).map(|o| o.value).sum();
+  assert!(parser.instant.rest().is_empty());
+  sum
+}
+
+fn ports(c: &mut Criterion) {
+  let text = "80,443,8080,65535,0,22,3000,".repeat(10000);
+
+  c.bench_function("bounded_uint", |b| {
+    b.iter(|| process(bounded_uint(0, 65535) + eat(','), black_box(&text)))
+  });
+
+  c.bench_function("select-parse", |b| {
+    b.iter(|| {
+      process(
+        (next(|c| c.is_ascii_digit()) * (1..))
+          .reject(|accepted| {
+            accepted
+              .content()
+              .parse::<u64>()
+              .map_or(true, |v| v > 65535)
+          })
+          .select(|accepted| accepted.content().parse::<u64>().unwrap())
+          + eat(','),
+        black_box(&text),
+      )
+    })
+  });
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default();
+  targets = ports
+}
+criterion_main!(benches);
@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use whitehole::{
+  action::Action,
+  combinator::{bytes, eat, eat_until, next, till_exclusive},
+  parser::Parser,
+};
+
+fn log_text() -> String {
+  "2024-01-01T00:00:00Z INFO server started on port 8080, waiting for connections\n".repeat(10000)
+}
+
+fn process(entry: impl Action<Text = str, State = (), Heap = (), Value = ()>, s: &str) {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn process_bytes(entry: impl Action<Text = [u8], State = (), Heap = (), Value = ()>, s: &[u8]) {
+  let mut parser = Parser::builder().entry(entry).build(s);
+  for _ in &mut parser {}
+  assert!(parser.instant.rest().is_empty());
+}
+
+fn scan_lines(c: &mut Criterion) {
+  let text = log_text();
+
+  c.bench_function("line with `next * (1..)`", |b| {
+    b.iter(|| process((next(|c| c != '\n') * (1..)) + eat('\n'), black_box(&text)))
+  });
+
+  c.bench_function("line with `till_exclusive`", |b| {
+    b.iter(|| process(till_exclusive('\n') + eat('\n'), black_box(&text)))
+  });
+
+  c.bench_function("line with `eat_until`", |b| {
+    b.iter(|| process(eat_until(['\n']) + eat('\n'), black_box(&text)))
+  });
+
+  c.bench_function("line with `eat_until` 3 needles", |b| {
+    b.iter(|| process(eat_until(['\n', '\r', '\0']) + eat('\n'), black_box(&text)))
+  });
+
+  c.bench_function("line with `bytes::next * (1..)`", |b| {
+    b.iter(|| {
+      process_bytes(
+        (bytes::next(|c| c != b'\n') * (1..)) + bytes::eat(b'\n'),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+
+  c.bench_function("line with `bytes::eat_until`", |b| {
+    b.iter(|| {
+      process_bytes(
+        bytes::eat_until([b'\n']) + bytes::eat(b'\n'),
+        black_box(text.as_bytes()),
+      )
+    })
+  });
+}
+
+criterion_group! {
+  name = benches;
+  config = Criterion::default();
+  targets = scan_lines
+}
+criterion_main!(benches);
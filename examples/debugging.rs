@@ -0,0 +1,54 @@
+//! This example demonstrates how to debug a grammar.
+//!
+//! - [`Combinator::log`] prints the input and the digested text of a combinator,
+//!   with indentation for nested logs.
+//! - [`Combinator::inspect_value`] observes the value after being accepted.
+//! - [`Combinator::inspect_err`] observes the rejection.
+//!
+//! None of them changes the behavior of the parser,
+//! so they can be added and removed freely while debugging.
+
+use whitehole::{
+  action::Action,
+  combinator::{eat, next, Combinator},
+  parser::Parser,
+};
+
+/// Parse a comma separated list of integers, e.g. `1,22,333`, and sum them up.
+pub fn sum() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = u32>> {
+  let integer = (next(|c| c.is_ascii_digit()) * (1..))
+    .log("integer")
+    .select(|accepted| accepted.content().parse::<u32>().unwrap())
+    // observe the value in the middle of the chain
+    .inspect_value(|v| println!("integer value: {}", v));
+
+  (integer * (1..))
+    .sep(eat(',').inspect_err(|| println!("no more separators")))
+    .fold(|| 0, |acc, v| acc + v)
+    .inspect_value(|v| println!("sum: {}", v))
+    .inspect_err(|| println!("sum rejected"))
+    .log("sum")
+}
+
+fn main() {
+  let mut parser = Parser::builder().entry(sum()).build("1,22,333");
+  println!("{:?}", parser.next());
+
+  let mut parser = Parser::builder().entry(sum()).build("a");
+  println!("{:?}", parser.next());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_debugging() {
+    let mut parser = Parser::builder().entry(sum()).build("1,22,333");
+    assert_eq!(parser.next().unwrap().value, 356);
+    assert_eq!(parser.instant.rest(), "");
+
+    let mut parser = Parser::builder().entry(sum()).build("a");
+    assert!(parser.next().is_none());
+  }
+}
@@ -0,0 +1,342 @@
+//! A TOML-lite format, line by line:
+//!
+//! ```toml
+//! # comments start with '#'
+//! key = "value"
+//!
+//! [section]
+//! int = -123
+//! bool = true
+//! array = [1, "two", false] # trailing comments are allowed
+//! ```
+
+use whitehole::{
+  action::Action,
+  combinator::Combinator,
+  contextual,
+  diagnostic::{Diag, Diagnostics, Severity},
+  parser::Parser,
+};
+
+// the heap is used to collect diagnostics in the lenient mode
+contextual!((), Diagnostics);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+  String(String),
+  Int(i64),
+  Bool(bool),
+  Array(Vec<Value>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Section {
+  /// The root section has an empty name.
+  pub name: String,
+  pub entries: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+  /// The first section is always the root section.
+  pub sections: Vec<Section>,
+}
+
+impl Default for Document {
+  fn default() -> Self {
+    Self {
+      sections: vec![Section::default()],
+    }
+  }
+}
+
+impl Document {
+  pub fn get(&self, section: &str, key: &str) -> Option<&Value> {
+    self
+      .sections
+      .iter()
+      .filter(|s| s.name == section)
+      .flat_map(|s| s.entries.iter())
+      .find(|(k, _)| k == key)
+      .map(|(_, v)| v)
+  }
+}
+
+/// The value of each line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+  /// Empty lines or comment lines.
+  Empty,
+  Section(String),
+  Entry(String, Value),
+  /// Only yielded in the lenient mode. A diagnostic is emitted to the heap.
+  Invalid,
+}
+
+fn blank() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = ()>> {
+  next(|c| c == ' ' || c == '\t') * (..)
+}
+
+/// Optional blanks and an optional comment, then the line ending.
+fn line_tail() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = ()>> {
+  blank() + (eat('#') + till_line_end()).optional() + line_end()
+}
+
+fn name() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = String>> {
+  (next(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') * (1..))
+    .select(|accepted| accepted.content().to_string())
+}
+
+fn string() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  let escape = eat('\\') + next(|c| matches!(c, '"' | '\\' | 'n' | 't'));
+  let non_escape = next(|c| c != '"' && c != '\\' && c != '\n') * (1..);
+  (eat('"') + (escape | non_escape) * (..) + '"').select(|accepted| {
+    let content = accepted.content();
+    let mut s = String::with_capacity(content.len());
+    let mut chars = content[1..content.len() - 1].chars();
+    while let Some(c) = chars.next() {
+      s.push(match c {
+        '\\' => match chars.next() {
+          Some('n') => '\n',
+          Some('t') => '\t',
+          Some(c) => c,
+          None => unreachable!(),
+        },
+        c => c,
+      });
+    }
+    Value::String(s)
+  })
+}
+
+fn int() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  ((eat('-') | '+').optional() + next(|c| c.is_ascii_digit()) * (1..))
+    // reject on overflow
+    .reject(|accepted| accepted.content().parse::<i64>().is_err())
+    .select(|accepted| Value::Int(accepted.content().parse().unwrap()))
+}
+
+fn bool() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  eat("true").bind(Value::Bool(true)) | eat("false").bind(Value::Bool(false))
+}
+
+fn scalar() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  string() | int() | bool()
+}
+
+fn array() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  let items = (scalar() * (..))
+    .sep(blank() + ',' + blank())
+    .fold(Vec::new, |mut acc, v| {
+      acc.push(v);
+      acc
+    });
+  (eat('[') + blank() + items.tuple() + blank() + ']').map(|(items,)| Value::Array(items))
+}
+
+fn value() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Value>> {
+  scalar() | array()
+}
+
+fn valid_line() -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Line>>
+{
+  let section = (blank() + '[' + blank() + name().tuple() + blank() + ']' + line_tail())
+    .map(|(name,)| Line::Section(name));
+  let entry = (blank() + name().tuple() + blank() + '=' + blank() + value().tuple() + line_tail())
+    .map(|(k, v)| Line::Entry(k, v));
+  // line_tail may accept 0 bytes at the end of the input, reject it to prevent infinite loops
+  let empty = line_tail()
+    .reject(|accepted| accepted.digested() == 0)
+    .bind(Line::Empty);
+  section | entry | empty
+}
+
+fn invalid_line(
+) -> Combinator<impl Action<Text = str, State = (), Heap = Diagnostics, Value = Line>> {
+  (till_line_end() + line_end())
+    .reject(|accepted| accepted.digested() == 0)
+    .emit(|accepted| Diag {
+      range: accepted.range(),
+      severity: Severity::Error,
+      code: Some("E001"),
+      message: "invalid line".into(),
+    })
+    .bind(Line::Invalid)
+}
+
+fn build(lines: impl Iterator<Item = (Line, usize)>, diagnostics: &mut Diagnostics) -> Document {
+  let mut doc = Document::default();
+  for (line, start) in lines {
+    match line {
+      Line::Section(name) => doc.sections.push(Section {
+        name,
+        entries: Vec::new(),
+      }),
+      Line::Entry(key, value) => {
+        let section = doc.sections.last_mut().unwrap();
+        if section.entries.iter().any(|(k, _)| *k == key) {
+          diagnostics.push(Diag {
+            range: start..start + key.len(),
+            severity: Severity::Warning,
+            code: Some("W001"),
+            message: format!("duplicated key `{}` is ignored", key),
+          });
+        } else {
+          section.entries.push((key, value));
+        }
+      }
+      Line::Empty | Line::Invalid => {}
+    }
+  }
+  doc
+}
+
+/// Parse the text strictly.
+/// Return diagnostics if any line is invalid.
+pub fn parse(text: &str) -> Result<Document, Diagnostics> {
+  let mut parser = Parser::builder()
+    .heap(Diagnostics::new())
+    .entry(valid_line().range())
+    .build(text);
+  let mut diagnostics = Diagnostics::new();
+  let lines = (&mut parser)
+    .map(|o| (o.value.data, o.value.range.start))
+    .collect::<Vec<_>>();
+  let doc = build(lines.into_iter(), &mut diagnostics);
+
+  match parser.finish() {
+    Ok(_) if diagnostics.is_empty() => Ok(doc),
+    Ok(_) => Err(diagnostics),
+    Err(parser) => {
+      let start = parser.instant.digested();
+      let line_len = parser
+        .instant
+        .rest()
+        .find('\n')
+        .unwrap_or(parser.instant.rest().len());
+      diagnostics.push(Diag {
+        range: start..start + line_len,
+        severity: Severity::Error,
+        code: Some("E001"),
+        message: "invalid line".into(),
+      });
+      Err(diagnostics)
+    }
+  }
+}
+
+/// Parse the text leniently.
+/// Invalid lines are skipped and reported in the returned diagnostics.
+pub fn parse_lenient(text: &str) -> (Document, Diagnostics) {
+  let mut parser = Parser::builder()
+    .heap(Diagnostics::new())
+    .entry((valid_line() | invalid_line()).range())
+    .build(text);
+  let lines = (&mut parser)
+    .map(|o| (o.value.data, o.value.range.start))
+    .collect::<Vec<_>>();
+  // in the lenient mode every line is accepted
+  let (_, mut diagnostics) = parser.finish().ok().unwrap();
+  let doc = build(lines.into_iter(), &mut diagnostics);
+  (doc, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const VALID: &str = "\
+# comment
+title = \"hello \\\"world\\\"\"
+
+[owner]
+name = \"Tom\" # trailing comment
+age = 18
+\t[ server-1 ]\r
+enabled = true
+ports = [ 8000, 8001 ,8002 ]
+mixed = [1, \"two\", false]
+empty = []";
+
+  #[test]
+  fn parse_valid() {
+    let doc = parse(VALID).unwrap();
+    assert_eq!(doc.sections.len(), 3);
+    assert_eq!(
+      doc.get("", "title"),
+      Some(&Value::String("hello \"world\"".into()))
+    );
+    assert_eq!(doc.get("owner", "name"), Some(&Value::String("Tom".into())));
+    assert_eq!(doc.get("owner", "age"), Some(&Value::Int(18)));
+    assert_eq!(doc.get("server-1", "enabled"), Some(&Value::Bool(true)));
+    assert_eq!(
+      doc.get("server-1", "ports"),
+      Some(&Value::Array(vec![
+        Value::Int(8000),
+        Value::Int(8001),
+        Value::Int(8002)
+      ]))
+    );
+    assert_eq!(
+      doc.get("server-1", "mixed"),
+      Some(&Value::Array(vec![
+        Value::Int(1),
+        Value::String("two".into()),
+        Value::Bool(false)
+      ]))
+    );
+    assert_eq!(doc.get("server-1", "empty"), Some(&Value::Array(vec![])));
+
+    // the lenient mode yields the same document without diagnostics
+    let (lenient, diagnostics) = parse_lenient(VALID);
+    assert_eq!(lenient, doc);
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn parse_empty() {
+    assert_eq!(parse("").unwrap(), Document::default());
+    assert_eq!(parse("\n\n# \n").unwrap(), Document::default());
+  }
+
+  #[test]
+  fn parse_broken() {
+    let text = "a = 1\nb = truex\n[c\nd = 99999999999999999999\ne = 2\na = 3\n";
+
+    // strict mode stops at the first invalid line
+    let diagnostics = parse(text).unwrap_err();
+    assert_eq!(diagnostics.diags().len(), 1);
+    assert_eq!(diagnostics.diags()[0].range, 6..15);
+
+    // lenient mode collects all
+    let (doc, diagnostics) = parse_lenient(text);
+    assert_eq!(doc.get("", "a"), Some(&Value::Int(1)));
+    assert_eq!(doc.get("", "e"), Some(&Value::Int(2)));
+    assert_eq!(doc.get("", "b"), None);
+    assert_eq!(
+      diagnostics.render(text),
+      "\
+error[E001]: invalid line
+ --> 2:1
+  |
+2 | b = truex
+  | ^^^^^^^^^
+error[E001]: invalid line
+ --> 3:1
+  |
+3 | [c
+  | ^^
+error[E001]: invalid line
+ --> 4:1
+  |
+4 | d = 99999999999999999999
+  | ^^^^^^^^^^^^^^^^^^^^^^^^
+warning[W001]: duplicated key `a` is ignored
+ --> 6:1
+  |
+6 | a = 3
+  | ^
+"
+    );
+  }
+}
@@ -0,0 +1,45 @@
+//! Parse a TOML-lite file and print the parsed structure or diagnostics.
+//!
+//! Usage: `cargo run --example ini -- [--lenient] [path]`.
+//! If the path is not provided, a built-in text will be used.
+
+mod ini;
+
+use std::{env, fs::read_to_string, process::exit};
+
+const TEXT: &str = r#"# built-in example
+name = "whitehole"
+
+[package]
+version = 8
+keywords = ["parser", "combinator"]
+invalid line
+"#;
+
+fn main() {
+  let mut lenient = false;
+  let mut path = None;
+  for arg in env::args().skip(1) {
+    if arg == "--lenient" {
+      lenient = true;
+    } else {
+      path = Some(arg);
+    }
+  }
+  let text = path.map_or_else(|| TEXT.to_string(), |p| read_to_string(p).unwrap());
+
+  if lenient {
+    let (doc, diagnostics) = ini::parse_lenient(&text);
+    println!("{:#?}", doc);
+    println!("package.version = {:?}", doc.get("package", "version"));
+    eprint!("{}", diagnostics.render(&text));
+  } else {
+    match ini::parse(&text) {
+      Ok(doc) => println!("{:#?}", doc),
+      Err(diagnostics) => {
+        eprint!("{}", diagnostics.render(&text));
+        exit(1);
+      }
+    }
+  }
+}
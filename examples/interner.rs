@@ -0,0 +1,113 @@
+//! This example demonstrates how to build an AST into an arena in the heap,
+//! with identifiers interned into symbols,
+//! so no [`String`] is allocated per identifier occurrence.
+
+use std::borrow::{Borrow, BorrowMut};
+use whitehole::{
+  action::Action,
+  contextual,
+  intern::{Interner, Symbol},
+  parser::Parser,
+};
+
+/// `target = value;`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assign {
+  pub target: Symbol,
+  pub value: Symbol,
+}
+
+/// The index of a node in [`MyHeap::nodes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Default)]
+pub struct MyHeap {
+  pub interner: Interner,
+  // the arena of AST nodes
+  pub nodes: Vec<Assign>,
+}
+
+// let `Combinator::interned` find the interner in the heap
+impl Borrow<Interner> for MyHeap {
+  fn borrow(&self) -> &Interner {
+    &self.interner
+  }
+}
+impl BorrowMut<Interner> for MyHeap {
+  fn borrow_mut(&mut self) -> &mut Interner {
+    &mut self.interner
+  }
+}
+
+// generate contextual combinators for the custom heap
+contextual!((), MyHeap);
+
+pub fn build_parser(
+  s: &str,
+) -> Parser<'_, impl Action<Text = str, State = (), Heap = MyHeap, Value = NodeId>> {
+  let ws = || next(|c| c.is_ascii_whitespace()) * (..);
+  // the digested text is interned, the value is a `Symbol`
+  let ident = || {
+    (next(|c| c.is_ascii_alphabetic()) * (1..))
+      .interned()
+      .tuple()
+  };
+
+  // allocate the node into the arena and yield its id,
+  // instead of moving the node through `Output::value`
+  let assign = (ws() + ident() + ws() + '=' + ws() + ident() + ws() + ';').select(|accepted| {
+    let (target, value) = accepted.output().value;
+    accepted.heap.nodes.push(Assign { target, value });
+    NodeId(accepted.heap.nodes.len() - 1)
+  });
+
+  Parser::builder()
+    .heap(MyHeap::default())
+    .entry(assign)
+    .build(s)
+}
+
+fn main() {
+  let mut parser = build_parser("a = b; c = a; a = c;");
+  let ids = parser.by_ref().collect::<Vec<_>>();
+  assert_eq!(ids.len(), 3);
+  assert!(parser.instant.rest().is_empty());
+
+  let heap = &parser.heap;
+  for node in &heap.nodes {
+    println!(
+      "{} = {}",
+      heap.interner.resolve(node.target).unwrap(),
+      heap.interner.resolve(node.value).unwrap()
+    );
+  }
+  // only 3 distinct identifiers are allocated
+  assert_eq!(heap.interner.len(), 3);
+}
+
+#[test]
+fn interned_symbols() {
+  let mut parser = build_parser("x = y;\ny = x;");
+  let ids = parser.by_ref().map(|o| o.value).collect::<Vec<_>>();
+  assert_eq!(ids, [NodeId(0), NodeId(1)]);
+
+  let heap = &parser.heap;
+  let (x, y) = (
+    heap.interner.get("x").unwrap(),
+    heap.interner.get("y").unwrap(),
+  );
+  assert_eq!(
+    heap.nodes,
+    [
+      Assign {
+        target: x,
+        value: y
+      },
+      Assign {
+        target: y,
+        value: x
+      }
+    ]
+  );
+}
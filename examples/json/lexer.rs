@@ -2,11 +2,15 @@ use crate::common::{number, string, whitespaces};
 use in_str::in_str;
 use whitehole::{
   action::Action,
+  assert_value_size,
   combinator::{next, Combinator},
 };
 
 pub fn lexer_entry() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>> {
   let boundary = next(in_str!("[]{}:,"));
 
-  whitespaces() | boundary | number() | string() | "true" | "false" | "null"
+  let entry = whitespaces() | boundary | number() | string() | "true" | "false" | "null";
+  // Guard the value size of the top-level rule against regressions.
+  assert_value_size!(entry: entry, 0);
+  entry
 }
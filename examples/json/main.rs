@@ -6,7 +6,13 @@ use crate::{
   lexer::lexer_entry,
   parser::{parser_entry_with_recur, parser_entry_with_static},
 };
-use whitehole::{action::Action, combinator::Combinator, parser::Parser};
+use whitehole::{
+  action::Action, assert_value_size, combinator::Combinator, parser::Parser, range::Ranged,
+};
+
+// All entries yield `()` (0 bytes), `.ranged()` wraps them into `Ranged<()>` (16 bytes).
+// There is nothing to shrink, but keep the budget explicit.
+assert_value_size!(Ranged<()>, 16);
 
 const TEXT: &str = r#"
 {
@@ -24,7 +30,7 @@ const TEXT: &str = r#"
 fn print_all_with_range(
   entry: Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>>,
 ) {
-  let mut parser = Parser::builder().entry(entry.range()).build(TEXT);
+  let mut parser = Parser::builder().entry(entry.ranged()).build(TEXT);
 
   for output in &mut parser {
     println!(
@@ -69,4 +75,20 @@ mod tests {
     print_all_with_range(parser_entry_with_recur());
     print_all_with_range(parser_entry_with_static());
   }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_json_lexer_serde() {
+    use whitehole::{action::Output, range::WithRange};
+
+    let outputs = Parser::builder()
+      .entry(lexer_entry().range())
+      .build(r#"{"a": [1, true]}"#)
+      .collect::<Vec<_>>();
+    let json = serde_json::to_string_pretty(&outputs).unwrap();
+    assert_eq!(json, include_str!("lexer.golden.json").trim_end());
+
+    let deserialized: Vec<Output<WithRange<()>>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, outputs);
+  }
 }
@@ -2,6 +2,7 @@ use crate::common::{number, string, whitespaces};
 use std::sync::LazyLock;
 use whitehole::{
   action::Action,
+  assert_value_size,
   combinator::{eat, recur, wrap, Combinator},
 };
 
@@ -28,7 +29,9 @@ pub fn parser_entry_with_recur(
   // Finally, define `value` with `array` and `object`.
   value_setter.boxed(array() | object() | number() | string() | "true" | "false" | "null");
 
-  whitespaces() | value()
+  let entry = whitespaces() | value();
+  assert_value_size!(entry: entry, 0);
+  entry
 }
 
 pub fn parser_entry_with_static(
@@ -54,5 +57,7 @@ pub fn parser_entry_with_static(
     wrap(|input| VALUE.exec(input))
   }
 
-  whitespaces() | value()
+  let entry = whitespaces() | value();
+  assert_value_size!(entry: entry, 0);
+  entry
 }
@@ -13,7 +13,7 @@ fn build_entry() -> impl Action<Text = str, State = (), Heap = ()> {
   let non_digit = || letter() | '-';
   let identifier_character = || digit() | non_digit();
   let identifier_characters = || identifier_character() * (1..);
-  let numeric_identifier = || eat('0') | positive_digit() + digits().optional();
+  let numeric_identifier = || eat('0') | (positive_digit() + digits().optional());
 
   let alphanumeric_identifier = || {
     (non_digit() + identifier_characters().optional())
@@ -11,7 +11,7 @@ pub struct MyState {
 // generate contextual combinators for the custom state
 contextual!(MyState, ());
 
-pub fn build_lexer(s: &str) -> Parser<impl Action<Text = str, State = MyState, Heap = ()>> {
+pub fn build_lexer(s: &str) -> Parser<'_, impl Action<Text = str, State = MyState, Heap = ()>> {
   let body_optional = || {
     let escape = {
       let simple = next(in_str!("0'\"\\nrvtbf\u{000a}\u{000d}\u{2028}\u{2029}"));
@@ -0,0 +1,176 @@
+//! This example demonstrates the classic two-stage pipeline:
+//! the lexer turns the text into tokens with their ranges,
+//! then the parser consumes the tokens (not the text) with the same combinator algebra.
+//!
+//! Implement [`Unit`] for the token type so `[Token]` is digest-able,
+//! and use [`token_by`] to match tokens.
+
+use whitehole::{
+  action::Action,
+  combinator::{eat_in_char, next, token_by, whitespaces, Combinator},
+  digest::Unit,
+  parser::Parser,
+  range::Range,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+  Num,
+  Op(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+  pub kind: Kind,
+  /// The byte range in the text.
+  pub range: Range,
+}
+
+// let `[Token]` be the `Text` of the parser
+impl Unit for Token {}
+
+/// Stage 1: lex the text into tokens, skipping whitespaces.
+/// Return the tokens and the undigested byte offset if the lexer can't continue.
+pub fn lex(text: &str) -> Result<Vec<Token>, usize> {
+  let entry = (whitespaces().bind(None)
+    | (next(|c| c.is_ascii_digit()) * (1..)).bind(Some(Kind::Num))
+    | eat_in_char("+-*/").map(|c| Some(Kind::Op(c))))
+  .range();
+
+  let mut parser = Parser::builder().entry(entry).build(text);
+  let tokens = parser
+    .by_ref()
+    .filter_map(|o| {
+      let range = o.value.range;
+      o.value.data.map(|kind| Token { kind, range })
+    })
+    .collect();
+  if parser.is_done() {
+    Ok(tokens)
+  } else {
+    Err(parser.instant.digested())
+  }
+}
+
+fn op(
+  ops: &'static str,
+) -> Combinator<impl Action<Text = [Token], State = (), Heap = (), Value = char>> {
+  token_by(move |t: &Token| matches!(t.kind, Kind::Op(c) if ops.contains(c))).select(|accepted| {
+    match accepted.content()[0].kind {
+      Kind::Op(c) => c,
+      Kind::Num => unreachable!(),
+    }
+  })
+}
+
+/// Stage 2: parse the tokens as an arithmetic expression with the usual precedence.
+/// The `text` is only used to read the value of number tokens.
+pub fn expr(
+  text: &str,
+) -> Combinator<impl Action<Text = [Token], State = (), Heap = (), Value = i64> + '_> {
+  let num = move || {
+    token_by(|t: &Token| t.kind == Kind::Num).select(move |accepted| {
+      text[accepted.content()[0].range.clone()]
+        .parse::<i64>()
+        .unwrap()
+    })
+  };
+  let apply = |acc: i64, value: i64, op: Option<char>| match op {
+    None => value,
+    Some('+') => acc + value,
+    Some('-') => acc - value,
+    Some('*') => acc * value,
+    Some('/') => acc / value,
+    Some(_) => unreachable!(),
+  };
+  let term = move || (num() * (1..)).sep_fold(op("*/"), || 0, apply);
+  (term() * (1..)).sep_fold(op("+-"), || 0, apply)
+}
+
+/// Map a range of tokens back to the byte range in the text.
+pub fn text_range(tokens: &[Token], range: Range) -> Range {
+  tokens[range.start].range.start..tokens[range.end - 1].range.end
+}
+
+fn main() {
+  let text = "1 + 2 * 3 - 8 / 4";
+  let tokens = lex(text).unwrap();
+  assert_eq!(tokens.len(), 9);
+
+  let mut parser = Parser::builder()
+    .entry(expr(text).range())
+    .build(&tokens[..]);
+  let output = parser.next().unwrap();
+  println!("{} = {}", text, output.value.data);
+  assert_eq!(output.value.data, 5);
+  assert_eq!(text_range(&tokens, output.value.range), 0..text.len());
+}
+
+#[test]
+fn two_stage() {
+  let text = "10 - 2 * 3";
+  let tokens = lex(text).unwrap();
+  assert_eq!(
+    tokens,
+    [
+      Token {
+        kind: Kind::Num,
+        range: 0..2
+      },
+      Token {
+        kind: Kind::Op('-'),
+        range: 3..4
+      },
+      Token {
+        kind: Kind::Num,
+        range: 5..6
+      },
+      Token {
+        kind: Kind::Op('*'),
+        range: 7..8
+      },
+      Token {
+        kind: Kind::Num,
+        range: 9..10
+      },
+    ]
+  );
+  let output = Parser::builder()
+    .entry(expr(text))
+    .build(&tokens[..])
+    .next()
+    .unwrap();
+  assert_eq!(output.value, 4);
+  assert_eq!(output.digested, 5);
+}
+
+#[test]
+fn errors() {
+  // stage 1 stops at the unknown char
+  assert_eq!(lex("1 + x"), Err(4));
+
+  // stage 2 rejects the wrong kind of token
+  let text = "+ 1";
+  let tokens = lex(text).unwrap();
+  assert!(Parser::builder()
+    .entry(expr(text))
+    .build(&tokens[..])
+    .next()
+    .is_none());
+
+  // the trailing operator is left undigested,
+  // and the token offset maps back to the text offset
+  let text = "1 * 2 +";
+  let tokens = lex(text).unwrap();
+  let mut parser = Parser::builder()
+    .entry(expr(text).range())
+    .build(&tokens[..]);
+  let output = parser.next().unwrap();
+  assert_eq!(output.value.data, 2);
+  assert_eq!(output.value.range, 0..3);
+  assert_eq!(text_range(&tokens, output.value.range), 0..5);
+  assert_eq!(
+    tokens[parser.instant.digested()].range.start,
+    text.len() - 1
+  );
+}
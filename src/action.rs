@@ -19,12 +19,16 @@
 
 mod input;
 mod output;
+mod rejection;
+mod size;
 
 use crate::instant::Instant;
 use std::rc::Rc;
 
 pub use input::*;
 pub use output::*;
+pub use rejection::*;
+pub use size::*;
 
 /// The basic building block of a parser.
 /// See the [module level documentation](crate::action) for more information.
@@ -48,6 +52,20 @@ pub unsafe trait Action {
     &self,
     input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
   ) -> Option<Output<Self::Value>>;
+
+  /// Same as [`Self::exec`], but tell whether the rejection happened after a
+  /// [`cut`](crate::combinator::Combinator::cut) point.
+  ///
+  /// This is used by `|` to stop trying other branches after a cut.
+  /// The default implementation treats all rejections as [`Rejection::Backtrack`],
+  /// only override this if the action may propagate a [`Rejection::Cut`] from its inner actions.
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.exec(input).ok_or(Rejection::Backtrack)
+  }
 }
 
 unsafe impl<T: Action + ?Sized> Action for &T {
@@ -63,6 +81,14 @@ unsafe impl<T: Action + ?Sized> Action for &T {
   ) -> Option<Output<Self::Value>> {
     (**self).exec(input)
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    (**self).exec_cut(input)
+  }
 }
 
 unsafe impl<T: Action + ?Sized> Action for Box<T> {
@@ -78,8 +104,21 @@ unsafe impl<T: Action + ?Sized> Action for Box<T> {
   ) -> Option<Output<Self::Value>> {
     self.as_ref().exec(input)
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.as_ref().exec_cut(input)
+  }
 }
 
+/// A type-erased [`Action`] in a [`Box`].
+/// See [`Combinator::boxed`](crate::combinator::Combinator::boxed).
+pub type BoxedAction<'a, Text, State, Heap, Value> =
+  Box<dyn Action<Text = Text, State = State, Heap = Heap, Value = Value> + 'a>;
+
 unsafe impl<T: Action + ?Sized> Action for Rc<T> {
   type Text = T::Text;
   type State = T::State;
@@ -93,13 +132,43 @@ unsafe impl<T: Action + ?Sized> Action for Rc<T> {
   ) -> Option<Output<Self::Value>> {
     self.as_ref().exec(input)
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.as_ref().exec_cut(input)
+  }
 }
 
+/// Implement [`Action::exec`] by [`Action::exec_cut`] in an `impl Action` block.
+///
+/// This is used by wrappers which don't change the rejection of their inner actions,
+/// so the logic is only written once in [`Action::exec_cut`]
+/// and a [`Rejection::Cut`] from the inner actions is propagated.
+macro_rules! exec_by_cut {
+  () => {
+    #[inline]
+    fn exec(
+      &self,
+      input: $crate::action::Input<
+        &$crate::instant::Instant<&Self::Text>,
+        &mut Self::State,
+        &mut Self::Heap,
+      >,
+    ) -> Option<$crate::action::Output<Self::Value>> {
+      self.exec_cut(input).ok()
+    }
+  };
+}
+pub(crate) use exec_by_cut;
+
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::{
-    combinator::{bytes, take},
+    combinator::{bytes, take, Combinator},
     instant::Instant,
   };
 
@@ -123,6 +192,7 @@ mod tests {
   }
 
   #[test]
+  #[allow(clippy::needless_borrows_for_generic_args)]
   fn action_ref() {
     assert_str_action(&take(1));
     assert_bytes_action(&bytes::take(1));
@@ -167,4 +237,54 @@ mod tests {
       Rc::new(bytes::take(1)) as Rc<dyn Action<Text = [u8], State = (), Heap = (), Value = ()>>
     );
   }
+
+  #[test]
+  fn boxed_combinator() {
+    use crate::{combinator::eat, parser::Parser};
+
+    // different types in the same vec
+    let entries: Vec<Combinator<BoxedAction<str, (), (), ()>>> =
+      vec![eat("a").boxed(), (eat("b") * 2).boxed()];
+    assert_eq!(
+      Parser::builder()
+        .entry(&entries[0])
+        .build("ab")
+        .next()
+        .map(|o| o.digested),
+      Some(1)
+    );
+    assert_eq!(
+      Parser::builder()
+        .entry(&entries[1])
+        .build("bbb")
+        .next()
+        .map(|o| o.digested),
+      Some(2)
+    );
+    assert!(Parser::builder()
+      .entry(&entries[1])
+      .build("ab")
+      .next()
+      .is_none());
+
+    // combine boxed combinators with operators, the value is preserved
+    let mut parser = Parser::builder()
+      .entry(
+        entries
+          .into_iter()
+          .reduce(|a, b| (a | b).boxed())
+          .unwrap()
+          .select(|a| a.digested()),
+      )
+      .build("abbab");
+    assert_eq!(
+      (&mut parser).map(|o| o.value).collect::<Vec<_>>(),
+      vec![1, 2, 1]
+    );
+    assert_eq!(parser.instant.rest(), "b");
+
+    // borrowed closures
+    let n = 1;
+    assert_str_action(crate::combinator::take(n).reject(|_| n == 0).boxed());
+  }
 }
@@ -45,6 +45,24 @@ impl<Instant, State, Heap> Input<&Instant, &mut State, &mut Heap> {
   }
 }
 
+impl<'text, Text: ?Sized, StateRef, HeapRef>
+  Input<&crate::instant::Instant<&'text Text>, StateRef, HeapRef>
+{
+  /// The rest of the text. Shortcut of `self.instant.rest()`.
+  /// See [`Instant::rest`](crate::instant::Instant::rest).
+  #[inline]
+  pub const fn rest(&self) -> &'text Text {
+    self.instant.rest()
+  }
+
+  /// The expected literal. Shortcut of `self.instant.expectation()`.
+  /// See [`Instant::expectation`](crate::instant::Instant::expectation).
+  #[inline]
+  pub const fn expectation(&self) -> Option<&'text Text> {
+    self.instant.expectation()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -60,4 +78,17 @@ mod tests {
     };
     let _ = format!("{:?}", input);
   }
+
+  #[test]
+  fn input_rest() {
+    let mut instant = Instant::new("123");
+    instant.digest(1).unwrap();
+    let input = Input {
+      instant: &instant,
+      state: &mut (),
+      heap: &mut (),
+    };
+    assert_eq!(input.rest(), "23");
+    assert_eq!(input.rest(), input.instant.rest());
+  }
 }
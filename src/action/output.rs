@@ -3,6 +3,7 @@ use crate::{digest::Digest, instant::Instant};
 /// The output of [`Action::exec`](crate::action::Action::exec).
 /// Usually built by [`Instant::accept`].
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Output<Value = ()> {
   /// The yielded value.
   pub value: Value,
@@ -35,6 +36,29 @@ impl<Text: ?Sized + Digest> Instant<&Text> {
       .validate(n)
       .then(|| unsafe { self.accept_unchecked(n) })
   }
+
+  /// Try to build an [`Output`] with the `value` by digesting `n` bytes.
+  /// Return [`Some`] if `n` is valid according to [`Digest::validate`].
+  ///
+  /// This is a shortcut of `self.accept(n).map(|output| output.map(|_| value))`.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::wrap, parser::Parser};
+  /// // eat a decimal digit and yield its value
+  /// let digit = wrap(|input| {
+  ///   let c = input.rest().chars().next()?;
+  ///   input.instant.accept_value(1, c.to_digit(10)?)
+  /// });
+  /// let mut parser = Parser::builder().entry(digit).build("7");
+  /// assert_eq!(parser.next().unwrap().value, 7);
+  /// ```
+  #[inline]
+  pub fn accept_value<Value>(&self, n: usize, value: Value) -> Option<Output<Value>> {
+    self
+      .rest()
+      .validate(n)
+      .then(|| Output { value, digested: n })
+  }
 }
 
 impl<Value> Output<Value> {
@@ -47,6 +71,24 @@ impl<Value> Output<Value> {
     }
   }
 
+  /// Replace [`Self::digested`] with `n`, keeping [`Self::value`].
+  ///
+  /// This doesn't validate `n`, make sure it satisfies the requirement of [`Self::digested`]
+  /// before returning the output from [`Action::exec`](crate::action::Action::exec).
+  /// # Examples
+  /// ```
+  /// # use whitehole::action::Output;
+  /// let output = Output { value: 'a', digested: 1 };
+  /// assert_eq!(output.with_digested(3), Output { value: 'a', digested: 3 });
+  /// ```
+  #[inline]
+  pub fn with_digested(self, n: usize) -> Self {
+    Output {
+      value: self.value,
+      digested: n,
+    }
+  }
+
   /// Converts from `&Output<T>` to `Output<&T>`.
   #[inline]
   pub const fn as_ref(&self) -> Output<&Value> {
@@ -122,6 +164,99 @@ mod tests {
     assert!(instant.accept(4).is_none());
   }
 
+  #[test]
+  fn instant_accept_value() {
+    let instant = Instant::new("好");
+    assert_eq!(
+      instant.accept_value(3, 'a'),
+      Some(Output {
+        value: 'a',
+        digested: 3
+      })
+    );
+    assert_eq!(instant.accept_value(0, ()).map(|o| o.digested), Some(0));
+    assert!(instant.accept_value(1, 'a').is_none());
+    assert!(instant.accept_value(4, 'a').is_none());
+
+    let instant = Instant::new(b"123" as &[u8]);
+    assert_eq!(instant.accept_value(3, 1).map(|o| o.value), Some(1));
+    assert!(instant.accept_value(4, 1).is_none());
+  }
+
+  #[test]
+  fn output_with_digested() {
+    let o = Output {
+      value: "a",
+      digested: 1,
+    };
+    assert_eq!(
+      o.with_digested(2),
+      Output {
+        value: "a",
+        digested: 2
+      }
+    );
+  }
+
+  #[test]
+  fn output_helpers_hand_written_action() {
+    use crate::{
+      action::{Action, Input},
+      combinator::wrap,
+    };
+
+    // eat an optionally signed integer, yield its value
+    let verbose = wrap(|input| {
+      let rest = input.instant.rest();
+      let sign = rest.starts_with('-') as usize;
+      let len = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+      if len == 0 {
+        return None;
+      }
+      let value = rest[..sign + len].parse::<i32>().ok()?;
+      let output = input.instant.accept(sign + len)?;
+      Some(Output {
+        value,
+        digested: output.digested,
+      })
+    });
+    let concise = wrap(|input| {
+      let sign = input.rest().starts_with('-') as usize;
+      let len = sign
+        + input.rest()[sign..]
+          .bytes()
+          .take_while(u8::is_ascii_digit)
+          .count();
+      input
+        .instant
+        .accept_value(len, input.rest()[..len].parse::<i32>().ok()?)
+    });
+
+    for text in ["123", "-42abc", "-", "abc", ""] {
+      let instant = Instant::new(text);
+      let exec = |action: &dyn Action<Text = str, State = (), Heap = (), Value = i32>| {
+        action.exec(Input {
+          instant: &instant,
+          state: &mut (),
+          heap: &mut (),
+        })
+      };
+      assert_eq!(exec(&verbose), exec(&concise));
+    }
+    let instant = Instant::new("-42abc");
+    assert_eq!(
+      concise.exec(Input {
+        instant: &instant,
+        state: &mut (),
+        heap: &mut ()
+      }),
+      Some(Output {
+        value: -42,
+        digested: 3
+      })
+    );
+  }
+
   #[test]
   fn output_map() {
     assert_eq!(
@@ -0,0 +1,66 @@
+/// The reason why an action rejected.
+/// See [`Action::exec_cut`](crate::action::Action::exec_cut).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rejection {
+  /// A normal rejection, the surrounding `|` will try other branches.
+  Backtrack,
+  /// The action rejected after a [`cut`](crate::combinator::Combinator::cut) point,
+  /// the surrounding `|` won't try other branches.
+  Cut,
+  /// The action rejected because the input ended too early,
+  /// it may accept with more input (e.g. [`eat("true")`](crate::combinator::eat) with `"tr"`).
+  ///
+  /// This is only distinguished by [`StreamingParser`](crate::parser::StreamingParser),
+  /// otherwise it is treated as [`Self::Backtrack`].
+  Incomplete,
+}
+
+impl Rejection {
+  /// Merge the rejections of two branches of `|`, if the first branch can be backtracked.
+  /// [`Self::Cut`] takes precedence over [`Self::Incomplete`],
+  /// which takes precedence over [`Self::Backtrack`].
+  #[inline]
+  pub(crate) const fn merge(self, other: Self) -> Self {
+    match (self, other) {
+      (Self::Cut, _) | (_, Self::Cut) => Self::Cut,
+      (Self::Incomplete, _) | (_, Self::Incomplete) => Self::Incomplete,
+      _ => Self::Backtrack,
+    }
+  }
+
+  /// Return [`Self::Incomplete`] if `rest` is a proper prefix of the `pattern`,
+  /// otherwise [`Self::Backtrack`].
+  /// This is used by literal matchers which rejected.
+  #[inline]
+  pub(crate) fn by_prefix(rest: &[u8], pattern: &[u8]) -> Self {
+    if rest.len() < pattern.len() && pattern.starts_with(rest) {
+      Self::Incomplete
+    } else {
+      Self::Backtrack
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejection_merge() {
+    use Rejection::*;
+    assert_eq!(Backtrack.merge(Backtrack), Backtrack);
+    assert_eq!(Backtrack.merge(Incomplete), Incomplete);
+    assert_eq!(Incomplete.merge(Backtrack), Incomplete);
+    assert_eq!(Incomplete.merge(Cut), Cut);
+    assert_eq!(Backtrack.merge(Cut), Cut);
+  }
+
+  #[test]
+  fn rejection_by_prefix() {
+    assert_eq!(Rejection::by_prefix(b"tr", b"true"), Rejection::Incomplete);
+    assert_eq!(Rejection::by_prefix(b"", b"true"), Rejection::Incomplete);
+    assert_eq!(Rejection::by_prefix(b"fa", b"true"), Rejection::Backtrack);
+    assert_eq!(Rejection::by_prefix(b"tru!", b"true"), Rejection::Backtrack);
+    assert_eq!(Rejection::by_prefix(b"", b""), Rejection::Backtrack);
+  }
+}
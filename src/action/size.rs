@@ -0,0 +1,69 @@
+use super::Action;
+
+/// Used by [`assert_value_size`](crate::assert_value_size).
+#[doc(hidden)]
+#[inline(always)]
+pub const fn __assert_value_size<T: Action + ?Sized, const MAX: usize>(_: &T) {
+  const {
+    assert!(
+      std::mem::size_of::<T::Value>() <= MAX,
+      "the size of `Action::Value` exceeds the budget"
+    )
+  }
+}
+
+/// Assert the size of an [`Action::Value`] is not greater than a budget (in bytes),
+/// at compile time.
+///
+/// Values are moved in every [`Output`](crate::action::Output),
+/// deeply composed values (e.g. long `+` chains with [`Combinator::range`](crate::combinator::Combinator::range))
+/// may grow silently and slow down the parsing.
+/// Put this next to each top-level rule to guard the size against regressions.
+///
+/// - `assert_value_size!(Type, max)` checks a type, this can be used at the item level.
+/// - `assert_value_size!(entry: action, max)` checks the value type of an [`Action`],
+///   this should be used inside a function.
+///   The check happens when the function is monomorphized,
+///   so it is reported by `cargo build` but may not be reported by `cargo check`.
+/// # Examples
+/// ```
+/// use whitehole::{
+///   action::Action,
+///   assert_value_size,
+///   combinator::{eat, Combinator},
+///   range::WithRange,
+/// };
+///
+/// assert_value_size!(WithRange<()>, 16);
+///
+/// fn rule() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = (u8, u8)>> {
+///   let rule = eat('a').bind(1u8).tuple() + eat('b').bind(2u8).tuple();
+///   assert_value_size!(entry: rule, 2);
+///   rule
+/// }
+/// # rule();
+/// ```
+/// Exceeding the budget is a compile error.
+/// ```compile_fail
+/// whitehole::assert_value_size!(u64, 4);
+/// ```
+/// ```compile_fail
+/// # use whitehole::{assert_value_size, combinator::eat};
+/// fn rule() {
+///   let rule = eat('a').bind(0u64);
+///   assert_value_size!(entry: rule, 4);
+/// }
+/// # rule();
+/// ```
+#[macro_export]
+macro_rules! assert_value_size {
+  (entry: $action:expr, $max:expr) => {
+    $crate::action::__assert_value_size::<_, { $max }>(&$action)
+  };
+  ($ty:ty, $max:expr) => {
+    const _: () = assert!(
+      ::std::mem::size_of::<$ty>() <= $max,
+      concat!("the size of `", stringify!($ty), "` exceeds the budget")
+    );
+  };
+}
@@ -11,17 +11,32 @@
 //! ```
 //! To save the memory of your brain, we have very limited number of provided combinators.
 //! Here are them all:
-//! - [`eat`]: eat a pattern.
-//! - [`till`]: eat until a pattern, inclusive.
+//! - [`eat`]: eat a pattern. See also [`eat_ci`] to ignore ASCII case.
+//! - [`till`]: eat until a pattern, inclusive. See also [`till_at_most`], [`till_exclusive`], [`till_inclusive`] and [`till_eof`].
+//! - [`eat_until`]: eat until any of the given chars or bytes, exclusive, with a fast scan.
+//! - [`dispatch`]: dispatch by the first char or byte of the rest to registered combinators.
 //! - [`next`]: eat the next char or byte by a predicate.
+//!   See also [`next_n`] to match exactly `n` of them, [`one_of`] and [`none_of`].
+//! - [`take_while`]: take chars or bytes by a predicate in one scan. See also [`take_while_min_max`] and [`take_till`].
+//! - [`identifier`]: eat an identifier, optionally excluding keywords.
+//! - [`line_end`]: match a line ending or the end of input. See also [`till_line_end`].
+//! - [`whitespaces`]: eat one or more whitespaces. See also [`line_comment`] and [`block_comment`].
+//! - [`json_string`] and [`json_number`]: match JSON string and number literals.
 //! - [`take`]: take the next `n` chars or bytes.
+//!   See also [`take_at_most`], [`skip_at_most`] and [`take_bytes`].
+//! - [`position`]: yield the current position without digesting.
+//! - [`token_by`]: match the next element of a `[T]` (e.g. a token from a lexer) by a predicate.
 //! - [`wrap`]: wrap a closure as a combinator.
+//! - [`from_fn`]: wrap a closure as a combinator with any `Text`, `State` and `Heap`.
 //! - [`recur`]: create a recursive combinator.
 //!
 //! Tips: Some of the provided combinators may have faster `unsafe` variants
 //! named with suffix `_unchecked`.
 //!
-//! To parse bytes, see the [`bytes`] module for the provided combinators with the same name.
+//! To parse bytes, see the [`bytes`] module for the provided combinators with the same name,
+//! and [`bytes::tlv`] for binary tag-length-value formats.
+//! To parse `[u16]` (e.g. UTF-16 text), see the [`u16s`] module.
+//! To parse dates and times, see the [`datetime`] module.
 //! # Composition
 //! Use `+` and `|` to compose multiple combinators
 //! for more complex tasks:
@@ -38,6 +53,11 @@
 //! # );
 //! ```
 //! See [`ops::add`] and [`ops::bitor`] for more information.
+//!
+//! For long sequences, [`seq`] is a flattened alternative to chaining `+`.
+//! For long alternations, [`choice!`](crate::choice) is a flattened alternative to chaining `|`.
+//! To keep only some of the values, use [`preceded`], [`terminated`], [`delimited`] and [`separated_pair`].
+//! To combine non-tuple values, use [`Combinator::pair`].
 //! # Repetition
 //! Use `*` to repeat a combinator:
 //! ```
@@ -61,6 +81,7 @@
 //! (eat("true") + "true" + "true") | (eat("true") + "true") | eat("true")
 //! # );
 //! ```
+//! To repeat with a count parsed from the input, see [`Combinator::then_repeat`].
 //! See [`ops::mul`] for more information.
 //! # Lookahead
 //! Use `!` for negative lookahead:
@@ -78,17 +99,34 @@
 //! # );
 //! ```
 //! See [`ops::not`] for more information.
+//! Use [`not`] if the state modified by the probed combinator shouldn't leak,
+//! and [`peek`] for positive lookahead which keeps the value.
+//! Use [`Combinator::and_also`] to require the input to match two combinators at the same time.
 //! # Decorator
 //! [`Combinator`] provides a set of methods as decorators
 //! to modify the behavior of the combinator.
 //! ## Debug
 //! - [`Combinator::log`] to print debug information.
+//! - [`Combinator::inspect_value`] to observe the value after being accepted.
+//! - [`Combinator::inspect_err`] to observe the rejection.
+//!
+//! Use [`Combinator::describe`] to print the structure of a composed combinator
+//! as a concise s-expression, see [`Describe`].
+//! Use [`Combinator::first_set`] to compute which chars a combinator may start with,
+//! see [`FirstSet`].
 //! ## Flow Control
 //! - [`Combinator::optional`] to make a combinator optional.
+//! - [`Combinator::opt`] to make a combinator optional and wrap the value in [`Option`].
+//! - [`Combinator::cut`] to prevent `|` from trying other branches when rejected.
+//! - [`Combinator::or_else`] to accept with a value computed from the input when rejected.
+//! - [`Combinator::recover_until`] to skip to a sync point when rejected (panic-mode recovery).
 //! - [`Combinator::when`] to conditionally execute the combinator.
 //! - [`Combinator::prevent`] to conditionally reject the combinator before it is executed.
+//! - [`Combinator::when_head`], [`Combinator::when_head_byte`] and [`Combinator::when_literal`] to check the head of the rest before the combinator is executed.
+//! - [`Combinator::when_expected`] to check the expectation of [`Parser::next_expecting`](crate::parser::Parser::next_expecting) before the combinator is executed.
 //! - [`Combinator::reject`] to conditionally reject the combinator after it is executed.
 //! - [`Combinator::boundary`] to require a word boundary after the action is accepted.
+//! - [`Combinator::max_len`] to limit the digested bytes of a combinator.
 //! ## Value Transformation
 //! You can set [`Output::value`] to distinguish different output types
 //! or carrying additional data.
@@ -97,10 +135,13 @@
 //! - [`Combinator::map`] to convert the value to a new value.
 //! - [`Combinator::bind`] to set the value to a provided clone-able value.
 //! - [`Combinator::bind_with`] to set the value with a provided factory.
+//! - [`Combinator::bind_default`] to set the value to [`Default::default`].
 //! - [`Combinator::select`] to calculate the value with a closure.
+//! - [`Combinator::try_map`] and [`Combinator::try_select`] to convert the value or reject.
 //! - [`Combinator::tuple`] to wrap the value in an one-element tuple.
 //! - [`Combinator::pop`] to unwrap the value from the one-element tuple.
 //! - [`Combinator::range`] to wrap the value in a [`WithRange`](crate::range::WithRange) struct.
+//! - [`Combinator::ranged`] to wrap the value in a [`Ranged`](crate::range::Ranged) struct which derefs to the value.
 //! ## State Manipulation
 //! [`Combinator`]s are stateless, but you can access external states
 //! via [`Input::state`] to realize stateful parsing.
@@ -109,7 +150,15 @@
 //! - [`Combinator::prepare`] to modify states before being executed.
 //! - [`Combinator::then`] to modify states after being accepted.
 //! - [`Combinator::catch`] to modify states after being rejected.
+//! - [`Combinator::atomic`] to restore states after being rejected.
 //! - [`Combinator::finally`] to modify states after being executed.
+//! - [`Combinator::emit`] to push a diagnostic into the heap after being accepted.
+//! - [`Combinator::counted`] to count how many times a branch is accepted into the heap.
+//! - [`Combinator::expect`] to record what was expected into the heap when rejected.
+//! - [`Combinator::track`] to record the furthest offset where it rejected into the heap, without a label.
+//! - [`Combinator::interned`] to intern the digested text into the heap and use the symbol as the value.
+//! - [`Combinator::memo`] to memoize the results by offset into the heap (packrat parsing).
+//! - [`Combinator::map_state`] and [`Combinator::map_heap`] to embed a sub-grammar with a different state or heap type.
 //! # Contextual
 //! By default and for simplicity, all combinators are non-contextual,
 //! which means the `State` and `Heap` types are `()`.
@@ -133,16 +182,24 @@
 //! See [`contextual`] for more information.
 
 mod decorator;
+mod describe;
+mod first_set;
+mod from_combinator;
 mod provided;
 
 pub mod ops;
 
 pub use crate::contextual;
 pub use decorator::*;
+pub use describe::*;
+pub use first_set::*;
+pub use from_combinator::*;
 pub use provided::*;
+#[cfg(feature = "derive")]
+pub use whitehole_macros::FromCombinator;
 
 use crate::{
-  action::{Action, Input, Output},
+  action::{Action, BoxedAction, Input, Output, Rejection},
   instant::Instant,
 };
 
@@ -163,6 +220,26 @@ impl<T> Combinator<T> {
   pub const fn new(action: T) -> Self {
     Self { action }
   }
+
+  /// Erase the type of the action by boxing it,
+  /// so combinators built by different decorators can be stored in one collection
+  /// or returned from different branches of a function.
+  ///
+  /// This costs a heap allocation when boxing and a virtual call for each execution.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::BoxedAction, combinator::{eat, Combinator}};
+  /// let entries: Vec<Combinator<BoxedAction<str, (), (), ()>>> =
+  ///   vec![eat("a").boxed(), (eat("b") * 2).boxed()];
+  /// ```
+  #[allow(clippy::type_complexity)]
+  #[inline]
+  pub fn boxed<'a>(self) -> Combinator<BoxedAction<'a, T::Text, T::State, T::Heap, T::Value>>
+  where
+    T: Action + 'a,
+  {
+    Combinator::new(Box::new(self.action))
+  }
 }
 
 unsafe impl<T: Action> Action for Combinator<T> {
@@ -178,4 +255,12 @@ unsafe impl<T: Action> Action for Combinator<T> {
   ) -> Option<Output<Self::Value>> {
     self.action.exec(input)
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(input)
+  }
 }
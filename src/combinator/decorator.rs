@@ -24,6 +24,15 @@ macro_rules! create_simple_decorator {
         Self { action }
       }
     }
+
+    impl<T: $crate::combinator::Describe> $crate::combinator::Describe for $name<T> {
+      fn describe(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        f.write_str(" ")?;
+        self.action.describe(f)?;
+        f.write_str(")")
+      }
+    }
   };
 }
 // https://github.com/rust-lang/rust-clippy/issues/12808
@@ -31,6 +40,35 @@ macro_rules! create_simple_decorator {
 #[allow(clippy::needless_pub_self)]
 pub(self) use create_simple_decorator;
 
+/// Implement [`FirstSet`](crate::combinator::FirstSet) for decorators
+/// which don't change where the action starts and what it digests first,
+/// by delegating to the inner action.
+/// Decorators with one type parameter are listed before `;`, others after `;`.
+macro_rules! delegate_first_set {
+  ($($simple:ident),* ; $($generic:ident),*) => {
+    $(
+      impl<T: $crate::combinator::FirstSet> $crate::combinator::FirstSet for $simple<T> {
+        #[inline]
+        fn first_set(&self) -> Option<$crate::combinator::LookaheadSet> {
+          self.action.first_set()
+        }
+      }
+    )*
+    $(
+      impl<T: $crate::combinator::FirstSet, D> $crate::combinator::FirstSet for $generic<T, D> {
+        #[inline]
+        fn first_set(&self) -> Option<$crate::combinator::LookaheadSet> {
+          self.action.first_set()
+        }
+      }
+    )*
+  };
+}
+// https://github.com/rust-lang/rust-clippy/issues/12808
+#[allow(clippy::useless_attribute)]
+#[allow(clippy::needless_pub_self)]
+pub(self) use delegate_first_set;
+
 macro_rules! create_generic_decorator {
   ($name:ident, $usage:literal, ($($derives:ident),*)) => {
     #[doc = $usage]
@@ -59,6 +97,17 @@ pub(self) use create_generic_decorator;
 macro_rules! create_generic_value_decorator {
   ($name:ident, $usage:literal) => {
     $crate::combinator::decorator::create_generic_decorator!($name, $usage, (Debug));
+
+    impl<T: $crate::combinator::Describe, D: core::fmt::Debug> $crate::combinator::Describe
+      for $name<T, D>
+    {
+      fn describe(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        f.write_str(" ")?;
+        self.action.describe(f)?;
+        write!(f, " {:?})", self.inner)
+      }
+    }
   };
 }
 // https://github.com/rust-lang/rust-clippy/issues/12808
@@ -79,6 +128,15 @@ macro_rules! create_closure_decorator {
           .finish()
       }
     }
+
+    impl<T: $crate::combinator::Describe, D> $crate::combinator::Describe for $name<T, D> {
+      fn describe(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        f.write_str(" ")?;
+        self.action.describe(f)?;
+        f.write_str(" <fn>)")
+      }
+    }
   };
 }
 // https://github.com/rust-lang/rust-clippy/issues/12808
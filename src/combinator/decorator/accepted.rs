@@ -1,3 +1,4 @@
+use crate::idx;
 use crate::{action::Output, digest::Digest, instant::Instant};
 use std::{
   ops::{Range, RangeFrom, RangeTo},
@@ -70,12 +71,16 @@ impl<'instant, TextRef, StateRef, HeapRef, Value>
   #[inline]
   pub const fn end(&self) -> usize {
     debug_assert!(usize::MAX - self.start() >= self.digested());
-    unsafe { self.start().unchecked_add(self.digested()) }
+    // SAFETY: the accepted content is inside the whole text,
+    // whose length is a valid usize
+    idx::unchecked!(idx::add(self.start(), self.digested()))
   }
 
   /// The byte range of the digested content in the whole input text.
   ///
   /// Shortcut for `self.start()..self.end()`.
+  /// This is the same range as the one wrapped by
+  /// [`Combinator::range`](crate::combinator::Combinator::range).
   #[inline]
   pub const fn range(&self) -> Range<usize> {
     self.start()..self.end()
@@ -118,7 +123,9 @@ impl<'text, Text: ?Sized + Digest, StateRef, HeapRef, Value>
     RangeTo<usize>: SliceIndex<Text, Output = Text>,
   {
     debug_assert!(self.instant.rest().validate(self.output.digested));
-    unsafe { self.instant.rest().get_unchecked(..self.digested()) }
+    // SAFETY: the digested length is valid against the rest,
+    // guaranteed by the caller of `Accepted::new_unchecked`
+    idx::unchecked!(idx::slice_to(self.instant.rest(), self.digested()))
   }
 
   /// Get the rest of the input text after accepting this combinator.
@@ -128,7 +135,9 @@ impl<'text, Text: ?Sized + Digest, StateRef, HeapRef, Value>
     RangeFrom<usize>: SliceIndex<Text, Output = Text>,
   {
     debug_assert!(self.instant.rest().validate(self.output.digested));
-    unsafe { self.instant.rest().get_unchecked(self.digested()..) }
+    // SAFETY: the digested length is valid against the rest,
+    // guaranteed by the caller of `Accepted::new_unchecked`
+    idx::unchecked!(idx::slice_from(self.instant.rest(), self.digested()))
   }
 }
 
@@ -1,10 +1,17 @@
+use super::{create_closure_decorator, delegate_first_set};
 use crate::{
-  action::{Action, Input, Output},
+  action::{exec_by_cut, Action, Input, Output, Rejection},
   combinator::Combinator,
   digest::Digest,
+  idx,
   instant::Instant,
 };
-use std::{cell::Cell, fmt::Debug, ops::RangeTo, slice::SliceIndex};
+use std::{
+  cell::{Cell, RefCell},
+  fmt::Debug,
+  ops::RangeTo,
+  slice::SliceIndex,
+};
 
 /// See [`Combinator::log`].
 #[derive(Copy, Clone, Debug)]
@@ -20,6 +27,14 @@ impl<'a, T> Log<'a, T> {
   }
 }
 
+create_closure_decorator!(InspectValue, "See [`Combinator::inspect_value`].");
+create_closure_decorator!(InspectErr, "See [`Combinator::inspect_err`].");
+
+delegate_first_set!(; InspectValue, InspectErr);
+
+/// See [`LOG_SINK`].
+pub type LogSink = Box<dyn FnMut(&str)>;
+
 thread_local! {
   /// The indentation used in [`Combinator::log`].
   pub static LOG_INDENTATION: Cell<&str> = const { Cell::new("| ") };
@@ -28,6 +43,18 @@ thread_local! {
   /// If the actual length is greater than this, it will be truncated.
   pub static LOG_UNDIGESTED_MAX_LEN: Cell<usize> = const { Cell::new(100) };
 
+  /// The sink of the lines printed by [`Combinator::log`].
+  /// If this is [`None`], lines will be printed to stdout.
+  /// # Examples
+  /// ```
+  /// # use whitehole::combinator::LOG_SINK;
+  /// # use std::{cell::RefCell, rc::Rc};
+  /// let lines = Rc::new(RefCell::new(Vec::new()));
+  /// let sink = lines.clone();
+  /// LOG_SINK.set(Some(Box::new(move |line: &str| sink.borrow_mut().push(line.to_string()))));
+  /// ```
+  pub static LOG_SINK: RefCell<Option<LogSink>> = const { RefCell::new(None) };
+
   static INDENT_LEVEL: Cell<usize> = const { Cell::new(0) };
 }
 
@@ -36,6 +63,13 @@ fn indentation() -> String {
   LOG_INDENTATION.get().repeat(INDENT_LEVEL.get())
 }
 
+fn write_line(line: &str) {
+  LOG_SINK.with_borrow_mut(|sink| match sink {
+    Some(sink) => sink(line),
+    None => println!("{}", line),
+  })
+}
+
 /// A trait to format the undigested text.
 /// # Safety
 /// The implementor must ensure the return value is valid according to [`Digest::validate`].
@@ -76,37 +110,66 @@ unsafe impl FormatUndigested for str {
   }
 }
 
-#[inline]
-fn format_input<Text: FormatUndigested + Digest + Debug + ?Sized>(name: &str, rest: &Text) -> String
+/// Format the undigested text by [`Debug`],
+/// truncated by [`LOG_UNDIGESTED_MAX_LEN`].
+pub(crate) fn preview<Text: FormatUndigested + Digest + Debug + ?Sized>(rest: &Text) -> String
 where
   RangeTo<usize>: SliceIndex<Text, Output = Text>,
 {
-  let truncated = if let Some(len) = rest.truncated_len() {
-    format!("{:?} (truncated)", unsafe { rest.get_unchecked(..len) })
+  if let Some(len) = rest.truncated_len() {
+    // SAFETY: `len` is valid, guaranteed by the implementor of `FormatUndigested`
+    format!(
+      "{:?} (truncated)",
+      idx::unchecked!(idx::slice_to(rest, len))
+    )
   } else {
     format!("{:?}", rest)
-  };
-
-  format!("{}({}) input: {}", &indentation(), name, truncated)
+  }
 }
 
 #[inline]
-fn format_output<Text: ?Sized + Digest + Debug, Value>(
+fn format_input<Text: FormatUndigested + Digest + Debug + ?Sized>(
   name: &str,
+  digested: usize,
   rest: &Text,
-  output: &Option<Output<Value>>,
 ) -> String
 where
   RangeTo<usize>: SliceIndex<Text, Output = Text>,
 {
   format!(
-    "{}({}) output: {:?}",
+    "{}({}) input at {}: {}",
     &indentation(),
     name,
-    output.as_ref().and_then(|o| { rest.get(..o.digested) }),
+    digested,
+    preview(rest)
   )
 }
 
+#[inline]
+fn format_output<Text: ?Sized + Digest + Debug, Value>(
+  name: &str,
+  rest: &Text,
+  output: &Result<Output<Value>, Rejection>,
+) -> String
+where
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  match output {
+    Ok(output) => format!(
+      "{}({}) accepted {} bytes: {:?}",
+      &indentation(),
+      name,
+      output.digested,
+      // SAFETY: `rest` is the rest of the input of the action,
+      // so `output.digested` is valid according to the safety requirement of `Action`
+      idx::unchecked!(idx::slice_to(rest, output.digested)),
+    ),
+    Err(Rejection::Backtrack) => format!("{}({}) rejected", &indentation(), name),
+    Err(Rejection::Cut) => format!("{}({}) rejected after a cut", &indentation(), name),
+    Err(Rejection::Incomplete) => format!("{}({}) rejected, need more input", &indentation(), name),
+  }
+}
+
 unsafe impl<T: Action<Text: FormatUndigested + Digest + Debug>> Action for Log<'_, T>
 where
   RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
@@ -116,26 +179,114 @@ where
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
+  ) -> Result<Output<Self::Value>, Rejection> {
     let rest = input.instant.rest();
-    println!("{}", &format_input(self.name, rest));
+    write_line(&format_input(self.name, input.instant.digested(), rest));
     INDENT_LEVEL.set(INDENT_LEVEL.get() + 1);
-    let output = self.action.exec(input);
+    let output = self.action.exec_cut(input);
     INDENT_LEVEL.set(INDENT_LEVEL.get() - 1);
-    println!("{}", &format_output(self.name, rest, &output));
+    write_line(&format_output(self.name, rest, &output));
+    output
+  }
+}
+
+unsafe impl<T: Action, D: Fn(&T::Value)> Action for InspectValue<T, D> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .inspect(|output| (self.inner)(&output.value))
+  }
+}
+
+unsafe impl<T: Action, D: Fn()> Action for InspectErr<T, D> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input);
+    if output.is_err() {
+      (self.inner)();
+    }
     output
   }
 }
 
 impl<T> Combinator<T> {
-  /// Create a new combinator to print the input text
-  /// and the digested text by the action.
+  /// Create a new combinator to observe the value after being accepted.
   ///
-  /// For customization, see [`LOG_INDENTATION`] and [`LOG_UNDIGESTED_MAX_LEN`].
+  /// The observer only gets a shared reference of the value,
+  /// so it can't affect the parsing.
+  /// This is useful for `dbg!`-style inspection in the middle of a chain,
+  /// and can be removed without changing the behavior.
+  /// See also [`Combinator::inspect_err`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Value = i32>>) {
+  /// combinator
+  ///   .inspect_value(|v| println!("before map: {}", v))
+  ///   .map(|v| v * 2)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn inspect_value<F: Fn(&T::Value)>(self, observer: F) -> Combinator<InspectValue<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(InspectValue::new(self.action, observer))
+  }
+
+  /// Create a new combinator to observe the rejection.
+  ///
+  /// The observer can't affect the parsing,
+  /// and can be removed without changing the behavior.
+  /// See also [`Combinator::inspect_value`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action>) {
+  /// combinator.inspect_err(|| println!("rejected"))
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn inspect_err<F: Fn()>(self, observer: F) -> Combinator<InspectErr<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(InspectErr::new(self.action, observer))
+  }
+
+  /// Create a new combinator to print the input text with the current [`Instant::digested`],
+  /// and whether the action is accepted (with the digested text) or rejected.
+  /// Nested logs are indented so the attempt tree can be read from the output.
+  ///
+  /// For customization, see [`LOG_INDENTATION`], [`LOG_UNDIGESTED_MAX_LEN`] and [`LOG_SINK`].
   /// # Caveats
   /// Be careful in multi-threaded environments since this uses thread-local variables.
   /// # Examples
@@ -146,7 +297,7 @@ impl<T> Combinator<T> {
   /// # ;}
   /// ```
   #[inline]
-  pub fn log(self, name: &str) -> Combinator<Log<T>> {
+  pub fn log(self, name: &str) -> Combinator<Log<'_, T>> {
     Combinator::new(Log::new(self.action, name))
   }
 }
@@ -155,7 +306,7 @@ impl<T> Combinator<T> {
 mod tests {
   use super::*;
   use crate::{
-    combinator::{bytes, take},
+    combinator::{bytes, eat, take},
     instant::Instant,
   };
 
@@ -187,34 +338,139 @@ mod tests {
     assert_eq!(output.value, 2);
   }
 
+  #[test]
+  fn combinator_inspect_value() {
+    let observed = std::cell::RefCell::new(Vec::new());
+    let c = (take(1).select(|a| a.content().to_string()) * (1..))
+      .fold(String::new, |acc, v: String| acc + &v)
+      .inspect_value(|v| observed.borrow_mut().push(v.clone()));
+    let output = c
+      .exec(Input {
+        instant: &Instant::new("123"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    // the observed value matches the yielded value
+    assert_eq!(*observed.borrow(), vec![output.value.clone()]);
+    assert_eq!(output.value, "123");
+    assert_eq!(output.digested, 3);
+
+    // not called when rejected
+    observed.borrow_mut().clear();
+    assert!(c
+      .exec(Input {
+        instant: &Instant::new(""),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .is_none());
+    assert!(observed.borrow().is_empty());
+
+    // bytes
+    let observed = Cell::new(0);
+    let output = bytes::take(1)
+      .bind(1)
+      .inspect_value(|v| observed.set(*v))
+      .exec(Input {
+        instant: &Instant::new(b"1" as &[u8]),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(observed.get(), output.value);
+  }
+
+  #[test]
+  fn combinator_inspect_err() {
+    let rejected = Cell::new(0);
+    let c = (take(1).inspect_err(|| rejected.set(rejected.get() + 1)) | take(0))
+      .inspect_err(|| rejected.set(rejected.get() + 10));
+    for text in ["1", ""] {
+      assert!(c
+        .exec(Input {
+          instant: &Instant::new(text),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .is_some());
+    }
+    // only the inner `take(1)` is rejected once
+    assert_eq!(rejected.get(), 1);
+
+    let rejected = Cell::new(false);
+    assert!(bytes::take(1)
+      .inspect_err(|| rejected.set(true))
+      .exec(Input {
+        instant: &Instant::new(b"" as &[u8]),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .is_none());
+    assert!(rejected.get());
+  }
+
   #[test]
   fn check_format_input() {
     INDENT_LEVEL.set(0);
-    assert_eq!(format_input("name", "123"), "(name) input: \"123\"");
+    assert_eq!(format_input("name", 0, "123"), "(name) input at 0: \"123\"");
   }
 
   #[test]
   fn check_format_input_indent() {
     LOG_INDENTATION.set("| ");
     INDENT_LEVEL.set(1);
-    assert_eq!(format_input("name", "123"), "| (name) input: \"123\"");
+    assert_eq!(
+      format_input("name", 0, "123"),
+      "| (name) input at 0: \"123\""
+    );
     INDENT_LEVEL.set(2);
-    assert_eq!(format_input("name", "123"), "| | (name) input: \"123\"");
+    assert_eq!(
+      format_input("name", 0, "123"),
+      "| | (name) input at 0: \"123\""
+    );
     // custom indentation
     LOG_INDENTATION.set("  ");
-    assert_eq!(format_input("name", "123"), "    (name) input: \"123\"");
+    assert_eq!(
+      format_input("name", 0, "123"),
+      "    (name) input at 0: \"123\""
+    );
   }
 
   #[test]
   fn check_format_input_truncated() {
     INDENT_LEVEL.set(0);
     assert_eq!(
-      format_input("name", "1234567890".repeat(10).as_str()),
-      format!("(name) input: {:?}", "1234567890".repeat(10))
+      format_input("name", 0, "1234567890".repeat(10).as_str()),
+      format!("(name) input at 0: {:?}", "1234567890".repeat(10))
     );
     assert_eq!(
-      format_input("name", "1234567890".repeat(11).as_str()),
-      format!("(name) input: {:?} (truncated)", "1234567890".repeat(10))
+      format_input("name", 0, "1234567890".repeat(11).as_str()),
+      format!(
+        "(name) input at 0: {:?} (truncated)",
+        "1234567890".repeat(10)
+      )
+    );
+  }
+
+  #[test]
+  fn check_format_input_control_chars() {
+    INDENT_LEVEL.set(0);
+    // control chars should be escaped to avoid mangling the terminal
+    assert_eq!(
+      format_input("name", 0, "\0\x1b[31m\r"),
+      "(name) input at 0: \"\\0\\u{1b}[31m\\r\""
+    );
+    assert_eq!(
+      format_output(
+        "name",
+        "\x1b[0m",
+        &Ok(Output {
+          value: (),
+          digested: 1
+        })
+      ),
+      "(name) accepted 1 bytes: \"\\u{1b}\""
     );
   }
 
@@ -222,9 +478,9 @@ mod tests {
   fn check_format_input_bytes() {
     INDENT_LEVEL.set(0);
     assert_eq!(
-      format_input("name", b"123" as &[u8]),
+      format_input("name", 0, b"123" as &[u8]),
       // TODO: prettier format bytes
-      "(name) input: [49, 50, 51]"
+      "(name) input at 0: [49, 50, 51]"
     );
   }
 
@@ -233,19 +489,19 @@ mod tests {
     LOG_INDENTATION.set("| ");
     INDENT_LEVEL.set(1);
     assert_eq!(
-      format_input("name", b"123" as &[u8]),
-      "| (name) input: [49, 50, 51]"
+      format_input("name", 0, b"123" as &[u8]),
+      "| (name) input at 0: [49, 50, 51]"
     );
     INDENT_LEVEL.set(2);
     assert_eq!(
-      format_input("name", b"123" as &[u8]),
-      "| | (name) input: [49, 50, 51]"
+      format_input("name", 0, b"123" as &[u8]),
+      "| | (name) input at 0: [49, 50, 51]"
     );
     // custom indentation
     LOG_INDENTATION.set("  ");
     assert_eq!(
-      format_input("name", b"123" as &[u8]),
-      "    (name) input: [49, 50, 51]"
+      format_input("name", 0, b"123" as &[u8]),
+      "    (name) input at 0: [49, 50, 51]"
     );
   }
 
@@ -253,13 +509,16 @@ mod tests {
   fn check_format_input_truncated_bytes() {
     INDENT_LEVEL.set(0);
     assert_eq!(
-      format_input("name", b"1234567890".repeat(10).as_slice()),
-      format!("(name) input: {:?}", b"1234567890".repeat(10).as_slice())
+      format_input("name", 0, b"1234567890".repeat(10).as_slice()),
+      format!(
+        "(name) input at 0: {:?}",
+        b"1234567890".repeat(10).as_slice()
+      )
     );
     assert_eq!(
-      format_input("name", b"1234567890".repeat(11).as_slice()),
+      format_input("name", 0, b"1234567890".repeat(11).as_slice()),
       format!(
-        "(name) input: {:?} (truncated)",
+        "(name) input at 0: {:?} (truncated)",
         b"1234567890".repeat(10).as_slice()
       )
     );
@@ -272,16 +531,24 @@ mod tests {
       format_output(
         "name",
         "123",
-        &Some(Output {
+        &Ok(Output {
           value: (),
           digested: 1
         })
       ),
-      "(name) output: Some(\"1\")"
+      "(name) accepted 1 bytes: \"1\""
+    );
+    assert_eq!(
+      format_output::<_, ()>("name", "123", &Err(Rejection::Backtrack)),
+      "(name) rejected"
+    );
+    assert_eq!(
+      format_output::<_, ()>("name", "123", &Err(Rejection::Cut)),
+      "(name) rejected after a cut"
     );
     assert_eq!(
-      format_output::<_, ()>("name", "123", &None),
-      "(name) output: None"
+      format_output::<_, ()>("name", "123", &Err(Rejection::Incomplete)),
+      "(name) rejected, need more input"
     );
   }
 
@@ -292,21 +559,104 @@ mod tests {
       format_output(
         "name",
         b"123" as &[u8],
-        &Some(Output {
+        &Ok(Output {
           value: (),
           digested: 1
         })
       ),
-      "(name) output: Some([49])"
+      "(name) accepted 1 bytes: [49]"
     );
     assert_eq!(
-      format_output::<_, ()>("name", b"123" as &[u8], &None),
-      "(name) output: None"
+      format_output::<_, ()>("name", b"123" as &[u8], &Err(Rejection::Backtrack)),
+      "(name) rejected"
+    );
+  }
+
+  fn capture_log(f: impl FnOnce()) -> Vec<String> {
+    use std::rc::Rc;
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let sink = lines.clone();
+    LOG_INDENTATION.set("| ");
+    INDENT_LEVEL.set(0);
+    LOG_SINK.set(Some(Box::new(move |line: &str| {
+      sink.borrow_mut().push(line.to_string())
+    })));
+    f();
+    LOG_SINK.set(None);
+    lines.take()
+  }
+
+  #[test]
+  fn log_trace() {
+    let item = (eat('a').log("a") + eat('b').log("b")).log("ab") | eat('c').log("c");
+    let entry = (item.log("item") * (1..)).log("items");
+    let lines = capture_log(|| {
+      entry.exec(Input {
+        instant: &Instant::new("abcx"),
+        state: &mut (),
+        heap: &mut (),
+      });
+    });
+    assert_eq!(
+      lines,
+      [
+        "(items) input at 0: \"abcx\"",
+        "| (item) input at 0: \"abcx\"",
+        "| | (ab) input at 0: \"abcx\"",
+        "| | | (a) input at 0: \"abcx\"",
+        "| | | (a) accepted 1 bytes: \"a\"",
+        "| | | (b) input at 1: \"bcx\"",
+        "| | | (b) accepted 1 bytes: \"b\"",
+        "| | (ab) accepted 2 bytes: \"ab\"",
+        "| (item) accepted 2 bytes: \"ab\"",
+        "| (item) input at 2: \"cx\"",
+        "| | (ab) input at 2: \"cx\"",
+        "| | | (a) input at 2: \"cx\"",
+        "| | | (a) rejected",
+        "| | (ab) rejected",
+        "| | (c) input at 2: \"cx\"",
+        "| | (c) accepted 1 bytes: \"c\"",
+        "| (item) accepted 1 bytes: \"c\"",
+        "| (item) input at 3: \"x\"",
+        "| | (ab) input at 3: \"x\"",
+        "| | | (a) input at 3: \"x\"",
+        "| | | (a) rejected",
+        "| | (ab) rejected",
+        "| | (c) input at 3: \"x\"",
+        "| | (c) rejected",
+        "| (item) rejected",
+        "(items) accepted 3 bytes: \"abc\"",
+      ]
+    );
+  }
+
+  #[test]
+  fn log_trace_bytes() {
+    let entry = (bytes::eat(b'a').log("a") + bytes::eat(b'b').log("b")).log("ab");
+    let lines = capture_log(|| {
+      entry.exec(Input {
+        instant: &Instant::new(b"ax" as &[u8]),
+        state: &mut (),
+        heap: &mut (),
+      });
+    });
+    assert_eq!(
+      lines,
+      [
+        "(ab) input at 0: [97, 120]",
+        "| (a) input at 0: [97, 120]",
+        "| (a) accepted 1 bytes: [97]",
+        "| (b) input at 1: [120]",
+        "| (b) rejected",
+        "(ab) rejected",
+      ]
     );
   }
 
   fn _ensure_debug() {
     let _ = format!("{:?}", take(1).log("take"));
+    let _ = format!("{:?}", take(1).inspect_value(|_| {}));
+    let _ = format!("{:?}", take(1).inspect_err(|| {}));
   }
 
   fn _ensure_clone_copy() {
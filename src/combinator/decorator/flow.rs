@@ -1,19 +1,71 @@
 //! Decorators that modify the acceptance of a combinator.
 
-use super::{create_closure_decorator, create_simple_decorator, Accepted};
+use super::{
+  create_closure_decorator, create_generic_value_decorator, create_simple_decorator,
+  delegate_first_set, Accepted,
+};
 use crate::{
-  action::Input,
-  combinator::{Action, Combinator, Output},
-  digest::Digest,
+  action::{exec_by_cut, Input, Rejection},
+  combinator::{Action, Combinator, FirstSet, LookaheadSet, Output},
+  digest::{self, Digest},
+  idx,
   instant::Instant,
+  range::Range,
+};
+use std::{
+  ops::{RangeFrom, RangeTo},
+  slice::SliceIndex,
 };
 
 create_closure_decorator!(When, "See [`Combinator::when`].");
 create_closure_decorator!(Prevent, "See [`Combinator::prevent`].");
+create_closure_decorator!(WhenHead, "See [`Combinator::when_head`].");
+create_closure_decorator!(WhenHeadByte, "See [`Combinator::when_head_byte`].");
+create_generic_value_decorator!(WhenLiteral, "See [`Combinator::when_literal`].");
+create_generic_value_decorator!(WhenExpected, "See [`Combinator::when_expected`].");
 create_closure_decorator!(Reject, "See [`Combinator::reject`].");
 create_simple_decorator!(Optional, "See [`Combinator::optional`].");
+create_simple_decorator!(Opt, "See [`Combinator::opt`].");
+create_simple_decorator!(Cut, "See [`Combinator::cut`].");
+create_closure_decorator!(OrElse, "See [`Combinator::or_else`].");
 create_simple_decorator!(Boundary, "See [`Combinator::boundary`].");
 
+/// See [`Combinator::max_len`].
+#[derive(Copy, Clone, Debug)]
+pub struct MaxLen<T> {
+  action: T,
+  max: usize,
+  truncate: bool,
+}
+
+impl<T> MaxLen<T> {
+  #[inline]
+  const fn new(action: T, max: usize) -> Self {
+    Self {
+      action,
+      max,
+      truncate: false,
+    }
+  }
+}
+
+/// See [`Combinator::recover_until`].
+#[derive(Copy, Clone, Debug)]
+pub struct RecoverUntil<T, S> {
+  action: T,
+  sync: S,
+  inclusive: bool,
+}
+
+/// The error value of [`Combinator::recover_until`]
+/// when the original combinator rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Recovered {
+  /// The skipped byte range before the sync point.
+  /// This is relative to the start of the whole text, like [`Combinator::range`].
+  pub skipped: Range,
+}
+
 unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>) -> bool> Action
   for When<T, D>
 {
@@ -22,15 +74,17 @@ unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::He
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
+  ) -> Result<Output<Self::Value>, Rejection> {
     if (self.inner)(input.reborrow()) {
-      self.action.exec(input)
+      self.action.exec_cut(input)
     } else {
-      None
+      Err(Rejection::Backtrack)
     }
   }
 }
@@ -43,15 +97,108 @@ unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::He
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
+  ) -> Result<Output<Self::Value>, Rejection> {
     if !(self.inner)(input.reborrow()) {
-      self.action.exec(input)
+      self.action.exec_cut(input)
     } else {
-      None
+      Err(Rejection::Backtrack)
+    }
+  }
+}
+
+unsafe impl<T: Action<Text = str>, D: Fn(char) -> bool> Action for WhenHead<T, D> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    if input.instant.rest().chars().next().is_some_and(&self.inner) {
+      self.action.exec_cut(input)
+    } else {
+      Err(Rejection::Backtrack)
+    }
+  }
+}
+
+unsafe impl<T: Action<Text = [u8]>, D: Fn(u8) -> bool> Action for WhenHeadByte<T, D> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    if input
+      .instant
+      .rest()
+      .first()
+      .is_some_and(|b| (self.inner)(*b))
+    {
+      self.action.exec_cut(input)
+    } else {
+      Err(Rejection::Backtrack)
+    }
+  }
+}
+
+unsafe impl<T: Action<Text: Digest + PartialEq>, L: AsRef<T::Text>> Action for WhenLiteral<T, L>
+where
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    if digest::starts_with(input.instant.rest(), self.inner.as_ref()) {
+      self.action.exec_cut(input)
+    } else {
+      Err(Rejection::Backtrack)
+    }
+  }
+}
+
+unsafe impl<T: Action<Text: PartialEq>, L: AsRef<T::Text>> Action for WhenExpected<T, L> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    match input.instant.expectation() {
+      Some(expectation) if expectation != self.inner.as_ref() => Err(Rejection::Backtrack),
+      _ => self.action.exec_cut(input),
     }
   }
 }
@@ -66,18 +213,20 @@ unsafe impl<
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
-    self.action.exec(input.reborrow()).and_then(|output| {
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(input.reborrow()).and_then(|output| {
       if (self.inner)(unsafe {
         Accepted::new_unchecked(input.instant, output.as_ref(), input.state, input.heap)
       }) {
-        None
+        Err(Rejection::Backtrack)
       } else {
-        output.into()
+        Ok(output)
       }
     })
   }
@@ -101,7 +250,28 @@ unsafe impl<T: Action<Value: Default>> Action for Optional<T> {
   }
 }
 
-unsafe impl<T: Action<Text = str>> Action for Boundary<T> {
+unsafe impl<T: Action> Action for Opt<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = Option<T::Value>;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    Some(match self.action.exec(input) {
+      Some(output) => output.map(Some),
+      None => Output {
+        value: None,
+        digested: 0,
+      },
+    })
+  }
+}
+
+unsafe impl<T: Action> Action for Cut<T> {
   type Text = T::Text;
   type State = T::State;
   type Heap = T::Heap;
@@ -112,17 +282,203 @@ unsafe impl<T: Action<Text = str>> Action for Boundary<T> {
     &self,
     input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
   ) -> Option<Output<Self::Value>> {
+    self.action.exec(input)
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    // more input may still be accepted, so don't turn it into a cut
+    self
+      .action
+      .exec_cut(input)
+      .map_err(|rejection| match rejection {
+        Rejection::Incomplete => Rejection::Incomplete,
+        _ => Rejection::Cut,
+      })
+  }
+}
+
+unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>) -> T::Value>
+  Action for OrElse<T, D>
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    Some(
+      self
+        .action
+        .exec(input.reborrow())
+        .unwrap_or_else(|| Output {
+          value: (self.inner)(input),
+          digested: 0,
+        }),
+    )
+  }
+}
+
+unsafe impl<T: Action<Text = str>> Action for Boundary<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  // `Option::is_none_or` requires Rust 1.82
+  #[allow(clippy::unnecessary_map_or)]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
     let rest = input.instant.rest();
-    self.action.exec(input).and_then(|output| {
-      unsafe { rest.get_unchecked(output.digested..) }
+    self.action.exec_cut(input).and_then(|output| {
+      // SAFETY: `output.digested` is valid according to the safety requirement of `Action`
+      idx::unchecked!(idx::slice_from(rest, output.digested))
         .chars()
         .next()
-        .is_none_or(|c| !c.is_alphanumeric() && c != '_')
+        .map_or(true, |c| !c.is_alphanumeric() && c != '_')
         .then_some(output)
+        .ok_or(Rejection::Backtrack)
+    })
+  }
+}
+
+unsafe impl<T: Action<Text: Digest>> Action for MaxLen<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let rest = input.instant.rest();
+    self.action.exec_cut(input).and_then(|mut output| {
+      if output.digested <= self.max {
+        Ok(output)
+      } else if self.truncate {
+        // truncate to a valid position, at most 3 steps back for `str`
+        let mut digested = self.max;
+        while !rest.validate(digested) {
+          // SAFETY: 0 is always valid so the loop stops before underflow
+          digested = idx::unchecked!(idx::sub(digested, 1));
+        }
+        output.digested = digested;
+        Ok(output)
+      } else {
+        Err(Rejection::Backtrack)
+      }
+    })
+  }
+}
+
+delegate_first_set!(
+  Cut, Boundary;
+  When, Prevent, WhenHead, WhenHeadByte, WhenLiteral, WhenExpected, Reject
+);
+
+// accept with zero digested when the original combinator rejects
+macro_rules! nullable_first_set {
+  ($name:ident<$($generic:ident),+>) => {
+    impl<$($generic),+> FirstSet for $name<$($generic),+>
+    where
+      T: FirstSet,
+    {
+      #[inline]
+      fn first_set(&self) -> Option<LookaheadSet> {
+        self.action.first_set().map(|set| LookaheadSet {
+          nullable: true,
+          ..set
+        })
+      }
+    }
+  };
+}
+nullable_first_set!(Optional<T>);
+nullable_first_set!(Opt<T>);
+nullable_first_set!(OrElse<T, D>);
+
+impl<T: FirstSet> FirstSet for MaxLen<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    // truncation may make the digested length zero
+    self.action.first_set().map(|set| LookaheadSet {
+      nullable: set.nullable || self.truncate,
+      ..set
     })
   }
 }
 
+unsafe impl<T: Action<Text: Digest>, S: Action<Text = T::Text, State = T::State, Heap = T::Heap>>
+  Action for RecoverUntil<T, S>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = Result<T::Value, Recovered>;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    if let Some(output) = self.action.exec(input.reborrow()) {
+      return Some(output.map(Ok));
+    }
+
+    // panic mode, scan forward for the sync point
+    let start = input.instant.digested();
+    let rest = input.instant.rest();
+    let len = rest.len();
+    let mut offset = 0;
+    loop {
+      // skip positions inside a char for `str`
+      if rest.validate(offset) {
+        if let Some(sync) = self
+          .sync
+          .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(offset) }))
+        {
+          debug_assert!(usize::MAX - offset >= sync.digested);
+          // SAFETY: the skipped and the sync content are inside the whole text,
+          // whose length is a valid usize
+          return Some(Output {
+            value: Err(Recovered {
+              skipped: start..idx::unchecked!(idx::add(start, offset)),
+            }),
+            digested: if self.inclusive {
+              idx::unchecked!(idx::add(offset, sync.digested))
+            } else {
+              offset
+            },
+          });
+        }
+      }
+      if offset == len {
+        return None;
+      }
+      // SAFETY: `offset < len` so this won't overflow
+      offset = idx::unchecked!(idx::add(offset, 1));
+    }
+  }
+}
+
 impl<T> Combinator<T> {
   /// Create a new combinator to check the [`Input`] before being executed.
   /// The combinator will be executed only if the `condition` returns `true`.
@@ -170,6 +526,82 @@ impl<T> Combinator<T> {
     Combinator::new(Prevent::new(self.action, preventer))
   }
 
+  /// Create a new combinator to check the next undigested char before being executed.
+  /// The combinator will be executed only if the `condition` returns `true`.
+  /// If the rest is empty, the combinator will reject.
+  ///
+  /// Unlike [`Combinator::when`], the condition only sees the head of the rest,
+  /// so it is cheap and can be inspected statically.
+  /// For `[u8]`, use [`Combinator::when_head_byte`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Text = str>>) {
+  /// combinator.when_head(|c| c.is_ascii_digit())
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn when_head<F: Fn(char) -> bool>(self, condition: F) -> Combinator<WhenHead<T, F>> {
+    Combinator::new(WhenHead::new(self.action, condition))
+  }
+
+  /// Create a new combinator to check the next undigested byte before being executed.
+  /// The combinator will be executed only if the `condition` returns `true`.
+  /// If the rest is empty, the combinator will reject.
+  ///
+  /// This is the `[u8]` version of [`Combinator::when_head`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Text = [u8]>>) {
+  /// combinator.when_head_byte(|b| b.is_ascii_digit())
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn when_head_byte<F: Fn(u8) -> bool>(self, condition: F) -> Combinator<WhenHeadByte<T, F>> {
+    Combinator::new(WhenHeadByte::new(self.action, condition))
+  }
+
+  /// Create a new combinator to check whether the rest starts with the `literal`
+  /// before being executed.
+  /// The combinator will be executed only if the rest starts with the `literal`.
+  ///
+  /// The literal is not digested, the original combinator is executed
+  /// from the same position.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Text = str>>) {
+  /// combinator.when_literal("/*")
+  /// # ;}
+  /// # fn tb(combinator: Combinator<impl Action<Text = [u8]>>) {
+  /// combinator.when_literal(b"/*")
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn when_literal<L>(self, literal: L) -> Combinator<WhenLiteral<T, L>> {
+    Combinator::new(WhenLiteral::new(self.action, literal))
+  }
+
+  /// Create a new combinator to check the [`Instant::expectation`]
+  /// (set by [`Parser::next_expecting`](crate::parser::Parser::next_expecting))
+  /// before being executed.
+  /// The combinator will be executed only if nothing is expected,
+  /// or the expectation equals the `literal`.
+  ///
+  /// Combinators without this decorator (e.g. whitespaces) ignore the expectation.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Text = str>>) {
+  /// combinator.when_expected("==")
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn when_expected<L>(self, literal: L) -> Combinator<WhenExpected<T, L>> {
+    Combinator::new(WhenExpected::new(self.action, literal))
+  }
+
   /// Create a new combinator to check the [`Accepted`] after being executed.
   /// The combinator will reject if the `rejecter` returns `true`.
   /// # Examples
@@ -234,6 +666,87 @@ impl<T> Combinator<T> {
     Combinator::new(Optional::new(self.action))
   }
 
+  /// Make the combinator optional and wrap the value in [`Option`].
+  ///
+  /// If the original combinator accepts, the new combinator will accept with `Some(value)`,
+  /// otherwise it will accept with [`None`] and zero digested.
+  ///
+  /// Unlike [`Combinator::optional`], this doesn't require the `Value` to implement [`Default`].
+  /// # Caveats
+  /// Be careful of infinite loops since this may accept with 0 bytes digested.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::eat, parser::Parser};
+  /// let entry = || eat('-').bind('-').opt().tuple() + eat('1');
+  /// assert_eq!(Parser::builder().entry(entry()).build("-1").next().unwrap().value, (Some('-'),));
+  /// assert_eq!(Parser::builder().entry(entry()).build("1").next().unwrap().value, (None,));
+  /// ```
+  #[inline]
+  pub fn opt(self) -> Combinator<Opt<T>> {
+    Combinator::new(Opt::new(self.action))
+  }
+
+  /// Mark the combinator as a point of no return (a.k.a. commit).
+  ///
+  /// If the new combinator rejects, the surrounding `|` (and [`Combinator::or_either`])
+  /// won't try the other branches and will reject directly.
+  /// This is usually used after a distinctive prefix like a keyword,
+  /// so that the parser won't backtrack to other branches once the prefix is matched.
+  ///
+  /// The cut is propagated through `+`, `|`, [`seq`](crate::combinator::seq),
+  /// [`preceded`](crate::combinator::preceded) and other pickers,
+  /// [`dispatch`](crate::combinator::dispatch),
+  /// and decorators which don't change whether the combinator rejects,
+  /// like [`Combinator::map`], [`Combinator::then`] and [`Combinator::atomic`].
+  /// Decorators which may turn a rejection into an acceptance and `*`
+  /// treat it as a normal rejection,
+  /// e.g. `a.cut().optional()` still accepts when `a` rejects.
+  /// See [`Action::exec_cut`] for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, next}, parser::Parser};
+  /// let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+  /// let other = || (next(|_| true) * (1..)).bind(false);
+  /// // once `let` is matched, never fall back to `other`
+  /// let entry = (eat("let ") + (ident() + " = " + ident()).cut()).bind(true) | other();
+  /// assert_eq!(Parser::builder().entry(entry).build("let x = y").next().unwrap().value, true);
+  ///
+  /// # let entry = (eat("let ") + (ident() + " = " + ident()).cut()).bind(true) | other();
+  /// assert!(Parser::builder().entry(entry).build("let x 1").next().is_none());
+  /// ```
+  #[inline]
+  pub fn cut(self) -> Combinator<Cut<T>> {
+    Combinator::new(Cut::new(self.action))
+  }
+
+  /// Make the combinator always accept.
+  /// If the original combinator rejects, the new combinator will accept
+  /// with 0 bytes digested and the value returned by `f`.
+  ///
+  /// Unlike [`Combinator::optional`], the value is computed lazily from the [`Input`],
+  /// so you can inspect the rest of the text and the state to build a recovery value.
+  /// Modifications to the state and heap by the rejected combinator are visible to `f`.
+  /// # Caveats
+  /// Be careful of infinite loops since this may accept with 0 bytes digested.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::eat, parser::Parser};
+  /// let entry = eat("true").bind(Ok(true))
+  ///   | eat("false").bind(Ok(false));
+  /// let entry = entry.or_else(|input| Err(input.instant.digested()));
+  /// assert_eq!(Parser::builder().entry(entry).build("x").next().unwrap().value, Err(0));
+  /// ```
+  #[inline]
+  pub fn or_else<F: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>) -> T::Value>(
+    self,
+    f: F,
+  ) -> Combinator<OrElse<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(OrElse::new(self.action, f))
+  }
+
   /// Create a new combinator to reject after execution
   /// if the next undigested char is alphanumeric or `_`.
   /// See [`char::is_alphanumeric`].
@@ -248,6 +761,103 @@ impl<T> Combinator<T> {
   pub fn boundary(self) -> Combinator<Boundary<T>> {
     Combinator::new(Boundary::new(self.action))
   }
+
+  /// Create a new combinator to reject after execution
+  /// if more than `max` bytes are digested.
+  /// Use [`Combinator::truncate`] to truncate the digested bytes instead.
+  ///
+  /// This is a cheap post-check, the original combinator is still fully executed.
+  /// To stop unbounded scanners from scanning the whole input,
+  /// use the bounded built-ins instead, e.g. [`till_at_most`](crate::combinator::till_at_most),
+  /// [`skip_at_most`](crate::combinator::skip_at_most)
+  /// and [`Mul`](crate::combinator::ops::mul) with an upper bound like `next(f) * (..=max)`.
+  ///
+  /// For network-facing parsers, bound every token which can grow with the input
+  /// (string bodies, comments, identifiers, numbers, etc.),
+  /// e.g. a few KiB for identifiers and numbers
+  /// and the max message size allowed by the protocol for string bodies.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Text=str>>) {
+  /// combinator.max_len(1024)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn max_len(self, max: usize) -> Combinator<MaxLen<T>> {
+    Combinator::new(MaxLen::new(self.action, max))
+  }
+}
+
+impl<T> Combinator<T> {
+  /// Create a new combinator to recover from rejection in panic mode.
+  ///
+  /// If the original combinator accepts, the value is wrapped in [`Ok`].
+  /// Otherwise, `sync` is tried at every position (every char boundary for `str`,
+  /// every byte for `[u8]`) starting from the current one,
+  /// and the first match accepts with [`Err`] of [`Recovered`].
+  /// The sync point is not digested by default, use [`Combinator::inclusive`] to digest it.
+  ///
+  /// The combinator rejects if `sync` never matches.
+  /// Use `sync | end` (e.g. with [`till_eof`](crate::combinator::till_eof)) to recover till the end.
+  ///
+  /// `sync` is executed with the real state and heap, so it should be side-effect free
+  /// or only mutate them when it accepts.
+  /// # Caveats
+  /// If `sync` matches at the current position, the combinator accepts 0 bytes in the exclusive mode.
+  /// Be careful with infinite loops, e.g. in [`Mul`](crate::combinator::ops::mul).
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, next, Recovered}, parser::Parser};
+  /// let stmt = (next(|c| c.is_ascii_digit()) + eat(';'))
+  ///   .recover_until(eat(';'))
+  ///   .inclusive();
+  /// let mut parser = Parser::builder().entry(stmt).build("1;ab;2;");
+  /// assert_eq!(parser.next().unwrap().value, Ok(()));
+  /// assert_eq!(parser.next().unwrap().value, Err(Recovered { skipped: 2..4 }));
+  /// assert_eq!(parser.next().unwrap().value, Ok(()));
+  /// ```
+  #[inline]
+  pub fn recover_until<S>(self, sync: impl Into<Combinator<S>>) -> Combinator<RecoverUntil<T, S>> {
+    Combinator::new(RecoverUntil {
+      action: self.action,
+      sync: sync.into().action,
+      inclusive: false,
+    })
+  }
+}
+
+impl<T, S> Combinator<RecoverUntil<T, S>> {
+  /// Digest the sync point after [`Combinator::recover_until`] recovers.
+  /// The sync point is still excluded from [`Recovered::skipped`].
+  #[inline]
+  pub fn inclusive(self) -> Self {
+    Combinator::new(RecoverUntil {
+      inclusive: true,
+      ..self.action
+    })
+  }
+}
+
+impl<T> Combinator<MaxLen<T>> {
+  /// Truncate the digested bytes to at most `max` bytes instead of rejecting
+  /// after [`Combinator::max_len`].
+  ///
+  /// For `str`, the digested bytes are truncated to the nearest char boundary before `max`.
+  /// The value is not changed, so it may be built from the untruncated content.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::till, parser::Parser};
+  /// let entry = till(()).max_len(3).truncate();
+  /// assert_eq!(Parser::builder().entry(entry).build("12345").next().unwrap().digested, 3);
+  /// ```
+  #[inline]
+  pub fn truncate(self) -> Self {
+    Combinator::new(MaxLen {
+      truncate: true,
+      ..self.action
+    })
+  }
 }
 
 #[cfg(test)]
@@ -462,6 +1072,561 @@ mod tests {
     assert!(executed);
   }
 
+  #[test]
+  fn combinator_opt() {
+    // accept
+    let mut executed = false;
+    helper(accepter().opt().bind(()), "123", &mut executed, Some(1));
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      accepter_bytes().opt().bind(()),
+      b"123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+
+    // reject but optional
+    let mut executed = false;
+    helper(rejecter().opt().bind(()), "123", &mut executed, Some(0));
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      rejecter_bytes().opt().bind(()),
+      b"123",
+      &mut executed,
+      Some(0),
+    );
+    assert!(executed);
+
+    // at the end of input
+    let mut executed = false;
+    helper(accepter().opt().bind(()), "", &mut executed, Some(0));
+    assert!(executed);
+
+    // debug
+    let _ = format!("{:?}", accepter().opt());
+    // copy & clone
+    let c = accepter().opt();
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_opt_value() {
+    // no `Default` is required
+    #[derive(Debug, Clone, PartialEq)]
+    struct Node(usize);
+
+    let exec =
+      |action: &dyn Action<Text = str, State = bool, Heap = (), Value = (Option<Node>, Node)>,
+       input: &str| {
+        action
+          .exec(Input {
+            instant: &Instant::new(input),
+            state: &mut false,
+            heap: &mut (),
+          })
+          .map(|o| (o.value, o.digested))
+      };
+    let sign = || eat('-').select(|a| Node(a.start())).opt();
+    let digit = || next(|c| c.is_ascii_digit()).select(|a| Node(a.start()));
+
+    // concat with the optional part present or absent
+    let entry = sign().tuple() + digit().tuple();
+    assert_eq!(exec(&entry, "-1"), Some(((Some(Node(0)), Node(1)), 2)));
+    assert_eq!(exec(&entry, "1"), Some(((None, Node(0)), 1)));
+    assert_eq!(exec(&entry, "-"), None);
+
+    // `(Option<T>,)` is concatenated with `()` like other tuples
+    let entry = next(|c| c.is_ascii_digit()) + sign().tuple() + eat(',');
+    let exec = |input: &str| {
+      entry
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut false,
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    };
+    assert_eq!(exec("1-,"), Some(((Some(Node(1)),), 3)));
+    assert_eq!(exec("1,"), Some(((None,), 2)));
+  }
+
+  #[test]
+  fn combinator_cut() {
+    let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+    let other = || next(|_| true) * (1..);
+
+    // without cut, fall back to other branches
+    helper(
+      (eat("let ") + ident() + " = " + ident()) | other(),
+      "let x = 1",
+      &mut false,
+      Some(9),
+    );
+    // with cut, reject directly
+    helper(
+      (eat("let ") + (ident() + " = " + ident()).cut()) | other(),
+      "let x = 1",
+      &mut false,
+      None,
+    );
+    helper(
+      (eat("let ") + (ident() + " = " + ident()).cut()) | other(),
+      "let x = y",
+      &mut false,
+      Some(9),
+    );
+    // rejected before the cut, try other branches
+    helper(
+      (eat("let ") + (ident() + " = " + ident()).cut()) | other(),
+      "const x = 1",
+      &mut false,
+      Some(11),
+    );
+    // propagated through value decorators, nested `|` and `or_either`
+    helper(
+      (eat("let ") + ident().cut()).bind(()) | eat("let") | other(),
+      "let 1",
+      &mut false,
+      None,
+    );
+    helper(
+      eat("x") | (eat("let ") + ident().cut()).map(|_| ()) | other(),
+      "let 1",
+      &mut false,
+      None,
+    );
+    helper(
+      (eat("let ") + ident().cut()).or_either(other()).bind(()),
+      "let 1",
+      &mut false,
+      None,
+    );
+    // decorators which may accept on rejection treat it as a normal rejection
+    helper(
+      (eat("let ") + ident().cut()).optional() | other(),
+      "let 1",
+      &mut false,
+      Some(0),
+    );
+    // other decorators propagate it
+    helper(
+      (eat("let ") + ident().cut()).when(|_| true) | other(),
+      "let 1",
+      &mut false,
+      None,
+    );
+    // without `|`
+    helper(eat("a").cut(), "b", &mut false, None);
+    helper(eat("a").cut(), "a", &mut false, Some(1));
+  }
+
+  #[test]
+  fn combinator_exec_cut() {
+    let exec_cut = |action: &dyn Action<Text = str, State = bool, Heap = (), Value = ()>,
+                    text: &str| {
+      action
+        .exec_cut(Input {
+          instant: &Instant::new(text),
+          state: &mut false,
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    };
+    assert_eq!(exec_cut(&eat("a"), "a"), Ok(1));
+    assert_eq!(exec_cut(&eat("a"), "b"), Err(Rejection::Backtrack));
+    assert_eq!(exec_cut(&eat("a").cut(), "b"), Err(Rejection::Cut));
+    assert_eq!(
+      exec_cut(&(eat("a") + eat("b").cut()), "ac"),
+      Err(Rejection::Cut)
+    );
+    assert_eq!(
+      exec_cut(&(eat("a") + eat("b").cut()), "c"),
+      Err(Rejection::Backtrack)
+    );
+    assert_eq!(
+      exec_cut(&(eat("a").cut() | eat("b")), "b"),
+      Err(Rejection::Cut)
+    );
+    assert_eq!(
+      exec_cut(&(eat("a") | eat("b").cut()), "c"),
+      Err(Rejection::Cut)
+    );
+    assert_eq!(
+      exec_cut(&Box::new(eat("a").cut()), "b"),
+      Err(Rejection::Cut)
+    );
+    assert_eq!(
+      exec_cut(&std::rc::Rc::new(eat("a").cut()), "b"),
+      Err(Rejection::Cut)
+    );
+
+    // transparent decorators propagate the cut
+    let cut = || eat("a").cut();
+    assert_eq!(exec_cut(&cut().prepare(|_| {}), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().then(|_| {}), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().catch(|_| {}), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().finally(|_| {}), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().atomic(), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().when(|_| true), "b"), Err(Rejection::Cut));
+    assert_eq!(
+      exec_cut(&cut().when(|_| false), "b"),
+      Err(Rejection::Backtrack)
+    );
+    assert_eq!(exec_cut(&cut().boundary(), "b"), Err(Rejection::Cut));
+    assert_eq!(exec_cut(&cut().log("cut"), "b"), Err(Rejection::Cut));
+    // but not the repetition and optional decorators
+    assert_eq!(exec_cut(&cut().optional(), "b"), Ok(0));
+  }
+
+  #[test]
+  fn combinator_or_else() {
+    // accepted, the closure is not called
+    let mut executed = false;
+    helper(
+      accepter().or_else(|_| unreachable!()),
+      "123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      accepter_bytes().or_else(|_| unreachable!()),
+      b"123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+
+    // rejected, the state modified by the rejected combinator is visible
+    let mut executed = false;
+    helper(
+      rejecter().or_else(|input| assert!(*input.state)),
+      "123",
+      &mut executed,
+      Some(0),
+    );
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      rejecter_bytes().or_else(|input| assert!(*input.state)),
+      b"123",
+      &mut executed,
+      Some(0),
+    );
+    assert!(executed);
+
+    // debug
+    let _ = format!("{:?}", accepter().or_else(|_| ()));
+    // copy & clone
+    let c = accepter().or_else(|_| ());
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_or_else_compose() {
+    use crate::parser::Parser;
+
+    // the recovery value is computed from the rest
+    let value = (eat('a').bind('a') | eat('b').bind('b')).or_else(|input| {
+      input
+        .instant
+        .rest()
+        .chars()
+        .next()
+        .map_or('?', |c| c.to_ascii_uppercase())
+    });
+    let entry = (value * 3).fold(String::new, |mut acc, c| {
+      acc.push(c);
+      acc
+    });
+    let output = Parser::builder()
+      .state(false)
+      .entry(entry)
+      .build("abx")
+      .next()
+      .unwrap();
+    assert_eq!(output.value, "abX");
+    assert_eq!(output.digested, 2);
+
+    let entry = eat('[') + value.tuple() + value.tuple() + ']';
+    let output = Parser::builder()
+      .state(false)
+      .entry(entry)
+      .build("[a]")
+      .next()
+      .unwrap();
+    assert_eq!(output.value, ('a', ']'));
+    assert_eq!(output.digested, 3);
+  }
+
+  #[test]
+  fn combinator_max_len() {
+    let mut executed = false;
+    helper(till(()).max_len(3), "123", &mut executed, Some(3));
+    helper(till(()).max_len(3), "1234", &mut executed, None);
+    helper(bytes::till(()).max_len(3), b"1234", &mut executed, None);
+    helper(accepter().max_len(0), "123", &mut executed, None);
+    assert!(executed);
+
+    // truncate
+    helper(
+      till(()).max_len(3).truncate(),
+      "123",
+      &mut executed,
+      Some(3),
+    );
+    helper(
+      till(()).max_len(3).truncate(),
+      "1234",
+      &mut executed,
+      Some(3),
+    );
+    helper(
+      bytes::till(()).max_len(3).truncate(),
+      b"1234",
+      &mut executed,
+      Some(3),
+    );
+    // truncate to char boundary
+    helper(
+      till(()).max_len(2).truncate(),
+      "a好",
+      &mut executed,
+      Some(1),
+    );
+    helper(till(()).max_len(0).truncate(), "好", &mut executed, Some(0));
+
+    // debug
+    let _ = format!("{:?}", accepter().max_len(1).truncate());
+    // copy & clone
+    let c = accepter().max_len(1);
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_max_len_post_check() {
+    use std::cell::Cell;
+    let count = Cell::new(0);
+    let a = || {
+      next(|c| {
+        count.set(count.get() + 1);
+        c == 'a'
+      })
+    };
+    let text = "a".repeat(100);
+
+    // the post-check doesn't stop the scanning
+    helper((a() * (..)).max_len(10), text.as_str(), &mut false, None);
+    assert_eq!(count.get(), 100);
+
+    // use an upper bound to short-circuit
+    count.set(0);
+    helper(a() * (..=10), text.as_str(), &mut false, Some(10));
+    assert_eq!(count.get(), 10);
+  }
+
+  #[test]
+  fn combinator_when_head() {
+    // executed
+    let mut executed = false;
+    helper(
+      accepter().when_head(|c| c == '1'),
+      "123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+    // prevented
+    let mut executed = false;
+    helper(
+      accepter().when_head(|c| c == '2'),
+      "123",
+      &mut executed,
+      None,
+    );
+    assert!(!executed);
+    // empty rest
+    let mut executed = false;
+    helper(accepter().when_head(|_| true), "", &mut executed, None);
+    assert!(!executed);
+    // multi-byte chars
+    let mut executed = false;
+    helper(
+      wrap(|input| {
+        *input.state = true;
+        input.instant.accept(3)
+      })
+      .when_head(|c| c == '好'),
+      "好",
+      &mut executed,
+      Some(3),
+    );
+    assert!(executed);
+
+    // compose under `|`
+    let mut executed = false;
+    helper(
+      rejecter().when_head(|c| c == 'a') | accepter().when_head(|c| c == '1'),
+      "1",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+
+    // bytes
+    let mut executed = false;
+    helper(
+      accepter_bytes().when_head_byte(|b| b == b'1'),
+      b"123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      accepter_bytes().when_head_byte(|b| b == b'2'),
+      b"123",
+      &mut executed,
+      None,
+    );
+    assert!(!executed);
+    let mut executed = false;
+    helper(
+      accepter_bytes().when_head_byte(|_| true),
+      b"",
+      &mut executed,
+      None,
+    );
+    assert!(!executed);
+
+    // debug
+    let _ = format!("{:?}", accepter().when_head(|_| true));
+    let _ = format!("{:?}", accepter_bytes().when_head_byte(|_| true));
+    // copy & clone
+    let c = accepter().when_head(|_| true);
+    let _c = c;
+    let _c = c.clone();
+    let c = accepter_bytes().when_head_byte(|_| true);
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_when_literal() {
+    // executed
+    let mut executed = false;
+    helper(accepter().when_literal("12"), "123", &mut executed, Some(1));
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      accepter().when_literal(String::from("1好")),
+      "1好",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+    // prevented
+    let mut executed = false;
+    helper(accepter().when_literal("13"), "123", &mut executed, None);
+    assert!(!executed);
+    let mut executed = false;
+    helper(accepter().when_literal("1"), "", &mut executed, None);
+    assert!(!executed);
+    // empty literal
+    let mut executed = false;
+    helper(accepter().when_literal(""), "1", &mut executed, Some(1));
+    assert!(executed);
+
+    // compose under `|`
+    let mut executed = false;
+    helper(
+      rejecter().when_literal("/*") | accepter().when_literal("//"),
+      "//",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+
+    // bytes
+    let mut executed = false;
+    helper(
+      accepter_bytes().when_literal(b"12"),
+      b"123",
+      &mut executed,
+      Some(1),
+    );
+    assert!(executed);
+    let mut executed = false;
+    helper(
+      accepter_bytes().when_literal(vec![b'2']),
+      b"123",
+      &mut executed,
+      None,
+    );
+    assert!(!executed);
+
+    // debug
+    let _ = format!("{:?}", accepter().when_literal("1"));
+    // copy & clone
+    let c = accepter().when_literal("1");
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_when_expected() {
+    let exec = |action: &dyn Action<Text = str, State = bool, Heap = (), Value = ()>,
+                expectation: Option<&str>| {
+      let mut executed = false;
+      let output = action
+        .exec(Input {
+          instant: &Instant::new("123").with_expectation(expectation),
+          state: &mut executed,
+          heap: &mut (),
+        })
+        .map(|o| o.digested);
+      (output, executed)
+    };
+
+    // nothing is expected
+    assert_eq!(exec(&accepter().when_expected("1"), None), (Some(1), true));
+    // expected
+    assert_eq!(
+      exec(&accepter().when_expected("1"), Some("1")),
+      (Some(1), true)
+    );
+    assert_eq!(
+      exec(&accepter().when_expected(String::from("1")), Some("1")),
+      (Some(1), true)
+    );
+    // prevented, even if the rest starts with the literal
+    assert_eq!(
+      exec(&accepter().when_expected("1"), Some("12")),
+      (None, false)
+    );
+    // combinators without the decorator ignore the expectation
+    assert_eq!(exec(&accepter(), Some("12")), (Some(1), true));
+
+    // the expectation is kept in sub-instants
+    let c = eat('1') + accepter().when_expected("2");
+    assert_eq!(exec(&c, Some("2")), (Some(2), true));
+    assert_eq!(exec(&c, Some("1")), (None, false));
+
+    // debug
+    let _ = format!("{:?}", accepter().when_expected("1"));
+    // copy & clone
+    let c = accepter().when_expected("1");
+    let _c = c;
+    let _c = c.clone();
+  }
+
   #[test]
   fn combinator_boundary() {
     let mut executed = false;
@@ -495,4 +1660,144 @@ mod tests {
     let _c = c;
     let _c = c.clone();
   }
+
+  #[test]
+  fn combinator_recover_until() {
+    fn exec<Text: ?Sized + Digest, V>(
+      action: impl Action<Text = Text, State = bool, Heap = (), Value = V>,
+      instant: &Instant<&Text>,
+    ) -> Option<(V, usize)>
+    where
+      RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+    {
+      action
+        .exec(Input {
+          instant,
+          state: &mut false,
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    }
+    let err = |skipped| Err(Recovered { skipped });
+
+    // accepted
+    assert_eq!(
+      exec(accepter().recover_until(eat(';')), &Instant::new("1;")),
+      Some((Ok(()), 1))
+    );
+    // recovery at offset 0
+    assert_eq!(
+      exec(rejecter().recover_until(eat(';')), &Instant::new(";1")),
+      Some((err(0..0), 0))
+    );
+    assert_eq!(
+      exec(
+        rejecter().recover_until(eat(';')).inclusive(),
+        &Instant::new(";1")
+      ),
+      Some((err(0..0), 1))
+    );
+    // skip to the sync point, char by char
+    assert_eq!(
+      exec(rejecter().recover_until(eat(';')), &Instant::new("a好;1")),
+      Some((err(0..4), 4))
+    );
+    assert_eq!(
+      exec(rejecter().recover_until(eat("好")), &Instant::new("ab好")),
+      Some((err(0..2), 2))
+    );
+    assert_eq!(
+      exec(rejecter().recover_until(eat(';')).inclusive(), &unsafe {
+        Instant::new("1a;").to_digested_unchecked(1)
+      }),
+      Some((err(1..2), 2))
+    );
+    // bytes
+    assert_eq!(
+      exec(
+        rejecter_bytes().recover_until(bytes::eat(b';')).inclusive(),
+        &Instant::new(b"ab;" as &[u8])
+      ),
+      Some((err(0..2), 3))
+    );
+    // the sync point never appears
+    assert_eq!(
+      exec(rejecter().recover_until(eat(';')), &Instant::new("abc")),
+      None
+    );
+    assert_eq!(
+      exec(
+        rejecter_bytes().recover_until(bytes::eat(b';')),
+        &Instant::new(b"" as &[u8])
+      ),
+      None
+    );
+    // sync till the end
+    assert_eq!(
+      exec(
+        rejecter()
+          .recover_until(eat(';') | till_eof().when(|input| input.instant.rest().is_empty())),
+        &Instant::new("abc")
+      ),
+      Some((err(0..3), 3))
+    );
+
+    // debug
+    let _ = format!("{:?}", rejecter().recover_until(eat(';')));
+    // copy & clone
+    let c = rejecter().recover_until(eat(';'));
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_recover_until_in_mul() {
+    let stmt = || (eat('a') + eat(';')).recover_until(eat(';')).inclusive();
+    let entry = (stmt() * (..)).fold(Vec::new, |mut acc, v| {
+      acc.push(v);
+      acc
+    });
+    let output = entry
+      .exec(Input {
+        instant: &Instant::new("a;xx;a;y;b"),
+        state: &mut false,
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(
+      output.value,
+      vec![
+        Ok(()),
+        Err(Recovered { skipped: 2..4 }),
+        Ok(()),
+        Err(Recovered { skipped: 7..8 })
+      ]
+    );
+    // the last item can't recover
+    assert_eq!(output.digested, 9);
+
+    // nested recovery
+    let item = || eat('a').recover_until(eat(','));
+    let list = (eat('[')
+      + (item() * (..))
+        .sep(eat(','))
+        .fold(|| 0, |n, v: Result<(), Recovered>| n + v.is_err() as usize)
+        .tuple()
+      + eat(']'))
+    .recover_until(eat(']'))
+    .inclusive();
+    let exec = |text| {
+      list
+        .exec(Input {
+          instant: &Instant::new(text),
+          state: &mut false,
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    };
+    // the inner recovery skips the bad item
+    assert_eq!(exec("[a,b,a]"), Some((Ok((1,)), 7)));
+    // the outer recovery skips the bad list
+    assert_eq!(exec("[a,b;]"), Some((Err(Recovered { skipped: 0..5 }), 6)));
+  }
 }
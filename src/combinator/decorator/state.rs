@@ -1,15 +1,90 @@
-use super::{create_closure_decorator, Accepted};
+use super::{
+  create_closure_decorator, create_generic_value_decorator, create_simple_decorator,
+  delegate_first_set, Accepted,
+};
 use crate::{
-  action::{Action, Input, Output},
+  action::{exec_by_cut, Action, Input, Output, Rejection},
   combinator::Combinator,
+  diagnostic::{Diag, Diagnostics},
   digest::Digest,
+  expect::Expectations,
+  idx,
   instant::Instant,
+  intern::{Interner, Symbol},
+  memo::MemoTable,
+  stats::BranchStats,
 };
+use std::{borrow::BorrowMut, fmt::Debug, marker::PhantomData};
 
 create_closure_decorator!(Prepare, "See [`Combinator::prepare`].");
 create_closure_decorator!(Then, "See [`Combinator::then`].");
 create_closure_decorator!(Catch, "See [`Combinator::catch`].");
+create_simple_decorator!(Atomic, "See [`Combinator::atomic`].");
 create_closure_decorator!(Finally, "See [`Combinator::finally`].");
+create_closure_decorator!(Emit, "See [`Combinator::emit`].");
+create_generic_value_decorator!(Counted, "See [`Combinator::counted`].");
+create_generic_value_decorator!(Expect, "See [`Combinator::expect`].");
+create_generic_value_decorator!(Memo, "See [`Combinator::memo`].");
+create_simple_decorator!(Track, "See [`Combinator::track`].");
+create_simple_decorator!(Interned, "See [`Combinator::interned`].");
+
+delegate_first_set!(
+  Atomic, Track, Interned;
+  Prepare, Then, Catch, Finally, Emit, Counted, Expect, Memo
+);
+
+macro_rules! create_projection_decorator {
+  ($name:ident, $usage:literal) => {
+    #[doc = $usage]
+    pub struct $name<T, F, Outer> {
+      action: T,
+      get: F,
+      _phantom: PhantomData<Outer>,
+    }
+
+    impl<T, F, Outer> $name<T, F, Outer> {
+      #[inline]
+      const fn new(action: T, get: F) -> Self {
+        Self {
+          action,
+          get,
+          _phantom: PhantomData,
+        }
+      }
+    }
+
+    impl<T: Clone, F: Clone, Outer> Clone for $name<T, F, Outer> {
+      #[inline]
+      fn clone(&self) -> Self {
+        Self::new(self.action.clone(), self.get.clone())
+      }
+    }
+
+    impl<T: Copy, F: Copy, Outer> Copy for $name<T, F, Outer> {}
+
+    impl<T: Debug, F, Outer> Debug for $name<T, F, Outer> {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(stringify!($name))
+          .field("action", &self.action)
+          .finish()
+      }
+    }
+
+    impl<T: $crate::combinator::Describe, F, Outer> $crate::combinator::Describe
+      for $name<T, F, Outer>
+    {
+      fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        f.write_str(" ")?;
+        self.action.describe(f)?;
+        f.write_str(" <fn>)")
+      }
+    }
+  };
+}
+
+create_projection_decorator!(MapState, "See [`Combinator::map_state`].");
+create_projection_decorator!(MapHeap, "See [`Combinator::map_heap`].");
 
 unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>)> Action
   for Prepare<T, D>
@@ -19,13 +94,15 @@ unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::He
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
+  ) -> Result<Output<Self::Value>, Rejection> {
     (self.inner)(input.reborrow());
-    self.action.exec(input)
+    self.action.exec_cut(input)
   }
 }
 
@@ -39,12 +116,14 @@ unsafe impl<
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
-    self.action.exec(input.reborrow()).inspect(|output| {
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(input.reborrow()).inspect(|output| {
       (self.inner)(unsafe {
         Accepted::new_unchecked(input.instant, output.as_ref(), input.state, input.heap)
       });
@@ -60,19 +139,47 @@ unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::He
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
-    let output = self.action.exec(input.reborrow());
-    if output.is_none() {
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow());
+    if output.is_err() {
       (self.inner)(input);
     }
     output
   }
 }
 
+unsafe impl<T: Action<State: Clone>> Action for Atomic<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let snapshot = input.state.clone();
+    let output = self.action.exec_cut(Input {
+      instant: input.instant,
+      state: input.state,
+      heap: input.heap,
+    });
+    if output.is_err() {
+      *input.state = snapshot;
+    }
+    output
+  }
+}
+
 unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>)> Action
   for Finally<T, D>
 {
@@ -81,17 +188,213 @@ unsafe impl<T: Action, D: Fn(Input<&Instant<&T::Text>, &mut T::State, &mut T::He
   type Heap = T::Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
-    let output = self.action.exec(input.reborrow());
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow());
     (self.inner)(input);
     output
   }
 }
 
+unsafe impl<
+    T: Action<Text: Digest, Heap: BorrowMut<Diagnostics>>,
+    D: Fn(Accepted<&Instant<&T::Text>, &mut T::State, &mut T::Heap, &T::Value>) -> Diag,
+  > Action for Emit<T, D>
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(input.reborrow()).inspect(|output| {
+      let diag = (self.inner)(unsafe {
+        Accepted::new_unchecked(input.instant, output.as_ref(), input.state, input.heap)
+      });
+      input.heap.borrow_mut().push(diag);
+    })
+  }
+}
+
+unsafe impl<T: Action<Heap: BorrowMut<BranchStats>>> Action for Counted<T, usize> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input.reborrow())
+      .inspect(|_| input.heap.borrow_mut().record(self.inner))
+  }
+}
+
+unsafe impl<T: Action<Text: Digest, Value: Clone + 'static, Heap: BorrowMut<MemoTable>>> Action
+  for Memo<T, usize>
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let offset = input.instant.digested();
+    let table = input.heap.borrow_mut();
+    table.sync(input.instant.text());
+    match table.get(self.inner, offset) {
+      // the text may be mutated in place without clearing the table,
+      // never return an output which can't be digested
+      Some(Ok(output)) if !input.instant.rest().validate(output.digested) => {}
+      Some(result) => return result,
+      None => {}
+    }
+    let output = self.action.exec_cut(input.reborrow());
+    input
+      .heap
+      .borrow_mut()
+      .insert(self.inner, offset, output.clone());
+    output
+  }
+}
+
+unsafe impl<T: Action<Heap: BorrowMut<Expectations>>> Action for Expect<T, &'static str> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow());
+    if output.is_err() {
+      input
+        .heap
+        .borrow_mut()
+        .record(input.instant.digested(), self.inner);
+    }
+    output
+  }
+}
+
+unsafe impl<T: Action<Text = str, Heap: BorrowMut<Interner>>> Action for Interned<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = Symbol;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow())?;
+    let rest = input.instant.rest();
+    // SAFETY: `output.digested` is valid according to the safety requirement of `Action`
+    let content = idx::unchecked!(idx::slice_to(rest, output.digested));
+    Ok(Output {
+      value: input.heap.borrow_mut().intern(content),
+      digested: output.digested,
+    })
+  }
+}
+
+unsafe impl<T: Action<Heap: BorrowMut<Expectations>>> Action for Track<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow());
+    if output.is_err() {
+      input
+        .heap
+        .borrow_mut()
+        .record_offset(input.instant.digested());
+    }
+    output
+  }
+}
+
+unsafe impl<T: Action, F: Fn(&mut Outer) -> &mut T::State, Outer> Action for MapState<T, F, Outer> {
+  type Text = T::Text;
+  type State = Outer;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(Input {
+      instant: input.instant,
+      state: (self.get)(input.state),
+      heap: input.heap,
+    })
+  }
+}
+
+unsafe impl<T: Action, F: Fn(&mut Outer) -> &mut T::Heap, Outer> Action for MapHeap<T, F, Outer> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = Outer;
+  type Value = T::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(Input {
+      instant: input.instant,
+      state: input.state,
+      heap: (self.get)(input.heap),
+    })
+  }
+}
+
 impl<T> Combinator<T> {
   /// Create a new combinator to modify [`Input::state`] and [`Input::heap`]
   /// before being executed.
@@ -156,6 +459,29 @@ impl<T> Combinator<T> {
     Combinator::new(Catch::new(self.action, modifier))
   }
 
+  /// Create a new combinator to restore [`Input::state`] if the combinator is rejected,
+  /// so the mutations made by a partial match won't leak into other branches.
+  ///
+  /// The state is cloned before each execution.
+  /// [`Input::heap`] is not restored.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::contextual, parser::Parser};
+  /// contextual!(i32, ());
+  ///
+  /// // without `atomic`, the state would be 1 when executing `eat('c')`
+  /// let entry = (eat('a').then(|a| *a.state = 1) + 'b').atomic()
+  ///   | eat('c').reject(|a| *a.state != 0);
+  /// assert!(Parser::builder().state(0).entry(entry).build("c").next().is_some());
+  /// ```
+  #[inline]
+  pub fn atomic(self) -> Combinator<Atomic<T>>
+  where
+    T: Action<State: Clone>,
+  {
+    Combinator::new(Atomic::new(self.action))
+  }
+
   /// Create a new combinator to modify [`Input::state`] and [`Input::heap`]
   /// after the combinator is executed,
   /// no matter whether it is accepted or rejected.
@@ -177,6 +503,202 @@ impl<T> Combinator<T> {
   {
     Combinator::new(Finally::new(self.action, modifier))
   }
+
+  /// Create a new combinator to push a [`Diag`] into the [`Diagnostics`]
+  /// in [`Input::heap`] after being accepted.
+  /// See [`diagnostic`](crate::diagnostic) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, diagnostic::{Diag, Diagnostics, Severity}};
+  /// # fn t(combinator: Combinator<impl Action<Text=str, Heap=Diagnostics>>) {
+  /// combinator.emit(|accepted| Diag {
+  ///   range: accepted.range(),
+  ///   severity: Severity::Error,
+  ///   code: None,
+  ///   message: "unexpected token".into(),
+  /// })
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn emit<F: Fn(Accepted<&Instant<&T::Text>, &mut T::State, &mut T::Heap, &T::Value>) -> Diag>(
+    self,
+    f: F,
+  ) -> Combinator<Emit<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(Emit::new(self.action, f))
+  }
+
+  /// Create a new combinator to count how many times it is accepted
+  /// into the [`BranchStats`] in [`Input::heap`], as the `branch`.
+  /// This is usually used to mark branches of a `|` chain.
+  /// See [`stats`](crate::stats) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, stats::BranchStats};
+  /// # fn t(
+  /// #   a: Combinator<impl Action<Text=str, Heap=BranchStats>>,
+  /// #   b: Combinator<impl Action<Text=str, Heap=BranchStats>>
+  /// # ) {
+  /// a.counted(0) | b.counted(1)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn counted(self, branch: usize) -> Combinator<Counted<T, usize>>
+  where
+    T: Action,
+  {
+    Combinator::new(Counted::new(self.action, branch))
+  }
+
+  /// Create a new combinator to record the `label` with the start offset
+  /// into the [`Expectations`] in [`Input::heap`] when it rejects.
+  /// Use [`Parser::next_verbose`](crate::parser::Parser::next_verbose)
+  /// to get the labels at the furthest offset.
+  /// See [`expect`](crate::expect) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, expect::Expectations};
+  /// # fn t(
+  /// #   a: Combinator<impl Action<Text=str, Heap=Expectations>>,
+  /// #   b: Combinator<impl Action<Text=str, Heap=Expectations>>
+  /// # ) {
+  /// a.expect("identifier") | b.expect("number")
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn expect(self, label: &'static str) -> Combinator<Expect<T, &'static str>>
+  where
+    T: Action,
+  {
+    Combinator::new(Expect::new(self.action, label))
+  }
+
+  /// Create a new combinator to record the start offset without a label
+  /// into the [`Expectations`] in [`Input::heap`] when it rejects.
+  /// Use [`Parser::furthest_failure`](crate::parser::Parser::furthest_failure)
+  /// to get the furthest offset.
+  /// See [`expect`](crate::expect) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, expect::Expectations};
+  /// # fn t(
+  /// #   a: Combinator<impl Action<Text=str, Heap=Expectations>>,
+  /// #   b: Combinator<impl Action<Text=str, Heap=Expectations>>
+  /// # ) {
+  /// a.track() | b.track()
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn track(self) -> Combinator<Track<T>>
+  where
+    T: Action,
+  {
+    Combinator::new(Track::new(self.action))
+  }
+
+  /// Create a new combinator to intern the digested text
+  /// into the [`Interner`] in [`Input::heap`],
+  /// and use the [`Symbol`] as [`Output::value`].
+  ///
+  /// The original [`Output::value`] is discarded.
+  /// Only the first occurrence of a text allocates.
+  /// See [`intern`](crate::intern) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, intern::Interner};
+  /// # fn t(ident: Combinator<impl Action<Text=str, Heap=Interner>>) {
+  /// ident.interned()
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn interned(self) -> Combinator<Interned<T>>
+  where
+    T: Action,
+  {
+    Combinator::new(Interned::new(self.action))
+  }
+
+  /// Create a new combinator to memoize its results by offset
+  /// into the [`MemoTable`] in [`Input::heap`], as the combinator `id`.
+  /// Each memoized combinator should have a unique `id`.
+  ///
+  /// The original combinator is executed at most once per offset,
+  /// the result is cloned on cache hits.
+  /// The table is cleared automatically for a different text,
+  /// but must be cleared manually if the text is mutated in place.
+  /// See [`memo`](crate::memo) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator, memo::MemoTable};
+  /// # fn t(
+  /// #   a: Combinator<impl Action<Text=str, Heap=MemoTable>>,
+  /// #   b: Combinator<impl Action<Text=str, Heap=MemoTable>>
+  /// # ) {
+  /// a.memo(0) | b.memo(1)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn memo(self, id: usize) -> Combinator<Memo<T, usize>>
+  where
+    T: Action,
+  {
+    Combinator::new(Memo::new(self.action, id))
+  }
+
+  /// Create a new combinator to use a different `State` type,
+  /// by projecting the outer state into the state of the original combinator with `get`.
+  ///
+  /// This is useful to embed a sub-grammar written against its own state
+  /// into a parser with a larger state.
+  /// For sub-grammars with `State = ()` and `Heap = ()`,
+  /// [`Contextual`](crate::combinator::Contextual) can be used instead.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// struct MyState {
+  ///   depth: usize,
+  /// }
+  /// # fn t(combinator: Combinator<impl Action<State = usize>>) {
+  /// combinator.map_state(|s: &mut MyState| &mut s.depth)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn map_state<Outer, F: Fn(&mut Outer) -> &mut T::State>(
+    self,
+    get: F,
+  ) -> Combinator<MapState<T, F, Outer>>
+  where
+    T: Action,
+  {
+    Combinator::new(MapState::new(self.action, get))
+  }
+
+  /// Create a new combinator to use a different `Heap` type,
+  /// by projecting the outer heap into the heap of the original combinator with `get`.
+  ///
+  /// See [`Combinator::map_state`] for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// struct MyHeap {
+  ///   names: Vec<String>,
+  /// }
+  /// # fn t(combinator: Combinator<impl Action<Heap = Vec<String>>>) {
+  /// combinator.map_heap(|h: &mut MyHeap| &mut h.names)
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn map_heap<Outer, F: Fn(&mut Outer) -> &mut T::Heap>(
+    self,
+    get: F,
+  ) -> Combinator<MapHeap<T, F, Outer>>
+  where
+    T: Action,
+  {
+    Combinator::new(MapHeap::new(self.action, get))
+  }
 }
 
 #[cfg(test)]
@@ -185,7 +707,7 @@ mod tests {
   use crate::{contextual, digest::Digest, instant::Instant};
   use std::{fmt::Debug, ops::RangeFrom, slice::SliceIndex};
 
-  #[derive(Debug, Default, PartialEq, Eq)]
+  #[derive(Debug, Default, Clone, PartialEq, Eq)]
   pub struct State {
     from: i32,
     to: i32,
@@ -405,6 +927,51 @@ mod tests {
     let _c = c.clone();
   }
 
+  #[test]
+  fn combinator_atomic() {
+    // `a` mutates the state, `b` rejects, `c` records the state
+    let a = || accepter().then(|accepted| accepted.state.from = 1);
+    let b = rejecter;
+    let c = || {
+      wrap(|input| {
+        input.state.to = input.state.from + 10;
+        input.instant.accept(1)
+      })
+    };
+
+    // without atomic, the mutation leaks into `c`
+    let mut state = State::default();
+    helper((a() + b()) | c(), "123", &mut state, Some(1));
+    assert_eq!(state, State { from: 1, to: 11 });
+
+    // with atomic, `c` sees the original state
+    let mut state = State::default();
+    helper((a() + b()).atomic() | c(), "123", &mut state, Some(1));
+    assert_eq!(state, State { from: 0, to: 10 });
+
+    // accepted, the state is not restored
+    let mut state = State::default();
+    helper(a().atomic(), "123", &mut state, Some(1));
+    assert_eq!(state, State { from: 1, to: 0 });
+
+    // bytes
+    let mut state = State::default();
+    helper(
+      (accepter_bytes().then(|accepted| accepted.state.from = 1) + rejecter_bytes()).atomic(),
+      b"123",
+      &mut state,
+      None,
+    );
+    assert_eq!(state, State::default());
+
+    // debug
+    let _ = format!("{:?}", accepter().atomic());
+    // copy & clone
+    let c = accepter().atomic();
+    let _c = c;
+    let _c = c.clone();
+  }
+
   #[test]
   fn combinator_finally() {
     // accepted
@@ -458,4 +1025,357 @@ mod tests {
     let _c = c;
     let _c = c.clone();
   }
+
+  #[test]
+  fn combinator_emit() {
+    use crate::diagnostic::Severity;
+    contextual!((), Diagnostics);
+
+    let c = take(1).emit(|accepted| Diag {
+      range: accepted.range(),
+      severity: Severity::Error,
+      code: None,
+      message: accepted.content().into(),
+    });
+
+    let mut heap = Diagnostics::new();
+    for digested in [0, 1, 0] {
+      assert_eq!(
+        c.exec(Input {
+          instant: &unsafe { Instant::new("123").to_digested_unchecked(digested) },
+          state: &mut (),
+          heap: &mut heap,
+        })
+        .map(|o| o.digested),
+        Some(1)
+      );
+    }
+    // the duplicated diag is ignored
+    assert_eq!(
+      heap
+        .diags()
+        .iter()
+        .map(|d| (d.range.clone(), d.message.as_str()))
+        .collect::<Vec<_>>(),
+      vec![(0..1, "1"), (1..2, "2")]
+    );
+
+    // rejected, no diag
+    let mut heap = Diagnostics::new();
+    assert!(c
+      .exec(Input {
+        instant: &Instant::new(""),
+        state: &mut (),
+        heap: &mut heap,
+      })
+      .is_none());
+    assert!(heap.is_empty());
+
+    // debug
+    let _ = format!("{:?}", c);
+  }
+
+  #[test]
+  fn combinator_counted() {
+    contextual!((), BranchStats);
+
+    let c = eat('a').counted(0) | eat('b').counted(2);
+    let mut heap = BranchStats::new();
+    for text in ["a", "b", "b", "c"] {
+      c.exec(Input {
+        instant: &Instant::new(text),
+        state: &mut (),
+        heap: &mut heap,
+      });
+    }
+    assert_eq!(heap.counts(), &[1, 0, 2]);
+
+    // debug
+    let _ = format!("{:?}", c);
+    // copy & clone
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_expect() {
+    contextual!((), Expectations);
+
+    let c = eat('a').expect("a") | eat('b').expect("b");
+    let exec = |text: &str, heap: &mut Expectations| {
+      c.exec(Input {
+        instant: &Instant::new(text),
+        state: &mut (),
+        heap,
+      })
+      .map(|o| o.digested)
+    };
+
+    // accepted, the rejected branch is still recorded
+    let mut heap = Expectations::new();
+    assert_eq!(exec("b", &mut heap), Some(1));
+    assert_eq!(heap.offset(), Some(0));
+    assert_eq!(heap.labels(), ["a"]);
+    heap.clear();
+    assert_eq!(exec("a", &mut heap), Some(1));
+    assert_eq!(heap.offset(), None);
+    // rejected, both branches are recorded
+    assert_eq!(exec("c", &mut heap), None);
+    assert_eq!(heap.offset(), Some(0));
+    assert_eq!(heap.labels(), ["a", "b"]);
+
+    // the start offset is recorded, not the offset where the inner combinator failed
+    let c = (eat("ab") + eat('c').expect("c")).expect("abc");
+    let mut heap = Expectations::new();
+    assert!(c
+      .exec(Input {
+        instant: &Instant::new("abd"),
+        state: &mut (),
+        heap: &mut heap,
+      })
+      .is_none());
+    assert_eq!(heap.offset(), Some(2));
+    assert_eq!(heap.labels(), ["c"]);
+
+    // debug
+    let _ = format!("{:?}", c);
+    // copy & clone
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_interned() {
+    contextual!((), Interner);
+
+    let c = (next(|c| c.is_ascii_alphabetic()) * (1..)).interned();
+    let mut heap = Interner::new();
+    let mut exec = |text: &str| {
+      c.exec(Input {
+        instant: &Instant::new(text),
+        state: &mut (),
+        heap: &mut heap,
+      })
+      .map(|o| (o.value, o.digested))
+    };
+    let (foo, digested) = exec("foo bar").unwrap();
+    assert_eq!(digested, 3);
+    let (bar, _) = exec("bar").unwrap();
+    assert_ne!(foo, bar);
+    assert_eq!(exec("foo1"), Some((foo, 3)));
+    assert_eq!(exec("1"), None);
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.resolve(bar), Some("bar"));
+
+    // debug
+    let _ = format!("{:?}", c);
+    // copy & clone
+    let c = eat("a").interned();
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_track() {
+    contextual!((), Expectations);
+
+    // the left branch fails deep, the right branch fails shallow
+    let c = (eat("ab").track() + eat('c').track()) | eat('x').track();
+    let exec = |text: &str, heap: &mut Expectations| {
+      c.exec(Input {
+        instant: &Instant::new(text),
+        state: &mut (),
+        heap,
+      })
+      .map(|o| o.digested)
+    };
+
+    let mut heap = Expectations::new();
+    assert_eq!(exec("abd", &mut heap), None);
+    assert_eq!(heap.offset(), Some(2));
+    assert!(heap.labels().is_empty());
+    // accepted, the rejected branch is still recorded
+    heap.clear();
+    assert_eq!(exec("x", &mut heap), Some(1));
+    assert_eq!(heap.offset(), Some(0));
+    heap.clear();
+    assert_eq!(exec("abc", &mut heap), Some(3));
+    assert_eq!(heap.offset(), None);
+
+    // debug
+    let _ = format!("{:?}", c);
+    // copy & clone
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_memo() {
+    contextual!(usize, MemoTable);
+
+    // count executions via the state
+    let number = || {
+      (next(|c| c.is_ascii_digit()) * (1..))
+        .prepare(|input| *input.state += 1)
+        .select(|a| a.content().parse::<u32>().unwrap())
+    };
+    fn sum(
+      a: Combinator<impl Action<Text = str, State = usize, Heap = MemoTable, Value = u32> + Clone>,
+      b: Combinator<impl Action<Text = str, State = usize, Heap = MemoTable, Value = u32> + Clone>,
+    ) -> Combinator<impl Action<Text = str, State = usize, Heap = MemoTable, Value = u32>> {
+      // the left number is executed at the same offset in all branches
+      (a.clone().tuple() + eat('+') + b.clone().tuple()).map(|(a, b)| a + b)
+        | (a.clone().tuple() + eat('-') + b.tuple()).map(|(a, b)| a - b)
+        | a
+    }
+    let memoized = sum(number().memo(0), number().memo(1));
+    let plain = sum(number(), number());
+
+    let exec = |c: &dyn Action<Text = str, State = usize, Heap = MemoTable, Value = u32>,
+                text: &str,
+                heap: &mut MemoTable| {
+      let mut state = 0;
+      let output = c
+        .exec(Input {
+          instant: &Instant::new(text),
+          state: &mut state,
+          heap,
+        })
+        .map(|o| (o.value, o.digested));
+      (output, state)
+    };
+
+    for text in ["12+3", "12-3", "12", "12*3", "a"] {
+      let mut heap = MemoTable::new();
+      let (memoized_output, memoized_count) = exec(&memoized, text, &mut heap);
+      let (plain_output, plain_count) = exec(&plain, text, &mut MemoTable::new());
+      // identical results
+      assert_eq!(memoized_output, plain_output);
+      // executed at most once per offset
+      assert!(memoized_count <= 2);
+      assert!(plain_count >= memoized_count);
+    }
+
+    // the left number is executed once instead of three times
+    let mut heap = MemoTable::new();
+    assert_eq!(exec(&memoized, "12", &mut heap), (Some((12, 2)), 1));
+    assert_eq!(
+      exec(&plain, "12", &mut MemoTable::new()),
+      (Some((12, 2)), 3)
+    );
+    assert_eq!(heap.len(), 1);
+
+    // rejections are memoized too
+    let mut heap = MemoTable::new();
+    assert_eq!(exec(&memoized, "a", &mut heap), (None, 1));
+
+    // reuse the buffer with different content of the same length,
+    // the results are stale until the table is cleared
+    let mut heap = MemoTable::new();
+    let mut buffer = String::from("12");
+    assert_eq!(exec(&memoized, &buffer, &mut heap), (Some((12, 2)), 1));
+    buffer.clear();
+    buffer.push_str("34");
+    assert_eq!(exec(&memoized, &buffer, &mut heap), (Some((12, 2)), 0));
+    heap.clear();
+    assert_eq!(exec(&memoized, &buffer, &mut heap), (Some((34, 2)), 1));
+
+    // the table is cleared for a different text
+    let mut heap = MemoTable::new();
+    assert_eq!(exec(&memoized, "12345", &mut heap), (Some((12345, 5)), 1));
+    assert_eq!(exec(&memoized, "1", &mut heap), (Some((1, 1)), 1));
+
+    // stale outputs which can't be digested are re-executed
+    let mut heap = MemoTable::new();
+    let mut buffer = String::from("1+");
+    assert_eq!(exec(&memoized, &buffer, &mut heap), (Some((1, 1)), 2));
+    buffer.clear();
+    buffer.push('é');
+    assert_eq!(exec(&memoized, &buffer, &mut heap), (None, 1));
+
+    // debug
+    let c = eat('a').memo(0);
+    let _ = format!("{:?}", c);
+    // copy & clone
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[derive(Debug, Default)]
+  struct Outer {
+    items: usize,
+    unit: (),
+    done: bool,
+  }
+
+  #[derive(Debug, Default)]
+  struct OuterHeap {
+    starts: Vec<usize>,
+    unit: (),
+  }
+
+  #[test]
+  fn combinator_map_state_heap() {
+    mod sub {
+      use crate::{
+        action::Action,
+        combinator::{contextual, Combinator},
+      };
+
+      contextual!(usize, Vec<usize>);
+
+      // a sub-grammar with its own state and heap
+      pub fn item(
+      ) -> Combinator<impl Action<Text = str, State = usize, Heap = Vec<usize>, Value = ()>> {
+        eat('a').then(|accepted| {
+          *accepted.state += 1;
+          accepted.heap.push(accepted.start());
+        })
+      }
+    }
+
+    contextual!(Outer, OuterHeap);
+
+    let c = (sub::item()
+      .map_state(|s: &mut Outer| &mut s.items)
+      .map_heap(|h: &mut OuterHeap| &mut h.starts)
+      * (1..))
+      // a stateless sub-grammar
+      + crate::combinator::eat(';')
+        .map_state(|s: &mut Outer| &mut s.unit)
+        .map_heap(|h: &mut OuterHeap| &mut h.unit)
+      // the stateful part observes the mutations of the sub-grammar
+      + eat("").then(|accepted| accepted.state.done = accepted.state.items == 2);
+
+    let mut state = Outer::default();
+    let mut heap = OuterHeap::default();
+    let output = c
+      .exec(Input {
+        instant: &Instant::new("aa;"),
+        state: &mut state,
+        heap: &mut heap,
+      })
+      .unwrap();
+    assert_eq!(output.digested, 3);
+    assert_eq!(state.items, 2);
+    assert!(state.done);
+    assert_eq!(heap.starts, vec![0, 1]);
+  }
+
+  fn _map_state_heap_debug() {
+    let _ = format!(
+      "{:?}",
+      crate::combinator::eat('a').map_state(|s: &mut Outer| &mut s.unit)
+    );
+    let _ = format!(
+      "{:?}",
+      crate::combinator::eat('a').map_heap(|h: &mut OuterHeap| &mut h.unit)
+    );
+  }
+
+  fn _map_state_heap_clone_copy() {
+    let c = crate::combinator::eat('a').map_state(|s: &mut Outer| &mut s.unit);
+    let _c = c;
+    let _c = c.clone();
+  }
 }
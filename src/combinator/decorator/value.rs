@@ -1,21 +1,33 @@
 use super::{
-  create_closure_decorator, create_generic_value_decorator, create_simple_decorator, Accepted,
+  create_closure_decorator, create_generic_value_decorator, create_simple_decorator,
+  delegate_first_set, Accepted,
 };
 use crate::{
-  action::{Action, Input, Output},
+  action::{Action, Input, Output, Rejection},
   combinator::Combinator,
   digest::Digest,
+  idx,
   instant::Instant,
   range::WithRange,
 };
+use std::{ops::RangeTo, slice::SliceIndex};
 
 create_closure_decorator!(Map, "See [`Combinator::map`].");
 create_simple_decorator!(Tuple, "See [`Combinator::tuple`].");
 create_generic_value_decorator!(Bind, "See [`Combinator::bind`].");
 create_closure_decorator!(BindWith, "See [`Combinator::bind_with`].");
 create_closure_decorator!(Select, "See [`Combinator::select`].");
+create_closure_decorator!(TryMap, "See [`Combinator::try_map`].");
+create_closure_decorator!(TrySelect, "See [`Combinator::try_select`].");
 create_simple_decorator!(Range, "See [`Combinator::range`].");
+create_simple_decorator!(Ranged, "See [`Combinator::ranged`].");
 create_simple_decorator!(Pop, "See [`Combinator::pop`].");
+create_simple_decorator!(Recognize, "See [`Combinator::recognize`].");
+
+delegate_first_set!(
+  Tuple, Range, Ranged, Pop, Recognize;
+  Map, Bind, BindWith, Select, TryMap, TrySelect
+);
 
 unsafe impl<NewValue, T: Action, D: Fn(T::Value) -> NewValue> Action for Map<T, D> {
   type Text = T::Text;
@@ -33,6 +45,17 @@ unsafe impl<NewValue, T: Action, D: Fn(T::Value) -> NewValue> Action for Map<T,
       .exec(input)
       .map(|output| output.map(&self.inner))
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .map(|output| output.map(&self.inner))
+  }
 }
 
 unsafe impl<T: Action> Action for Tuple<T> {
@@ -48,6 +71,17 @@ unsafe impl<T: Action> Action for Tuple<T> {
   ) -> Option<Output<Self::Value>> {
     self.action.exec(input).map(|output| output.map(|v| (v,)))
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .map(|output| output.map(|v| (v,)))
+  }
 }
 
 unsafe impl<T: Action, D: Clone> Action for Bind<T, D> {
@@ -66,6 +100,17 @@ unsafe impl<T: Action, D: Clone> Action for Bind<T, D> {
       .exec(input)
       .map(|output| output.map(|_| self.inner.clone()))
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .map(|output| output.map(|_| self.inner.clone()))
+  }
 }
 
 unsafe impl<T: Action, NewValue, D: Fn() -> NewValue> Action for BindWith<T, D> {
@@ -84,6 +129,17 @@ unsafe impl<T: Action, NewValue, D: Fn() -> NewValue> Action for BindWith<T, D>
       .exec(input)
       .map(|output| output.map(|_| (self.inner)()))
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .map(|output| output.map(|_| (self.inner)()))
+  }
 }
 
 unsafe impl<
@@ -109,6 +165,91 @@ unsafe impl<
       }),
     })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(input.reborrow()).map(|output| Output {
+      digested: output.digested,
+      value: (self.inner)(unsafe {
+        Accepted::new_unchecked(input.instant, output, input.state, input.heap)
+      }),
+    })
+  }
+}
+
+unsafe impl<NewValue, T: Action, D: Fn(T::Value) -> Option<NewValue>> Action for TryMap<T, D> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = NewValue;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.action.exec(input).and_then(|output| {
+      (self.inner)(output.value).map(|value| Output {
+        value,
+        digested: output.digested,
+      })
+    })
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input)?;
+    (self.inner)(output.value)
+      .map(|value| Output {
+        value,
+        digested: output.digested,
+      })
+      .ok_or(Rejection::Backtrack)
+  }
+}
+
+unsafe impl<
+    NewValue,
+    T: Action<Text: Digest>,
+    D: Fn(Accepted<&Instant<&T::Text>, &mut T::State, &mut T::Heap, T::Value>) -> Option<NewValue>,
+  > Action for TrySelect<T, D>
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = NewValue;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.action.exec(input.reborrow()).and_then(|output| {
+      let digested = output.digested;
+      (self.inner)(unsafe {
+        Accepted::new_unchecked(input.instant, output, input.state, input.heap)
+      })
+      .map(|value| Output { value, digested })
+    })
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.action.exec_cut(input.reborrow())?;
+    let digested = output.digested;
+    (self.inner)(unsafe { Accepted::new_unchecked(input.instant, output, input.state, input.heap) })
+      .map(|value| Output { value, digested })
+      .ok_or(Rejection::Backtrack)
+  }
 }
 
 unsafe impl<T: Action> Action for Range<T> {
@@ -127,11 +268,70 @@ unsafe impl<T: Action> Action for Range<T> {
       let digested = output.digested;
       debug_assert!(usize::MAX - start >= digested);
       output.map(|data| WithRange {
-        range: start..unsafe { start.unchecked_add(digested) },
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        range: start..idx::unchecked!(idx::add(start, digested)),
         data,
       })
     })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let start = input.instant.digested();
+    self.action.exec_cut(input).map(|output| {
+      let digested = output.digested;
+      debug_assert!(usize::MAX - start >= digested);
+      output.map(|data| WithRange {
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        range: start..idx::unchecked!(idx::add(start, digested)),
+        data,
+      })
+    })
+  }
+}
+
+unsafe impl<T: Action> Action for Ranged<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = crate::range::Ranged<T::Value>;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let start = input.instant.digested();
+    self.action.exec(input).map(|output| {
+      let digested = output.digested;
+      debug_assert!(usize::MAX - start >= digested);
+      output.map(|value| crate::range::Ranged {
+        value,
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        range: start..idx::unchecked!(idx::add(start, digested)),
+      })
+    })
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let start = input.instant.digested();
+    self.action.exec_cut(input).map(|output| {
+      let digested = output.digested;
+      debug_assert!(usize::MAX - start >= digested);
+      output.map(|value| crate::range::Ranged {
+        value,
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        range: start..idx::unchecked!(idx::add(start, digested)),
+      })
+    })
+  }
 }
 
 unsafe impl<V, T: Action<Value = (V,)>> Action for Pop<T> {
@@ -147,6 +347,57 @@ unsafe impl<V, T: Action<Value = (V,)>> Action for Pop<T> {
   ) -> Option<Output<Self::Value>> {
     self.action.exec(input).map(|output| output.map(|(v,)| v))
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self
+      .action
+      .exec_cut(input)
+      .map(|output| output.map(|(v,)| v))
+  }
+}
+
+unsafe impl<T: Action<Text: Digest + ToOwned>> Action for Recognize<T>
+where
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = <T::Text as ToOwned>::Owned;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    self.action.exec(input).map(|output| {
+      Output {
+        // SAFETY: `output.digested` is valid according to the safety requirement of `Action`
+        value: idx::unchecked!(idx::slice_to(rest, output.digested)).to_owned(),
+        digested: output.digested,
+      }
+    })
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let rest = input.instant.rest();
+    self.action.exec_cut(input).map(|output| {
+      Output {
+        // SAFETY: `output.digested` is valid according to the safety requirement of `Action`
+        value: idx::unchecked!(idx::slice_to(rest, output.digested)).to_owned(),
+        digested: output.digested,
+      }
+    })
+  }
 }
 
 impl<T> Combinator<T> {
@@ -168,6 +419,33 @@ impl<T> Combinator<T> {
     Combinator::new(Map::new(self.action, mapper))
   }
 
+  /// Create a new combinator to convert [`Output::value`] to a new value,
+  /// or reject if the `mapper` returns [`None`].
+  ///
+  /// Like [`Self::map`], but the conversion can fail,
+  /// e.g. a number overflows. The rejection is a normal one
+  /// even if the original combinator is [`cut`](Self::cut), so the next branch of the surrounding `|` will be tried.
+  ///
+  /// State changes made by the original combinator are not reverted
+  /// when the `mapper` rejects, use [`Self::atomic`] if needed.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action<Value = u32>>) {
+  /// combinator.try_map(|value| u8::try_from(value).ok())
+  /// # ;}
+  /// ```
+  #[inline]
+  pub fn try_map<NewValue, F: Fn(T::Value) -> Option<NewValue>>(
+    self,
+    mapper: F,
+  ) -> Combinator<TryMap<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(TryMap::new(self.action, mapper))
+  }
+
   /// Create a new combinator to wrap [`Output::value`] in an one-element tuple.
   ///
   /// This is useful when you use `+` to combine multiple combinators.
@@ -219,6 +497,12 @@ impl<T> Combinator<T> {
   }
 
   /// Create a new combinator to set [`Output::value`] with the provided factory.
+  ///
+  /// The factory is called once per acceptance and never on rejection,
+  /// so the value doesn't need to implement [`Clone`]
+  /// and the construction cost is only paid when the combinator accepts.
+  /// Prefer [`Self::bind`] for cheap clone-able values,
+  /// and [`Self::select`] if the value depends on the accepted output.
   /// # Examples
   /// ```
   /// # use whitehole::{action::Action, combinator::Combinator};
@@ -234,6 +518,21 @@ impl<T> Combinator<T> {
     Combinator::new(BindWith::new(self.action, factory))
   }
 
+  /// Create a new combinator to set [`Output::value`] to [`Default::default`].
+  ///
+  /// This is a shortcut for `self.bind_with(NewValue::default)`.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{action::Action, combinator::Combinator};
+  /// # fn t(combinator: Combinator<impl Action>) -> Combinator<impl Action<Value=Vec<i32>>> {
+  /// combinator.bind_default::<Vec<i32>>()
+  /// # }
+  /// ```
+  #[inline]
+  pub fn bind_default<NewValue: Default>(self) -> Combinator<BindWith<T, fn() -> NewValue>> {
+    self.bind_with(NewValue::default)
+  }
+
   /// Create a new combinator to set [`Output::value`] by the `selector`.
   ///
   /// Use this if you need to calculate the value based on the [`Instant`],
@@ -261,6 +560,37 @@ impl<T> Combinator<T> {
     Combinator::new(Select::new(self.action, selector))
   }
 
+  /// Create a new combinator to set [`Output::value`] by the `selector`,
+  /// or reject if the `selector` returns [`None`].
+  ///
+  /// Like [`Self::select`], but the conversion can fail,
+  /// e.g. the digested text can't be parsed into the target type.
+  /// The rejection is a normal one even if the original combinator is [`cut`](Self::cut),
+  /// so the next branch of the surrounding `|` will be tried.
+  ///
+  /// State and heap changes made by the original combinator or the `selector`
+  /// are not reverted when the `selector` rejects, use [`Self::atomic`] if needed.
+  /// # Examples
+  /// ```
+  /// # use whitehole::combinator::{next, Combinator};
+  /// // reject if the number overflows
+  /// (next(|c| c.is_ascii_digit()) * (1..)).try_select(|accepted| accepted.content().parse::<u8>().ok())
+  /// # ;
+  /// ```
+  #[inline]
+  pub fn try_select<
+    NewValue,
+    F: Fn(Accepted<&Instant<&T::Text>, &mut T::State, &mut T::Heap, T::Value>) -> Option<NewValue>,
+  >(
+    self,
+    selector: F,
+  ) -> Combinator<TrySelect<T, F>>
+  where
+    T: Action,
+  {
+    Combinator::new(TrySelect::new(self.action, selector))
+  }
+
   /// Create a new combinator to wrap [`Output::value`] in [`WithRange`]
   /// which includes the byte range of the digested text.
   /// # Examples
@@ -273,13 +603,51 @@ impl<T> Combinator<T> {
   pub fn range(self) -> Combinator<Range<T>> {
     Combinator::new(Range::new(self.action))
   }
+
+  /// Create a new combinator to wrap [`Output::value`] in [`Ranged`](crate::range::Ranged)
+  /// which includes the byte range of the digested text.
+  ///
+  /// Unlike [`Combinator::range`], the value can be accessed directly via [`Deref`](std::ops::Deref).
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::next, parser::Parser};
+  /// let digit = next(|c| c.is_ascii_digit()).select(|accepted| accepted.content().parse::<u8>().unwrap());
+  /// let output = Parser::builder().entry(digit.ranged()).build("12").next().unwrap();
+  /// assert_eq!(*output.value, 1);
+  /// assert_eq!(output.value.range, 0..1);
+  /// ```
+  #[inline]
+  pub fn ranged(self) -> Combinator<Ranged<T>> {
+    Combinator::new(Ranged::new(self.action))
+  }
+
+  /// Create a new combinator to discard [`Output::value`]
+  /// and use the digested text as the new value,
+  /// e.g. a [`String`] for `str` and a [`Vec<u8>`] for `[u8]`.
+  ///
+  /// The value is owned because [`Action::Value`] can't borrow the input text.
+  /// To avoid the allocation, use [`Self::range`] to index the text later,
+  /// or [`Accepted::content`] in [`Self::select`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::next, parser::Parser};
+  /// let entry = (next(|c| c.is_ascii_alphabetic()) * (1..)).recognize();
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("abc123").next().unwrap().value,
+  ///   "abc"
+  /// );
+  /// ```
+  #[inline]
+  pub fn recognize(self) -> Combinator<Recognize<T>> {
+    Combinator::new(Recognize::new(self.action))
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::{
-    combinator::{bytes, take},
+    combinator::{bytes, eat, take},
     digest::Digest,
   };
   use std::{fmt::Debug, ops::RangeFrom, slice::SliceIndex};
@@ -368,6 +736,41 @@ mod tests {
     let _c = c.clone();
   }
 
+  #[test]
+  fn combinator_bind_with_lazy() {
+    // non-clone value
+    #[derive(Debug, PartialEq)]
+    struct NotClone(i32);
+    helper(take(1).bind_with(|| NotClone(1)), "123", NotClone(1));
+
+    // the factory is called once per acceptance and never on rejection
+    let calls = std::cell::Cell::new(0);
+    let c = (take(1) * 2).bind_with(|| calls.set(calls.get() + 1));
+    let mut parser = crate::parser::Parser::builder().entry(&c).build("12345");
+    assert_eq!(parser.by_ref().count(), 2);
+    assert_eq!(parser.instant.rest(), "5");
+    assert_eq!(calls.get(), 2);
+    assert!(parser.next().is_none());
+    assert_eq!(calls.get(), 2);
+  }
+
+  #[test]
+  fn combinator_bind_default() {
+    helper(take(1).bind_default::<i32>(), "123", 0);
+    helper(
+      bytes::take(1).bind_default::<Vec<u8>>(),
+      b"123" as &[u8],
+      vec![],
+    );
+
+    // debug
+    let _ = format!("{:?}", take(1).bind_default::<i32>());
+    // copy & clone
+    let c = take(1).bind_default::<i32>();
+    let _c = c;
+    let _c = c.clone();
+  }
+
   #[test]
   fn combinator_select() {
     helper(
@@ -389,6 +792,163 @@ mod tests {
     let _c = c.clone();
   }
 
+  #[test]
+  fn combinator_try_map() {
+    helper(
+      take(1).bind(1u32).try_map(|v| u8::try_from(v).ok()),
+      "1",
+      1u8,
+    );
+    helper(
+      bytes::take(1).bind(1u32).try_map(|v| u8::try_from(v).ok()),
+      b"1" as &[u8],
+      1u8,
+    );
+    assert!(take(1)
+      .bind(256u32)
+      .try_map(|v| u8::try_from(v).ok())
+      .exec(Input {
+        instant: &Instant::new("1"),
+        state: &mut (),
+        heap: &mut ()
+      })
+      .is_none());
+
+    // debug
+    let _ = format!("{:?}", take(1).try_map(Some));
+    // copy & clone
+    let c = take(1).try_map(Some);
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_try_select() {
+    use crate::{combinator::next, contextual};
+
+    fn exec<Value>(
+      action: impl Action<Text = str, State = (), Heap = (), Value = Value>,
+      input: &str,
+    ) -> Option<Output<Value>> {
+      action.exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+    }
+    let digits = || next(|c| c.is_ascii_digit()) * (1..);
+    let small = || digits().try_select(|a| a.content().parse::<u8>().ok());
+
+    // success preserves digested
+    let output = exec(small(), "255,").unwrap();
+    assert_eq!((output.value, output.digested), (255, 3));
+    // overflow rejects
+    assert!(exec(small(), "256").is_none());
+    // and falls through to the next branch
+    let entry =
+      || small().map(u32::from) | digits().try_select(|a| a.content().parse::<u32>().ok());
+    let output = exec(entry(), "256").unwrap();
+    assert_eq!((output.value, output.digested), (256, 3));
+    let output = exec(entry(), "25").unwrap();
+    assert_eq!((output.value, output.digested), (25, 2));
+
+    // a failed selection is a normal rejection even if the original combinator is cut
+    let entry = || {
+      digits()
+        .cut()
+        .try_select(|a| a.content().parse::<u8>().ok().map(u32::from))
+        | next(|_| true).bind(0)
+    };
+    assert_eq!(exec(entry(), "256").unwrap().value, 0);
+    // the cut is still propagated
+    assert!(exec(entry(), "a").is_none());
+
+    // state changes are kept when the selector rejects
+    contextual!(usize, ());
+    let c = eat('a').then(|a| *a.state += 1).try_select(|a| {
+      *a.state += 1;
+      None::<()>
+    });
+    let mut state = 0;
+    assert!(c
+      .exec(Input {
+        instant: &Instant::new("a"),
+        state: &mut state,
+        heap: &mut ()
+      })
+      .is_none());
+    assert_eq!(state, 2);
+
+    // debug
+    let _ = format!("{:?}", take(1).try_select(|_| Some(0i32)));
+    // copy & clone
+    let c = take(1).try_select(|_| Some(0i32));
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_select_offsets() {
+    fn exec<Value: PartialEq + Debug, Text: ?Sized + Digest>(
+      action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+      input: &Text,
+    ) -> Option<Value>
+    where
+      RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+    {
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.value)
+    }
+
+    fn offsets<Text: ?Sized>(
+      a: Accepted<&Instant<&Text>, &mut (), &mut (), ()>,
+    ) -> (usize, usize, std::ops::Range<usize>, usize) {
+      (a.start(), a.end(), a.range(), a.digested())
+    }
+
+    // offsets are relative to the whole text, not the rest
+    assert_eq!(
+      exec(eat("ab") + take(2).select(offsets), "ab12c"),
+      Some((2, 4, 2..4, 2))
+    );
+    // multi-byte chars are counted in bytes
+    assert_eq!(
+      exec(eat("好") + take(1).select(offsets), "好好"),
+      Some((3, 6, 3..6, 3))
+    );
+    assert_eq!(
+      exec(bytes::eat(b"ab") + bytes::take(2).select(offsets), b"ab12c"),
+      Some((2, 4, 2..4, 2))
+    );
+
+    // consistent with `range`
+    assert_eq!(
+      exec(eat("ab") + take(2).range().map(|v| (v.range,)), "ab12c"),
+      exec(eat("ab") + take(2).select(|a| (a.range(),)), "ab12c")
+    );
+    assert_eq!(
+      exec(
+        bytes::eat(b"ab") + bytes::take(2).range().map(|v| (v.range,)),
+        b"ab12c" as &[u8]
+      ),
+      exec(
+        bytes::eat(b"ab") + bytes::take(2).select(|a| (a.range(),)),
+        b"ab12c" as &[u8]
+      )
+    );
+
+    // reject by offsets
+    let entry = eat("ab") + take(2).reject(|a| a.range() != (2..4));
+    assert_eq!(exec(entry, "ab12"), Some(()));
+    let entry = eat("a") + take(2).reject(|a| a.range() != (2..4));
+    assert_eq!(exec(entry, "a12"), None);
+  }
+
   #[test]
   fn combinator_range() {
     helper(
@@ -415,4 +975,169 @@ mod tests {
     let _c = c;
     let _c = c.clone();
   }
+
+  #[test]
+  fn combinator_ranged() {
+    use crate::{combinator::next, range::Ranged};
+
+    helper(
+      take(1).ranged(),
+      "123",
+      Ranged {
+        value: (),
+        range: 0..1,
+      },
+    );
+    helper(
+      bytes::take(1).bind(1).ranged(),
+      b"123" as &[u8],
+      Ranged {
+        value: 1,
+        range: 0..1,
+      },
+    );
+
+    fn exec<Value>(
+      action: impl Action<Text = str, State = (), Heap = (), Value = Value>,
+      input: &str,
+    ) -> Output<Value> {
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .unwrap()
+    }
+    let digit = || {
+      next(|c| c.is_ascii_digit())
+        .select(|accepted| accepted.content().parse::<u8>().unwrap())
+        .ranged()
+    };
+
+    // with `+`, ranges are relative to the start of the whole text
+    let output = exec(digit().tuple() + eat(',') + digit().tuple(), "1,2");
+    assert_eq!(output.digested, 3);
+    let (a, b) = output.value;
+    assert_eq!((*a, a.range), (1, 0..1));
+    assert_eq!((*b, b.range), (2, 2..3));
+
+    // with `*` and array accumulation
+    let output = exec(digit() * <[Ranged<u8>; 3]>::default(), "123");
+    assert_eq!(
+      output.value.map(|r| (r.value, r.range)),
+      [(1, 0..1), (2, 1..2), (3, 2..3)]
+    );
+
+    // nested
+    let output = exec(
+      (eat('[') + (digit() * <[Ranged<u8>; 2]>::default()).ranged().tuple() + eat(']')).ranged(),
+      "[12]",
+    );
+    let outer = output.value;
+    assert_eq!(outer.range, 0..4);
+    let inner = &outer.0;
+    assert_eq!(inner.range, 1..3);
+    assert_eq!((*inner[1], inner[1].range.clone()), (2, 2..3));
+
+    // debug
+    let _ = format!("{:?}", take(1).ranged());
+    // copy & clone
+    let c = take(1).ranged();
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn combinator_recognize() {
+    fn exec<Value: PartialEq + Debug, Text: ?Sized + Digest>(
+      action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+      input: &Text,
+      expected: (Value, usize),
+    ) where
+      RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+    {
+      assert_eq!(
+        action
+          .exec(Input {
+            instant: &Instant::new(input),
+            state: &mut (),
+            heap: &mut ()
+          })
+          .map(|o| (o.value, o.digested)),
+        Some(expected)
+      )
+    }
+
+    exec(take(2).recognize(), "123", ("12".to_string(), 2));
+    exec(take(1).recognize(), "好1", ("好".to_string(), 3));
+    exec(take(0).recognize(), "123", ("".to_string(), 0));
+    exec(
+      bytes::take(2).recognize(),
+      b"123" as &[u8],
+      (b"12".to_vec(), 2),
+    );
+
+    // the original value is discarded
+    helper(take(1).bind(1).recognize(), "123", "1".to_string());
+
+    // in concatenation, each value is the digested text of the item
+    exec(
+      take(1).recognize().tuple() + eat(',') + take(2).recognize().tuple(),
+      "1,23",
+      (("1".to_string(), "23".to_string()), 4),
+    );
+    exec(
+      (take(1) + eat(',') + take(2)).recognize(),
+      "1,23",
+      ("1,23".to_string(), 4),
+    );
+
+    // in repetition with fold
+    exec(
+      (take(1).recognize() * (..))
+        .sep(',')
+        .fold(String::new, |acc, s: String| acc + &s),
+      "1,2,3",
+      ("123".to_string(), 5),
+    );
+    exec(
+      (bytes::take(1).recognize() * 2).fold(Vec::new, |mut acc, v| {
+        acc.extend(v);
+        acc
+      }),
+      b"123" as &[u8],
+      (b"12".to_vec(), 2),
+    );
+
+    // only the rest of the instant is used
+    assert_eq!(
+      take(1)
+        .recognize()
+        .exec(Input {
+          instant: &unsafe { Instant::new("123").to_digested_unchecked(1) },
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      Some(("2".to_string(), 1))
+    );
+
+    // rejected
+    assert!(take(1)
+      .recognize()
+      .exec(Input {
+        instant: &Instant::new(""),
+        state: &mut (),
+        heap: &mut ()
+      })
+      .is_none());
+
+    // debug
+    let _ = format!("{:?}", take(1).recognize());
+    // copy & clone
+    let c = take(1).recognize();
+    let _c = c;
+    let _c = c.clone();
+  }
 }
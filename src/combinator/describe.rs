@@ -0,0 +1,298 @@
+use super::Combinator;
+use std::{
+  fmt::{self, Display},
+  rc::Rc,
+};
+
+/// Describe the structure of an [`Action`](crate::action::Action)
+/// as a concise s-expression, e.g. `(add (eat "true") (mul (next <fn>) 1..))`.
+///
+/// Closures are elided as `<fn>`.
+/// Combinators from [`bytes`](crate::combinator::bytes) and [`u16s`](crate::combinator::u16s)
+/// are prefixed with their module, e.g. `(bytes::eat 97)`.
+/// Chains of `+` and `|` are flattened, e.g. `(bitor (eat 'a') (eat 'b') (eat 'c'))`.
+///
+/// This is implemented for the operators, decorators and basic provided combinators.
+/// Use [`Combinator::describe`] to get the description,
+/// the derived [`Debug`] output is still available via `{:?}`.
+pub trait Describe {
+  /// Write the description into `f`.
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+  /// Write the description into `f`, as an operand of the operator `op`.
+  ///
+  /// The default implementation just calls [`Self::describe`].
+  /// Operators override this to flatten chains of the same operator.
+  #[inline]
+  fn describe_operand(&self, _op: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.describe(f)
+  }
+}
+
+impl<T: Describe + ?Sized> Describe for &T {
+  #[inline]
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (**self).describe(f)
+  }
+}
+
+impl<T: Describe + ?Sized> Describe for Box<T> {
+  #[inline]
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (**self).describe(f)
+  }
+}
+
+impl<T: Describe + ?Sized> Describe for Rc<T> {
+  #[inline]
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (**self).describe(f)
+  }
+}
+
+/// Display the description of a [`Describe`] implementor.
+/// See [`Combinator::describe`].
+struct Description<'a, T: ?Sized>(&'a T);
+
+impl<T: Describe + ?Sized> Display for Description<'_, T> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.describe(f)
+  }
+}
+
+impl<T: Describe> Combinator<T> {
+  /// Describe the structure of the combinator as a concise s-expression.
+  /// See [`Describe`] for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::combinator::{eat, next};
+  /// let entry = eat("true") + (next(|c| c.is_ascii_digit()) * (1..)).select(|a| a.content().len());
+  /// assert_eq!(
+  ///   entry.describe(),
+  ///   r#"(add (eat "true") (select (mul (next <fn>) 1..) <fn>))"#
+  /// );
+  /// ```
+  #[inline]
+  pub fn describe(&self) -> String {
+    Description(&self.action).to_string()
+  }
+}
+
+/// Write `(` and the `name` in snake case, e.g. `EatUntil` to `(eat_until`.
+/// If the `module` is in [`bytes`](crate::combinator::bytes) or [`u16s`](crate::combinator::u16s),
+/// the name is prefixed with the module name, e.g. `(bytes::eat_until`.
+pub(crate) fn write_head(f: &mut fmt::Formatter<'_>, name: &str, module: &str) -> fmt::Result {
+  f.write_str("(")?;
+  if let Some(prefix) = module
+    .split("::")
+    .find(|segment| *segment == "bytes" || *segment == "u16s")
+  {
+    write!(f, "{}::", prefix)?;
+  }
+  for (i, c) in name.char_indices() {
+    if c.is_ascii_uppercase() {
+      if i != 0 {
+        f.write_str("_")?;
+      }
+      write!(f, "{}", c.to_ascii_lowercase())?;
+    } else {
+      write!(f, "{}", c)?;
+    }
+  }
+  Ok(())
+}
+
+/// Write a binary operator `op` as `(op lhs rhs)`,
+/// flattening the operands if they are the same operator.
+pub(crate) fn write_binary(
+  f: &mut fmt::Formatter<'_>,
+  op: &str,
+  lhs: &impl Describe,
+  rhs: &impl Describe,
+) -> fmt::Result {
+  write!(f, "({} ", op)?;
+  write_operands(f, op, lhs, rhs)?;
+  f.write_str(")")
+}
+
+/// Write the operands of a binary operator `op`, separated by a space.
+/// See [`Describe::describe_operand`].
+pub(crate) fn write_operands(
+  f: &mut fmt::Formatter<'_>,
+  op: &str,
+  lhs: &impl Describe,
+  rhs: &impl Describe,
+) -> fmt::Result {
+  lhs.describe_operand(op, f)?;
+  f.write_str(" ")?;
+  rhs.describe_operand(op, f)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    action::Action,
+    combinator::{bytes, contextual, eat, next, take, u16s, Combinator},
+  };
+
+  #[test]
+  fn describe_provided() {
+    assert_eq!(eat("true").describe(), r#"(eat "true")"#);
+    assert_eq!(eat('a').describe(), "(eat 'a')");
+    assert_eq!(take(3).describe(), "(take 3)");
+    assert_eq!(next(|c| c == 'a').describe(), "(next <fn>)");
+    assert_eq!(
+      crate::combinator::next_n(|c| c == 'a', 4).describe(),
+      "(next_n <fn> 4)"
+    );
+    assert_eq!(
+      crate::combinator::none_of("\"\\").describe(),
+      r#"(none_of "\"\\")"#
+    );
+    struct Token;
+    impl crate::digest::Unit for Token {}
+    assert_eq!(
+      crate::combinator::token_by(|_: &Token| true).describe(),
+      "(token_by <fn>)"
+    );
+    assert_eq!(crate::combinator::till("*/").describe(), r#"(till "*/")"#);
+    assert_eq!(
+      crate::combinator::eat_until(['a', 'b']).describe(),
+      "(eat_until ['a', 'b'])"
+    );
+    assert_eq!(
+      crate::combinator::eat_ci("ab").describe(),
+      r#"(eat_case_insensitive "ab")"#
+    );
+    assert_eq!(
+      crate::combinator::eat_in("+-").describe(),
+      r#"(eat_in "+-")"#
+    );
+    assert_eq!(
+      crate::combinator::eat_range('a'..='z').describe(),
+      "(eat_range 'a'..='z')"
+    );
+    assert_eq!(crate::combinator::identifier().describe(), "(identifier)");
+    assert_eq!(
+      crate::combinator::identifier()
+        .except(&["let", "fn"])
+        .describe(),
+      r#"(identifier ["let", "fn"])"#
+    );
+
+    // bytes
+    assert_eq!(bytes::eat(b'a').describe(), "(bytes::eat 97)");
+    assert_eq!(bytes::eat(b"ab").describe(), "(bytes::eat [97, 98])");
+    assert_eq!(bytes::eat_in(b"ab").describe(), "(bytes::eat_in [97, 98])");
+    assert_eq!(bytes::ascii_digit().describe(), "(bytes::ascii_digit)");
+    assert_eq!(bytes::ascii_ws1().describe(), "(bytes::ascii_ws1)");
+    assert_eq!(bytes::take(2).describe(), "(bytes::take 2)");
+    assert_eq!(bytes::next(|b| b == b'a').describe(), "(bytes::next <fn>)");
+    assert_eq!(
+      bytes::next_n(|b| b == b'a', 2).describe(),
+      "(bytes::next_n <fn> 2)"
+    );
+    assert_eq!(bytes::none_of(b"a").describe(), "(bytes::none_of [97])");
+
+    // u16s
+    assert_eq!(u16s::take(2).describe(), "(u16s::take 2)");
+    assert_eq!(u16s::next(|_| true).describe(), "(u16s::next <fn>)");
+  }
+
+  #[test]
+  fn describe_ops() {
+    assert_eq!(
+      (eat("true") + (next(|c| c.is_ascii_digit()) * (1..))).describe(),
+      r#"(add (eat "true") (mul (next <fn>) 1..))"#
+    );
+    // flatten chains of the same operator
+    assert_eq!(
+      (eat('a') | 'b' | "c" | (eat('d') + 'e' + 'f')).describe(),
+      r#"(bitor (eat 'a') (eat 'b') (eat "c") (add (eat 'd') (eat 'e') (eat 'f')))"#
+    );
+    assert_eq!(
+      (eat('a') | (eat('b') | 'c')).describe(),
+      "(bitor (eat 'a') (eat 'b') (eat 'c'))"
+    );
+    // repetitions
+    assert_eq!((eat('a') * 3).describe(), "(mul (eat 'a') 3)");
+    assert_eq!((eat('a') * ..).describe(), "(mul (eat 'a') ..)");
+    assert_eq!(
+      (eat('a') * (1..=3)).sep(',').describe(),
+      "(mul (eat 'a') 1..=3 (sep (eat ',')))"
+    );
+    assert_eq!(
+      (eat('a') * (1..))
+        .fold(|| 0, |acc: i32, _: ()| acc + 1)
+        .describe(),
+      "(mul (eat 'a') 1..)"
+    );
+    assert_eq!((!eat('a')).describe(), "(not (eat 'a'))");
+    assert_eq!(
+      eat('a').and_also(next(|_| true)).describe(),
+      "(and_also (eat 'a') (next <fn>))"
+    );
+    // bytes
+    assert_eq!(
+      ((bytes::eat(b'a') + (bytes::next(|b| b.is_ascii_digit()) * (1..))) | b"xy").describe(),
+      "(bitor (add (bytes::eat 97) (mul (bytes::next <fn>) 1..)) (bytes::eat [120, 121]))"
+    );
+  }
+
+  #[test]
+  fn describe_decorators() {
+    assert_eq!(eat('a').bind(1).describe(), "(bind (eat 'a') 1)");
+    assert_eq!(eat('a').select(|_| 1).describe(), "(select (eat 'a') <fn>)");
+    assert_eq!(
+      eat('a').try_select(|_| Some(1)).try_map(Some).describe(),
+      "(try_map (try_select (eat 'a') <fn>) <fn>)"
+    );
+    assert_eq!(eat('a').optional().describe(), "(optional (eat 'a'))");
+    assert_eq!(
+      eat('a').when(|_| true).reject(|_| false).describe(),
+      "(reject (when (eat 'a') <fn>) <fn>)"
+    );
+    assert_eq!(
+      eat('a').tuple().range().describe(),
+      "(range (tuple (eat 'a')))"
+    );
+    assert_eq!(
+      (next(|c| c.is_ascii_digit()) * (1..))
+        .select(|a| a.content().len())
+        .map(|n| n * 2)
+        .describe(),
+      "(map (select (mul (next <fn>) 1..) <fn>) <fn>)"
+    );
+  }
+
+  #[test]
+  fn describe_contextual_recur() {
+    contextual!(i32, ());
+
+    // `Contextual` is transparent
+    assert_eq!(
+      (eat('a').prepare(|input| *input.state += 1) + take(1)).describe(),
+      "(add (prepare (eat 'a') <fn>) (take 1))"
+    );
+
+    // recursion is not expanded
+    let (value, setter) = recur::<()>();
+    let array = eat('[') + (value() * ..).sep(eat(',')) + eat(']');
+    assert_eq!(
+      array.describe(),
+      "(add (eat '[') (mul (recur) .. (sep (eat ','))) (eat ']'))"
+    );
+    setter.boxed(array | eat('a'));
+    assert_eq!(value().describe(), "(recur)");
+  }
+
+  #[test]
+  fn describe_boxed() {
+    fn t(c: Combinator<impl Action<Text = str> + super::Describe>) -> String {
+      c.describe()
+    }
+    assert_eq!(t(Combinator::new(&eat('a').action)), "(eat 'a')");
+    assert_eq!(t(Combinator::new(Box::new(eat('a').action))), "(eat 'a')");
+  }
+}
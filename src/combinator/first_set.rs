@@ -0,0 +1,400 @@
+use super::Combinator;
+use std::{cmp::Ordering, ops::RangeInclusive, rc::Rc};
+
+/// A set of [`char`]s, stored as sorted, non-overlapping and non-adjacent ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharSet {
+  ranges: Vec<RangeInclusive<char>>,
+}
+
+/// The next valid [`char`] after `c`, skipping the surrogates.
+#[inline]
+const fn next_char(c: char) -> Option<char> {
+  match c {
+    '\u{D7FF}' => Some('\u{E000}'),
+    char::MAX => None,
+    // SAFETY: `c + 1` is not a surrogate and not greater than `char::MAX`
+    _ => Some(unsafe { char::from_u32_unchecked(c as u32 + 1) }),
+  }
+}
+
+impl CharSet {
+  /// Create an empty set.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { ranges: Vec::new() }
+  }
+
+  /// Create a set of all chars.
+  #[inline]
+  pub fn full() -> Self {
+    Self {
+      ranges: vec!['\0'..=char::MAX],
+    }
+  }
+
+  /// Add a char into the set.
+  #[inline]
+  pub fn insert(&mut self, c: char) {
+    self.insert_range(c..=c);
+  }
+
+  /// Add a range of chars into the set.
+  /// Empty ranges are ignored.
+  pub fn insert_range(&mut self, range: RangeInclusive<char>) {
+    if range.is_empty() {
+      return;
+    }
+    self.ranges.push(range);
+    self.normalize();
+  }
+
+  /// Add all chars in `other` into the set.
+  pub fn union(&mut self, other: &Self) {
+    self.ranges.extend(other.ranges.iter().cloned());
+    self.normalize();
+  }
+
+  /// Check if `c` is in the set.
+  pub fn contains(&self, c: char) -> bool {
+    self
+      .ranges
+      .binary_search_by(|range| {
+        if *range.end() < c {
+          Ordering::Less
+        } else if *range.start() > c {
+          Ordering::Greater
+        } else {
+          Ordering::Equal
+        }
+      })
+      .is_ok()
+  }
+
+  /// Return `true` if the set has no char.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  /// Return `true` if the set has all chars.
+  #[inline]
+  pub fn is_full(&self) -> bool {
+    self.ranges == ['\0'..=char::MAX]
+  }
+
+  /// The sorted, non-overlapping and non-adjacent ranges of the set.
+  #[inline]
+  pub fn ranges(&self) -> &[RangeInclusive<char>] {
+    &self.ranges
+  }
+
+  /// Sort and merge the ranges.
+  // `Option::is_none_or` requires Rust 1.82
+  #[allow(clippy::unnecessary_map_or)]
+  fn normalize(&mut self) {
+    self.ranges.sort_unstable_by_key(|range| *range.start());
+    let mut merged: Vec<RangeInclusive<char>> = Vec::with_capacity(self.ranges.len());
+    for range in self.ranges.drain(..) {
+      if let Some(last) = merged.last_mut() {
+        // overlapping or adjacent
+        if next_char(*last.end()).map_or(true, |next| next >= *range.start()) {
+          if range.end() > last.end() {
+            *last = *last.start()..=*range.end();
+          }
+          continue;
+        }
+      }
+      merged.push(range);
+    }
+    self.ranges = merged;
+  }
+}
+
+impl FromIterator<char> for CharSet {
+  fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+    let mut set = Self {
+      ranges: iter.into_iter().map(|c| c..=c).collect(),
+    };
+    set.normalize();
+    set
+  }
+}
+
+/// What a combinator may see at the head of the rest when it accepts.
+/// See [`FirstSet`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LookaheadSet {
+  /// If the combinator accepts with a non-zero digested length,
+  /// the first char of the digested text is in this set.
+  pub chars: CharSet,
+  /// `true` if the combinator may accept with zero digested length.
+  pub nullable: bool,
+}
+
+impl LookaheadSet {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(chars: CharSet, nullable: bool) -> Self {
+    Self { chars, nullable }
+  }
+
+  /// The set of a combinator which only accepts with zero digested length,
+  /// e.g. a lookahead.
+  #[inline]
+  pub const fn empty() -> Self {
+    Self::new(CharSet::new(), true)
+  }
+
+  /// The set of a combinator which may accept any char, e.g. [`take`](crate::combinator::take).
+  #[inline]
+  pub fn full() -> Self {
+    Self::new(CharSet::full(), false)
+  }
+
+  /// The set of `self` or `other`, e.g. for `|`.
+  #[inline]
+  pub fn union(mut self, other: &Self) -> Self {
+    self.chars.union(&other.chars);
+    self.nullable |= other.nullable;
+    self
+  }
+
+  /// The set of `self` followed by `next`, e.g. for `+`.
+  ///
+  /// `next` is only needed when `self` is nullable,
+  /// so an unknown `next` ([`None`]) only makes the result unknown in that case.
+  #[inline]
+  pub fn then(self, next: impl FnOnce() -> Option<Self>) -> Option<Self> {
+    if !self.nullable {
+      return Some(self);
+    }
+    next().map(|next| Self {
+      nullable: next.nullable,
+      ..self.union(&next)
+    })
+  }
+}
+
+/// Statically compute the [`LookaheadSet`] of an [`Action`](crate::action::Action),
+/// i.e. which chars the action may start with when it accepts,
+/// and whether it may accept without digesting.
+///
+/// The result is an over-approximation:
+/// if the first char of the rest is not in [`LookaheadSet::chars`]
+/// and the set is not [`nullable`](LookaheadSet::nullable),
+/// the action is guaranteed to reject.
+/// This can be used to build dispatch tables or to report what is expected.
+///
+/// [`None`] means the set is unknown,
+/// e.g. for [`next`](crate::combinator::next) and [`wrap`](crate::combinator::wrap)
+/// whose conditions are opaque closures.
+/// Operators propagate [`None`] only if the set is needed:
+/// `eat('a') + wrap(...)` is still known, but `eat('a') | wrap(...)` is not.
+///
+/// This is implemented for the operators, decorators and the provided combinators for `str`
+/// whose sets are known, like [`eat`](crate::combinator::eat),
+/// [`eat_in`](crate::combinator::eat_in), [`eat_range`](crate::combinator::eat_range)
+/// and [`take`](crate::combinator::take).
+/// Use [`Combinator::first_set`] to get the set.
+pub trait FirstSet {
+  /// Compute the [`LookaheadSet`], or [`None`] if it is unknown.
+  fn first_set(&self) -> Option<LookaheadSet>;
+}
+
+impl<T: FirstSet + ?Sized> FirstSet for &T {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    (**self).first_set()
+  }
+}
+
+impl<T: FirstSet + ?Sized> FirstSet for Box<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    (**self).first_set()
+  }
+}
+
+impl<T: FirstSet + ?Sized> FirstSet for Rc<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    (**self).first_set()
+  }
+}
+
+impl<T: FirstSet> Combinator<T> {
+  /// Compute which chars the combinator may start with when it accepts.
+  /// See [`FirstSet`] for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::combinator::{eat, eat_range, next};
+  /// let entry = eat("true") | eat("false") | eat_range('0'..='9');
+  /// let set = entry.first_set().unwrap();
+  /// assert_eq!(set.chars.ranges(), ['0'..='9', 'f'..='f', 't'..='t']);
+  /// assert!(!set.nullable);
+  ///
+  /// // the condition of `next` is unknown
+  /// assert_eq!((entry | next(|c| c == '-')).first_set(), None);
+  /// ```
+  #[inline]
+  pub fn first_set(&self) -> Option<LookaheadSet> {
+    self.action.first_set()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{eat, eat_in, eat_range, next, not, peek, recur, take, wrap};
+
+  fn chars(set: Option<LookaheadSet>) -> Vec<RangeInclusive<char>> {
+    set.unwrap().chars.ranges().to_vec()
+  }
+
+  #[test]
+  fn char_set() {
+    let mut set = CharSet::new();
+    assert!(set.is_empty());
+    set.insert('c');
+    set.insert('a');
+    assert_eq!(set.ranges(), ['a'..='a', 'c'..='c']);
+    // adjacent
+    set.insert('b');
+    assert_eq!(set.ranges(), ['a'..='c']);
+    // overlapping
+    set.insert_range('x'..='z');
+    set.insert_range('b'..='y');
+    assert_eq!(set.ranges(), ['a'..='z']);
+    // empty range
+    #[allow(clippy::reversed_empty_ranges)]
+    set.insert_range('z'..='a');
+    assert_eq!(set.ranges(), ['a'..='z']);
+    assert!(set.contains('a') && set.contains('m') && set.contains('z'));
+    assert!(!set.contains('A') && !set.contains('{'));
+
+    // surrogates are skipped
+    let mut set = CharSet::new();
+    set.insert('\u{D7FF}');
+    set.insert('\u{E000}');
+    assert_eq!(set.ranges(), ['\u{D7FF}'..='\u{E000}']);
+
+    let mut set: CharSet = "0123456789-".chars().collect();
+    assert_eq!(set.ranges(), ['-'..='-', '0'..='9']);
+    set.union(&CharSet::full());
+    assert!(set.is_full());
+    assert!(set.contains(char::MAX));
+  }
+
+  #[test]
+  fn first_set_provided() {
+    assert_eq!(chars(eat('a').first_set()), ['a'..='a']);
+    assert_eq!(chars(eat("ab").first_set()), ['a'..='a']);
+    assert_eq!(chars(eat("ab".to_string()).first_set()), ['a'..='a']);
+    assert_eq!(eat("").first_set(), Some(LookaheadSet::empty()));
+    assert_eq!(chars(eat_in("+-").first_set()), ['+'..='+', '-'..='-']);
+    assert_eq!(chars(eat_range('a'..='z').first_set()), ['a'..='z']);
+    assert_eq!(take(1).first_set(), Some(LookaheadSet::full()));
+    assert_eq!(take(0).first_set(), Some(LookaheadSet::empty()));
+    assert_eq!(next(|_| true).first_set(), None);
+    assert_eq!(not('a').first_set(), Some(LookaheadSet::empty()));
+    assert_eq!(peek('a').first_set(), Some(LookaheadSet::empty()));
+    assert_eq!((!eat('a')).first_set(), Some(LookaheadSet::empty()));
+  }
+
+  #[test]
+  fn first_set_ops() {
+    // add
+    assert_eq!(chars((eat('a') + 'b').first_set()), ['a'..='a']);
+    assert_eq!(chars((eat('a').optional() + 'b').first_set()), ['a'..='b']);
+    assert!(!(eat('a').optional() + 'b').first_set().unwrap().nullable);
+    assert!(
+      (eat('a').optional() + eat('b').opt())
+        .first_set()
+        .unwrap()
+        .nullable
+    );
+    assert_eq!(chars((peek('a') + 'b').first_set()), ['b'..='b']);
+    // bitor
+    assert_eq!(chars((eat('a') | 'c' | "b").first_set()), ['a'..='c']);
+    assert!((eat('a') | "").first_set().unwrap().nullable);
+    // mul
+    assert_eq!(chars((eat('a') * (1..)).first_set()), ['a'..='a']);
+    assert!(!(eat('a') * (1..)).first_set().unwrap().nullable);
+    assert!((eat('a') * (..)).first_set().unwrap().nullable);
+    assert!((eat('a') * 0).first_set().unwrap().nullable);
+    assert!(!(eat('a') * [(); 1]).first_set().unwrap().nullable);
+    // the separator may be the first if the item is nullable
+    assert_eq!(chars((eat('a') * (1..)).sep(',').first_set()), ['a'..='a']);
+    assert_eq!(
+      chars((eat('a').optional() * (1..)).sep(',').first_set()),
+      [','..=',', 'a'..='a']
+    );
+    // decorators
+    assert_eq!(
+      chars(eat('a').map(|_| 1).when(|_| true).first_set()),
+      ['a'..='a']
+    );
+    assert!(eat('a').or_else(|_| ()).first_set().unwrap().nullable);
+  }
+
+  #[test]
+  fn first_set_json() {
+    let ws = || eat_in(" \t\r\n") * (..);
+    let digits = || eat_range('0'..='9') * (1..);
+    let number = eat('-').optional()
+      + (eat('0') | (eat_range('1'..='9') + digits().optional()))
+      + (eat('.') + digits()).optional()
+      + ((eat_in("eE") + eat_in("+-").optional()) + digits()).optional();
+    let string = || eat('"') + next(|c| c != '"') * (..) + '"';
+    let (value, setter) = recur::<()>();
+    let array = eat('[') + ws() + value() * (..) + ']';
+    let object = eat('{') + ws() + (string() + ws() + ':' + ws() + value()) * (..) + '}';
+    let json_value = array | object | string() | number | "true" | "false" | "null";
+
+    // the recursive parts are after the first char, so the set is known
+    let set = json_value.first_set().unwrap();
+    assert!(!set.nullable);
+    assert_eq!(
+      set.chars.ranges(),
+      [
+        '"'..='"',
+        '-'..='-',
+        '0'..='9',
+        '['..='[',
+        'f'..='f',
+        'n'..='n',
+        't'..='t',
+        '{'..='{',
+      ]
+    );
+    assert!(set.chars.contains('7'));
+    assert!(!set.chars.contains(' '));
+
+    // leading whitespaces are nullable
+    let padded = ws() + json_value;
+    let set = padded.first_set().unwrap();
+    assert!(!set.nullable);
+    assert!(set.chars.contains(' ') && set.chars.contains('\n') && set.chars.contains('{'));
+
+    setter.boxed(padded);
+    // the recursive combinator is unknown
+    assert_eq!(value().first_set(), None);
+  }
+
+  #[test]
+  fn first_set_unknown() {
+    let w = || wrap(|input| input.instant.accept(1));
+    assert_eq!(w().first_set(), None);
+    // unknown if the closure may be the first
+    assert_eq!((eat('a') | w()).first_set(), None);
+    assert_eq!((w() + 'a').first_set(), None);
+    assert_eq!((eat('a').optional() + w()).first_set(), None);
+    assert_eq!((w() * (1..)).first_set(), None);
+    assert_eq!((eat('a').optional() * (..)).sep(w()).first_set(), None);
+    assert_eq!(w().map(|_| 1).first_set(), None);
+    // known if the closure is never the first
+    assert_eq!(chars((eat('a') + w()).first_set()), ['a'..='a']);
+    assert_eq!(chars((eat('a') * (..)).sep(w()).first_set()), ['a'..='a']);
+  }
+}
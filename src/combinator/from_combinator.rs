@@ -0,0 +1,100 @@
+use super::{bounded_uint, eat, next, Combinator};
+use crate::action::Action;
+
+/// Types which have a default combinator to parse them from [`str`].
+///
+/// With the `derive` feature, `#[derive(FromCombinator)]` implements this for structs
+/// by parsing the fields in declaration order,
+/// fields without a `#[parse(with = "...")]` attribute are parsed by their own implementation.
+/// See the `whitehole-macros` crate for more information.
+///
+/// The combinator is not contextual, the `State` and `Heap` are always `()`.
+/// # Examples
+/// ```
+/// use whitehole::{
+///   action::Action,
+///   combinator::{eat, Combinator, FromCombinator},
+///   parser::Parser,
+/// };
+///
+/// struct Point(u8, u8);
+///
+/// impl FromCombinator for Point {
+///   fn combinator() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = Self>> {
+///     (u8::combinator().tuple() + ',' + u8::combinator().tuple()).map(|(x, y)| Point(x, y))
+///   }
+/// }
+///
+/// let point = Parser::builder().entry(Point::combinator()).build("1,2").next().unwrap().value;
+/// assert_eq!((point.0, point.1), (1, 2));
+/// ```
+pub trait FromCombinator: Sized {
+  /// Returns the default combinator to parse `Self`.
+  fn combinator() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = Self>>;
+}
+
+macro_rules! impl_from_combinator_for_uint {
+  ($($t:ty),*) => {
+    $(
+      /// Parse ascii digits by [`bounded_uint`], reject if the value overflows.
+      impl FromCombinator for $t {
+        #[inline]
+        fn combinator() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = Self>> {
+          bounded_uint(0, <$t>::MAX as u64).map(|n| n as $t)
+        }
+      }
+    )*
+  };
+}
+impl_from_combinator_for_uint!(u8, u16, u32, u64, usize);
+
+/// Parse `true` or `false`.
+impl FromCombinator for bool {
+  #[inline]
+  fn combinator() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = Self>> {
+    eat("true").bind(true) | eat("false").bind(false)
+  }
+}
+
+/// Parse any one char.
+impl FromCombinator for char {
+  #[inline]
+  fn combinator() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = Self>> {
+    next(|_| true).select(|accepted| accepted.content().chars().next().unwrap())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{action::Input, instant::Instant};
+  use std::fmt::Debug;
+
+  fn helper<T: FromCombinator + PartialEq + Debug>(input: &str, expected: Option<(T, usize)>) {
+    assert_eq!(
+      T::combinator()
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn from_combinator_primitives() {
+    helper::<u8>("255", Some((255, 3)));
+    helper::<u8>("256", None);
+    helper::<u16>("65535a", Some((65535, 5)));
+    helper::<u32>("a", None);
+    helper::<u64>("18446744073709551615", Some((u64::MAX, 20)));
+    helper::<usize>("0", Some((0, 1)));
+    helper::<bool>("true", Some((true, 4)));
+    helper::<bool>("false", Some((false, 5)));
+    helper::<bool>("t", None);
+    helper::<char>("好a", Some(('好', 3)));
+    helper::<char>("", None);
+  }
+}
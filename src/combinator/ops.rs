@@ -1,6 +1,7 @@
 //! Operator overloading for [`Combinator`](crate::combinator::Combinator).
 
 pub mod add;
+pub mod and_also;
 pub mod bitor;
 pub mod mul;
 pub mod not;
@@ -57,6 +57,17 @@
 //! let output = parser.next().unwrap();
 //! assert_eq!(output.value, (123, 456));
 //! ```
+//!
+//! If the values are not tuples, `+` won't compile.
+//! Use [`Combinator::tuple`] to wrap them, or use [`Combinator::pair`]
+//! to yield both values as a pair without concatenation.
+//! ```
+//! # use whitehole::{combinator::next, parser::Parser};
+//! let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap());
+//! // digit() + digit() won't compile
+//! let entry = digit().pair(digit());
+//! assert_eq!(Parser::builder().entry(entry).build("12").next().unwrap().value, (1, 2));
+//! ```
 //! See [`Concat`] for more information.
 
 mod concat;
@@ -64,12 +75,17 @@ mod concat;
 pub use concat::*;
 
 use crate::{
-  action::{Action, Input, Output},
-  combinator::{bytes, Combinator, Contextual, Eat},
+  action::{Action, Input, Output, Rejection},
+  combinator::{
+    bytes, write_binary, write_operands, Combinator, Contextual, Describe, Eat, FirstSet,
+    LookaheadSet,
+  },
   digest::Digest,
+  idx,
   instant::Instant,
 };
 use std::{
+  fmt,
   ops::{self, RangeFrom},
   slice::SliceIndex,
 };
@@ -90,6 +106,28 @@ impl<Lhs, Rhs> Add<Lhs, Rhs> {
   }
 }
 
+impl<Lhs: Describe, Rhs: Describe> Describe for Add<Lhs, Rhs> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_binary(f, "add", &self.lhs, &self.rhs)
+  }
+
+  fn describe_operand(&self, op: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if op == "add" {
+      write_operands(f, op, &self.lhs, &self.rhs)
+    } else {
+      self.describe(f)
+    }
+  }
+}
+
+/// The first of `lhs`, and the first of `rhs` if `lhs` may accept without digesting.
+impl<Lhs: FirstSet, Rhs: FirstSet> FirstSet for Add<Lhs, Rhs> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    self.lhs.first_set()?.then(|| self.rhs.first_set())
+  }
+}
+
 impl<Lhs, Rhs> ops::Add<Combinator<Rhs>> for Combinator<Lhs> {
   type Output = Combinator<Add<Lhs, Rhs>>;
 
@@ -123,10 +161,27 @@ where
         .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(output.digested) }))
         .map(|rhs_output| Output {
           value: output.value.concat(rhs_output.value),
-          digested: unsafe { output.digested.unchecked_add(rhs_output.digested) },
+          // SAFETY: both are inside the rest, whose length is a valid usize
+          digested: idx::unchecked!(idx::add(output.digested, rhs_output.digested)),
         })
     })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.lhs.exec_cut(input.reborrow())?;
+    let rhs_output = self.rhs.exec_cut(
+      input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(output.digested) }),
+    )?;
+    Ok(Output {
+      value: output.value.concat(rhs_output.value),
+      // SAFETY: both are inside the rest, whose length is a valid usize
+      digested: idx::unchecked!(idx::add(output.digested, rhs_output.digested)),
+    })
+  }
 }
 
 impl<Lhs: Action<Text = str>> ops::Add<char> for Combinator<Lhs> {
@@ -6,6 +6,11 @@
 /// - `().concat((T1, T2, ...)) -> (T1, T2, ...)` for results with up to 12 elements.
 /// - `(T1, T2, ...).concat((U1, U2, ...)) -> (T1, T2, ..., U1, U2, ...)`
 ///   for results with up to 12 elements.
+#[diagnostic::on_unimplemented(
+  message = "`{Self}` can't be concatenated with `{Rhs}`",
+  note = "only tuples and `()` can be concatenated when performing `+` on combinators",
+  note = "use `Combinator::tuple` to wrap the value in a tuple, or use `Combinator::pair` to yield `(Lhs, Rhs)`"
+)]
 pub trait Concat<Rhs> {
   /// The concat result.
   type Output;
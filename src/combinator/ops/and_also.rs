@@ -0,0 +1,201 @@
+//! Intersection of two combinators.
+//!
+//! [`Combinator::and_also`] will create a new combinator
+//! that executes both combinators at the same position,
+//! and accepts only if both of them accept with the same digested length.
+//! # Basics
+//! ```
+//! # use whitehole::{combinator::{eat, next, Combinator}, action::Action};
+//! # fn t(_: Combinator<impl Action<Text = str>>) {}
+//! // an identifier which is also a keyword
+//! # t(
+//! (next(|c| c.is_ascii_alphabetic()) * (1..)).and_also(eat("if") | eat("else"))
+//! # );
+//! ```
+//! # Digested Length
+//! The two sides must digest the same number of bytes,
+//! otherwise the new combinator will reject.
+//! E.g. `eat("ab").and_also(eat("a"))` will reject on `"ab"`,
+//! since the input digested by `eat("a")` is not the same as `eat("ab")`.
+//! # Value
+//! The value of the left-hand side is yielded,
+//! the value of the right-hand side is discarded.
+//! # State
+//! The right-hand side is executed after the left-hand side accepts,
+//! so modifications to [`Input::state`] and [`Input::heap`] made by the left-hand side
+//! are visible to the right-hand side and won't be reverted if the right-hand side rejects.
+
+use crate::{
+  action::{exec_by_cut, Action, Input, Output, Rejection},
+  combinator::{write_binary, Combinator, Describe},
+  instant::Instant,
+};
+use std::fmt;
+
+/// An [`Action`] created by [`Combinator::and_also`].
+/// See [`ops::and_also`](crate::combinator::ops::and_also) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct AndAlso<Lhs, Rhs> {
+  lhs: Lhs,
+  rhs: Rhs,
+}
+
+impl<Lhs, Rhs> AndAlso<Lhs, Rhs> {
+  #[inline]
+  const fn new(lhs: Lhs, rhs: Rhs) -> Self {
+    Self { lhs, rhs }
+  }
+}
+
+impl<Lhs: Describe, Rhs: Describe> Describe for AndAlso<Lhs, Rhs> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_binary(f, "and_also", &self.lhs, &self.rhs)
+  }
+}
+
+impl<T> Combinator<T> {
+  /// Create a new combinator to execute `self` and `rhs` at the same position,
+  /// accept only if both of them accept with the same digested length.
+  /// The value of `self` is yielded.
+  ///
+  /// `rhs` is only executed if `self` accepts.
+  /// See [`ops::and_also`](crate::combinator::ops::and_also) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, next}, parser::Parser};
+  /// let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+  /// let keyword = || eat("if") | eat("else");
+  ///
+  /// let entry = || ident().and_also(keyword());
+  /// assert_eq!(Parser::builder().entry(entry()).build("if").next().unwrap().digested, 2);
+  /// // the identifier is longer than the keyword
+  /// assert!(Parser::builder().entry(entry()).build("iff").next().is_none());
+  /// ```
+  #[inline]
+  pub fn and_also<Rhs>(self, rhs: impl Into<Combinator<Rhs>>) -> Combinator<AndAlso<T, Rhs>> {
+    Combinator::new(AndAlso::new(self.action, rhs.into().action))
+  }
+}
+
+unsafe impl<Lhs: Action, Rhs: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>> Action
+  for AndAlso<Lhs, Rhs>
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Lhs::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let output = self.lhs.exec_cut(input.reborrow())?;
+    (self.rhs.exec_cut(input)?.digested == output.digested)
+      .then_some(output)
+      .ok_or(Rejection::Backtrack)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, eat, from_fn, next, take},
+    digest::Digest,
+  };
+  use std::{ops::RangeFrom, slice::SliceIndex};
+
+  fn helper<Text: ?Sized + Digest, Value: PartialEq + std::fmt::Debug>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    expected: Option<(Value, usize)>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn combinator_and_also() {
+    let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+    let keyword = || eat("if") | eat("else");
+
+    // equal digested length
+    helper(ident().and_also(keyword()), "if", Some(((), 2)));
+    helper(ident().and_also(keyword()), "else x", Some(((), 4)));
+    helper(keyword().and_also(ident()), "if(", Some(((), 2)));
+    helper(
+      bytes::eat(b"ab").and_also(bytes::take(2)),
+      b"abc",
+      Some(((), 2)),
+    );
+
+    // mismatched lengths
+    helper(ident().and_also(keyword()), "iff", None);
+    helper(ident().and_also(keyword()), "elsewhere", None);
+    helper(eat("ab").and_also(eat("a")), "ab", None);
+    helper(bytes::take(1).and_also(bytes::take(2)), b"ab", None);
+
+    // one side rejects
+    helper(ident().and_also(keyword()), "x", None);
+    helper(ident().and_also(keyword()), "1", None);
+    helper(keyword().and_also(ident()), "", None);
+    helper(take(1).and_also(eat('a')), "b", None);
+
+    // the value of the left-hand side is yielded
+    helper(take(1).bind(1).and_also(take(1).bind(2)), "a", Some((1, 1)));
+    // with `+`
+    helper(
+      take(1).and_also(eat('a')) + take(1).and_also(eat('b')),
+      "ab",
+      Some(((), 2)),
+    );
+  }
+
+  #[test]
+  fn combinator_and_also_skip_rhs() {
+    let rhs = || {
+      from_fn(|input: Input<&Instant<&str>, &mut usize, &mut ()>| {
+        *input.state += 1;
+        input.instant.accept(0)
+      })
+    };
+    let exec = |action: &dyn Action<Text = str, State = usize, Heap = (), Value = ()>| {
+      let mut executed = 0;
+      let accepted = action
+        .exec(Input {
+          instant: &Instant::new("a"),
+          state: &mut executed,
+          heap: &mut (),
+        })
+        .is_some();
+      (accepted, executed)
+    };
+
+    // rhs is executed after lhs accepts
+    assert_eq!(exec(&rhs().and_also(rhs())), (true, 2));
+    // rhs is not executed if lhs rejects
+    assert_eq!(exec(&rhs().reject(|_| true).and_also(rhs())), (false, 1));
+    // rhs rejects
+    assert_eq!(exec(&rhs().and_also(rhs().reject(|_| true))), (false, 2));
+  }
+
+  fn _and_also_debug_clone_copy() {
+    let c = eat('a').and_also(take(1));
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
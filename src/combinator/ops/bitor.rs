@@ -34,14 +34,64 @@
 //! # tb(
 //! bytes::eat(b"true") | b"false".to_vec()
 //! # );
+//!
+//! // keyword alternation with bare literals
+//! # t(
+//! eat("let") | "const" | "var"
+//! # );
+//! # tb(
+//! bytes::eat(b"GET") | b"PUT" | b"POST" | b"DELETE"
+//! # );
+//! ```
+//! Literal shortcuts also work with [`contextual`](crate::contextual) combinators,
+//! the literal will share the same `State` and `Heap` with the left-hand side.
+//!
+//! Literals can't be at the left-hand side of `|`.
+//! Use [`choice!`](crate::choice) to start an alternation with a literal,
+//! which also keeps the type flat for long alternations.
+//! ```
+//! # use whitehole::{choice, combinator::{eat, Combinator}, action::Action};
+//! # fn t(_: Combinator<impl Action<Text = str>>) {}
+//! # t(
+//! choice!("let", "const", eat("var"))
+//! # );
 //! ```
+//! # Different Value Types
+//! Both sides of `|` must have the same value type.
+//! Use [`Combinator::or_either`] to wrap the value in [`Either`] instead.
+//! ```
+//! # use whitehole::{combinator::{eat, ops::bitor::Either, Combinator}, action::Action};
+//! # fn t(_: Combinator<impl Action<Text = str, Value = Either<bool, ()>>>) {}
+//! # t(
+//! eat("true").bind(true).or_either(eat("null"))
+//! # );
+//! ```
+//! # Cut
+//! Use [`Combinator::cut`] to prevent the `|` from trying other branches
+//! once a distinctive prefix is matched.
+//! ```
+//! # use whitehole::{combinator::{eat, next, Combinator}, action::Action};
+//! # fn t(_: Combinator<impl Action<Text = str>>) {}
+//! # let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+//! # let other = || next(|_| true) * (1..);
+//! # t(
+//! (eat("let ") + (ident() + " = " + ident()).cut()) | other()
+//! # );
+//! ```
+
+mod either;
+
+pub use either::*;
 
 use crate::{
-  action::{Action, Input, Output},
-  combinator::{bytes, Combinator, Contextual, Eat},
+  action::{Action, Input, Output, Rejection},
+  combinator::{
+    bytes, write_binary, write_operands, Combinator, Contextual, Describe, Eat, FirstSet,
+    LookaheadSet,
+  },
   instant::Instant,
 };
-use std::ops;
+use std::{fmt, ops};
 
 /// An [`Action`] created by the `|` operator.
 /// See [`ops::bitor`](crate::combinator::ops::bitor) for more information.
@@ -59,6 +109,27 @@ impl<Lhs, Rhs> BitOr<Lhs, Rhs> {
   }
 }
 
+impl<Lhs: Describe, Rhs: Describe> Describe for BitOr<Lhs, Rhs> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_binary(f, "bitor", &self.lhs, &self.rhs)
+  }
+
+  fn describe_operand(&self, op: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if op == "bitor" {
+      write_operands(f, op, &self.lhs, &self.rhs)
+    } else {
+      self.describe(f)
+    }
+  }
+}
+
+impl<Lhs: FirstSet, Rhs: FirstSet> FirstSet for BitOr<Lhs, Rhs> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(self.lhs.first_set()?.union(&self.rhs.first_set()?))
+  }
+}
+
 impl<Lhs, Rhs> ops::BitOr<Combinator<Rhs>> for Combinator<Lhs> {
   type Output = Combinator<BitOr<Lhs, Rhs>>;
 
@@ -84,10 +155,25 @@ unsafe impl<
     &self,
     mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
   ) -> Option<Output<Self::Value>> {
-    self
-      .lhs
-      .exec(input.reborrow())
-      .or_else(|| self.rhs.exec(input))
+    match self.lhs.exec_cut(input.reborrow()) {
+      Ok(output) => Some(output),
+      Err(Rejection::Cut) => None,
+      Err(Rejection::Backtrack | Rejection::Incomplete) => self.rhs.exec(input),
+    }
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    match self.lhs.exec_cut(input.reborrow()) {
+      Err(rejection @ (Rejection::Backtrack | Rejection::Incomplete)) => self
+        .rhs
+        .exec_cut(input)
+        .map_err(|other| rejection.merge(other)),
+      res => res,
+    }
   }
 }
 
@@ -263,6 +349,25 @@ mod tests {
     helper(rejecter() | vec![b'1'], b"1", &mut (), Some(1));
   }
 
+  #[test]
+  fn combinator_bit_or_literal_chain() {
+    contextual!(i32, ());
+
+    // the first accepted literal wins, the state is shared
+    let keyword = || take(0).reject(|_| true).prepare(|input| *input.state += 1) | "let" | "const";
+    let mut state = 0;
+    helper(keyword(), "const", &mut state, Some(5));
+    helper(keyword(), "let", &mut state, Some(3));
+    helper(keyword(), "var", &mut state, None);
+    assert_eq!(state, 3);
+
+    let method = || bytes::take(0).reject(|_| true) | b"GET" | b"PUT".as_slice() | b"POST".to_vec();
+    helper(method(), b"GET /", &mut 0, Some(3));
+    helper(method(), b"PUT /", &mut 0, Some(3));
+    helper(method(), b"POST /", &mut 0, Some(4));
+    helper(method(), b"DELETE /", &mut 0, None);
+  }
+
   fn _with_contextual() {
     contextual!(i32, i32);
 
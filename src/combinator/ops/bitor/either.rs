@@ -0,0 +1,229 @@
+use crate::{
+  action::{Action, Input, Output, Rejection},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// The value of [`Combinator::or_either`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+  /// The value of the left-hand side.
+  Left(L),
+  /// The value of the right-hand side.
+  Right(R),
+}
+
+impl<L, R> Either<L, R> {
+  /// Return `true` if this is [`Either::Left`].
+  #[inline]
+  pub const fn is_left(&self) -> bool {
+    matches!(self, Self::Left(_))
+  }
+
+  /// Return `true` if this is [`Either::Right`].
+  #[inline]
+  pub const fn is_right(&self) -> bool {
+    matches!(self, Self::Right(_))
+  }
+
+  /// Return the left value if this is [`Either::Left`].
+  #[inline]
+  pub fn left(self) -> Option<L> {
+    match self {
+      Self::Left(l) => Some(l),
+      Self::Right(_) => None,
+    }
+  }
+
+  /// Return the right value if this is [`Either::Right`].
+  #[inline]
+  pub fn right(self) -> Option<R> {
+    match self {
+      Self::Left(_) => None,
+      Self::Right(r) => Some(r),
+    }
+  }
+
+  /// Convert both sides to a common type.
+  #[inline]
+  pub fn either<T>(self, left: impl FnOnce(L) -> T, right: impl FnOnce(R) -> T) -> T {
+    match self {
+      Self::Left(l) => left(l),
+      Self::Right(r) => right(r),
+    }
+  }
+}
+
+impl<T> Either<T, T> {
+  /// Return the inner value when both sides have the same type.
+  #[inline]
+  pub fn into_inner(self) -> T {
+    match self {
+      Self::Left(v) | Self::Right(v) => v,
+    }
+  }
+}
+
+/// An [`Action`] created by [`Combinator::or_either`].
+#[derive(Debug, Clone, Copy)]
+pub struct OrEither<Lhs, Rhs> {
+  lhs: Lhs,
+  rhs: Rhs,
+}
+
+impl<T> Combinator<T> {
+  /// Like `|`, but the two sides can have different value types.
+  /// The value is [`Either::Left`] if the left-hand side accepts,
+  /// otherwise [`Either::Right`] if the right-hand side accepts.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, ops::bitor::Either}, parser::Parser};
+  /// let entry = eat("true").bind(true).or_either(eat("null"));
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("null").next().unwrap().value,
+  ///   Either::Right(())
+  /// );
+  /// ```
+  #[inline]
+  pub fn or_either<Rhs>(self, rhs: impl Into<Combinator<Rhs>>) -> Combinator<OrEither<T, Rhs>> {
+    Combinator::new(OrEither {
+      lhs: self.action,
+      rhs: rhs.into().action,
+    })
+  }
+}
+
+unsafe impl<Lhs: Action, Rhs: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>> Action
+  for OrEither<Lhs, Rhs>
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Either<Lhs::Value, Rhs::Value>;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    match self.lhs.exec_cut(input.reborrow()) {
+      Ok(output) => Some(output.map(Either::Left)),
+      Err(Rejection::Cut) => None,
+      Err(Rejection::Backtrack | Rejection::Incomplete) => {
+        self.rhs.exec(input).map(|output| output.map(Either::Right))
+      }
+    }
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    match self.lhs.exec_cut(input.reborrow()) {
+      Ok(output) => Ok(output.map(Either::Left)),
+      Err(Rejection::Cut) => Err(Rejection::Cut),
+      Err(rejection) => self
+        .rhs
+        .exec_cut(input)
+        .map(|output| output.map(Either::Right))
+        .map_err(|other| rejection.merge(other)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, eat, take},
+    digest::Digest,
+  };
+  use std::{fmt::Debug, ops::RangeFrom, slice::SliceIndex};
+
+  fn helper<Text: ?Sized + Digest, Value: PartialEq + Debug>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    expected: Option<(Value, usize)>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    );
+  }
+
+  #[test]
+  fn combinator_or_either() {
+    let c = eat("true")
+      .bind(true)
+      .or_either(take(2).select(|a| a.start()));
+    helper(c, "true", Some((Either::Left(true), 4)));
+    // digested is taken from the accepted branch
+    helper(c, "tru", Some((Either::Right(0), 2)));
+    helper(c, "t", None);
+
+    // bytes
+    let c = bytes::eat(b'a').bind('a').or_either(b"bc");
+    helper(c, b"a" as &[u8], Some((Either::Left('a'), 1)));
+    helper(c, b"bc" as &[u8], Some((Either::Right(()), 2)));
+    helper(c, b"c" as &[u8], None);
+  }
+
+  #[test]
+  fn combinator_or_either_nested() {
+    let c = eat('a')
+      .bind(1)
+      .or_either(eat("bb").bind('b'))
+      .or_either(eat("ccc").bind("c"));
+    helper(c, "a", Some((Either::Left(Either::Left(1)), 1)));
+    helper(c, "bb", Some((Either::Left(Either::Right('b')), 2)));
+    helper(c, "ccc", Some((Either::Right("c"), 3)));
+    helper(c, "d", None);
+  }
+
+  #[test]
+  fn combinator_or_either_map() {
+    let c = eat('a')
+      .bind(1)
+      .or_either(eat("bb").bind("2"))
+      .map(|v| v.either(|n| n, |s| s.parse().unwrap()));
+    helper(c, "a", Some((1, 1)));
+    helper(c, "bb", Some((2, 2)));
+
+    let c = eat('a')
+      .bind(1)
+      .or_either(eat("bb").bind(2))
+      .map(Either::into_inner);
+    helper(c, "bb", Some((2, 2)));
+  }
+
+  #[test]
+  fn either_methods() {
+    let l: Either<i32, char> = Either::Left(1);
+    let r: Either<i32, char> = Either::Right('a');
+    assert!(l.is_left() && !l.is_right());
+    assert!(r.is_right() && !r.is_left());
+    assert_eq!(l.left(), Some(1));
+    assert_eq!(l.right(), None);
+    assert_eq!(r.left(), None);
+    assert_eq!(r.right(), Some('a'));
+  }
+
+  fn _or_either_debug() {
+    let _ = format!("{:?}", eat('a').or_either('b'));
+  }
+
+  fn _or_either_clone_copy() {
+    let c = eat('a').or_either('b');
+    let _c = c;
+    let _c = c.clone();
+  }
+}
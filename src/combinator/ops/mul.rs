@@ -92,6 +92,10 @@
 //!   123
 //! )
 //! ```
+//!
+//! If the accumulation may fail (e.g. integer overflow),
+//! use [`Combinator::try_fold`] to reject the whole combinator by returning
+//! [`ControlFlow::Break`].
 //! ## To the Heap
 //! If your accumulator requires heap allocation,
 //! each time the combinator is executed, the accumulator will be re-allocated and dropped.
@@ -137,6 +141,21 @@
 //!   3
 //! );
 //! ```
+//! If the separator values are meaningful (e.g. binary operators),
+//! use [`Combinator::sep_fold`] to fold them together with the values:
+//! ```
+//! # use whitehole::{combinator::{eat, next}, parser::Parser};
+//! let num = next(|c| c.is_ascii_digit()).select(|a| (a.content().as_bytes()[0] - b'0') as i32);
+//! let op = eat('+').bind(1) | eat('-').bind(-1);
+//! let entry = (num * (1..)).sep_fold(op, || 0, |acc, value, sign| match sign {
+//!   None => value,
+//!   Some(sign) => acc + sign * value,
+//! });
+//! assert_eq!(
+//!   Parser::builder().entry(entry).build("1-2+3").next().unwrap().value,
+//!   2
+//! );
+//! ```
 //! Or with array accumulator
 //! ```
 //! # use whitehole::{combinator::eat, parser::Parser};
@@ -146,22 +165,103 @@
 //!   [1, 1, 1]
 //! );
 //! ```
+//! The separator after the last repetition is not digested by default,
+//! use [`Combinator::sep_policy`] to allow or require it:
+//! ```
+//! # use whitehole::{combinator::{eat, ops::mul::SepPolicy}, parser::Parser};
+//! let entry = (eat('a') * (1..)).sep(',').sep_policy(SepPolicy::AllowTrailing);
+//! assert_eq!(
+//!   Parser::builder().entry(entry).build("a,a,a,").next().unwrap().digested,
+//!   6
+//! );
+//! ```
+//! With array accumulator, the repetition stops after `N` items
+//! and the following separator is left undigested (with the default policy),
+//! even if more items follow.
+//! Use [`Combinator::exactly_sep`] to reject in that case:
+//! ```
+//! # use whitehole::{combinator::eat, parser::Parser};
+//! let entry = (eat('a').bind(1) * [0; 3]).sep(',');
+//! let mut parser = Parser::builder().entry(entry).build("a,a,a,a");
+//! assert_eq!(parser.next().unwrap().digested, 5);
+//! assert_eq!(parser.instant.rest(), ",a");
+//!
+//! let entry = (eat('a').bind(1) * [0; 3]).exactly_sep(',');
+//! assert!(Parser::builder().entry(entry).build("a,a,a,a").next().is_none());
+//! ```
 //! See [`Combinator::sep`] for more information.
+//! # Count from the Value
+//! If the repetition count is parsed from the input (e.g. `3:a,b,c`),
+//! use [`Combinator::then_repeat`] to calculate the count from the value of a combinator,
+//! then repeat an other combinator exactly that many times.
+//! ```
+//! # use whitehole::{combinator::{bounded_uint, eat}, parser::Parser};
+//! let entry = (bounded_uint(0, 100) + ':').then_repeat(|n| *n as usize, eat('a')).sep(',');
+//! assert_eq!(
+//!   Parser::builder().entry(entry).build("3:a,a,a").next().unwrap().digested,
+//!   7
+//! );
+//! ```
+//! If the count is stored in the state (e.g. by a previous combinator),
+//! use [`Combinator::repeat_with`] to calculate the [`Repeat`] from the state at runtime.
+//! ```
+//! # use whitehole::{combinator::contextual, parser::Parser};
+//! contextual!(usize, ());
+//!
+//! let entry = eat("aa").then(|a| *a.state = 2) + eat('b').repeat_with(|n| *n);
+//! assert_eq!(
+//!   Parser::builder().state(0).entry(entry).build("aabbb").next().unwrap().digested,
+//!   4
+//! );
+//! ```
+//! # Until a Terminator
+//! If the items are terminated by a closing token (e.g. `]`),
+//! use [`Combinator::repeat_until`] to check the terminator before each repetition,
+//! so permissive items won't overrun the terminator.
+//! ```
+//! # use whitehole::{combinator::{next, preceded}, parser::Parser};
+//! let item = next(|c| c != ',').select(|a| a.content().to_string());
+//! let entry = preceded('[', item.repeat_until(']').consume_terminator(true).sep(','));
+//! assert_eq!(
+//!   Parser::builder().entry(entry).build("[a,b]c]").next().unwrap().value,
+//!   vec!["a".to_string(), "b".to_string()]
+//! );
+//! ```
+//! # Zero-width Repetitions
+//! If the combinator and the separator both accept with 0 bytes digested,
+//! an unbounded repetition (e.g. `* (..)` or `* (1..)`) will never stop.
+//! In debug builds this is detected at runtime and the combinator will panic
+//! with a clear message, in release builds it will loop forever.
+//! Bounded repetitions (e.g. `* 3`, `* (..=3)` and `* [v; N]`) are not affected.
+//! ```should_panic
+//! # use whitehole::{combinator::eat, parser::Parser};
+//! # #[cfg(not(debug_assertions))] panic!();
+//! // `eat('a').optional()` may accept with 0 bytes digested
+//! let entry = eat('a').optional() * (..);
+//! Parser::builder().entry(entry).build("b").next();
+//! ```
 mod fold;
 mod repeat;
+mod repeat_until;
 mod sep;
+mod then_repeat;
 
+pub use fold::*;
 pub use repeat::*;
+pub use repeat_until::*;
 pub use sep::*;
+pub use then_repeat::*;
 
 use crate::{
   action::{Action, Input, Output},
-  combinator::Combinator,
+  combinator::{Combinator, Describe, FirstSet, LookaheadSet},
   digest::Digest,
+  idx,
   instant::Instant,
 };
 use std::{
-  ops::{self, RangeFrom},
+  fmt::{self, Debug},
+  ops::{self, ControlFlow, RangeFrom},
   slice::SliceIndex,
 };
 
@@ -172,6 +272,9 @@ pub struct Mul<Lhs, Rhs, Sep = NoSep<Lhs>, Init = fn(), Fold = fn((), ())> {
   lhs: Lhs,
   rhs: Rhs,
   sep: Sep,
+  sep_policy: SepPolicy,
+  /// See [`Combinator::exactly_sep`].
+  exact: bool,
   init: Init,
   fold: Fold,
 }
@@ -183,12 +286,62 @@ impl<Lhs, Rhs> Mul<Lhs, Rhs> {
       lhs,
       rhs,
       sep: NoSep::new(),
+      sep_policy: SepPolicy::NoTrailing,
+      exact: false,
       init: || (),
       fold: |_, _| (),
     }
   }
 }
 
+impl<Lhs: Describe, Rhs: Debug, Sep: DescribeSep, Init, Fold> Describe
+  for Mul<Lhs, Rhs, Sep, Init, Fold>
+{
+  /// Write `(mul lhs rhs)` or `(mul lhs rhs (sep sep))`.
+  /// The folding closures are not described.
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("(mul ")?;
+    self.lhs.describe(f)?;
+    write!(f, " {:?}", self.rhs)?;
+    self.sep.describe_sep(f)?;
+    f.write_str(")")
+  }
+}
+
+/// The first of `lhs`, and the first of `sep` if `lhs` may accept without digesting.
+/// If `accept_zero` is `true`, the set is nullable.
+#[inline]
+fn repeat_first_set(
+  lhs: &impl FirstSet,
+  sep: &impl FirstSet,
+  accept_zero: bool,
+) -> Option<LookaheadSet> {
+  let item = lhs.first_set()?;
+  let mut set = if item.nullable {
+    item.union(&sep.first_set()?)
+  } else {
+    item
+  };
+  set.nullable |= accept_zero;
+  Some(set)
+}
+
+impl<Lhs: FirstSet, Rhs: Repeat, Sep: FirstSet, Init, Fold> FirstSet
+  for Mul<Lhs, Rhs, Sep, Init, Fold>
+{
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    repeat_first_set(&self.lhs, &self.sep, self.rhs.accept(0))
+  }
+}
+
+impl<Lhs: FirstSet, Value, const N: usize, Sep: FirstSet> FirstSet for Mul<Lhs, [Value; N], Sep> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    repeat_first_set(&self.lhs, &self.sep, N == 0)
+  }
+}
+
 impl<Lhs: Action, Rhs: Repeat> ops::Mul<Rhs> for Combinator<Lhs> {
   type Output = Combinator<Mul<Lhs, Rhs, NoSep<Lhs>>>;
 
@@ -209,27 +362,25 @@ impl<Lhs: Action, const N: usize> ops::Mul<[Lhs::Value; N]> for Combinator<Lhs>
   }
 }
 
-unsafe impl<
-    Lhs: Action<Text: Digest>,
-    Rhs: Repeat,
-    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
-    Acc,
-    Init: Fn() -> Acc,
-    Fold: Fn(Acc, Lhs::Value) -> Acc,
-  > Action for Mul<Lhs, Rhs, Sep, Init, Fold>
+impl<Lhs: Action<Text: Digest>, Rhs, Sep, Init, Fold> Mul<Lhs, Rhs, Sep, Init, Fold>
 where
   RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
 {
-  type Text = Lhs::Text;
-  type State = Lhs::State;
-  type Heap = Lhs::Heap;
-  type Value = Acc;
-
+  /// Execute with the resolved [`Repeat`].
+  /// `fold` receives each item with the value of the separator before it
+  /// ([`None`] for the first item).
+  /// The whole repetition is rejected if `fold` breaks.
   #[inline]
-  fn exec(
+  fn exec_repeat<Acc>(
     &self,
-    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
+    repeat: &impl Repeat,
+    fold: impl Fn(Acc, Lhs::Value, Option<Sep::Value>) -> ControlFlow<(), Acc>,
+    mut input: Input<&Instant<&Lhs::Text>, &mut Lhs::State, &mut Lhs::Heap>,
+  ) -> Option<Output<Acc>>
+  where
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Init: Fn() -> Acc,
+  {
     let mut repeated = 0;
     let mut output = Output {
       value: (self.init)(),
@@ -237,18 +388,36 @@ where
     };
 
     let mut digested_with_sep = 0;
-    while unsafe { self.rhs.validate(repeated) } {
+    // the value of the separator after the last repetition, if accepted
+    let mut sep_value = None;
+    // whether the separator after the last repetition is accepted
+    let mut trailing = false;
+    // whether another item is allowed by `repeat`
+    let mut more = unsafe { repeat.validate(repeated) };
+    while more {
+      #[cfg(debug_assertions)]
+      let start = digested_with_sep;
       let Some(value_output) = self.lhs.exec(
         input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested_with_sep) }),
       ) else {
         break;
       };
       repeated += 1;
-      output.value = (self.fold)(output.value, value_output.value);
+      output.value = match fold(output.value, value_output.value, sep_value.take()) {
+        ControlFlow::Continue(acc) => acc,
+        ControlFlow::Break(()) => return None,
+      };
       // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
       debug_assert!(usize::MAX - digested_with_sep > value_output.digested);
-      output.digested = unsafe { digested_with_sep.unchecked_add(value_output.digested) };
+      output.digested = idx::unchecked!(idx::add(digested_with_sep, value_output.digested));
+      trailing = false;
 
+      more = unsafe { repeat.validate(repeated) };
+      // skip the last separator if the max repetition is reached,
+      // unless trailing separators are expected
+      if !more && self.sep_policy == SepPolicy::NoTrailing {
+        break;
+      }
       let Some(sep_output) = self.sep.exec(
         input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(output.digested) }),
       ) else {
@@ -256,10 +425,68 @@ where
       };
       // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
       debug_assert!(usize::MAX - output.digested > sep_output.digested);
-      digested_with_sep = unsafe { output.digested.unchecked_add(sep_output.digested) };
+      digested_with_sep = idx::unchecked!(idx::add(output.digested, sep_output.digested));
+      sep_value = Some(sep_output.value);
+      trailing = true;
+
+      // the next iteration starts from the same position,
+      // which will loop forever if there is no upper bound
+      #[cfg(debug_assertions)]
+      assert!(
+        digested_with_sep != start || !repeat.is_unbounded(),
+        "infinite loop detected: the item and the separator digested 0 bytes in an unbounded repetition"
+      );
     }
 
-    self.rhs.accept(repeated).then_some(output)
+    if !repeat.accept(repeated) {
+      return None;
+    }
+    if repeated != 0 {
+      match self.sep_policy {
+        SepPolicy::NoTrailing => {}
+        SepPolicy::AllowTrailing => {
+          if trailing {
+            output.digested = digested_with_sep;
+          }
+        }
+        SepPolicy::RequireTrailing => {
+          if !trailing {
+            return None;
+          }
+          output.digested = digested_with_sep;
+        }
+      }
+    }
+    Some(output)
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    Rhs: Repeat,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value) -> Acc,
+  > Action for Mul<Lhs, Rhs, Sep, Init, Fold>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.exec_repeat(
+      &self.rhs,
+      |acc, value, _| ControlFlow::Continue((self.fold)(acc, value)),
+      input,
+    )
   }
 }
 
@@ -295,14 +522,55 @@ where
       )?;
       // SAFETY: `i` must be in `0..N`
       debug_assert!(i < N);
-      *unsafe { output.value.get_unchecked_mut(i) } = value_output.value;
+      *idx::unchecked!(idx::get_mut(&mut output.value, i)) = value_output.value;
       // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
       debug_assert!(usize::MAX - digested_with_sep > value_output.digested);
-      output.digested = unsafe { digested_with_sep.unchecked_add(value_output.digested) };
+      output.digested = idx::unchecked!(idx::add(digested_with_sep, value_output.digested));
 
       // SAFETY: `i` must be smaller than `N` and `N` is a valid usize
-      if unsafe { i.unchecked_add(1) } == N {
-        // skip the last separator if `N` is reached
+      if idx::unchecked!(idx::add(i, 1)) == N {
+        // reject if another separator and item follow in strict mode
+        if self.exact {
+          if let Some(sep_output) = self.sep.exec(
+            input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(output.digested) }),
+          ) {
+            // SAFETY: since `slice::len` is usize, so the sum must be a valid usize
+            debug_assert!(usize::MAX - output.digested > sep_output.digested);
+            let next = idx::unchecked!(idx::add(output.digested, sep_output.digested));
+            if self
+              .lhs
+              .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(next) }))
+              .is_some()
+            {
+              return None;
+            }
+          }
+        }
+
+        // skip the last separator if `N` is reached, unless trailing separators are expected
+        match self.sep_policy {
+          SepPolicy::NoTrailing => {}
+          SepPolicy::AllowTrailing => {
+            if let Some(sep_output) = self.sep.exec(
+              input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(output.digested) }),
+            ) {
+              // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
+              debug_assert!(usize::MAX - output.digested > sep_output.digested);
+              output.digested = idx::unchecked!(idx::add(output.digested, sep_output.digested));
+            }
+          }
+          SepPolicy::RequireTrailing => {
+            let sep_output =
+              self
+                .sep
+                .exec(input.reborrow_with(&unsafe {
+                  input.instant.to_digested_unchecked(output.digested)
+                }))?;
+            // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
+            debug_assert!(usize::MAX - output.digested > sep_output.digested);
+            output.digested = idx::unchecked!(idx::add(output.digested, sep_output.digested));
+          }
+        }
         break;
       }
 
@@ -311,7 +579,7 @@ where
       )?;
       // SAFETY: since `slice::len` is usize, so `output.digested` must be a valid usize
       debug_assert!(usize::MAX - output.digested > sep_output.digested);
-      digested_with_sep = unsafe { output.digested.unchecked_add(sep_output.digested) };
+      digested_with_sep = idx::unchecked!(idx::add(output.digested, sep_output.digested));
     }
 
     Some(output)
@@ -594,4 +862,120 @@ mod tests {
       }),
     );
   }
+
+  #[test]
+  fn combinator_mul_array_drop() {
+    use std::{cell::Cell, rc::Rc};
+
+    // count live instances to detect leaks and double drops
+    #[derive(Debug, PartialEq)]
+    struct Counted {
+      live: Rc<Cell<isize>>,
+      value: String,
+    }
+    impl Counted {
+      fn new(live: &Rc<Cell<isize>>, value: &str) -> Self {
+        live.set(live.get() + 1);
+        Self {
+          live: live.clone(),
+          value: value.to_string(),
+        }
+      }
+    }
+    impl Clone for Counted {
+      fn clone(&self) -> Self {
+        Self::new(&self.live, &self.value)
+      }
+    }
+    impl Drop for Counted {
+      fn drop(&mut self) {
+        self.live.set(self.live.get() - 1);
+      }
+    }
+
+    let live = Rc::new(Cell::new(0));
+    let exec = |input: &str, n: usize| {
+      let seed = Counted::new(&live, "");
+      let item = || {
+        let live = live.clone();
+        take(1).select(move |a| Counted::new(&live, a.content()))
+      };
+      let values = match n {
+        0 => (item() * [(); 0].map(|_| seed.clone()))
+          .exec(Input {
+            instant: &Instant::new(input),
+            state: &mut (),
+            heap: &mut (),
+          })
+          .map(|o| {
+            o.value
+              .into_iter()
+              .map(|c| c.value.clone())
+              .collect::<Vec<_>>()
+          }),
+        _ => (item().reject(|a| a.content() == "x") * [(); 3].map(|_| seed.clone()))
+          .sep(',')
+          .exec(Input {
+            instant: &Instant::new(input),
+            state: &mut (),
+            heap: &mut (),
+          })
+          .map(|o| {
+            o.value
+              .into_iter()
+              .map(|c| c.value.clone())
+              .collect::<Vec<_>>()
+          }),
+      };
+      drop(seed);
+      values
+    };
+
+    // accept
+    assert_eq!(
+      exec("a,b,c", 3),
+      Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+    );
+    assert_eq!(live.get(), 0);
+    // the second element rejects
+    assert_eq!(exec("a,x,c", 3), None);
+    assert_eq!(live.get(), 0);
+    // the separator rejects after the first element
+    assert_eq!(exec("ab", 3), None);
+    assert_eq!(live.get(), 0);
+    // not enough input
+    assert_eq!(exec("", 3), None);
+    assert_eq!(live.get(), 0);
+    // zero-length array
+    assert_eq!(exec("abc", 0), Some(vec![]));
+    assert_eq!(live.get(), 0);
+  }
+
+  #[test]
+  fn combinator_mul_zero_width_bounded() {
+    let zero = || take(0);
+    // bounded repetitions are fine with zero-width items
+    helper(zero() * 3, "123", Some(0));
+    helper(zero() * (1..3), "123", Some(0));
+    helper(zero() * (..=3), "123", Some(0));
+    helper((zero() * 3).sep(take(0)), "123", Some(0));
+    // zero-width separator with non-zero-width items
+    helper((take(1) * (..)).sep(take(0)), "123", Some(3));
+    // zero-width items with non-zero-width separator
+    helper((zero() * (..)).sep(take(1)), "123", Some(3));
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "infinite loop detected")]
+  fn combinator_mul_zero_width_range_from() {
+    helper(take(0) * (1..), "123", None);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "infinite loop detected")]
+  fn combinator_mul_zero_width_range_full() {
+    helper((take(0) * (..)).sep(take(0)), "123", None);
+  }
 }
@@ -1,5 +1,27 @@
-use super::Mul;
-use crate::combinator::Combinator;
+use super::{Mul, Repeat};
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, Range},
+  digest::Digest,
+  instant::Instant,
+  range::WithRange,
+};
+use std::{
+  ops::{ControlFlow, RangeFrom},
+  slice::SliceIndex,
+};
+
+/// A fallible fold function created by [`Combinator::try_fold`].
+#[derive(Debug, Clone, Copy)]
+pub struct TryFold<F> {
+  pub(super) fold: F,
+}
+
+/// A fold function with separator values created by [`Combinator::sep_fold`].
+#[derive(Debug, Clone, Copy)]
+pub struct SepFold<F> {
+  pub(super) fold: F,
+}
 
 impl<Lhs, Rhs, Sep, Init, Fold> Combinator<Mul<Lhs, Rhs, Sep, Init, Fold>> {
   /// Fold values with an ad-hoc accumulator.
@@ -35,21 +57,223 @@ impl<Lhs, Rhs, Sep, Init, Fold> Combinator<Mul<Lhs, Rhs, Sep, Init, Fold>> {
       lhs: self.action.lhs,
       rhs: self.action.rhs,
       sep: self.action.sep,
+      sep_policy: self.action.sep_policy,
+      exact: self.action.exact,
       init,
       fold,
     })
   }
+
+  /// Fold values with an ad-hoc accumulator which may stop the repetition.
+  ///
+  /// If `fold` returns [`ControlFlow::Break`],
+  /// the repetition stops and the whole combinator rejects,
+  /// instead of accepting what is consumed so far.
+  /// This is useful to reject invalid accumulations, e.g. integer overflow.
+  ///
+  /// See [`Self::fold`] for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::next, parser::Parser};
+  /// # use std::ops::ControlFlow;
+  /// let entry = || {
+  ///   (next(|c| c.is_ascii_digit()).select(|a| a.content().as_bytes()[0] - b'0') * (1..))
+  ///     .try_fold(
+  ///       || 0u8,
+  ///       |acc, value| match acc.checked_mul(10).and_then(|acc| acc.checked_add(value)) {
+  ///         Some(acc) => ControlFlow::Continue(acc),
+  ///         None => ControlFlow::Break(()),
+  ///       },
+  ///     )
+  /// };
+  /// assert_eq!(Parser::builder().entry(entry()).build("255").next().unwrap().value, 255);
+  /// assert!(Parser::builder().entry(entry()).build("256").next().is_none());
+  /// ```
+  #[inline]
+  pub fn try_fold<
+    Value,
+    Acc,
+    NewInit: Fn() -> Acc,
+    NewFold: Fn(Acc, Value) -> ControlFlow<(), Acc>,
+  >(
+    self,
+    init: NewInit,
+    fold: NewFold,
+  ) -> Combinator<Mul<Lhs, Rhs, Sep, NewInit, TryFold<NewFold>>> {
+    Combinator::new(Mul {
+      lhs: self.action.lhs,
+      rhs: self.action.rhs,
+      sep: self.action.sep,
+      sep_policy: self.action.sep_policy,
+      exact: self.action.exact,
+      init,
+      fold: TryFold { fold },
+    })
+  }
+
+  /// Specify an other combinator as the separator,
+  /// and fold values of both items and separators with an ad-hoc accumulator.
+  ///
+  /// `fold` is called for each item with the value of the separator before it,
+  /// which is [`None`] for the first item.
+  /// The value of a separator which is not followed by an item
+  /// (e.g. with [`SepPolicy::AllowTrailing`](crate::combinator::ops::mul::SepPolicy::AllowTrailing))
+  /// is dropped.
+  ///
+  /// This is useful when the separators are meaningful, e.g. binary operators.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, next}, parser::Parser};
+  /// let num = next(|c| c.is_ascii_digit()).select(|a| (a.content().as_bytes()[0] - b'0') as i32);
+  /// let op = eat('+').bind(1) | eat('-').bind(-1);
+  /// // left-associative
+  /// let entry = (num * (1..)).sep_fold(op, || 0, |acc, value, sign| match sign {
+  ///   None => value,
+  ///   Some(sign) => acc + sign * value,
+  /// });
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("1-2+3").next().unwrap().value,
+  ///   2
+  /// );
+  /// ```
+  #[inline]
+  pub fn sep_fold<
+    Value,
+    SepValue,
+    Acc,
+    NewSep,
+    NewInit: Fn() -> Acc,
+    NewFold: Fn(Acc, Value, Option<SepValue>) -> Acc,
+  >(
+    self,
+    sep: impl Into<Combinator<NewSep>>,
+    init: NewInit,
+    fold: NewFold,
+  ) -> Combinator<Mul<Lhs, Rhs, NewSep, NewInit, SepFold<NewFold>>> {
+    Combinator::new(Mul {
+      lhs: self.action.lhs,
+      rhs: self.action.rhs,
+      sep: sep.into().action,
+      sep_policy: self.action.sep_policy,
+      exact: self.action.exact,
+      init,
+      fold: SepFold { fold },
+    })
+  }
+
+  /// Fold values with an ad-hoc accumulator,
+  /// with the byte range of each item.
+  ///
+  /// The range is relative to the start of the whole input text
+  /// (like [`Combinator::range`]), and separators are excluded from item ranges.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::next, parser::Parser};
+  /// let entry = (next(|c| c.is_ascii_alphabetic()) * (1..))
+  ///   .sep(',')
+  ///   .fold_with_range(Vec::new, |mut acc, _, range| {
+  ///     acc.push(range);
+  ///     acc
+  ///   });
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("a,b,c").next().unwrap().value,
+  ///   vec![0..1, 2..3, 4..5]
+  /// );
+  /// ```
+  #[allow(clippy::type_complexity)]
+  #[inline]
+  pub fn fold_with_range<
+    Value,
+    Acc,
+    NewInit: Fn() -> Acc,
+    NewFold: Fn(Acc, Value, std::ops::Range<usize>) -> Acc,
+  >(
+    self,
+    init: NewInit,
+    fold: NewFold,
+  ) -> Combinator<Mul<Range<Lhs>, Rhs, Sep, NewInit, impl Fn(Acc, WithRange<Value>) -> Acc>> {
+    Combinator::new(Mul {
+      lhs: Combinator::new(self.action.lhs).range().action,
+      rhs: self.action.rhs,
+      sep: self.action.sep,
+      sep_policy: self.action.sep_policy,
+      exact: self.action.exact,
+      init,
+      fold: move |acc, value: WithRange<Value>| fold(acc, value.data, value.range),
+    })
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    Rhs: Repeat,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value) -> ControlFlow<(), Acc>,
+  > Action for Mul<Lhs, Rhs, Sep, Init, TryFold<Fold>>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.exec_repeat(
+      &self.rhs,
+      |acc, value, _| (self.fold.fold)(acc, value),
+      input,
+    )
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    Rhs: Repeat,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value, Option<Sep::Value>) -> Acc,
+  > Action for Mul<Lhs, Rhs, Sep, Init, SepFold<Fold>>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.exec_repeat(
+      &self.rhs,
+      |acc, value, sep| ControlFlow::Continue((self.fold.fold)(acc, value, sep)),
+      input,
+    )
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use crate::{
     action::{Action, Input},
-    combinator::{bytes, take, Bind, Combinator, Take},
+    combinator::{bytes, eat, next, ops::mul::SepPolicy, take, Bind, Combinator, Take},
     digest::Digest,
     instant::Instant,
   };
-  use std::{ops::RangeFrom, slice::SliceIndex};
+  use std::{
+    ops::{ControlFlow, RangeFrom},
+    slice::SliceIndex,
+  };
 
   fn helper<Text: ?Sized + Digest>(
     action: impl Action<Text = Text, State = (), Heap = (), Value = i32>,
@@ -185,4 +409,341 @@ mod tests {
     helper((rejecter() * (0..=0)).fold(init, fold), "123", 0);
     helper((rejecter_b() * (0..=0)).fold(init, fold), b"123", 0);
   }
+
+  fn ranges<Text: ?Sized + Digest>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Vec<std::ops::Range<usize>>>,
+    instant: &Instant<&Text>,
+  ) -> Option<(Vec<std::ops::Range<usize>>, usize)>
+  where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    action
+      .exec(Input {
+        instant,
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  fn push(
+    mut acc: Vec<std::ops::Range<usize>>,
+    _: i32,
+    range: std::ops::Range<usize>,
+  ) -> Vec<std::ops::Range<usize>> {
+    acc.push(range);
+    acc
+  }
+
+  #[test]
+  fn combinator_mul_fold_with_range() {
+    // without separator
+    assert_eq!(
+      ranges(
+        (accepter() * (..)).fold_with_range(Vec::new, push),
+        &Instant::new("a好c")
+      ),
+      Some((vec![0..1, 1..4, 4..5], 5))
+    );
+    assert_eq!(
+      ranges(
+        (accepter_b() * 2).fold_with_range(Vec::new, push),
+        &Instant::new(b"abc" as &[u8])
+      ),
+      Some((vec![0..1, 1..2], 2))
+    );
+
+    // separators are excluded from item ranges
+    assert_eq!(
+      ranges(
+        (accepter() * (..))
+          .sep("--")
+          .fold_with_range(Vec::new, push),
+        &Instant::new("a--b--c")
+      ),
+      Some((vec![0..1, 3..4, 6..7], 7))
+    );
+    assert_eq!(
+      ranges(
+        (accepter_b() * (..=3))
+          .sep(b',')
+          .fold_with_range(Vec::new, push),
+        &Instant::new(b"a,b," as &[u8])
+      ),
+      Some((vec![0..1, 2..3], 3))
+    );
+    // the trailing separator is digested but not included in item ranges
+    assert_eq!(
+      ranges(
+        (accepter() * (..=3))
+          .sep(',')
+          .sep_policy(SepPolicy::AllowTrailing)
+          .fold_with_range(Vec::new, push),
+        &Instant::new("a,b,")
+      ),
+      Some((vec![0..1, 2..3], 4))
+    );
+
+    // ranges are relative to the start of the whole input text
+    assert_eq!(
+      ranges(
+        (accepter() * (..)).sep(',').fold_with_range(Vec::new, push),
+        &unsafe { Instant::new("xx,a,b").to_digested_unchecked(3) }
+      ),
+      Some((vec![3..4, 5..6], 3))
+    );
+
+    // repeat for 0 times will accept with init value
+    assert_eq!(
+      ranges(
+        (rejecter() * (..)).fold_with_range(Vec::new, push),
+        &Instant::new("abc")
+      ),
+      Some((vec![], 0))
+    );
+    assert_eq!(
+      ranges(
+        (accepter() * (2..)).fold_with_range(Vec::new, push),
+        &Instant::new("a")
+      ),
+      None
+    );
+  }
+
+  fn exec_u8<Text: ?Sized + Digest>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = u8>,
+    input: &Text,
+  ) -> Option<(u8, usize)>
+  where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  fn digits(acc: u8, value: u8) -> ControlFlow<(), u8> {
+    match acc.checked_mul(10).and_then(|acc| acc.checked_add(value)) {
+      Some(acc) => ControlFlow::Continue(acc),
+      None => ControlFlow::Break(()),
+    }
+  }
+
+  fn sum(acc: u8, value: u8) -> ControlFlow<(), u8> {
+    acc
+      .checked_add(value)
+      .map_or(ControlFlow::Break(()), ControlFlow::Continue)
+  }
+
+  #[test]
+  fn combinator_mul_try_fold() {
+    let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().as_bytes()[0] - b'0');
+    let digit_b = || bytes::next(|b| b.is_ascii_digit()).select(|a| a.content()[0] - b'0');
+
+    // no overflow
+    assert_eq!(
+      exec_u8((digit() * (1..)).try_fold(|| 0, digits), "255a"),
+      Some((255, 3))
+    );
+    assert_eq!(
+      exec_u8((digit_b() * (1..)).try_fold(|| 0, digits), b"042"),
+      Some((42, 3))
+    );
+    // overflow mid-way rejects the whole combinator
+    assert_eq!(
+      exec_u8((digit() * (1..)).try_fold(|| 0, digits), "256"),
+      None
+    );
+    assert_eq!(
+      exec_u8((digit_b() * (1..)).try_fold(|| 0, digits), b"1000"),
+      None
+    );
+    // even if the repetition is already acceptable
+    assert_eq!(
+      exec_u8((digit() * (..)).try_fold(|| 0, digits), "2560"),
+      None
+    );
+    // items after the repetition ends are not folded
+    assert_eq!(exec_u8((digit() * 3).try_fold(|| 0, digits), "2560"), None);
+    assert_eq!(
+      exec_u8((digit() * 2).try_fold(|| 0, digits), "2560"),
+      Some((25, 2))
+    );
+    // repeat for 0 times will accept with init value
+    assert_eq!(
+      exec_u8((digit() * (..)).try_fold(|| 0, digits), "a"),
+      Some((0, 0))
+    );
+  }
+
+  #[test]
+  fn combinator_mul_try_fold_sep() {
+    let num = || {
+      (next(|c| c.is_ascii_digit()).select(|a| a.content().as_bytes()[0] - b'0') * (1..))
+        .try_fold(|| 0, digits)
+    };
+
+    // separators are not counted after the last item
+    assert_eq!(
+      exec_u8((num() * (..)).sep(',').try_fold(|| 0, sum), "100,100,x"),
+      Some((200, 7))
+    );
+    assert_eq!(
+      exec_u8(
+        (num() * (..))
+          .sep(',')
+          .sep_policy(SepPolicy::AllowTrailing)
+          .try_fold(|| 0, sum),
+        "100,100,x"
+      ),
+      Some((200, 8))
+    );
+    // break after a separator rejects the whole combinator,
+    // the consumed separator is not counted
+    assert_eq!(
+      exec_u8((num() * (..)).sep(',').try_fold(|| 0, sum), "200,100"),
+      None
+    );
+    // inner overflow makes the item reject, the repetition stops before the separator
+    assert_eq!(
+      exec_u8((num() * (..)).sep(',').try_fold(|| 0, sum), "200,300"),
+      Some((200, 3))
+    );
+  }
+
+  #[test]
+  fn combinator_repeat_with_try_fold() {
+    crate::contextual!(usize, ());
+
+    let entry = bytes::take(1)
+      .select(|a| a.content()[0])
+      .repeat_with(|n| *n)
+      .try_fold(|| 0, sum);
+    let exec = |state: usize, input: &[u8]| {
+      entry
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut { state },
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    };
+    assert_eq!(exec(2, b"\x64\x64\x64"), Some((200, 2)));
+    assert_eq!(exec(3, b"\x64\x64\x64"), None);
+  }
+
+  #[derive(Debug, Clone, Copy, PartialEq)]
+  enum Op {
+    Add,
+    Sub,
+  }
+
+  #[derive(Debug, PartialEq)]
+  enum Expr {
+    Num(u8),
+    Binary(Box<Expr>, Op, Box<Expr>),
+  }
+
+  fn num(n: u8) -> Expr {
+    Expr::Num(n)
+  }
+
+  fn binary(lhs: Expr, op: Op, rhs: Expr) -> Expr {
+    Expr::Binary(Box::new(lhs), op, Box::new(rhs))
+  }
+
+  fn build(acc: Option<Expr>, value: u8, op: Option<Op>) -> Option<Expr> {
+    Some(match (acc, op) {
+      (Some(lhs), Some(op)) => binary(lhs, op, num(value)),
+      (None, None) => num(value),
+      _ => unreachable!(),
+    })
+  }
+
+  #[test]
+  fn combinator_mul_sep_fold() {
+    let expr = || {
+      (next(|c| c.is_ascii_digit()).select(|a| a.content().as_bytes()[0] - b'0') * (1..)).sep_fold(
+        eat('+').bind(Op::Add) | eat('-').bind(Op::Sub),
+        || None,
+        build,
+      )
+    };
+    let exec = |action: &dyn Action<Text = str, State = (), Heap = (), Value = Option<Expr>>,
+                input: &str| {
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    };
+
+    // left-associative
+    assert_eq!(
+      exec(&expr(), "1-2+3"),
+      Some((
+        Some(binary(binary(num(1), Op::Sub, num(2)), Op::Add, num(3))),
+        5
+      ))
+    );
+    // the first item has no separator
+    assert_eq!(exec(&expr(), "1"), Some((Some(num(1)), 1)));
+    // the trailing separator is not digested and not folded
+    assert_eq!(
+      exec(&expr(), "1+2-"),
+      Some((Some(binary(num(1), Op::Add, num(2))), 3))
+    );
+    assert_eq!(
+      exec(&expr().sep_policy(SepPolicy::AllowTrailing), "1+2-"),
+      Some((Some(binary(num(1), Op::Add, num(2))), 4))
+    );
+    assert_eq!(
+      exec(&expr().sep_policy(SepPolicy::RequireTrailing), "1+2-"),
+      Some((Some(binary(num(1), Op::Add, num(2))), 4))
+    );
+    // reject
+    assert_eq!(exec(&expr(), "+1"), None);
+  }
+
+  #[test]
+  fn combinator_repeat_with_sep_fold() {
+    crate::contextual!(usize, ());
+
+    let entry = (next(|c| c.is_ascii_digit())
+      .select(|a| (a.content().as_bytes()[0] - b'0') as i32))
+    .repeat_with(|n| *n)
+    .sep_fold(
+      eat('+').bind(1) | eat('-').bind(-1),
+      || 0,
+      |acc, value, sign| sign.map_or(value, |sign| acc + sign * value),
+    );
+    let exec = |state: usize, input: &str| {
+      entry
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut { state },
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    };
+    assert_eq!(exec(3, "9-5-3+1"), Some((1, 5)));
+    assert_eq!(exec(4, "9-5-3+1"), Some((2, 7)));
+    assert_eq!(exec(5, "9-5-3+1"), None);
+  }
+
+  fn _try_fold_debug_clone_copy() {
+    let c = (take(1).bind(1u8) * (..)).try_fold(
+      (|| 0) as fn() -> u8,
+      sum as fn(u8, u8) -> ControlFlow<(), u8>,
+    );
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
 }
@@ -1,4 +1,15 @@
-use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use super::{Mul, SepFold, TryFold};
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  digest::Digest,
+  idx,
+  instant::Instant,
+};
+use std::{
+  ops::{ControlFlow, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
+  slice::SliceIndex,
+};
 
 /// A helper trait to represent repetition when performing `*`
 /// on [`Combinator`](crate::combinator::Combinator)s.
@@ -19,6 +30,14 @@ pub trait Repeat {
   /// Check if the repetition should be accepted
   /// based on the current repeated times.
   fn accept(&self, repeated: usize) -> bool;
+
+  /// Return `true` if the repetition has no upper bound, e.g. `..` and `1..`.
+  /// This is used to detect zero-width infinite loops in debug builds.
+  /// The default implementation returns `false`.
+  #[inline]
+  fn is_unbounded(&self) -> bool {
+    false
+  }
 }
 
 impl Repeat for usize {
@@ -37,7 +56,7 @@ impl Repeat for Range<usize> {
   #[inline]
   unsafe fn validate(&self, repeated: usize) -> bool {
     debug_assert!(self.end >= repeated);
-    self.end.unchecked_sub(repeated) > 1
+    idx::sub(self.end, repeated) > 1
   }
 
   #[inline]
@@ -56,6 +75,11 @@ impl Repeat for RangeFrom<usize> {
   fn accept(&self, repeated: usize) -> bool {
     self.contains(&repeated)
   }
+
+  #[inline]
+  fn is_unbounded(&self) -> bool {
+    true
+  }
 }
 
 impl Repeat for RangeFull {
@@ -68,6 +92,11 @@ impl Repeat for RangeFull {
   fn accept(&self, _: usize) -> bool {
     true
   }
+
+  #[inline]
+  fn is_unbounded(&self) -> bool {
+    true
+  }
 }
 
 impl Repeat for RangeInclusive<usize> {
@@ -86,7 +115,7 @@ impl Repeat for RangeTo<usize> {
   #[inline]
   unsafe fn validate(&self, repeated: usize) -> bool {
     debug_assert!(self.end >= repeated);
-    self.end.unchecked_sub(repeated) > 1
+    idx::sub(self.end, repeated) > 1
   }
 
   #[inline]
@@ -107,7 +136,163 @@ impl Repeat for RangeToInclusive<usize> {
   }
 }
 
+/// A helper struct to calculate the [`Repeat`] from the state at runtime.
+/// See [`Combinator::repeat_with`].
+#[derive(Clone, Copy)]
+pub struct RepeatWith<F> {
+  count: F,
+}
+
+impl<F> std::fmt::Debug for RepeatWith<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("RepeatWith").finish()
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    R: Repeat,
+    F: Fn(&Lhs::State) -> R,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value) -> Acc,
+  > Action for Mul<Lhs, RepeatWith<F>, Sep, Init, Fold>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    // resolve the count once per execution
+    let repeat = (self.rhs.count)(input.state);
+    self.exec_repeat(
+      &repeat,
+      |acc, value, _| ControlFlow::Continue((self.fold)(acc, value)),
+      input,
+    )
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    R: Repeat,
+    F: Fn(&Lhs::State) -> R,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value) -> ControlFlow<(), Acc>,
+  > Action for Mul<Lhs, RepeatWith<F>, Sep, Init, TryFold<Fold>>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let repeat = (self.rhs.count)(input.state);
+    self.exec_repeat(&repeat, |acc, value, _| (self.fold.fold)(acc, value), input)
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    R: Repeat,
+    F: Fn(&Lhs::State) -> R,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value, Option<Sep::Value>) -> Acc,
+  > Action for Mul<Lhs, RepeatWith<F>, Sep, Init, SepFold<Fold>>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let repeat = (self.rhs.count)(input.state);
+    self.exec_repeat(
+      &repeat,
+      |acc, value, sep| ControlFlow::Continue((self.fold.fold)(acc, value, sep)),
+      input,
+    )
+  }
+}
+
+impl<T: Action> Combinator<T> {
+  /// Repeat self with a [`Repeat`] calculated from [`Input::state`](crate::action::Input::state).
+  ///
+  /// `count` is called once each time the new combinator is executed,
+  /// before the first repetition.
+  /// This is useful when the count is parsed by a previous combinator
+  /// and stored in the state, e.g. length-prefixed formats.
+  /// If the count is available in the value of the previous combinator,
+  /// consider [`Combinator::then_repeat`] instead.
+  ///
+  /// Any [`Repeat`] can be returned, so both bounds can come from the state,
+  /// e.g. `|s| s.min..=s.max` for a header which specifies the range of the items.
+  ///
+  /// Just like `*`, you can use [`Combinator::sep`], [`Combinator::fold`],
+  /// [`Combinator::try_fold`] and [`Combinator::sep_fold`] after this.
+  /// Array accumulators are not supported since the length must be known at compile time.
+  /// See [`ops::mul`](crate::combinator::ops::mul) for more information.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::contextual, parser::Parser};
+  /// contextual!(usize, ());
+  ///
+  /// // a length byte, then that many payload bytes
+  /// let entry = bytes::take(1).then(|a| *a.state = a.content()[0] as usize)
+  ///   + bytes::take(1).repeat_with(|len| *len);
+  /// assert_eq!(
+  ///   Parser::builder().state(0).entry(entry).build(b"\x02ab").next().unwrap().digested,
+  ///   3
+  /// );
+  /// ```
+  /// With both the min and max from the state:
+  /// ```
+  /// # use whitehole::{combinator::contextual, parser::Parser};
+  /// contextual!((usize, usize), ());
+  ///
+  /// // the min and max bytes, then min..=max payload bytes
+  /// let entry = bytes::take(2).then(|a| *a.state = (a.content()[0] as usize, a.content()[1] as usize))
+  ///   + bytes::take(1).repeat_with(|(min, max)| *min..=*max);
+  /// let parse = |s| Parser::builder().state((0, 0)).entry(&entry).build(s).next().map(|o| o.digested);
+  /// assert_eq!(parse(b"\x02\x03a"), None);
+  /// assert_eq!(parse(b"\x02\x03ab"), Some(4));
+  /// assert_eq!(parse(b"\x02\x03abcd"), Some(5));
+  /// ```
+  #[inline]
+  pub fn repeat_with<R: Repeat, F: Fn(&T::State) -> R>(
+    self,
+    count: F,
+  ) -> Combinator<Mul<T, RepeatWith<F>>> {
+    Combinator::new(Mul::new(self.action, RepeatWith { count }))
+  }
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
   use super::*;
 
@@ -219,4 +404,168 @@ mod tests {
     assert_eq!((..=3).accept(3), true);
     assert_eq!((..=3).accept(4), false);
   }
+
+  #[test]
+  fn combinator_repeat_with() {
+    use crate::combinator::contextual;
+
+    contextual!(usize, ());
+
+    fn helper(
+      action: impl Action<Text = [u8], State = usize, Heap = (), Value = ()>,
+      input: &[u8],
+    ) -> Option<usize> {
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut 0,
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    }
+
+    // a length byte, then exactly that many payload bytes
+    let entry = || {
+      bytes::take(1).then(|a| *a.state = a.content()[0] as usize)
+        + bytes::take(1).repeat_with(|len| *len)
+    };
+    assert_eq!(helper(entry(), b"\x03abc"), Some(4));
+    // extra bytes are not digested
+    assert_eq!(helper(entry(), b"\x02abc"), Some(3));
+    // length 0
+    assert_eq!(helper(entry(), b"\x00abc"), Some(1));
+    assert_eq!(helper(entry(), b"\x00"), Some(1));
+    // length larger than the remaining input
+    assert_eq!(helper(entry(), b"\x04abc"), None);
+    assert_eq!(helper(entry(), b"\xff"), None);
+
+    // the count is resolved once per execution
+    let entry = bytes::take(1)
+      .then(|a| *a.state += 1)
+      .repeat_with(|n| *n + 2);
+    assert_eq!(helper(entry, b"abcd"), Some(2));
+
+    // with ranges, sep and fold
+    let entry = (bytes::take(1).bind(10).repeat_with(|len| ..=*len))
+      .sep(bytes::eat(b','))
+      .fold(|| 0, |acc, v| acc + v)
+      .then(|a| *a.state = *a.output().value);
+    let mut state = 2;
+    assert_eq!(
+      entry
+        .exec(Input {
+          instant: &Instant::new(b"a,b,c" as &[u8]),
+          state: &mut state,
+          heap: &mut (),
+        })
+        .map(|o| o.digested),
+      Some(3)
+    );
+    // the sum of 2 items
+    assert_eq!(state, 20);
+  }
+
+  #[test]
+  fn combinator_repeat_with_min_max() {
+    use crate::combinator::{contextual, preceded};
+    use std::ops::ControlFlow;
+
+    // (min, max)
+    contextual!((usize, usize), ());
+
+    fn helper<T: Action<Text = [u8], State = (usize, usize), Heap = ()>>(
+      action: T,
+      input: &[u8],
+    ) -> Option<Output<T::Value>> {
+      action.exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (0, 0),
+        heap: &mut (),
+      })
+    }
+
+    // the header sets the bounds, the payload is repeated within them
+    let header = || {
+      bytes::take(2).then(|a| {
+        a.state.0 = a.content()[0] as usize;
+        a.state.1 = a.content()[1] as usize;
+      })
+    };
+    let item = || bytes::next(|b| b.is_ascii_lowercase());
+    let entry = || header() + item().repeat_with(|(min, max)| *min..=*max);
+    assert_eq!(helper(entry(), b"\x02\x03a").map(|o| o.digested), None);
+    assert_eq!(helper(entry(), b"\x02\x03ab").map(|o| o.digested), Some(4));
+    assert_eq!(helper(entry(), b"\x02\x03abc").map(|o| o.digested), Some(5));
+    // stop at max
+    assert_eq!(
+      helper(entry(), b"\x02\x03abcd").map(|o| o.digested),
+      Some(5)
+    );
+
+    // with sep and fold
+    let entry = || {
+      preceded(
+        header(),
+        item()
+          .bind(1)
+          .repeat_with(|(min, max)| *min..=*max)
+          .sep(bytes::eat(b','))
+          .fold(|| 0, |acc, v| acc + v),
+      )
+    };
+    let items = |input| helper(entry(), input).map(|o| (o.value, o.digested));
+    assert_eq!(items(b"\x02\x03a"), None);
+    assert_eq!(items(b"\x02\x03a,b"), Some((2, 5)));
+    assert_eq!(items(b"\x02\x03a,b,c"), Some((3, 7)));
+    assert_eq!(items(b"\x02\x03a,b,c,d"), Some((3, 7)));
+
+    // with try_fold
+    let entry = || {
+      preceded(
+        header(),
+        item()
+          .select(|a| a.content()[0])
+          .repeat_with(|(min, max)| *min..=*max)
+          .try_fold(Vec::new, |mut acc, v| {
+            if v == b'x' {
+              return ControlFlow::Break(());
+            }
+            acc.push(v);
+            ControlFlow::Continue(acc)
+          }),
+      )
+    };
+    assert_eq!(
+      helper(entry(), b"\x01\x02abc").map(|o| o.value),
+      Some(vec![b'a', b'b'])
+    );
+    assert_eq!(helper(entry(), b"\x01\x02ax").map(|o| o.value), None);
+
+    // with sep_fold
+    let entry = || {
+      preceded(
+        header(),
+        item()
+          .bind(1)
+          .repeat_with(|(min, max)| *min..=*max)
+          .sep_fold(
+            bytes::eat(b'+').bind(1) | bytes::eat(b'-').bind(-1),
+            || 0,
+            |acc, v, sign| acc + sign.unwrap_or(1) * v,
+          ),
+      )
+    };
+    assert_eq!(
+      helper(entry(), b"\x02\x03a-b+c-d").map(|o| (o.value, o.digested)),
+      Some((1, 7))
+    );
+    assert_eq!(helper(entry(), b"\x02\x03a-").map(|o| o.value), None);
+  }
+
+  fn _repeat_with_debug_clone_copy() {
+    let c = crate::combinator::eat('a').repeat_with(|_| 1);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
 }
@@ -0,0 +1,354 @@
+use super::{DescribeSep, NoSep};
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, Describe},
+  digest::Digest,
+  idx,
+  instant::Instant,
+};
+use std::{fmt, ops::RangeFrom, slice::SliceIndex};
+
+/// An [`Action`] created by [`Combinator::repeat_until`].
+#[derive(Clone, Copy)]
+pub struct RepeatUntil<Lhs, Terminator, Sep, Init, Fold> {
+  lhs: Lhs,
+  terminator: Terminator,
+  consume_terminator: bool,
+  sep: Sep,
+  init: Init,
+  fold: Fold,
+}
+
+impl<Lhs: fmt::Debug, Terminator: fmt::Debug, Sep: fmt::Debug, Init, Fold> fmt::Debug
+  for RepeatUntil<Lhs, Terminator, Sep, Init, Fold>
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("RepeatUntil")
+      .field("lhs", &self.lhs)
+      .field("terminator", &self.terminator)
+      .field("consume_terminator", &self.consume_terminator)
+      .field("sep", &self.sep)
+      .finish()
+  }
+}
+
+impl<Lhs: Describe, Terminator: Describe, Sep: DescribeSep, Init, Fold> Describe
+  for RepeatUntil<Lhs, Terminator, Sep, Init, Fold>
+{
+  /// Write `(repeat_until lhs terminator)` or `(repeat_until lhs terminator (sep sep))`.
+  /// The folding closures are not described.
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("(repeat_until ")?;
+    self.lhs.describe(f)?;
+    f.write_str(" ")?;
+    self.terminator.describe(f)?;
+    self.sep.describe_sep(f)?;
+    f.write_str(")")
+  }
+}
+
+impl<T: Action> Combinator<T> {
+  /// Repeat self until the `terminator` accepts.
+  ///
+  /// Before each repetition, the `terminator` is tried first at the current position.
+  /// If it accepts, the repetition stops successfully,
+  /// otherwise self must accept, or the whole combinator rejects
+  /// (e.g. when the input ends without the terminator).
+  /// Since the terminator is checked first, items which could also match the terminator
+  /// (e.g. a permissive [`take`](crate::combinator::take)) won't overrun it.
+  ///
+  /// The terminator is not digested by default, use [`Combinator::consume_terminator`] to change it.
+  ///
+  /// The value is the values of self accumulated in a [`Vec`].
+  /// Use [`Combinator::sep`] to set a separator between items,
+  /// and [`Combinator::fold`] to use an ad-hoc accumulator,
+  /// just like [`ops::mul`](crate::combinator::ops::mul).
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{preceded, take}, parser::Parser};
+  /// // a block comment, `take(1)` would also accept `*` but the terminator is checked first
+  /// let entry = preceded("/*", take(1).repeat_until("*/").consume_terminator(true));
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("/* a*b */ c").next().unwrap().digested,
+  ///   9
+  /// );
+  /// ```
+  #[allow(clippy::type_complexity)]
+  #[inline]
+  pub fn repeat_until<Terminator>(
+    self,
+    terminator: impl Into<Combinator<Terminator>>,
+  ) -> Combinator<
+    RepeatUntil<
+      T,
+      Terminator,
+      NoSep<T>,
+      fn() -> Vec<T::Value>,
+      fn(Vec<T::Value>, T::Value) -> Vec<T::Value>,
+    >,
+  > {
+    Combinator::new(RepeatUntil {
+      lhs: self.action,
+      terminator: terminator.into().action,
+      consume_terminator: false,
+      sep: NoSep::new(),
+      init: Vec::new,
+      fold: |mut acc, value| {
+        acc.push(value);
+        acc
+      },
+    })
+  }
+}
+
+impl<Lhs, Terminator, Sep, Init, Fold> Combinator<RepeatUntil<Lhs, Terminator, Sep, Init, Fold>> {
+  /// Set whether to digest the terminator after [`Combinator::repeat_until`].
+  /// The default is `false`.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::eat, parser::Parser};
+  /// let entry = eat('a').repeat_until(';');
+  /// assert_eq!(Parser::builder().entry(entry).build("aa;").next().unwrap().digested, 2);
+  /// let entry = eat('a').repeat_until(';').consume_terminator(true);
+  /// assert_eq!(Parser::builder().entry(entry).build("aa;").next().unwrap().digested, 3);
+  /// ```
+  #[inline]
+  pub fn consume_terminator(self, consume: bool) -> Self {
+    Combinator::new(RepeatUntil {
+      consume_terminator: consume,
+      ..self.action
+    })
+  }
+
+  /// Specify an other combinator as the separator between repeated items
+  /// after [`Combinator::repeat_until`].
+  /// The separator is not executed before the first item,
+  /// and a separator before the terminator is not allowed.
+  ///
+  /// Tips: you can use [`char`], `&str`, [`String`], [`u8`], `&[u8]` and [`Vec<u8>`] as the shorthand
+  /// for [`eat`](crate::combinator::eat) in the separator.
+  #[inline]
+  pub fn sep<NewSep>(
+    self,
+    sep: impl Into<Combinator<NewSep>>,
+  ) -> Combinator<RepeatUntil<Lhs, Terminator, NewSep, Init, Fold>> {
+    Combinator::new(RepeatUntil {
+      lhs: self.action.lhs,
+      terminator: self.action.terminator,
+      consume_terminator: self.action.consume_terminator,
+      sep: sep.into().action,
+      init: self.action.init,
+      fold: self.action.fold,
+    })
+  }
+
+  /// Fold values of repeated items with an ad-hoc accumulator
+  /// after [`Combinator::repeat_until`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{next, preceded}, parser::Parser};
+  /// let digit = next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<i32>().unwrap());
+  /// let entry = preceded(
+  ///   '[',
+  ///   digit.repeat_until(']').consume_terminator(true).sep(',').fold(|| 0, |acc, v| acc + v),
+  /// );
+  /// assert_eq!(Parser::builder().entry(entry).build("[1,2,3]").next().unwrap().value, 6);
+  /// ```
+  #[inline]
+  pub fn fold<Value, Acc, NewInit: Fn() -> Acc, NewFold: Fn(Acc, Value) -> Acc>(
+    self,
+    init: NewInit,
+    fold: NewFold,
+  ) -> Combinator<RepeatUntil<Lhs, Terminator, Sep, NewInit, NewFold>> {
+    Combinator::new(RepeatUntil {
+      lhs: self.action.lhs,
+      terminator: self.action.terminator,
+      consume_terminator: self.action.consume_terminator,
+      sep: self.action.sep,
+      init,
+      fold,
+    })
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    Terminator: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Lhs::Value) -> Acc,
+  > Action for RepeatUntil<Lhs, Terminator, Sep, Init, Fold>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = Acc;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut acc = (self.init)();
+    let mut digested = 0;
+    let mut first = true;
+    loop {
+      if let Some(terminator_output) = self
+        .terminator
+        .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }))
+      {
+        if self.consume_terminator {
+          // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+          debug_assert!(usize::MAX - digested >= terminator_output.digested);
+          digested = idx::unchecked!(idx::add(digested, terminator_output.digested));
+        }
+        return Some(Output {
+          value: acc,
+          digested,
+        });
+      }
+
+      #[cfg(debug_assertions)]
+      let start = digested;
+      if !first {
+        // skip the separator before the first item
+        let sep_output = self
+          .sep
+          .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }))?;
+        // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+        debug_assert!(usize::MAX - digested >= sep_output.digested);
+        digested = idx::unchecked!(idx::add(digested, sep_output.digested));
+      }
+      first = false;
+
+      let lhs_output = self
+        .lhs
+        .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }))?;
+      acc = (self.fold)(acc, lhs_output.value);
+      // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+      debug_assert!(usize::MAX - digested >= lhs_output.digested);
+      digested = idx::unchecked!(idx::add(digested, lhs_output.digested));
+
+      // the next iteration starts from the same position,
+      // which will loop forever since the terminator is already rejected
+      #[cfg(debug_assertions)]
+      assert!(
+        digested != start,
+        "infinite loop detected: the item and the separator digested 0 bytes before the terminator"
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{bytes, eat, next, preceded, recur, take, whitespaces};
+  use std::fmt::Debug;
+
+  fn helper<Text: ?Sized + Digest, Value: PartialEq + Debug>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    expected: Option<(Value, usize)>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    );
+  }
+
+  #[test]
+  fn combinator_repeat_until_json_array() {
+    // a json array of numbers or nested arrays, count the leaf numbers
+    let (value, setter) = recur::<usize>();
+    let ws = || whitespaces() * (..=1);
+    let number = (next(|c| c.is_ascii_digit()) * (1..)).bind(1);
+    let array = preceded(
+      eat('[') + ws(),
+      (value() + ws())
+        .repeat_until(']')
+        .consume_terminator(true)
+        .sep(eat(',') + ws())
+        .fold(|| 0, |acc, v| acc + v),
+    );
+    setter.boxed(number | array);
+
+    helper(value(), "[]", Some((0, 2)));
+    helper(value(), "[ ]", Some((0, 3)));
+    helper(value(), "[1, 2 ,3]", Some((3, 9)));
+    helper(value(), "[[1], [], [2, [3, 4]]]", Some((4, 22)));
+    // unterminated
+    helper(value(), "[", None);
+    helper(value(), "[1, 2", None);
+    helper(value(), "[[1]", None);
+    // trailing separator is not allowed
+    helper(value(), "[1,]", None);
+    // missing separator
+    helper(value(), "[1 2]", None);
+  }
+
+  #[test]
+  fn combinator_repeat_until_terminator_lookalike() {
+    // `take(1)` would also accept `*`
+    let entry = || take(1).repeat_until("*/").fold(|| (), |_, _| ());
+    helper(entry(), "a*b*/", Some(((), 3)));
+    helper(entry(), "*/", Some(((), 0)));
+    helper(entry().consume_terminator(true), "a*b*/c", Some(((), 5)));
+    helper(entry(), "a*b*", None);
+
+    // default accumulator
+    let entry = next(|_| true)
+      .select(|a| a.content().chars().next().unwrap())
+      .repeat_until(']');
+    helper(entry, "ab]c]", Some((vec!['a', 'b'], 2)));
+    helper(entry, "]", Some((vec![], 0)));
+
+    // bytes
+    let entry = bytes::take(1)
+      .select(|a| a.content()[0])
+      .repeat_until(b"\r\n")
+      .consume_terminator(true);
+    helper(entry, b"ab\r\r\n", Some((b"ab\r".to_vec(), 5)));
+    helper(entry, b"ab\r", None);
+  }
+
+  #[test]
+  #[cfg(debug_assertions)]
+  #[should_panic(expected = "infinite loop detected")]
+  fn combinator_repeat_until_zero_width() {
+    helper(
+      eat('a').optional().repeat_until(';').fold(|| (), |_, _| ()),
+      "b",
+      None,
+    );
+  }
+
+  #[test]
+  fn combinator_repeat_until_describe() {
+    assert_eq!(
+      eat('a').repeat_until(']').sep(',').describe(),
+      "(repeat_until (eat 'a') (eat ']') (sep (eat ',')))"
+    );
+  }
+
+  fn _repeat_until_debug() {
+    let _ = format!("{:?}", eat('a').repeat_until(']').sep(','));
+  }
+
+  fn _repeat_until_clone_copy() {
+    let c = eat('a').repeat_until(']');
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -1,10 +1,10 @@
 use super::Mul;
 use crate::{
   action::{Action, Input, Output},
-  combinator::Combinator,
+  combinator::{Combinator, Describe, FirstSet, LookaheadSet},
   instant::Instant,
 };
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
 /// A util struct to represent no separator.
 /// See [`ops::mul`](crate::combinator::ops::mul) for more information.
@@ -21,6 +21,16 @@ impl<Lhs> NoSep<Lhs> {
   }
 }
 
+// implement manually to avoid the `Lhs: Clone` bound
+impl<Lhs> Clone for NoSep<Lhs> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Lhs> Copy for NoSep<Lhs> {}
+
 impl<Lhs> Default for NoSep<Lhs> {
   #[inline]
   fn default() -> Self {
@@ -28,6 +38,35 @@ impl<Lhs> Default for NoSep<Lhs> {
   }
 }
 
+/// Describe the separator of [`Mul`] as ` (sep ...)`, or nothing for [`NoSep`].
+pub(crate) trait DescribeSep {
+  fn describe_sep(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<Lhs> DescribeSep for NoSep<Lhs> {
+  #[inline]
+  fn describe_sep(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Ok(())
+  }
+}
+
+impl<Sep: Describe> DescribeSep for Sep {
+  #[inline]
+  fn describe_sep(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(" (sep ")?;
+    self.describe(f)?;
+    f.write_str(")")
+  }
+}
+
+/// Never digest.
+impl<Lhs> FirstSet for NoSep<Lhs> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::empty())
+  }
+}
+
 unsafe impl<Lhs: Action> Action for NoSep<Lhs> {
   type Text = Lhs::Text;
   type State = Lhs::State;
@@ -47,6 +86,20 @@ unsafe impl<Lhs: Action> Action for NoSep<Lhs> {
   }
 }
 
+/// How to handle the separator after the last repetition.
+/// See [`Combinator::sep_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SepPolicy {
+  /// The separator after the last repetition is not digested.
+  #[default]
+  NoTrailing,
+  /// The separator after the last repetition is digested if present.
+  AllowTrailing,
+  /// The separator after the last repetition is required and digested.
+  /// Empty repetitions don't require a separator.
+  RequireTrailing,
+}
+
 impl<Lhs, Rhs, Sep, Init, Fold> Combinator<Mul<Lhs, Rhs, Sep, Init, Fold>> {
   /// Specify an other combinator as the separator
   /// after performing `*` on [`Combinator`]s.
@@ -108,8 +161,8 @@ impl<Lhs, Rhs, Sep, Init, Fold> Combinator<Mul<Lhs, Rhs, Sep, Init, Fold>> {
   ///   3
   /// );
   /// ```
-  /// You can't use [`Combinator::fold`] to accumulate values in the separator combinator.
-  /// You can fold values of the separator combinator to the heap.
+  /// You can't use [`Combinator::fold`] to accumulate values in the separator combinator,
+  /// use [`Combinator::sep_fold`] instead, or fold values of the separator combinator to the heap.
   /// See [`ops::mul`](crate::combinator::ops::mul) for more information.
   #[inline]
   pub fn sep<NewSep>(
@@ -120,10 +173,73 @@ impl<Lhs, Rhs, Sep, Init, Fold> Combinator<Mul<Lhs, Rhs, Sep, Init, Fold>> {
       lhs: self.action.lhs,
       rhs: self.action.rhs,
       sep: sep.into().action,
+      sep_policy: self.action.sep_policy,
+      exact: self.action.exact,
       init: self.action.init,
       fold: self.action.fold,
     })
   }
+
+  /// Set how to handle the separator after the last repetition.
+  /// The default is [`SepPolicy::NoTrailing`].
+  ///
+  /// With [`SepPolicy::RequireTrailing`], the combinator will reject
+  /// if any item is accepted without a following separator,
+  /// it won't backtrack to fewer repetitions.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{eat, ops::mul::SepPolicy}, parser::Parser};
+  /// let entry = (eat('a') * (1..)).sep(',').sep_policy(SepPolicy::AllowTrailing);
+  /// assert_eq!(Parser::builder().entry(&entry).build("a,a").next().unwrap().digested, 3);
+  /// assert_eq!(Parser::builder().entry(&entry).build("a,a,").next().unwrap().digested, 4);
+  ///
+  /// let entry = (eat('a') * (1..)).sep(';').sep_policy(SepPolicy::RequireTrailing);
+  /// assert!(Parser::builder().entry(&entry).build("a;a").next().is_none());
+  /// assert_eq!(Parser::builder().entry(&entry).build("a;a;").next().unwrap().digested, 4);
+  /// ```
+  #[inline]
+  pub fn sep_policy(self, policy: SepPolicy) -> Self {
+    Combinator::new(Mul {
+      sep_policy: policy,
+      ..self.action
+    })
+  }
+}
+
+impl<Lhs, Value, const N: usize, Sep, Init, Fold>
+  Combinator<Mul<Lhs, [Value; N], Sep, Init, Fold>>
+{
+  /// Like [`Self::sep`], but reject if another separator and item follow the `N`-th item,
+  /// for strict fixed-arity lists.
+  ///
+  /// By default, the repetition stops after `N` items
+  /// and leaves the following separator and items undigested.
+  /// With this, the separator and the item after the `N`-th item are executed as a lookahead
+  /// (their side effects on the state and the heap are not reverted),
+  /// and the whole combinator rejects if both of them accept.
+  /// Empty arrays (`N == 0`) are not affected.
+  ///
+  /// This works with [`Self::sep_policy`]:
+  /// the check happens before the trailing separator is handled.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::eat, parser::Parser};
+  /// let entry = (eat('a').bind(1) * [0; 3]).exactly_sep(',');
+  /// assert_eq!(Parser::builder().entry(&entry).build("a,a,a").next().unwrap().digested, 5);
+  /// // the separator is not followed by an item
+  /// assert_eq!(Parser::builder().entry(&entry).build("a,a,a,").next().unwrap().digested, 5);
+  /// // more than 3 items
+  /// assert!(Parser::builder().entry(&entry).build("a,a,a,a").next().is_none());
+  /// ```
+  #[inline]
+  pub fn exactly_sep<NewSep>(
+    self,
+    sep: impl Into<Combinator<NewSep>>,
+  ) -> Combinator<Mul<Lhs, [Value; N], NewSep, Init, Fold>> {
+    let mut c = self.sep(sep);
+    c.action.exact = true;
+    c
+  }
 }
 
 #[cfg(test)]
@@ -339,4 +455,264 @@ mod tests {
       }),
     );
   }
+
+  #[test]
+  fn combinator_mul_sep_skip_last() {
+    crate::contextual!(usize, ());
+
+    // count the executions of the separator via the state
+    fn exec(
+      c: Combinator<impl Action<Text = str, State = usize, Heap = ()>>,
+      input: &str,
+    ) -> (Option<usize>, usize) {
+      let mut count = 0;
+      let digested = c
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut count,
+          heap: &mut (),
+        })
+        .map(|o| o.digested);
+      (digested, count)
+    }
+    let sep = || eat(',').then(|a| *a.state += 1);
+
+    // the separator is executed `items - 1` times when the max is reached,
+    // the trailing separator is left undigested
+    assert_eq!(exec((eat('a') * 3).sep(sep()), "a,a,a,a"), (Some(5), 2));
+    assert_eq!(
+      exec((eat('a') * (1..=3)).sep(sep()), "a,a,a,"),
+      (Some(5), 2)
+    );
+    assert_eq!(exec((eat('a') * (..4)).sep(sep()), "a,a,a,"), (Some(5), 2));
+    assert_eq!(exec((eat('a') * (1..4)).sep(sep()), "a,a,a,"), (Some(5), 2));
+    assert_eq!(exec((eat('a') * (..=3)).sep(sep()), "a,a,a,"), (Some(5), 2));
+    assert_eq!(exec((eat('a') * 1).sep(sep()), "a,"), (Some(1), 0));
+    // the max is not reached, the separator before the rejected item is still executed
+    assert_eq!(exec((eat('a') * (..=3)).sep(sep()), "a,a,b"), (Some(3), 2));
+    // trailing separators are expected
+    assert_eq!(
+      exec(
+        (eat('a') * (..=3))
+          .sep(sep())
+          .sep_policy(SepPolicy::AllowTrailing),
+        "a,a,a,"
+      ),
+      (Some(6), 3)
+    );
+    assert_eq!(
+      exec(
+        (eat('a') * 3)
+          .sep(sep())
+          .sep_policy(SepPolicy::RequireTrailing),
+        "a,a,a,"
+      ),
+      (Some(6), 3)
+    );
+  }
+
+  #[test]
+  fn combinator_mul_sep_policy_allow_trailing() {
+    let c = || {
+      (eat('a') * (..))
+        .sep(',')
+        .sep_policy(SepPolicy::AllowTrailing)
+    };
+    // empty
+    helper(c(), "", 0);
+    helper(c(), ",", 0);
+    // exactly one
+    helper(c(), "a", 1);
+    helper(c(), "a,", 2);
+    helper(c(), "a,,", 2);
+    // trailing at the end of input
+    helper(c(), "a,a,a,", 6);
+    helper(c(), "a,a,a", 5);
+    helper(c(), "a,a,ab", 5);
+    // bounded
+    helper(
+      (eat('a') * (..=2))
+        .sep(',')
+        .sep_policy(SepPolicy::AllowTrailing),
+      "a,a,a",
+      4,
+    );
+    helper(
+      (bytes::eat(b'a') * (1..))
+        .sep(b',')
+        .sep_policy(SepPolicy::AllowTrailing),
+      b"a,a,",
+      4,
+    );
+  }
+
+  #[test]
+  fn combinator_mul_sep_policy_require_trailing() {
+    let c = || {
+      (eat('a') * (..))
+        .sep(';')
+        .sep_policy(SepPolicy::RequireTrailing)
+    };
+    let exec = |input| {
+      c()
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    };
+    // empty lists don't require a separator
+    assert_eq!(exec(""), Some(0));
+    assert_eq!(exec(";"), Some(0));
+    // exactly one
+    assert_eq!(exec("a"), None);
+    assert_eq!(exec("a;"), Some(2));
+    // trailing at the end of input
+    assert_eq!(exec("a;a;"), Some(4));
+    assert_eq!(exec("a;a"), None);
+    helper(
+      (bytes::eat(b'a') * (1..))
+        .sep(b';')
+        .sep_policy(SepPolicy::RequireTrailing),
+      b"a;a;",
+      4,
+    );
+  }
+
+  #[test]
+  fn combinator_mul_sep_policy_fold() {
+    let c = (eat('a').bind(1) * (1..))
+      .sep(',')
+      .sep_policy(SepPolicy::AllowTrailing)
+      .fold(|| 0, |acc, v| acc + v);
+    let output = c
+      .exec(Input {
+        instant: &Instant::new("a,a,a,"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(output.value, 3);
+    assert_eq!(output.digested, 6);
+
+    // the policy is kept after `sep` and `fold` in any order
+    let c = (eat('a').bind(1) * (1..))
+      .sep_policy(SepPolicy::RequireTrailing)
+      .fold(|| 0, |acc, v| acc + v)
+      .sep(';');
+    let output = c
+      .exec(Input {
+        instant: &Instant::new("a;a;"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(output.value, 2);
+    assert_eq!(output.digested, 4);
+  }
+
+  #[test]
+  fn combinator_mul_sep_array_overfull() {
+    let exec = |c: Combinator<_>, input| {
+      c.exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o: Output<[i32; 3]>| (o.value, o.digested))
+    };
+    let c = || (eat('a').bind(1) * [0; 3]).sep(',');
+    let strict = || (eat('a').bind(1) * [0; 3]).exactly_sep(',');
+
+    // under-full
+    assert_eq!(exec(c(), "a,a"), None);
+    assert_eq!(exec(strict(), "a,a"), None);
+    assert_eq!(exec(strict(), "a,a,"), None);
+    // exactly N, the trailing separator is not digested
+    assert_eq!(exec(c(), "a,a,a"), Some(([1, 1, 1], 5)));
+    assert_eq!(exec(strict(), "a,a,a"), Some(([1, 1, 1], 5)));
+    assert_eq!(exec(c(), "a,a,a,"), Some(([1, 1, 1], 5)));
+    assert_eq!(exec(strict(), "a,a,a,"), Some(([1, 1, 1], 5)));
+    assert_eq!(exec(strict(), "a,a,a,b"), Some(([1, 1, 1], 5)));
+    // an item without a separator is not counted
+    assert_eq!(exec(strict(), "a,a,aa"), Some(([1, 1, 1], 5)));
+    // over-full, stop after N by default
+    assert_eq!(exec(c(), "a,a,a,a"), Some(([1, 1, 1], 5)));
+    assert_eq!(exec(c(), "a,a,a,a,a"), Some(([1, 1, 1], 5)));
+    // over-full, reject in strict mode
+    assert_eq!(exec(strict(), "a,a,a,a"), None);
+    assert_eq!(exec(strict(), "a,a,a,a,a"), None);
+
+    // with sep policy
+    assert_eq!(
+      exec(strict().sep_policy(SepPolicy::AllowTrailing), "a,a,a,"),
+      Some(([1, 1, 1], 6))
+    );
+    assert_eq!(
+      exec(strict().sep_policy(SepPolicy::AllowTrailing), "a,a,a,a"),
+      None
+    );
+    assert_eq!(
+      exec(strict().sep_policy(SepPolicy::RequireTrailing), "a,a,a,a"),
+      None
+    );
+    // the strict flag is kept by `sep`
+    assert_eq!(exec(strict().sep(';'), "a;a;a;a"), None);
+
+    // empty array is not affected
+    let c = (eat('a').bind(1) * [0; 0]).exactly_sep(',');
+    assert_eq!(
+      c.exec(Input {
+        instant: &Instant::new(",a"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested),
+      Some(0)
+    );
+  }
+
+  #[test]
+  fn combinator_mul_sep_policy_array() {
+    let exec = |c: Combinator<_>, input| {
+      c.exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o: Output<[i32; 2]>| (o.value, o.digested))
+    };
+    let c = || (eat('a').bind(1) * [0; 2]).sep(',');
+    assert_eq!(exec(c(), "a,a,"), Some(([1, 1], 3)));
+    assert_eq!(
+      exec(c().sep_policy(SepPolicy::AllowTrailing), "a,a,"),
+      Some(([1, 1], 4))
+    );
+    assert_eq!(
+      exec(c().sep_policy(SepPolicy::AllowTrailing), "a,a"),
+      Some(([1, 1], 3))
+    );
+    assert_eq!(
+      exec(c().sep_policy(SepPolicy::RequireTrailing), "a,a,"),
+      Some(([1, 1], 4))
+    );
+    assert_eq!(
+      exec(c().sep_policy(SepPolicy::RequireTrailing), "a,a"),
+      None
+    );
+    // empty array
+    let c = (eat('a').bind(1) * [0; 0])
+      .sep(',')
+      .sep_policy(SepPolicy::RequireTrailing);
+    assert_eq!(
+      c.exec(Input {
+        instant: &Instant::new(","),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested),
+      Some(0)
+    );
+  }
 }
@@ -0,0 +1,273 @@
+use super::NoSep;
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  digest::Digest,
+  idx,
+  instant::Instant,
+};
+use std::{ops::RangeFrom, slice::SliceIndex};
+
+/// An [`Action`] created by [`Combinator::then_repeat`].
+#[derive(Clone, Copy)]
+pub struct ThenRepeat<Lhs, Rhs, Count, Sep, Init, Fold> {
+  lhs: Lhs,
+  rhs: Rhs,
+  count: Count,
+  sep: Sep,
+  init: Init,
+  fold: Fold,
+}
+
+impl<Lhs: std::fmt::Debug, Rhs: std::fmt::Debug, Count, Sep: std::fmt::Debug, Init, Fold>
+  std::fmt::Debug for ThenRepeat<Lhs, Rhs, Count, Sep, Init, Fold>
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ThenRepeat")
+      .field("lhs", &self.lhs)
+      .field("rhs", &self.rhs)
+      .field("sep", &self.sep)
+      .finish()
+  }
+}
+
+impl<T: Action> Combinator<T> {
+  /// Execute self, calculate the repetition count from the value by `count`,
+  /// then repeat `rhs` exactly that many times.
+  ///
+  /// This is useful when the count lives in the value of a previous element,
+  /// e.g. `3:a,b,c` or a length byte followed by that many items,
+  /// so no state or heap round-trip is needed for the count.
+  ///
+  /// The value is a tuple of the value of self and the values of `rhs` accumulated in a [`Vec`].
+  /// The combinator will reject if `rhs` can't be repeated for the count.
+  /// Use [`Combinator::sep`] to set a separator between items,
+  /// and [`Combinator::fold`] to use an ad-hoc accumulator,
+  /// just like [`ops::mul`](crate::combinator::ops::mul).
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{bounded_uint, next}, parser::Parser};
+  /// let entry = (bounded_uint(0, 100) + ':')
+  ///   .then_repeat(|n| *n as usize, next(|c| c.is_ascii_alphabetic()).select(|a| a.start()))
+  ///   .sep(',');
+  /// // the start index of each item
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build("3:a,b,c").next().unwrap().value,
+  ///   (3, vec![2, 4, 6])
+  /// );
+  /// ```
+  #[allow(clippy::type_complexity)]
+  #[inline]
+  pub fn then_repeat<Rhs: Action, Count: Fn(&T::Value) -> usize>(
+    self,
+    count: Count,
+    rhs: impl Into<Combinator<Rhs>>,
+  ) -> Combinator<
+    ThenRepeat<
+      T,
+      Rhs,
+      Count,
+      NoSep<Rhs>,
+      fn() -> Vec<Rhs::Value>,
+      fn(Vec<Rhs::Value>, Rhs::Value) -> Vec<Rhs::Value>,
+    >,
+  > {
+    Combinator::new(ThenRepeat {
+      lhs: self.action,
+      rhs: rhs.into().action,
+      count,
+      sep: NoSep::new(),
+      init: Vec::new,
+      fold: |mut acc, value| {
+        acc.push(value);
+        acc
+      },
+    })
+  }
+}
+
+impl<Lhs, Rhs, Count, Sep, Init, Fold> Combinator<ThenRepeat<Lhs, Rhs, Count, Sep, Init, Fold>> {
+  /// Specify an other combinator as the separator between repeated items
+  /// after [`Combinator::then_repeat`].
+  /// The separator is not executed before the first item or after the last item.
+  ///
+  /// Tips: you can use [`char`], `&str`, [`String`], [`u8`], `&[u8]` and [`Vec<u8>`] as the shorthand
+  /// for [`eat`](crate::combinator::eat) in the separator.
+  #[inline]
+  pub fn sep<NewSep>(
+    self,
+    sep: impl Into<Combinator<NewSep>>,
+  ) -> Combinator<ThenRepeat<Lhs, Rhs, Count, NewSep, Init, Fold>> {
+    Combinator::new(ThenRepeat {
+      lhs: self.action.lhs,
+      rhs: self.action.rhs,
+      count: self.action.count,
+      sep: sep.into().action,
+      init: self.action.init,
+      fold: self.action.fold,
+    })
+  }
+
+  /// Fold values of repeated items with an ad-hoc accumulator
+  /// after [`Combinator::then_repeat`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::bytes, parser::Parser};
+  /// // a count byte followed by that many bytes, sum them up
+  /// let byte = || bytes::take(1).select(|a| a.content()[0]);
+  /// let entry = byte()
+  ///   .then_repeat(|n| *n as usize, byte())
+  ///   .fold(|| 0, |acc, v| acc + v as u32);
+  /// assert_eq!(
+  ///   Parser::builder().entry(entry).build(b"\x02\x01\x02").next().unwrap().value,
+  ///   (2, 3)
+  /// );
+  /// ```
+  #[inline]
+  pub fn fold<Value, Acc, NewInit: Fn() -> Acc, NewFold: Fn(Acc, Value) -> Acc>(
+    self,
+    init: NewInit,
+    fold: NewFold,
+  ) -> Combinator<ThenRepeat<Lhs, Rhs, Count, Sep, NewInit, NewFold>> {
+    Combinator::new(ThenRepeat {
+      lhs: self.action.lhs,
+      rhs: self.action.rhs,
+      count: self.action.count,
+      sep: self.action.sep,
+      init,
+      fold,
+    })
+  }
+}
+
+unsafe impl<
+    Lhs: Action<Text: Digest>,
+    Rhs: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Count: Fn(&Lhs::Value) -> usize,
+    Sep: Action<Text = Lhs::Text, State = Lhs::State, Heap = Lhs::Heap>,
+    Acc,
+    Init: Fn() -> Acc,
+    Fold: Fn(Acc, Rhs::Value) -> Acc,
+  > Action for ThenRepeat<Lhs, Rhs, Count, Sep, Init, Fold>
+where
+  RangeFrom<usize>: SliceIndex<Lhs::Text, Output = Lhs::Text>,
+{
+  type Text = Lhs::Text;
+  type State = Lhs::State;
+  type Heap = Lhs::Heap;
+  type Value = (Lhs::Value, Acc);
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let lhs_output = self.lhs.exec(input.reborrow())?;
+    let n = (self.count)(&lhs_output.value);
+
+    let mut acc = (self.init)();
+    let mut digested = lhs_output.digested;
+    for i in 0..n {
+      if i != 0 {
+        // skip the separator before the first item
+        let sep_output = self
+          .sep
+          .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }))?;
+        // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+        debug_assert!(usize::MAX - digested >= sep_output.digested);
+        digested = idx::unchecked!(idx::add(digested, sep_output.digested));
+      }
+
+      let rhs_output = self
+        .rhs
+        .exec(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }))?;
+      acc = (self.fold)(acc, rhs_output.value);
+      // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+      debug_assert!(usize::MAX - digested >= rhs_output.digested);
+      digested = idx::unchecked!(idx::add(digested, rhs_output.digested));
+    }
+
+    Some(Output {
+      value: (lhs_output.value, acc),
+      digested,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{bounded_uint, bytes, next};
+  use std::fmt::Debug;
+
+  fn helper<Text: ?Sized + Digest, Value: PartialEq + Debug>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    expected: Option<(Value, usize)>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    );
+  }
+
+  #[test]
+  fn combinator_then_repeat_text() {
+    let entry = (bounded_uint(0, 100) + ':')
+      .then_repeat(
+        |n| *n as usize,
+        next(|c| c.is_ascii_alphabetic()).select(|a| a.start()),
+      )
+      .sep(',');
+    helper(entry, "3:a,b,c", Some(((3, vec![2, 4, 6]), 7)));
+    // extra items are not digested
+    helper(entry, "2:a,b,c", Some(((2, vec![2, 4]), 5)));
+    // count = 0
+    helper(entry, "0:", Some(((0, vec![]), 2)));
+    helper(entry, "0:a", Some(((0, vec![]), 2)));
+    // count larger than remaining input
+    helper(entry, "3:a,b", None);
+    helper(entry, "3:a,b,", None);
+    // lhs rejected
+    helper(entry, ":a", None);
+  }
+
+  #[test]
+  fn combinator_then_repeat_bytes() {
+    let byte = || bytes::take(1).select(|a| a.content()[0]);
+    let entry = byte().then_repeat(|n| *n as usize, byte());
+    helper(entry, b"\x03abc", Some(((3, b"abc".to_vec()), 4)));
+    helper(entry, b"\x00abc", Some(((0, vec![]), 1)));
+    helper(entry, b"\x04abc", None);
+    helper(entry, b"\xffabc", None);
+    helper(entry, b"", None);
+
+    // fold
+    let entry = byte()
+      .then_repeat(|n| *n as usize, byte())
+      .fold(|| 0, |acc, v| acc + v as u32);
+    helper(entry, b"\x02\x01\x02", Some(((2, 3), 3)));
+    helper(entry, b"\x00", Some(((0, 0), 1)));
+  }
+
+  fn _then_repeat_debug() {
+    let _ = format!(
+      "{:?}",
+      bytes::take(1).then_repeat(|_| 1, bytes::take(1)).sep(b',')
+    );
+  }
+
+  fn _then_repeat_clone_copy() {
+    let c = bytes::take(1).then_repeat(|_| 1, bytes::take(1));
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -20,10 +20,10 @@
 
 use crate::{
   action::{Action, Input, Output},
-  combinator::Combinator,
+  combinator::{write_head, Combinator, Describe, FirstSet, LookaheadSet},
   instant::Instant,
 };
-use std::ops;
+use std::{fmt, ops};
 
 /// An [`Action`] created by the `!` operator.
 /// See [`ops::not`](crate::combinator::ops::not) for more information.
@@ -61,6 +61,23 @@ unsafe impl<T: Action<Value: Default>> Action for Not<T> {
   }
 }
 
+/// Never digest.
+impl<T> FirstSet for Not<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::empty())
+  }
+}
+
+impl<T: Describe> Describe for Not<T> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "Not", module_path!())?;
+    f.write_str(" ")?;
+    self.action.describe(f)?;
+    f.write_str(")")
+  }
+}
+
 impl<T> ops::Not for Combinator<T> {
   type Output = Combinator<Not<T>>;
 
@@ -1,20 +1,62 @@
+mod choice;
+mod comment;
 mod contextual;
+mod dispatch;
 mod eat;
+mod eat_in;
+mod from_fn;
+#[cfg(feature = "unicode-segmentation")]
+mod graphemes;
+mod identifier;
+mod json;
+mod line;
+mod lookahead;
 mod next;
+mod pick;
+mod position;
 mod recur;
+#[cfg(feature = "regex")]
+mod regex;
+mod seq;
 mod take;
+mod take_while;
 mod till;
+mod token;
+mod uint;
+mod until;
 mod wrap;
 
+pub use choice::*;
+pub use comment::*;
 pub use contextual::*;
+pub use dispatch::*;
 pub use eat::*;
+pub use eat_in::*;
+pub use from_fn::*;
+#[cfg(feature = "unicode-segmentation")]
+pub use graphemes::*;
+pub use identifier::*;
+pub use json::*;
+pub use line::*;
+pub use lookahead::*;
 pub use next::*;
+pub use pick::*;
+pub use position::*;
 pub use recur::*;
+#[cfg(feature = "regex")]
+pub use regex::*;
+pub use seq::*;
 pub use take::*;
+pub use take_while::*;
 pub use till::*;
+pub use token::*;
+pub use uint::*;
+pub use until::*;
 pub use wrap::*;
 
 pub mod bytes;
+pub mod datetime;
+pub mod u16s;
 
 macro_rules! create_combinator {
   ($name:ident, $usage:literal, ($($derives:ident),*)) => {
@@ -41,6 +83,13 @@ pub(self) use create_combinator;
 macro_rules! create_value_combinator {
   ($name:ident, $usage:literal) => {
     $crate::combinator::provided::create_combinator!($name, $usage, (Debug));
+
+    impl<T: core::fmt::Debug> $crate::combinator::Describe for $name<T> {
+      fn describe(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        write!(f, " {:?})", self.inner)
+      }
+    }
   };
 }
 // https://github.com/rust-lang/rust-clippy/issues/12808
@@ -58,6 +107,13 @@ macro_rules! create_closure_combinator {
         f.debug_struct(stringify!($name)).finish()
       }
     }
+
+    impl<T> $crate::combinator::Describe for $name<T> {
+      fn describe(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        $crate::combinator::write_head(f, stringify!($name), module_path!())?;
+        f.write_str(" <fn>)")
+      }
+    }
   };
 }
 // https://github.com/rust-lang/rust-clippy/issues/12808
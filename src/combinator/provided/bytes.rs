@@ -1,15 +1,33 @@
 //! Combinators for parsing bytes.
 
+mod ascii;
 mod eat;
+mod eat_in;
+mod line;
 mod next;
+mod num;
+mod position;
 mod recur;
 mod take;
+mod take_while;
 mod till;
+mod uint;
+mod until;
 mod wrap;
 
+pub use ascii::*;
 pub use eat::*;
+pub use eat_in::*;
+pub use line::*;
 pub use next::*;
+pub use num::*;
+pub use position::*;
 pub use recur::*;
 pub use take::*;
+pub use take_while::*;
 pub use till::*;
+pub use uint::*;
+pub use until::*;
 pub use wrap::*;
+
+pub mod tlv;
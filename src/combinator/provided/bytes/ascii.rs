@@ -0,0 +1,263 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, Combinator, Describe},
+  instant::Instant,
+  lookup,
+};
+use std::fmt;
+
+/// A class of ASCII bytes. Non-ASCII bytes belong to no class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiClass {
+  /// `A-Z` and `a-z`, see [`u8::is_ascii_alphabetic`].
+  Alpha,
+  /// `0-9`, see [`u8::is_ascii_digit`].
+  Digit,
+  /// `A-Z`, `a-z` and `0-9`, see [`u8::is_ascii_alphanumeric`].
+  Alnum,
+  /// Space, `\t`, `\n`, `\x0C` and `\r`, see [`u8::is_ascii_whitespace`].
+  Whitespace,
+  /// `0-9`, `A-F` and `a-f`, see [`u8::is_ascii_hexdigit`].
+  HexDigit,
+}
+
+impl AsciiClass {
+  #[inline]
+  const fn mask(self) -> u8 {
+    match self {
+      Self::Alpha => lookup::ALPHA,
+      Self::Digit => lookup::DIGIT,
+      Self::Alnum => lookup::ALNUM,
+      Self::Whitespace => lookup::WHITESPACE,
+      Self::HexDigit => lookup::HEX_DIGIT,
+    }
+  }
+
+  /// Check if `b` is in the class.
+  #[inline]
+  pub fn contains(self, b: u8) -> bool {
+    lookup::is_in(b, self.mask())
+  }
+}
+
+/// See [`ascii_alpha`], [`ascii_alphas1`] and other `ascii_*` functions.
+#[derive(Clone, Copy)]
+pub struct Ascii {
+  class: AsciiClass,
+  mask: u8,
+  many: bool,
+}
+
+impl Ascii {
+  /// Create a new instance.
+  /// If `many` is `true`, take as many bytes in the `class` as possible (at least one),
+  /// otherwise take exactly one byte.
+  #[inline]
+  pub const fn new(class: AsciiClass, many: bool) -> Self {
+    Self {
+      class,
+      mask: class.mask(),
+      many,
+    }
+  }
+
+  /// The name of the function which creates this.
+  const fn name(&self) -> &'static str {
+    match (self.class, self.many) {
+      (AsciiClass::Alpha, false) => "ascii_alpha",
+      (AsciiClass::Alpha, true) => "ascii_alphas1",
+      (AsciiClass::Digit, false) => "ascii_digit",
+      (AsciiClass::Digit, true) => "ascii_digits1",
+      (AsciiClass::Alnum, false) => "ascii_alnum",
+      (AsciiClass::Alnum, true) => "ascii_alnums1",
+      (AsciiClass::Whitespace, false) => "ascii_ws",
+      (AsciiClass::Whitespace, true) => "ascii_ws1",
+      (AsciiClass::HexDigit, false) => "ascii_hexdigit",
+      (AsciiClass::HexDigit, true) => "ascii_hexdigits1",
+    }
+  }
+}
+
+impl fmt::Debug for Ascii {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Ascii")
+      .field("class", &self.class)
+      .field("many", &self.many)
+      .finish()
+  }
+}
+
+impl Describe for Ascii {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, self.name(), module_path!())?;
+    f.write_str(")")
+  }
+}
+
+unsafe impl Action for Ascii {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let digested = if self.many {
+      lookup::scan(rest, self.mask)
+    } else {
+      rest
+        .first()
+        .is_some_and(|&b| lookup::is_in(b, self.mask))
+        .into()
+    };
+    // SAFETY: `digested` is no greater than the length of the rest
+    (digested != 0).then(|| unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+macro_rules! ascii_fns {
+  ($class:ident, $desc:literal, $one:ident, $many:ident) => {
+    #[doc = concat!("Returns a combinator to eat one ", $desc, ".")]
+    /// The combinator will reject if the next byte is not in the class or the rest is empty.
+    ///
+    /// This is a table-driven version of `bytes::next(predicate)`,
+    #[doc = concat!("see [`AsciiClass::", stringify!($class), "`].")]
+    #[doc = concat!("Use [`", stringify!($many), "`] to eat one or more bytes.")]
+    /// # Examples
+    /// ```
+    /// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+    /// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+    /// # t(
+    #[doc = concat!("bytes::", stringify!($one), "()")]
+    /// # );
+    /// ```
+    #[inline]
+    pub const fn $one() -> Combinator<Ascii> {
+      Combinator::new(Ascii::new(AsciiClass::$class, false))
+    }
+
+    #[doc = concat!("Returns a combinator to eat one or more ", $desc, "s in one scan.")]
+    /// The combinator will reject if the next byte is not in the class or the rest is empty.
+    ///
+    /// This is a table-driven version of
+    /// `bytes::take_while_min_max(predicate, 1, usize::MAX)`,
+    #[doc = concat!("see [`AsciiClass::", stringify!($class), "`].")]
+    /// # Examples
+    /// ```
+    /// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+    /// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+    /// # t(
+    #[doc = concat!("bytes::", stringify!($many), "()")]
+    /// # );
+    /// ```
+    #[inline]
+    pub const fn $many() -> Combinator<Ascii> {
+      Combinator::new(Ascii::new(AsciiClass::$class, true))
+    }
+  };
+}
+
+ascii_fns!(Alpha, "ASCII alphabetic byte", ascii_alpha, ascii_alphas1);
+ascii_fns!(Digit, "ASCII digit byte", ascii_digit, ascii_digits1);
+ascii_fns!(Alnum, "ASCII alphanumeric byte", ascii_alnum, ascii_alnums1);
+ascii_fns!(Whitespace, "ASCII whitespace byte", ascii_ws, ascii_ws1);
+ascii_fns!(
+  HexDigit,
+  "ASCII hex digit byte",
+  ascii_hexdigit,
+  ascii_hexdigits1
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = [u8], State = (), Heap = ()>,
+    input: &[u8],
+  ) -> Option<usize> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested)
+  }
+
+  #[test]
+  fn combinator_ascii_one() {
+    // boundaries of each class
+    for b in [b'A', b'Z', b'a', b'z'] {
+      assert_eq!(helper(ascii_alpha(), &[b, b'a']), Some(1));
+      assert_eq!(helper(ascii_alnum(), &[b]), Some(1));
+    }
+    for b in [b'@', b'[', b'`', b'{', b'0'] {
+      assert_eq!(helper(ascii_alpha(), &[b]), None);
+    }
+    for b in [b'0', b'9'] {
+      assert_eq!(helper(ascii_digit(), &[b]), Some(1));
+      assert_eq!(helper(ascii_alnum(), &[b]), Some(1));
+      assert_eq!(helper(ascii_hexdigit(), &[b]), Some(1));
+    }
+    for b in [b'/', b':'] {
+      assert_eq!(helper(ascii_digit(), &[b]), None);
+      assert_eq!(helper(ascii_alnum(), &[b]), None);
+      assert_eq!(helper(ascii_hexdigit(), &[b]), None);
+    }
+    for b in [b'A', b'F', b'a', b'f'] {
+      assert_eq!(helper(ascii_hexdigit(), &[b]), Some(1));
+    }
+    for b in [b'G', b'g', b'@', b'`'] {
+      assert_eq!(helper(ascii_hexdigit(), &[b]), None);
+    }
+    for b in [b' ', b'\t', b'\n', b'\x0C', b'\r'] {
+      assert_eq!(helper(ascii_ws(), &[b]), Some(1));
+    }
+    for b in [b'\x0B', b'\x08', b'\x0E', b'!'] {
+      assert_eq!(helper(ascii_ws(), &[b]), None);
+    }
+    // non-ascii bytes and empty input
+    for c in [
+      ascii_alpha(),
+      ascii_digit(),
+      ascii_alnum(),
+      ascii_ws(),
+      ascii_hexdigit(),
+    ] {
+      assert_eq!(helper(c, &[0x80]), None);
+      assert_eq!(helper(c, &[0xFF]), None);
+      assert_eq!(helper(c, "好".as_bytes()), None);
+      assert_eq!(helper(c, b""), None);
+    }
+  }
+
+  #[test]
+  fn combinator_ascii_many() {
+    assert_eq!(helper(ascii_alphas1(), b"abcXYZ0"), Some(6));
+    assert_eq!(helper(ascii_digits1(), b"0129a"), Some(4));
+    assert_eq!(helper(ascii_alnums1(), b"a0Z9_"), Some(4));
+    assert_eq!(helper(ascii_ws1(), b" \t\r\n\x0C\x0B"), Some(5));
+    assert_eq!(helper(ascii_hexdigits1(), b"09afAFg"), Some(6));
+    // stop at non-ascii bytes
+    assert_eq!(helper(ascii_alphas1(), "ab好".as_bytes()), Some(2));
+    // reject if no byte is taken
+    assert_eq!(helper(ascii_digits1(), b"a1"), None);
+    assert_eq!(helper(ascii_digits1(), b""), None);
+    // to the end
+    assert_eq!(helper(ascii_digits1(), b"123"), Some(3));
+  }
+
+  #[test]
+  fn combinator_ascii_debug() {
+    assert!(!AsciiClass::Alnum.contains(b'_'));
+    assert_eq!(
+      format!("{:?}", ascii_hexdigits1()),
+      "Combinator { action: Ascii { class: HexDigit, many: true } }"
+    );
+  }
+}
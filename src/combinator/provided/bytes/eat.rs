@@ -127,6 +127,113 @@ impl<'a, const N: usize> From<&'a [u8; N]> for Combinator<Eat<&'a [u8; N]>> {
   }
 }
 
+create_value_combinator!(EatCaseInsensitive, "See [`eat_ci`].");
+
+unsafe impl Action for EatCaseInsensitive<u8> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .first()
+      .is_some_and(|c| c.eq_ignore_ascii_case(&self.inner))
+      .then(|| unsafe { input.instant.accept_unchecked(1) })
+  }
+}
+
+/// Check if `rest` starts with `pattern`, ignoring ASCII case.
+#[inline]
+fn starts_with_ignore_ascii_case(rest: &[u8], pattern: &[u8]) -> bool {
+  rest
+    .get(..pattern.len())
+    .is_some_and(|head| head.eq_ignore_ascii_case(pattern))
+}
+
+unsafe impl Action for EatCaseInsensitive<&[u8]> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    starts_with_ignore_ascii_case(input.instant.rest(), self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+unsafe impl<const N: usize> Action for EatCaseInsensitive<&[u8; N]> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    starts_with_ignore_ascii_case(input.instant.rest(), self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(N) })
+  }
+}
+
+unsafe impl Action for EatCaseInsensitive<Vec<u8>> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    starts_with_ignore_ascii_case(input.instant.rest(), &self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+/// Returns a combinator to eat from the head of [`Instant::rest`] by the provided pattern,
+/// ignoring ASCII case.
+/// The combinator will reject if the pattern is not found.
+///
+/// Only ASCII letters are compared case-insensitively (see [`slice::eq_ignore_ascii_case`]),
+/// other bytes are compared exactly.
+/// # Caveats
+/// Empty patterns are allowed and will always accept 0 bytes,
+/// even when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::eat_ci(b'a') // eat by a byte (u8)
+/// # );
+/// # t(
+/// bytes::eat_ci(b"content-length") // eat by &[u8] or &[u8; N]
+/// # );
+/// # t(
+/// bytes::eat_ci(vec![b'a']) // eat by Vec<u8>
+/// # );
+/// ```
+#[inline]
+pub const fn eat_ci<T>(pattern: T) -> Combinator<EatCaseInsensitive<T>> {
+  Combinator::new(EatCaseInsensitive::new(pattern))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -171,6 +278,28 @@ mod tests {
     helper(eat(vec![]), b"", Some(0));
   }
 
+  #[test]
+  fn combinator_eat_ci() {
+    // mixed case
+    helper(eat_ci(b'a'), b"A", Some(1));
+    helper(eat_ci(b"content-length"), b"Content-Length: 1", Some(14));
+    helper(eat_ci(b"GET" as &[u8]), b"get /", Some(3));
+    helper(eat_ci(b"get".to_vec()), b"GeT", Some(3));
+    // partial matches reject
+    helper(eat_ci(b"get"), b"GE", None);
+    helper(eat_ci(b"get"), b"GEX", None);
+    helper(eat_ci(b'a'), b"", None);
+    // non-ascii bytes are compared exactly
+    helper(eat_ci(b"\xe9a"), b"\xe9A", Some(2));
+    helper(eat_ci(b"\xe9"), b"\xc9", None);
+    // empty
+    helper(eat_ci(b""), b"", Some(0));
+    helper(eat_ci(vec![]), b"1", Some(0));
+    // literals in `+` are still case-sensitive
+    helper(eat_ci(b"get") + b" /", b"GET /", Some(5));
+    helper(eat_ci(b"get") + b"x", b"GETX", None);
+  }
+
   #[test]
   fn eat_into_combinator() {
     fn test_bytes(c: Combinator<impl Action<Text = [u8], State = (), Heap = (), Value = ()>>) {
@@ -184,6 +313,7 @@ mod tests {
 
   fn _eat_debug() {
     let _ = format!("{:?}", eat(b'a'));
+    let _ = format!("{:?}", eat_ci(b'a'));
   }
 
   fn _eat_clone_copy() {
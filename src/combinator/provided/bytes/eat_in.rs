@@ -0,0 +1,206 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, Combinator, Describe},
+  instant::Instant,
+};
+use std::{fmt, marker::PhantomData};
+
+/// Build a 256-bit bitmap of the bytes in `set`.
+const fn bitmap(set: &[u8]) -> [u64; 4] {
+  let mut bitmap = [0; 4];
+  let mut i = 0;
+  while i < set.len() {
+    let b = set[i];
+    bitmap[(b >> 6) as usize] |= 1 << (b & 63);
+    i += 1;
+  }
+  bitmap
+}
+
+/// See [`eat_in`] and [`eat_in_byte`].
+pub struct EatIn<'a, Value = ()> {
+  set: &'a [u8],
+  bitmap: [u64; 4],
+  _value: PhantomData<Value>,
+}
+
+impl<Value> Clone for EatIn<'_, Value> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Value> Copy for EatIn<'_, Value> {}
+
+impl<Value> fmt::Debug for EatIn<'_, Value> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("EatIn").field("set", &self.set).finish()
+  }
+}
+
+impl<Value> Describe for EatIn<'_, Value> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "EatIn", module_path!())?;
+    write!(f, " {:?})", self.set)
+  }
+}
+
+impl<'a, Value> EatIn<'a, Value> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(set: &'a [u8]) -> Self {
+    Self {
+      set,
+      bitmap: bitmap(set),
+      _value: PhantomData,
+    }
+  }
+
+  /// The provided set.
+  #[inline]
+  pub(super) const fn set(&self) -> &'a [u8] {
+    self.set
+  }
+
+  /// Return the matched byte if the head of `rest` is in the set.
+  #[inline]
+  pub(super) fn matches(&self, rest: &[u8]) -> Option<u8> {
+    rest
+      .first()
+      .copied()
+      .filter(|&b| self.bitmap[(b >> 6) as usize] & (1 << (b & 63)) != 0)
+  }
+}
+
+unsafe impl Action for EatIn<'_> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self
+      .matches(input.instant.rest())
+      .map(|_| unsafe { input.instant.accept_unchecked(1) })
+  }
+}
+
+unsafe impl Action for EatIn<'_, u8> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = u8;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.matches(input.instant.rest()).map(|b| Output {
+      value: b,
+      digested: 1,
+    })
+  }
+}
+
+/// Returns a combinator to eat one byte from the head of [`Instant::rest`]
+/// if the byte is in the provided set.
+/// The combinator will reject if the byte is not in the set or the rest is empty.
+///
+/// The set is checked by a 256-bit bitmap.
+/// An empty set always rejects.
+///
+/// Use [`eat_in_byte`] to get the matched byte as the value.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = ()>>) {}
+/// # t(
+/// bytes::eat_in(b"+-*/") // eat one of the operators
+/// # );
+/// ```
+#[inline]
+pub const fn eat_in(set: &[u8]) -> Combinator<EatIn<'_>> {
+  Combinator::new(EatIn::new(set))
+}
+
+/// Returns a combinator to eat one byte from the head of [`Instant::rest`]
+/// if the byte is in the provided set, and take the byte as the value.
+/// See [`eat_in`].
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = u8>>) {}
+/// # t(
+/// bytes::eat_in_byte(b"+-*/") // eat one of the operators as a byte
+/// # );
+/// ```
+#[inline]
+pub const fn eat_in_byte(set: &[u8]) -> Combinator<EatIn<'_, u8>> {
+  Combinator::new(EatIn::new(set))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fmt::Debug;
+
+  fn helper<Value: PartialEq + Debug>(
+    action: impl Action<Text = [u8], Value = Value, State = (), Heap = ()>,
+    input: &[u8],
+    expected: Option<(Value, usize)>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn combinator_eat_in() {
+    helper(eat_in(b"+-*/"), b"+1", Some(((), 1)));
+    helper(eat_in(b"+-*/"), b"/", Some(((), 1)));
+    helper(eat_in(b"+-*/"), b"1+", None);
+    helper(eat_in(b"+-*/"), b"", None);
+    // boundaries of the bitmap
+    helper(eat_in(&[0, 63, 64, 255]), &[0], Some(((), 1)));
+    helper(eat_in(&[0, 63, 64, 255]), &[63], Some(((), 1)));
+    helper(eat_in(&[0, 63, 64, 255]), &[64], Some(((), 1)));
+    helper(eat_in(&[0, 63, 64, 255]), &[255], Some(((), 1)));
+    helper(eat_in(&[0, 63, 64, 255]), &[254], None);
+    // non-ascii input against an ascii set
+    helper(eat_in(b"+-*/"), "好".as_bytes(), None);
+    // empty set always rejects
+    helper(eat_in(b""), b"a", None);
+    helper(eat_in(b""), b"", None);
+  }
+
+  #[test]
+  fn combinator_eat_in_byte() {
+    helper(eat_in_byte(b"+-*/"), b"*", Some((b'*', 1)));
+    helper(eat_in_byte(&[0x80]), &[0x80], Some((0x80, 1)));
+    helper(eat_in_byte(b"+-*/"), b"a", None);
+  }
+
+  fn _eat_in_debug() {
+    let _ = format!("{:?}", eat_in(b"+-"));
+    let _ = format!("{:?}", eat_in_byte(b"+-"));
+  }
+
+  fn _eat_in_clone_copy() {
+    let c = eat_in(b"+-");
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,377 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Numbers which can be read from fixed-size bytes.
+/// See [`LittleEndian`] and [`BigEndian`].
+pub trait FromBytes: Sized {
+  /// The number of bytes to read.
+  const SIZE: usize;
+
+  /// Read the number from the first [`Self::SIZE`] bytes in little-endian order.
+  /// Return [`None`] if there are not enough bytes.
+  fn from_le_prefix(bytes: &[u8]) -> Option<Self>;
+
+  /// Read the number from the first [`Self::SIZE`] bytes in big-endian order.
+  /// Return [`None`] if there are not enough bytes.
+  fn from_be_prefix(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_from_bytes {
+  ($($t:ty),*) => {$(
+    impl FromBytes for $t {
+      const SIZE: usize = std::mem::size_of::<$t>();
+
+      #[inline]
+      fn from_le_prefix(bytes: &[u8]) -> Option<Self> {
+        Some(Self::from_le_bytes(bytes.get(..Self::SIZE)?.try_into().ok()?))
+      }
+
+      #[inline]
+      fn from_be_prefix(bytes: &[u8]) -> Option<Self> {
+        Some(Self::from_be_bytes(bytes.get(..Self::SIZE)?.try_into().ok()?))
+      }
+    }
+  )*};
+}
+impl_from_bytes!(u8, u16, u32, u64, f32, f64);
+
+macro_rules! impl_endian {
+  ($name:ident, $read:ident, $doc:literal) => {
+    #[doc = $doc]
+    pub struct $name<T> {
+      _phantom: PhantomData<fn() -> T>,
+    }
+
+    impl<T> $name<T> {
+      /// Create a new instance.
+      #[inline]
+      pub const fn new() -> Self {
+        Self {
+          _phantom: PhantomData,
+        }
+      }
+    }
+
+    impl<T> Debug for $name<T> {
+      fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}<{}>", stringify!($name), std::any::type_name::<T>())
+      }
+    }
+
+    // implement manually to avoid the `T: Clone` bound
+    impl<T> Clone for $name<T> {
+      #[inline]
+      fn clone(&self) -> Self {
+        *self
+      }
+    }
+
+    impl<T> Copy for $name<T> {}
+
+    impl<T> Default for $name<T> {
+      #[inline]
+      fn default() -> Self {
+        Self::new()
+      }
+    }
+
+    unsafe impl<T: FromBytes> Action for $name<T> {
+      type Text = [u8];
+      type State = ();
+      type Heap = ();
+      type Value = T;
+
+      #[inline]
+      fn exec(
+        &self,
+        input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+      ) -> Option<Output<Self::Value>> {
+        T::$read(input.instant.rest()).map(|value| Output {
+          value,
+          digested: T::SIZE,
+        })
+      }
+    }
+  };
+}
+impl_endian!(
+  LittleEndian,
+  from_le_prefix,
+  "Read a number in little-endian order. See [`le_u16`], [`le_f32`], etc."
+);
+impl_endian!(
+  BigEndian,
+  from_be_prefix,
+  "Read a number in big-endian order. See [`be_u16`], [`be_f32`], etc."
+);
+
+/// Returns a combinator to read one byte as a [`u8`](prim@u8).
+///
+/// The combinator will reject if the rest is empty.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::u8()).build(b"\xff\x00" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 255);
+/// assert_eq!(output.digested, 1);
+/// ```
+#[inline]
+pub const fn u8() -> Combinator<LittleEndian<u8>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`u16`] in little-endian order.
+///
+/// The combinator will reject if there are less than 2 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::le_u16()).build(b"\x01\x02" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x0201);
+/// assert_eq!(output.digested, 2);
+/// ```
+#[inline]
+pub const fn le_u16() -> Combinator<LittleEndian<u16>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`u16`] in big-endian order.
+///
+/// The combinator will reject if there are less than 2 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::be_u16()).build(b"\x01\x02" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x0102);
+/// assert_eq!(output.digested, 2);
+/// ```
+#[inline]
+pub const fn be_u16() -> Combinator<BigEndian<u16>> {
+  Combinator::new(BigEndian::new())
+}
+
+/// Returns a combinator to read a [`u32`] in little-endian order.
+///
+/// The combinator will reject if there are less than 4 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::le_u32()).build(b"\x01\x02\x03\x04" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x04030201);
+/// assert_eq!(output.digested, 4);
+/// ```
+#[inline]
+pub const fn le_u32() -> Combinator<LittleEndian<u32>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`u32`] in big-endian order.
+///
+/// The combinator will reject if there are less than 4 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::be_u32()).build(b"\x01\x02\x03\x04" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x01020304);
+/// assert_eq!(output.digested, 4);
+/// ```
+#[inline]
+pub const fn be_u32() -> Combinator<BigEndian<u32>> {
+  Combinator::new(BigEndian::new())
+}
+
+/// Returns a combinator to read a [`u64`] in little-endian order.
+///
+/// The combinator will reject if there are less than 8 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::le_u64()).build(b"\x01\x02\x03\x04\x05\x06\x07\x08" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x0807060504030201);
+/// assert_eq!(output.digested, 8);
+/// ```
+#[inline]
+pub const fn le_u64() -> Combinator<LittleEndian<u64>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`u64`] in big-endian order.
+///
+/// The combinator will reject if there are less than 8 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::be_u64()).build(b"\x01\x02\x03\x04\x05\x06\x07\x08" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 0x0102030405060708);
+/// assert_eq!(output.digested, 8);
+/// ```
+#[inline]
+pub const fn be_u64() -> Combinator<BigEndian<u64>> {
+  Combinator::new(BigEndian::new())
+}
+
+/// Returns a combinator to read a [`f32`] in little-endian order.
+///
+/// The combinator will reject if there are less than 4 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::le_f32()).build(b"\x00\x00\xc0\x3f" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 1.5);
+/// assert_eq!(output.digested, 4);
+/// ```
+#[inline]
+pub const fn le_f32() -> Combinator<LittleEndian<f32>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`f32`] in big-endian order.
+///
+/// The combinator will reject if there are less than 4 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::be_f32()).build(b"\x3f\xc0\x00\x00" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 1.5);
+/// assert_eq!(output.digested, 4);
+/// ```
+#[inline]
+pub const fn be_f32() -> Combinator<BigEndian<f32>> {
+  Combinator::new(BigEndian::new())
+}
+
+/// Returns a combinator to read a [`f64`] in little-endian order.
+///
+/// The combinator will reject if there are less than 8 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::le_f64()).build(b"\x00\x00\x00\x00\x00\x00\xf8\x3f" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 1.5);
+/// assert_eq!(output.digested, 8);
+/// ```
+#[inline]
+pub const fn le_f64() -> Combinator<LittleEndian<f64>> {
+  Combinator::new(LittleEndian::new())
+}
+
+/// Returns a combinator to read a [`f64`] in big-endian order.
+///
+/// The combinator will reject if there are less than 8 bytes in the rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let output = Parser::builder().entry(bytes::be_f64()).build(b"\x3f\xf8\x00\x00\x00\x00\x00\x00" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, 1.5);
+/// assert_eq!(output.digested, 8);
+/// ```
+#[inline]
+pub const fn be_f64() -> Combinator<BigEndian<f64>> {
+  Combinator::new(BigEndian::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::bytes;
+
+  fn helper<Value: PartialEq + Debug>(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = Value>,
+    input: &[u8],
+    expected: Option<(Value, usize)>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn combinator_bytes_num() {
+    // exact length
+    helper(u8(), b"\x01", Some((1, 1)));
+    helper(le_u16(), b"\x01\x02", Some((0x0201, 2)));
+    helper(be_u16(), b"\x01\x02", Some((0x0102, 2)));
+    helper(le_u32(), b"\x01\x02\x03\x04", Some((0x04030201, 4)));
+    helper(be_u32(), b"\x01\x02\x03\x04", Some((0x01020304, 4)));
+    helper(
+      le_u64(),
+      &[1, 2, 3, 4, 5, 6, 7, 8],
+      Some((0x0807060504030201, 8)),
+    );
+    helper(
+      be_u64(),
+      &[1, 2, 3, 4, 5, 6, 7, 8],
+      Some((0x0102030405060708, 8)),
+    );
+    helper(le_f32(), &1.5f32.to_le_bytes(), Some((1.5, 4)));
+    helper(be_f32(), &1.5f32.to_be_bytes(), Some((1.5, 4)));
+    helper(le_f64(), &(-0.25f64).to_le_bytes(), Some((-0.25, 8)));
+    helper(be_f64(), &(-0.25f64).to_be_bytes(), Some((-0.25, 8)));
+
+    // longer input, only the prefix is digested
+    helper(u8(), b"\xff\x00", Some((255, 1)));
+    helper(be_u16(), b"\xff\xfe\x00", Some((0xfffe, 2)));
+    helper(le_u32(), b"\xff\xff\xff\xff\x01", Some((u32::MAX, 4)));
+
+    // short input
+    helper(u8(), b"", None);
+    helper(le_u16(), b"\x01", None);
+    helper(be_u16(), b"", None);
+    helper(le_u32(), b"\x01\x02\x03", None);
+    helper(be_u32(), b"\x01\x02\x03", None);
+    helper(le_u64(), &[0; 7], None);
+    helper(be_u64(), &[0; 7], None);
+    helper(le_f32(), &[0; 3], None);
+    helper(be_f32(), &[0; 3], None);
+    helper(le_f64(), &[0; 7], None);
+    helper(be_f64(), &[0; 7], None);
+
+    // debug
+    assert_eq!(format!("{:?}", BigEndian::<u16>::new()), "BigEndian<u16>");
+  }
+
+  #[test]
+  fn combinator_bytes_num_header() {
+    // magic + version + length + payload
+    let header = || bytes::eat(b"WH") + u8().tuple() + le_u32().tuple() + be_u16().tuple();
+    helper(
+      header(),
+      b"WH\x02\x10\x00\x00\x00\x01\x00rest",
+      Some(((2, 16, 256), 9)),
+    );
+    // wrong magic
+    helper(header(), b"WX\x02\x10\x00\x00\x00\x01\x00", None);
+    // truncated
+    helper(header(), b"WH\x02\x10\x00\x00\x00\x01", None);
+
+    // repeat with a count read from the input
+    helper(
+      u8()
+        .then_repeat(|n| *n as usize, be_u16())
+        .fold(Vec::new, |mut acc, v| {
+          acc.push(v);
+          acc
+        }),
+      b"\x02\x00\x01\x00\x02",
+      Some(((2, vec![1, 2]), 5)),
+    );
+  }
+
+  fn _num_debug_clone_copy() {
+    let c = le_u32();
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -1,6 +1,6 @@
 use crate::{
   action::{Action, Input, Output},
-  combinator::Combinator,
+  combinator::{write_head, Combinator, Describe},
   instant::Instant,
 };
 
@@ -18,6 +18,13 @@ impl Take {
   }
 }
 
+impl Describe for Take {
+  fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write_head(f, "Take", module_path!())?;
+    write!(f, " {})", self.n)
+  }
+}
+
 unsafe impl Action for Take {
   type Text = [u8];
   type State = ();
@@ -49,6 +56,136 @@ pub const fn take(n: usize) -> Combinator<Take> {
   Combinator::new(Take::new(n))
 }
 
+/// Returns a combinator to take the next `n` undigested bytes.
+///
+/// This is an alias of [`take`] to make the rejection explicit at the call site:
+/// the combinator will reject if there are not enough bytes.
+/// For the saturating version, see [`take_at_most`].
+#[inline]
+pub const fn take_exact(n: usize) -> Combinator<Take> {
+  take(n)
+}
+
+/// See [`take_at_most`].
+#[derive(Copy, Clone, Debug)]
+pub struct TakeAtMost {
+  n: usize,
+}
+
+impl TakeAtMost {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+unsafe impl Action for TakeAtMost {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = usize;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let n = self.n.min(input.instant.rest().len());
+    Some(Output {
+      value: n,
+      digested: n,
+    })
+  }
+}
+
+/// Returns a combinator to take at most `n` undigested bytes.
+///
+/// The combinator will always accept, with `min(n, remaining bytes)` bytes digested.
+/// The value is the number of taken bytes.
+/// For the rejecting version, see [`take_exact`].
+///
+/// Be careful with infinite loops since this may accept 0 bytes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = usize>>) {}
+/// # t(
+/// bytes::take_at_most(10) // take up to 10 bytes
+/// # );
+/// ```
+#[inline]
+pub const fn take_at_most(n: usize) -> Combinator<TakeAtMost> {
+  Combinator::new(TakeAtMost::new(n))
+}
+
+/// See [`skip_at_most`].
+#[derive(Copy, Clone)]
+pub struct SkipAtMost<F> {
+  n: usize,
+  predicate: F,
+}
+
+impl<F> SkipAtMost<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize, predicate: F) -> Self {
+    Self { n, predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for SkipAtMost<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SkipAtMost").field("n", &self.n).finish()
+  }
+}
+
+unsafe impl<F: Fn(u8) -> bool> Action for SkipAtMost<F> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = usize;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let bounded = &rest[..self.n.min(rest.len())];
+    let n = bounded
+      .iter()
+      .position(|b| !(self.predicate)(*b))
+      .unwrap_or(bounded.len());
+    Some(Output {
+      value: n,
+      digested: n,
+    })
+  }
+}
+
+/// Returns a combinator to take at most `n` undigested bytes
+/// which satisfy the `predicate`.
+///
+/// The combinator will always accept and stop at the first byte which doesn't satisfy the `predicate`.
+/// The value is the number of taken bytes.
+/// This is useful for bounded skipping during error recovery.
+///
+/// Be careful with infinite loops since this may accept 0 bytes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = usize>>) {}
+/// // skip up to 100 bytes until a semicolon
+/// # t(
+/// bytes::skip_at_most(100, |b| b != b';')
+/// # );
+/// ```
+#[inline]
+pub const fn skip_at_most<F: Fn(u8) -> bool>(n: usize, predicate: F) -> Combinator<SkipAtMost<F>> {
+  Combinator::new(SkipAtMost::new(n, predicate))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -85,6 +222,52 @@ mod tests {
     helper(take(0), b"123456", Some(0));
   }
 
+  fn exec<V>(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = V>,
+    input: &[u8],
+  ) -> Option<(V, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn test_take_exact() {
+    // exactly n remaining
+    helper(take_exact(3), b"123" as &[u8], Some(3));
+    // fewer
+    helper(take_exact(3), b"12", None);
+    // zero remaining
+    helper(take_exact(3), b"", None);
+    helper(take_exact(0), b"", Some(0));
+  }
+
+  #[test]
+  fn test_take_at_most() {
+    // more than n remaining
+    assert_eq!(exec(take_at_most(3), b"1234"), Some((3, 3)));
+    // exactly n remaining
+    assert_eq!(exec(take_at_most(3), b"123"), Some((3, 3)));
+    // fewer
+    assert_eq!(exec(take_at_most(3), b"12"), Some((2, 2)));
+    // zero remaining
+    assert_eq!(exec(take_at_most(3), b""), Some((0, 0)));
+  }
+
+  #[test]
+  fn test_skip_at_most() {
+    let skip = skip_at_most(3, |b| b != b';');
+    assert_eq!(exec(skip, b"1234"), Some((3, 3)));
+    assert_eq!(exec(skip, b"123"), Some((3, 3)));
+    assert_eq!(exec(skip, b"1;2"), Some((1, 1)));
+    assert_eq!(exec(skip, b";"), Some((0, 0)));
+    assert_eq!(exec(skip, b""), Some((0, 0)));
+  }
+
   fn _take_debug() {
     let _ = format!("{:?}", take(0));
   }
@@ -94,4 +277,15 @@ mod tests {
     let _c = c;
     let _c = c.clone();
   }
+
+  fn _take_at_most_debug_clone_copy() {
+    let c = take_at_most(0);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+    let c = skip_at_most(0, |_| true);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
 }
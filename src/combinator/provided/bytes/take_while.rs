@@ -0,0 +1,247 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// See [`take_while`] and [`take_while_min_max`].
+#[derive(Copy, Clone)]
+pub struct TakeWhile<F> {
+  predicate: F,
+  min: usize,
+  max: usize,
+}
+
+impl<F> TakeWhile<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F, min: usize, max: usize) -> Self {
+    Self {
+      predicate,
+      min,
+      max,
+    }
+  }
+}
+
+impl<F> std::fmt::Debug for TakeWhile<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TakeWhile")
+      .field("min", &self.min)
+      .field("max", &self.max)
+      .finish()
+  }
+}
+
+/// Return the number of the leading bytes of `rest` which satisfy `predicate`,
+/// at most `max` bytes.
+#[inline]
+fn scan(rest: &[u8], max: usize, predicate: impl Fn(u8) -> bool) -> usize {
+  let rest = &rest[..rest.len().min(max)];
+  rest
+    .iter()
+    .position(|b| !predicate(*b))
+    .unwrap_or(rest.len())
+}
+
+unsafe impl<F: Fn(u8) -> bool> Action for TakeWhile<F> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let digested = scan(input.instant.rest(), self.max, &self.predicate);
+    // SAFETY: `digested` is no greater than the length of the rest
+    (digested >= self.min).then(|| unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to take the undigested bytes which satisfy the `predicate`
+/// in one scan.
+///
+/// The combinator will always accept, even with 0 bytes digested.
+/// This is a faster version of `bytes::next(predicate) * (..)`.
+/// To require at least one byte, use [`take_while_min_max`].
+/// # Caveats
+/// Be careful with infinite loops since this may accept 0 bytes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::take_while(|b| b.is_ascii_digit())
+/// # );
+/// ```
+#[inline]
+pub const fn take_while<F: Fn(u8) -> bool>(predicate: F) -> Combinator<TakeWhile<F>> {
+  take_while_min_max(predicate, 0, usize::MAX)
+}
+
+/// Returns a combinator to take at least `min` and at most `max` undigested bytes
+/// which satisfy the `predicate` in one scan.
+///
+/// The combinator will stop at the first byte which doesn't satisfy the `predicate`
+/// or after `max` bytes are taken,
+/// and reject if fewer than `min` bytes are taken.
+/// If `min` is greater than `max`, the combinator will always reject.
+///
+/// This is a faster version of `bytes::next(predicate) * (min..=max)`.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// // an identifier with at least one byte
+/// # t(
+/// bytes::take_while_min_max(|b| b.is_ascii_alphanumeric(), 1, usize::MAX)
+/// # );
+/// // a 2-digit hex
+/// # t(
+/// bytes::take_while_min_max(|b| b.is_ascii_hexdigit(), 2, 2)
+/// # );
+/// ```
+#[inline]
+pub const fn take_while_min_max<F: Fn(u8) -> bool>(
+  predicate: F,
+  min: usize,
+  max: usize,
+) -> Combinator<TakeWhile<F>> {
+  Combinator::new(TakeWhile::new(predicate, min, max))
+}
+
+/// See [`take_till`].
+#[derive(Copy, Clone)]
+pub struct TakeTill<F> {
+  predicate: F,
+}
+
+impl<F> TakeTill<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F) -> Self {
+    Self { predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for TakeTill<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TakeTill").finish()
+  }
+}
+
+unsafe impl<F: Fn(u8) -> bool> Action for TakeTill<F> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let digested = scan(input.instant.rest(), usize::MAX, |b| !(self.predicate)(b));
+    // SAFETY: `digested` is no greater than the length of the rest
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to take the undigested bytes
+/// until the first byte which satisfies the `predicate` (exclusive) or the end of input.
+///
+/// The combinator will always accept, even with 0 bytes digested.
+/// To stop at a fixed set of bytes, [`eat_until`](crate::combinator::bytes::eat_until) is faster.
+/// # Caveats
+/// Be careful with infinite loops since this may accept 0 bytes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::take_till(|b| b.is_ascii_whitespace())
+/// # );
+/// ```
+#[inline]
+pub const fn take_till<F: Fn(u8) -> bool>(predicate: F) -> Combinator<TakeTill<F>> {
+  Combinator::new(TakeTill::new(predicate))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = ()>,
+    input: &[u8],
+    digested: Option<usize>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| o.digested),
+      digested
+    )
+  }
+
+  #[test]
+  fn combinator_take_while() {
+    let digit = |b: u8| b.is_ascii_digit();
+    helper(take_while(digit), b"123abc", Some(3));
+    helper(take_while(digit), b"123", Some(3));
+    helper(take_while(digit), b"abc", Some(0));
+    helper(take_while(digit), b"", Some(0));
+  }
+
+  #[test]
+  fn combinator_take_while_min_max() {
+    let digit = |b: u8| b.is_ascii_digit();
+    // min
+    helper(take_while_min_max(digit, 1, usize::MAX), b"123a", Some(3));
+    helper(take_while_min_max(digit, 1, usize::MAX), b"a", None);
+    helper(take_while_min_max(digit, 1, usize::MAX), b"", None);
+    helper(take_while_min_max(digit, 3, usize::MAX), b"123", Some(3));
+    helper(take_while_min_max(digit, 3, usize::MAX), b"12a", None);
+    // max
+    helper(take_while_min_max(digit, 0, 2), b"123", Some(2));
+    helper(take_while_min_max(digit, 0, 2), b"12", Some(2));
+    helper(take_while_min_max(digit, 0, 2), b"1", Some(1));
+    helper(take_while_min_max(digit, 2, 2), b"123", Some(2));
+    helper(take_while_min_max(digit, 2, 2), b"1a", None);
+    helper(take_while_min_max(digit, 0, 0), b"123", Some(0));
+    // min > max
+    helper(take_while_min_max(digit, 3, 2), b"1234", None);
+    // empty input
+    helper(take_while_min_max(digit, 0, 2), b"", Some(0));
+  }
+
+  #[test]
+  fn combinator_take_till() {
+    let ws = |b: u8| b.is_ascii_whitespace();
+    helper(take_till(ws), b"abc def", Some(3));
+    helper(take_till(ws), b"abc", Some(3));
+    helper(take_till(ws), b" abc", Some(0));
+    helper(take_till(ws), b"", Some(0));
+  }
+
+  fn _take_while_debug() {
+    let _ = format!("{:?}", take_while(|_| true));
+    let _ = format!("{:?}", take_till(|_| true));
+  }
+
+  fn _take_while_clone_copy() {
+    let c = take_while(|_| true);
+    let _c = c;
+    let _c = c.clone();
+    let c = take_till(|_| true);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
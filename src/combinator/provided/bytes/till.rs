@@ -1,11 +1,44 @@
 use crate::{
   action::{Action, Input, Output},
-  combinator::{provided::create_value_combinator, Combinator},
+  combinator::{
+    provided::{create_value_combinator, till::Terminator},
+    Combinator,
+  },
+  idx,
   instant::Instant,
+  range::Range,
 };
 
 create_value_combinator!(Till, "See [`till`].");
 
+impl Terminator for u8 {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    1
+  }
+}
+
+impl Terminator for &[u8] {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<const N: usize> Terminator for &[u8; N] {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    N
+  }
+}
+
+impl Terminator for Vec<u8> {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    self.len()
+  }
+}
+
 unsafe impl Action for Till<u8> {
   type Text = [u8];
   type State = ();
@@ -23,7 +56,7 @@ unsafe impl Action for Till<u8> {
       .iter()
       .enumerate()
       .find(|(_, b)| **b == self.inner)
-      .map(|(i, _)| unsafe { input.instant.accept_unchecked(i.unchecked_add(1)) })
+      .map(|(i, _)| unsafe { input.instant.accept_unchecked(idx::add(i, 1)) })
   }
 }
 
@@ -49,7 +82,7 @@ unsafe impl Action for Till<&[u8]> {
         .map(|(i, _)| unsafe {
           input
             .instant
-            .accept_unchecked(i.unchecked_add(self.inner.len()))
+            .accept_unchecked(idx::add(i, self.inner.len()))
         })
     } else {
       // window length can't be zero so we need special handling
@@ -80,7 +113,7 @@ unsafe impl<const N: usize> Action for Till<&[u8; N]> {
         .windows(N)
         .enumerate()
         .find(|(_, window)| *window == self.inner)
-        .map(|(i, _)| unsafe { input.instant.accept_unchecked(i.unchecked_add(N)) })
+        .map(|(i, _)| unsafe { input.instant.accept_unchecked(idx::add(i, N)) })
     } else {
       // window length can't be zero so we need special handling
       Some(Output {
@@ -113,7 +146,7 @@ unsafe impl Action for Till<Vec<u8>> {
         .map(|(i, _)| unsafe {
           input
             .instant
-            .accept_unchecked(i.unchecked_add(self.inner.len()))
+            .accept_unchecked(idx::add(i, self.inner.len()))
         })
     } else {
       // window length can't be zero so we need special handling
@@ -169,6 +202,218 @@ pub const fn till<T>(pattern: T) -> Combinator<Till<T>> {
   Combinator::new(Till::new(pattern))
 }
 
+/// See [`till_at_most`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillAtMost<T> {
+  n: usize,
+  till: Till<T>,
+}
+
+impl<T> TillAtMost<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize, pattern: T) -> Self {
+    Self {
+      n,
+      till: Till::new(pattern),
+    }
+  }
+}
+
+unsafe impl<T> Action for TillAtMost<T>
+where
+  Till<T>: Action<Text = [u8], State = (), Heap = (), Value = ()>,
+{
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let end = self.n.min(rest.len());
+    // only scan the first `n` bytes
+    self.till.exec(Input {
+      // SAFETY: `end` is in bounds
+      instant: &Instant::new(idx::unchecked!(idx::slice_to(rest, end))),
+      state: &mut (),
+      heap: &mut (),
+    })
+  }
+}
+
+/// Return a combinator like [`till`],
+/// but only the first `n` undigested bytes are scanned,
+/// so the pattern must end within `n` bytes.
+///
+/// Use this instead of [`till`] on untrusted inputs
+/// (e.g. to find the end of a string literal or a comment),
+/// so an unterminated token won't scan the whole input.
+/// With `()`, this will eat at most `n` bytes.
+/// See [`Combinator::max_len`] for more information.
+/// # Examples
+/// ```
+/// # use whitehole::combinator::{bytes, Combinator};
+/// # use whitehole::action::Action;
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::till_at_most(1024, b'"') // with u8
+/// # );
+/// ```
+#[inline]
+pub const fn till_at_most<T>(n: usize, pattern: T) -> Combinator<TillAtMost<T>> {
+  Combinator::new(TillAtMost::new(n, pattern))
+}
+
+/// Return a combinator to eat all the bytes till the end of the text.
+/// This is the same as `bytes::till(())`.
+/// # Caveats
+/// This will accept 0 bytes when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::till_eof()
+/// # );
+/// ```
+#[inline]
+pub const fn till_eof() -> Combinator<Till<()>> {
+  till(())
+}
+
+/// See [`till_exclusive`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillExclusive<T> {
+  till: Till<T>,
+}
+
+impl<T> TillExclusive<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self {
+      till: Till::new(pattern),
+    }
+  }
+}
+
+#[allow(private_bounds)]
+unsafe impl<T: Terminator> Action for TillExclusive<T>
+where
+  Till<T>: Action<Text = [u8], State = (), Heap = (), Value = ()>,
+{
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.till.exec(input).map(|output| Output {
+      value: (),
+      // SAFETY: `till` digests the pattern at the end, so this won't underflow
+      digested: idx::unchecked!(idx::sub(output.digested, self.till.inner.terminator_len())),
+    })
+  }
+}
+
+/// Return a combinator like [`till`],
+/// but stop before the first occurrence of the pattern (exclusive),
+/// so the pattern is left undigested.
+///
+/// With `()`, this is the same as [`till_eof`].
+/// # Caveats
+/// Empty patterns and `()` may accept 0 bytes.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// bytes::till_exclusive(b'\n') // with u8
+/// # );
+/// # t(
+/// bytes::till_exclusive(b"*/") // with &[u8] or &[u8; N]
+/// # );
+/// ```
+#[inline]
+pub const fn till_exclusive<T>(pattern: T) -> Combinator<TillExclusive<T>> {
+  Combinator::new(TillExclusive::new(pattern))
+}
+
+/// See [`till_inclusive`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillInclusive<T> {
+  till: Till<T>,
+}
+
+impl<T> TillInclusive<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self {
+      till: Till::new(pattern),
+    }
+  }
+}
+
+#[allow(private_bounds)]
+unsafe impl<T: Terminator> Action for TillInclusive<T>
+where
+  Till<T>: Action<Text = [u8], State = (), Heap = (), Value = ()>,
+{
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = Range;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let start = input.instant.digested();
+    self.till.exec(input).map(|output| {
+      // SAFETY: `till` digests the pattern at the end, so this won't underflow
+      let body = idx::unchecked!(idx::sub(output.digested, self.till.inner.terminator_len()));
+      Output {
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        value: start..idx::unchecked!(idx::add(start, body)),
+        digested: output.digested,
+      }
+    })
+  }
+}
+
+/// Return a combinator like [`till`] which consumes the pattern (inclusive),
+/// but the value is the byte range before the pattern.
+///
+/// Like [`Combinator::range`], the range is relative to the start of the whole text.
+/// # Caveats
+/// Empty patterns and `()` may accept 0 bytes.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes, parser::Parser};
+/// let entry = bytes::eat(b"/*") + bytes::till_inclusive(b"*/").tuple();
+/// let output = Parser::builder().entry(entry).build(b"/* x */").next().unwrap();
+/// assert_eq!(output.digested, 7);
+/// assert_eq!(output.value, (2..5,));
+/// ```
+#[inline]
+pub const fn till_inclusive<T>(pattern: T) -> Combinator<TillInclusive<T>> {
+  Combinator::new(TillInclusive::new(pattern))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -219,4 +464,104 @@ mod tests {
     helper(till(()), b"123" as &[u8], Some(3));
     helper(till(()), b"" as &[u8], Some(0));
   }
+
+  #[test]
+  fn test_till_at_most() {
+    helper(till_at_most(4, b';'), b"123;456", Some(4));
+    helper(till_at_most(3, b';'), b"123;456", None);
+    helper(till_at_most(100, b';'), b"123456", None);
+    helper(till_at_most(6, b"end"), b"123end456", Some(6));
+    helper(till_at_most(5, b"end"), b"123end456", None);
+    helper(till_at_most(6, b"end".to_vec()), b"123end456", Some(6));
+    helper(till_at_most(2, ()), b"123" as &[u8], Some(2));
+    helper(till_at_most(5, ()), b"123" as &[u8], Some(3));
+
+    // debug
+    let _ = format!("{:?}", till_at_most(1, b';'));
+  }
+
+  fn _till_at_most_clone_copy() {
+    let c = till_at_most(1, b';');
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn test_till_eof() {
+    helper(till_eof(), b"123" as &[u8], Some(3));
+    helper(till_eof(), b"" as &[u8], Some(0));
+  }
+
+  #[test]
+  fn test_till_exclusive() {
+    helper(till_exclusive(b';'), b"123;456", Some(3));
+    helper(till_exclusive(b';'), b"123456", None);
+    helper(till_exclusive(b"end"), b"123end456", Some(3));
+    helper(till_exclusive(b"end".as_slice()), b"end", Some(0));
+    helper(till_exclusive(b"end".to_vec()), b"1end", Some(1));
+    helper(till_exclusive(b""), b"123", Some(0));
+    helper(till_exclusive(()), b"123" as &[u8], Some(3));
+    // multi-byte terminators
+    helper(till_exclusive("好".as_bytes()), "a好b".as_bytes(), Some(1));
+
+    // debug
+    let _ = format!("{:?}", till_exclusive(b';'));
+  }
+
+  #[test]
+  fn test_till_inclusive() {
+    fn exec(
+      action: impl Action<Text = [u8], State = (), Heap = (), Value = Range>,
+      instant: &Instant<&[u8]>,
+    ) -> Option<(Range, usize)> {
+      action
+        .exec(Input {
+          instant,
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    }
+
+    assert_eq!(
+      exec(till_inclusive(b';'), &Instant::new(b"123;456")),
+      Some((0..3, 4))
+    );
+    assert_eq!(exec(till_inclusive(b';'), &Instant::new(b"123")), None);
+    assert_eq!(
+      exec(till_inclusive(b"end".to_vec()), &Instant::new(b"1end")),
+      Some((0..1, 4))
+    );
+    assert_eq!(
+      exec(till_inclusive(()), &Instant::new(b"123")),
+      Some((0..3, 3))
+    );
+    // multi-byte terminators
+    assert_eq!(
+      exec(
+        till_inclusive("好".as_bytes()),
+        &Instant::new("a好b".as_bytes())
+      ),
+      Some((0..1, 4))
+    );
+    // the range is relative to the whole text
+    assert_eq!(
+      exec(till_inclusive(b"*/"), &unsafe {
+        Instant::new(b"/* x */" as &[u8]).to_digested_unchecked(2)
+      }),
+      Some((2..5, 5))
+    );
+
+    // debug
+    let _ = format!("{:?}", till_inclusive(b';'));
+  }
+
+  fn _till_exclusive_inclusive_clone_copy() {
+    let c = till_exclusive(b';');
+    let _c = c;
+    let _c = c.clone();
+    let c = till_inclusive(b';');
+    let _c = c;
+    let _c = c.clone();
+  }
 }
@@ -0,0 +1,595 @@
+//! Combinators for binary tag-length-value formats,
+//! e.g. the protobuf wire format, BER or many proprietary protocols.
+//!
+//! - [`varint_u64`] reads an unsigned LEB128 integer.
+//! - [`length_prefixed`] reads a length, then restricts an inner combinator
+//!   to exactly that many bytes.
+//! - [`tagged`] reads a tag, then dispatches the length-prefixed value
+//!   to an inner combinator by the tag.
+//!
+//! Inner combinators of [`length_prefixed`] and [`tagged`] are executed against the region only,
+//! so [`Instant::text`] in inner combinators is the region instead of the whole text,
+//! and positions (e.g. [`Accepted::start`](crate::combinator::Accepted::start))
+//! are relative to the start of the region.
+//! # Examples
+//! ```
+//! use whitehole::{
+//!   action::Action,
+//!   combinator::bytes::{self, tlv::{tagged, varint_u64, Record}},
+//!   parser::Parser,
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Field {
+//!   Name(Vec<u8>),
+//!   Id(u64),
+//! }
+//!
+//! type Inner = Box<dyn Action<Text = [u8], State = (), Heap = (), Value = Field>>;
+//!
+//! // field 1 with wire type 2 is the name, field 2 with wire type 2 is a packed id
+//! let table: [(u64, Inner); 2] = [
+//!   (0x0a, Box::new((bytes::take(1) * ..).select(|accepted| Field::Name(accepted.content().to_vec())))),
+//!   (0x12, Box::new(varint_u64().map(Field::Id))),
+//! ];
+//! let message = tagged(varint_u64(), varint_u64(), table) * ..;
+//!
+//! let mut parser = Parser::builder()
+//!   .entry(message.fold(Vec::new, |mut acc: Vec<Field>, record: Record<Field>| {
+//!     acc.push(record.value.unwrap());
+//!     acc
+//!   }))
+//!   .build(b"\x0a\x03abc\x12\x02\xac\x02");
+//! assert_eq!(
+//!   parser.next().unwrap().value,
+//!   [Field::Name(b"abc".to_vec()), Field::Id(300)]
+//! );
+//! ```
+
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// See [`varint_u64`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Varint;
+
+impl Varint {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for Varint {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = u64;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut value: u64 = 0;
+    // a u64 needs at most 10 bytes
+    for (i, b) in input.instant.rest().iter().take(10).enumerate() {
+      let low = (b & 0x7f) as u64;
+      if i == 9 && low > 1 {
+        // overflow
+        return None;
+      }
+      value |= low << (7 * i);
+      if b & 0x80 == 0 {
+        return Some(Output {
+          value,
+          digested: i + 1,
+        });
+      }
+    }
+    // truncated, or too many continuation bytes
+    None
+  }
+}
+
+/// Returns a combinator to parse an unsigned LEB128 integer as a [`u64`],
+/// which is used as the varint in the protobuf wire format.
+///
+/// The combinator will reject if the input is truncated or the value overflows.
+/// Redundant continuation bytes (e.g. `b"\x80\x00"`) are accepted
+/// as long as the whole varint is not longer than 10 bytes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes::tlv::varint_u64, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = u64>>) {}
+/// // b"\xac\x02" is 300
+/// # t(
+/// varint_u64()
+/// # );
+/// ```
+#[inline]
+pub const fn varint_u64() -> Combinator<Varint> {
+  Combinator::new(Varint::new())
+}
+
+/// Execute `inner` against `rest[start..start + n]`,
+/// accept only if `inner` digests exactly `n` bytes.
+/// [`Output::digested`] is `start + n`.
+#[inline]
+fn exec_region<T: Action<Text = [u8]>>(
+  inner: &T,
+  mut input: Input<&Instant<&[u8]>, &mut T::State, &mut T::Heap>,
+  start: usize,
+  n: usize,
+) -> Option<Output<T::Value>> {
+  let end = start.checked_add(n)?;
+  // reject if truncated
+  let region = input.instant.rest().get(start..end)?;
+  let instant = Instant::new(region);
+  let output = inner.exec(input.reborrow_with(&instant))?;
+  if output.digested == n {
+    Some(Output {
+      value: output.value,
+      digested: end,
+    })
+  } else {
+    // under-consumed
+    None
+  }
+}
+
+/// Execute the length action and convert the value to [`usize`].
+#[inline]
+fn exec_len<L: Action<Text = [u8], Value: Into<u64>>>(
+  len: &L,
+  input: Input<&Instant<&[u8]>, &mut L::State, &mut L::Heap>,
+) -> Option<(usize, usize)> {
+  let output = len.exec(input)?;
+  Some((output.digested, usize::try_from(output.value.into()).ok()?))
+}
+
+/// See [`length_prefixed`].
+#[derive(Debug, Clone, Copy)]
+pub struct LengthPrefixed<L, T> {
+  len: L,
+  inner: T,
+}
+
+impl<L, T> LengthPrefixed<L, T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(len: L, inner: T) -> Self {
+    Self { len, inner }
+  }
+}
+
+unsafe impl<
+    L: Action<Text = [u8], State = T::State, Heap = T::Heap, Value: Into<u64>>,
+    T: Action<Text = [u8]>,
+  > Action for LengthPrefixed<L, T>
+{
+  type Text = [u8];
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let (start, n) = exec_len(&self.len, input.reborrow())?;
+    exec_region(&self.inner, input, start, n)
+  }
+}
+
+/// Returns a combinator to read a length by `len`,
+/// then execute `inner` against exactly that many bytes after the length.
+///
+/// The combinator will reject if the input is shorter than the length,
+/// or `inner` digests less than the length.
+/// `inner` can never digest more than the length since it only sees the region.
+///
+/// The value of `len` can be any unsigned integer which implements `Into<u64>`.
+/// The value of the combinator is the value of `inner`.
+/// See the [module level documentation](self) for more information.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes::{self, tlv::{length_prefixed, varint_u64}}, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// // a length-delimited byte string, e.g. b"\x03abc"
+/// # t(
+/// length_prefixed(varint_u64(), bytes::take(1) * ..)
+/// # );
+/// ```
+#[inline]
+pub fn length_prefixed<L, T>(
+  len: Combinator<L>,
+  inner: Combinator<T>,
+) -> Combinator<LengthPrefixed<L, T>> {
+  Combinator::new(LengthPrefixed::new(len.action, inner.action))
+}
+
+/// The value of [`tagged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Record<V> {
+  /// The tag.
+  pub tag: u64,
+  /// The value of the inner combinator.
+  /// This is [`None`] if the tag is unknown and skipped.
+  /// See [`Combinator::skip_unknown`].
+  pub value: Option<V>,
+}
+
+/// A table which maps tags to inner combinators.
+/// See [`tagged`].
+///
+/// This is implemented for arrays, slices and [`Vec`]s of `(tag, inner)` pairs
+/// (searched linearly), and [`HashMap`]s.
+pub trait DispatchTable {
+  /// The inner action.
+  type Inner;
+
+  /// Get the inner action by the tag.
+  fn get(&self, tag: u64) -> Option<&Self::Inner>;
+}
+
+impl<T> DispatchTable for [(u64, T)] {
+  type Inner = T;
+
+  #[inline]
+  fn get(&self, tag: u64) -> Option<&Self::Inner> {
+    self.iter().find(|(t, _)| *t == tag).map(|(_, inner)| inner)
+  }
+}
+
+impl<T, const N: usize> DispatchTable for [(u64, T); N] {
+  type Inner = T;
+
+  #[inline]
+  fn get(&self, tag: u64) -> Option<&Self::Inner> {
+    DispatchTable::get(self.as_slice(), tag)
+  }
+}
+
+impl<T> DispatchTable for Vec<(u64, T)> {
+  type Inner = T;
+
+  #[inline]
+  fn get(&self, tag: u64) -> Option<&Self::Inner> {
+    DispatchTable::get(self.as_slice(), tag)
+  }
+}
+
+impl<T, S: BuildHasher> DispatchTable for HashMap<u64, T, S> {
+  type Inner = T;
+
+  #[inline]
+  fn get(&self, tag: u64) -> Option<&Self::Inner> {
+    HashMap::get(self, &tag)
+  }
+}
+
+impl<Table: DispatchTable + ?Sized> DispatchTable for &Table {
+  type Inner = Table::Inner;
+
+  #[inline]
+  fn get(&self, tag: u64) -> Option<&Self::Inner> {
+    (**self).get(tag)
+  }
+}
+
+/// See [`tagged`].
+#[derive(Debug, Clone, Copy)]
+pub struct Tagged<Tag, L, Table> {
+  tag: Tag,
+  len: L,
+  table: Table,
+  skip_unknown: bool,
+}
+
+impl<Tag, L, Table> Tagged<Tag, L, Table> {
+  /// Create a new instance.
+  /// Unknown tags are rejected by default.
+  #[inline]
+  pub const fn new(tag: Tag, len: L, table: Table) -> Self {
+    Self {
+      tag,
+      len,
+      table,
+      skip_unknown: false,
+    }
+  }
+}
+
+impl<Tag, L, Table> Combinator<Tagged<Tag, L, Table>> {
+  /// Skip the value of an unknown tag using the length
+  /// and yield a [`Record`] with [`Record::value`] set to [`None`],
+  /// instead of rejecting.
+  #[inline]
+  pub fn skip_unknown(self) -> Self {
+    Self::new(Tagged {
+      skip_unknown: true,
+      ..self.action
+    })
+  }
+}
+
+unsafe impl<
+    Table: DispatchTable<Inner: Action<Text = [u8], State = Tag::State, Heap = Tag::Heap>>,
+    Tag: Action<Text = [u8], Value: Into<u64>>,
+    L: Action<Text = [u8], State = Tag::State, Heap = Tag::Heap, Value: Into<u64>>,
+  > Action for Tagged<Tag, L, Table>
+{
+  type Text = [u8];
+  type State = Tag::State;
+  type Heap = Tag::Heap;
+  type Value = Record<<Table::Inner as Action>::Value>;
+
+  #[inline]
+  fn exec(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let tag_output = self.tag.exec(input.reborrow())?;
+    let tag = tag_output.value.into();
+
+    // SAFETY: `tag_output.digested` is valid according to the `Action` trait
+    let instant = unsafe { input.instant.to_digested_unchecked(tag_output.digested) };
+    let (len_digested, n) = exec_len(&self.len, input.reborrow_with(&instant))?;
+    let start = tag_output.digested.checked_add(len_digested)?;
+
+    match self.table.get(tag) {
+      Some(inner) => exec_region(inner, input, start, n).map(|output| {
+        output.map(|value| Record {
+          tag,
+          value: Some(value),
+        })
+      }),
+      None => {
+        if !self.skip_unknown {
+          return None;
+        }
+        let end = start.checked_add(n)?;
+        // reject if truncated
+        (end <= input.instant.rest().len()).then_some(Output {
+          value: Record { tag, value: None },
+          digested: end,
+        })
+      }
+    }
+  }
+}
+
+/// Returns a combinator to read a tag by `tag` and a length by `len`,
+/// then execute the inner combinator selected by the tag against exactly that many bytes.
+///
+/// The `table` is a [`DispatchTable`], e.g. an array of `(tag, inner)` pairs.
+/// Use boxed actions if the inner combinators have different types.
+/// Unknown tags are rejected by default, see [`Combinator::skip_unknown`].
+///
+/// The value is a [`Record`].
+/// See the [module level documentation](self) for more information.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes::{self, tlv::{tagged, varint_u64}}, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8]>>) {}
+/// # t(
+/// tagged(varint_u64(), varint_u64(), [(0x08, varint_u64()), (0x10, varint_u64())]).skip_unknown()
+/// # );
+/// ```
+#[inline]
+pub fn tagged<Tag, L, Table>(
+  tag: Combinator<Tag>,
+  len: Combinator<L>,
+  table: Table,
+) -> Combinator<Tagged<Tag, L, Table>> {
+  Combinator::new(Tagged::new(tag.action, len.action, table))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::bytes::{eat, take};
+
+  fn helper<Value>(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = Value>,
+    input: &[u8],
+  ) -> Option<(Value, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn combinator_varint_u64() {
+    assert_eq!(helper(varint_u64(), b"\x00"), Some((0, 1)));
+    assert_eq!(helper(varint_u64(), b"\x01\xff"), Some((1, 1)));
+    assert_eq!(helper(varint_u64(), b"\x7f"), Some((127, 1)));
+    assert_eq!(helper(varint_u64(), b"\xac\x02"), Some((300, 2)));
+    assert_eq!(
+      helper(varint_u64(), b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01"),
+      Some((u64::MAX, 10))
+    );
+    // redundant continuation bytes
+    assert_eq!(helper(varint_u64(), b"\x80\x00"), Some((0, 2)));
+    // empty
+    assert_eq!(helper(varint_u64(), b""), None);
+    // truncated
+    assert_eq!(helper(varint_u64(), b"\xac"), None);
+    // overflow
+    assert_eq!(
+      helper(varint_u64(), b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"),
+      None
+    );
+    // too long
+    assert_eq!(
+      helper(
+        varint_u64(),
+        b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x00"
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn combinator_length_prefixed() {
+    let c = length_prefixed(varint_u64(), take(1) * ..);
+    assert_eq!(helper(c, b"\x03abcd"), Some(((), 4)));
+    assert_eq!(helper(c, b"\x00abcd"), Some(((), 1)));
+    // truncated length
+    assert_eq!(helper(c, b"\x03ab"), None);
+    assert_eq!(helper(c, b"\x83"), None);
+    assert_eq!(helper(c, b""), None);
+
+    // inner under-consumption
+    let c = length_prefixed(varint_u64(), eat(b"ab"));
+    assert_eq!(helper(c, b"\x02ab"), Some(((), 3)));
+    assert_eq!(helper(c, b"\x03abc"), None);
+    // inner can't over-consume
+    assert_eq!(helper(c, b"\x01ab"), None);
+
+    // inner sees the region only
+    let c = length_prefixed(
+      varint_u64(),
+      (take(1) * ..).select(|a| a.content().to_vec()),
+    );
+    assert_eq!(helper(c, b"\x02abc"), Some((b"ab".to_vec(), 3)));
+
+    // fixed width length
+    let c = length_prefixed(take(1).select(|a| a.content()[0]), take(1) * ..);
+    assert_eq!(helper(c, b"\x02ab"), Some(((), 3)));
+  }
+
+  #[derive(Debug, PartialEq)]
+  enum Field {
+    Name(Vec<u8>),
+    Id(u64),
+  }
+
+  type Inner = Box<dyn Action<Text = [u8], State = (), Heap = (), Value = Field>>;
+
+  fn table() -> Vec<(u64, Inner)> {
+    vec![
+      (
+        0x0a,
+        Box::new((take(1) * ..).select(|a| Field::Name(a.content().to_vec()))),
+      ),
+      (0x12, Box::new(varint_u64().map(Field::Id))),
+    ]
+  }
+
+  fn message(
+    record: impl Action<Text = [u8], State = (), Heap = (), Value = Record<Field>>,
+  ) -> impl Action<Text = [u8], State = (), Heap = (), Value = Vec<Record<Field>>> {
+    (Combinator::new(record) * ..).fold(Vec::new, |mut acc, r| {
+      acc.push(r);
+      acc
+    })
+  }
+
+  #[test]
+  fn combinator_tagged() {
+    // a two-record protobuf-like message
+    let c = message(tagged(varint_u64(), varint_u64(), table()));
+    assert_eq!(
+      helper(c, b"\x0a\x03abc\x12\x02\xac\x02"),
+      Some((
+        vec![
+          Record {
+            tag: 0x0a,
+            value: Some(Field::Name(b"abc".to_vec()))
+          },
+          Record {
+            tag: 0x12,
+            value: Some(Field::Id(300))
+          }
+        ],
+        9
+      ))
+    );
+
+    // truncated length
+    let c = tagged(varint_u64(), varint_u64(), table());
+    assert!(helper(&c, b"\x0a\x04abc").is_none());
+    assert!(helper(&c, b"\x0a").is_none());
+    assert!(helper(&c, b"\x0a\x83").is_none());
+
+    // inner under-consumption
+    assert!(helper(&c, b"\x12\x03\xac\x02\x00").is_none());
+
+    // unknown tag is rejected by default
+    assert!(helper(&c, b"\x1a\x01x").is_none());
+  }
+
+  #[test]
+  fn combinator_tagged_skip_unknown() {
+    let c = message(tagged(varint_u64(), varint_u64(), table()).skip_unknown());
+    assert_eq!(
+      helper(c, b"\x1a\x01x\x0a\x01a\x22\x00"),
+      Some((
+        vec![
+          Record {
+            tag: 0x1a,
+            value: None
+          },
+          Record {
+            tag: 0x0a,
+            value: Some(Field::Name(b"a".to_vec()))
+          },
+          Record {
+            tag: 0x22,
+            value: None
+          },
+        ],
+        8
+      ))
+    );
+
+    // truncated unknown value
+    let c = tagged(varint_u64(), varint_u64(), table()).skip_unknown();
+    assert!(helper(c, b"\x1a\x02x").is_none());
+  }
+
+  fn _varint_debug() {
+    let _ = format!("{:?}", varint_u64());
+  }
+
+  fn _varint_clone_copy() {
+    let c = varint_u64();
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  fn _length_prefixed_debug() {
+    let _ = format!("{:?}", length_prefixed(varint_u64(), take(1)));
+  }
+
+  fn _length_prefixed_clone_copy() {
+    let c = length_prefixed(varint_u64(), take(1));
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  fn _tagged_debug() {
+    let _ = format!(
+      "{:?}",
+      tagged(varint_u64(), varint_u64(), [(1, varint_u64())])
+    );
+  }
+
+  fn _tagged_clone_copy() {
+    let c = tagged(varint_u64(), varint_u64(), [(1, varint_u64())]);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
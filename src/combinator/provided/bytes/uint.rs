@@ -0,0 +1,141 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{provided::scan_bounded_uint, Combinator, Contextual},
+  instant::Instant,
+};
+
+/// See [`bounded_uint`] and [`uint_with_max_digits`].
+#[derive(Copy, Clone, Debug)]
+pub struct BoundedUint(crate::combinator::BoundedUint);
+
+impl BoundedUint {
+  /// Create a new instance.
+  /// Leading zeros are accepted by default.
+  #[inline]
+  pub const fn new(min: u64, max: u64, max_digits: usize) -> Self {
+    Self(crate::combinator::BoundedUint::new(min, max, max_digits))
+  }
+}
+
+impl Combinator<BoundedUint> {
+  /// Reject if the digits have leading zeros, e.g. `b"01"`.
+  /// A single `b"0"` is still accepted if it is in the bounds.
+  #[inline]
+  pub const fn reject_leading_zeros(self) -> Self {
+    Self::new(BoundedUint(self.action.0.with_leading_zeros_rejected()))
+  }
+}
+
+impl<State, Heap> Combinator<Contextual<BoundedUint, State, Heap>> {
+  /// See [`Combinator::<BoundedUint>::reject_leading_zeros`].
+  #[inline]
+  pub const fn reject_leading_zeros(self) -> Self {
+    Self::new(Contextual::new(BoundedUint(
+      self.action.action.0.with_leading_zeros_rejected(),
+    )))
+  }
+}
+
+unsafe impl Action for BoundedUint {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = u64;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_bounded_uint(input.instant.rest(), &self.0)
+      .map(|(value, digested)| Output { value, digested })
+  }
+}
+
+/// Returns a combinator to parse ascii digits as an unsigned integer
+/// within `min..=max` (inclusive).
+///
+/// See [`combinator::bounded_uint`](crate::combinator::bounded_uint) for more information.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = u64>>) {}
+/// // port number
+/// # t(
+/// bytes::bounded_uint(0, 65535)
+/// # );
+/// ```
+#[inline]
+pub const fn bounded_uint(min: u64, max: u64) -> Combinator<BoundedUint> {
+  Combinator::new(BoundedUint::new(min, max, usize::MAX))
+}
+
+/// Returns a combinator to parse at most `n` ascii digits as an unsigned integer.
+///
+/// See [`combinator::uint_with_max_digits`](crate::combinator::uint_with_max_digits) for more information.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bytes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u8], Value = u64>>) {}
+/// // year
+/// # t(
+/// bytes::uint_with_max_digits(4)
+/// # );
+/// ```
+#[inline]
+pub const fn uint_with_max_digits(n: usize) -> Combinator<BoundedUint> {
+  Combinator::new(BoundedUint::new(0, u64::MAX, n))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = u64>,
+    input: &[u8],
+  ) -> Option<(u64, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn combinator_bounded_uint() {
+    let port = bounded_uint(0, 65535);
+    assert_eq!(helper(port, b"0"), Some((0, 1)));
+    assert_eq!(helper(port, b"65535"), Some((65535, 5)));
+    assert_eq!(helper(port, b"65536"), None);
+    assert_eq!(helper(port, b""), None);
+    assert_eq!(helper(port, b"\xff"), None);
+    assert_eq!(helper(bounded_uint(1, 12), b"0"), None);
+    assert_eq!(helper(bounded_uint(1, 12), b"01"), Some((1, 2)));
+    assert_eq!(
+      helper(bounded_uint(1, 12).reject_leading_zeros(), b"01"),
+      None
+    );
+  }
+
+  #[test]
+  fn combinator_uint_with_max_digits() {
+    let year = uint_with_max_digits(4);
+    assert_eq!(helper(year, b"20251"), Some((2025, 4)));
+    assert_eq!(helper(year, b"0202"), Some((202, 4)));
+    assert_eq!(helper(year.reject_leading_zeros(), b"0202"), None);
+    assert_eq!(helper(year, b""), None);
+  }
+
+  fn _bounded_uint_debug() {
+    let _ = format!("{:?}", bounded_uint(0, 1));
+  }
+
+  fn _bounded_uint_clone_copy() {
+    let c = bounded_uint(0, 1);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
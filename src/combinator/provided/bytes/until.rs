@@ -0,0 +1,177 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{provided::create_value_combinator, Combinator},
+  instant::Instant,
+};
+use std::mem::size_of;
+
+create_value_combinator!(EatUntil, "See [`eat_until`].");
+
+const WORD: usize = size_of::<usize>();
+const LO: usize = usize::from_ne_bytes([0x01; WORD]);
+const HI: usize = usize::from_ne_bytes([0x80; WORD]);
+
+/// Whether any byte of `x` is zero.
+#[inline]
+const fn has_zero_byte(x: usize) -> bool {
+  x.wrapping_sub(LO) & !x & HI != 0
+}
+
+/// Return the index of the first byte in `haystack` which is one of `needles`.
+///
+/// For up to 3 needles, the haystack is scanned one word at a time (SWAR, like `memchr3`).
+/// Otherwise a lookup table is used.
+#[inline]
+pub(crate) fn find_any<const N: usize>(haystack: &[u8], needles: &[u8; N]) -> Option<usize> {
+  if N > 3 {
+    let mut table = [false; 256];
+    for n in needles {
+      table[*n as usize] = true;
+    }
+    return haystack.iter().position(|b| table[*b as usize]);
+  }
+
+  let repeated = needles.map(|n| LO * n as usize);
+  let mut i = 0;
+  // skip words which don't contain any needle
+  while let Some(chunk) = haystack.get(i..i + WORD) {
+    // the chunk length is always WORD
+    let word = usize::from_ne_bytes(chunk.try_into().unwrap());
+    if repeated.iter().any(|r| has_zero_byte(word ^ r)) {
+      break;
+    }
+    i += WORD;
+  }
+  haystack[i..]
+    .iter()
+    .position(|b| needles.contains(b))
+    .map(|p| i + p)
+}
+
+unsafe impl<const N: usize> Action for EatUntil<[u8; N]> {
+  type Text = [u8];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let n = find_any(rest, &self.inner).unwrap_or(rest.len());
+    // SAFETY: `n` is either the index of a needle in the rest or the length of the rest
+    Some(unsafe { input.instant.accept_unchecked(n) })
+  }
+}
+
+/// Returns a combinator to eat bytes until the first occurrence of any of the `needles`
+/// or the end of input.
+/// The needle is not digested.
+///
+/// The rest is scanned one word at a time for up to 3 needles,
+/// which is much faster than [`till`](crate::combinator::bytes::till) or
+/// [`next`](crate::combinator::bytes::next) with repetition for long inputs.
+/// # Caveats
+/// This will accept 0 bytes if the rest starts with a needle, is empty,
+/// or `needles` is empty (then the whole rest is digested).
+/// Be careful with infinite loops,
+/// use [`Combinator::reject`] to reject 0 bytes if needed.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::bytes::eat_until, parser::Parser};
+/// let entry = eat_until([b'\n', b'\r']);
+/// assert_eq!(
+///   Parser::builder().entry(entry).build(b"hello\nworld").next().unwrap().digested,
+///   5
+/// );
+/// ```
+#[inline]
+pub const fn eat_until<const N: usize>(needles: [u8; N]) -> Combinator<EatUntil<[u8; N]>> {
+  Combinator::new(EatUntil::new(needles))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = [u8], State = (), Heap = (), Value = ()>,
+    input: &[u8],
+  ) -> usize {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap()
+      .digested
+  }
+
+  fn naive<const N: usize>(haystack: &[u8], needles: &[u8; N]) -> Option<usize> {
+    haystack.iter().position(|b| needles.contains(b))
+  }
+
+  #[test]
+  fn bytes_find_any() {
+    let haystack: Vec<u8> = (0..=255).chain(0..=255).collect();
+    // every position, including across word boundaries
+    for offset in 0..40 {
+      let haystack = &haystack[offset..];
+      for n in [0u8, 1, 7, 8, 9, 0x7f, 0x80, 0xff] {
+        assert_eq!(find_any(haystack, &[n]), naive(haystack, &[n]));
+        assert_eq!(find_any(haystack, &[n, 3]), naive(haystack, &[n, 3]));
+        assert_eq!(find_any(haystack, &[9, n, 3]), naive(haystack, &[9, n, 3]));
+        assert_eq!(
+          find_any(haystack, &[9, n, 3, 200]),
+          naive(haystack, &[9, n, 3, 200])
+        );
+      }
+    }
+    // absent
+    assert_eq!(find_any(&[1; 100], &[0]), None);
+    assert_eq!(find_any(&[1; 100], &[0, 2, 3, 4]), None);
+    assert_eq!(find_any(&[], &[0]), None);
+    assert_eq!(find_any(&[0; 100], &[]), None);
+  }
+
+  #[test]
+  fn bytes_eat_until() {
+    // at position 0
+    assert_eq!(helper(eat_until([b',']), b",abc"), 0);
+    // in the middle
+    assert_eq!(helper(eat_until([b',']), b"abc,def"), 3);
+    assert_eq!(helper(eat_until([b',', b';']), b"abc;def,"), 3);
+    assert_eq!(helper(eat_until([b',', b';', b'\n']), b"abcdefghijk\n"), 11);
+    // at the end
+    assert_eq!(helper(eat_until([b',']), b"abcdefghijklmnopq,"), 17);
+    // absent
+    assert_eq!(helper(eat_until([b',']), b"abcdefghijklmnopq"), 17);
+    assert_eq!(helper(eat_until([b',']), b""), 0);
+    // no needles
+    assert_eq!(helper(eat_until([]), b"abc"), 3);
+    // many needles
+    assert_eq!(helper(eat_until(*b"0123456789"), b"abc5"), 3);
+    // respect the digested
+    assert_eq!(
+      eat_until([b','])
+        .exec(Input {
+          instant: &unsafe { Instant::new(b",a,b" as &[u8]).to_digested_unchecked(1) },
+          state: &mut (),
+          heap: &mut (),
+        })
+        .unwrap()
+        .digested,
+      1
+    );
+  }
+
+  fn _eat_until_debug_clone_copy() {
+    let c = eat_until([b',']);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
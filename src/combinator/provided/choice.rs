@@ -0,0 +1,403 @@
+use crate::{
+  action::{Action, Input, Output, Rejection},
+  combinator::{bytes, write_head, Combinator, Contextual, Describe, Eat, FirstSet, LookaheadSet},
+  instant::Instant,
+};
+use std::fmt;
+
+/// See [`choice`].
+#[derive(Copy, Clone, Debug)]
+pub struct Choice<T> {
+  actions: T,
+}
+
+impl<T> Choice<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(actions: T) -> Self {
+    Self { actions }
+  }
+}
+
+/// Convert a branch of [`choice!`](crate::choice) into an [`Action`].
+///
+/// This is implemented for [`Combinator`]s,
+/// and the literals which can be used at the right-hand side of `|`
+/// (see [`ops::bitor`](crate::combinator::ops::bitor)).
+/// Literals are converted to [`eat`](crate::combinator::eat) (or [`bytes::eat`])
+/// with the same `State` and `Heap` as the other branches.
+pub trait ChoiceBranch<State, Heap> {
+  /// The converted action.
+  type Action: Action<State = State, Heap = Heap>;
+
+  /// Convert self into [`Self::Action`].
+  fn into_branch(self) -> Self::Action;
+}
+
+impl<T: Action> ChoiceBranch<T::State, T::Heap> for Combinator<T> {
+  type Action = T;
+
+  #[inline]
+  fn into_branch(self) -> Self::Action {
+    self.action
+  }
+}
+
+macro_rules! impl_choice_branch_for_literal {
+  ($eat:ty, $lit:ty $(, $lt:lifetime)? $(, const $n:ident)?) => {
+    impl<$($lt,)? $(const $n: usize,)? State, Heap> ChoiceBranch<State, Heap> for $lit {
+      type Action = Contextual<$eat, State, Heap>;
+
+      #[inline]
+      fn into_branch(self) -> Self::Action {
+        Contextual::new(<$eat>::new(self))
+      }
+    }
+  };
+}
+impl_choice_branch_for_literal!(Eat<char>, char);
+impl_choice_branch_for_literal!(Eat<&'a str>, &'a str, 'a);
+impl_choice_branch_for_literal!(Eat<String>, String);
+impl_choice_branch_for_literal!(bytes::Eat<u8>, u8);
+impl_choice_branch_for_literal!(bytes::Eat<&'a [u8]>, &'a [u8], 'a);
+impl_choice_branch_for_literal!(bytes::Eat<&'a [u8; N]>, &'a [u8; N], 'a, const N);
+impl_choice_branch_for_literal!(bytes::Eat<Vec<u8>>, Vec<u8>);
+
+macro_rules! impl_choice {
+  ($first:ident $(, $rest:ident)*) => {
+    #[allow(non_snake_case)]
+    unsafe impl<
+        $first: Action,
+        $($rest: Action<
+          Text = $first::Text,
+          State = $first::State,
+          Heap = $first::Heap,
+          Value = $first::Value,
+        >),*
+      > Action for Choice<($first, $($rest),*)>
+    {
+      type Text = $first::Text;
+      type State = $first::State;
+      type Heap = $first::Heap;
+      type Value = $first::Value;
+
+      #[inline]
+      fn exec(
+        &self,
+        input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+      ) -> Option<Output<Self::Value>> {
+        self.exec_cut(input).ok()
+      }
+
+      #[inline]
+      fn exec_cut(
+        &self,
+        mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+      ) -> Result<Output<Self::Value>, Rejection> {
+        let ($first, $($rest),*) = &self.actions;
+        // try each branch in order, stop at the first accepted or cut one
+        let mut rejection = match $first.exec_cut(input.reborrow()) {
+          Err(rejection @ (Rejection::Backtrack | Rejection::Incomplete)) => rejection,
+          res => return res,
+        };
+        $(
+          match $rest.exec_cut(input.reborrow()) {
+            Err(other @ (Rejection::Backtrack | Rejection::Incomplete)) => {
+              rejection = rejection.merge(other)
+            }
+            res => return res,
+          }
+        )*
+        Err(rejection)
+      }
+    }
+
+    #[allow(non_snake_case)]
+    impl<$first: FirstSet $(, $rest: FirstSet)*> FirstSet for Choice<($first, $($rest),*)> {
+      #[inline]
+      fn first_set(&self) -> Option<LookaheadSet> {
+        let ($first, $($rest),*) = &self.actions;
+        Some($first.first_set()?$(.union(&$rest.first_set()?))*)
+      }
+    }
+
+    #[allow(non_snake_case)]
+    impl<$first: Describe $(, $rest: Describe)*> Describe for Choice<($first, $($rest),*)> {
+      fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ($first, $($rest),*) = &self.actions;
+        write_head(f, "Choice", module_path!())?;
+        f.write_str(" ")?;
+        $first.describe(f)?;
+        $(
+          f.write_str(" ")?;
+          $rest.describe(f)?;
+        )*
+        f.write_str(")")
+      }
+    }
+  };
+}
+impl_choice!(_1, _2);
+impl_choice!(_1, _2, _3);
+impl_choice!(_1, _2, _3, _4);
+impl_choice!(_1, _2, _3, _4, _5);
+impl_choice!(_1, _2, _3, _4, _5, _6);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7, _8);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7, _8, _9);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11);
+impl_choice!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12);
+
+/// Returns a combinator to try a tuple of actions in order,
+/// with the same semantics as chaining them with `|`.
+///
+/// The first accepted action wins, and a [`cut`](Combinator::cut) stops trying the rest.
+/// All actions must have the same value type.
+/// Tuples with 2 to 12 elements are supported.
+/// To use literals as branches or to have more than 12 branches, use [`choice!`](crate::choice).
+///
+/// Unlike nested `|`, the alternation is flattened into a single [`Action`],
+/// so the type of the combinator is much shallower.
+/// The runtime cost is the same: each branch is executed one by one until one accepts.
+/// This is preferred for long alternations.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{choice, eat, next, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// // same as `eat("true") | eat("false") | next(|c| c.is_ascii_digit())`
+/// # t(
+/// choice((eat("true"), eat("false"), next(|c| c.is_ascii_digit())))
+/// # );
+/// ```
+#[inline]
+pub const fn choice<T>(actions: T) -> Combinator<Choice<T>> {
+  Combinator::new(Choice::new(actions))
+}
+
+/// Create a [`choice`] from a list of branches, tried in order.
+///
+/// Each branch can be a [`Combinator`] or a literal shortcut of `|`
+/// (a [`char`], a [`&str`](str), a [`String`], a [`u8`], a `&[u8]`, a `&[u8; N]` or a `Vec<u8>`),
+/// see [`ChoiceBranch`](crate::combinator::ChoiceBranch).
+/// Unlike `|`, literals are allowed at any position, including the first one.
+/// Literals share the same `State` and `Heap` with the other branches,
+/// so this works with [`contextual`](crate::contextual) combinators too.
+///
+/// # Compile Time and Runtime
+/// `a | b | c | ...` creates a left-nested type `BitOr<BitOr<BitOr<A, B>, C>, ...>`
+/// whose depth grows with the number of branches,
+/// which slows down type checking and makes error messages hard to read.
+/// This macro creates a single flat [`Choice`] for up to 12 branches.
+/// Longer lists are split into nested [`Choice`]s of 12,
+/// so the depth only grows by one every 11 branches.
+///
+/// At runtime, this behaves exactly like chaining with `|`:
+/// branches are tried one by one until one accepts (or [`cut`](crate::combinator::Combinator::cut)s),
+/// so the cost of a rejection is the sum of all branches.
+/// # Examples
+/// ```
+/// use whitehole::{choice, combinator::{eat, next}, parser::Parser};
+///
+/// let keyword = choice!("let", "const", "var", eat("fn") + ' ', next(|c| c == '$'));
+/// let parse = |s| Parser::builder().entry(&keyword).build(s).next().map(|o| o.digested);
+/// assert_eq!(parse("var x"), Some(3));
+/// assert_eq!(parse("fn f"), Some(3));
+/// assert_eq!(parse("$"), Some(1));
+/// assert_eq!(parse("if"), None);
+/// ```
+/// All branches must have the same value type, otherwise it won't compile.
+/// ```compile_fail
+/// use whitehole::{choice, combinator::eat};
+///
+/// let _ = choice!(eat("true").bind(true), "null");
+/// ```
+#[macro_export]
+macro_rules! choice {
+  (
+    $_1:expr, $_2:expr, $_3:expr, $_4:expr, $_5:expr, $_6:expr,
+    $_7:expr, $_8:expr, $_9:expr, $_10:expr, $_11:expr, $_12:expr,
+    $($rest:expr),+ $(,)?
+  ) => {
+    $crate::choice!(
+      $_1, $_2, $_3, $_4, $_5, $_6, $_7, $_8, $_9, $_10, $_11,
+      $crate::choice!($_12, $($rest),+)
+    )
+  };
+  ($first:expr, $($rest:expr),+ $(,)?) => {
+    $crate::combinator::choice((
+      $crate::combinator::ChoiceBranch::into_branch($first),
+      $($crate::combinator::ChoiceBranch::into_branch($rest)),+
+    ))
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{eat, next, take, wrap},
+    contextual,
+    parser::Parser,
+  };
+
+  fn exec<T: Action<Text = str, State = (), Heap = ()>>(
+    action: T,
+    text: &str,
+  ) -> Option<Output<T::Value>> {
+    action.exec(Input {
+      instant: &Instant::new(text),
+      state: &mut (),
+      heap: &mut (),
+    })
+  }
+
+  #[test]
+  fn combinator_choice() {
+    let c = || choice((eat("true"), eat("false"), next(|c| c.is_ascii_digit())));
+    assert_eq!(exec(c(), "true").map(|o| o.digested), Some(4));
+    assert_eq!(exec(c(), "false").map(|o| o.digested), Some(5));
+    assert_eq!(exec(c(), "1").map(|o| o.digested), Some(1));
+    assert!(exec(c(), "null").is_none());
+    assert!(exec(c(), "").is_none());
+
+    // bytes
+    assert_eq!(
+      choice((bytes::eat(b"a"), bytes::take(2)))
+        .exec(Input {
+          instant: &Instant::new(b"bc" as &[u8]),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested),
+      Some(2)
+    );
+  }
+
+  #[test]
+  fn choice_first_match_wins() {
+    let c = choice!(
+      eat("a").bind(1),
+      eat("ab").bind(2),
+      take(2).bind(3),
+      eat("a").bind(4)
+    );
+    assert_eq!(exec(&c, "ab").map(|o| (o.value, o.digested)), Some((1, 1)));
+    assert_eq!(exec(&c, "bb").map(|o| (o.value, o.digested)), Some((3, 2)));
+    assert!(exec(&c, "b").is_none());
+  }
+
+  #[test]
+  fn choice_ten_branches() {
+    let c = choice!(
+      "let",
+      'x',
+      "const".to_string(),
+      eat("fn") + ' ',
+      "if",
+      next(|c| c == '$') * (1..),
+      "else",
+      "while",
+      eat("for"),
+      "return",
+    );
+    let parse = |s| exec(&c, s).map(|o| o.digested);
+    assert_eq!(parse("let"), Some(3));
+    assert_eq!(parse("x"), Some(1));
+    assert_eq!(parse("const"), Some(5));
+    assert_eq!(parse("fn f"), Some(3));
+    assert_eq!(parse("fnf"), None);
+    assert_eq!(parse("if"), Some(2));
+    assert_eq!(parse("$$$"), Some(3));
+    assert_eq!(parse("else"), Some(4));
+    assert_eq!(parse("while"), Some(5));
+    assert_eq!(parse("for"), Some(3));
+    assert_eq!(parse("return"), Some(6));
+    assert_eq!(parse("loop"), None);
+
+    // same as `|`
+    let by_bitor = eat("let")
+      | 'x'
+      | "const".to_string()
+      | (eat("fn") + ' ')
+      | "if"
+      | (next(|c| c == '$') * (1..))
+      | "else"
+      | "while"
+      | eat("for")
+      | "return";
+    for text in ["let", "fn f", "$$", "return", "loop", ""] {
+      assert_eq!(exec(&c, text), exec(&by_bitor, text));
+    }
+    assert_eq!(c.first_set(), by_bitor.first_set());
+  }
+
+  #[test]
+  fn choice_more_than_twelve() {
+    let c = choice!('a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o');
+    for (i, text) in ["a", "l", "m", "o", "p"].into_iter().enumerate() {
+      assert_eq!(exec(&c, text).is_some(), i < 4);
+    }
+    assert_eq!(
+      c.describe(),
+      "(choice (eat 'a') (eat 'b') (eat 'c') (eat 'd') (eat 'e') (eat 'f') (eat 'g') \
+       (eat 'h') (eat 'i') (eat 'j') (eat 'k') \
+       (choice (eat 'l') (eat 'm') (eat 'n') (eat 'o')))"
+    );
+  }
+
+  #[test]
+  fn choice_cut() {
+    let c = choice!((eat("let ") + eat("x").cut()).bind(1), take(1).bind(2));
+    assert_eq!(exec(&c, "let x").map(|o| o.value), Some(1));
+    // cut after "let ", the second branch is not tried
+    assert!(exec(&c, "let y").is_none());
+    assert_eq!(exec(&c, "y").map(|o| o.value), Some(2));
+    // the cut is propagated to the outer `|`
+    let outer = c | take(2).bind(3);
+    assert!(exec(&outer, "let y").is_none());
+  }
+
+  #[test]
+  fn choice_contextual() {
+    contextual!(i32, ());
+
+    // the literals share the state with the first branch
+    let c = choice!(
+      take(0).reject(|_| true).prepare(|input| *input.state += 1),
+      "a",
+      'b',
+      eat("c").prepare(|input| *input.state += 10)
+    );
+    let mut parser = Parser::builder().state(0).entry(c).build("abc");
+    assert_eq!(parser.by_ref().count(), 3);
+    // the first branch is tried 4 times (including the final rejection),
+    // the last branch is tried at "c" and at the end
+    assert_eq!(parser.state, 24);
+
+    // bytes literals
+    let c = choice!(b'a', b"bc", b"de".as_slice(), b"fg".to_vec());
+    let parser = Parser::builder().entry(c).build(b"abcdefg" as &[u8]);
+    assert_eq!(parser.count(), 4);
+  }
+
+  #[test]
+  fn choice_reject() {
+    let rejecter = || wrap(|_| Option::<Output<()>>::None);
+    assert!(exec(choice((rejecter(), rejecter())), "a").is_none());
+    assert_eq!(
+      exec(choice((rejecter(), eat('a'))), "a").map(|o| o.digested),
+      Some(1)
+    );
+  }
+
+  fn _choice_debug() {
+    let _ = format!("{:?}", choice((eat('a'), eat('b'))));
+  }
+
+  fn _choice_clone_copy() {
+    let c = choice((eat('a'), eat('b')));
+    let _c = c;
+    let _c = c.clone();
+  }
+}
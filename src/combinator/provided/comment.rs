@@ -0,0 +1,289 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  idx,
+  instant::Instant,
+};
+
+/// See [`whitespaces`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Whitespaces;
+
+impl Whitespaces {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for Whitespaces {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let digested = rest
+      .char_indices()
+      .find(|(_, c)| !c.is_whitespace())
+      .map_or(rest.len(), |(i, _)| i);
+    if digested == 0 {
+      return None;
+    }
+    // SAFETY: `digested` is from `char_indices` or the length of the rest
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to eat one or more Unicode whitespace chars
+/// (according to [`char::is_whitespace`]), including line endings.
+///
+/// This is the same as `next(char::is_whitespace) * (1..)`, but faster.
+/// The combinator will reject if the rest doesn't start with a whitespace.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::whitespaces, parser::Parser};
+/// assert_eq!(
+///   Parser::builder().entry(whitespaces()).build(" \t\r\n\u{3000}a").next().unwrap().digested,
+///   7
+/// );
+/// ```
+#[inline]
+pub const fn whitespaces() -> Combinator<Whitespaces> {
+  Combinator::new(Whitespaces::new())
+}
+
+/// See [`line_comment`].
+#[derive(Copy, Clone, Debug)]
+pub struct LineComment<Prefix> {
+  prefix: Prefix,
+}
+
+impl<Prefix> LineComment<Prefix> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(prefix: Prefix) -> Self {
+    Self { prefix }
+  }
+}
+
+unsafe impl<Prefix: AsRef<str>> Action for LineComment<Prefix> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let prefix = self.prefix.as_ref();
+    let rest = input.instant.rest();
+    if !rest.starts_with(prefix) {
+      return None;
+    }
+    // SAFETY: `rest` starts with `prefix`
+    let body = idx::unchecked!(idx::slice_from(rest.as_bytes(), prefix.len()));
+    let len = match body.iter().position(|b| *b == b'\n') {
+      Some(i) if i > 0 && body[i - 1] == b'\r' => i - 1,
+      Some(i) => i,
+      None => body.len(),
+    };
+    // SAFETY: '\r' and '\n' are ascii so the sum is a valid char boundary
+    Some(unsafe { input.instant.accept_unchecked(idx::add(prefix.len(), len)) })
+  }
+}
+
+/// Returns a combinator to eat a line comment starting with the `prefix`,
+/// till the end of the line, excluding the line ending (`"\n"` or `"\r\n"`).
+///
+/// The combinator will reject if the rest doesn't start with the `prefix`.
+/// A comment at the end of the input text is accepted.
+/// Combine it with [`line_end`](crate::combinator::line_end) to consume the line ending.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::line_comment, parser::Parser};
+/// assert_eq!(
+///   Parser::builder().entry(line_comment("//")).build("// hi\r\nx").next().unwrap().digested,
+///   5
+/// );
+/// ```
+#[inline]
+pub const fn line_comment<Prefix: AsRef<str>>(prefix: Prefix) -> Combinator<LineComment<Prefix>> {
+  Combinator::new(LineComment::new(prefix))
+}
+
+/// See [`block_comment`].
+#[derive(Copy, Clone, Debug)]
+pub struct BlockComment<Open, Close> {
+  open: Open,
+  close: Close,
+}
+
+impl<Open, Close> BlockComment<Open, Close> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(open: Open, close: Close) -> Self {
+    Self { open, close }
+  }
+}
+
+unsafe impl<Open: AsRef<str>, Close: AsRef<str>> Action for BlockComment<Open, Close> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let open = self.open.as_ref();
+    let close = self.close.as_ref();
+    let rest = input.instant.rest();
+    if !rest.starts_with(open) {
+      return None;
+    }
+    // reject unterminated comments
+    // SAFETY: `rest` starts with `open`, so the end of `open` is a valid char boundary
+    let i = idx::unchecked!(idx::slice_from(rest, open.len())).find(close)?;
+    // SAFETY: the end of the `close` is a valid char boundary
+    Some(unsafe {
+      input
+        .instant
+        .accept_unchecked(idx::add(idx::add(open.len(), i), close.len()))
+    })
+  }
+}
+
+/// Returns a combinator to eat a block comment from `open` to the first `close` (inclusive).
+///
+/// Nested comments are not supported, the first `close` ends the comment.
+/// The combinator will reject if the rest doesn't start with `open`,
+/// or the comment is unterminated (no `close` till the end of the input text).
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::block_comment, parser::Parser};
+/// assert_eq!(
+///   Parser::builder().entry(block_comment("/*", "*/")).build("/* a */b").next().unwrap().digested,
+///   7
+/// );
+/// assert!(Parser::builder().entry(block_comment("/*", "*/")).build("/* a").next().is_none());
+/// ```
+#[inline]
+pub const fn block_comment<Open: AsRef<str>, Close: AsRef<str>>(
+  open: Open,
+  close: Close,
+) -> Combinator<BlockComment<Open, Close>> {
+  Combinator::new(BlockComment::new(open, close))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{eat, line_end};
+
+  fn helper(
+    action: impl Action<Text = str, State = (), Heap = (), Value = ()>,
+    input: &str,
+    digested: Option<usize>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| o.digested),
+      digested
+    )
+  }
+
+  #[test]
+  fn combinator_whitespaces() {
+    helper(whitespaces(), " a", Some(1));
+    helper(whitespaces(), " \t\r\n a", Some(5));
+    // unicode whitespaces
+    helper(whitespaces(), "\u{3000}\u{a0}a", Some(5));
+    // till the end
+    helper(whitespaces(), "  ", Some(2));
+    // reject
+    helper(whitespaces(), "a ", None);
+    helper(whitespaces(), "", None);
+  }
+
+  #[test]
+  fn combinator_line_comment() {
+    helper(line_comment("//"), "// abc\nx", Some(6));
+    helper(line_comment('#'.to_string()), "# 好\nx", Some(5));
+    // CRLF is excluded
+    helper(line_comment("//"), "// abc\r\nx", Some(6));
+    // lone '\r' is not a line ending
+    helper(line_comment("//"), "//a\rb\n", Some(5));
+    // empty comment
+    helper(line_comment("//"), "//\n", Some(2));
+    helper(line_comment("//"), "//\r\n", Some(2));
+    // at EOF
+    helper(line_comment("//"), "// abc", Some(6));
+    helper(line_comment("//"), "//", Some(2));
+    // reject
+    helper(line_comment("//"), "/ abc", None);
+    helper(line_comment("//"), "", None);
+    // with line ending
+    helper(line_comment("//") + line_end(), "// abc\r\nx", Some(8));
+  }
+
+  #[test]
+  fn combinator_block_comment() {
+    helper(block_comment("/*", "*/"), "/* abc */x", Some(9));
+    helper(block_comment("/*", "*/"), "/*\n好\r\n*/", Some(10));
+    // the first close ends the comment
+    helper(block_comment("/*", "*/"), "/* /* */ */", Some(8));
+    // empty comment
+    helper(block_comment("/*", "*/"), "/**/", Some(4));
+    // the open and the close don't overlap
+    helper(block_comment("/*", "*/"), "/*/", None);
+    // at EOF
+    helper(block_comment("<!--", "-->"), "<!-- a -->", Some(10));
+    // unterminated
+    helper(block_comment("/*", "*/"), "/* abc", None);
+    helper(block_comment("/*", "*/"), "/*", None);
+    // reject
+    helper(block_comment("/*", "*/"), "abc */", None);
+    helper(block_comment("/*", "*/"), "", None);
+  }
+
+  #[test]
+  fn combinator_trivia() {
+    let trivia = || (whitespaces() | line_comment("//") | block_comment("/*", "*/")) * (..);
+    helper(trivia() + eat('x'), " // a\n /* b */\r\n x", Some(18));
+    helper(trivia() + eat('x'), "x", Some(1));
+  }
+
+  fn _comment_debug() {
+    let _ = format!("{:?}", whitespaces());
+    let _ = format!("{:?}", line_comment("//"));
+    let _ = format!("{:?}", block_comment("/*", "*/"));
+  }
+
+  fn _comment_clone_copy() {
+    let c = whitespaces();
+    let _c = c;
+    let _c = c.clone();
+    let c = line_comment("//");
+    let _c = c;
+    let _c = c.clone();
+    let c = block_comment("/*", "*/");
+    let _c = c;
+    let _c = c.clone();
+  }
+}
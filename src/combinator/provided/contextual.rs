@@ -1,5 +1,6 @@
 use crate::{
-  action::{Action, Input, Output},
+  action::{exec_by_cut, Action, Input, Output, Rejection},
+  combinator::{Describe, FirstSet, LookaheadSet},
   instant::Instant,
 };
 use std::{fmt::Debug, marker::PhantomData};
@@ -41,18 +42,40 @@ impl<T: Debug, State, Heap> Debug for Contextual<T, State, Heap> {
   }
 }
 
+/// Transparent, only the original action is described.
+impl<T: Describe, State, Heap> Describe for Contextual<T, State, Heap> {
+  #[inline]
+  fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.action.describe(f)
+  }
+
+  #[inline]
+  fn describe_operand(&self, op: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.action.describe_operand(op, f)
+  }
+}
+
+impl<T: FirstSet, State, Heap> FirstSet for Contextual<T, State, Heap> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    self.action.first_set()
+  }
+}
+
 unsafe impl<T: Action<State = (), Heap = ()>, State, Heap> Action for Contextual<T, State, Heap> {
   type Text = T::Text;
   type State = State;
   type Heap = Heap;
   type Value = T::Value;
 
+  exec_by_cut!();
+
   #[inline]
-  fn exec(
+  fn exec_cut(
     &self,
     input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
-  ) -> Option<Output<Self::Value>> {
-    self.action.exec(Input {
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.action.exec_cut(Input {
       instant: input.instant,
       state: &mut (),
       heap: &mut (),
@@ -79,11 +102,64 @@ unsafe impl<T: Action<State = (), Heap = ()>, State, Heap> Action for Contextual
 /// let _ = bytes::take(1);
 /// # }
 /// ```
+/// # Named Modules
+/// The generated combinators are placed in a private module
+/// and re-exported into the current module,
+/// so the short form can only be used once per module.
+/// To generate multiple sets of combinators, or to control the visibility,
+/// name the module explicitly:
+/// ```
+/// use whitehole::combinator::contextual;
+/// # pub struct MyState;
+///
+/// contextual!(pub mod ctx: MyState, ());
+/// contextual!(mod counter: usize, ());
+///
+/// # fn main() {
+/// let _ = ctx::take(1);
+/// let _ = counter::bytes::take(1);
+/// # }
+/// ```
+/// # Generics
+/// With the named form, lifetimes and type parameters used by the state and heap types
+/// can be declared after the module name, with an optional `where` clause at the end.
+/// They will be added to the generic parameters of every generated function.
+/// ```
+/// use whitehole::combinator::contextual;
+/// pub struct Scope<'a, T> {
+///   pub names: &'a [T],
+/// }
+///
+/// contextual!(pub mod scoped<'a, T>: Scope<'a, T>, Vec<T> where T: Clone + 'a);
+///
+/// # fn main() {
+/// let _ = scoped::eat::<u8, &str>("let");
+/// # }
+/// ```
+/// Type parameters with a leading double underscore (e.g. `__T`) are reserved.
+///
+/// Lifetimes can't be used without declaration:
+/// ```compile_fail
+/// use whitehole::combinator::contextual;
+/// pub struct Scope<'a>(&'a str);
+///
+/// contextual!(Scope<'a>, ());
+/// ```
+/// The short form can't be used twice in one module:
+/// ```compile_fail
+/// use whitehole::combinator::contextual;
+///
+/// contextual!(i32, ());
+/// contextual!(u32, ());
+/// ```
 #[macro_export]
 macro_rules! contextual {
-  ($state:ty, $heap:ty) => {
+  (
+    @impl $vis:vis mod $name:ident [$($lt:lifetime),*] [$($tp:ident),*] [$($wc:tt)*]:
+    $state:ty, $heap:ty; $($reexport:tt)*
+  ) => {
     #[allow(dead_code)]
-    mod _impl_contextual_combinators {
+    $vis mod $name {
       #[allow(unused_imports)]
       use super::*;
       use std::{cell::OnceCell, rc::Rc};
@@ -93,63 +169,488 @@ macro_rules! contextual {
 
       /// Contextual version of [`eat`](whitehole::combinator::eat).
       #[inline]
-      pub const fn eat<T>(
-        pattern: T,
-      ) -> Combinator<Contextual<$crate::combinator::Eat<T>, $state, $heap>> {
+      pub const fn eat<$($lt,)* $($tp,)* __T>(
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::Eat<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::Eat::new(pattern)))
       }
 
+      /// Contextual version of [`eat_ci`](whitehole::combinator::eat_ci).
+      #[inline]
+      pub const fn eat_ci<$($lt,)* $($tp,)* __T>(
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::EatCaseInsensitive<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new(
+          $crate::combinator::EatCaseInsensitive::new(pattern),
+        ))
+      }
+
+      /// Contextual version of [`eat_in`](whitehole::combinator::eat_in).
+      #[inline]
+      pub const fn eat_in<'__a, $($lt,)* $($tp,)*>(
+        set: &'__a str,
+      ) -> Combinator<Contextual<$crate::combinator::EatIn<'__a>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatIn::new(set)))
+      }
+
+      /// Contextual version of [`eat_in_char`](whitehole::combinator::eat_in_char).
+      #[inline]
+      pub const fn eat_in_char<'__a, $($lt,)* $($tp,)*>(
+        set: &'__a str,
+      ) -> Combinator<Contextual<$crate::combinator::EatIn<'__a, char>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatIn::new(set)))
+      }
+
+      /// Contextual version of [`eat_range`](whitehole::combinator::eat_range).
+      #[inline]
+      pub const fn eat_range<$($lt,)* $($tp,)*>(
+        range: std::ops::RangeInclusive<char>,
+      ) -> Combinator<Contextual<$crate::combinator::EatRange, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatRange::new(range)))
+      }
+
+      /// Contextual version of [`eat_range_char`](whitehole::combinator::eat_range_char).
+      #[inline]
+      pub const fn eat_range_char<$($lt,)* $($tp,)*>(
+        range: std::ops::RangeInclusive<char>,
+      ) -> Combinator<Contextual<$crate::combinator::EatRange<char>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatRange::new(range)))
+      }
+
+      /// Contextual version of [`line_end`](whitehole::combinator::line_end).
+      #[inline]
+      pub const fn line_end<$($lt,)* $($tp,)*>() -> Combinator<Contextual<$crate::combinator::LineEnd, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::LineEnd::new()))
+      }
+
+      /// Contextual version of [`till_line_end`](whitehole::combinator::till_line_end).
+      #[inline]
+      pub const fn till_line_end<$($lt,)* $($tp,)*>(
+      ) -> Combinator<Contextual<$crate::combinator::TillLineEnd, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TillLineEnd::new()))
+      }
+
+      /// Contextual version of [`whitespaces`](whitehole::combinator::whitespaces).
+      #[inline]
+      pub const fn whitespaces<$($lt,)* $($tp,)*>(
+      ) -> Combinator<Contextual<$crate::combinator::Whitespaces, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::Whitespaces::new()))
+      }
+
+      /// Contextual version of [`line_comment`](whitehole::combinator::line_comment).
+      #[inline]
+      pub const fn line_comment<$($lt,)* $($tp,)* __Prefix: AsRef<str>>(
+        prefix: __Prefix,
+      ) -> Combinator<Contextual<$crate::combinator::LineComment<__Prefix>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::LineComment::new(
+          prefix,
+        )))
+      }
+
+      /// Contextual version of [`block_comment`](whitehole::combinator::block_comment).
+      #[inline]
+      pub const fn block_comment<$($lt,)* $($tp,)* __Open: AsRef<str>, __Close: AsRef<str>>(
+        open: __Open,
+        close: __Close,
+      ) -> Combinator<Contextual<$crate::combinator::BlockComment<__Open, __Close>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::BlockComment::new(
+          open, close,
+        )))
+      }
+
+      /// Contextual version of [`json_string`](whitehole::combinator::json_string).
+      #[inline]
+      pub const fn json_string<$($lt,)* $($tp,)*>(
+      ) -> Combinator<Contextual<$crate::combinator::JsonString, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::JsonString::new()))
+      }
+
+      /// Contextual version of [`json_number`](whitehole::combinator::json_number).
+      #[inline]
+      pub const fn json_number<$($lt,)* $($tp,)*>(
+      ) -> Combinator<Contextual<$crate::combinator::JsonNumber, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::JsonNumber::new()))
+      }
+
       /// Contextual version of [`next`](whitehole::combinator::next).
       #[inline]
-      pub const fn next<F: Fn(char) -> bool>(
-        condition: F,
-      ) -> Combinator<Contextual<$crate::combinator::Next<F>, $state, $heap>> {
+      pub const fn next<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        condition: __F,
+      ) -> Combinator<Contextual<$crate::combinator::Next<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::Next::new(condition)))
       }
 
+      /// Contextual version of [`next_n`](whitehole::combinator::next_n).
+      #[inline]
+      pub const fn next_n<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        condition: __F,
+        n: usize,
+      ) -> Combinator<Contextual<$crate::combinator::NextN<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::NextN::new(condition, n)))
+      }
+
+      /// Contextual version of [`one_of`](whitehole::combinator::one_of).
+      #[inline]
+      pub const fn one_of<'__a, $($lt,)* $($tp,)*>(
+        set: &'__a str,
+      ) -> Combinator<Contextual<$crate::combinator::EatIn<'__a, char>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatIn::new(set)))
+      }
+
+      /// Contextual version of [`none_of`](whitehole::combinator::none_of).
+      #[inline]
+      pub const fn none_of<'__a, $($lt,)* $($tp,)*>(
+        set: &'__a str,
+      ) -> Combinator<Contextual<$crate::combinator::NoneOf<'__a>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::NoneOf::new(set)))
+      }
+
+      /// Contextual version of [`token_by`](whitehole::combinator::token_by).
+      #[inline]
+      pub const fn token_by<$($lt,)* $($tp,)* __T: $crate::digest::Unit, __F: Fn(&__T) -> bool>(
+        condition: __F,
+      ) -> Combinator<Contextual<$crate::combinator::TokenBy<__F, __T>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TokenBy::new(condition)))
+      }
+
+      /// Contextual version of [`position`](whitehole::combinator::position).
+      #[inline]
+      pub const fn position<$($lt,)* $($tp,)*>() -> Combinator<Contextual<$crate::combinator::Position, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::Position::new()))
+      }
+
+      /// Returns a combinator which always accepts with 0 bytes digested,
+      /// and yields the value calculated from the state by `f`.
+      #[inline]
+      pub fn state_value<$($lt,)* $($tp,)* __Value>(
+        f: impl Fn(&$state) -> __Value,
+      ) -> Combinator<
+        impl $crate::action::Action<Text = str, State = $state, Heap = $heap, Value = __Value>,
+      >
+      where
+        $($wc)*
+      {
+        position().select(move |accepted| f(accepted.state))
+      }
+
+      /// Returns a combinator which always accepts with 0 bytes digested,
+      /// and yields the value calculated from the heap by `f`.
+      #[inline]
+      pub fn heap_value<$($lt,)* $($tp,)* __Value>(
+        f: impl Fn(&$heap) -> __Value,
+      ) -> Combinator<
+        impl $crate::action::Action<Text = str, State = $state, Heap = $heap, Value = __Value>,
+      >
+      where
+        $($wc)*
+      {
+        position().select(move |accepted| f(accepted.heap))
+      }
+
       /// Contextual version of [`take`](whitehole::combinator::take).
       #[inline]
-      pub const fn take(
+      pub const fn take<$($lt,)* $($tp,)*>(
         n: usize,
-      ) -> Combinator<Contextual<$crate::combinator::Take, $state, $heap>> {
+      ) -> Combinator<Contextual<$crate::combinator::Take, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::Take::new(n)))
       }
 
+      /// Contextual version of [`take_exact`](whitehole::combinator::take_exact).
+      #[inline]
+      pub const fn take_exact<$($lt,)* $($tp,)*>(
+        n: usize,
+      ) -> Combinator<Contextual<$crate::combinator::Take, $state, $heap>>
+      where
+        $($wc)*
+      {
+        take(n)
+      }
+
+      /// Contextual version of [`take_bytes`](whitehole::combinator::take_bytes).
+      #[inline]
+      pub const fn take_bytes<$($lt,)* $($tp,)*>(
+        n: usize,
+      ) -> Combinator<Contextual<$crate::combinator::TakeBytes, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TakeBytes::new(n)))
+      }
+
+      /// Contextual version of [`take_at_most`](whitehole::combinator::take_at_most).
+      #[inline]
+      pub const fn take_at_most<$($lt,)* $($tp,)*>(
+        n: usize,
+      ) -> Combinator<Contextual<$crate::combinator::TakeAtMost, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TakeAtMost::new(n)))
+      }
+
+      /// Contextual version of [`skip_at_most`](whitehole::combinator::skip_at_most).
+      #[inline]
+      pub const fn skip_at_most<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        n: usize,
+        predicate: __F,
+      ) -> Combinator<Contextual<$crate::combinator::SkipAtMost<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::SkipAtMost::new(
+          n, predicate,
+        )))
+      }
+
+      /// Contextual version of [`take_while`](whitehole::combinator::take_while).
+      #[inline]
+      pub const fn take_while<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        predicate: __F,
+      ) -> Combinator<Contextual<$crate::combinator::TakeWhile<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TakeWhile::new(
+          predicate,
+          0,
+          usize::MAX,
+        )))
+      }
+
+      /// Contextual version of [`take_while_min_max`](whitehole::combinator::take_while_min_max).
+      #[inline]
+      pub const fn take_while_min_max<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        predicate: __F,
+        min: usize,
+        max: usize,
+      ) -> Combinator<Contextual<$crate::combinator::TakeWhile<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TakeWhile::new(
+          predicate, min, max,
+        )))
+      }
+
+      /// Contextual version of [`identifier`](whitehole::combinator::identifier).
+      #[inline]
+      pub const fn identifier<$($lt,)* $($tp,)*>(
+      ) -> Combinator<Contextual<$crate::combinator::Identifier<'static>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::Identifier::new()))
+      }
+
+      /// Contextual version of [`take_till`](whitehole::combinator::take_till).
+      #[inline]
+      pub const fn take_till<$($lt,)* $($tp,)* __F: Fn(char) -> bool>(
+        predicate: __F,
+      ) -> Combinator<Contextual<$crate::combinator::TakeTill<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TakeTill::new(predicate)))
+      }
+
       /// Contextual version of [`till`](whitehole::combinator::till).
       #[inline]
-      pub const fn till<T>(
-        pattern: T,
-      ) -> Combinator<Contextual<$crate::combinator::Till<T>, $state, $heap>> {
+      pub const fn till<$($lt,)* $($tp,)* __T>(
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::Till<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::Till::new(pattern)))
       }
 
+      /// Contextual version of [`till_at_most`](whitehole::combinator::till_at_most).
+      #[inline]
+      pub const fn till_at_most<$($lt,)* $($tp,)* __T>(
+        n: usize,
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::TillAtMost<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TillAtMost::new(
+          n, pattern,
+        )))
+      }
+
+      /// Contextual version of [`till_eof`](whitehole::combinator::till_eof).
+      #[inline]
+      pub const fn till_eof<$($lt,)* $($tp,)*>() -> Combinator<Contextual<$crate::combinator::Till<()>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::Till::new(())))
+      }
+
+      /// Contextual version of [`till_exclusive`](whitehole::combinator::till_exclusive).
+      #[inline]
+      pub const fn till_exclusive<$($lt,)* $($tp,)* __T>(
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::TillExclusive<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TillExclusive::new(
+          pattern,
+        )))
+      }
+
+      /// Contextual version of [`till_inclusive`](whitehole::combinator::till_inclusive).
+      #[inline]
+      pub const fn till_inclusive<$($lt,)* $($tp,)* __T>(
+        pattern: __T,
+      ) -> Combinator<Contextual<$crate::combinator::TillInclusive<__T>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::TillInclusive::new(
+          pattern,
+        )))
+      }
+
+      /// Contextual version of [`eat_until`](whitehole::combinator::eat_until).
+      #[inline]
+      pub const fn eat_until<$($lt,)* $($tp,)* const __N: usize>(
+        needles: [char; __N],
+      ) -> Combinator<Contextual<$crate::combinator::EatUntil<[char; __N]>, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::EatUntil::new(needles)))
+      }
+
+      /// Contextual version of [`bounded_uint`](whitehole::combinator::bounded_uint).
+      #[inline]
+      pub const fn bounded_uint<$($lt,)* $($tp,)*>(
+        min: u64,
+        max: u64,
+      ) -> Combinator<Contextual<$crate::combinator::BoundedUint, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::BoundedUint::new(
+          min,
+          max,
+          usize::MAX,
+        )))
+      }
+
+      /// Contextual version of [`uint_with_max_digits`](whitehole::combinator::uint_with_max_digits).
+      #[inline]
+      pub const fn uint_with_max_digits<$($lt,)* $($tp,)*>(
+        n: usize,
+      ) -> Combinator<Contextual<$crate::combinator::BoundedUint, $state, $heap>>
+      where
+        $($wc)*
+      {
+        Combinator::new(Contextual::new($crate::combinator::BoundedUint::new(
+          0,
+          u64::MAX,
+          n,
+        )))
+      }
+
       /// Contextual version of [`wrap_unchecked`](whitehole::combinator::wrap_unchecked).
       #[inline]
-      pub const unsafe fn wrap_unchecked<
-        Value,
-        F: Fn(Input<&Instant<&str>, &mut $state, &mut $heap>) -> Option<Output<Value>>,
+      pub const unsafe fn wrap_unchecked<$($lt,)* $($tp,)*
+        __Value,
+        __F: Fn(Input<&Instant<&str>, &mut $state, &mut $heap>) -> Option<Output<__Value>>,
       >(
-        f: F,
-      ) -> Combinator<Contextual<$crate::combinator::WrapUnchecked<F>, $state, $heap>> {
+        f: __F,
+      ) -> Combinator<Contextual<$crate::combinator::WrapUnchecked<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::WrapUnchecked::new(f)))
       }
 
       /// Contextual version of [`wrap`](whitehole::combinator::wrap).
       #[inline]
-      pub const fn wrap<
-        Value,
-        F: Fn(Input<&Instant<&str>, &mut $state, &mut $heap>) -> Option<Output<Value>>,
+      pub const fn wrap<$($lt,)* $($tp,)*
+        __Value,
+        __F: Fn(Input<&Instant<&str>, &mut $state, &mut $heap>) -> Option<Output<__Value>>,
       >(
-        f: F,
-      ) -> Combinator<Contextual<$crate::combinator::Wrap<F>, $state, $heap>> {
+        f: __F,
+      ) -> Combinator<Contextual<$crate::combinator::Wrap<__F>, $state, $heap>>
+      where
+        $($wc)*
+      {
         Combinator::new(Contextual::new($crate::combinator::Wrap::new(f)))
       }
 
       /// Contextual version of [`recur`](whitehole::combinator::recur).
-      pub fn recur<Value>() -> (
-        impl Fn() -> Combinator<$crate::combinator::Recur<$state, $heap, Value>>,
-        $crate::combinator::RecurSetter<$state, $heap, Value>,
-      ) {
+      pub fn recur<$($lt,)* $($tp,)* __Value>() -> (
+        impl Fn() -> Combinator<$crate::combinator::Recur<$state, $heap, __Value>>,
+        $crate::combinator::RecurSetter<$state, $heap, __Value>,
+      )
+      where
+        $($wc)*
+      {
         let inner = Rc::new(OnceCell::new());
         let setter = $crate::combinator::RecurSetter::new(inner.clone());
         let getter = move || Combinator::new($crate::combinator::Recur::new(inner.clone()));
@@ -157,10 +658,13 @@ macro_rules! contextual {
       }
 
       /// Contextual version of [`recur_unchecked`](whitehole::combinator::recur_unchecked).
-      pub unsafe fn recur_unchecked<Value>() -> (
-        impl Fn() -> Combinator<$crate::combinator::RecurUnchecked<$state, $heap, Value>>,
-        $crate::combinator::RecurSetter<$state, $heap, Value>,
-      ) {
+      pub unsafe fn recur_unchecked<$($lt,)* $($tp,)* __Value>() -> (
+        impl Fn() -> Combinator<$crate::combinator::RecurUnchecked<$state, $heap, __Value>>,
+        $crate::combinator::RecurSetter<$state, $heap, __Value>,
+      )
+      where
+        $($wc)*
+      {
         let inner = Rc::new(OnceCell::new());
         let setter = $crate::combinator::RecurSetter::new(inner.clone());
         let getter =
@@ -168,55 +672,565 @@ macro_rules! contextual {
         (getter, setter)
       }
 
+      pub mod datetime {
+        use super::*;
+
+        /// Contextual version of [`datetime::date`](whitehole::combinator::datetime::date).
+        #[inline]
+        pub const fn date<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::datetime::FullDate, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::datetime::FullDate::new(),
+          ))
+        }
+
+        /// Contextual version of [`datetime::time`](whitehole::combinator::datetime::time).
+        #[inline]
+        pub const fn time<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::datetime::PartialTime, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::datetime::PartialTime::new(),
+          ))
+        }
+
+        /// Contextual version of [`datetime::offset`](whitehole::combinator::datetime::offset).
+        #[inline]
+        pub const fn offset<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::datetime::TimeOffset, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::datetime::TimeOffset::new(),
+          ))
+        }
+
+        /// Contextual version of [`datetime::rfc3339`](whitehole::combinator::datetime::rfc3339).
+        #[inline]
+        pub const fn rfc3339<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::datetime::Rfc3339, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::datetime::Rfc3339::new()))
+        }
+      }
+
       pub mod bytes {
         use super::*;
 
+        /// Contextual version of [`bytes::ascii_alpha`](whitehole::combinator::bytes::ascii_alpha).
+        #[inline]
+        pub const fn ascii_alpha<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Alpha,
+            false,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_alphas1`](whitehole::combinator::bytes::ascii_alphas1).
+        #[inline]
+        pub const fn ascii_alphas1<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Alpha,
+            true,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_digit`](whitehole::combinator::bytes::ascii_digit).
+        #[inline]
+        pub const fn ascii_digit<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Digit,
+            false,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_digits1`](whitehole::combinator::bytes::ascii_digits1).
+        #[inline]
+        pub const fn ascii_digits1<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Digit,
+            true,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_alnum`](whitehole::combinator::bytes::ascii_alnum).
+        #[inline]
+        pub const fn ascii_alnum<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Alnum,
+            false,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_alnums1`](whitehole::combinator::bytes::ascii_alnums1).
+        #[inline]
+        pub const fn ascii_alnums1<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Alnum,
+            true,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_ws`](whitehole::combinator::bytes::ascii_ws).
+        #[inline]
+        pub const fn ascii_ws<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Whitespace,
+            false,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_ws1`](whitehole::combinator::bytes::ascii_ws1).
+        #[inline]
+        pub const fn ascii_ws1<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::Whitespace,
+            true,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_hexdigit`](whitehole::combinator::bytes::ascii_hexdigit).
+        #[inline]
+        pub const fn ascii_hexdigit<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::HexDigit,
+            false,
+          )))
+        }
+
+        /// Contextual version of [`bytes::ascii_hexdigits1`](whitehole::combinator::bytes::ascii_hexdigits1).
+        #[inline]
+        pub const fn ascii_hexdigits1<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Ascii, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Ascii::new(
+            $crate::combinator::bytes::AsciiClass::HexDigit,
+            true,
+          )))
+        }
+
         /// Contextual version of [`eat`](whitehole::combinator::bytes::eat).
         #[inline]
-        pub const fn eat<T>(
-          pattern: T,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::Eat<T>, $state, $heap>> {
+        pub const fn eat<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Eat<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
           Combinator::new(Contextual::new($crate::combinator::bytes::Eat::new(
             pattern,
           )))
         }
 
+        /// Contextual version of [`bytes::eat_ci`](whitehole::combinator::bytes::eat_ci).
+        #[inline]
+        pub const fn eat_ci<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::EatCaseInsensitive<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::EatCaseInsensitive::new(pattern),
+          ))
+        }
+
+        /// Contextual version of [`bytes::eat_in`](whitehole::combinator::bytes::eat_in).
+        #[inline]
+        pub const fn eat_in<'__a, $($lt,)* $($tp,)*>(
+          set: &'__a [u8],
+        ) -> Combinator<Contextual<$crate::combinator::bytes::EatIn<'__a>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::EatIn::new(set)))
+        }
+
+        /// Contextual version of [`bytes::eat_in_byte`](whitehole::combinator::bytes::eat_in_byte).
+        #[inline]
+        pub const fn eat_in_byte<'__a, $($lt,)* $($tp,)*>(
+          set: &'__a [u8],
+        ) -> Combinator<Contextual<$crate::combinator::bytes::EatIn<'__a, u8>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::EatIn::new(set)))
+        }
+
+        /// Contextual version of [`bytes::line_end`](whitehole::combinator::bytes::line_end).
+        #[inline]
+        pub const fn line_end<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::LineEnd, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::LineEnd::new()))
+        }
+
+        /// Contextual version of [`bytes::till_line_end`](whitehole::combinator::bytes::till_line_end).
+        #[inline]
+        pub const fn till_line_end<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TillLineEnd, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::TillLineEnd::new(),
+          ))
+        }
+
         /// Contextual version of [`bytes::next`](whitehole::combinator::bytes::next).
         #[inline]
-        pub const fn next<F: Fn(u8) -> bool>(
-          condition: F,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::Next<F>, $state, $heap>> {
+        pub const fn next<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          condition: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Next<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
           Combinator::new(Contextual::new($crate::combinator::bytes::Next::new(
             condition,
           )))
         }
 
+        /// Contextual version of [`bytes::next_n`](whitehole::combinator::bytes::next_n).
+        #[inline]
+        pub const fn next_n<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          condition: __F,
+          n: usize,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::NextN<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::NextN::new(
+            condition, n,
+          )))
+        }
+
+        /// Contextual version of [`bytes::one_of`](whitehole::combinator::bytes::one_of).
+        #[inline]
+        pub const fn one_of<'__a, $($lt,)* $($tp,)*>(
+          set: &'__a [u8],
+        ) -> Combinator<Contextual<$crate::combinator::bytes::EatIn<'__a, u8>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::EatIn::new(set)))
+        }
+
+        /// Contextual version of [`bytes::none_of`](whitehole::combinator::bytes::none_of).
+        #[inline]
+        pub const fn none_of<'__a, $($lt,)* $($tp,)*>(
+          set: &'__a [u8],
+        ) -> Combinator<Contextual<$crate::combinator::bytes::NoneOf<'__a>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::NoneOf::new(set)))
+        }
+
+        /// Contextual version of [`bytes::position`](whitehole::combinator::bytes::position).
+        #[inline]
+        pub const fn position<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Position, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Position::new()))
+        }
+
+        /// Returns a combinator which always accepts with 0 bytes digested,
+        /// and yields the value calculated from the state by `f`.
+        #[inline]
+        pub fn state_value<$($lt,)* $($tp,)* __Value>(
+          f: impl Fn(&$state) -> __Value,
+        ) -> Combinator<
+          impl $crate::action::Action<Text = [u8], State = $state, Heap = $heap, Value = __Value>,
+        >
+        where
+          $($wc)*
+        {
+          position().select(move |accepted| f(accepted.state))
+        }
+
+        /// Returns a combinator which always accepts with 0 bytes digested,
+        /// and yields the value calculated from the heap by `f`.
+        #[inline]
+        pub fn heap_value<$($lt,)* $($tp,)* __Value>(
+          f: impl Fn(&$heap) -> __Value,
+        ) -> Combinator<
+          impl $crate::action::Action<Text = [u8], State = $state, Heap = $heap, Value = __Value>,
+        >
+        where
+          $($wc)*
+        {
+          position().select(move |accepted| f(accepted.heap))
+        }
+
         /// Contextual version of [`take`](whitehole::combinator::bytes::take).
         #[inline]
-        pub const fn take(
+        pub const fn take<$($lt,)* $($tp,)*>(
           n: usize,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::Take, $state, $heap>> {
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Take, $state, $heap>>
+        where
+          $($wc)*
+        {
           Combinator::new(Contextual::new($crate::combinator::bytes::Take::new(n)))
         }
 
+        /// Contextual version of [`take_exact`](whitehole::combinator::bytes::take_exact).
+        #[inline]
+        pub const fn take_exact<$($lt,)* $($tp,)*>(
+          n: usize,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Take, $state, $heap>>
+        where
+          $($wc)*
+        {
+          take(n)
+        }
+
+        /// Contextual version of [`take_at_most`](whitehole::combinator::bytes::take_at_most).
+        #[inline]
+        pub const fn take_at_most<$($lt,)* $($tp,)*>(
+          n: usize,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TakeAtMost, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::TakeAtMost::new(
+            n,
+          )))
+        }
+
+        /// Contextual version of [`skip_at_most`](whitehole::combinator::bytes::skip_at_most).
+        #[inline]
+        pub const fn skip_at_most<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          n: usize,
+          predicate: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::SkipAtMost<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::SkipAtMost::new(
+            n, predicate,
+          )))
+        }
+
+        /// Contextual version of [`bytes::take_while`](whitehole::combinator::bytes::take_while).
+        #[inline]
+        pub const fn take_while<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          predicate: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TakeWhile<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::TakeWhile::new(
+            predicate,
+            0,
+            usize::MAX,
+          )))
+        }
+
+        /// Contextual version of [`bytes::take_while_min_max`](whitehole::combinator::bytes::take_while_min_max).
+        #[inline]
+        pub const fn take_while_min_max<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          predicate: __F,
+          min: usize,
+          max: usize,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TakeWhile<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::TakeWhile::new(
+            predicate, min, max,
+          )))
+        }
+
+        /// Contextual version of [`bytes::take_till`](whitehole::combinator::bytes::take_till).
+        #[inline]
+        pub const fn take_till<$($lt,)* $($tp,)* __F: Fn(u8) -> bool>(
+          predicate: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TakeTill<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::TakeTill::new(predicate)))
+        }
+
         /// Contextual version of [`till`](whitehole::combinator::bytes::till).
         #[inline]
-        pub const fn till<T>(
-          pattern: T,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::Till<T>, $state, $heap>> {
+        pub const fn till<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Till<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
           Combinator::new(Contextual::new($crate::combinator::bytes::Till::new(
             pattern,
           )))
         }
 
+        /// Contextual version of [`till_at_most`](whitehole::combinator::bytes::till_at_most).
+        #[inline]
+        pub const fn till_at_most<$($lt,)* $($tp,)* __T>(
+          n: usize,
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TillAtMost<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::TillAtMost::new(
+            n, pattern,
+          )))
+        }
+
+        /// Contextual version of [`till_eof`](whitehole::combinator::bytes::till_eof).
+        #[inline]
+        pub const fn till_eof<$($lt,)* $($tp,)*>(
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Till<()>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::Till::new(())))
+        }
+
+        /// Contextual version of [`till_exclusive`](whitehole::combinator::bytes::till_exclusive).
+        #[inline]
+        pub const fn till_exclusive<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TillExclusive<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::TillExclusive::new(pattern),
+          ))
+        }
+
+        /// Contextual version of [`till_inclusive`](whitehole::combinator::bytes::till_inclusive).
+        #[inline]
+        pub const fn till_inclusive<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::TillInclusive<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::TillInclusive::new(pattern),
+          ))
+        }
+
+        /// Contextual version of [`bytes::eat_until`](whitehole::combinator::bytes::eat_until).
+        #[inline]
+        pub const fn eat_until<$($lt,)* $($tp,)* const __N: usize>(
+          needles: [u8; __N],
+        ) -> Combinator<Contextual<$crate::combinator::bytes::EatUntil<[u8; __N]>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::bytes::EatUntil::new(
+            needles,
+          )))
+        }
+
+        /// Contextual version of [`bytes::bounded_uint`](whitehole::combinator::bytes::bounded_uint).
+        #[inline]
+        pub const fn bounded_uint<$($lt,)* $($tp,)*>(
+          min: u64,
+          max: u64,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::BoundedUint, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::BoundedUint::new(min, max, usize::MAX),
+          ))
+        }
+
+        /// Contextual version of [`bytes::uint_with_max_digits`](whitehole::combinator::bytes::uint_with_max_digits).
+        #[inline]
+        pub const fn uint_with_max_digits<$($lt,)* $($tp,)*>(
+          n: usize,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::BoundedUint, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new(
+            $crate::combinator::bytes::BoundedUint::new(0, u64::MAX, n),
+          ))
+        }
+
+        pub mod tlv {
+          use super::*;
+
+          /// Contextual version of [`bytes::tlv::varint_u64`](whitehole::combinator::bytes::tlv::varint_u64).
+          #[inline]
+          pub const fn varint_u64<$($lt,)* $($tp,)*>(
+          ) -> Combinator<Contextual<$crate::combinator::bytes::tlv::Varint, $state, $heap>>
+          where
+            $($wc)*
+          {
+            Combinator::new(Contextual::new(
+              $crate::combinator::bytes::tlv::Varint::new(),
+            ))
+          }
+        }
+
         /// Contextual version of [`bytes::wrap_unchecked`](whitehole::combinator::bytes::wrap_unchecked).
         #[inline]
-        pub const unsafe fn wrap_unchecked<
-          Value,
-          F: Fn(Input<&Instant<&[u8]>, &mut $state, &mut $heap>) -> Option<Output<Value>>,
+        pub const unsafe fn wrap_unchecked<$($lt,)* $($tp,)*
+          __Value,
+          __F: Fn(Input<&Instant<&[u8]>, &mut $state, &mut $heap>) -> Option<Output<__Value>>,
         >(
-          f: F,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::WrapUnchecked<F>, $state, $heap>>
+          f: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::WrapUnchecked<__F>, $state, $heap>>
+        where
+          $($wc)*
         {
           Combinator::new(Contextual::new(
             $crate::combinator::bytes::WrapUnchecked::new(f),
@@ -225,20 +1239,26 @@ macro_rules! contextual {
 
         /// Contextual version of [`bytes::wrap`](whitehole::combinator::bytes::wrap).
         #[inline]
-        pub const fn wrap<
-          Value,
-          F: Fn(Input<&Instant<&[u8]>, &mut $state, &mut $heap>) -> Option<Output<Value>>,
+        pub const fn wrap<$($lt,)* $($tp,)*
+          __Value,
+          __F: Fn(Input<&Instant<&[u8]>, &mut $state, &mut $heap>) -> Option<Output<__Value>>,
         >(
-          f: F,
-        ) -> Combinator<Contextual<$crate::combinator::bytes::Wrap<F>, $state, $heap>> {
+          f: __F,
+        ) -> Combinator<Contextual<$crate::combinator::bytes::Wrap<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
           Combinator::new(Contextual::new($crate::combinator::bytes::Wrap::new(f)))
         }
 
         /// Contextual version of [`bytes::recur`](whitehole::combinator::bytes::recur).
-        pub fn recur<Value>() -> (
-          impl Fn() -> Combinator<$crate::combinator::bytes::Recur<$state, $heap, Value>>,
-          $crate::combinator::bytes::RecurSetter<$state, $heap, Value>,
-        ) {
+        pub fn recur<$($lt,)* $($tp,)* __Value>() -> (
+          impl Fn() -> Combinator<$crate::combinator::bytes::Recur<$state, $heap, __Value>>,
+          $crate::combinator::bytes::RecurSetter<$state, $heap, __Value>,
+        )
+        where
+          $($wc)*
+        {
           let inner = Rc::new(OnceCell::new());
           let setter = $crate::combinator::bytes::RecurSetter::new(inner.clone());
           let getter =
@@ -247,10 +1267,13 @@ macro_rules! contextual {
         }
 
         /// Contextual version of [`bytes::recur_unchecked`](whitehole::combinator::bytes::recur_unchecked).
-        pub unsafe fn recur_unchecked<Value>() -> (
-          impl Fn() -> Combinator<$crate::combinator::bytes::RecurUnchecked<$state, $heap, Value>>,
-          $crate::combinator::bytes::RecurSetter<$state, $heap, Value>,
-        ) {
+        pub unsafe fn recur_unchecked<$($lt,)* $($tp,)* __Value>() -> (
+          impl Fn() -> Combinator<$crate::combinator::bytes::RecurUnchecked<$state, $heap, __Value>>,
+          $crate::combinator::bytes::RecurSetter<$state, $heap, __Value>,
+        )
+        where
+          $($wc)*
+        {
           let inner = Rc::new(OnceCell::new());
           let setter = $crate::combinator::bytes::RecurSetter::new(inner.clone());
           let getter = move || {
@@ -261,14 +1284,79 @@ macro_rules! contextual {
           (getter, setter)
         }
       }
+
+      pub mod u16s {
+        use super::*;
+
+        /// Contextual version of [`u16s::eat`](whitehole::combinator::u16s::eat).
+        #[inline]
+        pub const fn eat<$($lt,)* $($tp,)* __T>(
+          pattern: __T,
+        ) -> Combinator<Contextual<$crate::combinator::u16s::Eat<__T>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::u16s::Eat::new(pattern)))
+        }
+
+        /// Contextual version of [`u16s::take`](whitehole::combinator::u16s::take).
+        #[inline]
+        pub const fn take<$($lt,)* $($tp,)*>(
+          n: usize,
+        ) -> Combinator<Contextual<$crate::combinator::u16s::Take, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::u16s::Take::new(n)))
+        }
+
+        /// Contextual version of [`u16s::next`](whitehole::combinator::u16s::next).
+        #[inline]
+        pub const fn next<$($lt,)* $($tp,)* __F: Fn(u16) -> bool>(
+          condition: __F,
+        ) -> Combinator<Contextual<$crate::combinator::u16s::Next<__F>, $state, $heap>>
+        where
+          $($wc)*
+        {
+          Combinator::new(Contextual::new($crate::combinator::u16s::Next::new(
+            condition,
+          )))
+        }
+      }
     }
-    pub use _impl_contextual_combinators::*;
+    $($reexport)*
+  };
+  (
+    $vis:vis mod $name:ident $(<$($lt:lifetime),* $(,)? $($tp:ident),*>)?:
+    $state:ty, $heap:ty $(where $($wc:tt)*)?
+  ) => {
+    $crate::contextual!(
+      @impl $vis mod $name [$($($lt),*)?] [$($($tp),*)?] [$($($wc)*)?]: $state, $heap;
+    );
+  };
+  ($state:ty, $heap:ty) => {
+    // the re-export must be generated in the same expansion as the module
+    $crate::contextual!(
+      @impl mod _impl_contextual_combinators [] [] []: $state, $heap;
+      pub use _impl_contextual_combinators::*;
+    );
   };
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::parser::Parser;
+
+  /// A state borrowing from a keyword table outside of the parser.
+  pub struct Scope<'a> {
+    keywords: &'a [String],
+    count: usize,
+  }
+
+  contextual!(mod scoped<'a>: Scope<'a>, Vec<&'a str>);
+  contextual!(mod generic<T>: Vec<T>, () where T: Clone);
+  contextual!(pub(crate) mod plain: i32, i32);
 
   #[test]
   fn test_contextual() {
@@ -277,22 +1365,116 @@ mod tests {
     fn helper<Text: ?Sized>(_: impl Action<Text = Text, State = i32, Heap = i32>) {}
 
     helper(eat('a'));
+    helper(eat_ci("a"));
+    helper(eat_in("a"));
+    helper(eat_in_char("a"));
+    helper(eat_range('a'..='z'));
+    helper(eat_range_char('a'..='z'));
     helper(take(1));
+    helper(take_exact(1));
+    helper(take_bytes(1));
+    helper(take_bytes(1).boundary_policy(crate::combinator::BoundaryPolicy::SnapBack));
+    helper(take_at_most(1));
+    helper(skip_at_most(1, |_| true));
+    helper(take_while(|_| true));
+    helper(take_while_min_max(|_| true, 1, 2));
+    helper(take_till(|_| true));
+    helper(identifier());
+    helper(identifier().start(|_| true).cont(|_| true).except(&["a"]));
     helper(next(|_| true));
+    helper(next_n(|_| true, 1));
+    helper(one_of("a"));
+    helper(none_of("a"));
+    struct Token;
+    impl crate::digest::Unit for Token {}
+    helper(token_by(|_: &Token| true));
+    helper(line_end());
+    helper(till_line_end());
+    helper(whitespaces());
+    helper(line_comment("//"));
+    helper(block_comment("/*", "*/"));
+    helper(json_string());
+    helper(json_number());
+    helper(position());
+    helper(state_value(|s| *s));
+    helper(heap_value(|h| *h));
     helper(till('a'));
+    helper(till_at_most(1, 'a'));
+    helper(till_eof());
+    helper(till_exclusive('a'));
+    helper(till_inclusive('a'));
+    helper(eat_until(['a']));
+    helper(bounded_uint(0, 1).reject_leading_zeros());
+    helper(uint_with_max_digits(1));
     helper(wrap(|input| input.instant.accept(0)));
     helper(unsafe { wrap_unchecked(|input| input.instant.accept(0)) });
     helper(recur::<()>().0());
     helper(unsafe { recur_unchecked::<()>() }.0());
+    helper(datetime::date().strict());
+    helper(datetime::time());
+    helper(datetime::offset());
+    helper(datetime::rfc3339().strict());
+    helper(bytes::ascii_alpha());
+    helper(bytes::ascii_alphas1());
+    helper(bytes::ascii_digit());
+    helper(bytes::ascii_digits1());
+    helper(bytes::ascii_alnum());
+    helper(bytes::ascii_alnums1());
+    helper(bytes::ascii_ws());
+    helper(bytes::ascii_ws1());
+    helper(bytes::ascii_hexdigit());
+    helper(bytes::ascii_hexdigits1());
     helper(bytes::eat(b'a'));
+    helper(bytes::eat_ci(b"a"));
+    helper(bytes::eat_in(b"a"));
+    helper(bytes::eat_in_byte(b"a"));
     helper(bytes::take(1));
+    helper(bytes::take_exact(1));
+    helper(bytes::take_at_most(1));
+    helper(bytes::skip_at_most(1, |_| true));
+    helper(bytes::take_while(|_| true));
+    helper(bytes::take_while_min_max(|_| true, 1, 2));
+    helper(bytes::take_till(|_| true));
     helper(bytes::next(|_| true));
+    helper(bytes::next_n(|_| true, 1));
+    helper(bytes::one_of(b"a"));
+    helper(bytes::none_of(b"a"));
+    helper(bytes::line_end());
+    helper(bytes::till_line_end());
+    helper(bytes::position());
+    helper(bytes::state_value(|s| *s));
+    helper(bytes::heap_value(|h| *h));
     helper(bytes::till(b'a'));
+    helper(bytes::till_at_most(1, b'a'));
+    helper(bytes::till_eof());
+    helper(bytes::till_exclusive(b'a'));
+    helper(bytes::till_inclusive(b'a'));
+    helper(bytes::eat_until([b'a']));
+
+    helper(u16s::eat(1u16));
+    helper(u16s::take(1));
+    helper(u16s::next(|_| true));
+    helper(bytes::bounded_uint(0, 1).reject_leading_zeros());
+    helper(bytes::uint_with_max_digits(1));
+    helper(bytes::tlv::varint_u64());
     helper(bytes::wrap(|input| input.instant.accept(0)));
     helper(unsafe { bytes::wrap_unchecked(|input| input.instant.accept(0)) });
     helper(bytes::recur::<()>().0());
     helper(unsafe { bytes::recur_unchecked::<()>() }.0());
 
+    // state_value and heap_value
+    let mut state = 1;
+    let mut heap = 2;
+    let output = (state_value(|s| *s).tuple() + heap_value(|h| *h).tuple())
+      .exec(Input {
+        instant: &Instant::new("123"),
+        state: &mut state,
+        heap: &mut heap,
+      })
+      .unwrap();
+    assert_eq!(output.value, (1, 2));
+    assert_eq!(output.digested, 0);
+
     // debug
     let action = take(1);
     let _ = format!("{:?}", action);
@@ -300,4 +1482,48 @@ mod tests {
     let _c = action;
     let _c = action.clone();
   }
+
+  #[test]
+  fn test_contextual_generics() {
+    let keywords = vec!["let".to_string(), "fn".to_string()];
+    let ident = || {
+      (scoped::next(|c| c.is_ascii_alphabetic()) * (1..)).then(|accepted| {
+        let word = accepted.content();
+        if let Some(keyword) = accepted.state.keywords.iter().find(|k| *k == word) {
+          accepted.state.count += 1;
+          // the heap stores references with the same lifetime as the state
+          accepted.heap.push(keyword);
+        }
+      })
+    };
+    let entry = ident() | scoped::eat(' ');
+
+    let text = "let x fn y";
+    let mut parser = Parser::builder()
+      .state(Scope {
+        keywords: &keywords,
+        count: 0,
+      })
+      .heap(vec![])
+      .entry(entry)
+      .build(text);
+    assert_eq!(parser.by_ref().count(), 7);
+    assert_eq!(parser.state.count, 2);
+    assert_eq!(parser.heap, ["let", "fn"]);
+
+    // type parameters
+    let output = generic::state_value(|s: &Vec<char>| s.len())
+      .exec(Input {
+        instant: &Instant::new(""),
+        state: &mut vec!['a'],
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(output.value, 1);
+
+    // named module with a concrete state
+    fn helper<Text: ?Sized>(_: impl Action<Text = Text, State = i32, Heap = i32>) {}
+    helper(plain::eat('a'));
+    helper(plain::bytes::tlv::varint_u64());
+  }
 }
@@ -0,0 +1,820 @@
+//! Combinators for date and time, following the grammar of
+//! [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339#section-5.6)
+//! (a subset of ISO 8601).
+//!
+//! - [`rfc3339`]: a full date-time, e.g. `1985-04-12T23:20:50.52Z`.
+//! - [`date`]: a full date, e.g. `1985-04-12`.
+//! - [`time()`]: a partial time without offset, e.g. `23:20:50.52`.
+//! - [`offset`]: a time offset, e.g. `Z` or `-08:00`.
+//!
+//! All fields are range-checked (e.g. month `1..=12`, hour `0..=23`).
+//! By default the day of month is only checked to be in `1..=31`,
+//! use `strict` to check it against the real calendar.
+//!
+//! Values are plain structs without any dependency.
+//! Enable the `chrono` or `time` feature for conversions to types of those crates.
+//! # Examples
+//! ```
+//! use whitehole::{combinator::datetime::{rfc3339, DateTime}, parser::Parser};
+//!
+//! let mut parser = Parser::builder()
+//!   .entry(rfc3339())
+//!   .build("1996-12-19T16:39:57-08:00");
+//! assert_eq!(
+//!   parser.next().unwrap().value,
+//!   DateTime {
+//!     year: 1996,
+//!     month: 12,
+//!     day: 19,
+//!     hour: 16,
+//!     minute: 39,
+//!     second: 57,
+//!     nanos: 0,
+//!     utc_offset_minutes: -480,
+//!   }
+//! );
+//! ```
+
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, Contextual},
+  instant::Instant,
+};
+
+/// The value of [`date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Date {
+  pub year: u16,
+  /// `1..=12`.
+  pub month: u8,
+  /// `1..=31`.
+  pub day: u8,
+}
+
+/// The value of [`time()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Time {
+  /// `0..=23`.
+  pub hour: u8,
+  /// `0..=59`.
+  pub minute: u8,
+  /// `0..=60`, `60` is a leap second.
+  pub second: u8,
+  /// Fractional seconds in nanoseconds.
+  /// Digits after the 9th are truncated.
+  pub nanos: u32,
+}
+
+/// The value of [`rfc3339`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+  pub year: u16,
+  /// `1..=12`.
+  pub month: u8,
+  /// `1..=31`.
+  pub day: u8,
+  /// `0..=23`.
+  pub hour: u8,
+  /// `0..=59`.
+  pub minute: u8,
+  /// `0..=60`, `60` is a leap second.
+  pub second: u8,
+  /// Fractional seconds in nanoseconds.
+  /// Digits after the 9th are truncated.
+  pub nanos: u32,
+  /// The offset from UTC in minutes, e.g. `-480` for `-08:00`.
+  /// `Z` and `-00:00` are both `0`.
+  pub utc_offset_minutes: i16,
+}
+
+impl DateTime {
+  /// The date part.
+  #[inline]
+  pub const fn date(&self) -> Date {
+    Date {
+      year: self.year,
+      month: self.month,
+      day: self.day,
+    }
+  }
+
+  /// The time part, without the offset.
+  #[inline]
+  pub const fn time(&self) -> Time {
+    Time {
+      hour: self.hour,
+      minute: self.minute,
+      second: self.second,
+      nanos: self.nanos,
+    }
+  }
+}
+
+/// Return `true` if the `year` is a leap year in the Gregorian calendar.
+// `is_multiple_of` requires Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+#[inline]
+const fn is_leap_year(year: u16) -> bool {
+  year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// The number of days in the `month` (1-based).
+#[inline]
+const fn days_in_month(year: u16, month: u8) -> u8 {
+  match month {
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+/// Parse exactly `n` ascii digits.
+#[inline]
+fn digits(bytes: &[u8], n: usize) -> Option<u16> {
+  let digits = bytes.get(..n)?;
+  let mut value = 0;
+  for &b in digits {
+    if !b.is_ascii_digit() {
+      return None;
+    }
+    value = value * 10 + (b - b'0') as u16;
+  }
+  Some(value)
+}
+
+/// Parse 2 ascii digits which should be in `0..=max`.
+#[inline]
+fn two_digits(bytes: &[u8], max: u8) -> Option<u8> {
+  digits(bytes, 2).map(|v| v as u8).filter(|&v| v <= max)
+}
+
+#[inline]
+fn expect(bytes: &[u8], index: usize, expected: u8) -> Option<()> {
+  (bytes.get(index) == Some(&expected)).then_some(())
+}
+
+/// `YYYY-MM-DD`.
+fn scan_date(bytes: &[u8], strict: bool) -> Option<(Date, usize)> {
+  let year = digits(bytes, 4)?;
+  expect(bytes, 4, b'-')?;
+  let month = two_digits(&bytes[5..], 12).filter(|&m| m != 0)?;
+  expect(bytes, 7, b'-')?;
+  let max_day = if strict {
+    days_in_month(year, month)
+  } else {
+    31
+  };
+  let day = two_digits(&bytes[8..], max_day).filter(|&d| d != 0)?;
+  Some((Date { year, month, day }, 10))
+}
+
+/// `HH:MM:SS[.F+]`.
+fn scan_time(bytes: &[u8]) -> Option<(Time, usize)> {
+  let hour = two_digits(bytes, 23)?;
+  expect(bytes, 2, b':')?;
+  let minute = two_digits(&bytes[3..], 59)?;
+  expect(bytes, 5, b':')?;
+  let second = two_digits(&bytes[6..], 60)?;
+
+  let mut len = 8;
+  let mut nanos = 0;
+  if bytes.get(len) == Some(&b'.') {
+    let frac = bytes[len + 1..]
+      .iter()
+      .take_while(|b| b.is_ascii_digit())
+      .count();
+    if frac == 0 {
+      // at least one digit is required after the dot
+      return None;
+    }
+    for i in 0..9 {
+      let digit = if i < frac {
+        bytes[len + 1 + i] - b'0'
+      } else {
+        0
+      };
+      nanos = nanos * 10 + digit as u32;
+    }
+    len += 1 + frac;
+  }
+
+  Some((
+    Time {
+      hour,
+      minute,
+      second,
+      nanos,
+    },
+    len,
+  ))
+}
+
+/// `Z` or `(+|-)HH:MM`, case-insensitive.
+fn scan_offset(bytes: &[u8]) -> Option<(i16, usize)> {
+  let sign = match bytes.first()? {
+    b'Z' | b'z' => return Some((0, 1)),
+    b'+' => 1,
+    b'-' => -1,
+    _ => return None,
+  };
+  let hour = two_digits(&bytes[1..], 23)?;
+  expect(bytes, 3, b':')?;
+  let minute = two_digits(&bytes[4..], 59)?;
+  Some((sign * (hour as i16 * 60 + minute as i16), 6))
+}
+
+fn scan_rfc3339(bytes: &[u8], strict: bool) -> Option<(DateTime, usize)> {
+  let (date, mut len) = scan_date(bytes, strict)?;
+  match bytes.get(len)? {
+    b'T' | b't' => len += 1,
+    _ => return None,
+  }
+  let (time, time_len) = scan_time(&bytes[len..])?;
+  len += time_len;
+  let (utc_offset_minutes, offset_len) = scan_offset(&bytes[len..])?;
+  len += offset_len;
+
+  if strict && time.second == 60 {
+    // leap seconds can only be at 23:59:60 UTC
+    let utc_minutes =
+      (time.hour as i16 * 60 + time.minute as i16 - utc_offset_minutes).rem_euclid(24 * 60);
+    if utc_minutes != 23 * 60 + 59 {
+      return None;
+    }
+  }
+
+  Some((
+    DateTime {
+      year: date.year,
+      month: date.month,
+      day: date.day,
+      hour: time.hour,
+      minute: time.minute,
+      second: time.second,
+      nanos: time.nanos,
+      utc_offset_minutes,
+    },
+    len,
+  ))
+}
+
+macro_rules! impl_strict {
+  ($name:ident) => {
+    impl $name {
+      #[inline]
+      const fn with_strict(mut self) -> Self {
+        self.strict = true;
+        self
+      }
+    }
+
+    impl Combinator<$name> {
+      /// Check the day of month against the real calendar (including leap years).
+      #[inline]
+      pub const fn strict(self) -> Self {
+        Self::new(self.action.with_strict())
+      }
+    }
+
+    impl<State, Heap> Combinator<Contextual<$name, State, Heap>> {
+      #[doc = concat!("See [`Combinator::<", stringify!($name), ">::strict`].")]
+      #[inline]
+      pub const fn strict(self) -> Self {
+        Self::new(Contextual::new(self.action.action.with_strict()))
+      }
+    }
+  };
+}
+
+/// See [`date`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FullDate {
+  strict: bool,
+}
+
+impl FullDate {
+  /// Create a new non-strict instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { strict: false }
+  }
+}
+
+impl_strict!(FullDate);
+
+unsafe impl Action for FullDate {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = Date;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_date(input.instant.rest().as_bytes(), self.strict).map(|(value, digested)| Output {
+      value,
+      // only ascii chars are digested, so this is always on a char boundary
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to match a full date `YYYY-MM-DD`.
+///
+/// The day of month is only checked to be in `1..=31` by default,
+/// use [`Combinator::<FullDate>::strict`] to check it against the real calendar.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{datetime::{date, Date}, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = Date>>) {}
+/// # t(
+/// date().strict() // reject "2023-02-29"
+/// # );
+/// ```
+#[inline]
+pub const fn date() -> Combinator<FullDate> {
+  Combinator::new(FullDate::new())
+}
+
+/// See [`time()`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PartialTime;
+
+impl PartialTime {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for PartialTime {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = Time;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_time(input.instant.rest().as_bytes()).map(|(value, digested)| Output {
+      value,
+      // only ascii chars are digested, so this is always on a char boundary
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to match a partial time `HH:MM:SS` with optional fractional seconds,
+/// without the offset.
+///
+/// A leap second (`60`) is always accepted.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{datetime::{time, Time}, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = Time>>) {}
+/// # t(
+/// time() // "23:20:50.52"
+/// # );
+/// ```
+#[inline]
+pub const fn time() -> Combinator<PartialTime> {
+  Combinator::new(PartialTime::new())
+}
+
+/// See [`offset`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TimeOffset;
+
+impl TimeOffset {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for TimeOffset {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = i16;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_offset(input.instant.rest().as_bytes()).map(|(value, digested)| Output {
+      value,
+      // only ascii chars are digested, so this is always on a char boundary
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to match a time offset `Z` or `(+|-)HH:MM`.
+///
+/// The value is the offset from UTC in minutes.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{datetime::offset, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = i16>>) {}
+/// # t(
+/// offset() // "-08:00" => -480
+/// # );
+/// ```
+#[inline]
+pub const fn offset() -> Combinator<TimeOffset> {
+  Combinator::new(TimeOffset::new())
+}
+
+/// See [`rfc3339`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Rfc3339 {
+  strict: bool,
+}
+
+impl Rfc3339 {
+  /// Create a new non-strict instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { strict: false }
+  }
+}
+
+impl_strict!(Rfc3339);
+
+unsafe impl Action for Rfc3339 {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = DateTime;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_rfc3339(input.instant.rest().as_bytes(), self.strict).map(|(value, digested)| Output {
+      value,
+      // only ascii chars are digested, so this is always on a char boundary
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to match an RFC 3339 date-time,
+/// e.g. `1985-04-12T23:20:50.52Z`.
+///
+/// `T` and `Z` are case-insensitive.
+/// By default the day of month is only checked to be in `1..=31`
+/// and leap seconds are always accepted.
+/// Use [`Combinator::<Rfc3339>::strict`] to check the day of month against the real calendar
+/// and only accept leap seconds at `23:59:60` UTC.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{datetime::{rfc3339, DateTime}, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = DateTime>>) {}
+/// # t(
+/// rfc3339()
+/// # );
+/// # t(
+/// rfc3339().strict()
+/// # );
+/// ```
+#[inline]
+pub const fn rfc3339() -> Combinator<Rfc3339> {
+  Combinator::new(Rfc3339::new())
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Date> for chrono::NaiveDate {
+  type Error = Date;
+
+  /// Fail if the date doesn't exist in the calendar.
+  #[inline]
+  fn try_from(date: Date) -> Result<Self, Self::Error> {
+    chrono::NaiveDate::from_ymd_opt(date.year as i32, date.month as u32, date.day as u32)
+      .ok_or(date)
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+  type Error = DateTime;
+
+  /// Fail if the date doesn't exist in the calendar.
+  /// Leap seconds are represented by chrono's leap second convention.
+  fn try_from(dt: DateTime) -> Result<Self, Self::Error> {
+    let date: chrono::NaiveDate = dt.date().try_into().map_err(|_| dt)?;
+    let (second, nanos) = if dt.second == 60 {
+      (59, dt.nanos + 1_000_000_000)
+    } else {
+      (dt.second, dt.nanos)
+    };
+    let time =
+      chrono::NaiveTime::from_hms_nano_opt(dt.hour as u32, dt.minute as u32, second as u32, nanos)
+        .ok_or(dt)?;
+    let offset = chrono::FixedOffset::east_opt(dt.utc_offset_minutes as i32 * 60).ok_or(dt)?;
+    date
+      .and_time(time)
+      .and_local_timezone(offset)
+      .single()
+      .ok_or(dt)
+  }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Date> for ::time::Date {
+  type Error = Date;
+
+  /// Fail if the date doesn't exist in the calendar.
+  #[inline]
+  fn try_from(date: Date) -> Result<Self, Self::Error> {
+    let month = ::time::Month::try_from(date.month).map_err(|_| date)?;
+    ::time::Date::from_calendar_date(date.year as i32, month, date.day).map_err(|_| date)
+  }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<DateTime> for ::time::OffsetDateTime {
+  type Error = DateTime;
+
+  /// Fail if the date doesn't exist in the calendar
+  /// or the time is a leap second, which is not supported by the `time` crate.
+  fn try_from(dt: DateTime) -> Result<Self, Self::Error> {
+    let date: ::time::Date = dt.date().try_into().map_err(|_| dt)?;
+    let time =
+      ::time::Time::from_hms_nano(dt.hour, dt.minute, dt.second, dt.nanos).map_err(|_| dt)?;
+    let offset =
+      ::time::UtcOffset::from_whole_seconds(dt.utc_offset_minutes as i32 * 60).map_err(|_| dt)?;
+    Ok(::time::PrimitiveDateTime::new(date, time).assume_offset(offset))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn exec<V>(
+    action: impl Action<Text = str, State = (), Heap = (), Value = V>,
+    input: &str,
+  ) -> Option<(V, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn dt(
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanos: u32,
+    utc_offset_minutes: i16,
+  ) -> DateTime {
+    DateTime {
+      year,
+      month,
+      day,
+      hour,
+      minute,
+      second,
+      nanos,
+      utc_offset_minutes,
+    }
+  }
+
+  #[test]
+  fn test_rfc3339_examples() {
+    // examples from RFC 3339 section 5.8
+    for (text, expected) in [
+      (
+        "1985-04-12T23:20:50.52Z",
+        dt(1985, 4, 12, 23, 20, 50, 520_000_000, 0),
+      ),
+      (
+        "1996-12-19T16:39:57-08:00",
+        dt(1996, 12, 19, 16, 39, 57, 0, -480),
+      ),
+      ("1990-12-31T23:59:60Z", dt(1990, 12, 31, 23, 59, 60, 0, 0)),
+      (
+        "1990-12-31T15:59:60-08:00",
+        dt(1990, 12, 31, 15, 59, 60, 0, -480),
+      ),
+      (
+        "1937-01-01T12:00:27.87+00:20",
+        dt(1937, 1, 1, 12, 0, 27, 870_000_000, 20),
+      ),
+    ] {
+      assert_eq!(exec(rfc3339(), text), Some((expected, text.len())));
+      assert_eq!(exec(rfc3339().strict(), text), Some((expected, text.len())));
+    }
+  }
+
+  #[test]
+  fn test_rfc3339() {
+    // lowercase separators
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01t00:00:00z").map(|(_, d)| d),
+      Some(20)
+    );
+    // `Z` vs numeric offsets
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01T00:00:00Z").unwrap().0,
+      exec(rfc3339(), "2020-01-01T00:00:00+00:00").unwrap().0
+    );
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01T00:00:00-00:00")
+        .unwrap()
+        .0
+        .utc_offset_minutes,
+      0
+    );
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01T00:00:00+05:45")
+        .unwrap()
+        .0
+        .utc_offset_minutes,
+      345
+    );
+    // rest is not digested
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01T00:00:00Z INFO").map(|(_, d)| d),
+      Some(20)
+    );
+    // nanos are truncated after 9 digits
+    assert_eq!(
+      exec(rfc3339(), "2020-01-01T00:00:00.1234567891Z"),
+      Some((dt(2020, 1, 1, 0, 0, 0, 123_456_789, 0), 31))
+    );
+    // missing separator or offset
+    assert_eq!(exec(rfc3339(), "2020-01-01 00:00:00Z"), None);
+    assert_eq!(exec(rfc3339(), "2020-01-01T00:00:00"), None);
+  }
+
+  #[test]
+  fn test_rfc3339_truncated() {
+    let text = "1985-04-12T23:20:50.52+08:00";
+    for i in 0..text.len() {
+      assert_eq!(exec(rfc3339(), &text[..i]), None, "{}", &text[..i]);
+    }
+    // dangling dot
+    assert_eq!(exec(rfc3339(), "1985-04-12T23:20:50.Z"), None);
+  }
+
+  #[test]
+  fn test_rfc3339_leap_seconds() {
+    // invalid leap seconds
+    assert_eq!(exec(rfc3339(), "1990-12-31T23:59:61Z"), None);
+    assert_eq!(exec(rfc3339(), "1990-12-31T23:59:99Z"), None);
+    // leap seconds not at 23:59 UTC are only rejected in strict mode
+    assert!(exec(rfc3339(), "1990-12-31T12:00:60Z").is_some());
+    assert_eq!(exec(rfc3339().strict(), "1990-12-31T12:00:60Z"), None);
+    assert_eq!(exec(rfc3339().strict(), "1990-12-31T23:59:60-08:00"), None);
+    assert!(exec(rfc3339().strict(), "1991-01-01T00:29:60+00:30").is_some());
+  }
+
+  #[test]
+  fn test_date() {
+    assert_eq!(
+      exec(date(), "2024-02-29"),
+      Some((
+        Date {
+          year: 2024,
+          month: 2,
+          day: 29
+        },
+        10
+      ))
+    );
+    // ranges
+    assert_eq!(exec(date(), "2024-00-01"), None);
+    assert_eq!(exec(date(), "2024-13-01"), None);
+    assert_eq!(exec(date(), "2024-01-00"), None);
+    assert_eq!(exec(date(), "2024-01-32"), None);
+    assert_eq!(exec(date(), "24-01-01"), None);
+    // leap-day-agnostic by default
+    assert!(exec(date(), "2023-02-31").is_some());
+    // strict
+    assert!(exec(date().strict(), "2024-02-29").is_some());
+    assert!(exec(date().strict(), "2000-02-29").is_some());
+    assert_eq!(exec(date().strict(), "2023-02-29"), None);
+    assert_eq!(exec(date().strict(), "1900-02-29"), None);
+    assert_eq!(exec(date().strict(), "2023-04-31"), None);
+    assert!(exec(date().strict(), "2023-12-31").is_some());
+  }
+
+  #[test]
+  fn test_time() {
+    assert_eq!(
+      exec(time(), "23:59:60.5"),
+      Some((
+        Time {
+          hour: 23,
+          minute: 59,
+          second: 60,
+          nanos: 500_000_000
+        },
+        10
+      ))
+    );
+    // no offset is digested
+    assert_eq!(exec(time(), "00:00:00Z").map(|(_, d)| d), Some(8));
+    assert_eq!(exec(time(), "24:00:00"), None);
+    assert_eq!(exec(time(), "00:60:00"), None);
+    assert_eq!(exec(time(), "00:00"), None);
+  }
+
+  #[test]
+  fn test_offset() {
+    assert_eq!(exec(offset(), "Z"), Some((0, 1)));
+    assert_eq!(exec(offset(), "z"), Some((0, 1)));
+    assert_eq!(exec(offset(), "+08:00"), Some((480, 6)));
+    assert_eq!(exec(offset(), "-23:59"), Some((-1439, 6)));
+    assert_eq!(exec(offset(), "+24:00"), None);
+    assert_eq!(exec(offset(), "+08"), None);
+    assert_eq!(exec(offset(), ""), None);
+  }
+
+  #[test]
+  fn datetime_parts() {
+    let value = dt(1985, 4, 12, 23, 20, 50, 1, 0);
+    assert_eq!(
+      value.date(),
+      Date {
+        year: 1985,
+        month: 4,
+        day: 12
+      }
+    );
+    assert_eq!(
+      value.time(),
+      Time {
+        hour: 23,
+        minute: 20,
+        second: 50,
+        nanos: 1
+      }
+    );
+  }
+
+  #[cfg(feature = "chrono")]
+  #[test]
+  fn datetime_into_chrono() {
+    let (value, _) = exec(rfc3339(), "1996-12-19T16:39:57.5-08:00").unwrap();
+    let chrono: chrono::DateTime<chrono::FixedOffset> = value.try_into().unwrap();
+    assert_eq!(chrono.timestamp(), 851042397);
+    assert_eq!(chrono.timestamp_subsec_nanos(), 500_000_000);
+
+    let (value, _) = exec(rfc3339(), "1990-12-31T23:59:60Z").unwrap();
+    assert!(chrono::DateTime::<chrono::FixedOffset>::try_from(value).is_ok());
+
+    let (value, _) = exec(rfc3339(), "2023-02-31T00:00:00Z").unwrap();
+    assert_eq!(
+      chrono::DateTime::<chrono::FixedOffset>::try_from(value),
+      Err(value)
+    );
+  }
+
+  #[cfg(feature = "time")]
+  #[test]
+  fn datetime_into_time() {
+    let (value, _) = exec(rfc3339(), "1996-12-19T16:39:57.5-08:00").unwrap();
+    let time: ::time::OffsetDateTime = value.try_into().unwrap();
+    assert_eq!(time.unix_timestamp(), 851042397);
+    assert_eq!(time.nanosecond(), 500_000_000);
+
+    // leap seconds and non-existing dates are not supported
+    let (value, _) = exec(rfc3339(), "1990-12-31T23:59:60Z").unwrap();
+    assert!(::time::OffsetDateTime::try_from(value).is_err());
+    let (value, _) = exec(rfc3339(), "2023-02-31T00:00:00Z").unwrap();
+    assert!(::time::OffsetDateTime::try_from(value).is_err());
+  }
+
+  fn _datetime_debug() {
+    let _ = format!("{:?}", rfc3339());
+    let _ = format!("{:?}", date());
+    let _ = format!("{:?}", time());
+    let _ = format!("{:?}", offset());
+  }
+
+  fn _datetime_clone_copy() {
+    let c = rfc3339();
+    let _c = c;
+    let _c = c.clone();
+  }
+}
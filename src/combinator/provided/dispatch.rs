@@ -0,0 +1,440 @@
+use crate::{
+  action::{exec_by_cut, Action, Input, Output, Rejection},
+  combinator::Combinator,
+  digest::Digest,
+  instant::Instant,
+};
+use std::{fmt::Debug, marker::PhantomData, ops::RangeInclusive};
+
+/// A set of heads for [`Combinator::on`].
+///
+/// Heads are keyed by the first byte of their encoding,
+/// so for [`str`], chars which share the same leading UTF-8 byte share the same slot.
+///
+/// Built-in implementations are provided for
+/// [`char`], `&str` (each char is a head) and [`RangeInclusive<char>`] for [`str`],
+/// and [`u8`], `&[u8]`, `&[u8; N]` and [`RangeInclusive<u8>`] for `[u8]`.
+pub trait DispatchHeads<Text: ?Sized> {
+  /// Mark the first byte of each head in the `mask`.
+  fn mark(&self, mask: &mut [bool; 256]);
+}
+
+impl DispatchHeads<str> for char {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    let mut buf = [0; 4];
+    mask[self.encode_utf8(&mut buf).as_bytes()[0] as usize] = true;
+  }
+}
+
+impl DispatchHeads<str> for &str {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    self.chars().for_each(|c| c.mark(mask));
+  }
+}
+
+impl DispatchHeads<str> for RangeInclusive<char> {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    self.clone().for_each(|c| c.mark(mask));
+  }
+}
+
+impl DispatchHeads<[u8]> for u8 {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    mask[*self as usize] = true;
+  }
+}
+
+impl DispatchHeads<[u8]> for &[u8] {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    self.iter().for_each(|b| b.mark(mask));
+  }
+}
+
+impl<const N: usize> DispatchHeads<[u8]> for &[u8; N] {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    self.iter().for_each(|b| b.mark(mask));
+  }
+}
+
+impl DispatchHeads<[u8]> for RangeInclusive<u8> {
+  #[inline]
+  fn mark(&self, mask: &mut [bool; 256]) {
+    self.clone().for_each(|b| b.mark(mask));
+  }
+}
+
+/// An arm registered by [`Combinator::on`].
+/// See [`dispatch`].
+#[derive(Clone, Copy)]
+pub struct DispatchArm<Prev, A> {
+  prev: Prev,
+  mask: [bool; 256],
+  action: A,
+}
+
+impl<Prev: Debug, A: Debug> Debug for DispatchArm<Prev, A> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("DispatchArm")
+      .field("prev", &self.prev)
+      .field("action", &self.action)
+      .finish()
+  }
+}
+
+/// A helper trait to execute the registered arms of [`dispatch`] in registration order.
+///
+/// Implemented for `()` (no arm) and [`DispatchArm`].
+pub trait DispatchArms<Text: ?Sized, State, Heap, Value> {
+  /// Try the arms registered for the `head` in registration order.
+  /// Stop at the first arm which accepts or rejects with [`Rejection::Cut`].
+  fn exec_arms(
+    &self,
+    head: u8,
+    input: Input<&Instant<&Text>, &mut State, &mut Heap>,
+  ) -> Result<Output<Value>, Rejection>;
+}
+
+impl<Text: ?Sized, State, Heap, Value> DispatchArms<Text, State, Heap, Value> for () {
+  #[inline]
+  fn exec_arms(
+    &self,
+    _: u8,
+    _: Input<&Instant<&Text>, &mut State, &mut Heap>,
+  ) -> Result<Output<Value>, Rejection> {
+    Err(Rejection::Backtrack)
+  }
+}
+
+impl<Prev: DispatchArms<A::Text, A::State, A::Heap, A::Value>, A: Action>
+  DispatchArms<A::Text, A::State, A::Heap, A::Value> for DispatchArm<Prev, A>
+{
+  #[inline]
+  fn exec_arms(
+    &self,
+    head: u8,
+    mut input: Input<&Instant<&A::Text>, &mut A::State, &mut A::Heap>,
+  ) -> Result<Output<A::Value>, Rejection> {
+    let rejection = match self.prev.exec_arms(head, input.reborrow()) {
+      Err(rejection @ (Rejection::Backtrack | Rejection::Incomplete)) => rejection,
+      res => return res,
+    };
+    if self.mask[head as usize] {
+      self
+        .action
+        .exec_cut(input)
+        .map_err(|other| rejection.merge(other))
+    } else {
+      Err(rejection)
+    }
+  }
+}
+
+/// The default fallback of [`dispatch`] which always rejects.
+pub struct NoFallback<Text: ?Sized, State, Heap, Value> {
+  #[allow(clippy::type_complexity)]
+  _phantom: PhantomData<fn(&Text) -> (State, Heap, Value)>,
+}
+
+impl<Text: ?Sized, State, Heap, Value> NoFallback<Text, State, Heap, Value> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      _phantom: PhantomData,
+    }
+  }
+}
+
+// implement manually to avoid the bounds on generic types
+impl<Text: ?Sized, State, Heap, Value> Clone for NoFallback<Text, State, Heap, Value> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Text: ?Sized, State, Heap, Value> Copy for NoFallback<Text, State, Heap, Value> {}
+
+impl<Text: ?Sized, State, Heap, Value> Default for NoFallback<Text, State, Heap, Value> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Text: ?Sized, State, Heap, Value> Debug for NoFallback<Text, State, Heap, Value> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("NoFallback").finish()
+  }
+}
+
+unsafe impl<Text: ?Sized, State, Heap, Value> Action for NoFallback<Text, State, Heap, Value> {
+  type Text = Text;
+  type State = State;
+  type Heap = Heap;
+  type Value = Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    _: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    None
+  }
+}
+
+/// See [`dispatch`].
+#[derive(Clone, Copy)]
+pub struct Dispatch<Arms, Fallback> {
+  arms: Arms,
+  /// Whether any arm is registered for each head.
+  known: [bool; 256],
+  fallback: Fallback,
+}
+
+impl<Arms: Debug, Fallback: Debug> Debug for Dispatch<Arms, Fallback> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Dispatch")
+      .field("arms", &self.arms)
+      .field("fallback", &self.fallback)
+      .finish()
+  }
+}
+
+unsafe impl<
+    Arms: DispatchArms<Fallback::Text, Fallback::State, Fallback::Heap, Fallback::Value>,
+    Fallback: Action<Text: Digest>,
+  > Action for Dispatch<Arms, Fallback>
+{
+  type Text = Fallback::Text;
+  type State = Fallback::State;
+  type Heap = Fallback::Heap;
+  type Value = Fallback::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    match input.instant.rest().as_bytes().first() {
+      Some(&head) if self.known[head as usize] => self.arms.exec_arms(head, input),
+      _ => self.fallback.exec_cut(input),
+    }
+  }
+}
+
+impl<Arms, Fallback: Action> Combinator<Dispatch<Arms, Fallback>> {
+  /// Register an arm for [`dispatch`].
+  /// The `action` will only be tried if the rest starts with one of the `heads`.
+  ///
+  /// Arms are tried in registration order, the first accepted one wins.
+  ///
+  /// Tips: you can use [`char`], `&str`, [`String`], [`u8`], `&[u8]` and [`Vec<u8>`] as the shorthand
+  /// for [`eat`](crate::combinator::eat) in the `action`.
+  #[inline]
+  pub fn on<
+    A: Action<
+      Text = Fallback::Text,
+      State = Fallback::State,
+      Heap = Fallback::Heap,
+      Value = Fallback::Value,
+    >,
+  >(
+    self,
+    heads: impl DispatchHeads<Fallback::Text>,
+    action: impl Into<Combinator<A>>,
+  ) -> Combinator<Dispatch<DispatchArm<Arms, A>, Fallback>> {
+    let mut mask = [false; 256];
+    heads.mark(&mut mask);
+    let mut known = self.action.known;
+    for (known, marked) in known.iter_mut().zip(mask) {
+      *known |= marked;
+    }
+    Combinator::new(Dispatch {
+      arms: DispatchArm {
+        prev: self.action.arms,
+        mask,
+        action: action.into().action,
+      },
+      known,
+      fallback: self.action.fallback,
+    })
+  }
+
+  /// Set the fallback arm for [`dispatch`].
+  /// The `action` will only be tried if no arm is registered for the head of the rest,
+  /// or the rest is empty.
+  #[inline]
+  pub fn fallback<
+    NewFallback: Action<
+      Text = Fallback::Text,
+      State = Fallback::State,
+      Heap = Fallback::Heap,
+      Value = Fallback::Value,
+    >,
+  >(
+    self,
+    action: impl Into<Combinator<NewFallback>>,
+  ) -> Combinator<Dispatch<Arms, NewFallback>> {
+    Combinator::new(Dispatch {
+      arms: self.action.arms,
+      known: self.action.known,
+      fallback: action.into().action,
+    })
+  }
+}
+
+/// Returns a combinator to dispatch by the first char or byte of the rest.
+///
+/// Register arms with [`Combinator::on`] and an optional fallback with [`Combinator::fallback`].
+/// When executed, the first byte of the rest is used to index a lookup table,
+/// then only the arms registered for that head are tried in registration order.
+/// This is faster than a long `|` chain where every rejected branch is executed.
+///
+/// All arms must have the same `Value`.
+///
+/// The combinator will reject if all tried arms reject.
+/// The fallback is not tried in this case,
+/// it is only for heads without any registered arm and the empty rest.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{dispatch, eat, next}, parser::Parser};
+/// let entry = dispatch()
+///   .on('0'..='9', next(|c| c.is_ascii_digit()) * (1..))
+///   .on("tf", eat("true") | "false")
+///   .on('"', eat('"') + (next(|c| c != '"') * ..) + '"')
+///   .fallback(next(|_| true));
+/// let mut parser = Parser::builder().entry(entry).build("123true\"a\"x");
+/// assert_eq!(parser.next().unwrap().digested, 3);
+/// assert_eq!(parser.next().unwrap().digested, 4);
+/// assert_eq!(parser.next().unwrap().digested, 3);
+/// assert_eq!(parser.next().unwrap().digested, 1);
+/// ```
+#[inline]
+pub const fn dispatch<Text: ?Sized, State, Heap, Value>(
+) -> Combinator<Dispatch<(), NoFallback<Text, State, Heap, Value>>> {
+  Combinator::new(Dispatch {
+    arms: (),
+    known: [false; 256],
+    fallback: NoFallback::new(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, contextual, eat, next},
+    parser::Parser,
+  };
+
+  fn digested<Text: ?Sized + Digest, Value>(
+    action: &impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+  ) -> Option<(Value, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn combinator_dispatch() {
+    let entry = dispatch()
+      .on('0'..='9', (next(|c| c.is_ascii_digit()) * (1..)).bind(1))
+      .on("tf", (eat("true") | "false").bind(2))
+      .on('-', eat("->").bind(3))
+      .on('-', eat('-').bind(4))
+      .fallback(next(|_| true).bind(0));
+
+    assert_eq!(digested(&entry, "123a"), Some((1, 3)));
+    assert_eq!(digested(&entry, "true"), Some((2, 4)));
+    assert_eq!(digested(&entry, "false"), Some((2, 5)));
+    // registration order
+    assert_eq!(digested(&entry, "->"), Some((3, 2)));
+    assert_eq!(digested(&entry, "-1"), Some((4, 1)));
+    // known head but all arms rejected, the fallback is not tried
+    assert_eq!(digested(&entry, "tx"), None);
+    // unknown head
+    assert_eq!(digested(&entry, "x"), Some((0, 1)));
+    assert_eq!(digested(&entry, "好"), Some((0, 3)));
+    // empty rest
+    assert_eq!(digested(&entry, ""), None);
+
+    // without fallback
+    let entry = dispatch().on('a', eat('a'));
+    assert_eq!(digested(&entry, "a"), Some(((), 1)));
+    assert_eq!(digested(&entry, "b"), None);
+    assert_eq!(digested(&entry, ""), None);
+    let entry = dispatch::<str, (), (), ()>();
+    assert_eq!(digested(&entry, "a"), None);
+    assert_eq!(digested(&entry, ""), None);
+
+    // the fallback for the empty rest
+    let entry = dispatch().on('a', eat('a')).fallback(eat(""));
+    assert_eq!(digested(&entry, ""), Some(((), 0)));
+  }
+
+  #[test]
+  fn combinator_dispatch_multi_byte_heads() {
+    // '好' (E5 A5 BD) and '嘿' (E5 98 BF) share the first byte
+    let entry = dispatch()
+      .on('好', eat('好').bind(1))
+      .on("嘿", eat('嘿').bind(2))
+      .fallback(next(|_| true).bind(0));
+    assert_eq!(digested(&entry, "好"), Some((1, 3)));
+    assert_eq!(digested(&entry, "嘿"), Some((2, 3)));
+    // same first byte, no arm accepts, the fallback is not tried
+    assert_eq!(digested(&entry, "嗨"), None);
+    assert_eq!(digested(&entry, "a"), Some((0, 1)));
+  }
+
+  #[test]
+  fn combinator_dispatch_bytes() {
+    let entry = dispatch()
+      .on(b'0'..=b'9', bytes::next(|b| b.is_ascii_digit()) * (1..))
+      .on(b"tf", bytes::eat(b"true") | b"false".as_slice())
+      .on(b',', b',');
+    assert_eq!(digested(&entry, b"12,true" as &[u8]), Some(((), 2)));
+    assert_eq!(digested(&entry, b"false" as &[u8]), Some(((), 5)));
+    assert_eq!(digested(&entry, b"," as &[u8]), Some(((), 1)));
+    assert_eq!(digested(&entry, b"x" as &[u8]), None);
+
+    let mut parser = Parser::builder().entry(entry).build(b"1,true,23" as &[u8]);
+    assert_eq!(
+      (&mut parser).map(|o| o.digested).collect::<Vec<_>>(),
+      vec![1, 1, 4, 1, 2]
+    );
+  }
+
+  #[test]
+  fn combinator_dispatch_contextual() {
+    contextual!(usize, ());
+
+    let entry = dispatch()
+      .on('a', eat('a').then(|a| *a.state += 1))
+      .fallback(take(1));
+    let mut parser = Parser::builder().state(0).entry(entry).build("abaa");
+    for _ in &mut parser {}
+    assert_eq!(parser.state, 3);
+  }
+
+  fn _dispatch_debug_clone_copy() {
+    let c = dispatch().on('a', eat('a')).fallback(eat('b'));
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+    let _ = NoFallback::<str, (), (), ()>::default();
+  }
+}
@@ -1,6 +1,8 @@
 use crate::{
-  action::{Action, Input},
-  combinator::{provided::create_value_combinator, Combinator, Output},
+  action::{Action, Input, Rejection},
+  combinator::{
+    provided::create_value_combinator, CharSet, Combinator, FirstSet, LookaheadSet, Output,
+  },
   instant::Instant,
 };
 
@@ -23,6 +25,20 @@ unsafe impl Action for Eat<char> {
       .starts_with(self.inner)
       .then(|| unsafe { input.instant.accept_unchecked(self.inner.len_utf8()) })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let rest = input.instant.rest();
+    self.exec(input).ok_or_else(|| {
+      Rejection::by_prefix(
+        rest.as_bytes(),
+        self.inner.encode_utf8(&mut [0; 4]).as_bytes(),
+      )
+    })
+  }
 }
 
 unsafe impl Action for Eat<String> {
@@ -42,6 +58,17 @@ unsafe impl Action for Eat<String> {
       .starts_with(&self.inner)
       .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let rest = input.instant.rest();
+    self
+      .exec(input)
+      .ok_or_else(|| Rejection::by_prefix(rest.as_bytes(), self.inner.as_bytes()))
+  }
 }
 
 unsafe impl Action for Eat<&str> {
@@ -61,6 +88,51 @@ unsafe impl Action for Eat<&str> {
       .starts_with(self.inner)
       .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
   }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let rest = input.instant.rest();
+    self
+      .exec(input)
+      .ok_or_else(|| Rejection::by_prefix(rest.as_bytes(), self.inner.as_bytes()))
+  }
+}
+
+impl FirstSet for Eat<char> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::new(CharSet::from_iter([self.inner]), false))
+  }
+}
+
+/// The first char of the pattern, or nullable if the pattern is empty.
+#[inline]
+fn str_first_set(pattern: &str) -> Option<LookaheadSet> {
+  Some(
+    pattern
+      .chars()
+      .next()
+      .map_or_else(LookaheadSet::empty, |c| {
+        LookaheadSet::new(CharSet::from_iter([c]), false)
+      }),
+  )
+}
+
+impl FirstSet for Eat<String> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    str_first_set(&self.inner)
+  }
+}
+
+impl FirstSet for Eat<&str> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    str_first_set(self.inner)
+  }
 }
 
 /// Returns a combinator to eat from the head of [`Instant::rest`] by the provided pattern.
@@ -109,6 +181,102 @@ impl<'a> From<&'a str> for Combinator<Eat<&'a str>> {
   }
 }
 
+create_value_combinator!(EatCaseInsensitive, "See [`eat_ci`].");
+
+unsafe impl Action for EatCaseInsensitive<char> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .chars()
+      .next()
+      .is_some_and(|c| c.eq_ignore_ascii_case(&self.inner))
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len_utf8()) })
+  }
+}
+
+/// Check if `rest` starts with `pattern`, ignoring ASCII case.
+#[inline]
+fn starts_with_ignore_ascii_case(rest: &str, pattern: &str) -> bool {
+  // non-ascii bytes are compared exactly, so the matched prefix
+  // always ends at a char boundary
+  rest
+    .as_bytes()
+    .get(..pattern.len())
+    .is_some_and(|head| head.eq_ignore_ascii_case(pattern.as_bytes()))
+}
+
+unsafe impl Action for EatCaseInsensitive<String> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    starts_with_ignore_ascii_case(input.instant.rest(), &self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+unsafe impl Action for EatCaseInsensitive<&str> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    starts_with_ignore_ascii_case(input.instant.rest(), self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+/// Returns a combinator to eat from the head of [`Instant::rest`] by the provided pattern,
+/// ignoring ASCII case.
+/// The combinator will reject if the pattern is not found.
+///
+/// Only ASCII letters are compared case-insensitively (see [`str::eq_ignore_ascii_case`]),
+/// non-ASCII chars are compared exactly without Unicode case folding.
+/// The digested length is always the length of the pattern.
+/// # Caveats
+/// Empty patterns are allowed and will always accept 0 bytes,
+/// even when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat_ci, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// eat_ci('a') // eat by char, matches 'a' or 'A'
+/// # );
+/// # t(
+/// eat_ci("select") // eat by &str, matches "SELECT", "Select", etc.
+/// # );
+/// # t(
+/// eat_ci("select".to_string()) // eat by String
+/// # );
+/// ```
+#[inline]
+pub const fn eat_ci<T>(pattern: T) -> Combinator<EatCaseInsensitive<T>> {
+  Combinator::new(EatCaseInsensitive::new(pattern))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -152,6 +320,73 @@ mod tests {
     helper(eat("".to_string()), "", Some(0));
   }
 
+  #[test]
+  fn combinator_eat_incomplete() {
+    fn exec_cut(
+      action: impl Action<Text = str, State = (), Heap = (), Value = ()>,
+      input: &str,
+    ) -> Result<usize, Rejection> {
+      action
+        .exec_cut(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    }
+    assert_eq!(exec_cut(eat("123"), "123"), Ok(3));
+    // the rest is a prefix of the pattern
+    assert_eq!(exec_cut(eat("123"), "12"), Err(Rejection::Incomplete));
+    assert_eq!(
+      exec_cut(eat("123".to_string()), ""),
+      Err(Rejection::Incomplete)
+    );
+    assert_eq!(exec_cut(eat('好'), ""), Err(Rejection::Incomplete));
+    // mismatch
+    assert_eq!(exec_cut(eat("123"), "13"), Err(Rejection::Backtrack));
+    assert_eq!(exec_cut(eat('好'), "a"), Err(Rejection::Backtrack));
+  }
+
+  #[test]
+  fn combinator_eat_ci() {
+    // mixed case
+    helper(eat_ci("select"), "SELECT *", Some(6));
+    helper(eat_ci("select"), "SeLeCt", Some(6));
+    helper(eat_ci("SELECT"), "select", Some(6));
+    helper(eat_ci("select".to_string()), "Select", Some(6));
+    helper(eat_ci('a'), "A", Some(1));
+    helper(eat_ci('A'), "a", Some(1));
+    // partial matches reject
+    helper(eat_ci("select"), "SELEC", None);
+    helper(eat_ci("select"), "SELECX", None);
+    helper(eat_ci("select".to_string()), "sel", None);
+    helper(eat_ci('a'), "b", None);
+    helper(eat_ci('a'), "", None);
+    // non-ascii chars are compared exactly
+    helper(eat_ci("好a"), "好A", Some(4));
+    helper(eat_ci("é"), "é", Some(2));
+    helper(eat_ci("é"), "É", None);
+    helper(eat_ci('é'), "É", None);
+    helper(eat_ci("straße"), "STRASSE", None);
+    helper(eat_ci("straße"), "STRAßE", Some(7));
+    // empty string is allowed and always accept
+    helper(eat_ci(""), "123", Some(0));
+    helper(eat_ci(""), "", Some(0));
+    helper(eat_ci("".to_string()), "", Some(0));
+  }
+
+  #[test]
+  fn combinator_eat_ci_composition() {
+    // literals in `+` are still case-sensitive
+    helper(eat_ci("select") + " *", "SELECT *", Some(8));
+    helper(eat_ci("select") + "x", "SELECTX", None);
+    // boundary
+    helper(eat_ci("select").boundary(), "SELECT *", Some(6));
+    helper(eat_ci("select").boundary(), "SELECTED", None);
+    helper(eat_ci("select").boundary(), "Select_1", None);
+    helper(eat_ci("select").boundary(), "select", Some(6));
+  }
+
   #[test]
   fn eat_into_combinator() {
     fn test(c: Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>>) {
@@ -164,11 +399,15 @@ mod tests {
 
   fn _eat_debug() {
     let _ = format!("{:?}", eat('a'));
+    let _ = format!("{:?}", eat_ci('a'));
   }
 
   fn _eat_clone_copy() {
     let c = eat('a');
     let _c = c;
     let _c = c.clone();
+    let c = eat_ci("a");
+    let _c = c;
+    let _c = c.clone();
   }
 }
@@ -0,0 +1,407 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, CharSet, Combinator, Describe, FirstSet, LookaheadSet},
+  instant::Instant,
+};
+use std::{fmt, marker::PhantomData, ops::RangeInclusive};
+
+/// Build a 128-bit bitmap of the ASCII bytes in `set`.
+/// Non-ASCII bytes are ignored.
+const fn ascii_bitmap(set: &[u8]) -> [u64; 2] {
+  let mut bitmap = [0; 2];
+  let mut i = 0;
+  while i < set.len() {
+    let b = set[i];
+    if b.is_ascii() {
+      bitmap[(b >> 6) as usize] |= 1 << (b & 63);
+    }
+    i += 1;
+  }
+  bitmap
+}
+
+/// Return `true` if any byte in `set` is not ASCII.
+const fn has_non_ascii(set: &[u8]) -> bool {
+  let mut i = 0;
+  while i < set.len() {
+    if !set[i].is_ascii() {
+      return true;
+    }
+    i += 1;
+  }
+  false
+}
+
+/// See [`eat_in`] and [`eat_in_char`].
+pub struct EatIn<'a, Value = ()> {
+  set: &'a str,
+  ascii: [u64; 2],
+  non_ascii: bool,
+  _value: PhantomData<Value>,
+}
+
+impl<Value> Clone for EatIn<'_, Value> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Value> Copy for EatIn<'_, Value> {}
+
+impl<Value> fmt::Debug for EatIn<'_, Value> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("EatIn").field("set", &self.set).finish()
+  }
+}
+
+impl<Value> Describe for EatIn<'_, Value> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "EatIn", module_path!())?;
+    write!(f, " {:?})", self.set)
+  }
+}
+
+impl<'a, Value> EatIn<'a, Value> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(set: &'a str) -> Self {
+    Self {
+      set,
+      ascii: ascii_bitmap(set.as_bytes()),
+      non_ascii: has_non_ascii(set.as_bytes()),
+      _value: PhantomData,
+    }
+  }
+
+  /// The provided set.
+  #[inline]
+  pub(super) const fn set(&self) -> &'a str {
+    self.set
+  }
+
+  /// Return the matched char and its length in bytes
+  /// if the head of `rest` is in the set.
+  #[inline]
+  pub(super) fn matches(&self, rest: &str) -> Option<(char, usize)> {
+    let b = *rest.as_bytes().first()?;
+    if b.is_ascii() {
+      // fast path, check the bitmap
+      return (self.ascii[(b >> 6) as usize] & (1 << (b & 63)) != 0).then_some((b as char, 1));
+    }
+    if !self.non_ascii {
+      return None;
+    }
+    let c = rest.chars().next()?;
+    self.set.contains(c).then(|| (c, c.len_utf8()))
+  }
+}
+
+impl<Value> FirstSet for EatIn<'_, Value> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::new(self.set.chars().collect(), false))
+  }
+}
+
+unsafe impl Action for EatIn<'_> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self
+      .matches(input.instant.rest())
+      .map(|(_, len)| unsafe { input.instant.accept_unchecked(len) })
+  }
+}
+
+unsafe impl Action for EatIn<'_, char> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = char;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.matches(input.instant.rest()).map(|(c, len)| Output {
+      value: c,
+      digested: len,
+    })
+  }
+}
+
+/// Returns a combinator to eat one char from the head of [`Instant::rest`]
+/// if the char is in the provided set.
+/// The combinator will reject if the char is not in the set or the rest is empty.
+///
+/// ASCII chars are checked by a bitmap, non-ASCII chars are searched in the set linearly.
+/// An empty set always rejects.
+///
+/// Use [`eat_in_char`] to get the matched char as the value.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat_in, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = ()>>) {}
+/// # t(
+/// eat_in("+-*/") // eat one of the operators
+/// # );
+/// ```
+#[inline]
+pub const fn eat_in(set: &str) -> Combinator<EatIn<'_>> {
+  Combinator::new(EatIn::new(set))
+}
+
+/// Returns a combinator to eat one char from the head of [`Instant::rest`]
+/// if the char is in the provided set, and take the char as the value.
+/// See [`eat_in`].
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat_in_char, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = char>>) {}
+/// # t(
+/// eat_in_char("+-*/") // eat one of the operators as a char
+/// # );
+/// ```
+#[inline]
+pub const fn eat_in_char(set: &str) -> Combinator<EatIn<'_, char>> {
+  Combinator::new(EatIn::new(set))
+}
+
+/// See [`eat_range`] and [`eat_range_char`].
+pub struct EatRange<Value = ()> {
+  start: char,
+  end: char,
+  _value: PhantomData<Value>,
+}
+
+impl<Value> Clone for EatRange<Value> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Value> Copy for EatRange<Value> {}
+
+impl<Value> fmt::Debug for EatRange<Value> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("EatRange")
+      .field("start", &self.start)
+      .field("end", &self.end)
+      .finish()
+  }
+}
+
+impl<Value> Describe for EatRange<Value> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "EatRange", module_path!())?;
+    write!(f, " {:?}..={:?})", self.start, self.end)
+  }
+}
+
+impl<Value> EatRange<Value> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(range: RangeInclusive<char>) -> Self {
+    Self {
+      start: *range.start(),
+      end: *range.end(),
+      _value: PhantomData,
+    }
+  }
+
+  /// Return the matched char if the head of `rest` is in the range.
+  #[inline]
+  fn matches(&self, rest: &str) -> Option<char> {
+    rest
+      .chars()
+      .next()
+      .filter(|c| (self.start..=self.end).contains(c))
+  }
+}
+
+impl<Value> FirstSet for EatRange<Value> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    let mut chars = CharSet::new();
+    chars.insert_range(self.start..=self.end);
+    Some(LookaheadSet::new(chars, false))
+  }
+}
+
+unsafe impl Action for EatRange {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self
+      .matches(input.instant.rest())
+      .map(|c| unsafe { input.instant.accept_unchecked(c.len_utf8()) })
+  }
+}
+
+unsafe impl Action for EatRange<char> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = char;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.matches(input.instant.rest()).map(|c| Output {
+      value: c,
+      digested: c.len_utf8(),
+    })
+  }
+}
+
+/// Returns a combinator to eat one char from the head of [`Instant::rest`]
+/// if the char is in the provided inclusive range.
+/// The combinator will reject if the char is not in the range or the rest is empty.
+///
+/// An empty range (e.g. `'z'..='a'`) always rejects.
+///
+/// Use [`eat_range_char`] to get the matched char as the value.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat_range, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = ()>>) {}
+/// # t(
+/// eat_range('a'..='z') // eat one lowercase ASCII letter
+/// # );
+/// ```
+#[inline]
+pub const fn eat_range(range: RangeInclusive<char>) -> Combinator<EatRange> {
+  Combinator::new(EatRange::new(range))
+}
+
+/// Returns a combinator to eat one char from the head of [`Instant::rest`]
+/// if the char is in the provided inclusive range, and take the char as the value.
+/// See [`eat_range`].
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat_range_char, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = char>>) {}
+/// # t(
+/// eat_range_char('0'..='9') // eat one digit as a char
+/// # );
+/// ```
+#[inline]
+pub const fn eat_range_char(range: RangeInclusive<char>) -> Combinator<EatRange<char>> {
+  Combinator::new(EatRange::new(range))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fmt::Debug;
+
+  fn helper<Value: PartialEq + Debug>(
+    action: impl Action<Text = str, Value = Value, State = (), Heap = ()>,
+    input: &str,
+    expected: Option<(Value, usize)>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn combinator_eat_in() {
+    helper(eat_in("+-*/"), "+1", Some(((), 1)));
+    helper(eat_in("+-*/"), "/", Some(((), 1)));
+    helper(eat_in("+-*/"), "1+", None);
+    helper(eat_in("+-*/"), "", None);
+    // boundaries of the bitmap
+    helper(eat_in("\0?@\x7f"), "\0", Some(((), 1)));
+    helper(eat_in("\0?@\x7f"), "?", Some(((), 1)));
+    helper(eat_in("\0?@\x7f"), "@", Some(((), 1)));
+    helper(eat_in("\0?@\x7f"), "\x7f", Some(((), 1)));
+    helper(eat_in("\0?@\x7f"), "A", None);
+    // non-ascii input against an ascii set
+    helper(eat_in("+-*/"), "好", None);
+    helper(eat_in("+-*/"), "\u{80}", None);
+    // non-ascii set
+    helper(eat_in("+好"), "好+", Some(((), 3)));
+    helper(eat_in("+好"), "+好", Some(((), 1)));
+    helper(eat_in("+好"), "你", None);
+    // empty set always rejects
+    helper(eat_in(""), "a", None);
+    helper(eat_in(""), "", None);
+  }
+
+  #[test]
+  fn combinator_eat_in_char() {
+    helper(eat_in_char("+-*/"), "*", Some(('*', 1)));
+    helper(eat_in_char("+好"), "好", Some(('好', 3)));
+    helper(eat_in_char("+-*/"), "好", None);
+    helper(eat_in_char(""), "a", None);
+  }
+
+  #[test]
+  fn combinator_eat_range() {
+    // boundaries
+    helper(eat_range('a'..='z'), "a", Some(((), 1)));
+    helper(eat_range('a'..='z'), "z", Some(((), 1)));
+    helper(eat_range('a'..='z'), "`", None);
+    helper(eat_range('a'..='z'), "{", None);
+    helper(eat_range('a'..='z'), "", None);
+    // single char
+    helper(eat_range('a'..='a'), "a", Some(((), 1)));
+    // empty range always rejects
+    helper(eat_range('z'..='a'), "a", None);
+    helper(eat_range('z'..='a'), "z", None);
+    // non-ascii
+    helper(eat_range('一'..='龥'), "好", Some(((), 3)));
+    helper(eat_range('a'..='z'), "好", None);
+  }
+
+  #[test]
+  fn combinator_eat_range_char() {
+    helper(eat_range_char('0'..='9'), "0", Some(('0', 1)));
+    helper(eat_range_char('0'..='9'), "9", Some(('9', 1)));
+    helper(eat_range_char('一'..='龥'), "好", Some(('好', 3)));
+    helper(eat_range_char('0'..='9'), "a", None);
+  }
+
+  fn _eat_in_debug() {
+    let _ = format!("{:?}", eat_in("+-"));
+    let _ = format!("{:?}", eat_in_char("+-"));
+    let _ = format!("{:?}", eat_range('a'..='z'));
+    let _ = format!("{:?}", eat_range_char('a'..='z'));
+  }
+
+  fn _eat_in_clone_copy() {
+    let c = eat_in("+-");
+    let _c = c;
+    let _c = c.clone();
+    let c = eat_range_char('a'..='z');
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,301 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  digest::Digest,
+  instant::Instant,
+};
+use std::{fmt::Debug, marker::PhantomData, ops::RangeFrom, slice::SliceIndex};
+
+/// See [`from_fn`] and [`from_fn_unchecked`].
+pub struct FromFn<F, Text: ?Sized, State, Heap> {
+  f: F,
+  checked: bool,
+  #[allow(clippy::type_complexity)]
+  _phantom: PhantomData<fn(&Text, State, Heap)>,
+}
+
+impl<F, Text: ?Sized, State, Heap> FromFn<F, Text, State, Heap> {
+  #[inline]
+  const fn new(f: F, checked: bool) -> Self {
+    Self {
+      f,
+      checked,
+      _phantom: PhantomData,
+    }
+  }
+}
+
+impl<F, Text: ?Sized, State, Heap> Debug for FromFn<F, Text, State, Heap> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("FromFn")
+      .field("checked", &self.checked)
+      .finish()
+  }
+}
+
+// implement manually to avoid the `Text: Clone` bound
+impl<F: Clone, Text: ?Sized, State, Heap> Clone for FromFn<F, Text, State, Heap> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self::new(self.f.clone(), self.checked)
+  }
+}
+
+impl<F: Copy, Text: ?Sized, State, Heap> Copy for FromFn<F, Text, State, Heap> {}
+
+unsafe impl<
+    Text: ?Sized + Digest,
+    State,
+    Heap,
+    Value,
+    F: Fn(Input<&Instant<&Text>, &mut State, &mut Heap>) -> Option<Output<Value>>,
+  > Action for FromFn<F, Text, State, Heap>
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  type Text = Text;
+  type State = State;
+  type Heap = Heap;
+  type Value = Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let instant = input.instant;
+    let output = (self.f)(input);
+    if let Some(output) = &output {
+      if self.checked {
+        assert!(
+          instant.rest().validate(output.digested),
+          "invalid digested length {} for the rest with length {}",
+          output.digested,
+          instant.rest().len()
+        );
+      } else {
+        debug_assert!(
+          instant.rest().validate(output.digested),
+          "invalid digested length {} for the rest with length {}",
+          output.digested,
+          instant.rest().len()
+        );
+      }
+    }
+    output
+  }
+}
+
+/// Create a [`Combinator`] from a closure or function with any `Text`, `State` and `Heap`.
+///
+/// Unlike [`wrap`](crate::combinator::wrap), this is not bound to a `Text`, `State` or `Heap`,
+/// so it can be used outside of [`contextual!`](crate::contextual) without wrappers.
+/// The types are inferred from the closure parameter,
+/// annotate it if the inference fails.
+/// # Panics
+/// The returned [`Output`] should satisfy the requirement of [`Output::digested`],
+/// otherwise the combinator will panic when executed.
+/// For the unchecked version, see [`from_fn_unchecked`].
+/// # Examples
+/// ```
+/// # use whitehole::{action::Input, combinator::from_fn, instant::Instant, parser::Parser};
+/// // a length-prefixed field
+/// let field = from_fn(|input: Input<&Instant<&[u8]>, &mut (), &mut ()>| {
+///   let len = *input.instant.rest().first()? as usize;
+///   let content = input.instant.rest().get(1..1 + len)?.to_vec();
+///   input.instant.accept(1 + len).map(|output| output.map(|_| content))
+/// });
+/// let output = Parser::builder().entry(field).build(b"\x02abc" as &[u8]).next().unwrap();
+/// assert_eq!(output.value, b"ab");
+/// assert_eq!(output.digested, 3);
+/// ```
+#[inline]
+pub const fn from_fn<
+  Text: ?Sized,
+  State,
+  Heap,
+  Value,
+  F: Fn(Input<&Instant<&Text>, &mut State, &mut Heap>) -> Option<Output<Value>>,
+>(
+  f: F,
+) -> Combinator<FromFn<F, Text, State, Heap>> {
+  Combinator::new(FromFn::new(f, true))
+}
+
+/// Create a [`Combinator`] from a closure or function with any `Text`, `State` and `Heap`.
+///
+/// See [`from_fn`] for more details.
+/// # Safety
+/// The returned [`Output`] should satisfy the requirement of [`Output::digested`].
+/// This will be checked using [`debug_assert!`].
+/// # Examples
+/// ```
+/// # use whitehole::{action::Input, combinator::from_fn_unchecked, instant::Instant};
+/// // accept with 0 bytes digested
+/// let c = unsafe { from_fn_unchecked(|input: Input<&Instant<&str>, &mut (), &mut ()>| input.instant.accept(0)) };
+/// ```
+#[inline]
+pub const unsafe fn from_fn_unchecked<
+  Text: ?Sized,
+  State,
+  Heap,
+  Value,
+  F: Fn(Input<&Instant<&Text>, &mut State, &mut Heap>) -> Option<Output<Value>>,
+>(
+  f: F,
+) -> Combinator<FromFn<F, Text, State, Heap>> {
+  Combinator::new(FromFn::new(f, false))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::bytes;
+
+  fn exec<Text: ?Sized + Digest, Value>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+  ) -> Option<(Value, usize)>
+  where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  fn field(
+  ) -> Combinator<impl Action<Text = [u8], State = (), Heap = (), Value = Vec<u8>> + Copy + Debug>
+  {
+    from_fn(|input: Input<&Instant<&[u8]>, &mut (), &mut ()>| {
+      let len = *input.instant.rest().first()? as usize;
+      let content = input.instant.rest().get(1..1 + len)?.to_vec();
+      input
+        .instant
+        .accept(1 + len)
+        .map(|output| output.map(|_| content))
+    })
+  }
+
+  #[test]
+  fn combinator_from_fn() {
+    // length-prefixed fields
+    assert_eq!(exec(field(), b"\x03abcd"), Some((b"abc".to_vec(), 4)));
+    assert_eq!(exec(field(), b"\x00abc"), Some((vec![], 1)));
+    assert_eq!(exec(field(), b"\x04abc"), None);
+    assert_eq!(exec(field(), b""), None);
+    // with other combinators
+    assert_eq!(
+      exec(
+        field().tuple() + bytes::eat(b';') + field().tuple(),
+        b"\x01a;\x02bc"
+      ),
+      Some(((b"a".to_vec(), b"bc".to_vec()), 6))
+    );
+    assert_eq!(
+      exec(
+        (field() * (..)).fold(Vec::new, |mut acc, v| {
+          acc.push(v);
+          acc
+        }),
+        b"\x01a\x02bc\x05"
+      ),
+      Some((vec![b"a".to_vec(), b"bc".to_vec()], 5))
+    );
+
+    // str
+    let word = || {
+      from_fn(|input: Input<&Instant<&str>, &mut (), &mut ()>| {
+        let len = input
+          .instant
+          .rest()
+          .find(|c: char| !c.is_alphabetic())
+          .unwrap_or(input.instant.rest().len());
+        if len == 0 {
+          return None;
+        }
+        input.instant.accept(len)
+      })
+    };
+    assert_eq!(exec(word(), "好a b"), Some(((), 4)));
+    assert_eq!(exec(word(), " b"), None);
+
+    // with state and heap
+    let c = from_fn(
+      |input: Input<&Instant<&str>, &mut usize, &mut Vec<usize>>| {
+        *input.state += 1;
+        input.heap.push(*input.state);
+        input.instant.accept(0)
+      },
+    );
+    let mut state = 0;
+    let mut heap = vec![];
+    for _ in 0..2 {
+      c.exec(Input {
+        instant: &Instant::new(""),
+        state: &mut state,
+        heap: &mut heap,
+      });
+    }
+    assert_eq!(state, 2);
+    assert_eq!(heap, vec![1, 2]);
+  }
+
+  #[test]
+  fn combinator_from_fn_unchecked() {
+    let c = unsafe {
+      from_fn_unchecked(|input: Input<&Instant<&str>, &mut (), &mut ()>| input.instant.accept(1))
+    };
+    assert_eq!(exec(c, "ab"), Some(((), 1)));
+  }
+
+  #[test]
+  #[should_panic(expected = "invalid digested length 4 for the rest with length 3")]
+  fn combinator_from_fn_overflow() {
+    let c = from_fn(|input: Input<&Instant<&[u8]>, &mut (), &mut ()>| {
+      Some(Output {
+        value: (),
+        digested: input.instant.rest().len() + 1,
+      })
+    });
+    exec(c, b"abc");
+  }
+
+  #[test]
+  #[should_panic(expected = "invalid digested length 1")]
+  fn combinator_from_fn_invalid_boundary() {
+    let c = from_fn(|_: Input<&Instant<&str>, &mut (), &mut ()>| {
+      Some(Output {
+        value: (),
+        digested: 1,
+      })
+    });
+    exec(c, "好");
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "invalid digested length 4 for the rest with length 3")]
+  fn combinator_from_fn_unchecked_overflow() {
+    let c = unsafe {
+      from_fn_unchecked(|input: Input<&Instant<&[u8]>, &mut (), &mut ()>| {
+        Some(Output {
+          value: (),
+          digested: input.instant.rest().len() + 1,
+        })
+      })
+    };
+    exec(c, b"abc");
+  }
+
+  fn _from_fn_debug_clone_copy() {
+    let c = field();
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,132 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, Combinator, Describe},
+  idx,
+  instant::Instant,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// See [`take_graphemes`].
+#[derive(Copy, Clone, Debug)]
+pub struct TakeGraphemes {
+  n: usize,
+}
+
+impl TakeGraphemes {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+impl Describe for TakeGraphemes {
+  fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write_head(f, "TakeGraphemes", module_path!())?;
+    write!(f, " {})", self.n)
+  }
+}
+
+unsafe impl Action for TakeGraphemes {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut digested: usize = 0;
+    let mut graphemes = input.instant.rest().graphemes(true);
+    for _ in 0..self.n {
+      // no enough graphemes, reject
+      // SAFETY: the graphemes are inside the rest, whose length is a valid usize
+      digested = idx::unchecked!(idx::add(digested, graphemes.next()?.len()));
+    }
+    // SAFETY: grapheme clusters end on char boundaries
+    unsafe { input.instant.accept_unchecked(digested) }.into()
+  }
+}
+
+/// Returns a combinator to take the next `n` undigested extended grapheme clusters,
+/// as defined by [UAX #29](https://www.unicode.org/reports/tr29/).
+///
+/// A grapheme cluster is what a user perceives as a single character,
+/// e.g. `"e\u{301}"` (`e` with a combining accent) or an emoji joined by ZWJ (`"👨‍👩‍👧"`),
+/// which may consist of multiple [`char`]s.
+/// The combinator will reject if there are not enough grapheme clusters.
+///
+/// To count [`char`]s, use [`take`](super::take).
+/// To count bytes, use [`take_bytes`](super::take_bytes).
+/// On pure ASCII text, all of them digest the same bytes, except for `"\r\n"`
+/// which is a single grapheme cluster.
+///
+/// `0` is allowed but be careful with infinite loops.
+/// This requires the `unicode-segmentation` feature.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_graphemes, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// take_graphemes(10) // take 10 user-perceived characters
+/// # );
+/// ```
+#[inline]
+pub const fn take_graphemes(n: usize) -> Combinator<TakeGraphemes> {
+  Combinator::new(TakeGraphemes::new(n))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{take, take_bytes};
+
+  fn helper(action: impl Action<Text = str, State = (), Heap = ()>, input: &str) -> Option<usize> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested)
+  }
+
+  #[test]
+  fn test_take_graphemes() {
+    // a family emoji joined by ZWJ is a single grapheme cluster of 5 chars
+    let family = "👨\u{200d}👩\u{200d}👧";
+    assert_eq!(family.chars().count(), 5);
+    assert_eq!(helper(take_graphemes(1), family), Some(family.len()));
+    assert_eq!(helper(take(1), family), Some(4));
+    // the boundary is after the whole cluster
+    let text = format!("{}{}a", family, family);
+    assert_eq!(helper(take_graphemes(2), &text), Some(family.len() * 2));
+    assert_eq!(helper(take_graphemes(3), &text), Some(text.len()));
+    // combining marks
+    assert_eq!(helper(take_graphemes(1), "e\u{301}x"), Some(3));
+    // reject
+    assert_eq!(helper(take_graphemes(4), &text), None);
+    assert_eq!(helper(take_graphemes(1), ""), None);
+    // 0 is always accepted
+    assert_eq!(helper(take_graphemes(0), ""), Some(0));
+    // `\r\n` is a single grapheme cluster
+    assert_eq!(helper(take_graphemes(1), "\r\n"), Some(2));
+
+    // debug
+    let _ = format!("{:?}", take_graphemes(1));
+    assert_eq!(take_graphemes(2).describe(), "(take_graphemes 2)");
+  }
+
+  #[test]
+  fn take_units_on_ascii() {
+    let text = "hello, world";
+    for n in 0..=text.len() + 1 {
+      let expected = (n <= text.len()).then_some(n);
+      assert_eq!(helper(take(n), text), expected);
+      assert_eq!(helper(take_bytes(n), text), expected);
+      assert_eq!(helper(take_graphemes(n), text), expected);
+    }
+  }
+}
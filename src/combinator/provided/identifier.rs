@@ -0,0 +1,349 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, Combinator, Contextual, Describe},
+  idx,
+  instant::Instant,
+};
+use std::fmt;
+
+/// Check if `c` can start an identifier.
+///
+/// With the `unicode-ident` feature, this is the Unicode `XID_Start` property
+/// ([UAX #31](https://www.unicode.org/reports/tr31/)) or `_`.
+/// Otherwise, this is ASCII alphabetic or `_`.
+#[inline]
+pub fn is_ident_start(c: char) -> bool {
+  #[cfg(feature = "unicode-ident")]
+  {
+    c == '_' || unicode_ident::is_xid_start(c)
+  }
+  #[cfg(not(feature = "unicode-ident"))]
+  {
+    c == '_' || c.is_ascii_alphabetic()
+  }
+}
+
+/// Check if `c` can continue an identifier.
+///
+/// With the `unicode-ident` feature, this is the Unicode `XID_Continue` property
+/// ([UAX #31](https://www.unicode.org/reports/tr31/)), which includes `_`.
+/// Otherwise, this is ASCII alphanumeric or `_`.
+#[inline]
+pub fn is_ident_continue(c: char) -> bool {
+  #[cfg(feature = "unicode-ident")]
+  {
+    unicode_ident::is_xid_continue(c)
+  }
+  #[cfg(not(feature = "unicode-ident"))]
+  {
+    c == '_' || c.is_ascii_alphanumeric()
+  }
+}
+
+/// See [`identifier`].
+#[derive(Copy, Clone)]
+pub struct Identifier<'a, S = fn(char) -> bool, C = fn(char) -> bool> {
+  start: S,
+  cont: C,
+  except: &'a [&'a str],
+}
+
+impl Identifier<'_> {
+  /// Create a new instance with [`is_ident_start`], [`is_ident_continue`] and no keywords.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      start: is_ident_start,
+      cont: is_ident_continue,
+      except: &[],
+    }
+  }
+}
+
+impl Default for Identifier<'_> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<'a, S, C> Identifier<'a, S, C> {
+  #[inline]
+  fn with_start<NewS>(self, start: NewS) -> Identifier<'a, NewS, C> {
+    Identifier {
+      start,
+      cont: self.cont,
+      except: self.except,
+    }
+  }
+
+  #[inline]
+  fn with_cont<NewC>(self, cont: NewC) -> Identifier<'a, S, NewC> {
+    Identifier {
+      start: self.start,
+      cont,
+      except: self.except,
+    }
+  }
+
+  #[inline]
+  fn with_except<'b>(self, except: &'b [&'b str]) -> Identifier<'b, S, C> {
+    Identifier {
+      start: self.start,
+      cont: self.cont,
+      except,
+    }
+  }
+}
+
+impl<S, C> fmt::Debug for Identifier<'_, S, C> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Identifier")
+      .field("except", &self.except)
+      .finish()
+  }
+}
+
+impl<S, C> Describe for Identifier<'_, S, C> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "Identifier", module_path!())?;
+    if !self.except.is_empty() {
+      write!(f, " {:?}", self.except)?;
+    }
+    f.write_str(")")
+  }
+}
+
+unsafe impl<S: Fn(char) -> bool, C: Fn(char) -> bool> Action for Identifier<'_, S, C> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let mut chars = rest.char_indices();
+    if !(self.start)(chars.next()?.1) {
+      return None;
+    }
+    let digested = chars
+      .find(|&(_, c)| !(self.cont)(c))
+      .map_or(rest.len(), |(i, _)| i);
+    // exact match, so a keyword prefix like `letter` is still an identifier
+    // SAFETY: `digested` is a char boundary from `char_indices` or the length of `rest`
+    if self
+      .except
+      .contains(&idx::unchecked!(idx::slice_to(rest, digested)))
+    {
+      return None;
+    }
+    // SAFETY: `digested` is a char boundary
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+impl<'a, S, C> Combinator<Identifier<'a, S, C>> {
+  /// Set the predicate of the first char, [`is_ident_start`] by default.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{identifier, is_ident_start, Combinator}, action::Action};
+  /// # fn t(_: Combinator<impl Action<Text = str>>) {}
+  /// # t(
+  /// // allow `$` like JavaScript
+  /// identifier().start(|c| c == '$' || is_ident_start(c))
+  /// # );
+  /// ```
+  #[inline]
+  pub fn start<NewS: Fn(char) -> bool>(self, start: NewS) -> Combinator<Identifier<'a, NewS, C>> {
+    Combinator::new(self.action.with_start(start))
+  }
+
+  /// Set the predicate of the rest chars, [`is_ident_continue`] by default.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{identifier, is_ident_continue, Combinator}, action::Action};
+  /// # fn t(_: Combinator<impl Action<Text = str>>) {}
+  /// # t(
+  /// // allow `-` like CSS
+  /// identifier().cont(|c| c == '-' || is_ident_continue(c))
+  /// # );
+  /// ```
+  #[inline]
+  pub fn cont<NewC: Fn(char) -> bool>(self, cont: NewC) -> Combinator<Identifier<'a, S, NewC>> {
+    Combinator::new(self.action.with_cont(cont))
+  }
+
+  /// Reject if the whole identifier exactly matches one of the `keywords`.
+  /// Identifiers which only start with a keyword (e.g. `letter` for `let`) are accepted.
+  ///
+  /// This is a faster version of
+  /// `identifier().reject(|accepted| keywords.contains(&accepted.content()))`.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{identifier, Combinator}, action::Action};
+  /// # fn t(_: Combinator<impl Action<Text = str>>) {}
+  /// # t(
+  /// identifier().except(&["let", "fn"])
+  /// # );
+  /// ```
+  #[inline]
+  pub fn except<'b>(self, keywords: &'b [&'b str]) -> Combinator<Identifier<'b, S, C>> {
+    Combinator::new(self.action.with_except(keywords))
+  }
+}
+
+impl<'a, S, C, State, Heap> Combinator<Contextual<Identifier<'a, S, C>, State, Heap>> {
+  /// See [`Combinator::<Identifier>::start`].
+  #[inline]
+  pub fn start<NewS: Fn(char) -> bool>(
+    self,
+    start: NewS,
+  ) -> Combinator<Contextual<Identifier<'a, NewS, C>, State, Heap>> {
+    Combinator::new(Contextual::new(self.action.action.with_start(start)))
+  }
+
+  /// See [`Combinator::<Identifier>::cont`].
+  #[inline]
+  pub fn cont<NewC: Fn(char) -> bool>(
+    self,
+    cont: NewC,
+  ) -> Combinator<Contextual<Identifier<'a, S, NewC>, State, Heap>> {
+    Combinator::new(Contextual::new(self.action.action.with_cont(cont)))
+  }
+
+  /// See [`Combinator::<Identifier>::except`].
+  #[inline]
+  pub fn except<'b>(
+    self,
+    keywords: &'b [&'b str],
+  ) -> Combinator<Contextual<Identifier<'b, S, C>, State, Heap>> {
+    Combinator::new(Contextual::new(self.action.action.with_except(keywords)))
+  }
+}
+
+/// Returns a combinator to eat an identifier.
+///
+/// The first char must satisfy [`is_ident_start`] and the rest chars [`is_ident_continue`],
+/// which are Unicode `XID_Start`/`XID_Continue` (plus `_`) with the `unicode-ident` feature,
+/// or ASCII alphanumeric (plus `_`) without it.
+/// Use [`Combinator::start`] and [`Combinator::cont`] to customize them,
+/// and [`Combinator::except`] to exclude keywords.
+///
+/// The value is `()`, use [`Combinator::recognize`] to get the identifier as a [`String`],
+/// or [`Combinator::range`] to index the text later.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::identifier, parser::Parser};
+/// let entry = identifier().except(&["let", "fn"]).recognize();
+/// let parse = |s| Parser::builder().entry(&entry).build(s).next().map(|o| o.value);
+/// assert_eq!(parse("_foo1 = 1"), Some("_foo1".to_string()));
+/// assert_eq!(parse("letter"), Some("letter".to_string()));
+/// assert_eq!(parse("let"), None);
+/// assert_eq!(parse("1foo"), None);
+/// ```
+#[inline]
+pub const fn identifier() -> Combinator<Identifier<'static>> {
+  Combinator::new(Identifier::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(action: impl Action<Text = str, State = (), Heap = ()>, input: &str) -> Option<usize> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested)
+  }
+
+  #[test]
+  fn combinator_identifier() {
+    assert_eq!(helper(identifier(), "abc def"), Some(3));
+    assert_eq!(helper(identifier(), "a1_b2+"), Some(5));
+    // leading digit
+    assert_eq!(helper(identifier(), "1abc"), None);
+    assert_eq!(helper(identifier(), "+"), None);
+    assert_eq!(helper(identifier(), ""), None);
+    // underscore
+    assert_eq!(helper(identifier(), "_"), Some(1));
+    assert_eq!(helper(identifier(), "__init__()"), Some(8));
+    assert_eq!(helper(identifier(), "_1"), Some(2));
+    // to the end
+    assert_eq!(helper(identifier(), "abc"), Some(3));
+  }
+
+  #[test]
+  fn combinator_identifier_except() {
+    let keywords = ["let", "fn", "f"];
+    let c = || identifier().except(&keywords);
+    // exact match
+    assert_eq!(helper(c(), "let"), None);
+    assert_eq!(helper(c(), "let x"), None);
+    assert_eq!(helper(c(), "fn("), None);
+    assert_eq!(helper(c(), "f"), None);
+    // prefix
+    assert_eq!(helper(c(), "letter"), Some(6));
+    assert_eq!(helper(c(), "fnx"), Some(3));
+    assert_eq!(helper(c(), "le"), Some(2));
+    assert_eq!(helper(c(), "_let"), Some(4));
+    // case sensitive
+    assert_eq!(helper(c(), "Let"), Some(3));
+    // an empty list excludes nothing
+    assert_eq!(helper(identifier().except(&[]), "let"), Some(3));
+  }
+
+  #[test]
+  fn combinator_identifier_custom() {
+    let c = identifier()
+      .start(|c| c == '$' || is_ident_start(c))
+      .cont(|c| c == '-' || is_ident_continue(c))
+      .except(&["$"]);
+    assert_eq!(helper(c, "$foo-bar baz"), Some(8));
+    assert_eq!(helper(c, "$ "), None);
+    assert_eq!(helper(c, "-a"), None);
+
+    // non-ascii with custom predicates
+    let c = identifier()
+      .start(char::is_alphabetic)
+      .cont(char::is_alphanumeric);
+    assert_eq!(helper(c, "变量1 = 1"), Some(7));
+    assert_eq!(helper(c, "_a"), None);
+  }
+
+  #[cfg(feature = "unicode-ident")]
+  #[test]
+  fn combinator_identifier_unicode() {
+    assert_eq!(helper(identifier(), "变量1 = 1"), Some(7));
+    assert_eq!(helper(identifier(), "café!"), Some(5));
+    assert_eq!(helper(identifier(), "αβγ"), Some(6));
+    // combining marks can continue but not start
+    assert_eq!(helper(identifier(), "e\u{301}"), Some(3));
+    assert_eq!(helper(identifier(), "\u{301}e"), None);
+    // not XID
+    assert_eq!(helper(identifier(), "😀"), None);
+    assert_eq!(helper(identifier(), "a😀"), Some(1));
+  }
+
+  #[cfg(not(feature = "unicode-ident"))]
+  #[test]
+  fn combinator_identifier_ascii() {
+    assert_eq!(helper(identifier(), "变量"), None);
+    assert_eq!(helper(identifier(), "caf\u{e9}"), Some(3));
+  }
+
+  #[test]
+  fn combinator_identifier_debug() {
+    assert_eq!(
+      format!("{:?}", identifier().except(&["let"])),
+      r#"Combinator { action: Identifier { except: ["let"] } }"#
+    );
+  }
+}
@@ -0,0 +1,361 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// See [`json_string`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct JsonString;
+
+impl JsonString {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+/// Parse 4 hex digits.
+#[inline]
+fn hex4(digits: &[u8]) -> Option<u32> {
+  digits
+    .iter()
+    .try_fold(0, |acc, b| Some((acc << 4) | (*b as char).to_digit(16)?))
+}
+
+unsafe impl Action for JsonString {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = String;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let bytes = rest.as_bytes();
+    if bytes.first() != Some(&b'"') {
+      return None;
+    }
+
+    let mut value = String::new();
+    let mut i = 1;
+    // the start of the current run of unescaped chars
+    let mut run = 1;
+    loop {
+      // reject unterminated strings
+      match *bytes.get(i)? {
+        b'"' => {
+          value.push_str(&rest[run..i]);
+          i += 1;
+          break;
+        }
+        b'\\' => {
+          value.push_str(&rest[run..i]);
+          let escaped = *bytes.get(i + 1)?;
+          i += 2;
+          value.push(match escaped {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+              let high = hex4(bytes.get(i..i + 4)?)?;
+              i += 4;
+              match high {
+                0xD800..=0xDBFF => {
+                  // a high surrogate must be followed by a low surrogate
+                  if bytes.get(i..i + 2)? != b"\\u" {
+                    return None;
+                  }
+                  let low = hex4(bytes.get(i + 2..i + 6)?)?;
+                  if !(0xDC00..=0xDFFF).contains(&low) {
+                    return None;
+                  }
+                  i += 6;
+                  char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))?
+                }
+                // lone low surrogate
+                0xDC00..=0xDFFF => return None,
+                _ => char::from_u32(high)?,
+              }
+            }
+            _ => return None,
+          });
+          run = i;
+        }
+        // control chars must be escaped
+        0x00..=0x1F => return None,
+        _ => i += 1,
+      }
+    }
+
+    // SAFETY: `i` is right after the closing '"' which is ascii
+    Some(unsafe { input.instant.accept_unchecked(i) }.map(|_| value))
+  }
+}
+
+/// Returns a combinator to match a JSON string literal (RFC 8259)
+/// and yield the decoded [`String`] as the value.
+///
+/// The double quotes are digested but not included in the value.
+/// Escapes `\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`, `\t` and `\uXXXX` are decoded,
+/// and UTF-16 surrogate pairs (e.g. `\ud83d\ude00`) are combined into one char.
+///
+/// The combinator will reject if:
+/// - the rest doesn't start with `"`,
+/// - the string is unterminated,
+/// - an escape is invalid, including lone surrogates,
+/// - or there is an unescaped control char (`U+0000` to `U+001F`).
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::json_string, parser::Parser};
+/// let output = Parser::builder().entry(json_string()).build(r#""a\n好\ud83d\ude00""#).next().unwrap();
+/// assert_eq!(output.value, "a\n好😀");
+/// assert_eq!(output.digested, 20);
+/// ```
+#[inline]
+pub const fn json_string() -> Combinator<JsonString> {
+  Combinator::new(JsonString::new())
+}
+
+/// See [`json_number`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct JsonNumber;
+
+impl JsonNumber {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+/// Return the index after the ascii digits starting from `i`.
+#[inline]
+fn skip_digits(bytes: &[u8], mut i: usize) -> usize {
+  while matches!(bytes.get(i), Some(b'0'..=b'9')) {
+    i += 1;
+  }
+  i
+}
+
+unsafe impl Action for JsonNumber {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = f64;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let bytes = rest.as_bytes();
+
+    let mut i = (bytes.first() == Some(&b'-')) as usize;
+    // integer
+    i = match bytes.get(i) {
+      Some(b'0') => i + 1,
+      Some(b'1'..=b'9') => skip_digits(bytes, i + 1),
+      _ => return None,
+    };
+    // fraction
+    if bytes.get(i) == Some(&b'.') && matches!(bytes.get(i + 1), Some(b'0'..=b'9')) {
+      i = skip_digits(bytes, i + 2);
+    }
+    // exponent
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+      let mut j = i + 1;
+      if matches!(bytes.get(j), Some(b'+' | b'-')) {
+        j += 1;
+      }
+      if matches!(bytes.get(j), Some(b'0'..=b'9')) {
+        i = skip_digits(bytes, j + 1);
+      }
+    }
+
+    // the grammar is a subset of what `f64::from_str` accepts
+    let value = rest[..i].parse().ok()?;
+    // SAFETY: `i` is after ascii bytes
+    Some(unsafe { input.instant.accept_unchecked(i) }.map(|_| value))
+  }
+}
+
+/// Returns a combinator to match a JSON number literal (RFC 8259)
+/// and yield the value as [`f64`].
+///
+/// The grammar is `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`.
+/// The longest valid prefix is digested, e.g. only `0` is digested in `01`,
+/// and only `1` is digested in `1.` or `1e`.
+/// The combinator will reject if the rest doesn't start with a valid number.
+///
+/// Numbers out of the range of [`f64`] are converted to infinity (e.g. `1e400`)
+/// or zero (e.g. `1e-400`), and precision may be lost for large integers.
+/// Use [`Combinator::range`] to get the original text if you need more precision.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::json_number, parser::Parser};
+/// let output = Parser::builder().entry(json_number()).build("-1.5e3,").next().unwrap();
+/// assert_eq!(output.value, -1500.0);
+/// assert_eq!(output.digested, 6);
+/// ```
+#[inline]
+pub const fn json_number() -> Combinator<JsonNumber> {
+  Combinator::new(JsonNumber::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fmt::Debug;
+
+  fn helper<Value: PartialEq + Debug>(
+    action: impl Action<Text = str, State = (), Heap = (), Value = Value>,
+    input: &str,
+    expected: Option<(Value, usize)>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    );
+  }
+
+  fn string(input: &str, expected: Option<(&str, usize)>) {
+    helper(
+      json_string(),
+      input,
+      expected.map(|(v, d)| (v.to_string(), d)),
+    );
+  }
+
+  #[test]
+  fn combinator_json_string() {
+    string(r#""""#, Some(("", 2)));
+    string(r#""abc"x"#, Some(("abc", 5)));
+    string("\"好\"", Some(("好", 5)));
+    // simple escapes
+    string(r#""\"\\\/\b\f\n\r\t""#, Some(("\"\\/\u{8}\u{c}\n\r\t", 18)));
+    string(r#""a\nb""#, Some(("a\nb", 6)));
+    // unicode escapes
+    string(r#""\u0000""#, Some(("\0", 8)));
+    string(r#""\u0041\u00e9\u597d""#, Some(("Aé好", 20)));
+    string(r#""\uffff""#, Some(("\u{ffff}", 8)));
+    // surrogate pairs
+    string(r#""\ud83d\ude00""#, Some(("😀", 14)));
+    string(r#""\uD834\uDD1E""#, Some(("𝄞", 14)));
+    // DEL and non-ascii are allowed without escaping
+    string("\"\u{7f}\u{80}\"", Some(("\u{7f}\u{80}", 5)));
+  }
+
+  #[test]
+  fn combinator_json_string_reject() {
+    // not a string
+    string("abc", None);
+    string("", None);
+    string("'a'", None);
+    // unterminated
+    string(r#"""#, None);
+    string(r#""abc"#, None);
+    string(r#""\""#, None);
+    string(r#""\"#, None);
+    // invalid escapes
+    string(r#""\a""#, None);
+    string(r#""\U0041""#, None);
+    string(r#""\u004""#, None);
+    string(r#""\u00G1""#, None);
+    string(r#""\x41""#, None);
+    // lone surrogates
+    string(r#""\ud83d""#, None);
+    string(r#""\ud83dx""#, None);
+    string(r#""\ud83d\n""#, None);
+    string(r#""\ud83dA""#, None);
+    string(r#""\ud83d\ud83d""#, None);
+    string(r#""\ude00""#, None);
+    // unescaped control chars
+    string("\"\n\"", None);
+    string("\"\t\"", None);
+    string("\"\0\"", None);
+    string("\"\u{1f}\"", None);
+  }
+
+  #[test]
+  fn combinator_json_number() {
+    helper(json_number(), "0", Some((0.0, 1)));
+    helper(json_number(), "123", Some((123.0, 3)));
+    helper(json_number(), "-123.456", Some((-123.456, 8)));
+    helper(json_number(), "1e10", Some((1e10, 4)));
+    helper(json_number(), "1E+2", Some((100.0, 4)));
+    helper(json_number(), "1e-2", Some((0.01, 4)));
+    helper(json_number(), "0.5e1", Some((5.0, 5)));
+    helper(json_number(), "1e308", Some((1e308, 5)));
+    helper(
+      json_number(),
+      "-1.7976931348623157e308",
+      Some((f64::MIN, 23)),
+    );
+    // out of range
+    helper(json_number(), "1e400", Some((f64::INFINITY, 5)));
+    helper(json_number(), "-1e400", Some((f64::NEG_INFINITY, 6)));
+    helper(json_number(), "1e-400", Some((0.0, 6)));
+    // the longest valid prefix
+    helper(json_number(), "01", Some((0.0, 1)));
+    helper(json_number(), "-01", Some((-0.0, 2)));
+    helper(json_number(), "1.", Some((1.0, 1)));
+    helper(json_number(), "1.e1", Some((1.0, 1)));
+    helper(json_number(), "1e", Some((1.0, 1)));
+    helper(json_number(), "1e+", Some((1.0, 1)));
+    helper(json_number(), "1.5.5", Some((1.5, 3)));
+    helper(json_number(), "1,2", Some((1.0, 1)));
+    // reject
+    helper(json_number(), "", None);
+    helper(json_number(), "-", None);
+    helper(json_number(), "+1", None);
+    helper(json_number(), ".5", None);
+    helper(json_number(), "-.5", None);
+    helper(json_number(), "Infinity", None);
+    helper(json_number(), "NaN", None);
+  }
+
+  #[test]
+  fn combinator_json_number_negative_zero() {
+    let value = json_number()
+      .exec(Input {
+        instant: &Instant::new("-0"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap()
+      .value;
+    assert_eq!(value, 0.0);
+    assert!(value.is_sign_negative());
+  }
+
+  fn _json_debug() {
+    let _ = format!("{:?}", json_string());
+    let _ = format!("{:?}", json_number());
+  }
+
+  fn _json_clone_copy() {
+    let c = json_string();
+    let _c = c;
+    let _c = c.clone();
+    let c = json_number();
+    let _c = c;
+    let _c = c.clone();
+  }
+}
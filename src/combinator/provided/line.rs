@@ -0,0 +1,172 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// See [`line_end`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LineEnd;
+
+impl LineEnd {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for LineEnd {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest().as_bytes();
+    let digested = match rest {
+      [] => 0,
+      [b'\n', ..] => 1,
+      [b'\r', b'\n', ..] => 2,
+      _ => return None,
+    };
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to match the end of a line:
+/// `"\n"`, `"\r\n"` or the end of the input text.
+///
+/// The combinator will reject if none of them is matched.
+/// # Caveats
+/// This will accept 0 bytes when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, line_end, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// eat("[section]") + line_end()
+/// # );
+/// ```
+#[inline]
+pub const fn line_end() -> Combinator<LineEnd> {
+  Combinator::new(LineEnd::new())
+}
+
+/// See [`till_line_end`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TillLineEnd;
+
+impl TillLineEnd {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for TillLineEnd {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest().as_bytes();
+    let digested = match rest.iter().position(|b| *b == b'\n') {
+      Some(i) if i > 0 && rest[i - 1] == b'\r' => i - 1,
+      Some(i) => i,
+      None => rest.len(),
+    };
+    // SAFETY: '\r' and '\n' are ascii so `digested` is a valid char boundary
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to eat all the chars till the end of the current line,
+/// excluding the line ending (`"\n"` or `"\r\n"`).
+///
+/// The combinator will always accept, even with 0 bytes digested.
+/// Combine it with [`line_end`] to consume the line ending.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, line_end, till_line_end, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// // a comment line
+/// # t(
+/// eat('#') + till_line_end() + line_end()
+/// # );
+/// ```
+#[inline]
+pub const fn till_line_end() -> Combinator<TillLineEnd> {
+  Combinator::new(TillLineEnd::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{digest::Digest, instant::Instant};
+  use std::{ops::RangeFrom, slice::SliceIndex};
+
+  fn helper<Text: ?Sized + Digest>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = ()>,
+    input: &Text,
+    digested: Option<usize>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| o.digested),
+      digested
+    )
+  }
+
+  #[test]
+  fn combinator_line_end() {
+    helper(line_end(), "\n123", Some(1));
+    helper(line_end(), "\r\n123", Some(2));
+    helper(line_end(), "", Some(0));
+    helper(line_end(), "\r", None);
+    helper(line_end(), "123\n", None);
+  }
+
+  #[test]
+  fn combinator_till_line_end() {
+    helper(till_line_end(), "123\n456", Some(3));
+    helper(till_line_end(), "123\r\n456", Some(3));
+    helper(till_line_end(), "1\r2\n", Some(3));
+    helper(till_line_end(), "好", Some(3));
+    helper(till_line_end(), "\n", Some(0));
+    helper(till_line_end(), "", Some(0));
+    helper(till_line_end() + line_end(), "123\r\n", Some(5));
+  }
+
+  fn _line_debug() {
+    let _ = format!("{:?}", line_end());
+    let _ = format!("{:?}", till_line_end());
+  }
+
+  fn _line_clone_copy() {
+    let c = line_end();
+    let _c = c;
+    let _c = c.clone();
+    let c = till_line_end();
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,344 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, FirstSet, LookaheadSet},
+  instant::Instant,
+};
+
+/// See [`not`].
+#[derive(Debug, Clone, Copy)]
+pub struct NegativeLookahead<T> {
+  action: T,
+}
+
+impl<T> NegativeLookahead<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(action: T) -> Self {
+    Self { action }
+  }
+}
+
+/// Never digest.
+impl<T> FirstSet for NegativeLookahead<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::empty())
+  }
+}
+
+unsafe impl<T: Action<State: Clone>> Action for NegativeLookahead<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    // execute with a cloned state so modifications won't leak
+    let mut state = input.state.clone();
+    self
+      .action
+      .exec(Input {
+        instant: input.instant,
+        state: &mut state,
+        heap: input.heap,
+      })
+      .is_none()
+      .then_some(Output {
+        value: (),
+        digested: 0,
+      })
+  }
+}
+
+/// Returns a combinator to accept with `()` and 0 bytes digested
+/// if the provided combinator rejects, or reject if the provided combinator accepts
+/// (negative lookahead).
+///
+/// The provided combinator is executed with a clone of the state,
+/// so modifications to the state won't leak.
+/// The heap is shared and won't be reset, since it is designed for reusable allocations.
+///
+/// Unlike `!` (see [`ops::not`](crate::combinator::ops::not)),
+/// the value is always `()` and the state is isolated.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, next, not}, parser::Parser};
+/// let keyword = || eat("if").boundary();
+/// let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+/// // keywords won't be lexed as identifiers
+/// let entry = || not(keyword()) + ident();
+/// assert!(Parser::builder().entry(entry()).build("if").next().is_none());
+/// assert_eq!(Parser::builder().entry(entry()).build("iff").next().unwrap().digested, 3);
+/// ```
+#[inline]
+pub fn not<T>(combinator: impl Into<Combinator<T>>) -> Combinator<NegativeLookahead<T>> {
+  Combinator::new(NegativeLookahead::new(combinator.into().action))
+}
+
+/// See [`peek`].
+#[derive(Debug, Clone, Copy)]
+pub struct Peek<T> {
+  action: T,
+}
+
+impl<T> Peek<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(action: T) -> Self {
+    Self { action }
+  }
+}
+
+/// Never digest.
+impl<T> FirstSet for Peek<T> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(LookaheadSet::empty())
+  }
+}
+
+unsafe impl<T: Action<State: Clone>> Action for Peek<T> {
+  type Text = T::Text;
+  type State = T::State;
+  type Heap = T::Heap;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    // execute with a cloned state so modifications won't leak
+    let mut state = input.state.clone();
+    self
+      .action
+      .exec(Input {
+        instant: input.instant,
+        state: &mut state,
+        heap: input.heap,
+      })
+      .map(|output| Output {
+        value: output.value,
+        digested: 0,
+      })
+  }
+}
+
+/// Returns a combinator to accept with the value of the provided combinator
+/// and 0 bytes digested if the provided combinator accepts,
+/// or reject if the provided combinator rejects (positive lookahead).
+///
+/// The provided combinator is executed with a clone of the state,
+/// so modifications to the state won't leak.
+/// The heap is shared and won't be reset, since it is designed for reusable allocations.
+/// See also [`not`].
+/// # Caveats
+/// Be careful of infinite loops since this always accepts with 0 bytes digested,
+/// e.g. don't repeat it with `*` without an upper bound.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, next, peek}, parser::Parser};
+/// // a statement must be followed by `;` or `}`,
+/// // but the terminator belongs to the next rule
+/// let stmt = (next(|c| c.is_ascii_alphabetic()) * (1..)) + peek(eat(';') | eat('}'));
+/// assert_eq!(Parser::builder().entry(stmt).build("a}").next().unwrap().digested, 1);
+/// ```
+#[inline]
+pub fn peek<T>(combinator: impl Into<Combinator<T>>) -> Combinator<Peek<T>> {
+  Combinator::new(Peek::new(combinator.into().action))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, eat, next, take},
+    contextual,
+    digest::Digest,
+    range::WithRange,
+  };
+  use std::{ops::RangeFrom, slice::SliceIndex};
+
+  fn helper<Text: ?Sized + Digest, Value>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    digested: Option<usize>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| o.digested),
+      digested
+    )
+  }
+
+  #[test]
+  fn combinator_not() {
+    helper(not(eat('a')), "a", None);
+    helper(not(eat('a')), "b", Some(0));
+    helper(not(eat('a')), "", Some(0));
+    helper(not(take(0)), "", None);
+    helper(not(not(eat('a'))), "a", Some(0));
+    helper(not('a'), "b", Some(0));
+
+    helper(not(bytes::eat(b'a')), b"a" as &[u8], None);
+    helper(not(bytes::eat(b'a')), b"b" as &[u8], Some(0));
+    helper(not(b'a'), b"" as &[u8], Some(0));
+  }
+
+  #[test]
+  fn combinator_not_compose() {
+    let keyword = || eat("if").boundary() | eat("else").boundary();
+    let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+
+    // in `+`
+    helper(not(keyword()) + ident(), "if", None);
+    helper(not(keyword()) + ident(), "else", None);
+    helper(not(keyword()) + ident(), "iff", Some(3));
+    helper(not(keyword()) + ident(), "x", Some(1));
+
+    // in `|`
+    let token = || keyword().bind(true) | (not(keyword()) + ident()).bind(false);
+    helper(token(), "if", Some(2));
+    helper(token(), "elsex", Some(5));
+    helper(not(eat('a')) | eat('a'), "a", Some(1));
+
+    // in `*`
+    let till_semicolon = (not(';') + take(1)) * (..);
+    helper(till_semicolon, "abc;d", Some(3));
+    helper(till_semicolon, ";", Some(0));
+    helper(till_semicolon, "abc", Some(3));
+    let till_semicolon = (not(b';') + bytes::take(1)) * (..);
+    helper(till_semicolon, b"ab;" as &[u8], Some(2));
+  }
+
+  #[test]
+  fn combinator_not_state() {
+    contextual!(i32, Vec<i32>);
+    let probe = eat('a').prepare(|input| {
+      *input.state += 1;
+      input.heap.push(*input.state);
+    });
+    let mut state = 0;
+    let mut heap = vec![];
+    for text in ["a", "b"] {
+      not(probe).exec(Input {
+        instant: &Instant::new(text),
+        state: &mut state,
+        heap: &mut heap,
+      });
+    }
+    // state is isolated
+    assert_eq!(state, 0);
+    // heap is shared
+    assert_eq!(heap, [1, 1]);
+  }
+
+  #[test]
+  fn combinator_peek() {
+    helper(peek(eat('a')), "a", Some(0));
+    helper(peek(eat('a')), "b", None);
+    helper(peek(eat('a')), "", None);
+    helper(peek(take(0)), "", Some(0));
+    helper(peek('a') + eat("ab"), "ab", Some(2));
+    helper(peek('a') + eat("ab"), "b", None);
+
+    helper(peek(bytes::eat(b'a')), b"a" as &[u8], Some(0));
+    helper(peek(b'a'), b"b" as &[u8], None);
+    helper(peek(b'a') + bytes::eat(b"ab"), b"ab" as &[u8], Some(2));
+
+    // the value is kept
+    let output = peek(take(2).select(|a| a.content().to_string()))
+      .exec(Input {
+        instant: &Instant::new("abc"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(output.value, "ab");
+    assert_eq!(output.digested, 0);
+
+    // zero width range
+    let output = (eat('a') + peek(eat('b')).range().tuple())
+      .exec(Input {
+        instant: &Instant::new("ab"),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap();
+    assert_eq!(output.digested, 1);
+    assert_eq!(
+      output.value,
+      (WithRange {
+        data: (),
+        range: 1..1
+      },)
+    );
+  }
+
+  #[test]
+  fn combinator_peek_mul() {
+    // bounded repetition of a zero-width combinator stops
+    helper(peek(eat('a')) * 3, "a", Some(0));
+    helper(peek(eat('a')) * (..=3), "a", Some(0));
+    helper(peek(bytes::eat(b'a')) * (1..=3), b"a" as &[u8], Some(0));
+    // combined with digesting combinators
+    helper((peek(eat('a')) + take(1)) * (..), "aab", Some(2));
+    helper(
+      (peek(b'a') + bytes::take(1)) * (..),
+      b"aab" as &[u8],
+      Some(2),
+    );
+  }
+
+  #[test]
+  fn combinator_peek_state() {
+    contextual!(i32, Vec<i32>);
+    let probe = eat('a').prepare(|input| {
+      *input.state += 1;
+      input.heap.push(*input.state);
+    });
+    let mut state = 0;
+    let mut heap = vec![];
+    let output = peek(probe).exec(Input {
+      instant: &Instant::new("a"),
+      state: &mut state,
+      heap: &mut heap,
+    });
+    assert_eq!(output.map(|o| o.digested), Some(0));
+    // state is isolated
+    assert_eq!(state, 0);
+    // heap is shared
+    assert_eq!(heap, [1]);
+  }
+
+  fn _peek_debug() {
+    let _ = format!("{:?}", peek(eat('a')));
+  }
+
+  fn _peek_clone_copy() {
+    let c = peek(eat('a'));
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  fn _not_debug() {
+    let _ = format!("{:?}", not(eat('a')));
+  }
+
+  fn _not_clone_copy() {
+    let c = not(eat('a'));
+    let _c = c;
+    let _c = c.clone();
+  }
+}
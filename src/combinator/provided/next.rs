@@ -1,11 +1,25 @@
+use super::EatIn;
 use crate::{
   action::{Action, Input, Output},
-  combinator::{provided::create_closure_combinator, Combinator},
+  combinator::{
+    provided::create_closure_combinator, write_head, CharSet, Combinator, Describe, FirstSet,
+    LookaheadSet,
+  },
+  idx,
   instant::Instant,
 };
+use std::fmt;
 
 create_closure_combinator!(Next, "See [`next`].");
 
+/// The condition is opaque, so the set is unknown.
+impl<F> FirstSet for Next<F> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    None
+  }
+}
+
 unsafe impl<F: Fn(char) -> bool> Action for Next<F> {
   type Text = str;
   type State = ();
@@ -45,6 +59,210 @@ pub const fn next<F: Fn(char) -> bool>(condition: F) -> Combinator<Next<F>> {
   Combinator::new(Next::new(condition))
 }
 
+/// See [`next_n`].
+#[derive(Copy, Clone)]
+pub struct NextN<F> {
+  condition: F,
+  n: usize,
+}
+
+impl<F> NextN<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(condition: F, n: usize) -> Self {
+    Self { condition, n }
+  }
+}
+
+impl<F> fmt::Debug for NextN<F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("NextN").field("n", &self.n).finish()
+  }
+}
+
+impl<F> Describe for NextN<F> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "NextN", module_path!())?;
+    write!(f, " <fn> {})", self.n)
+  }
+}
+
+/// The condition is opaque, so the set is unknown unless `n` is `0`.
+impl<F> FirstSet for NextN<F> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    (self.n == 0).then(LookaheadSet::empty)
+  }
+}
+
+unsafe impl<F: Fn(char) -> bool> Action for NextN<F> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut chars = input.instant.rest().chars();
+    let mut digested = 0;
+    for _ in 0..self.n {
+      let next = chars.next()?;
+      if !(self.condition)(next) {
+        return None;
+      }
+      // SAFETY: the chars are inside the rest, whose length is a valid usize
+      digested = idx::unchecked!(idx::add(digested, next.len_utf8()));
+    }
+    // SAFETY: `digested` is the sum of the lengths of the matched chars,
+    // so it is a char boundary in the rest
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to match exactly `n` undigested [`char`]s by the condition.
+/// The combinator will reject at the first unmatched char, or if there are less than `n` chars.
+///
+/// This is faster than `next(condition) * n` since it scans in one loop
+/// without the repetition machinery.
+/// `n == 0` always accepts with nothing digested.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, next_n, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// // match a unicode escape like `\u00e9`
+/// eat("\\u") + next_n(|c| c.is_ascii_hexdigit(), 4)
+/// # );
+/// ```
+#[inline]
+pub const fn next_n<F: Fn(char) -> bool>(condition: F, n: usize) -> Combinator<NextN<F>> {
+  Combinator::new(NextN::new(condition, n))
+}
+
+/// Returns a combinator to eat one char if it is in the provided set,
+/// and take the char as the value.
+///
+/// This is the same as [`eat_in_char`](crate::combinator::eat_in_char),
+/// see [`none_of`] for the negation.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{one_of, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = char>>) {}
+/// # t(
+/// one_of("+-*/")
+/// # );
+/// ```
+#[inline]
+pub const fn one_of(set: &str) -> Combinator<EatIn<'_, char>> {
+  Combinator::new(EatIn::new(set))
+}
+
+/// See [`none_of`].
+#[derive(Copy, Clone)]
+pub struct NoneOf<'a> {
+  set: EatIn<'a, char>,
+}
+
+impl<'a> NoneOf<'a> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(set: &'a str) -> Self {
+    Self {
+      set: EatIn::new(set),
+    }
+  }
+}
+
+impl fmt::Debug for NoneOf<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("NoneOf")
+      .field("set", &self.set.set())
+      .finish()
+  }
+}
+
+impl Describe for NoneOf<'_> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "NoneOf", module_path!())?;
+    write!(f, " {:?})", self.set.set())
+  }
+}
+
+/// The complement of the set.
+impl FirstSet for NoneOf<'_> {
+  fn first_set(&self) -> Option<LookaheadSet> {
+    let mut excluded = self.set.set().chars().collect::<Vec<_>>();
+    excluded.sort_unstable();
+    excluded.dedup();
+
+    let mut chars = CharSet::new();
+    let mut start = Some('\0');
+    for c in excluded {
+      if let Some(s) = start.filter(|&s| s < c) {
+        // `c` is greater than `s`, so `c` is not `\0`, skip surrogates
+        let end = match c as u32 {
+          0xE000 => '\u{D7FF}',
+          n => char::from_u32(n - 1).unwrap(),
+        };
+        chars.insert_range(s..=end);
+      }
+      start = match c as u32 {
+        0xD7FF => Some('\u{E000}'),
+        n => char::from_u32(n + 1),
+      };
+    }
+    if let Some(s) = start {
+      chars.insert_range(s..=char::MAX);
+    }
+    Some(LookaheadSet::new(chars, false))
+  }
+}
+
+unsafe impl Action for NoneOf<'_> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = char;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let next = rest.chars().next()?;
+    if self.set.matches(rest).is_some() {
+      return None;
+    }
+    Some(Output {
+      value: next,
+      digested: next.len_utf8(),
+    })
+  }
+}
+
+/// Returns a combinator to eat one char if it is NOT in the provided set,
+/// and take the char as the value.
+/// The combinator will reject if the char is in the set or the rest is empty.
+///
+/// ASCII chars are checked by a bitmap, like [`eat_in`](crate::combinator::eat_in).
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{none_of, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = char>>) {}
+/// # t(
+/// // a char in a string literal which is not a quote or an escape
+/// none_of("\"\\")
+/// # );
+/// ```
+#[inline]
+pub const fn none_of(set: &str) -> Combinator<NoneOf<'_>> {
+  Combinator::new(NoneOf::new(set))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -88,6 +306,100 @@ mod tests {
     assert_eq!(format!("{:?}", c), "Combinator { action: Next }");
   }
 
+  #[test]
+  fn combinator_next_n() {
+    let hex = || next_n(|c| c.is_ascii_hexdigit(), 4);
+    helper(hex(), "12aF", Some(4));
+    helper(hex(), "12aF5", Some(4));
+    // reject at the third char without consuming
+    helper(hex(), "12g4", None);
+    // not enough chars
+    helper(hex(), "12a", None);
+    helper(hex(), "", None);
+    // multi-byte chars
+    helper(next_n(|c| !c.is_ascii(), 2), "好好好", Some(6));
+    helper(next_n(|c| !c.is_ascii(), 2), "é好a", Some(5));
+    helper(next_n(|c| !c.is_ascii(), 2), "好a", None);
+    // zero
+    helper(next_n(|_| false, 0), "", Some(0));
+    helper(next_n(|_| false, 0), "a", Some(0));
+
+    assert_eq!(
+      format!("{:?}", hex()),
+      "Combinator { action: NextN { n: 4 } }"
+    );
+  }
+
+  fn helper_char(
+    action: impl Action<Text = str, State = (), Heap = (), Value = char>,
+    input: &str,
+    expected: Option<(char, usize)>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    )
+  }
+
+  #[test]
+  fn combinator_one_of_none_of() {
+    helper_char(one_of("abc"), "b1", Some(('b', 1)));
+    helper_char(one_of("abc"), "d", None);
+    helper_char(one_of("a好"), "好", Some(('好', 3)));
+    helper_char(one_of("abc"), "", None);
+
+    let c = || none_of("\"\\");
+    helper_char(c(), "a\"", Some(('a', 1)));
+    helper_char(c(), "\"", None);
+    helper_char(c(), "\\", None);
+    // reject at EOF
+    helper_char(c(), "", None);
+    // multi-byte chars
+    helper_char(c(), "好\"", Some(('好', 3)));
+    helper_char(c(), "😀", Some(('😀', 4)));
+    helper_char(none_of("好"), "好", None);
+    helper_char(none_of("好"), "你", Some(('你', 3)));
+    // empty set accepts any char
+    helper_char(none_of(""), "a", Some(('a', 1)));
+
+    assert_eq!(
+      format!("{:?}", none_of("ab")),
+      r#"Combinator { action: NoneOf { set: "ab" } }"#
+    );
+  }
+
+  #[test]
+  fn first_set_none_of() {
+    let set = none_of("b\0好").first_set().unwrap();
+    assert!(!set.nullable);
+    assert!(set.chars.contains('a'));
+    assert!(set.chars.contains('c'));
+    assert!(!set.chars.contains('b'));
+    assert!(!set.chars.contains('\0'));
+    assert!(!set.chars.contains('好'));
+    assert!(set.chars.contains('你'));
+    assert!(set.chars.contains(char::MAX));
+
+    // surrogates and the max char
+    let set = none_of("\u{D7FF}\u{E000}").first_set().unwrap();
+    assert!(set.chars.contains('\u{D7FE}'));
+    assert!(!set.chars.contains('\u{D7FF}'));
+    assert!(!set.chars.contains('\u{E000}'));
+    assert!(set.chars.contains('\u{E001}'));
+    let set = none_of("\u{10FFFF}").first_set().unwrap();
+    assert!(!set.chars.contains(char::MAX));
+    assert!(none_of("").first_set().unwrap().chars.is_full());
+
+    assert_eq!(next_n(|_| true, 0).first_set(), Some(LookaheadSet::empty()));
+    assert_eq!(next_n(|_| true, 1).first_set(), None);
+  }
+
   #[test]
   fn one_or_more_next() {
     // normal
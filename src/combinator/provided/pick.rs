@@ -0,0 +1,696 @@
+use crate::{
+  action::{exec_by_cut, Action, Input, Output, Rejection},
+  combinator::Combinator,
+  digest::Digest,
+  idx,
+  instant::Instant,
+};
+use std::{ops::RangeFrom, slice::SliceIndex};
+
+/// See [`preceded`].
+#[derive(Copy, Clone, Debug)]
+pub struct Preceded<Prefix, Main> {
+  prefix: Prefix,
+  main: Main,
+}
+
+/// See [`terminated`].
+#[derive(Copy, Clone, Debug)]
+pub struct Terminated<Main, Suffix> {
+  main: Main,
+  suffix: Suffix,
+}
+
+/// See [`delimited`].
+#[derive(Copy, Clone, Debug)]
+pub struct Delimited<Open, Main, Close> {
+  open: Open,
+  main: Main,
+  close: Close,
+}
+
+/// See [`separated_pair`].
+#[derive(Copy, Clone, Debug)]
+pub struct SeparatedPair<A, Sep, B> {
+  a: A,
+  sep: Sep,
+  b: B,
+}
+
+/// See [`pair`].
+#[derive(Copy, Clone, Debug)]
+pub struct Pair<A, B> {
+  a: A,
+  b: B,
+}
+
+/// See [`padded`].
+#[derive(Copy, Clone, Debug)]
+pub struct Padded<Ws, Main> {
+  ws: Ws,
+  main: Main,
+}
+
+/// Execute `action` after `digested` bytes and accumulate the digested length.
+#[inline]
+fn exec_after<T: Action<Text: Digest>>(
+  action: &T,
+  digested: &mut usize,
+  input: &mut Input<&Instant<&T::Text>, &mut T::State, &mut T::Heap>,
+) -> Result<T::Value, Rejection>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  let output = action
+    .exec_cut(input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(*digested) }))?;
+  // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+  debug_assert!(usize::MAX - *digested >= output.digested);
+  *digested = idx::unchecked!(idx::add(*digested, output.digested));
+  Ok(output.value)
+}
+
+unsafe impl<
+    Prefix: Action<Text: Digest>,
+    Main: Action<Text = Prefix::Text, State = Prefix::State, Heap = Prefix::Heap>,
+  > Action for Preceded<Prefix, Main>
+where
+  RangeFrom<usize>: SliceIndex<Prefix::Text, Output = Prefix::Text>,
+{
+  type Text = Prefix::Text;
+  type State = Prefix::State;
+  type Heap = Prefix::Heap;
+  type Value = Main::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    exec_after(&self.prefix, &mut digested, &mut input)?;
+    let value = exec_after(&self.main, &mut digested, &mut input)?;
+    Ok(Output { value, digested })
+  }
+}
+
+unsafe impl<
+    Main: Action<Text: Digest>,
+    Suffix: Action<Text = Main::Text, State = Main::State, Heap = Main::Heap>,
+  > Action for Terminated<Main, Suffix>
+where
+  RangeFrom<usize>: SliceIndex<Main::Text, Output = Main::Text>,
+{
+  type Text = Main::Text;
+  type State = Main::State;
+  type Heap = Main::Heap;
+  type Value = Main::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    let value = exec_after(&self.main, &mut digested, &mut input)?;
+    exec_after(&self.suffix, &mut digested, &mut input)?;
+    Ok(Output { value, digested })
+  }
+}
+
+unsafe impl<
+    Open: Action<Text: Digest>,
+    Main: Action<Text = Open::Text, State = Open::State, Heap = Open::Heap>,
+    Close: Action<Text = Open::Text, State = Open::State, Heap = Open::Heap>,
+  > Action for Delimited<Open, Main, Close>
+where
+  RangeFrom<usize>: SliceIndex<Open::Text, Output = Open::Text>,
+{
+  type Text = Open::Text;
+  type State = Open::State;
+  type Heap = Open::Heap;
+  type Value = Main::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    exec_after(&self.open, &mut digested, &mut input)?;
+    let value = exec_after(&self.main, &mut digested, &mut input)?;
+    exec_after(&self.close, &mut digested, &mut input)?;
+    Ok(Output { value, digested })
+  }
+}
+
+unsafe impl<Ws: Action<Text: Digest>, Main: Action<Text = Ws::Text, State = Ws::State, Heap = Ws::Heap>>
+  Action for Padded<Ws, Main>
+where
+  RangeFrom<usize>: SliceIndex<Ws::Text, Output = Ws::Text>,
+{
+  type Text = Ws::Text;
+  type State = Ws::State;
+  type Heap = Ws::Heap;
+  type Value = Main::Value;
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    // the padding is optional, a rejected `ws` digests nothing
+    _ = exec_after(&self.ws, &mut digested, &mut input);
+    let value = exec_after(&self.main, &mut digested, &mut input)?;
+    _ = exec_after(&self.ws, &mut digested, &mut input);
+    Ok(Output { value, digested })
+  }
+}
+
+unsafe impl<
+    A: Action<Text: Digest>,
+    Sep: Action<Text = A::Text, State = A::State, Heap = A::Heap>,
+    B: Action<Text = A::Text, State = A::State, Heap = A::Heap>,
+  > Action for SeparatedPair<A, Sep, B>
+where
+  RangeFrom<usize>: SliceIndex<A::Text, Output = A::Text>,
+{
+  type Text = A::Text;
+  type State = A::State;
+  type Heap = A::Heap;
+  type Value = (A::Value, B::Value);
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    let a = exec_after(&self.a, &mut digested, &mut input)?;
+    exec_after(&self.sep, &mut digested, &mut input)?;
+    let b = exec_after(&self.b, &mut digested, &mut input)?;
+    Ok(Output {
+      value: (a, b),
+      digested,
+    })
+  }
+}
+
+unsafe impl<A: Action<Text: Digest>, B: Action<Text = A::Text, State = A::State, Heap = A::Heap>>
+  Action for Pair<A, B>
+where
+  RangeFrom<usize>: SliceIndex<A::Text, Output = A::Text>,
+{
+  type Text = A::Text;
+  type State = A::State;
+  type Heap = A::Heap;
+  type Value = (A::Value, B::Value);
+
+  exec_by_cut!();
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    let mut digested = 0;
+    let a = exec_after(&self.a, &mut digested, &mut input)?;
+    let b = exec_after(&self.b, &mut digested, &mut input)?;
+    Ok(Output {
+      value: (a, b),
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to execute `prefix` then `main`, keeping only the value of `main`.
+///
+/// Unlike `prefix + main`, the value of `prefix` is discarded
+/// and the value of `main` is never [`Concat`](crate::combinator::ops::add::Concat)-ed,
+/// so it doesn't need to be a tuple.
+///
+/// Tips: you can use [`char`], `&str`, [`String`], [`u8`], `&[u8]` and [`Vec<u8>`] as the shorthand
+/// for [`eat`](crate::combinator::eat) in all parts.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{preceded, next}, parser::Parser};
+/// let entry = preceded('$', next(|c| c.is_ascii_alphabetic()).select(|a| a.start()));
+/// assert_eq!(Parser::builder().entry(entry).build("$a").next().unwrap().value, 1);
+/// ```
+#[inline]
+pub fn preceded<Prefix, Main>(
+  prefix: impl Into<Combinator<Prefix>>,
+  main: impl Into<Combinator<Main>>,
+) -> Combinator<Preceded<Prefix, Main>> {
+  Combinator::new(Preceded {
+    prefix: prefix.into().action,
+    main: main.into().action,
+  })
+}
+
+/// Returns a combinator to execute `main` then `suffix`, keeping only the value of `main`.
+///
+/// See [`preceded`] for more details.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{terminated, next}, parser::Parser};
+/// let entry = terminated(next(|c| c.is_ascii_alphabetic()).select(|a| a.start()), ';');
+/// assert_eq!(Parser::builder().entry(entry).build("a;").next().unwrap().value, 0);
+/// ```
+#[inline]
+pub fn terminated<Main, Suffix>(
+  main: impl Into<Combinator<Main>>,
+  suffix: impl Into<Combinator<Suffix>>,
+) -> Combinator<Terminated<Main, Suffix>> {
+  Combinator::new(Terminated {
+    main: main.into().action,
+    suffix: suffix.into().action,
+  })
+}
+
+/// Returns a combinator to execute `open`, `main` then `close`,
+/// keeping only the value of `main`.
+///
+/// See [`preceded`] for more details.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{delimited, next}, parser::Parser};
+/// let entry = delimited('(', next(|c| c.is_ascii_digit()).select(|a| a.start()), ')');
+/// assert_eq!(Parser::builder().entry(entry).build("(1)").next().unwrap().value, 1);
+/// ```
+#[inline]
+pub fn delimited<Open, Main, Close>(
+  open: impl Into<Combinator<Open>>,
+  main: impl Into<Combinator<Main>>,
+  close: impl Into<Combinator<Close>>,
+) -> Combinator<Delimited<Open, Main, Close>> {
+  Combinator::new(Delimited {
+    open: open.into().action,
+    main: main.into().action,
+    close: close.into().action,
+  })
+}
+
+/// Returns a combinator to execute `a`, `sep` then `b`,
+/// yielding the values of `a` and `b` as a tuple `(A, B)`.
+///
+/// The value of `sep` is discarded,
+/// and the values of `a` and `b` are never [`Concat`](crate::combinator::ops::add::Concat)-ed,
+/// so they don't need to be tuples.
+///
+/// See [`preceded`] for more details.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{separated_pair, next}, parser::Parser};
+/// let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap());
+/// let entry = separated_pair(digit(), '=', digit());
+/// assert_eq!(Parser::builder().entry(entry).build("1=2").next().unwrap().value, (1, 2));
+/// ```
+#[inline]
+pub fn separated_pair<A, Sep, B>(
+  a: impl Into<Combinator<A>>,
+  sep: impl Into<Combinator<Sep>>,
+  b: impl Into<Combinator<B>>,
+) -> Combinator<SeparatedPair<A, Sep, B>> {
+  Combinator::new(SeparatedPair {
+    a: a.into().action,
+    sep: sep.into().action,
+    b: b.into().action,
+  })
+}
+
+/// Returns a combinator to execute `a` then `b`,
+/// yielding the values of `a` and `b` as a tuple `(A, B)`.
+///
+/// Unlike `a + b`, the values are never [`Concat`](crate::combinator::ops::add::Concat)-ed,
+/// so they don't need to be tuples, and tuple values are nested instead of flattened.
+/// See also [`Combinator::pair`].
+///
+/// See [`preceded`] for more details.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{pair, next}, parser::Parser};
+/// let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap());
+/// let entry = pair(digit(), digit());
+/// assert_eq!(Parser::builder().entry(entry).build("12").next().unwrap().value, (1, 2));
+/// ```
+#[inline]
+pub fn pair<A, B>(
+  a: impl Into<Combinator<A>>,
+  b: impl Into<Combinator<B>>,
+) -> Combinator<Pair<A, B>> {
+  Combinator::new(Pair {
+    a: a.into().action,
+    b: b.into().action,
+  })
+}
+
+/// Returns a combinator to execute `ws` optionally before and after `main`,
+/// keeping only the value of `main`.
+///
+/// The padding is digested, but a rejected `ws` is treated as zero-width padding,
+/// so `ws` should match one or more whitespaces (e.g. [`whitespaces`](crate::combinator::whitespaces))
+/// instead of being optional itself.
+/// This is useful for separators in whitespace-insensitive grammars,
+/// e.g. `(item * (1..)).sep(padded(whitespaces(), ','))`.
+///
+/// See [`preceded`] for more details.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{padded, next, whitespaces}, parser::Parser};
+/// let entry = (next(|c| c.is_ascii_digit()) * (1..)).sep(padded(whitespaces(), ','));
+/// assert_eq!(Parser::builder().entry(entry).build("1 , 2,\t3").next().unwrap().digested, 8);
+/// ```
+#[inline]
+pub fn padded<Ws, Main>(
+  ws: impl Into<Combinator<Ws>>,
+  main: impl Into<Combinator<Main>>,
+) -> Combinator<Padded<Ws, Main>> {
+  Combinator::new(Padded {
+    ws: ws.into().action,
+    main: main.into().action,
+  })
+}
+
+impl<T> Combinator<T> {
+  /// Create a new combinator to execute `ws` optionally before and after self,
+  /// keeping only the value of self.
+  ///
+  /// This is the same as [`padded(ws, self)`](padded).
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::{next, whitespaces}, parser::Parser};
+  /// let entry = next(|c| c.is_ascii_digit()).select(|a| a.start()).padded_by(whitespaces());
+  /// let output = Parser::builder().entry(entry).build(" \n1 ").next().unwrap();
+  /// assert_eq!((output.value, output.digested), (2, 4));
+  /// ```
+  #[inline]
+  pub fn padded_by<Ws>(self, ws: impl Into<Combinator<Ws>>) -> Combinator<Padded<Ws, T>> {
+    padded(ws, self)
+  }
+
+  /// Create a new combinator to execute self then `rhs`,
+  /// yielding the values of both as a tuple `(T::Value, Rhs::Value)`.
+  ///
+  /// This is the same as [`pair(self, rhs)`](pair).
+  /// Use this instead of `+` when the values are not tuples.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::next, parser::Parser};
+  /// let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap());
+  /// let entry = digit().pair(':').pair(digit());
+  /// assert_eq!(Parser::builder().entry(entry).build("1:2").next().unwrap().value, ((1, ()), 2));
+  /// ```
+  #[inline]
+  pub fn pair<Rhs>(self, rhs: impl Into<Combinator<Rhs>>) -> Combinator<Pair<T, Rhs>> {
+    pair(self, rhs)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, eat, next},
+    contextual,
+    range::WithRange,
+  };
+  use std::fmt::Debug;
+
+  fn helper<Text: ?Sized + Digest, Value: PartialEq + Debug>(
+    action: impl Action<Text = Text, State = (), Heap = (), Value = Value>,
+    input: &Text,
+    expected: Option<(Value, usize)>,
+  ) where
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      expected
+    );
+  }
+
+  fn digit() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = usize>> {
+    next(|c| c.is_ascii_digit()).select(|a| a.start())
+  }
+
+  fn byte() -> Combinator<impl Action<Text = [u8], State = (), Heap = (), Value = usize>> {
+    bytes::next(|b| b.is_ascii_digit()).select(|a| a.start())
+  }
+
+  #[test]
+  fn combinator_preceded() {
+    helper(preceded("$$", digit()), "$$1", Some((2, 3)));
+    helper(preceded(eat("好"), digit()), "好1", Some((3, 4)));
+    helper(preceded(b'$', byte()), b"$1", Some((1, 2)));
+    // rejected
+    helper(preceded("$$", digit()), "$1", None);
+    helper(preceded("$$", digit()), "$$a", None);
+    helper(preceded(b'$', byte()), b"$", None);
+  }
+
+  #[test]
+  fn combinator_preceded_cut() {
+    let exec_cut = |text: &str| {
+      preceded("$$", digit().cut())
+        .exec_cut(Input {
+          instant: &Instant::new(text),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    };
+    assert_eq!(exec_cut("$$1"), Ok(3));
+    assert_eq!(exec_cut("$1"), Err(Rejection::Backtrack));
+    assert_eq!(exec_cut("$$a"), Err(Rejection::Cut));
+
+    // the surrounding `|` won't try other branches
+    helper(
+      preceded("$$", digit().cut()) | eat("$$a").bind(0),
+      "$$a",
+      None,
+    );
+    helper(
+      preceded("$$", digit()) | eat("$$a").bind(0),
+      "$$a",
+      Some((0, 3)),
+    );
+    // other pickers propagate the cut too
+    helper(
+      delimited('(', digit().cut(), ')') | eat("(a)").bind(0),
+      "(a)",
+      None,
+    );
+    helper(
+      pair(digit(), digit().cut()) | eat("1a").bind((0, 0)),
+      "1a",
+      None,
+    );
+  }
+
+  #[test]
+  fn combinator_terminated() {
+    helper(terminated(digit(), ";;"), "1;;", Some((0, 3)));
+    helper(terminated(byte(), b';'), b"1;2", Some((0, 2)));
+    // rejected
+    helper(terminated(digit(), ";;"), "1;", None);
+    helper(terminated(digit(), ";;"), "a;;", None);
+    helper(terminated(byte(), b';'), b"1", None);
+  }
+
+  #[test]
+  fn combinator_delimited() {
+    helper(delimited('(', digit(), ')'), "(1)", Some((1, 3)));
+    helper(delimited("<<", digit(), ">>"), "<<1>>2", Some((2, 5)));
+    helper(delimited(b'(', byte(), b')'), b"(1)", Some((1, 3)));
+    // rejected
+    helper(delimited('(', digit(), ')'), "1)", None);
+    helper(delimited('(', digit(), ')'), "(a)", None);
+    helper(delimited('(', digit(), ')'), "(1", None);
+    helper(delimited(b'(', byte(), b')'), b"(1]", None);
+  }
+
+  #[test]
+  fn combinator_separated_pair() {
+    helper(
+      separated_pair(digit(), ", ", digit()),
+      "1, 2",
+      Some(((0, 3), 4)),
+    );
+    helper(
+      separated_pair(byte(), b'=', byte()),
+      b"1=2",
+      Some(((0, 2), 3)),
+    );
+    // unit values are kept as-is
+    helper(separated_pair('a', '=', 'b'), "a=b", Some((((), ()), 3)));
+    // rejected
+    helper(separated_pair(digit(), ',', digit()), "a,2", None);
+    helper(separated_pair(digit(), ',', digit()), "1;2", None);
+    helper(separated_pair(digit(), ',', digit()), "1,", None);
+    helper(separated_pair(byte(), b'=', byte()), b"1=", None);
+  }
+
+  #[test]
+  fn combinator_pair() {
+    helper(pair(digit(), digit()), "12", Some(((0, 1), 2)));
+    helper(digit().pair(digit()), "123", Some(((0, 1), 2)));
+    helper(pair(eat("好"), digit()), "好1", Some((((), 3), 4)));
+    helper(byte().pair(byte()), b"12", Some(((0, 1), 2)));
+    // tuple values are not flattened
+    helper(
+      digit().tuple().pair(digit().tuple()),
+      "12",
+      Some((((0,), (1,)), 2)),
+    );
+    // nested
+    helper(
+      pair(pair(digit(), digit()), digit()),
+      "123",
+      Some((((0, 1), 2), 3)),
+    );
+    helper(
+      digit().pair(',').pair(digit()).pair(byte_len()),
+      "1,2好",
+      Some(((((0, ()), 2), 3), 6)),
+    );
+    // rejected
+    helper(pair(digit(), digit()), "1", None);
+    helper(pair(digit(), digit()), "a1", None);
+    helper(byte().pair(byte()), b"1a", None);
+
+    fn byte_len() -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = usize>> {
+      eat("好").select(|a| a.content().len())
+    }
+  }
+
+  #[test]
+  fn combinator_padded() {
+    let ws = || next(|c| c == ' ' || c == '\t' || c == '\n') * (1..);
+    helper(padded(ws(), digit()), "1", Some((0, 1)));
+    helper(padded(ws(), digit()), " \t1\n\n2", Some((2, 5)));
+    helper(digit().padded_by(ws()), "\n1 ", Some((1, 3)));
+    helper(padded(b' ', byte()), b" 1 ", Some((1, 3)));
+    // only one `ws` on each side
+    helper(padded(' ', digit()), "  1", None);
+    // zero-width whitespaces
+    helper(padded(eat(""), digit()), "1", Some((0, 1)));
+    helper(padded(ws() * (..), digit()), "  1  ", Some((2, 5)));
+    // rejected
+    helper(padded(ws(), digit()), " a ", None);
+    helper(padded(ws(), digit()), "  ", None);
+
+    // the range of the inner value excludes the padding
+    helper(
+      padded(ws(), digit().range()),
+      "  1 ",
+      Some((
+        WithRange {
+          data: 2,
+          range: 2..3,
+        },
+        4,
+      )),
+    );
+  }
+
+  #[test]
+  fn combinator_padded_sep() {
+    let ws = || next(|c| c == ' ' || c == '\t' || c == '\n') * (1..);
+    let list = || {
+      (digit().padded_by(ws()) * (1..))
+        .sep(padded(ws(), ','))
+        .fold(Vec::new, |mut acc, v| {
+          acc.push(v);
+          acc
+        })
+    };
+    helper(list(), "1 , 2 ,3", Some((vec![0, 4, 7], 8)));
+    helper(list(), " 1,\t2\n,\n3 ", Some((vec![1, 4, 8], 10)));
+    helper(list(), "1", Some((vec![0], 1)));
+    // trailing separator is not digested
+    helper(list(), "1 , 2 , ", Some((vec![0, 4], 6)));
+    helper(list(), " , ", None);
+
+    // without padding the items
+    let list = (next(|c| c.is_ascii_digit()) * (1..)).sep(padded(ws(), ','));
+    helper(list, "1 ,2", Some(((), 4)));
+  }
+
+  #[test]
+  fn combinator_pick_nested() {
+    // `[1=2]`
+    let entry = delimited('[', separated_pair(digit(), '=', digit()), ']');
+    helper(entry, "[1=2]", Some(((1, 3), 5)));
+    // `$(1);`
+    let entry = preceded('$', terminated(delimited('(', digit(), ')'), ';'));
+    helper(entry, "$(1);", Some((2, 5)));
+    // with `+`
+    let entry = preceded('$', digit().tuple()) + digit().tuple();
+    helper(entry, "$12", Some(((1, 2), 3)));
+  }
+
+  #[test]
+  fn combinator_pick_contextual() {
+    contextual!(usize, ());
+
+    let entry = delimited(
+      eat('(').then(|a| *a.state += 1),
+      ctx_digit(),
+      eat(')').then(|a| *a.state += 1),
+    );
+    let mut state = 0;
+    assert_eq!(
+      entry
+        .exec(Input {
+          instant: &Instant::new("(1)"),
+          state: &mut state,
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested)),
+      Some((1, 3))
+    );
+    assert_eq!(state, 2);
+
+    fn ctx_digit() -> Combinator<impl Action<Text = str, State = usize, Heap = (), Value = usize>> {
+      next(|c| c.is_ascii_digit()).select(|a| a.start())
+    }
+  }
+
+  fn _pick_debug_clone_copy() {
+    let _ = format!("{:?}", preceded('a', 'b'));
+    let _ = format!("{:?}", terminated('a', 'b'));
+    let _ = format!("{:?}", delimited('a', 'b', 'c'));
+    let _ = format!("{:?}", separated_pair('a', 'b', 'c'));
+    let _ = format!("{:?}", pair('a', 'b'));
+    let _ = format!("{:?}", padded('a', 'b'));
+    let c = delimited('a', 'b', 'c');
+    let _c = c;
+    let _c = c.clone();
+    let c = separated_pair('a', 'b', 'c');
+    let _c = c;
+    let _c = c.clone();
+    let c = pair('a', 'b');
+    let _c = c;
+    let _c = c.clone();
+    let c = padded('a', 'b');
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,131 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// See [`position`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Position;
+
+impl Position {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+unsafe impl Action for Position {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = usize;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    Some(Output {
+      value: input.instant.digested(),
+      digested: 0,
+    })
+  }
+}
+
+/// Returns a combinator which always accepts with 0 bytes digested,
+/// and yields the current [`Instant::digested`] as the value.
+///
+/// This is useful to record positions inside a `+` chain.
+/// To read values from the `State` or `Heap` without digesting,
+/// see `state_value` and `heap_value` generated by [`contextual`](crate::contextual).
+/// # Caveats
+/// Since this combinator never digests, don't repeat it alone with `*` unbounded.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, position, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = (usize, usize)>>) {}
+/// // record the start and the end of "abc"
+/// # t(
+/// position().tuple() + eat("abc") + position().tuple()
+/// # );
+/// ```
+#[inline]
+pub const fn position() -> Combinator<Position> {
+  Combinator::new(Position::new())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{combinator::eat, parser::Parser};
+
+  #[test]
+  fn combinator_position() {
+    assert_eq!(
+      position()
+        .exec(Input {
+          instant: &unsafe { Instant::new("123").to_digested_unchecked(2) },
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      Some((2, 0))
+    );
+
+    // accept empty input
+    assert_eq!(
+      position()
+        .exec(Input {
+          instant: &Instant::new(""),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| (o.value, o.digested)),
+      Some((0, 0))
+    );
+  }
+
+  #[test]
+  fn position_span_matches_range() {
+    let mut parser = Parser::builder()
+      .entry(
+        eat("a") * (..)
+          + (position().tuple() + eat("bc") + position().tuple())
+            .range()
+            .tuple(),
+      )
+      .build("aabcd");
+    let output = parser.next().unwrap();
+    let (start, end) = output.value.0.data;
+    assert_eq!(start..end, output.value.0.range);
+    assert_eq!(start..end, 2..4);
+  }
+
+  #[test]
+  fn position_in_loop() {
+    // position is zero-width, combined with a digesting combinator it is safe to repeat
+    let mut parser = Parser::builder()
+      .entry(
+        ((position().tuple() + eat('a')) * (..)).fold(Vec::new, |mut acc, (p,)| {
+          acc.push(p);
+          acc
+        }),
+      )
+      .build("aaa");
+    let output = parser.next().unwrap();
+    assert_eq!(output.digested, 3);
+    assert_eq!(output.value, vec![0, 1, 2]);
+  }
+
+  fn _position_debug() {
+    let _ = format!("{:?}", position());
+  }
+
+  fn _position_clone_copy() {
+    let c = position();
+    let _c = c;
+    let _c = c.clone();
+  }
+}
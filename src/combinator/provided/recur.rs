@@ -70,6 +70,21 @@ macro_rules! create_recur {
       }
     }
 
+    // the recursive action is not expanded to avoid infinite recursion
+    impl<State, Heap, Value> $crate::combinator::FirstSet for Recur<State, Heap, Value> {
+      #[inline]
+      fn first_set(&self) -> Option<$crate::combinator::LookaheadSet> {
+        None
+      }
+    }
+
+    impl<State, Heap, Value> $crate::combinator::Describe for Recur<State, Heap, Value> {
+      fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        $crate::combinator::write_head(f, "Recur", module_path!())?;
+        f.write_str(")")
+      }
+    }
+
     impl<State, Heap, Value> Clone for Recur<State, Heap, Value> {
       #[inline]
       fn clone(&self) -> Self {
@@ -92,6 +107,14 @@ macro_rules! create_recur {
       ) -> Option<Output<Self::Value>> {
         self.inner.get().unwrap().exec(input)
       }
+
+      #[inline]
+      fn exec_cut(
+        &self,
+        input: Input<&Instant<&Self::Text>, &mut State, &mut Heap>,
+      ) -> Result<Output<Self::Value>, $crate::action::Rejection> {
+        self.inner.get().unwrap().exec_cut(input)
+      }
     }
 
     /// See [`recur_unchecked`].
@@ -113,6 +136,13 @@ macro_rules! create_recur {
       }
     }
 
+    impl<State, Heap, Value> $crate::combinator::Describe for RecurUnchecked<State, Heap, Value> {
+      fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        $crate::combinator::write_head(f, "Recur", module_path!())?;
+        f.write_str(")")
+      }
+    }
+
     impl<State, Heap, Value> Clone for RecurUnchecked<State, Heap, Value> {
       #[inline]
       fn clone(&self) -> Self {
@@ -133,8 +163,17 @@ macro_rules! create_recur {
         &self,
         input: Input<&Instant<&Self::Text>, &mut State, &mut Heap>,
       ) -> Option<Output<Self::Value>> {
-        debug_assert!(self.inner.get().is_some());
-        unsafe { self.inner.get().unwrap_unchecked() }.exec(input)
+        // SAFETY: the action is set before execution, guaranteed by the caller of `recur_unchecked`
+        $crate::idx::unchecked!($crate::idx::unwrap(self.inner.get())).exec(input)
+      }
+
+      #[inline]
+      fn exec_cut(
+        &self,
+        input: Input<&Instant<&Self::Text>, &mut State, &mut Heap>,
+      ) -> Result<Output<Self::Value>, $crate::action::Rejection> {
+        // SAFETY: the action is set before execution, guaranteed by the caller of `recur_unchecked`
+        $crate::idx::unchecked!($crate::idx::unwrap(self.inner.get())).exec_cut(input)
       }
     }
   };
@@ -0,0 +1,267 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, Contextual},
+  instant::Instant,
+  range::Range,
+};
+
+/// See [`fn@regex`].
+#[derive(Debug, Clone)]
+pub struct Regex {
+  re: ::regex::Regex,
+}
+
+impl Regex {
+  /// Create a new instance.
+  /// The pattern will be anchored at the start of the undigested text.
+  #[inline]
+  pub fn new(pattern: &str) -> Result<Self, ::regex::Error> {
+    ::regex::Regex::new(&format!("^(?:{})", pattern)).map(|re| Self { re })
+  }
+
+  /// The anchored regex.
+  #[inline]
+  pub const fn as_regex(&self) -> &::regex::Regex {
+    &self.re
+  }
+}
+
+unsafe impl Action for Regex {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let m = self.re.find(input.instant.rest())?;
+    debug_assert_eq!(m.start(), 0);
+    // SAFETY: the match end is a valid char boundary of the rest
+    Some(unsafe { input.instant.accept_unchecked(m.end()) })
+  }
+}
+
+/// See [`Combinator::captures`].
+#[derive(Debug, Clone)]
+pub struct RegexCaptures {
+  re: ::regex::Regex,
+}
+
+unsafe impl Action for RegexCaptures {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = Vec<Option<Range>>;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let captures = self.re.captures(input.instant.rest())?;
+    let start = input.instant.digested();
+    // the whole match always exists
+    let digested = captures.get(0)?.end();
+    Some(Output {
+      value: captures
+        .iter()
+        .map(|m| m.map(|m| start + m.start()..start + m.end()))
+        .collect(),
+      digested,
+    })
+  }
+}
+
+impl Combinator<Regex> {
+  /// Create a new combinator to yield the byte ranges of capture groups as the value.
+  ///
+  /// The value is indexed by the group index, the first one is the whole match.
+  /// A group is [`None`] if it didn't participate in the match.
+  /// Like [`Combinator::range`], the ranges are relative to the start of the whole text.
+  /// Use [`Regex::as_regex`] to look up the index of a named group.
+  /// # Examples
+  /// ```
+  /// # use whitehole::{combinator::regex, parser::Parser};
+  /// let entry = regex(r"(\d+)(?:\.(\d+))?").captures();
+  /// let output = Parser::builder().entry(entry).build("12;").next().unwrap();
+  /// assert_eq!(output.value, [Some(0..2), Some(0..2), None]);
+  /// ```
+  #[inline]
+  pub fn captures(self) -> Combinator<RegexCaptures> {
+    Combinator::new(RegexCaptures { re: self.action.re })
+  }
+}
+
+impl<State, Heap> Combinator<Contextual<Regex, State, Heap>> {
+  /// See [`Combinator::<Regex>::captures`].
+  #[inline]
+  pub fn captures(self) -> Combinator<Contextual<RegexCaptures, State, Heap>> {
+    Combinator::new(Contextual::new(RegexCaptures {
+      re: self.action.action.re,
+    }))
+  }
+}
+
+/// Returns a combinator to match a regex pattern at the start of the undigested text.
+///
+/// The pattern is anchored, so the combinator never scans ahead.
+/// A zero-width match (e.g. `a*` on `"b"`) is accepted with 0 bytes digested,
+/// use [`Combinator::reject`] to reject it if needed.
+/// The value is `()`, use [`Combinator::captures`] to get the capture groups.
+///
+/// For contextual grammars, wrap the action with [`Contextual`],
+/// e.g. `Combinator::new(Contextual::new(Regex::new(pattern)?))`.
+///
+/// This is only available with the `regex` feature.
+/// # Panics
+/// Panics if the pattern is invalid. Use [`Regex::new`] to handle the error.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::regex, parser::Parser};
+/// let entry = regex(r"[a-z]+\d*");
+/// assert_eq!(
+///   Parser::builder().entry(entry).build("ab12 cd").next().unwrap().digested,
+///   4
+/// );
+/// ```
+#[inline]
+pub fn regex(pattern: &str) -> Combinator<Regex> {
+  Combinator::new(Regex::new(pattern).expect("invalid regex pattern"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::eat;
+
+  fn exec<V>(
+    action: impl Action<Text = str, State = (), Heap = (), Value = V>,
+    instant: &Instant<&str>,
+  ) -> Option<(V, usize)> {
+    action
+      .exec(Input {
+        instant,
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn combinator_regex() {
+    assert_eq!(exec(regex(r"\d+"), &Instant::new("123a")), Some(((), 3)));
+    assert_eq!(exec(regex("好+"), &Instant::new("好好a")), Some(((), 6)));
+    // anchored at the start of the rest, never scan ahead
+    assert_eq!(exec(regex(r"\d+"), &Instant::new("a123")), None);
+    assert_eq!(
+      exec(regex(r"\d+"), &unsafe {
+        Instant::new("a123").to_digested_unchecked(1)
+      }),
+      Some(((), 3))
+    );
+    // alternation is anchored as a whole
+    assert_eq!(exec(regex("a|b"), &Instant::new("cb")), None);
+    // zero-width match is accepted with 0 bytes digested
+    assert_eq!(exec(regex("a*"), &Instant::new("b")), Some(((), 0)));
+    assert_eq!(exec(regex("a*"), &Instant::new("")), Some(((), 0)));
+    assert_eq!(
+      exec(
+        regex("a*").reject(|a| a.digested() == 0),
+        &Instant::new("b")
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn combinator_regex_invalid() {
+    assert!(Regex::new("(").is_err());
+    assert_eq!(Regex::new("a").unwrap().as_regex().as_str(), "^(?:a)");
+  }
+
+  #[test]
+  #[should_panic(expected = "invalid regex pattern")]
+  fn combinator_regex_panic() {
+    regex("(");
+  }
+
+  #[test]
+  fn combinator_regex_decorators() {
+    assert_eq!(
+      exec(regex(r"\d+").bind(1), &Instant::new("12")),
+      Some((1, 2))
+    );
+    assert_eq!(
+      exec(
+        regex(r"\d+").select(|a| a.content().parse::<u32>().unwrap()),
+        &Instant::new("12a")
+      ),
+      Some((12, 2))
+    );
+    assert_eq!(
+      exec(
+        regex(r"\d+").bind((1,)) + eat(',') + regex(r"\d+").bind((2,)),
+        &Instant::new("1,2")
+      ),
+      Some(((1, 2), 3))
+    );
+    // contextual
+    assert_eq!(
+      Combinator::new(Contextual::<_, i32, i32>::new(Regex::new("a").unwrap()))
+        .exec(Input {
+          instant: &Instant::new("a"),
+          state: &mut 0,
+          heap: &mut 0,
+        })
+        .map(|o| o.digested),
+      Some(1)
+    );
+    assert_eq!(
+      Combinator::new(Contextual::<_, i32, i32>::new(Regex::new("(a)").unwrap()))
+        .captures()
+        .exec(Input {
+          instant: &Instant::new("a"),
+          state: &mut 0,
+          heap: &mut 0,
+        })
+        .map(|o| o.value),
+      Some(vec![Some(0..1), Some(0..1)])
+    );
+  }
+
+  #[test]
+  fn combinator_regex_captures() {
+    let c = || regex(r"(\d+)(?:\.(\d+))?").captures();
+    assert_eq!(
+      exec(c(), &Instant::new("12.5;")),
+      Some((vec![Some(0..4), Some(0..2), Some(3..4)], 4))
+    );
+    assert_eq!(
+      exec(c(), &Instant::new("12;")),
+      Some((vec![Some(0..2), Some(0..2), None], 2))
+    );
+    // ranges are relative to the whole text
+    assert_eq!(
+      exec(c(), &unsafe {
+        Instant::new("a12").to_digested_unchecked(1)
+      }),
+      Some((vec![Some(1..3), Some(1..3), None], 2))
+    );
+    assert_eq!(exec(c(), &Instant::new("a12")), None);
+  }
+
+  fn _regex_debug() {
+    let _ = format!("{:?}", regex("a"));
+    let _ = format!("{:?}", regex("a").captures());
+  }
+
+  fn _regex_clone() {
+    let c = regex("a");
+    let _c = c.clone();
+    let c = regex("a").captures();
+    let _c = c.clone();
+  }
+}
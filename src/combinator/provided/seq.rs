@@ -0,0 +1,283 @@
+use crate::{
+  action::{exec_by_cut, Action, Input, Output, Rejection},
+  combinator::{ops::add::Concat, Combinator},
+  digest::Digest,
+  instant::Instant,
+};
+use std::{ops::RangeFrom, slice::SliceIndex};
+
+/// See [`seq`].
+#[derive(Copy, Clone, Debug)]
+pub struct Seq<T> {
+  actions: T,
+}
+
+impl<T> Seq<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(actions: T) -> Self {
+    Self { actions }
+  }
+}
+
+/// A helper trait to [`Concat`] all values of a [`Seq`] from left to right.
+///
+/// Built-in implementations for tuples with up to 12 elements.
+pub trait ConcatAll {
+  /// The concat result.
+  type Output;
+  /// Concat all elements from left to right.
+  fn concat_all(self) -> Self::Output;
+}
+
+impl<_1> ConcatAll for (_1,) {
+  type Output = _1;
+  #[inline]
+  fn concat_all(self) -> Self::Output {
+    self.0
+  }
+}
+
+macro_rules! impl_concat_all {
+  ($($rest:ident),*) => {
+    #[allow(non_snake_case)]
+    impl<_1: Concat<_2>, _2 $(, $rest)*> ConcatAll for (_1, _2 $(, $rest)*)
+    where
+      (<_1 as Concat<_2>>::Output, $($rest),*): ConcatAll,
+    {
+      type Output = <(<_1 as Concat<_2>>::Output, $($rest),*) as ConcatAll>::Output;
+      #[inline]
+      fn concat_all(self) -> Self::Output {
+        let (_1, _2 $(, $rest)*) = self;
+        (_1.concat(_2), $($rest),*).concat_all()
+      }
+    }
+  };
+}
+impl_concat_all!();
+impl_concat_all!(_3);
+impl_concat_all!(_3, _4);
+impl_concat_all!(_3, _4, _5);
+impl_concat_all!(_3, _4, _5, _6);
+impl_concat_all!(_3, _4, _5, _6, _7);
+impl_concat_all!(_3, _4, _5, _6, _7, _8);
+impl_concat_all!(_3, _4, _5, _6, _7, _8, _9);
+impl_concat_all!(_3, _4, _5, _6, _7, _8, _9, _10);
+impl_concat_all!(_3, _4, _5, _6, _7, _8, _9, _10, _11);
+impl_concat_all!(_3, _4, _5, _6, _7, _8, _9, _10, _11, _12);
+
+macro_rules! impl_seq {
+  ($first:ident $(, $rest:ident)*) => {
+    #[allow(non_snake_case)]
+    unsafe impl<
+        $first: Action<Text: Digest>,
+        $($rest: Action<Text = $first::Text, State = $first::State, Heap = $first::Heap>),*
+      > Action for Seq<($first, $($rest),*)>
+    where
+      RangeFrom<usize>: SliceIndex<$first::Text, Output = $first::Text>,
+      ($first::Value, $($rest::Value),*): ConcatAll,
+    {
+      type Text = $first::Text;
+      type State = $first::State;
+      type Heap = $first::Heap;
+      type Value = <($first::Value, $($rest::Value),*) as ConcatAll>::Output;
+
+      exec_by_cut!();
+
+      #[inline]
+      fn exec_cut(
+        &self,
+        mut input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+      ) -> Result<Output<Self::Value>, Rejection> {
+        let ($first, $($rest),*) = &self.actions;
+        let output = $first.exec_cut(input.reborrow())?;
+        // the only cursor threaded through the whole sequence
+        let mut digested = output.digested;
+        let values = (
+          output.value,
+          $({
+            let output = $rest.exec_cut(
+              input.reborrow_with(&unsafe { input.instant.to_digested_unchecked(digested) }),
+            )?;
+            // SAFETY: since `slice::len` is usize, so `digested` must be a valid usize
+            digested = $crate::idx::unchecked!($crate::idx::add(digested, output.digested));
+            output.value
+          }),*
+        );
+        Ok(Output {
+          value: values.concat_all(),
+          digested,
+        })
+      }
+    }
+  };
+}
+impl_seq!(_1, _2);
+impl_seq!(_1, _2, _3);
+impl_seq!(_1, _2, _3, _4);
+impl_seq!(_1, _2, _3, _4, _5);
+impl_seq!(_1, _2, _3, _4, _5, _6);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7, _8);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7, _8, _9);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11);
+impl_seq!(_1, _2, _3, _4, _5, _6, _7, _8, _9, _10, _11, _12);
+
+/// Returns a combinator to execute a tuple of actions one by one,
+/// with the same semantics as chaining them with `+`.
+///
+/// Values are [`Concat`]-ed from left to right, just like `+`.
+/// Tuples with 2 to 12 elements are supported.
+///
+/// Unlike nested `+`, the sequence is flattened into a single [`Action`],
+/// so the digested offset is threaded through all elements directly
+/// and the type of the combinator is much shallower.
+/// This is preferred for long fixed-field sequences.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, next, seq, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// // same as `eat('a') + next(|c| c.is_ascii_digit()) + eat("b")`
+/// # t(
+/// seq((eat('a'), next(|c| c.is_ascii_digit()), eat("b")))
+/// # );
+/// ```
+#[inline]
+pub const fn seq<T>(actions: T) -> Combinator<Seq<T>> {
+  Combinator::new(Seq::new(actions))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{bytes, eat, next, take};
+
+  fn exec<T: Action<Text = str, State = (), Heap = ()>>(
+    action: T,
+    text: &str,
+  ) -> Option<Output<T::Value>> {
+    action.exec(Input {
+      instant: &Instant::new(text),
+      state: &mut (),
+      heap: &mut (),
+    })
+  }
+
+  #[test]
+  fn combinator_seq() {
+    // accept
+    assert_eq!(
+      exec(seq((eat('a'), eat("bc"), take(1))), "abcde").map(|o| o.digested),
+      Some(4)
+    );
+    // reject in the middle
+    assert!(exec(seq((eat('a'), eat("bc"), take(1))), "abd").is_none());
+    // reject at the end
+    assert!(exec(seq((eat('a'), eat("bc"), take(1))), "abc").is_none());
+
+    // bytes
+    assert_eq!(
+      seq((bytes::eat(b'a'), bytes::take(2)))
+        .exec(Input {
+          instant: &Instant::new(b"abcd" as &[u8]),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested),
+      Some(3)
+    );
+  }
+
+  #[test]
+  fn seq_cut() {
+    let exec_cut = |text: &str| {
+      seq((eat("let "), eat('x').cut(), eat(';')))
+        .exec_cut(Input {
+          instant: &Instant::new(text),
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| o.digested)
+    };
+    assert_eq!(exec_cut("let x;"), Ok(6));
+    assert_eq!(exec_cut("var x;"), Err(Rejection::Backtrack));
+    assert_eq!(exec_cut("let y;"), Err(Rejection::Cut));
+    // rejections after the cut point are not cuts
+    assert_eq!(exec_cut("let x!"), Err(Rejection::Backtrack));
+    assert_eq!(exec_cut("let x"), Err(Rejection::Incomplete));
+
+    // the surrounding `|` won't try other branches
+    let entry = || seq((eat("let "), eat('x').cut())) | eat("let y");
+    assert_eq!(exec(entry(), "let x").map(|o| o.digested), Some(5));
+    assert!(exec(entry(), "let y").is_none());
+  }
+
+  #[test]
+  fn seq_concat_values() {
+    let digit = || next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap());
+    let output = exec(
+      seq((
+        digit().tuple(),
+        eat(','),
+        digit().tuple(),
+        eat(','),
+        digit().tuple(),
+      )),
+      "1,2,3",
+    )
+    .unwrap();
+    assert_eq!(output.value, (1, 2, 3));
+    assert_eq!(output.digested, 5);
+  }
+
+  #[test]
+  fn seq_same_as_add() {
+    let field = || {
+      (next(|c| c.is_ascii_alphanumeric()) * (1..))
+        .range()
+        .tuple()
+    };
+    let by_add = field() + ';' + field() + ';' + field();
+    let by_seq = seq((field(), eat(';'), field(), eat(';'), field()));
+    for text in ["a;bb;ccc", "a;bb;", "a;bb", ";", "", "a;b;c;d"] {
+      assert_eq!(
+        exec(&by_add, text).map(|o| (o.digested, o.value)),
+        exec(&by_seq, text).map(|o| (o.digested, o.value))
+      );
+    }
+  }
+
+  #[test]
+  fn seq_max_len() {
+    let output = exec(
+      seq((
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+        take(1),
+      )),
+      "123456789abc",
+    )
+    .unwrap();
+    assert_eq!(output.digested, 12);
+  }
+
+  fn _seq_debug() {
+    let _ = format!("{:?}", seq((eat('a'), eat('b'))));
+  }
+
+  fn _seq_clone_copy() {
+    let c = seq((eat('a'), eat('b')));
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -1,6 +1,7 @@
 use crate::{
   action::{Action, Input, Output},
-  combinator::Combinator,
+  combinator::{write_head, Combinator, Contextual, Describe, FirstSet, LookaheadSet},
+  idx,
   instant::Instant,
 };
 
@@ -18,6 +19,24 @@ impl Take {
   }
 }
 
+impl Describe for Take {
+  fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write_head(f, "Take", module_path!())?;
+    write!(f, " {})", self.n)
+  }
+}
+
+impl FirstSet for Take {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    Some(if self.n == 0 {
+      LookaheadSet::empty()
+    } else {
+      LookaheadSet::full()
+    })
+  }
+}
+
 unsafe impl Action for Take {
   type Text = str;
   type State = ();
@@ -33,7 +52,8 @@ unsafe impl Action for Take {
     let mut chars = input.instant.rest().chars();
     for _ in 0..self.n {
       if let Some(c) = chars.next() {
-        digested = unsafe { digested.unchecked_add(c.len_utf8()) };
+        // SAFETY: the digested chars are inside the rest, whose length is a valid usize
+        digested = idx::unchecked!(idx::add(digested, c.len_utf8()));
       } else {
         // no enough chars, reject
         return None;
@@ -46,6 +66,10 @@ unsafe impl Action for Take {
 
 /// Returns a combinator to take the next `n` undigested [`char`]s.
 ///
+/// The unit is [`char`] (Unicode scalar value), not byte or user-perceived character.
+/// To count bytes, use [`take_bytes`].
+/// To count extended grapheme clusters, use `take_graphemes` with the `unicode-segmentation` feature.
+///
 /// `0` is allowed but be careful with infinite loops.
 /// # Examples
 /// ```
@@ -60,6 +84,273 @@ pub const fn take(n: usize) -> Combinator<Take> {
   Combinator::new(Take::new(n))
 }
 
+/// Returns a combinator to take the next `n` undigested [`char`]s.
+///
+/// This is an alias of [`take`] to make the rejection explicit at the call site:
+/// the combinator will reject if there are not enough chars.
+/// For the saturating version, see [`take_at_most`].
+#[inline]
+pub const fn take_exact(n: usize) -> Combinator<Take> {
+  take(n)
+}
+
+/// What [`take_bytes`] should do when the cut is not on a char boundary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BoundaryPolicy {
+  /// Reject.
+  #[default]
+  Reject,
+  /// Digest up to the previous char boundary.
+  /// This may digest 0 bytes if the first char straddles the cut.
+  SnapBack,
+  /// Digest up to the next char boundary.
+  SnapForward,
+}
+
+/// See [`take_bytes`].
+#[derive(Copy, Clone, Debug)]
+pub struct TakeBytes {
+  n: usize,
+  policy: BoundaryPolicy,
+}
+
+impl TakeBytes {
+  /// Create a new instance with [`BoundaryPolicy::Reject`].
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self {
+      n,
+      policy: BoundaryPolicy::Reject,
+    }
+  }
+
+  #[inline]
+  const fn with_policy(mut self, policy: BoundaryPolicy) -> Self {
+    self.policy = policy;
+    self
+  }
+}
+
+impl Combinator<TakeBytes> {
+  /// Set the [`BoundaryPolicy`] when the cut is not on a char boundary.
+  #[inline]
+  pub const fn boundary_policy(self, policy: BoundaryPolicy) -> Self {
+    Self::new(self.action.with_policy(policy))
+  }
+}
+
+impl<State, Heap> Combinator<Contextual<TakeBytes, State, Heap>> {
+  /// See [`Combinator::<TakeBytes>::boundary_policy`].
+  #[inline]
+  pub const fn boundary_policy(self, policy: BoundaryPolicy) -> Self {
+    Self::new(Contextual::new(self.action.action.with_policy(policy)))
+  }
+}
+
+unsafe impl Action for TakeBytes {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    if self.n > rest.len() {
+      // no enough bytes, reject
+      return None;
+    }
+
+    let digested = if rest.is_char_boundary(self.n) {
+      self.n
+    } else {
+      match self.policy {
+        BoundaryPolicy::Reject => return None,
+        // a char is at most 4 bytes and `0` is always a char boundary
+        BoundaryPolicy::SnapBack => (self.n.saturating_sub(3)..self.n)
+          .rev()
+          .find(|&i| rest.is_char_boundary(i))
+          .unwrap_or(0),
+        // `rest.len()` is always a char boundary
+        BoundaryPolicy::SnapForward => (self.n + 1..=rest.len())
+          .find(|&i| rest.is_char_boundary(i))
+          .unwrap_or(rest.len()),
+      }
+    };
+    // SAFETY: `digested` is a char boundary and not greater than `rest.len()`
+    unsafe { input.instant.accept_unchecked(digested) }.into()
+  }
+}
+
+/// Returns a combinator to take the next `n` undigested bytes.
+///
+/// The unit is byte, not [`char`] (see [`take`]).
+/// The combinator will reject if there are not enough bytes.
+/// If the cut is not on a char boundary, the behavior is decided by the [`BoundaryPolicy`],
+/// which is [`BoundaryPolicy::Reject`] by default.
+/// Use [`Combinator::boundary_policy`] to change it.
+///
+/// This is useful for fixed-width layouts with UTF-8 data.
+/// `0` is allowed but be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_bytes, BoundaryPolicy, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// take_bytes(10) // take 10 bytes
+/// # );
+/// # t(
+/// take_bytes(10).boundary_policy(BoundaryPolicy::SnapBack)
+/// # );
+/// ```
+#[inline]
+pub const fn take_bytes(n: usize) -> Combinator<TakeBytes> {
+  Combinator::new(TakeBytes::new(n))
+}
+
+/// See [`take_at_most`].
+#[derive(Copy, Clone, Debug)]
+pub struct TakeAtMost {
+  n: usize,
+}
+
+impl TakeAtMost {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+unsafe impl Action for TakeAtMost {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = usize;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut digested: usize = 0;
+    let mut taken: usize = 0;
+    for c in input.instant.rest().chars().take(self.n) {
+      // SAFETY: the digested chars are inside the rest, whose length is a valid usize
+      digested = idx::unchecked!(idx::add(digested, c.len_utf8()));
+      // SAFETY: each taken char has at least 1 byte,
+      // so `taken` is no more than `digested` which is a valid usize
+      taken = idx::unchecked!(idx::add(taken, 1));
+    }
+    Some(Output {
+      value: taken,
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to take at most `n` undigested [`char`]s.
+///
+/// The combinator will always accept, with `min(n, remaining chars)` chars digested.
+/// The value is the number of taken chars.
+/// For the rejecting version, see [`take_exact`].
+///
+/// Be careful with infinite loops since this may accept 0 chars.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_at_most, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = usize>>) {}
+/// # t(
+/// take_at_most(10) // take up to 10 chars
+/// # );
+/// ```
+#[inline]
+pub const fn take_at_most(n: usize) -> Combinator<TakeAtMost> {
+  Combinator::new(TakeAtMost::new(n))
+}
+
+/// See [`skip_at_most`].
+#[derive(Copy, Clone)]
+pub struct SkipAtMost<F> {
+  n: usize,
+  predicate: F,
+}
+
+impl<F> SkipAtMost<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize, predicate: F) -> Self {
+    Self { n, predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for SkipAtMost<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SkipAtMost").field("n", &self.n).finish()
+  }
+}
+
+unsafe impl<F: Fn(char) -> bool> Action for SkipAtMost<F> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = usize;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let mut digested: usize = 0;
+    let mut taken: usize = 0;
+    for c in input
+      .instant
+      .rest()
+      .chars()
+      .take(self.n)
+      .take_while(|c| (self.predicate)(*c))
+    {
+      // SAFETY: the digested chars are inside the rest, whose length is a valid usize
+      digested = idx::unchecked!(idx::add(digested, c.len_utf8()));
+      // SAFETY: each taken char has at least 1 byte,
+      // so `taken` is no more than `digested` which is a valid usize
+      taken = idx::unchecked!(idx::add(taken, 1));
+    }
+    Some(Output {
+      value: taken,
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to take at most `n` undigested [`char`]s
+/// which satisfy the `predicate`.
+///
+/// The combinator will always accept and stop at the first char which doesn't satisfy the `predicate`.
+/// The value is the number of taken chars.
+/// This is useful for bounded skipping during error recovery.
+///
+/// Be careful with infinite loops since this may accept 0 chars.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{skip_at_most, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = usize>>) {}
+/// // skip up to 100 chars until a semicolon
+/// # t(
+/// skip_at_most(100, |c| c != ';')
+/// # );
+/// ```
+#[inline]
+pub const fn skip_at_most<F: Fn(char) -> bool>(
+  n: usize,
+  predicate: F,
+) -> Combinator<SkipAtMost<F>> {
+  Combinator::new(SkipAtMost::new(n, predicate))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -99,6 +390,108 @@ mod tests {
     helper(take(2), "好好", Some(6));
   }
 
+  #[test]
+  fn test_take_bytes() {
+    // on boundaries, all policies are the same
+    for policy in [
+      BoundaryPolicy::Reject,
+      BoundaryPolicy::SnapBack,
+      BoundaryPolicy::SnapForward,
+    ] {
+      let c = || take_bytes(3).boundary_policy(policy);
+      helper(c(), "123456", Some(3));
+      // straddling the cut
+      helper(
+        c(),
+        "a好",
+        match policy {
+          BoundaryPolicy::Reject => None,
+          BoundaryPolicy::SnapBack => Some(1),
+          BoundaryPolicy::SnapForward => Some(4),
+        },
+      );
+      helper(c(), "好a", Some(3));
+      // at EOF
+      helper(c(), "123", Some(3));
+      helper(c(), "12", None);
+      helper(take_bytes(0).boundary_policy(policy), "", Some(0));
+    }
+
+    // default is reject
+    helper(take_bytes(1), "好", None);
+    // offset 0
+    helper(
+      take_bytes(1).boundary_policy(BoundaryPolicy::SnapBack),
+      "好",
+      Some(0),
+    );
+    helper(
+      take_bytes(1).boundary_policy(BoundaryPolicy::SnapForward),
+      "好",
+      Some(3),
+    );
+    // straddling the end of the text
+    helper(
+      take_bytes(5).boundary_policy(BoundaryPolicy::SnapForward),
+      "a好好",
+      Some(7),
+    );
+    helper(
+      take_bytes(5).boundary_policy(BoundaryPolicy::SnapBack),
+      "a好好",
+      Some(4),
+    );
+  }
+
+  fn exec<V>(
+    action: impl Action<Text = str, State = (), Heap = (), Value = V>,
+    input: &str,
+  ) -> Option<(V, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn test_take_exact() {
+    // exactly n remaining
+    helper(take_exact(3), "123", Some(3));
+    // fewer
+    helper(take_exact(3), "12", None);
+    // zero remaining
+    helper(take_exact(3), "", None);
+    helper(take_exact(0), "", Some(0));
+  }
+
+  #[test]
+  fn test_take_at_most() {
+    // more than n remaining
+    assert_eq!(exec(take_at_most(3), "1234"), Some((3, 3)));
+    // exactly n remaining
+    assert_eq!(exec(take_at_most(3), "123"), Some((3, 3)));
+    // fewer
+    assert_eq!(exec(take_at_most(3), "12"), Some((2, 2)));
+    // zero remaining
+    assert_eq!(exec(take_at_most(3), ""), Some((0, 0)));
+    // by chars
+    assert_eq!(exec(take_at_most(3), "好好"), Some((2, 6)));
+  }
+
+  #[test]
+  fn test_skip_at_most() {
+    let skip = skip_at_most(3, |c| c != ';');
+    assert_eq!(exec(skip, "1234"), Some((3, 3)));
+    assert_eq!(exec(skip, "123"), Some((3, 3)));
+    assert_eq!(exec(skip, "1;2"), Some((1, 1)));
+    assert_eq!(exec(skip, ";"), Some((0, 0)));
+    assert_eq!(exec(skip, ""), Some((0, 0)));
+    assert_eq!(exec(skip, "好;"), Some((1, 3)));
+  }
+
   fn _take_debug() {
     let _ = format!("{:?}", take(0));
   }
@@ -108,4 +501,15 @@ mod tests {
     let _c = c;
     let _c = c.clone();
   }
+
+  fn _take_at_most_debug_clone_copy() {
+    let c = take_at_most(0);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+    let c = skip_at_most(0, |_| true);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
 }
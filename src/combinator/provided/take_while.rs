@@ -0,0 +1,268 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+/// See [`take_while`] and [`take_while_min_max`].
+#[derive(Copy, Clone)]
+pub struct TakeWhile<F> {
+  predicate: F,
+  min: usize,
+  max: usize,
+}
+
+impl<F> TakeWhile<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F, min: usize, max: usize) -> Self {
+    Self {
+      predicate,
+      min,
+      max,
+    }
+  }
+}
+
+impl<F> std::fmt::Debug for TakeWhile<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TakeWhile")
+      .field("min", &self.min)
+      .field("max", &self.max)
+      .finish()
+  }
+}
+
+/// Scan the leading chars of `rest` which satisfy `predicate`, at most `max` chars.
+/// Return the number of chars and the digested bytes.
+#[inline]
+fn scan(rest: &str, max: usize, predicate: impl Fn(char) -> bool) -> (usize, usize) {
+  let mut count = 0;
+  for (i, c) in rest.char_indices() {
+    if count == max || !predicate(c) {
+      return (count, i);
+    }
+    count += 1;
+  }
+  (count, rest.len())
+}
+
+unsafe impl<F: Fn(char) -> bool> Action for TakeWhile<F> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let (count, digested) = scan(input.instant.rest(), self.max, &self.predicate);
+    // SAFETY: `digested` is a char boundary
+    (count >= self.min).then(|| unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to take the undigested [`char`]s which satisfy the `predicate`
+/// in one scan.
+///
+/// The combinator will always accept, even with 0 bytes digested.
+/// This is a faster version of `next(predicate) * (..)`.
+/// To require at least one char, use [`take_while_min_max`].
+/// # Caveats
+/// Be careful with infinite loops since this may accept 0 chars.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_while, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// take_while(|c| c.is_ascii_digit())
+/// # );
+/// ```
+#[inline]
+pub const fn take_while<F: Fn(char) -> bool>(predicate: F) -> Combinator<TakeWhile<F>> {
+  take_while_min_max(predicate, 0, usize::MAX)
+}
+
+/// Returns a combinator to take at least `min` and at most `max` undigested [`char`]s
+/// which satisfy the `predicate` in one scan.
+///
+/// The combinator will stop at the first char which doesn't satisfy the `predicate`
+/// or after `max` chars are taken,
+/// and reject if fewer than `min` chars are taken.
+/// If `min` is greater than `max`, the combinator will always reject.
+///
+/// This is a faster version of `next(predicate) * (min..=max)`.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_while_min_max, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// // an identifier with at least one char
+/// # t(
+/// take_while_min_max(|c| c.is_ascii_alphanumeric(), 1, usize::MAX)
+/// # );
+/// // a 2-digit hex
+/// # t(
+/// take_while_min_max(|c| c.is_ascii_hexdigit(), 2, 2)
+/// # );
+/// ```
+#[inline]
+pub const fn take_while_min_max<F: Fn(char) -> bool>(
+  predicate: F,
+  min: usize,
+  max: usize,
+) -> Combinator<TakeWhile<F>> {
+  Combinator::new(TakeWhile::new(predicate, min, max))
+}
+
+/// See [`take_till`].
+#[derive(Copy, Clone)]
+pub struct TakeTill<F> {
+  predicate: F,
+}
+
+impl<F> TakeTill<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F) -> Self {
+    Self { predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for TakeTill<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TakeTill").finish()
+  }
+}
+
+unsafe impl<F: Fn(char) -> bool> Action for TakeTill<F> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let (_, digested) = scan(input.instant.rest(), usize::MAX, |c| !(self.predicate)(c));
+    // SAFETY: `digested` is a char boundary
+    Some(unsafe { input.instant.accept_unchecked(digested) })
+  }
+}
+
+/// Returns a combinator to take the undigested [`char`]s
+/// until the first char which satisfies the `predicate` (exclusive) or the end of input.
+///
+/// The combinator will always accept, even with 0 bytes digested.
+/// To stop at a fixed set of chars, [`eat_until`](crate::combinator::eat_until) is faster.
+/// # Caveats
+/// Be careful with infinite loops since this may accept 0 chars.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{take_till, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// take_till(|c| c.is_whitespace())
+/// # );
+/// ```
+#[inline]
+pub const fn take_till<F: Fn(char) -> bool>(predicate: F) -> Combinator<TakeTill<F>> {
+  Combinator::new(TakeTill::new(predicate))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = str, State = (), Heap = (), Value = ()>,
+    input: &str,
+    digested: Option<usize>,
+  ) {
+    assert_eq!(
+      action
+        .exec(Input {
+          instant: &Instant::new(input),
+          state: &mut (),
+          heap: &mut ()
+        })
+        .map(|o| o.digested),
+      digested
+    )
+  }
+
+  #[test]
+  fn combinator_take_while() {
+    let digit = |c: char| c.is_ascii_digit();
+    helper(take_while(digit), "123abc", Some(3));
+    helper(take_while(digit), "123", Some(3));
+    helper(take_while(digit), "abc", Some(0));
+    helper(take_while(digit), "", Some(0));
+    // count by chars, digest by bytes
+    helper(take_while(|c| c != 'a'), "好好a", Some(6));
+    helper(take_while(|_| true), "好好", Some(6));
+  }
+
+  #[test]
+  fn combinator_take_while_min_max() {
+    let digit = |c: char| c.is_ascii_digit();
+    // min
+    helper(take_while_min_max(digit, 1, usize::MAX), "123a", Some(3));
+    helper(take_while_min_max(digit, 1, usize::MAX), "a", None);
+    helper(take_while_min_max(digit, 1, usize::MAX), "", None);
+    helper(take_while_min_max(digit, 3, usize::MAX), "123", Some(3));
+    helper(take_while_min_max(digit, 3, usize::MAX), "12a", None);
+    // max
+    helper(take_while_min_max(digit, 0, 2), "123", Some(2));
+    helper(take_while_min_max(digit, 0, 2), "12", Some(2));
+    helper(take_while_min_max(digit, 0, 2), "1", Some(1));
+    helper(take_while_min_max(digit, 2, 2), "123", Some(2));
+    helper(take_while_min_max(digit, 2, 2), "1a", None);
+    helper(take_while_min_max(digit, 0, 0), "123", Some(0));
+    // max counts chars
+    helper(take_while_min_max(|_| true, 2, 2), "好好好", Some(6));
+    helper(take_while_min_max(|_| true, 2, 2), "好", None);
+    // min > max
+    helper(take_while_min_max(digit, 3, 2), "1234", None);
+    // empty input
+    helper(take_while_min_max(digit, 0, 2), "", Some(0));
+  }
+
+  #[test]
+  fn combinator_take_till() {
+    let ws = |c: char| c.is_whitespace();
+    helper(take_till(ws), "abc def", Some(3));
+    helper(take_till(ws), "abc", Some(3));
+    helper(take_till(ws), " abc", Some(0));
+    helper(take_till(ws), "", Some(0));
+    helper(take_till(|c| c == '好'), "a好", Some(1));
+    helper(take_till(|c| c == 'a'), "好a", Some(3));
+  }
+
+  #[test]
+  fn combinator_take_while_compose() {
+    let ident = || {
+      take_while_min_max(|c| c.is_ascii_alphabetic() || c == '_', 1, 1)
+        + take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    helper(ident(), "_a1 b", Some(3));
+    helper(ident(), "1a", None);
+  }
+
+  fn _take_while_debug() {
+    let _ = format!("{:?}", take_while(|_| true));
+    let _ = format!("{:?}", take_till(|_| true));
+  }
+
+  fn _take_while_clone_copy() {
+    let c = take_while(|_| true);
+    let _c = c;
+    let _c = c.clone();
+    let c = take_till(|_| true);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -1,11 +1,46 @@
 use crate::{
   action::{Action, Input, Output},
   combinator::{provided::create_value_combinator, Combinator},
+  idx,
   instant::Instant,
+  range::Range,
 };
 
 create_value_combinator!(Till, "See [`till`].");
 
+/// The byte length of a pattern of [`till`]-like combinators.
+pub(crate) trait Terminator {
+  fn terminator_len(&self) -> usize;
+}
+
+impl Terminator for &str {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl Terminator for String {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    self.len()
+  }
+}
+
+impl Terminator for char {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    self.len_utf8()
+  }
+}
+
+impl Terminator for () {
+  #[inline]
+  fn terminator_len(&self) -> usize {
+    0
+  }
+}
+
 unsafe impl Action for Till<&str> {
   type Text = str;
   type State = ();
@@ -20,7 +55,7 @@ unsafe impl Action for Till<&str> {
     input.instant.rest().find(self.inner).map(|i| unsafe {
       input
         .instant
-        .accept_unchecked(i.unchecked_add(self.inner.len()))
+        .accept_unchecked(idx::add(i, self.inner.len()))
     })
   }
 }
@@ -39,7 +74,7 @@ unsafe impl Action for Till<String> {
     input.instant.rest().find(&self.inner).map(|i| unsafe {
       input
         .instant
-        .accept_unchecked(i.unchecked_add(self.inner.len()))
+        .accept_unchecked(idx::add(i, self.inner.len()))
     })
   }
 }
@@ -58,7 +93,7 @@ unsafe impl Action for Till<char> {
     input.instant.rest().find(self.inner).map(|i| unsafe {
       input
         .instant
-        .accept_unchecked(i.unchecked_add(self.inner.len_utf8()))
+        .accept_unchecked(idx::add(i, self.inner.len_utf8()))
     })
   }
 }
@@ -107,6 +142,226 @@ pub const fn till<T>(pattern: T) -> Combinator<Till<T>> {
   Combinator::new(Till::new(pattern))
 }
 
+/// See [`till_at_most`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillAtMost<T> {
+  n: usize,
+  till: Till<T>,
+}
+
+impl<T> TillAtMost<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize, pattern: T) -> Self {
+    Self {
+      n,
+      till: Till::new(pattern),
+    }
+  }
+}
+
+unsafe impl<T> Action for TillAtMost<T>
+where
+  Till<T>: Action<Text = str, State = (), Heap = (), Value = ()>,
+{
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let mut end = self.n.min(rest.len());
+    // at most 3 steps back to a char boundary
+    while !rest.is_char_boundary(end) {
+      // SAFETY: 0 is always a char boundary so the loop stops before underflow
+      end = idx::unchecked!(idx::sub(end, 1));
+    }
+    // only scan the first `n` bytes
+    self.till.exec(Input {
+      // SAFETY: `end` is a char boundary in bounds
+      instant: &Instant::new(idx::unchecked!(idx::slice_to(rest, end))),
+      state: &mut (),
+      heap: &mut (),
+    })
+  }
+}
+
+/// Return a combinator like [`till`],
+/// but only the first `n` undigested bytes are scanned,
+/// so the pattern must end within `n` bytes.
+///
+/// Use this instead of [`till`] on untrusted inputs
+/// (e.g. to find the end of a string literal or a comment),
+/// so an unterminated token won't scan the whole input.
+/// With `()`, this will eat at most `n` bytes.
+/// See [`Combinator::max_len`] for more information.
+/// # Examples
+/// ```
+/// # use whitehole::combinator::{till_at_most, Combinator};
+/// # use whitehole::action::Action;
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// till_at_most(1024, '"') // with char
+/// # );
+/// ```
+#[inline]
+pub const fn till_at_most<T>(n: usize, pattern: T) -> Combinator<TillAtMost<T>> {
+  Combinator::new(TillAtMost::new(n, pattern))
+}
+
+/// Return a combinator to eat all the bytes till the end of the text.
+/// This is the same as `till(())`.
+/// # Caveats
+/// This will accept 0 bytes when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{till_eof, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// till_eof()
+/// # );
+/// ```
+#[inline]
+pub const fn till_eof() -> Combinator<Till<()>> {
+  till(())
+}
+
+/// See [`till_exclusive`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillExclusive<T> {
+  till: Till<T>,
+}
+
+impl<T> TillExclusive<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self {
+      till: Till::new(pattern),
+    }
+  }
+}
+
+#[allow(private_bounds)]
+unsafe impl<T: Terminator> Action for TillExclusive<T>
+where
+  Till<T>: Action<Text = str, State = (), Heap = (), Value = ()>,
+{
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.till.exec(input).map(|output| Output {
+      value: (),
+      // SAFETY: `till` digests the pattern at the end, so this won't underflow,
+      // and the start of the pattern is a char boundary
+      digested: idx::unchecked!(idx::sub(output.digested, self.till.inner.terminator_len())),
+    })
+  }
+}
+
+/// Return a combinator like [`till`],
+/// but stop before the first occurrence of the pattern (exclusive),
+/// so the pattern is left undigested.
+///
+/// With `()`, this is the same as [`till_eof`].
+/// # Caveats
+/// Empty patterns and `()` may accept 0 bytes.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{till_exclusive, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str>>) {}
+/// # t(
+/// till_exclusive('\n') // with char
+/// # );
+/// # t(
+/// till_exclusive("*/") // with &str
+/// # );
+/// ```
+#[inline]
+pub const fn till_exclusive<T>(pattern: T) -> Combinator<TillExclusive<T>> {
+  Combinator::new(TillExclusive::new(pattern))
+}
+
+/// See [`till_inclusive`].
+#[derive(Copy, Clone, Debug)]
+pub struct TillInclusive<T> {
+  till: Till<T>,
+}
+
+impl<T> TillInclusive<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self {
+      till: Till::new(pattern),
+    }
+  }
+}
+
+#[allow(private_bounds)]
+unsafe impl<T: Terminator> Action for TillInclusive<T>
+where
+  Till<T>: Action<Text = str, State = (), Heap = (), Value = ()>,
+{
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = Range;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let start = input.instant.digested();
+    self.till.exec(input).map(|output| {
+      // SAFETY: `till` digests the pattern at the end, so this won't underflow
+      let body = idx::unchecked!(idx::sub(output.digested, self.till.inner.terminator_len()));
+      Output {
+        // SAFETY: the range is inside the whole text, whose length is a valid usize
+        value: start..idx::unchecked!(idx::add(start, body)),
+        digested: output.digested,
+      }
+    })
+  }
+}
+
+/// Return a combinator like [`till`] which consumes the pattern (inclusive),
+/// but the value is the byte range before the pattern.
+///
+/// Like [`Combinator::range`], the range is relative to the start of the whole text.
+/// This is useful when the terminator should be consumed but excluded from the body,
+/// e.g. the closing `*/` of a block comment.
+/// # Caveats
+/// Empty patterns and `()` may accept 0 bytes.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{eat, till_inclusive}, parser::Parser};
+/// let entry = eat("/*") + till_inclusive("*/").tuple();
+/// let output = Parser::builder().entry(entry).build("/* x */").next().unwrap();
+/// assert_eq!(output.digested, 7);
+/// assert_eq!(output.value, (2..5,));
+/// ```
+#[inline]
+pub const fn till_inclusive<T>(pattern: T) -> Combinator<TillInclusive<T>> {
+  Combinator::new(TillInclusive::new(pattern))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -152,4 +407,110 @@ mod tests {
     helper(till(()), "123", Some(3));
     helper(till(()), "", Some(0));
   }
+
+  #[test]
+  fn test_till_at_most() {
+    helper(till_at_most(4, ';'), "123;456", Some(4));
+    helper(till_at_most(3, ';'), "123;456", None);
+    helper(till_at_most(100, ';'), "123456", None);
+    helper(till_at_most(6, "end"), "123end456", Some(6));
+    helper(till_at_most(5, "end"), "123end456", None);
+    helper(till_at_most(6, "end".to_string()), "123end456", Some(6));
+    helper(till_at_most(0, ""), "123", Some(0));
+    // char boundary
+    helper(till_at_most(3, '好'), "a好", None);
+    helper(till_at_most(4, '好'), "a好", Some(4));
+    // ()
+    helper(till_at_most(2, ()), "123", Some(2));
+    helper(till_at_most(2, ()), "a好", Some(1));
+    helper(till_at_most(5, ()), "123", Some(3));
+
+    // debug
+    let _ = format!("{:?}", till_at_most(1, ';'));
+  }
+
+  fn _till_at_most_clone_copy() {
+    let c = till_at_most(1, ';');
+    let _c = c;
+    let _c = c.clone();
+  }
+
+  #[test]
+  fn test_till_eof() {
+    helper(till_eof(), "123", Some(3));
+    helper(till_eof(), "", Some(0));
+  }
+
+  #[test]
+  fn test_till_exclusive() {
+    helper(till_exclusive(';'), "123;456", Some(3));
+    helper(till_exclusive(';'), "123456", None);
+    helper(till_exclusive("end"), "123end456", Some(3));
+    helper(till_exclusive("end".to_string()), "end", Some(0));
+    helper(till_exclusive(""), "123", Some(0));
+    helper(till_exclusive(()), "123", Some(3));
+    // multi-byte terminators
+    helper(till_exclusive('好'), "a好b", Some(1));
+    helper(till_exclusive("好b"), "好a好b", Some(4));
+
+    // debug
+    let _ = format!("{:?}", till_exclusive(';'));
+  }
+
+  #[test]
+  fn test_till_inclusive() {
+    fn exec(
+      action: impl Action<Text = str, State = (), Heap = (), Value = Range>,
+      instant: &Instant<&str>,
+    ) -> Option<(Range, usize)> {
+      action
+        .exec(Input {
+          instant,
+          state: &mut (),
+          heap: &mut (),
+        })
+        .map(|o| (o.value, o.digested))
+    }
+
+    let start = |s| Instant::new(s);
+    assert_eq!(
+      exec(till_inclusive(';'), &start("123;456")),
+      Some((0..3, 4))
+    );
+    assert_eq!(exec(till_inclusive(';'), &start("123456")), None);
+    assert_eq!(
+      exec(till_inclusive("end"), &start("123end456")),
+      Some((0..3, 6))
+    );
+    assert_eq!(
+      exec(till_inclusive("end".to_string()), &start("end")),
+      Some((0..0, 3))
+    );
+    assert_eq!(exec(till_inclusive(()), &start("123")), Some((0..3, 3)));
+    // multi-byte terminators
+    assert_eq!(exec(till_inclusive('好'), &start("a好b")), Some((0..1, 4)));
+    assert_eq!(
+      exec(till_inclusive("*/好"), &start("好*/好")),
+      Some((0..3, 8))
+    );
+    // the range is relative to the whole text
+    assert_eq!(
+      exec(till_inclusive("*/"), &unsafe {
+        start("/* x */").to_digested_unchecked(2)
+      }),
+      Some((2..5, 5))
+    );
+
+    // debug
+    let _ = format!("{:?}", till_inclusive(';'));
+  }
+
+  fn _till_exclusive_inclusive_clone_copy() {
+    let c = till_exclusive(';');
+    let _c = c;
+    let _c = c.clone();
+    let c = till_inclusive(';');
+    let _c = c;
+    let _c = c.clone();
+  }
 }
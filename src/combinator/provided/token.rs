@@ -0,0 +1,152 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{write_head, Combinator, Describe},
+  digest::Unit,
+  instant::Instant,
+};
+use std::{fmt, marker::PhantomData};
+
+/// See [`token_by`].
+pub struct TokenBy<F, T> {
+  condition: F,
+  _unit: PhantomData<fn(&T)>,
+}
+
+impl<F, T> TokenBy<F, T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(condition: F) -> Self {
+    Self {
+      condition,
+      _unit: PhantomData,
+    }
+  }
+}
+
+// implement manually to avoid the `T: Clone` bound
+impl<F: Clone, T> Clone for TokenBy<F, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self::new(self.condition.clone())
+  }
+}
+
+impl<F: Copy, T> Copy for TokenBy<F, T> {}
+
+impl<F, T> fmt::Debug for TokenBy<F, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TokenBy").finish()
+  }
+}
+
+impl<F, T> Describe for TokenBy<F, T> {
+  fn describe(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_head(f, "TokenBy", module_path!())?;
+    f.write_str(" <fn>)")
+  }
+}
+
+unsafe impl<T: Unit, F: Fn(&T) -> bool> Action for TokenBy<F, T> {
+  type Text = [T];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let next = input.instant.rest().first()?;
+    if !(self.condition)(next) {
+      return None;
+    }
+    Some(unsafe { input.instant.accept_unchecked(1) })
+  }
+}
+
+/// Returns a combinator to match the next undigested element of a `[T]` by the condition,
+/// e.g. a token produced by a lexer.
+/// The combinator will reject if not matched or the rest is empty.
+///
+/// `T` must implement [`Unit`] so `[T]` is digest-able.
+/// Use [`Combinator::select`] with [`Accepted::content`](crate::combinator::Accepted::content)
+/// to get the matched element.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{token_by, Combinator}, action::Action, digest::Unit};
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Kind { Num, Plus }
+/// impl Unit for Kind {}
+///
+/// # fn t(_: Combinator<impl Action<Text = [Kind], Value = Kind>>) {}
+/// # t(
+/// token_by(|k| *k == Kind::Num).select(|accepted| accepted.content()[0])
+/// # );
+/// ```
+#[inline]
+pub const fn token_by<T: Unit, F: Fn(&T) -> bool>(condition: F) -> Combinator<TokenBy<F, T>> {
+  Combinator::new(TokenBy::new(condition))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, Clone, Copy, PartialEq)]
+  enum Kind {
+    Num,
+    Plus,
+  }
+  impl Unit for Kind {}
+
+  fn helper(
+    action: impl Action<Text = [Kind], State = (), Heap = (), Value = ()>,
+    input: &[Kind],
+  ) -> Option<usize> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested)
+  }
+
+  #[test]
+  fn combinator_token_by() {
+    let num = || token_by(|k| *k == Kind::Num);
+    assert_eq!(helper(num(), &[Kind::Num, Kind::Plus]), Some(1));
+    // wrong kind
+    assert_eq!(helper(num(), &[Kind::Plus, Kind::Num]), None);
+    // empty
+    assert_eq!(helper(num(), &[]), None);
+    // composed
+    let expr = || num() + (token_by(|k| *k == Kind::Plus) + num()) * (..);
+    assert_eq!(
+      helper(expr(), &[Kind::Num, Kind::Plus, Kind::Num, Kind::Plus]),
+      Some(3)
+    );
+    assert_eq!(helper(expr(), &[Kind::Plus]), None);
+
+    // ensure the combinator is copyable and clone-able
+    let c = num();
+    let _c = c;
+    let _c = c.clone();
+    assert_eq!(format!("{:?}", c), "Combinator { action: TokenBy }");
+  }
+
+  #[test]
+  fn combinator_token_by_range() {
+    let tokens = [Kind::Plus, Kind::Num, Kind::Num];
+    let entry = token_by(|k: &Kind| *k == Kind::Num).range();
+    let mut parser = crate::parser::Parser::builder()
+      .entry(entry)
+      .build_at(&tokens[..], 1)
+      .unwrap();
+    assert_eq!(parser.next().unwrap().value.range, 1..2);
+    assert_eq!(parser.next().unwrap().value.range, 2..3);
+    assert!(parser.next().is_none());
+    assert!(parser.is_done());
+  }
+}
@@ -0,0 +1,312 @@
+//! Combinators for parsing `[u16]`, e.g. UTF-16 text.
+//!
+//! All lengths and offsets are counted in code units (`u16`), not chars or bytes.
+//! A surrogate pair (e.g. an emoji) is 2 units,
+//! so [`take(1)`](take) or [`next`] will only digest half of it.
+//! # Examples
+//! ```
+//! use whitehole::{combinator::u16s::{eat, next}, parser::Parser};
+//!
+//! let text: Vec<u16> = "123,456".encode_utf16().collect();
+//! let digit = next(|u| (b'0' as u16..=b'9' as u16).contains(&u));
+//! let entry = ((digit * (1..)) * (1..)).sep(eat(b',' as u16));
+//! assert_eq!(
+//!   Parser::builder().entry(entry).build(text.as_slice()).next().unwrap().digested,
+//!   7
+//! );
+//! ```
+
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{
+    provided::{create_closure_combinator, create_value_combinator},
+    write_head, Combinator, Describe,
+  },
+  instant::Instant,
+};
+
+create_value_combinator!(Eat, "See [`eat`].");
+
+unsafe impl Action for Eat<u16> {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .first()
+      .is_some_and(|&c| c == self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(1) })
+  }
+}
+
+unsafe impl Action for Eat<&[u16]> {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .starts_with(self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+unsafe impl<const N: usize> Action for Eat<&[u16; N]> {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .starts_with(self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(N) })
+  }
+}
+
+unsafe impl Action for Eat<Vec<u16>> {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input
+      .instant
+      .rest()
+      .starts_with(&self.inner)
+      .then(|| unsafe { input.instant.accept_unchecked(self.inner.len()) })
+  }
+}
+
+/// Returns a combinator to eat from the head of [`Instant::rest`] by the provided pattern.
+/// The combinator will reject if the pattern is not found.
+/// # Caveats
+/// Empty patterns are allowed and will always accept 0 units,
+/// even when [`Instant::rest`] is empty.
+/// Be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{u16s, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u16]>>) {}
+/// # t(
+/// u16s::eat(b'a' as u16) // eat by a code unit (u16)
+/// # );
+/// # t(
+/// u16s::eat(&[0xD83D, 0xDE00]) // eat by &[u16] or &[u16; N]
+/// # );
+/// # t(
+/// u16s::eat("true".encode_utf16().collect::<Vec<_>>()) // eat by Vec<u16>
+/// # );
+/// ```
+#[inline]
+pub const fn eat<T>(pattern: T) -> Combinator<Eat<T>> {
+  Combinator::new(Eat::new(pattern))
+}
+
+/// See [`take`].
+#[derive(Copy, Clone, Debug)]
+pub struct Take {
+  n: usize,
+}
+
+impl Take {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+impl Describe for Take {
+  fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write_head(f, "Take", module_path!())?;
+    write!(f, " {})", self.n)
+  }
+}
+
+unsafe impl Action for Take {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    input.instant.accept(self.n)
+  }
+}
+
+/// Returns a combinator to take the next `n` undigested code units.
+/// The combinator will reject if there are not enough units.
+///
+/// `0` is allowed but be careful with infinite loops.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{u16s, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u16]>>) {}
+/// # t(
+/// u16s::take(10) // take 10 code units
+/// # );
+/// ```
+#[inline]
+pub const fn take(n: usize) -> Combinator<Take> {
+  Combinator::new(Take::new(n))
+}
+
+create_closure_combinator!(Next, "See [`next`].");
+
+unsafe impl<F: Fn(u16) -> bool> Action for Next<F> {
+  type Text = [u16];
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let &next = input.instant.rest().first()?;
+    if !(self.inner)(next) {
+      return None;
+    }
+    Some(unsafe { input.instant.accept_unchecked(1) })
+  }
+}
+
+/// Returns a combinator to match the next undigested code unit by the condition.
+/// The combinator will reject if not matched.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{u16s, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = [u16]>>) {}
+/// // match one ascii digit
+/// # t(
+/// u16s::next(|u| (b'0' as u16..=b'9' as u16).contains(&u))
+/// # );
+/// ```
+#[inline]
+pub const fn next<F: Fn(u16) -> bool>(condition: F) -> Combinator<Next<F>> {
+  Combinator::new(Next::new(condition))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::Parser;
+
+  fn utf16(s: &str) -> Vec<u16> {
+    s.encode_utf16().collect()
+  }
+
+  fn helper(
+    action: impl Action<Text = [u16], State = (), Heap = (), Value = ()>,
+    input: &str,
+  ) -> Option<usize> {
+    action
+      .exec(Input {
+        instant: &Instant::new(utf16(input).as_slice()),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| o.digested)
+  }
+
+  #[test]
+  fn u16s_eat() {
+    assert_eq!(helper(eat(b'a' as u16), "abc"), Some(1));
+    assert_eq!(helper(eat(b'b' as u16), "abc"), None);
+    assert_eq!(helper(eat(b'a' as u16), ""), None);
+    assert_eq!(helper(eat(utf16("ab").as_slice()), "abc"), Some(2));
+    assert_eq!(helper(eat(&[b'a' as u16, b'c' as u16]), "abc"), None);
+    // a surrogate pair is 2 units
+    assert_eq!(helper(eat(utf16("😀")), "😀a"), Some(2));
+    assert_eq!(helper(eat(0xD83D), "😀a"), Some(1));
+    // empty pattern
+    assert_eq!(helper(eat(vec![]), ""), Some(0));
+  }
+
+  #[test]
+  fn u16s_take_next() {
+    assert_eq!(helper(take(2), "abc"), Some(2));
+    assert_eq!(helper(take(4), "abc"), None);
+    assert_eq!(helper(take(0), ""), Some(0));
+    // unit-based, not char-based
+    assert_eq!(helper(take(1), "😀"), Some(1));
+    assert_eq!(helper(take(2), "好😀"), Some(2));
+    assert_eq!(helper(next(|u| u == b'a' as u16), "abc"), Some(1));
+    assert_eq!(helper(next(|u| u == b'a' as u16), "bc"), None);
+    assert_eq!(helper(next(|_| true), ""), None);
+    assert_eq!(helper(next(|u| u < 0xD800) * (1..), "好a😀"), Some(2));
+  }
+
+  #[test]
+  fn u16s_parser() {
+    // parse a list of numbers and surrogate pairs, collect the unit ranges
+    let text = utf16("123,😀,456");
+    let item = || {
+      (next(|u| (b'0' as u16..=b'9' as u16).contains(&u)) * (1..))
+        | (next(|u| (0xD800..0xDC00).contains(&u)) + next(|u| (0xDC00..0xE000).contains(&u)))
+    };
+    let mut parser = Parser::builder()
+      .entry(item().range() + eat(b',' as u16).optional())
+      .build(text.as_slice());
+    let ranges: Vec<_> = (&mut parser).map(|o| o.value.range).collect();
+    assert_eq!(ranges, vec![0..3, 4..6, 7..10]);
+    assert!(parser.instant.rest().is_empty());
+    assert_eq!(parser.instant.digested(), 10);
+
+    // the content is a slice of units
+    let output = Parser::builder()
+      .entry(item().select(|a| String::from_utf16(a.content()).unwrap()))
+      .build(&text[4..])
+      .next()
+      .unwrap();
+    assert_eq!(output.value, "😀");
+    assert_eq!(output.digested, 2);
+  }
+
+  fn _u16s_debug_clone_copy() {
+    let c = eat(1u16);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+    let c = take(1);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+    let c = next(|_| true);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,234 @@
+use crate::{
+  action::{Action, Input, Output},
+  combinator::{Combinator, Contextual},
+  instant::Instant,
+};
+
+/// See [`bounded_uint`] and [`uint_with_max_digits`].
+#[derive(Copy, Clone, Debug)]
+pub struct BoundedUint {
+  min: u64,
+  max: u64,
+  max_digits: usize,
+  reject_leading_zeros: bool,
+}
+
+impl BoundedUint {
+  /// Create a new instance.
+  /// Leading zeros are accepted by default.
+  #[inline]
+  pub const fn new(min: u64, max: u64, max_digits: usize) -> Self {
+    Self {
+      min,
+      max,
+      max_digits,
+      reject_leading_zeros: false,
+    }
+  }
+
+  #[inline]
+  pub(super) const fn with_leading_zeros_rejected(mut self) -> Self {
+    self.reject_leading_zeros = true;
+    self
+  }
+}
+
+impl Combinator<BoundedUint> {
+  /// Reject if the digits have leading zeros, e.g. `"01"`.
+  /// A single `"0"` is still accepted if it is in the bounds.
+  #[inline]
+  pub const fn reject_leading_zeros(self) -> Self {
+    Self::new(self.action.with_leading_zeros_rejected())
+  }
+}
+
+impl<State, Heap> Combinator<Contextual<BoundedUint, State, Heap>> {
+  /// See [`Combinator::<BoundedUint>::reject_leading_zeros`].
+  #[inline]
+  pub const fn reject_leading_zeros(self) -> Self {
+    Self::new(Contextual::new(
+      self.action.action.with_leading_zeros_rejected(),
+    ))
+  }
+}
+
+/// Scan ascii digits and return the value and the number of digits.
+#[inline]
+pub(super) fn scan_bounded_uint(bytes: &[u8], uint: &BoundedUint) -> Option<(u64, usize)> {
+  let mut value: u64 = 0;
+  let mut digits = 0;
+  for &b in bytes.iter().take(uint.max_digits) {
+    if !b.is_ascii_digit() {
+      break;
+    }
+    // early exit before overflow, no re-scan is needed
+    value = value
+      .checked_mul(10)
+      .and_then(|v| v.checked_add((b - b'0') as u64))
+      .filter(|v| *v <= uint.max)?;
+    digits += 1;
+  }
+
+  if digits == 0
+    || value < uint.min
+    || (uint.reject_leading_zeros && digits > 1 && bytes[0] == b'0')
+  {
+    return None;
+  }
+  Some((value, digits))
+}
+
+unsafe impl Action for BoundedUint {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = u64;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    scan_bounded_uint(input.instant.rest().as_bytes(), self).map(|(value, digested)| Output {
+      value,
+      // ascii digits are always on char boundaries
+      digested,
+    })
+  }
+}
+
+/// Returns a combinator to parse ascii digits as an unsigned integer
+/// within `min..=max` (inclusive).
+///
+/// The digits are scanned greedily and the value is accumulated on the fly.
+/// The combinator will reject as soon as the value exceeds `max`,
+/// so there is no overflow and no re-scan.
+/// It will also reject if there are no digits or the value is less than `min`.
+///
+/// Leading zeros are accepted by default,
+/// use [`Combinator::reject_leading_zeros`] to reject them.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{bounded_uint, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = u64>>) {}
+/// // port number
+/// # t(
+/// bounded_uint(0, 65535)
+/// # );
+/// // month without leading zeros
+/// # t(
+/// bounded_uint(1, 12).reject_leading_zeros()
+/// # );
+/// ```
+#[inline]
+pub const fn bounded_uint(min: u64, max: u64) -> Combinator<BoundedUint> {
+  Combinator::new(BoundedUint::new(min, max, usize::MAX))
+}
+
+/// Returns a combinator to parse at most `n` ascii digits as an unsigned integer.
+///
+/// Digits after the first `n` digits won't be digested.
+/// The combinator will reject if there are no digits or the value overflows [`u64`].
+///
+/// Leading zeros are accepted by default,
+/// use [`Combinator::reject_leading_zeros`] to reject them.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::{uint_with_max_digits, Combinator}, action::Action};
+/// # fn t(_: Combinator<impl Action<Text = str, Value = u64>>) {}
+/// // year
+/// # t(
+/// uint_with_max_digits(4)
+/// # );
+/// ```
+#[inline]
+pub const fn uint_with_max_digits(n: usize) -> Combinator<BoundedUint> {
+  Combinator::new(BoundedUint::new(0, u64::MAX, n))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = str, State = (), Heap = (), Value = u64>,
+    input: &str,
+  ) -> Option<(u64, usize)> {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .map(|o| (o.value, o.digested))
+  }
+
+  #[test]
+  fn combinator_bounded_uint() {
+    let port = bounded_uint(0, 65535);
+    assert_eq!(helper(port, "0"), Some((0, 1)));
+    assert_eq!(helper(port, "65535"), Some((65535, 5)));
+    assert_eq!(helper(port, "65535a"), Some((65535, 5)));
+    // one over
+    assert_eq!(helper(port, "65536"), None);
+    // no digits
+    assert_eq!(helper(port, ""), None);
+    assert_eq!(helper(port, "a"), None);
+    // no overflow with very long digits
+    assert_eq!(helper(port, "99999999999999999999999999"), None);
+    assert_eq!(
+      helper(bounded_uint(0, u64::MAX), "18446744073709551615"),
+      Some((u64::MAX, 20))
+    );
+    assert_eq!(
+      helper(bounded_uint(0, u64::MAX), "18446744073709551616"),
+      None
+    );
+
+    let month = bounded_uint(1, 12);
+    assert_eq!(helper(month, "1"), Some((1, 1)));
+    assert_eq!(helper(month, "12"), Some((12, 2)));
+    // below min
+    assert_eq!(helper(month, "0"), None);
+    // one over
+    assert_eq!(helper(month, "13"), None);
+    // leading zeros are accepted by default
+    assert_eq!(helper(month, "01"), Some((1, 2)));
+    assert_eq!(helper(month, "0012"), Some((12, 4)));
+    // reject leading zeros
+    let month = month.reject_leading_zeros();
+    assert_eq!(helper(month, "01"), None);
+    assert_eq!(helper(month, "10"), Some((10, 2)));
+    assert_eq!(
+      helper(bounded_uint(0, 1).reject_leading_zeros(), "0"),
+      Some((0, 1))
+    );
+  }
+
+  #[test]
+  fn combinator_uint_with_max_digits() {
+    let year = uint_with_max_digits(4);
+    assert_eq!(helper(year, "2025"), Some((2025, 4)));
+    assert_eq!(helper(year, "20251"), Some((2025, 4)));
+    assert_eq!(helper(year, "202"), Some((202, 3)));
+    assert_eq!(helper(year, "0202"), Some((202, 4)));
+    assert_eq!(helper(year.reject_leading_zeros(), "0202"), None);
+    assert_eq!(helper(year, ""), None);
+    assert_eq!(helper(uint_with_max_digits(0), "1"), None);
+    // overflow
+    assert_eq!(
+      helper(uint_with_max_digits(30), "99999999999999999999"),
+      None
+    );
+  }
+
+  fn _bounded_uint_debug() {
+    let _ = format!("{:?}", bounded_uint(0, 1));
+  }
+
+  fn _bounded_uint_clone_copy() {
+    let c = bounded_uint(0, 1);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
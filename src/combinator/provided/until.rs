@@ -0,0 +1,123 @@
+use super::{bytes::find_any, create_value_combinator};
+use crate::{
+  action::{Action, Input, Output},
+  combinator::Combinator,
+  instant::Instant,
+};
+
+create_value_combinator!(EatUntil, "See [`eat_until`].");
+
+unsafe impl<const N: usize> Action for EatUntil<[char; N]> {
+  type Text = str;
+  type State = ();
+  type Heap = ();
+  type Value = ();
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    let rest = input.instant.rest();
+    let n = if self.inner.iter().all(char::is_ascii) {
+      // ascii bytes never appear inside a multi-byte char,
+      // so we can scan bytes without decoding chars
+      find_any(rest.as_bytes(), &self.inner.map(|c| c as u8))
+    } else {
+      rest.find(self.inner)
+    }
+    .unwrap_or(rest.len());
+    // SAFETY: the needle starts at a char boundary, and so does the end of the rest
+    Some(unsafe { input.instant.accept_unchecked(n) })
+  }
+}
+
+/// Returns a combinator to eat chars until the first occurrence of any of the `needles`
+/// or the end of input.
+/// The needle is not digested.
+///
+/// If all needles are ASCII, the rest is scanned as bytes one word at a time
+/// without decoding chars,
+/// which is much faster than [`till`](crate::combinator::till) or
+/// [`next`](crate::combinator::next) with repetition for long inputs.
+/// # Caveats
+/// This will accept 0 bytes if the rest starts with a needle, is empty,
+/// or `needles` is empty (then the whole rest is digested).
+/// Be careful with infinite loops,
+/// use [`Combinator::reject`] to reject 0 bytes if needed.
+/// # Examples
+/// ```
+/// # use whitehole::{combinator::eat_until, parser::Parser};
+/// let entry = eat_until(['\n', '\r']);
+/// assert_eq!(
+///   Parser::builder().entry(entry).build("hello\nworld").next().unwrap().digested,
+///   5
+/// );
+/// ```
+#[inline]
+pub const fn eat_until<const N: usize>(needles: [char; N]) -> Combinator<EatUntil<[char; N]>> {
+  Combinator::new(EatUntil::new(needles))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn helper(
+    action: impl Action<Text = str, State = (), Heap = (), Value = ()>,
+    input: &str,
+  ) -> usize {
+    action
+      .exec(Input {
+        instant: &Instant::new(input),
+        state: &mut (),
+        heap: &mut (),
+      })
+      .unwrap()
+      .digested
+  }
+
+  #[test]
+  fn combinator_eat_until() {
+    // at position 0
+    assert_eq!(helper(eat_until([',']), ",abc"), 0);
+    // in the middle
+    assert_eq!(helper(eat_until([',']), "abc,def"), 3);
+    assert_eq!(helper(eat_until([',', ';']), "abc;def,"), 3);
+    assert_eq!(helper(eat_until([',', ';', '\n']), "abcdefghijk\n"), 11);
+    // at the end
+    assert_eq!(helper(eat_until([',']), "abcdefghijklmnopq,"), 17);
+    // absent
+    assert_eq!(helper(eat_until([',']), "abcdefghijklmnopq"), 17);
+    assert_eq!(helper(eat_until([',']), ""), 0);
+    // no needles
+    assert_eq!(helper(eat_until([]), "abc"), 3);
+    // multi-byte chars before the ascii needle
+    assert_eq!(helper(eat_until([',']), "好好好,"), 9);
+    // non-ascii needles
+    assert_eq!(helper(eat_until(['好']), "abc好"), 3);
+    assert_eq!(helper(eat_until([',', '好']), "你好,"), 3);
+    assert_eq!(helper(eat_until(['好']), "你你"), 6);
+    // same as the naive implementation
+    let text = "a,b;好\nc你,,;\n";
+    for needles in [[',', ';'], ['好', '\n'], ['x', 'y']] {
+      for start in text.char_indices().map(|(i, _)| i) {
+        let rest = &text[start..];
+        assert_eq!(
+          helper(eat_until(needles), rest),
+          rest
+            .char_indices()
+            .find(|(_, c)| needles.contains(c))
+            .map_or(rest.len(), |(i, _)| i)
+        );
+      }
+    }
+  }
+
+  fn _eat_until_debug_clone_copy() {
+    let c = eat_until([',']);
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
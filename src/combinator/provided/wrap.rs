@@ -1,6 +1,8 @@
 use crate::{
   action::{Action, Input, Output},
-  combinator::{provided::create_closure_combinator, Combinator, Contextual},
+  combinator::{
+    provided::create_closure_combinator, Combinator, Contextual, FirstSet, LookaheadSet,
+  },
   digest::Digest,
   instant::Instant,
 };
@@ -8,6 +10,22 @@ use crate::{
 create_closure_combinator!(WrapUnchecked, "See [`wrap_unchecked`].");
 create_closure_combinator!(Wrap, "See [`wrap`].");
 
+/// The closure is opaque, so the set is unknown.
+impl<F> FirstSet for Wrap<F> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    None
+  }
+}
+
+/// The closure is opaque, so the set is unknown.
+impl<F> FirstSet for WrapUnchecked<F> {
+  #[inline]
+  fn first_set(&self) -> Option<LookaheadSet> {
+    None
+  }
+}
+
 macro_rules! impl_wrap {
   ($name:ident, $assert:ident, $text:ty) => {
     unsafe impl<
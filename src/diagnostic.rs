@@ -0,0 +1,401 @@
+//! Collect and render diagnostics during parsing.
+//!
+//! [`Diagnostics`] is designed to be used as (or inside) the `Heap` of a parser,
+//! so combinators can report problems without stopping the parsing.
+//! Use [`Combinator::emit`](crate::combinator::Combinator::emit) to push a [`Diag`]
+//! when a combinator is accepted.
+//!
+//! ```
+//! use whitehole::{
+//!   combinator::contextual,
+//!   diagnostic::{Diag, Diagnostics, Severity},
+//!   parser::Parser,
+//! };
+//!
+//! contextual!((), Diagnostics);
+//!
+//! # fn main() {
+//! let entry = eat(';').emit(|accepted| Diag {
+//!   range: accepted.range(),
+//!   severity: Severity::Warning,
+//!   code: None,
+//!   message: "unnecessary semicolon".into(),
+//! });
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(Diagnostics::new())
+//!   .entry(entry)
+//!   .build(";");
+//! parser.next();
+//!
+//! assert_eq!(
+//!   parser.heap.render(";"),
+//!   "warning: unnecessary semicolon\n --> 1:1\n  |\n1 | ;\n  | ^\n"
+//! );
+//! # }
+//! ```
+
+use crate::range::Range;
+use std::{
+  borrow::Cow,
+  collections::HashSet,
+  fmt::{self, Display, Write},
+};
+
+/// The severity of a [`Diag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+  Error,
+  Warning,
+  Note,
+}
+
+impl Display for Severity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Severity::Error => "error",
+      Severity::Warning => "warning",
+      Severity::Note => "note",
+    })
+  }
+}
+
+/// A diagnostic message associated with a byte range of the text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diag {
+  /// The byte range of the text.
+  pub range: Range,
+  pub severity: Severity,
+  /// An optional code to identify the kind of the diagnostic, e.g. `"E001"`.
+  pub code: Option<&'static str>,
+  pub message: String,
+}
+
+/// A de-duplicated and optionally capped collection of [`Diag`]s.
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+  diags: Vec<Diag>,
+  /// Used to de-duplicate [`Self::diags`] without a linear search.
+  seen: HashSet<Diag>,
+  limit: Option<usize>,
+  suppressed: usize,
+}
+
+impl Diagnostics {
+  /// Create a new instance without a limit.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      diags: Vec::new(),
+      seen: HashSet::new(),
+      limit: None,
+      suppressed: 0,
+    }
+  }
+
+  /// Create a new instance which keeps at most `limit` [`Diag`]s.
+  /// Extra [`Diag`]s will be counted by [`Self::suppressed`].
+  #[inline]
+  pub fn with_limit(limit: usize) -> Self {
+    Self {
+      diags: Vec::new(),
+      seen: HashSet::new(),
+      limit: Some(limit),
+      suppressed: 0,
+    }
+  }
+
+  /// Push a [`Diag`].
+  /// Identical [`Diag`]s (with the same range) will only be stored once.
+  /// Return `false` if the [`Diag`] is a duplicate or suppressed by the limit.
+  pub fn push(&mut self, diag: Diag) -> bool {
+    if self.seen.contains(&diag) {
+      return false;
+    }
+    if self.limit.is_some_and(|limit| self.diags.len() >= limit) {
+      self.suppressed += 1;
+      return false;
+    }
+    self.seen.insert(diag.clone());
+    self.diags.push(diag);
+    true
+  }
+
+  /// Stored [`Diag`]s, in the order of being pushed.
+  #[inline]
+  pub fn diags(&self) -> &[Diag] {
+    &self.diags
+  }
+
+  /// How many [`Diag`]s are dropped because of the limit.
+  #[inline]
+  pub const fn suppressed(&self) -> usize {
+    self.suppressed
+  }
+
+  /// Return `true` if no [`Diag`] is stored or suppressed.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.diags.is_empty() && self.suppressed == 0
+  }
+
+  /// Remove all stored [`Diag`]s and reset the suppressed count.
+  /// The limit is kept.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.diags.clear();
+    self.seen.clear();
+    self.suppressed = 0;
+  }
+
+  /// Render all [`Diag`]s in a rustc-like style with source snippets.
+  ///
+  /// Lines and columns are 1-based, columns are counted in [`char`]s.
+  /// Only the first line of a multi-line range is underlined.
+  /// Control chars and bidirectional overrides in the snippets and messages
+  /// are escaped, see [`escape`].
+  /// # Panics
+  /// Panics if any range is out of bounds or not on char boundaries of the `text`.
+  pub fn render(&self, text: &str) -> String {
+    let mut out = String::new();
+    for diag in &self.diags {
+      render_diag(&mut out, diag, text);
+    }
+    if self.suppressed != 0 {
+      writeln!(out, "{} more diagnostics suppressed", self.suppressed).unwrap();
+    }
+    out
+  }
+}
+
+/// Return `true` if the char may mangle the terminal output when printed,
+/// e.g. control chars (including ANSI escape sequences) and bidirectional overrides.
+#[inline]
+fn needs_escape(c: char) -> bool {
+  c.is_control() || matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Escape chars which may mangle the terminal output,
+/// e.g. `"\x1b[31m"` will be escaped to `"\u{1b}[31m"`.
+/// Other chars are kept as is.
+/// # Examples
+/// ```
+/// # use whitehole::diagnostic::escape;
+/// assert_eq!(escape("a\0b"), "a\\0b");
+/// assert_eq!(escape("\x1b[31m"), "\\u{1b}[31m");
+/// ```
+pub fn escape(text: &str) -> Cow<'_, str> {
+  if !text.chars().any(needs_escape) {
+    return Cow::Borrowed(text);
+  }
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    if needs_escape(c) {
+      escaped.extend(c.escape_debug());
+    } else {
+      escaped.push(c);
+    }
+  }
+  Cow::Owned(escaped)
+}
+
+/// The width of the text after [`escape`], in chars.
+#[inline]
+fn escaped_width(text: &str) -> usize {
+  text
+    .chars()
+    .map(|c| {
+      if needs_escape(c) {
+        c.escape_debug().count()
+      } else {
+        1
+      }
+    })
+    .sum()
+}
+
+fn render_diag(out: &mut String, diag: &Diag, text: &str) {
+  let line_start = text[..diag.range.start].rfind('\n').map_or(0, |i| i + 1);
+  let line_end = text[line_start..]
+    .find('\n')
+    .map_or(text.len(), |i| line_start + i);
+  // exclude the '\r' of a CRLF line ending
+  let line_end = if text[line_start..line_end].ends_with('\r') {
+    line_end - 1
+  } else {
+    line_end
+  };
+  let line = &text[line_start..line_end];
+  let line_no = text[..line_start].matches('\n').count() + 1;
+  let col = text[line_start..diag.range.start].chars().count() + 1;
+  // the snippet is escaped so the underline should be calculated based on the escaped width
+  let indent = escaped_width(&text[line_start..diag.range.start]);
+  let underline =
+    escaped_width(&text[diag.range.start..diag.range.end.min(line_end).max(diag.range.start)])
+      .max(1);
+  let gutter = " ".repeat(line_no.to_string().len());
+
+  match diag.code {
+    Some(code) => writeln!(
+      out,
+      "{}[{}]: {}",
+      diag.severity,
+      code,
+      escape(&diag.message)
+    ),
+    None => writeln!(out, "{}: {}", diag.severity, escape(&diag.message)),
+  }
+  .unwrap();
+  writeln!(out, "{}--> {}:{}", gutter, line_no, col).unwrap();
+  writeln!(out, "{} |", gutter).unwrap();
+  writeln!(out, "{} | {}", line_no, escape(line)).unwrap();
+  writeln!(
+    out,
+    "{} | {}{}",
+    gutter,
+    " ".repeat(indent),
+    "^".repeat(underline)
+  )
+  .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn diag(range: Range, message: &str) -> Diag {
+    Diag {
+      range,
+      severity: Severity::Error,
+      code: Some("E001"),
+      message: message.into(),
+    }
+  }
+
+  #[test]
+  fn diagnostics_dedup() {
+    let mut diags = Diagnostics::new();
+    assert!(diags.is_empty());
+    assert!(diags.push(diag(0..1, "a")));
+    assert!(!diags.push(diag(0..1, "a")));
+    // same message, different range
+    assert!(diags.push(diag(1..2, "a")));
+    // same range, different message
+    assert!(diags.push(diag(0..1, "b")));
+    assert_eq!(diags.diags().len(), 3);
+    assert_eq!(diags.suppressed(), 0);
+
+    diags.clear();
+    assert!(diags.is_empty());
+  }
+
+  #[test]
+  fn diagnostics_limit() {
+    let mut diags = Diagnostics::with_limit(2);
+    assert!(diags.push(diag(0..1, "a")));
+    assert!(diags.push(diag(1..2, "a")));
+    assert!(!diags.push(diag(2..3, "a")));
+    assert!(!diags.push(diag(3..4, "a")));
+    // duplicates are not counted as suppressed
+    assert!(!diags.push(diag(0..1, "a")));
+    assert_eq!(diags.diags().len(), 2);
+    assert_eq!(diags.suppressed(), 2);
+    assert!(diags
+      .render("abcd")
+      .ends_with("2 more diagnostics suppressed\n"));
+  }
+
+  #[test]
+  fn diagnostics_render() {
+    let text = "let a = 1;\nlet 好 = ;\n";
+    let mut diags = Diagnostics::new();
+    diags.push(diag(15..18, "invalid identifier"));
+    diags.push(Diag {
+      range: 21..22,
+      severity: Severity::Warning,
+      code: None,
+      message: "missing value".into(),
+    });
+    assert_eq!(
+      diags.render(text),
+      "\
+error[E001]: invalid identifier
+ --> 2:5
+  |
+2 | let 好 = ;
+  |     ^
+warning: missing value
+ --> 2:9
+  |
+2 | let 好 = ;
+  |         ^
+"
+    );
+
+    // empty range and multi-line range
+    let mut diags = Diagnostics::new();
+    diags.push(diag(3..3, "empty"));
+    diags.push(diag(4..14, "multi-line"));
+    assert_eq!(
+      diags.render(text),
+      "\
+error[E001]: empty
+ --> 1:4
+  |
+1 | let a = 1;
+  |    ^
+error[E001]: multi-line
+ --> 1:5
+  |
+1 | let a = 1;
+  |     ^^^^^^
+"
+    );
+  }
+
+  #[test]
+  fn diagnostics_render_crlf() {
+    let text = "let a = 1;\r\nlet b = ;\r\n";
+    let mut diags = Diagnostics::new();
+    diags.push(diag(20..22, "missing value"));
+    // the range covers the line ending
+    diags.push(diag(7..12, "multi-line"));
+    assert_eq!(
+      diags.render(text),
+      "\
+error[E001]: missing value
+ --> 2:9
+  |
+2 | let b = ;
+  |         ^
+error[E001]: multi-line
+ --> 1:8
+  |
+1 | let a = 1;
+  |        ^^^
+"
+    );
+  }
+
+  #[test]
+  fn diagnostics_render_escaped() {
+    let text = "\x1b[31mred\0 = ;";
+    let mut diags = Diagnostics::new();
+    diags.push(diag(8..12, "invalid \x1b[0m name"));
+    assert_eq!(
+      diags.render(text),
+      "\
+error[E001]: invalid \\u{1b}[0m name
+ --> 1:9
+  |
+1 | \\u{1b}[31mred\\0 = ;
+  |              ^^^^^
+"
+    );
+
+    assert_eq!(escape("abc"), Cow::Borrowed("abc"));
+    assert_eq!(escape("\u{202E}"), "\\u{202e}");
+    assert_eq!(escape("\t\n"), "\\t\\n");
+  }
+}
@@ -1,21 +1,44 @@
 //! Digest-able byte sequence. See [`Digest`].
 
-use std::slice::SliceIndex;
+use std::{ops::RangeTo, slice::SliceIndex};
 
 /// A digest-able byte sequence.
 ///
-/// Built-in implementations are provided for `[u8]` and [`str`].
+/// Built-in implementations are provided for `[u8]`, [`str`] and `[u16]`,
+/// and slices of any [`Unit`] (e.g. tokens from a lexer).
+///
+/// For `[u16]` (e.g. UTF-16 text), the unit of digestion is one `u16`, not one byte,
+/// so all offsets and lengths (e.g. [`Instant::digested`](crate::instant::Instant::digested))
+/// are counted in code units.
+/// A surrogate pair is 2 units and can be split.
 pub trait Digest {
-  /// Validate if it is ok to digest the first `n` bytes.
+  /// Validate if it is ok to digest the first `n` units.
   ///
-  /// For both `[u8]` and [`str`], this method will
+  /// For `[u8]`, [`str`] and `[u16]`, this method will
   /// require `n` is no greater than the length of `self`.
   /// For [`str`], this method will also require `n` is a valid UTF-8 boundary.
   fn validate(&self, n: usize) -> bool;
 
   /// Convert self to a byte slice.
+  ///
+  /// For `[u16]`, this is the native-endian bytes of the code units.
   fn as_bytes(&self) -> &[u8];
 
+  /// The length of `self` in units, i.e. bytes for `[u8]` and [`str`],
+  /// and code units for `[u16]`.
+  ///
+  /// The default implementation returns the length of [`Digest::as_bytes`].
+  #[inline]
+  fn len(&self) -> usize {
+    self.as_bytes().len()
+  }
+
+  /// Whether [`Digest::len`] is `0`.
+  #[inline]
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
   /// Get a subslice of `self` if it is valid.
   fn get<I: SliceIndex<Self>>(&self, i: I) -> Option<&I::Output>;
 
@@ -70,6 +93,104 @@ impl Digest for str {
   }
 }
 
+impl Digest for [u16] {
+  #[inline]
+  fn validate(&self, n: usize) -> bool {
+    n <= <[u16]>::len(self)
+  }
+
+  #[inline]
+  fn as_bytes(&self) -> &[u8] {
+    // SAFETY: `u16` has no padding and `u8` has no alignment requirement
+    unsafe { std::slice::from_raw_parts(self.as_ptr().cast(), std::mem::size_of_val(self)) }
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    <[u16]>::len(self)
+  }
+
+  #[inline]
+  fn get<I: SliceIndex<Self>>(&self, i: I) -> Option<&I::Output> {
+    self.get(i)
+  }
+
+  #[inline]
+  unsafe fn get_unchecked<I: SliceIndex<Self>>(&self, i: I) -> &I::Output {
+    self.get_unchecked(i)
+  }
+}
+
+/// A unit of a digest-able slice other than `u8` and `u16`,
+/// e.g. a token produced by a lexer,
+/// so `[T]` implements [`Digest`] and combinators can parse over tokens instead of text.
+///
+/// The unit of digestion is one element,
+/// so all offsets and lengths are counted in elements.
+/// Any number of elements can be digested.
+///
+/// Since the elements may contain padding bytes, [`Digest::as_bytes`] for `[T]`
+/// is an empty slice at the same address.
+/// Literal checks (e.g. [`Combinator::when_literal`](crate::combinator::Combinator::when_literal)
+/// and [`Combinator::when_expected`](crate::combinator::Combinator::when_expected))
+/// compare elements with [`PartialEq`] instead, so they work if `T: PartialEq`.
+/// Byte-based dispatching (e.g. [`dispatch`](crate::combinator::dispatch)) only uses the fallback.
+/// Use [`token_by`](crate::combinator::token_by) to match elements.
+/// # Examples
+/// ```
+/// use whitehole::{combinator::token_by, digest::Unit, parser::Parser};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Token { Num(i32), Plus }
+/// impl Unit for Token {}
+///
+/// let tokens = [Token::Num(1), Token::Plus, Token::Num(2)];
+/// let num = || token_by(|t: &Token| matches!(t, Token::Num(_)));
+/// let entry = num() + token_by(|t| *t == Token::Plus) + num();
+/// let output = Parser::builder().entry(entry).build(&tokens[..]).next().unwrap();
+/// assert_eq!(output.digested, 3);
+/// ```
+pub trait Unit {}
+
+impl<T: Unit> Digest for [T] {
+  #[inline]
+  fn validate(&self, n: usize) -> bool {
+    n <= <[T]>::len(self)
+  }
+
+  #[inline]
+  fn as_bytes(&self) -> &[u8] {
+    // keep the address for identity checks, but don't expose the (maybe padded) memory
+    // SAFETY: the pointer is non-null and aligned for `u8`, the length is 0
+    unsafe { std::slice::from_raw_parts(self.as_ptr().cast(), 0) }
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    <[T]>::len(self)
+  }
+
+  #[inline]
+  fn get<I: SliceIndex<Self>>(&self, i: I) -> Option<&I::Output> {
+    self.get(i)
+  }
+
+  #[inline]
+  unsafe fn get_unchecked<I: SliceIndex<Self>>(&self, i: I) -> &I::Output {
+    self.get_unchecked(i)
+  }
+}
+
+/// Whether `text` starts with `prefix`, compared by units instead of [`Digest::as_bytes`],
+/// so this also works for `[T]` where `T: Unit`.
+#[inline]
+pub(crate) fn starts_with<Text: ?Sized + Digest + PartialEq>(text: &Text, prefix: &Text) -> bool
+where
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  Digest::get(text, ..prefix.len()).is_some_and(|head| head == prefix)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -104,4 +225,76 @@ mod tests {
     assert_eq!(<str as Digest>::get(text, 0..), Some("好"));
     assert_eq!(unsafe { <str as Digest>::get_unchecked(text, 0..) }, "好");
   }
+
+  #[test]
+  fn digest_u16s() {
+    let text: Vec<u16> = "a😀".encode_utf16().collect();
+    let text = text.as_slice();
+    assert_eq!(Digest::len(text), 3);
+    assert!(!Digest::is_empty(text));
+    assert!(text.validate(0));
+    // a surrogate pair can be split
+    assert!(text.validate(2));
+    assert!(text.validate(3));
+    assert!(!text.validate(4));
+    assert_eq!(text.as_bytes().len(), 6);
+    assert_eq!(<[u16] as Digest>::get(text, 0), Some(&(b'a' as u16)));
+    assert_eq!(<[u16] as Digest>::get(text, 3..), Some(&[] as &[u16]));
+    assert_eq!(
+      unsafe { <[u16] as Digest>::get_unchecked(text, 1..) },
+      &text[1..]
+    );
+  }
+
+  #[test]
+  fn digest_units() {
+    #[derive(Debug, PartialEq)]
+    struct Token(u8, usize);
+    impl Unit for Token {}
+
+    let tokens = [Token(0, 1), Token(1, 2)];
+    let tokens = &tokens[..];
+    assert_eq!(Digest::len(tokens), 2);
+    assert!(!Digest::is_empty(tokens));
+    assert!(Digest::is_empty(&tokens[2..]));
+    assert!(tokens.validate(0));
+    assert!(tokens.validate(2));
+    assert!(!tokens.validate(3));
+    assert!(tokens.as_bytes().is_empty());
+    assert_eq!(tokens.as_bytes().as_ptr(), tokens.as_ptr().cast());
+    assert_eq!(<[Token] as Digest>::get(tokens, 1), Some(&Token(1, 2)));
+    assert_eq!(
+      unsafe { <[Token] as Digest>::get_unchecked(tokens, 1..) },
+      &tokens[1..]
+    );
+  }
+
+  #[test]
+  fn digest_starts_with() {
+    assert!(starts_with("好a", "好"));
+    assert!(starts_with("好a", ""));
+    assert!(!starts_with("好", "好a"));
+    assert!(!starts_with("好", "a"));
+    assert!(starts_with(b"abc" as &[u8], b"ab"));
+    assert!(!starts_with(b"abc" as &[u8], b"b"));
+
+    #[derive(Debug, PartialEq)]
+    enum Token {
+      Num,
+      Plus,
+    }
+    impl Unit for Token {}
+
+    let tokens = &[Token::Num, Token::Plus][..];
+    assert!(starts_with(tokens, &[Token::Num]));
+    assert!(!starts_with(tokens, &[Token::Plus]));
+    assert!(!starts_with(&tokens[1..], &[Token::Plus, Token::Num]));
+  }
+
+  #[test]
+  fn digest_len() {
+    assert_eq!(Digest::len("好"), 3);
+    assert!(Digest::is_empty(""));
+    assert_eq!(Digest::len(b"12" as &[u8]), 2);
+  }
 }
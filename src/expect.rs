@@ -0,0 +1,224 @@
+//! Report what was expected when the parser rejects.
+//!
+//! When the entry action rejects, [`Parser::next`](crate::parser::Parser::next)
+//! only returns [`None`].
+//! To tell the user what was expected, label combinators with
+//! [`Combinator::expect`](crate::combinator::Combinator::expect)
+//! and use [`Expectations`] as (or inside) the `Heap` of the parser.
+//! Then use [`Parser::next_verbose`](crate::parser::Parser::next_verbose)
+//! to get a [`ParseError`] when the entry action rejects.
+//!
+//! Labeled combinators record their labels with the start offset when they reject.
+//! Only the labels at the furthest offset are kept (the "farthest failure" heuristic),
+//! since the furthest failure is usually the most relevant one.
+//! Labels from all branches of a `|` chain are collected if they reject at the same offset.
+//!
+//! ```
+//! use whitehole::{combinator::contextual, expect::Expectations, parser::Parser};
+//!
+//! contextual!((), Expectations);
+//!
+//! # fn main() {
+//! let value = || (eat("true") | eat("false")).expect("boolean");
+//! let entry = value() + eat(',').expect("','") + value();
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(Expectations::new())
+//!   .entry(entry)
+//!   .build("true,1");
+//! let err = parser.next_verbose().unwrap_err();
+//! assert_eq!(err.offset, 5);
+//! assert_eq!(err.expected, ["boolean"]);
+//! assert_eq!(err.to_string(), "expected boolean at offset 5");
+//! # }
+//! ```
+//!
+//! Labeled combinators pay a small cost when they reject, un-labeled combinators pay nothing.
+//!
+//! To only find out the furthest offset without labels, mark combinators with
+//! [`Combinator::track`](crate::combinator::Combinator::track),
+//! then use [`Parser::furthest_failure`](crate::parser::Parser::furthest_failure).
+//! Since the record lives in [`Expectations`],
+//! it is also cleared by [`Parser::next_verbose`](crate::parser::Parser::next_verbose).
+//!
+//! ```
+//! use whitehole::{combinator::contextual, expect::Expectations, parser::Parser};
+//!
+//! contextual!((), Expectations);
+//!
+//! # fn main() {
+//! let entry = (eat("let").track() + eat(' ').track() + eat("x").track())
+//!   | eat("const").track();
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(Expectations::new())
+//!   .entry(entry)
+//!   .build("let y");
+//! let err = parser.next_verbose().unwrap_err();
+//! // the left branch failed at offset 4, the right branch failed at offset 0
+//! assert_eq!(parser.furthest_failure(), Some(4));
+//! assert_eq!(err.to_string(), "unexpected input at offset 4");
+//! # }
+//! ```
+
+use std::fmt::{self, Display};
+
+/// Labels of rejected combinators at the furthest offset,
+/// recorded by [`Combinator::expect`](crate::combinator::Combinator::expect).
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expectations {
+  offset: Option<usize>,
+  labels: Vec<&'static str>,
+}
+
+impl Expectations {
+  /// Create a new instance with nothing recorded.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      offset: None,
+      labels: Vec::new(),
+    }
+  }
+
+  /// Record that `label` was expected at `offset`.
+  ///
+  /// If `offset` is further than the recorded offset, previous labels are discarded.
+  /// If `offset` is before the recorded offset, `label` is ignored.
+  /// Duplicated labels at the same offset are only recorded once.
+  #[inline]
+  pub fn record(&mut self, offset: usize, label: &'static str) {
+    match self.offset {
+      Some(furthest) if offset < furthest => {}
+      Some(furthest) if offset == furthest => {
+        if !self.labels.contains(&label) {
+          self.labels.push(label);
+        }
+      }
+      _ => {
+        self.offset = Some(offset);
+        self.labels.clear();
+        self.labels.push(label);
+      }
+    }
+  }
+
+  /// Record that a combinator rejected at `offset`, without a label.
+  ///
+  /// If `offset` is further than the recorded offset, previous labels are discarded.
+  /// Otherwise this does nothing.
+  #[inline]
+  // `Option::is_none_or` requires Rust 1.82
+  #[allow(clippy::unnecessary_map_or)]
+  pub fn record_offset(&mut self, offset: usize) {
+    if self.offset.map_or(true, |furthest| offset > furthest) {
+      self.offset = Some(offset);
+      self.labels.clear();
+    }
+  }
+
+  /// The furthest offset where a label or an offset is recorded.
+  #[inline]
+  pub const fn offset(&self) -> Option<usize> {
+    self.offset
+  }
+
+  /// Labels recorded at [`Self::offset`], in the recording order.
+  #[inline]
+  pub fn labels(&self) -> &[&'static str] {
+    &self.labels
+  }
+
+  /// Reset the recorded offset and labels.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.offset = None;
+    self.labels.clear();
+  }
+}
+
+/// The error returned by [`Parser::next_verbose`](crate::parser::Parser::next_verbose)
+/// when the entry action rejects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  /// The furthest offset where a labeled combinator rejected,
+  /// or [`Instant::digested`](crate::instant::Instant::digested) of the parser
+  /// if no labeled combinator rejected.
+  pub offset: usize,
+  /// Labels expected at [`Self::offset`].
+  /// This is empty if no labeled combinator rejected.
+  pub expected: Vec<&'static str>,
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.expected.as_slice() {
+      [] => write!(f, "unexpected input at offset {}", self.offset),
+      [label] => write!(f, "expected {} at offset {}", label, self.offset),
+      [init @ .., last] => write!(
+        f,
+        "expected {} or {} at offset {}",
+        init.join(", "),
+        last,
+        self.offset
+      ),
+    }
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn expectations() {
+    let mut e = Expectations::new();
+    assert_eq!(e.offset(), None);
+    assert!(e.labels().is_empty());
+
+    e.record(1, "a");
+    e.record(1, "b");
+    e.record(1, "a");
+    assert_eq!(e.offset(), Some(1));
+    assert_eq!(e.labels(), ["a", "b"]);
+
+    // before the furthest offset
+    e.record(0, "c");
+    assert_eq!(e.labels(), ["a", "b"]);
+
+    // further
+    e.record(3, "d");
+    assert_eq!(e.offset(), Some(3));
+    assert_eq!(e.labels(), ["d"]);
+
+    // offsets without labels
+    e.record_offset(3);
+    assert_eq!(e.labels(), ["d"]);
+    e.record_offset(5);
+    assert_eq!(e.offset(), Some(5));
+    assert!(e.labels().is_empty());
+    e.record(5, "e");
+    assert_eq!(e.labels(), ["e"]);
+
+    e.clear();
+    assert_eq!(e, Expectations::default());
+  }
+
+  #[test]
+  fn parse_error_display() {
+    let err = |expected: &[&'static str]| ParseError {
+      offset: 2,
+      expected: expected.to_vec(),
+    };
+    assert_eq!(err(&[]).to_string(), "unexpected input at offset 2");
+    assert_eq!(err(&["a"]).to_string(), "expected a at offset 2");
+    assert_eq!(err(&["a", "b"]).to_string(), "expected a or b at offset 2");
+    assert_eq!(
+      err(&["a", "b", "c"]).to_string(),
+      "expected a, b or c at offset 2"
+    );
+  }
+}
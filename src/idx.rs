@@ -0,0 +1,218 @@
+//! Index arithmetic and slicing for hot paths.
+//!
+//! By default, these helpers use unchecked operations
+//! and the invariants are only checked by [`debug_assert!`].
+//! With the `safe` feature, checked operations are used instead
+//! and a violated invariant will panic, so this module contains no `unsafe` code.
+//!
+//! By default these helpers are `unsafe fn`, so each call site must justify the invariants
+//! in a `SAFETY` comment. With the `safe` feature they are safe functions,
+//! so call them via [`unchecked!`] which only adds the `unsafe` block when needed.
+
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
+
+use crate::digest::Digest;
+use std::{
+  ops::{RangeFrom, RangeTo},
+  slice::SliceIndex,
+};
+
+/// Evaluate an expression which only calls the helpers in this module.
+/// The expression is wrapped in an `unsafe` block by default
+/// and evaluated as is with the `safe` feature.
+#[cfg(not(feature = "safe"))]
+macro_rules! unchecked {
+  ($e:expr) => {
+    unsafe { $e }
+  };
+}
+
+/// Evaluate an expression which only calls the helpers in this module.
+/// The expression is wrapped in an `unsafe` block by default
+/// and evaluated as is with the `safe` feature.
+#[cfg(feature = "safe")]
+macro_rules! unchecked {
+  ($e:expr) => {
+    $e
+  };
+}
+
+pub(crate) use unchecked;
+
+/// `a + b`.
+/// # Safety
+/// The result must not overflow.
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) const unsafe fn add(a: usize, b: usize) -> usize {
+  debug_assert!(a.checked_add(b).is_some());
+  unsafe { a.unchecked_add(b) }
+}
+
+/// `a + b`. Panic if the result overflows.
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) const fn add(a: usize, b: usize) -> usize {
+  a.checked_add(b).expect("index overflow")
+}
+
+/// `a - b`.
+/// # Safety
+/// The result must not underflow.
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) const unsafe fn sub(a: usize, b: usize) -> usize {
+  debug_assert!(a >= b);
+  unsafe { a.unchecked_sub(b) }
+}
+
+/// `a - b`. Panic if the result underflows.
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) const fn sub(a: usize, b: usize) -> usize {
+  a.checked_sub(b).expect("index underflow")
+}
+
+/// `&text[n..]`.
+/// # Safety
+/// `n` must be valid according to [`Digest::validate`].
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) unsafe fn slice_from<Text: ?Sized + Digest>(text: &Text, n: usize) -> &Text
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  debug_assert!(text.validate(n));
+  unsafe { text.get_unchecked(n..) }
+}
+
+/// `&text[n..]`. Panic if `n` is invalid according to [`Digest::validate`].
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) fn slice_from<Text: ?Sized + Digest>(text: &Text, n: usize) -> &Text
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  text.get(n..).expect("invalid index")
+}
+
+/// `&text[..n]`.
+/// # Safety
+/// `n` must be valid according to [`Digest::validate`].
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) unsafe fn slice_to<Text: ?Sized + Digest>(text: &Text, n: usize) -> &Text
+where
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  debug_assert!(text.validate(n));
+  unsafe { text.get_unchecked(..n) }
+}
+
+/// `&text[..n]`. Panic if `n` is invalid according to [`Digest::validate`].
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) fn slice_to<Text: ?Sized + Digest>(text: &Text, n: usize) -> &Text
+where
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  text.get(..n).expect("invalid index")
+}
+
+/// `&mut slice[i]`.
+/// # Safety
+/// `i` must be in bounds.
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) unsafe fn get_mut<T>(slice: &mut [T], i: usize) -> &mut T {
+  debug_assert!(i < slice.len());
+  unsafe { slice.get_unchecked_mut(i) }
+}
+
+/// `&mut slice[i]`. Panic if `i` is out of bounds.
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) fn get_mut<T>(slice: &mut [T], i: usize) -> &mut T {
+  &mut slice[i]
+}
+
+/// Unwrap an [`Option`].
+/// # Safety
+/// The option must be [`Some`].
+#[cfg(not(feature = "safe"))]
+#[inline]
+pub(crate) unsafe fn unwrap<T>(option: Option<T>) -> T {
+  debug_assert!(option.is_some());
+  unsafe { option.unwrap_unchecked() }
+}
+
+/// Unwrap an [`Option`]. Panic if the option is [`None`].
+#[cfg(feature = "safe")]
+#[inline]
+pub(crate) fn unwrap<T>(option: Option<T>) -> T {
+  option.expect("unexpected none")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn idx_valid() {
+    assert_eq!(unchecked!(add(1, 2)), 3);
+    assert_eq!(unchecked!(sub(3, 2)), 1);
+    assert_eq!(unchecked!(slice_from("a好", 1)), "好");
+    assert_eq!(unchecked!(slice_to("a好", 1)), "a");
+    assert_eq!(unchecked!(slice_from(b"ab" as &[u8], 2)), b"");
+    assert_eq!(unchecked!(slice_to(b"ab" as &[u8], 0)), b"");
+    let mut arr = [0, 1];
+    *unchecked!(get_mut(&mut arr, 1)) = 2;
+    assert_eq!(arr, [0, 2]);
+    assert_eq!(unchecked!(unwrap(Some(1))), 1);
+  }
+
+  // the default configuration panics by `debug_assert!` in debug builds,
+  // and the safe configuration always panics by checked operations
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_add_overflow() {
+    unchecked!(add(usize::MAX, 1));
+  }
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_sub_underflow() {
+    unchecked!(sub(0, 1));
+  }
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_slice_from_out_of_bounds() {
+    unchecked!(slice_from("a", 2));
+  }
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_slice_to_char_boundary() {
+    unchecked!(slice_to("好", 1));
+  }
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_get_mut_out_of_bounds() {
+    unchecked!(get_mut(&mut [0], 1));
+  }
+
+  #[test]
+  #[cfg(any(debug_assertions, feature = "safe"))]
+  #[should_panic]
+  fn idx_unwrap_none() {
+    unchecked!(unwrap(None::<()>));
+  }
+}
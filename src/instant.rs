@@ -2,7 +2,43 @@
 //! See [`Instant`].
 
 use crate::digest::Digest;
-use std::{ops::RangeFrom, slice::SliceIndex};
+use crate::idx;
+use std::{
+  fmt::{self, Display},
+  ops::RangeFrom,
+  slice::SliceIndex,
+};
+
+/// The error returned by [`Instant::digest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestError {
+  /// The requested length is greater than the length of [`Instant::rest`].
+  OutOfRange {
+    /// The requested length.
+    n: usize,
+    /// The length of [`Instant::rest`].
+    rest: usize,
+  },
+  /// The requested length is not a valid boundary according to [`Digest::validate`],
+  /// e.g. not a char boundary for [`str`].
+  InvalidBoundary {
+    /// The requested length.
+    n: usize,
+  },
+}
+
+impl Display for DigestError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::OutOfRange { n, rest } => {
+        write!(f, "can't digest {} from the rest with length {}", n, rest)
+      }
+      Self::InvalidBoundary { n } => write!(f, "can't digest {}: not a valid boundary", n),
+    }
+  }
+}
+
+impl std::error::Error for DigestError {}
 
 /// The instantaneous state of a parser (a.k.a the "configuration" in the automata theory).
 ///
@@ -15,6 +51,8 @@ pub struct Instant<TextRef> {
   rest: TextRef,
   /// See [`Self::digested`].
   digested: usize,
+  /// See [`Self::expectation`].
+  expectation: Option<TextRef>,
 }
 
 impl<'text, Text: ?Sized> Instant<&'text Text> {
@@ -26,6 +64,21 @@ impl<'text, Text: ?Sized> Instant<&'text Text> {
       text,
       rest: text,
       digested: 0,
+      expectation: None,
+    }
+  }
+
+  /// Create a new instance with the given text as the suffix of a larger input,
+  /// whose first `base` bytes are dropped.
+  /// [`Self::digested`] will be set to `base`,
+  /// while [`Self::text`] and [`Self::rest`] are the given text.
+  #[inline]
+  pub(crate) const fn new_with_base(text: &'text Text, base: usize) -> Self {
+    Instant {
+      text,
+      rest: text,
+      digested: base,
+      expectation: None,
     }
   }
 
@@ -45,6 +98,23 @@ impl<'text, Text: ?Sized> Instant<&'text Text> {
   pub const fn rest(&self) -> &'text Text {
     self.rest
   }
+
+  /// The literal expected by [`Parser::next_expecting`](crate::parser::Parser::next_expecting),
+  /// or [`None`] if nothing is expected.
+  /// See [`Combinator::when_expected`](crate::combinator::Combinator::when_expected).
+  ///
+  /// This is kept when digesting, so it is visible to all combinators in one execution.
+  #[inline]
+  pub const fn expectation(&self) -> Option<&'text Text> {
+    self.expectation
+  }
+
+  /// Set [`Self::expectation`].
+  #[inline]
+  pub(crate) const fn with_expectation(mut self, expectation: Option<&'text Text>) -> Self {
+    self.expectation = expectation;
+    self
+  }
 }
 
 impl<TextRef> Instant<TextRef> {
@@ -57,10 +127,30 @@ impl<TextRef> Instant<TextRef> {
   }
 }
 
-impl<Text: ?Sized + Digest> Instant<&Text>
+impl<'text, Text: ?Sized + Digest> Instant<&'text Text>
 where
   RangeFrom<usize>: SliceIndex<Text, Output = Text>,
 {
+  /// Create a new instance with the given text and digest the first `start` bytes,
+  /// so [`Self::digested`] starts at `start` and [`Self::rest`] is the suffix from `start`.
+  ///
+  /// Return a [`DigestError`] if `start` is invalid according to [`Digest::validate`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::instant::{DigestError, Instant};
+  /// let instant = Instant::new_at("好12", 3).unwrap();
+  /// assert_eq!(instant.digested(), 3);
+  /// assert_eq!(instant.rest(), "12");
+  /// assert_eq!(instant.text(), "好12");
+  /// assert_eq!(Instant::new_at("好12", 1).unwrap_err(), DigestError::InvalidBoundary { n: 1 });
+  /// ```
+  #[inline]
+  pub fn new_at(text: &'text Text, start: usize) -> Result<Self, DigestError> {
+    let mut instant = Self::new(text);
+    instant.digest(start)?;
+    Ok(instant)
+  }
+
   /// Digest the next `n` bytes.
   /// This will update [`Self::rest`] and [`Self::digested`].
   /// # Safety
@@ -69,8 +159,43 @@ where
   #[inline]
   pub unsafe fn digest_unchecked(&mut self, n: usize) {
     debug_assert!(self.rest.validate(n));
-    self.rest = self.rest.get_unchecked(n..);
-    self.digested = self.digested.unchecked_add(n);
+    self.rest = idx::slice_from(self.rest, n);
+    self.digested = idx::add(self.digested, n);
+  }
+
+  /// Digest the next `n` bytes if `n` is valid according to [`Digest::validate`].
+  /// This will update [`Self::rest`] and [`Self::digested`].
+  ///
+  /// If `n` is invalid, [`Self`] is unchanged and a [`DigestError`] is returned.
+  /// This is the safe version of [`Self::digest_unchecked`].
+  /// # Examples
+  /// ```
+  /// # use whitehole::instant::{DigestError, Instant};
+  /// let mut instant = Instant::new("好1");
+  /// assert_eq!(instant.digest(1), Err(DigestError::InvalidBoundary { n: 1 }));
+  /// assert_eq!(instant.digest(5), Err(DigestError::OutOfRange { n: 5, rest: 4 }));
+  /// assert_eq!(instant.digest(3), Ok(()));
+  /// assert_eq!(instant.rest(), "1");
+  /// ```
+  #[inline]
+  pub fn digest(&mut self, n: usize) -> Result<(), DigestError> {
+    let rest = self.rest.len();
+    if n > rest {
+      return Err(DigestError::OutOfRange { n, rest });
+    }
+    if !self.rest.validate(n) {
+      return Err(DigestError::InvalidBoundary { n });
+    }
+    unsafe { self.digest_unchecked(n) };
+    Ok(())
+  }
+
+  /// Digest the whole [`Self::rest`].
+  /// This will update [`Self::rest`] and [`Self::digested`].
+  #[inline]
+  pub fn digest_rest(&mut self) {
+    // SAFETY: the length of the rest is always a valid boundary
+    unsafe { self.digest_unchecked(self.rest.len()) };
   }
 
   /// Construct a new instance by digesting `n` bytes from [`Self::rest`].
@@ -99,6 +224,30 @@ mod tests {
     assert_eq!(i.text(), "123");
   }
 
+  #[test]
+  fn instant_new_at() {
+    let i = Instant::new_at("123", 1).unwrap();
+    assert_eq!(i.digested(), 1);
+    assert_eq!(i.rest(), "23");
+    assert_eq!(i.text(), "123");
+
+    let i = Instant::new_at("123", 3).unwrap();
+    assert_eq!(i.digested(), 3);
+    assert_eq!(i.rest(), "");
+
+    assert_eq!(
+      Instant::new_at("123", 4).unwrap_err(),
+      DigestError::OutOfRange { n: 4, rest: 3 }
+    );
+    assert_eq!(
+      Instant::new_at("好", 2).unwrap_err(),
+      DigestError::InvalidBoundary { n: 2 }
+    );
+
+    let i = Instant::new_at(b"123" as &[u8], 2).unwrap();
+    assert_eq!(i.rest(), b"3");
+  }
+
   #[test]
   fn instant_clone() {
     let i = Instant::new("123");
@@ -193,4 +342,76 @@ mod tests {
   fn instant_str_to_digested_unchecked_overflow() {
     let _ = unsafe { Instant::new("123").to_digested_unchecked(4) };
   }
+
+  #[test]
+  fn instant_str_digest() {
+    let mut i = Instant::new("a好b");
+    // not a char boundary
+    assert_eq!(i.digest(2), Err(DigestError::InvalidBoundary { n: 2 }));
+    assert_eq!(i.digested(), 0);
+    // over length
+    assert_eq!(i.digest(6), Err(DigestError::OutOfRange { n: 6, rest: 5 }));
+    assert_eq!(i.digested(), 0);
+    // valid
+    assert_eq!(i.digest(4), Ok(()));
+    assert_eq!(i.digested(), 4);
+    assert_eq!(i.rest(), "b");
+    // exact length
+    assert_eq!(i.digest(1), Ok(()));
+    assert_eq!(i.rest(), "");
+    assert_eq!(i.digest(0), Ok(()));
+    assert_eq!(i.digest(1), Err(DigestError::OutOfRange { n: 1, rest: 0 }));
+    assert_eq!(i.digested(), 5);
+  }
+
+  #[test]
+  fn instant_bytes_digest() {
+    let mut i = Instant::new("a好b".as_bytes());
+    // no char boundary for bytes
+    assert_eq!(i.digest(2), Ok(()));
+    assert_eq!(i.digest(4), Err(DigestError::OutOfRange { n: 4, rest: 3 }));
+    assert_eq!(i.digest(3), Ok(()));
+    assert_eq!(i.rest(), b"");
+    assert_eq!(i.digested(), 5);
+  }
+
+  #[test]
+  fn instant_digest_same_as_unchecked() {
+    let text = "a好b";
+    for n in [0, 1, 4, 5] {
+      let mut checked = Instant::new(text);
+      checked.digest(n).unwrap();
+      let unchecked = unsafe { Instant::new(text).to_digested_unchecked(n) };
+      assert_eq!(checked.digested(), unchecked.digested());
+      assert_eq!(checked.rest(), unchecked.rest());
+    }
+  }
+
+  #[test]
+  fn instant_digest_rest() {
+    let mut i = Instant::new("a好b");
+    unsafe { i.digest_unchecked(1) };
+    i.digest_rest();
+    assert_eq!(i.digested(), 5);
+    assert_eq!(i.rest(), "");
+    i.digest_rest();
+    assert_eq!(i.digested(), 5);
+
+    let mut i = Instant::new(b"123" as &[u8]);
+    i.digest_rest();
+    assert_eq!(i.digested(), 3);
+    assert_eq!(i.rest(), b"");
+  }
+
+  #[test]
+  fn digest_error_display() {
+    assert_eq!(
+      DigestError::OutOfRange { n: 2, rest: 1 }.to_string(),
+      "can't digest 2 from the rest with length 1"
+    );
+    assert_eq!(
+      DigestError::InvalidBoundary { n: 1 }.to_string(),
+      "can't digest 1: not a valid boundary"
+    );
+  }
 }
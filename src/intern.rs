@@ -0,0 +1,169 @@
+//! Intern identifiers into compact symbols.
+//!
+//! Building an AST usually needs the names of identifiers.
+//! Recognizing each identifier into a [`String`] allocates per occurrence,
+//! and comparing names compares strings.
+//! Instead, mark the combinator with
+//! [`Combinator::interned`](crate::combinator::Combinator::interned)
+//! and use [`Interner`] as (or inside) the `Heap` of the parser,
+//! then the digested text is interned and the value is a [`Symbol`].
+//! The same text always gets the same symbol,
+//! and only the first occurrence of a text allocates.
+//!
+//! ```
+//! use whitehole::{combinator::contextual, intern::Interner, parser::Parser};
+//!
+//! contextual!((), Interner);
+//!
+//! # fn main() {
+//! let ident = (next(|c| c.is_ascii_alphabetic()) * (1..)).interned();
+//! let entry = ident.tuple() + (eat(' ') * (..));
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(Interner::new())
+//!   .entry(entry)
+//!   .build("foo bar foo");
+//! let symbols = parser.by_ref().map(|o| o.value.0).collect::<Vec<_>>();
+//! assert_eq!(symbols[0], symbols[2]);
+//! assert_ne!(symbols[0], symbols[1]);
+//! assert_eq!(parser.heap.resolve(symbols[1]), Some("bar"));
+//! assert_eq!(parser.heap.len(), 2);
+//! # }
+//! ```
+//!
+//! To intern values other than the digested text,
+//! access the heap in [`Combinator::select`](crate::combinator::Combinator::select)
+//! via [`Accepted::heap`](crate::combinator::Accepted::heap).
+
+use std::{collections::HashMap, sync::Arc};
+
+/// A compact handle of an interned string.
+/// See [`Interner`].
+///
+/// Symbols are indices in the registration order, starting from 0,
+/// and are only meaningful for the [`Interner`] which created them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+  /// The index of the symbol in the registration order.
+  #[inline]
+  pub const fn index(self) -> usize {
+    self.0 as usize
+  }
+}
+
+/// Map strings to [`Symbol`]s with stable indices.
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+  symbols: HashMap<Arc<str>, Symbol>,
+  strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+  /// Create a new instance with nothing interned.
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Return the symbol of `s`, intern it first if it is not interned yet.
+  ///
+  /// Only interning a new string allocates.
+  /// # Panics
+  /// Panics if more than [`u32::MAX`] strings are interned.
+  pub fn intern(&mut self, s: &str) -> Symbol {
+    if let Some(&symbol) = self.symbols.get(s) {
+      return symbol;
+    }
+    let symbol = Symbol(u32::try_from(self.strings.len()).expect("too many interned strings"));
+    let s: Arc<str> = s.into();
+    self.strings.push(s.clone());
+    self.symbols.insert(s, symbol);
+    symbol
+  }
+
+  /// Return the symbol of `s` if it is interned.
+  #[inline]
+  pub fn get(&self, s: &str) -> Option<Symbol> {
+    self.symbols.get(s).copied()
+  }
+
+  /// Return the string of the `symbol`,
+  /// or [`None`] if the `symbol` is not created by this interner.
+  #[inline]
+  pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+    self.strings.get(symbol.index()).map(|s| &**s)
+  }
+
+  /// The number of interned strings.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.strings.len()
+  }
+
+  /// Return `true` if nothing is interned.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.strings.is_empty()
+  }
+
+  /// The interned strings in the registration order,
+  /// i.e. indexed by [`Symbol::index`].
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> + '_ {
+    self
+      .strings
+      .iter()
+      .enumerate()
+      .map(|(i, s)| (Symbol(i as u32), &**s))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn interner_same_string_same_symbol() {
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    assert_eq!(interner.intern("a"), a);
+    assert_eq!(interner.intern(&String::from("b")), b);
+    assert_eq!(interner.len(), 2);
+    // stable indices in the registration order
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(interner.get("a"), Some(a));
+    assert_eq!(interner.get("c"), None);
+    assert_eq!(interner.resolve(b), Some("b"));
+    assert_eq!(interner.resolve(Symbol(2)), None);
+    assert_eq!(interner.iter().collect::<Vec<_>>(), [(a, "a"), (b, "b")]);
+  }
+
+  #[test]
+  fn interner_no_collisions() {
+    let mut interner = Interner::new();
+    // similar strings, prefixes, the empty string and non-ascii strings
+    let strings = ["", "a", "aa", "ab", "ba", "A", "a ", "好", "好好", "\0"];
+    let symbols = strings.map(|s| interner.intern(s));
+    for (i, s) in strings.iter().enumerate() {
+      assert_eq!(symbols[i].index(), i);
+      assert_eq!(interner.resolve(symbols[i]), Some(*s));
+    }
+    // many strings
+    for i in 0..1000 {
+      let symbol = interner.intern(&i.to_string());
+      assert_eq!(interner.resolve(symbol), Some(i.to_string().as_str()));
+    }
+    assert_eq!(interner.len(), strings.len() + 1000);
+    for i in 0..1000 {
+      assert_eq!(
+        interner.get(&i.to_string()).map(Symbol::index),
+        Some(strings.len() + i)
+      );
+    }
+  }
+}
@@ -0,0 +1,84 @@
+//! Lookup tables for byte classification in hot paths.
+//!
+//! Each entry of [`ASCII_CLASSES`] is a bit set of the classes of the byte,
+//! so checking a byte against any combination of classes is one load and one mask.
+//! Non-ASCII bytes belong to no class.
+
+/// `A-Z` and `a-z`, see [`u8::is_ascii_alphabetic`].
+pub(crate) const ALPHA: u8 = 1 << 0;
+/// `0-9`, see [`u8::is_ascii_digit`].
+pub(crate) const DIGIT: u8 = 1 << 1;
+/// `0-9`, `A-F` and `a-f`, see [`u8::is_ascii_hexdigit`].
+pub(crate) const HEX_DIGIT: u8 = 1 << 2;
+/// Space, `\t`, `\n`, `\x0C` and `\r`, see [`u8::is_ascii_whitespace`].
+pub(crate) const WHITESPACE: u8 = 1 << 3;
+/// [`ALPHA`] or [`DIGIT`], see [`u8::is_ascii_alphanumeric`].
+pub(crate) const ALNUM: u8 = ALPHA | DIGIT;
+
+const fn build() -> [u8; 256] {
+  let mut table = [0; 256];
+  let mut i = 0;
+  while i < 256 {
+    let b = i as u8;
+    let mut class = 0;
+    if b.is_ascii_alphabetic() {
+      class |= ALPHA;
+    }
+    if b.is_ascii_digit() {
+      class |= DIGIT;
+    }
+    if b.is_ascii_hexdigit() {
+      class |= HEX_DIGIT;
+    }
+    if b.is_ascii_whitespace() {
+      class |= WHITESPACE;
+    }
+    table[i] = class;
+    i += 1;
+  }
+  table
+}
+
+/// The classes of each byte.
+static ASCII_CLASSES: [u8; 256] = build();
+
+/// Check if `b` is in any of the classes in `mask`.
+#[inline]
+pub(crate) fn is_in(b: u8, mask: u8) -> bool {
+  ASCII_CLASSES[b as usize] & mask != 0
+}
+
+/// Return the number of the leading bytes of `rest` which are in any of the classes in `mask`.
+#[inline]
+pub(crate) fn scan(rest: &[u8], mask: u8) -> usize {
+  let mut i = 0;
+  while i < rest.len() && is_in(rest[i], mask) {
+    i += 1;
+  }
+  i
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ascii_classes_match_std() {
+    for b in 0..=255u8 {
+      assert_eq!(is_in(b, ALPHA), b.is_ascii_alphabetic(), "{}", b);
+      assert_eq!(is_in(b, DIGIT), b.is_ascii_digit(), "{}", b);
+      assert_eq!(is_in(b, HEX_DIGIT), b.is_ascii_hexdigit(), "{}", b);
+      assert_eq!(is_in(b, WHITESPACE), b.is_ascii_whitespace(), "{}", b);
+      assert_eq!(is_in(b, ALNUM), b.is_ascii_alphanumeric(), "{}", b);
+    }
+  }
+
+  #[test]
+  fn ascii_classes_scan() {
+    assert_eq!(scan(b"123abc", DIGIT), 3);
+    assert_eq!(scan(b"123abc", ALNUM), 6);
+    assert_eq!(scan(b"abc", DIGIT), 0);
+    assert_eq!(scan(b"", DIGIT), 0);
+    assert_eq!(scan(" \t好".as_bytes(), WHITESPACE), 2);
+  }
+}
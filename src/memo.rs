@@ -0,0 +1,183 @@
+//! Memoize the results of combinators by offset (a.k.a packrat parsing).
+//!
+//! When a grammar backtracks heavily, the same sub-combinator might be executed
+//! at the same offset many times across the branches of a `|` chain.
+//! Mark the sub-combinator with [`Combinator::memo`](crate::combinator::Combinator::memo)
+//! and use [`MemoTable`] as (or inside) the `Heap` of the parser,
+//! then the sub-combinator is executed at most once per offset.
+//!
+//! ```
+//! use whitehole::{combinator::{contextual, Combinator}, memo::MemoTable, parser::Parser};
+//!
+//! contextual!((), MemoTable);
+//!
+//! # fn main() {
+//! let number = || (next(|c| c.is_ascii_digit()) * (1..)).memo(0);
+//! // without `memo`, `number` is executed twice when the first branch rejects
+//! let entry = (number() + eat('+') + number()) | number();
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(MemoTable::new())
+//!   .entry(entry)
+//!   .build("123");
+//! assert_eq!(parser.next().unwrap().digested, 3);
+//! # }
+//! ```
+//!
+//! Memoization is only correct if the result of the sub-combinator
+//! only depends on the text and the offset.
+//! On a cache hit, the sub-combinator is not executed,
+//! so its mutations to the `State` and `Heap` are not replayed.
+//!
+//! The table is cleared automatically when it is used with a different text
+//! (identified by the address and the length),
+//! e.g. after [`Parser::reload`](crate::parser::Parser::reload).
+//! If the text buffer is mutated in place and parsed again,
+//! call [`MemoTable::clear`], otherwise the stale results of the previous text may be returned.
+//! Stale outputs which can't be digested from the current text are never returned.
+//!
+//! ```
+//! # use whitehole::{combinator::{contextual, Combinator}, memo::MemoTable, parser::Parser};
+//! # contextual!((), MemoTable);
+//! # fn main() {
+//! # let number = || (next(|c| c.is_ascii_digit()) * (1..)).memo(0);
+//! # let entry = (number() + eat('+') + number()) | number();
+//! # let mut parser = Parser::builder()
+//! #   .heap(MemoTable::new())
+//! #   .entry(entry)
+//! #   .build("123");
+//! # parser.next();
+//! let mut parser = parser.reload("1+2");
+//! assert_eq!(parser.next().unwrap().digested, 3);
+//! # }
+//! ```
+//!
+//! Memoized combinators pay a hash map lookup per execution, un-memoized combinators pay nothing.
+
+use crate::{
+  action::{Output, Rejection},
+  digest::Digest,
+};
+use std::{any::Any, collections::HashMap};
+
+/// Results of combinators marked by [`Combinator::memo`](crate::combinator::Combinator::memo),
+/// keyed by the memo id and the start offset.
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Default)]
+pub struct MemoTable {
+  /// The address and the length of the text which the results belong to.
+  text: Option<(usize, usize)>,
+  results: HashMap<(usize, usize), Box<dyn Any>>,
+}
+
+impl MemoTable {
+  /// Create a new instance with nothing memoized.
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The number of memoized results.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.results.len()
+  }
+
+  /// Return `true` if nothing is memoized.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.results.is_empty()
+  }
+
+  /// Remove all memoized results, e.g. after the text is mutated in place.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.text = None;
+    self.results.clear();
+  }
+
+  /// Clear the table if the `text` (identified by the address and the length) is changed.
+  #[inline]
+  pub(crate) fn sync<Text: ?Sized + Digest>(&mut self, text: &Text) {
+    let key = (text.as_bytes().as_ptr() as usize, text.len());
+    if self.text != Some(key) {
+      self.results.clear();
+      self.text = Some(key);
+    }
+  }
+
+  /// Get the memoized result of the combinator `id` at the `offset`.
+  /// Return [`None`] if nothing is memoized.
+  #[inline]
+  pub(crate) fn get<Value: Clone + 'static>(
+    &self,
+    id: usize,
+    offset: usize,
+  ) -> Option<Result<Output<Value>, Rejection>> {
+    let result = self.results.get(&(id, offset))?;
+    let result = result.downcast_ref::<Result<Output<Value>, Rejection>>();
+    debug_assert!(
+      result.is_some(),
+      "memo id {} is used by combinators with different value types",
+      id
+    );
+    result.cloned()
+  }
+
+  /// Memoize the `result` of the combinator `id` at the `offset`.
+  #[inline]
+  pub(crate) fn insert<Value: 'static>(
+    &mut self,
+    id: usize,
+    offset: usize,
+    result: Result<Output<Value>, Rejection>,
+  ) {
+    self.results.insert((id, offset), Box::new(result));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn memo_table() {
+    let text = "123";
+    let mut table = MemoTable::new();
+    assert!(table.is_empty());
+
+    table.sync(text);
+
+    assert_eq!(table.get::<i32>(0, 0), None);
+    table.insert(
+      0,
+      0,
+      Ok(Output {
+        value: 1,
+        digested: 1,
+      }),
+    );
+    table.insert::<i32>(0, 1, Err(Rejection::Cut));
+    assert_eq!(table.len(), 2);
+    assert_eq!(
+      table.get::<i32>(0, 0),
+      Some(Ok(Output {
+        value: 1,
+        digested: 1
+      }))
+    );
+    assert_eq!(table.get::<i32>(0, 1), Some(Err(Rejection::Cut)));
+    assert_eq!(table.get::<i32>(1, 0), None);
+
+    // same text
+    table.sync(text);
+    assert_eq!(table.len(), 2);
+    // different text
+    table.sync(&text[1..]);
+    assert!(table.is_empty());
+
+    table.insert::<i32>(0, 1, Err(Rejection::Cut));
+    table.clear();
+    assert!(table.is_empty());
+  }
+}
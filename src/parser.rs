@@ -4,6 +4,7 @@
 //! # Build the Parser
 //!
 //! See [`Builder`].
+//! Use [`Builder::validate_with`] to check the entry action against probe inputs in debug builds.
 //!
 //! # Parse and Peek
 //!
@@ -23,6 +24,12 @@
 //! let (output, state) = parser.peek();
 //! ```
 //!
+//! [`Parser::peek`] doesn't clone the heap, so heap mutations during the peek are kept.
+//! Use [`Parser::peek_isolated`] to clone the heap too.
+//!
+//! To look several outputs ahead (e.g. for LL(k) decisions),
+//! use [`Parser::peek_n`] or the lazy [`Parser::peek_iter`].
+//!
 //! # Iter
 //!
 //! [`Parser`] implements [`Iterator`] so you can use it in a for-loop
@@ -50,6 +57,28 @@
 //! }
 //! ```
 //!
+//! [`Parser`] doesn't implement [`FusedIterator`](std::iter::FusedIterator),
+//! since [`Parser::next`] may yield again after a rejection
+//! if [`Parser::instant`] or [`Parser::state`] is updated from outside
+//! (see [External Digestion](#external-digestion)).
+//! Use [`Iterator::fuse`] if you want to stop at the first rejection
+//! without re-executing the entry action (and its side effects).
+//!
+//! ```
+//! use whitehole::{combinator::eat, parser::Parser};
+//!
+//! let mut parser = Parser::builder()
+//!   .entry(eat("123"))
+//!   .build("123a");
+//!
+//! let mut iter = (&mut parser).fuse();
+//! assert!(iter.next().is_some());
+//! assert!(iter.next().is_none());
+//! assert!(iter.next().is_none());
+//! assert!(!parser.is_done());
+//! assert_eq!(parser.remaining(), 1);
+//! ```
+//!
 //! # Progress
 //!
 //! ## Instant
@@ -87,6 +116,8 @@
 //!
 //! You can update [`Parser::instant`] to digest from outside of the parser.
 //! e.g. in error handling or recovery.
+//! Use [`Instant::digest`] (or [`Instant::digest_rest`]) to digest safely,
+//! or [`Instant::digest_unchecked`] if the length is already validated.
 //!
 //! Be ware to keep the `State` and `Heap` in sync with the progress.
 //!
@@ -101,13 +132,25 @@
 //!
 //! // enter "panic mode", digest the next char from outside
 //! let next_len = parser.instant.rest().chars().next().unwrap().len_utf8();
-//! unsafe { parser.instant.digest_unchecked(next_len) };
+//! parser.instant.digest(next_len).unwrap();
 //! assert_eq!(parser.instant.rest(), "123");
 //!
 //! // now we can try to yield again
 //! assert!(parser.next().is_some());
 //! ```
 //!
+//! ## Multiple Entries
+//!
+//! To start from different top-level rules (e.g. an expression or a statement)
+//! with the same parser, use [`Builder::entry_named`] to register [`NamedEntries`],
+//! then use [`Parser::next_from`] or [`Parser::set_default_entry`] to select one at runtime.
+//!
+//! ## Error Reporting
+//!
+//! To tell the user what was expected when the entry action rejects,
+//! use [`Combinator::expect`](crate::combinator::Combinator::expect) with [`Parser::next_verbose`].
+//! See [`expect`](crate::expect) for more information.
+//!
 //! ## Snapshots
 //!
 //! [`Parser`] is clone-able when your entry action, `State` and `Heap` are all clone-able.
@@ -133,6 +176,31 @@
 //!
 //! It's like [`Parser::peek`], but you can save as many snapshots as you want.
 //!
+//! ## Owned Text
+//!
+//! [`Parser`] borrows the text. To store a parser in a struct
+//! or move it into another thread together with its input,
+//! use [`Builder::build_owned`] to create an [`OwnedParser`]
+//! which owns the text (e.g. an [`Arc<str>`](std::sync::Arc)).
+//!
+//! ## Incremental Re-parsing
+//!
+//! For editors (e.g. a language server), use [`Parser::record_boundaries`]
+//! to record the boundary after each output,
+//! then use [`IncrementalParser::apply_edit`] to resume from the last boundary before an edit
+//! instead of re-parsing the whole text.
+//!
+//! ## Progress Reporting
+//!
+//! For long inputs (e.g. a progress bar in a CLI tool),
+//! use [`Builder::progress`] to get throttled callbacks with the digested bytes and the total bytes.
+//!
+//! ## Streaming
+//!
+//! If the text arrives in chunks (e.g. from a socket),
+//! use [`Builder::build_streaming`] to create a [`StreamingParser`]
+//! which can be fed incrementally and tells "need more input" apart from rejection.
+//!
 //! # State and Heap
 //!
 //! Parser will manage [`Parser::state`] which is accessible by actions
@@ -142,20 +210,60 @@
 //! You can use the heap to pass data to actions or store data that is generated by actions.
 //!
 //! See [`Parser::state`] and [`Parser::heap`] for more information.
+//!
+//! To reuse heaps across many small parses (e.g. in a server), see [`ParserPool`].
+//!
+//! # Panic Safety
+//!
+//! If a user closure (e.g. in `select`, `then` or `fold`) panics
+//! and the panic is caught outside of the parser (e.g. by a server worker),
+//! [`Parser::instant`] is guaranteed to be unchanged,
+//! since the digestion only happens after the entry action returns.
+//! A subsequent [`Parser::next`] will restart from the same position.
+//!
+//! However, [`Parser::state`] and [`Parser::heap`] might be torn,
+//! since they can be mutated by actions before the panic.
+//! It's recommended to drop the parser after a panic,
+//! or restore a [`Snapshot`] and reset the heap before reusing it.
 
 mod builder;
+mod entries;
+mod incremental;
+mod owned;
+mod peek;
+mod pool;
+mod progress;
 mod snapshot;
+mod streaming;
+mod validate;
 
 pub use builder::*;
+pub use entries::*;
+pub use incremental::*;
+pub use owned::*;
+pub use peek::*;
+pub use pool::*;
+pub use progress::*;
 pub use snapshot::*;
+pub use streaming::*;
+pub use validate::*;
 
 use crate::{
   action::{Action, Input, Output},
-  combinator::Take,
+  combinator::{preview, FormatUndigested, Take},
   digest::Digest,
+  expect::{Expectations, ParseError},
   instant::Instant,
+  position::LineColLookup,
+  range::Range,
+  stats::BranchStats,
+};
+use std::{
+  borrow::{Borrow, BorrowMut},
+  fmt::{self, Debug, Display},
+  ops::{RangeFrom, RangeTo},
+  slice::SliceIndex,
 };
-use std::{ops::RangeFrom, slice::SliceIndex};
 
 /// Manage the [`State`](Parser::state), [`Heap`](Parser::heap)
 /// and the [parsing progress](Parser::instant).
@@ -230,7 +338,7 @@ impl<'text, T: Action> Parser<'text, T> {
   /// [`Self::instant`] and [`Self::state`] will be reset to default.
   /// [`Self::heap`] won't change.
   #[inline]
-  pub fn reload(self, text: &T::Text) -> Parser<T>
+  pub fn reload(self, text: &T::Text) -> Parser<'_, T>
   where
     T::State: Default,
   {
@@ -243,7 +351,7 @@ impl<'text, T: Action> Parser<'text, T> {
   /// [`Self::instant`] will be reset to default.
   /// [`Self::heap`] won't change.
   #[inline]
-  pub fn reload_with(self, state: impl Into<Option<T::State>>, text: &T::Text) -> Parser<T> {
+  pub fn reload_with(self, state: impl Into<Option<T::State>>, text: &T::Text) -> Parser<'_, T> {
     Parser {
       entry: self.entry,
       heap: self.heap,
@@ -274,6 +382,13 @@ impl<'text, T: Action> Parser<'text, T> {
   /// Try to yield the next [`Output`] without updating [`Self::instant`] and [`Self::state`].
   /// [`Self::state`] will be cloned and returned.
   /// Return [`None`] if the action rejects.
+  ///
+  /// **[`Self::heap`] is NOT cloned.**
+  /// The entry action is executed with the real heap,
+  /// so heap mutations (e.g. in [`prepare`](crate::combinator::Combinator::prepare)
+  /// or [`then`](crate::combinator::Combinator::then)) during the peek are kept.
+  /// This is cheap and fine for reusable buffers,
+  /// use [`Self::peek_isolated`] if the heap must not be changed.
   #[inline]
   pub fn peek(&mut self) -> (Option<Output<T::Value>>, T::State)
   where
@@ -289,6 +404,144 @@ impl<'text, T: Action> Parser<'text, T> {
       tmp_state,
     )
   }
+
+  /// Like [`Self::peek`], but [`Self::heap`] is also cloned,
+  /// so the parser is not changed at all.
+  /// The state and heap after the peek are returned.
+  ///
+  /// This is more expensive than [`Self::peek`] if the heap is large.
+  /// # Examples
+  /// ```
+  /// use whitehole::{contextual, parser::Parser};
+  ///
+  /// contextual!((), Vec<usize>);
+  ///
+  /// let mut parser = Parser::builder()
+  ///   .heap(vec![])
+  ///   .entry(eat("a").then(|accepted| accepted.heap.push(accepted.start())))
+  ///   .build("a");
+  /// let (output, _state, heap) = parser.peek_isolated();
+  /// assert!(output.is_some());
+  /// assert_eq!(heap, [0]);
+  /// assert!(parser.heap.is_empty());
+  /// ```
+  #[inline]
+  pub fn peek_isolated(&self) -> (Option<Output<T::Value>>, T::State, T::Heap)
+  where
+    T::State: Clone,
+    T::Heap: Clone,
+  {
+    let mut tmp_state = self.state.clone();
+    let mut tmp_heap = self.heap.clone();
+    let output = self.entry.exec(Input {
+      instant: &self.instant,
+      state: &mut tmp_state,
+      heap: &mut tmp_heap,
+    });
+    (output, tmp_state, tmp_heap)
+  }
+
+  /// The length of the undigested rest in bytes.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11a");
+  /// assert_eq!(parser.remaining(), 3);
+  /// parser.next();
+  /// assert_eq!(parser.remaining(), 2);
+  /// ```
+  #[inline]
+  pub fn remaining(&self) -> usize
+  where
+    T::Text: Digest,
+  {
+    self.instant.rest().len()
+  }
+
+  /// Return `true` if the whole text is digested.
+  ///
+  /// This doesn't mean [`Self::next`] will return [`None`],
+  /// since the entry action may still accept zero-width outputs.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("1");
+  /// assert!(!parser.is_done());
+  /// parser.next();
+  /// assert!(parser.is_done());
+  /// ```
+  #[inline]
+  pub fn is_done(&self) -> bool
+  where
+    T::Text: Digest,
+  {
+    self.instant.rest().is_empty()
+  }
+
+  /// Consume self, return [`Self::state`] and [`Self::heap`]
+  /// if the whole text is digested.
+  /// Otherwise return self back so you can inspect the undigested rest.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11a");
+  /// for _ in &mut parser {}
+  /// let parser = parser.finish().unwrap_err();
+  /// assert_eq!(parser.instant.rest(), "a");
+  /// ```
+  #[inline]
+  pub fn finish(self) -> Result<(T::State, T::Heap), Self>
+  where
+    T::Text: Digest,
+  {
+    if self.is_done() {
+      Ok((self.state, self.heap))
+    } else {
+      Err(self)
+    }
+  }
+
+  /// Create a [`LineColLookup`] for the whole text
+  /// to convert byte offsets (e.g. [`Instant::digested`]) to line and column numbers.
+  ///
+  /// This scans the whole text, so build it once and reuse it for multiple lookups.
+  #[inline]
+  pub fn line_col_lookup(&self) -> LineColLookup<'text, T::Text>
+  where
+    T::Text: Digest + AsRef<[u8]>,
+  {
+    LineColLookup::new(self.instant.text())
+  }
+
+  /// Get the [`BranchStats`] in [`Self::heap`].
+  /// See [`stats`](crate::stats) for more information.
+  #[inline]
+  pub fn branch_stats(&self) -> &BranchStats
+  where
+    T::Heap: Borrow<BranchStats>,
+  {
+    self.heap.borrow()
+  }
+
+  /// Get the furthest offset where a combinator marked by
+  /// [`Combinator::track`](crate::combinator::Combinator::track)
+  /// or [`Combinator::expect`](crate::combinator::Combinator::expect) rejected,
+  /// from the [`Expectations`] in [`Self::heap`].
+  /// Return [`None`] if no such combinator rejected.
+  ///
+  /// The record is cleared by [`Self::next_verbose`] before each execution,
+  /// so use it instead of [`Self::next`] to only track the last output.
+  /// See [`expect`](crate::expect) for more information.
+  #[inline]
+  pub fn furthest_failure(&self) -> Option<usize>
+  where
+    T::Heap: Borrow<Expectations>,
+  {
+    self.heap.borrow().offset()
+  }
 }
 
 impl<T: Action<Text: Digest>> Iterator for Parser<'_, T>
@@ -297,6 +550,12 @@ where
 {
   type Item = Output<T::Value>;
 
+  /// Try to yield the next [`Output`] and digest it.
+  /// Return [`None`] if the entry action rejects.
+  ///
+  /// [`Parser::instant`] is only updated after the entry action returns,
+  /// so it is unchanged if the entry action panics.
+  /// See the [module-level documentation](self#panic-safety) for more.
   #[inline]
   fn next(&mut self) -> Option<Self::Item> {
     self
@@ -308,6 +567,289 @@ where
       })
       .inspect(|output| unsafe { self.instant.digest_unchecked(output.digested) })
   }
+
+  /// Always return `(0, None)`.
+  ///
+  /// [`Self::remaining`] is not an upper bound of the number of outputs,
+  /// since the entry action may accept zero-width outputs (e.g. at the end of the text).
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, None)
+  }
+}
+
+/// The error returned by [`Parser::parse_to_end`], [`Parser::fold_outputs`] and similar methods
+/// when the entry action rejects before the end of the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIncompleteError {
+  /// The number of digested bytes, i.e. [`Instant::digested`] when the entry action rejected.
+  pub digested: usize,
+  /// The length of the undigested rest in bytes.
+  pub remaining: usize,
+  /// The undigested rest formatted by [`Debug`]
+  /// and truncated by [`LOG_UNDIGESTED_MAX_LEN`](crate::combinator::LOG_UNDIGESTED_MAX_LEN).
+  pub preview: String,
+}
+
+impl Display for ParseIncompleteError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "parsing stopped at {} with {} bytes remaining: {}",
+      self.digested, self.remaining, self.preview
+    )
+  }
+}
+
+impl std::error::Error for ParseIncompleteError {}
+
+impl<T: Action<Text: Digest>> Parser<'_, T>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  /// Like [`Self::next`], but pass the expected `literal` to the entry action
+  /// via [`Instant::expectation`].
+  ///
+  /// Combinators decorated by [`Combinator::when_expected`](crate::combinator::Combinator::when_expected)
+  /// with a different literal won't be executed,
+  /// so the expectation can disambiguate the grammar,
+  /// while other combinators (e.g. whitespaces) are executed as usual.
+  /// The expectation is only used in this execution, [`Self::instant`] won't keep it.
+  ///
+  /// This is useful for guided parsing, e.g. when the grammar expects a specific token next.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let entry = || eat("=").when_expected("=") | eat("==").when_expected("==");
+  /// // without the expectation, the first branch accepts
+  /// assert_eq!(Parser::builder().entry(entry()).build("==").next().unwrap().digested, 1);
+  /// // with the expectation, the first branch is skipped
+  /// let mut parser = Parser::builder().entry(entry()).build("==");
+  /// assert_eq!(parser.next_expecting("==").unwrap().digested, 2);
+  /// ```
+  pub fn next_expecting(&mut self, literal: &T::Text) -> Option<Output<T::Value>> {
+    let instant = self.instant.clone().with_expectation(Some(literal));
+    let output = self.entry.exec(Input {
+      instant: &instant,
+      state: &mut self.state,
+      heap: &mut self.heap,
+    })?;
+    // SAFETY: the output is valid against the rest of `instant`,
+    // which is the same as the rest of `self.instant`
+    unsafe { self.instant.digest_unchecked(output.digested) };
+    Some(output)
+  }
+
+  /// Like [`Self::next`], but return a [`ParseError`] with the expected labels
+  /// at the furthest offset if the entry action rejects.
+  ///
+  /// The [`Expectations`] in [`Self::heap`] is cleared before the entry action is executed.
+  /// See [`expect`](crate::expect) for more information.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::contextual, expect::Expectations, parser::Parser};
+  ///
+  /// contextual!((), Expectations);
+  ///
+  /// # fn main() {
+  /// let mut parser = Parser::builder()
+  ///   .heap(Expectations::new())
+  ///   .entry(eat("a").expect("'a'") | eat("b").expect("'b'"))
+  ///   .build("c");
+  /// assert_eq!(parser.next_verbose().unwrap_err().to_string(), "expected 'a' or 'b' at offset 0");
+  /// # }
+  /// ```
+  pub fn next_verbose(&mut self) -> Result<Output<T::Value>, ParseError>
+  where
+    T::Heap: BorrowMut<Expectations>,
+  {
+    self.heap.borrow_mut().clear();
+    self.next().ok_or_else(|| {
+      let expectations = self.heap.borrow_mut();
+      ParseError {
+        offset: expectations
+          .offset()
+          .unwrap_or_else(|| self.instant.digested()),
+        expected: expectations.labels().to_vec(),
+      }
+    })
+  }
+
+  /// Yield and digest outputs until the entry action rejects,
+  /// collect the byte range of each output together with its value.
+  ///
+  /// The ranges are relative to the start of the whole text, not the position where this is called.
+  /// This is useful for golden tests and external tooling,
+  /// see the `serde` feature to serialize [`Output`]s directly.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("a").bind(1) | eat("bb").bind(2)).build("abba");
+  /// assert_eq!(parser.collect_ranges(), [(0..1, 1), (1..3, 2), (3..4, 1)]);
+  /// ```
+  pub fn collect_ranges(&mut self) -> Vec<(Range, T::Value)> {
+    let mut ranges = Vec::new();
+    loop {
+      let start = self.instant.digested();
+      let Some(output) = self.next() else {
+        break ranges;
+      };
+      ranges.push((start..self.instant.digested(), output.value));
+    }
+  }
+}
+
+impl<T: Action<Text: Digest + FormatUndigested + Debug>> Parser<'_, T>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  /// Yield all outputs until the entry action rejects,
+  /// then check whether the whole text is digested.
+  ///
+  /// On error, [`Self::instant`] stays where the entry action rejected,
+  /// so you can inspect the rest or recover from there.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11");
+  /// assert_eq!(parser.parse_to_end().unwrap().len(), 2);
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11a");
+  /// let err = parser.parse_to_end().unwrap_err();
+  /// assert_eq!(err.digested, 2);
+  /// assert_eq!(err.remaining, 1);
+  /// assert_eq!(err.preview, "\"a\"");
+  /// ```
+  #[inline]
+  pub fn parse_to_end(&mut self) -> Result<Vec<Output<T::Value>>, ParseIncompleteError> {
+    self.collect_outputs()
+  }
+
+  /// Like [`Self::parse_to_end`], but collect the outputs into any [`FromIterator`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{action::Output, combinator::eat, parser::Parser};
+  /// use std::collections::VecDeque;
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11");
+  /// let outputs: VecDeque<Output<()>> = parser.collect_outputs().unwrap();
+  /// assert_eq!(outputs.len(), 2);
+  /// ```
+  #[inline]
+  pub fn collect_outputs<C: FromIterator<Output<T::Value>>>(
+    &mut self,
+  ) -> Result<C, ParseIncompleteError> {
+    let outputs = self.by_ref().collect();
+    self.check_end().map(|_| outputs)
+  }
+
+  /// Yield all outputs until the entry action rejects and fold them into one value,
+  /// then check whether the whole text is digested.
+  ///
+  /// On error, [`Self::instant`] stays where the entry action rejected,
+  /// see [`Self::parse_to_end`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::{eat, next}, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder()
+  ///   .entry(next(|c| c.is_ascii_digit()).select(|a| a.content().parse().unwrap()) | eat(' ').bind(0))
+  ///   .build("1 2 3");
+  /// assert_eq!(parser.fold_outputs(0, |acc, o| acc + o.value), Ok(6));
+  /// ```
+  #[inline]
+  pub fn fold_outputs<Acc>(
+    &mut self,
+    init: Acc,
+    mut f: impl FnMut(Acc, Output<T::Value>) -> Acc,
+  ) -> Result<Acc, ParseIncompleteError> {
+    self.try_fold_outputs(init, |acc, output| Ok(f(acc, output)))
+  }
+
+  /// Like [`Self::fold_outputs`], but `f` can fail and stop the parsing early.
+  ///
+  /// The error type must be convertible from [`ParseIncompleteError`],
+  /// which is returned if the entry action rejects before the end of the text.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::{eat, next}, parser::{Parser, ParseIncompleteError}};
+  ///
+  /// #[derive(Debug, PartialEq)]
+  /// enum Error {
+  ///   Overflow,
+  ///   Incomplete(ParseIncompleteError),
+  /// }
+  /// impl From<ParseIncompleteError> for Error {
+  ///   fn from(e: ParseIncompleteError) -> Self {
+  ///     Error::Incomplete(e)
+  ///   }
+  /// }
+  ///
+  /// let mut parser = Parser::builder()
+  ///   .entry(next(|c| c.is_ascii_digit()).select(|a| a.content().parse::<u8>().unwrap()))
+  ///   .build("9999");
+  /// let sum = parser.try_fold_outputs(0u8, |acc, o| acc.checked_add(o.value * 10).ok_or(Error::Overflow));
+  /// assert_eq!(sum, Err(Error::Overflow));
+  /// ```
+  pub fn try_fold_outputs<Acc, E: From<ParseIncompleteError>>(
+    &mut self,
+    init: Acc,
+    mut f: impl FnMut(Acc, Output<T::Value>) -> Result<Acc, E>,
+  ) -> Result<Acc, E> {
+    let mut acc = init;
+    for output in self.by_ref() {
+      acc = f(acc, output)?;
+    }
+    self.check_end()?;
+    Ok(acc)
+  }
+
+  /// Call `f` with each output until the entry action rejects or `f` fails,
+  /// then check whether the whole text is digested.
+  ///
+  /// This is named differently from [`Iterator::try_for_each`],
+  /// which doesn't check the end of the text.
+  /// See [`Self::try_fold_outputs`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::{Parser, ParseIncompleteError}};
+  ///
+  /// let mut count = 0;
+  /// let mut parser = Parser::builder().entry(eat("1")).build("11a");
+  /// let err = parser
+  ///   .try_for_each_output(|_| {
+  ///     count += 1;
+  ///     Ok::<_, ParseIncompleteError>(())
+  ///   })
+  ///   .unwrap_err();
+  /// assert_eq!(count, 2);
+  /// assert_eq!(err.digested, 2);
+  /// ```
+  #[inline]
+  pub fn try_for_each_output<E: From<ParseIncompleteError>>(
+    &mut self,
+    mut f: impl FnMut(Output<T::Value>) -> Result<(), E>,
+  ) -> Result<(), E> {
+    self.try_fold_outputs((), |_, output| f(output))
+  }
+
+  /// Return [`ParseIncompleteError`] if the whole text is not digested.
+  fn check_end(&self) -> Result<(), ParseIncompleteError> {
+    let rest = self.instant.rest();
+    if rest.is_empty() {
+      Ok(())
+    } else {
+      Err(ParseIncompleteError {
+        digested: self.instant.digested(),
+        remaining: rest.len(),
+        preview: preview(rest),
+      })
+    }
+  }
 }
 
 #[cfg(test)]
@@ -480,6 +1022,54 @@ mod tests {
     assert!(parser.next().is_some());
   }
 
+  #[test]
+  fn parser_peek_heap() {
+    contextual!(i32, Vec<usize>);
+
+    let entry = || {
+      eat("a")
+        .prepare(|input| *input.state += 1)
+        .then(|accepted| accepted.heap.push(accepted.start()))
+    };
+
+    // the cheap peek keeps heap mutations
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(vec![])
+      .entry(entry())
+      .build("aa");
+    let (output, state) = parser.peek();
+    assert_eq!(output.unwrap().digested, 1);
+    assert_eq!(state, 1);
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.heap, [0]);
+    parser.next();
+    assert_eq!(parser.heap, [0, 0]);
+
+    // the isolated peek doesn't change the parser
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(vec![])
+      .entry(entry())
+      .build("aa");
+    let (output, state, heap) = parser.peek_isolated();
+    assert_eq!(output.unwrap().digested, 1);
+    assert_eq!(state, 1);
+    assert_eq!(heap, [0]);
+    assert_eq!(parser.state, 0);
+    assert!(parser.heap.is_empty());
+    assert_eq!(parser.instant.digested(), 0);
+    parser.next();
+    parser.next();
+    assert_eq!(parser.heap, [0, 1]);
+
+    // rejection
+    let (output, state, heap) = parser.peek_isolated();
+    assert!(output.is_none());
+    assert_eq!(state, 3);
+    assert_eq!(heap, [0, 1]);
+  }
+
   #[test]
   fn parser_iterator_in_for_loop() {
     contextual!(i32, i32);
@@ -497,6 +1087,7 @@ mod tests {
   }
 
   #[test]
+  #[allow(clippy::unused_enumerate_index)]
   fn parser_iterator_with_iter_methods() {
     contextual!(i32, i32);
 
@@ -511,4 +1102,512 @@ mod tests {
     }
     assert_eq!(parser.instant.digested(), 9);
   }
+
+  #[test]
+  fn parser_remaining_is_done() {
+    contextual!((), ());
+
+    let mut parser = Parser::builder().entry(eat("1")).build("11");
+    assert_eq!(parser.remaining(), 2);
+    assert!(!parser.is_done());
+    assert_eq!(parser.size_hint(), (0, None));
+    parser.next();
+    assert_eq!(parser.remaining(), 1);
+    assert!(!parser.is_done());
+    parser.next();
+    assert_eq!(parser.remaining(), 0);
+    assert!(parser.is_done());
+
+    // zero-width outputs at the end of the text
+    let mut parser = Parser::builder().entry(eat("")).build("");
+    assert!(parser.is_done());
+    assert_eq!(parser.size_hint(), (0, None));
+    assert_eq!(parser.by_ref().take(3).count(), 3);
+  }
+
+  #[test]
+  fn parser_fuse_with_side_effects() {
+    contextual!((), usize);
+
+    let mut parser = Parser::builder()
+      .heap(0)
+      .entry(eat("1").catch(|input| *input.heap += 1))
+      .build("11a");
+
+    let mut iter = (&mut parser).fuse();
+    assert_eq!(iter.by_ref().count(), 2);
+    // stays stopped without re-executing the entry action
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+    assert_eq!(parser.heap, 1);
+    assert_eq!(parser.remaining(), 1);
+
+    // the unfused parser re-executes the entry action
+    assert!(parser.next().is_none());
+    assert_eq!(parser.heap, 2);
+  }
+
+  #[test]
+  fn parser_finish() {
+    contextual!(i32, i32);
+
+    let mut parser = Parser::builder()
+      .state(1)
+      .heap(2)
+      .entry(eat("1"))
+      .build("11");
+    for _ in &mut parser {}
+    assert_eq!(parser.finish().ok(), Some((1, 2)));
+
+    let mut parser = Parser::builder()
+      .state(1)
+      .heap(2)
+      .entry(eat("1"))
+      .build("1a");
+    for _ in &mut parser {}
+    let parser = parser.finish().unwrap_err();
+    assert_eq!(parser.instant.digested(), 1);
+
+    // empty text
+    let parser = Parser::builder()
+      .state(1)
+      .heap(2)
+      .entry(bytes::eat(b'a'))
+      .build(b"" as &[u8]);
+    assert!(parser.finish().is_ok());
+  }
+
+  #[test]
+  fn parser_panic_safety() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    // the heap controls whether the selector panics
+    contextual!(i32, bool);
+
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(false)
+      .entry(
+        eat("a")
+          .then(|accepted| *accepted.state += 1)
+          .select(|accepted| {
+            if *accepted.heap {
+              panic!("selector panicked");
+            }
+            accepted.start()
+          }),
+      )
+      .build("aaa");
+
+    assert_eq!(parser.next().map(|o| o.value), Some(0));
+    assert_eq!(parser.instant.digested(), 1);
+
+    parser.heap = true;
+    let snapshot = parser.snapshot();
+    assert!(catch_unwind(AssertUnwindSafe(|| parser.next())).is_err());
+    // the instant is not advanced
+    assert_eq!(parser.instant.digested(), 1);
+    assert_eq!(parser.instant.rest(), "aa");
+    // the state is torn
+    assert_eq!(parser.state, 2);
+
+    // panic again deterministically
+    assert!(catch_unwind(AssertUnwindSafe(|| parser.next())).is_err());
+    assert_eq!(parser.instant.digested(), 1);
+
+    // restore and continue from the same position
+    parser.restore(snapshot);
+    parser.heap = false;
+    assert_eq!(parser.state, 1);
+    assert_eq!(parser.next().map(|o| o.value), Some(1));
+    assert_eq!(parser.next().map(|o| o.value), Some(2));
+    assert!(parser.next().is_none());
+    assert_eq!(parser.state, 3);
+  }
+
+  #[test]
+  fn parser_parse_to_end() {
+    contextual!((), ());
+
+    // full consumption
+    let mut parser = Parser::builder().entry(eat("12")).build("1212");
+    let outputs = parser.parse_to_end().unwrap();
+    assert_eq!(outputs.len(), 2);
+    assert!(outputs.iter().all(|o| o.digested == 2));
+    assert_eq!(parser.instant.digested(), 4);
+    let mut parser = Parser::builder().entry(eat("12")).build("");
+    assert!(parser.parse_to_end().unwrap().is_empty());
+
+    // failure at offset 0
+    let mut parser = Parser::builder().entry(eat("12")).build("a12");
+    assert_eq!(
+      parser.parse_to_end(),
+      Err(ParseIncompleteError {
+        digested: 0,
+        remaining: 3,
+        preview: "\"a12\"".into(),
+      })
+    );
+    assert_eq!(parser.instant.digested(), 0);
+
+    // failure mid-way with a multi-byte char at the boundary
+    let mut parser = Parser::builder().entry(eat("12")).build("12好");
+    let err = parser.parse_to_end().unwrap_err();
+    assert_eq!(err.digested, 2);
+    assert_eq!(err.remaining, 3);
+    assert_eq!(err.preview, "\"好\"");
+    assert_eq!(
+      err.to_string(),
+      "parsing stopped at 2 with 3 bytes remaining: \"好\""
+    );
+    assert_eq!(parser.instant.rest(), "好");
+
+    // bytes
+    let mut parser = Parser::builder()
+      .entry(crate::combinator::bytes::eat(b'1'))
+      .build(b"11\xff" as &[u8]);
+    let err = parser.parse_to_end().unwrap_err();
+    assert_eq!(err.digested, 2);
+    assert_eq!(err.remaining, 1);
+    assert_eq!(err.preview, "[255]");
+  }
+
+  #[test]
+  fn parser_parse_to_end_truncated_preview() {
+    contextual!((), ());
+
+    let text = "1".to_string() + &"好".repeat(200);
+    let mut parser = Parser::builder().entry(eat("1")).build(&text);
+    let err = parser.parse_to_end().unwrap_err();
+    assert_eq!(err.digested, 1);
+    assert_eq!(err.remaining, 600);
+    assert_eq!(err.preview, format!("{:?} (truncated)", "好".repeat(100)));
+  }
+
+  #[test]
+  fn parser_fold_outputs() {
+    contextual!((), ());
+
+    let digits = || {
+      Parser::builder().entry(
+        next(|c| c.is_ascii_digit()).select(|a| a.content().parse().unwrap()) | eat(' ').bind(0),
+      )
+    };
+
+    // sum
+    let mut parser = digits().build("1 2 3");
+    assert_eq!(parser.fold_outputs(0, |acc, o| acc + o.value), Ok(6));
+    assert!(parser.is_done());
+
+    // early failure mid-input
+    let mut parser = digits().build("1 2a3");
+    assert_eq!(
+      parser.fold_outputs(0, |acc, o| acc + o.value),
+      Err(ParseIncompleteError {
+        digested: 3,
+        remaining: 2,
+        preview: "\"a3\"".into(),
+      })
+    );
+    assert_eq!(parser.instant.rest(), "a3");
+
+    // empty input
+    let mut parser = digits().build("");
+    assert_eq!(parser.fold_outputs(0, |acc, o| acc + o.value), Ok(0));
+
+    // collect
+    let mut parser = digits().build("1 2");
+    let values: Vec<i32> = parser
+      .collect_outputs::<Vec<_>>()
+      .unwrap()
+      .into_iter()
+      .map(|o| o.value)
+      .collect();
+    assert_eq!(values, [1, 0, 2]);
+    let mut parser = digits().build("1a");
+    assert_eq!(parser.collect_outputs::<Vec<_>>().unwrap_err().digested, 1);
+  }
+
+  #[test]
+  fn parser_try_fold_outputs() {
+    contextual!((), ());
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+      Odd(usize),
+      Incomplete(ParseIncompleteError),
+    }
+    impl From<ParseIncompleteError> for Error {
+      fn from(e: ParseIncompleteError) -> Self {
+        Error::Incomplete(e)
+      }
+    }
+
+    let digits = || {
+      Parser::builder().entry(next(|c| c.is_ascii_digit()).select(|a| a.content().parse().unwrap()))
+    };
+    let even = |acc: i32, o: Output<i32>| {
+      if o.value % 2 == 0 {
+        Ok(acc + o.value)
+      } else {
+        Err(Error::Odd(acc as usize))
+      }
+    };
+
+    assert_eq!(digits().build("246").try_fold_outputs(0, even), Ok(12));
+    assert_eq!(digits().build("").try_fold_outputs(0, even), Ok(0));
+    // stop at the first error from `f`, the failed output is still digested
+    let mut parser = digits().build("2438");
+    assert_eq!(parser.try_fold_outputs(0, even), Err(Error::Odd(6)));
+    assert_eq!(parser.instant.rest(), "8");
+    // stop at the first rejection
+    let mut parser = digits().build("24a");
+    assert_eq!(
+      parser.try_fold_outputs(0, even),
+      Err(Error::Incomplete(ParseIncompleteError {
+        digested: 2,
+        remaining: 1,
+        preview: "\"a\"".into(),
+      }))
+    );
+
+    // for each
+    let mut values = vec![];
+    let mut parser = digits().build("12a");
+    let err = parser
+      .try_for_each_output(|o| {
+        values.push(o.value);
+        Ok::<_, ParseIncompleteError>(())
+      })
+      .unwrap_err();
+    assert_eq!(values, [1, 2]);
+    assert_eq!(err.digested, 2);
+    let mut parser = digits().build("");
+    assert_eq!(
+      parser.try_for_each_output(|_| Ok::<_, ParseIncompleteError>(())),
+      Ok(())
+    );
+  }
+
+  #[test]
+  fn parser_next_expecting() {
+    contextual!(usize, ());
+
+    // count the accepted outputs in the state
+    let entry = || {
+      (whitespaces()
+        | eat("=").when_expected("=")
+        | eat("==").when_expected("==")
+        | eat("!=").when_expected("!="))
+      .then(|accepted| *accepted.state += 1)
+    };
+    let build = |text| Parser::builder().state(0).entry(entry()).build(text);
+
+    // without the expectation, the first alternative wins
+    let mut parser = build("==");
+    assert_eq!(parser.next().unwrap().digested, 1);
+
+    // with the expectation, the ambiguity is resolved
+    let mut parser = build("==");
+    assert_eq!(parser.next_expecting("==").unwrap().digested, 2);
+    assert_eq!(parser.state, 1);
+    assert_eq!(parser.instant.digested(), 2);
+    // the expectation is not kept
+    assert_eq!(parser.instant.expectation(), None);
+
+    // rejected outputs are not digested
+    let mut parser = build("!=");
+    assert!(parser.next_expecting("=").is_none());
+    assert_eq!(parser.instant.digested(), 0);
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.next_expecting("!=").unwrap().digested, 2);
+
+    // combinators without `when_expected` still run ahead of the expected literal
+    let mut parser = build("  ==");
+    assert_eq!(parser.next_expecting("==").unwrap().digested, 2);
+    assert_eq!(parser.next_expecting("==").unwrap().digested, 2);
+    assert!(parser.is_done());
+  }
+
+  #[test]
+  fn parser_next_expecting_bytes() {
+    use crate::combinator::bytes;
+
+    let mut parser = Parser::builder()
+      .entry(
+        (bytes::eat(b'a') * (1..)).when_expected(b"a") | bytes::eat(b"aa").when_expected(b"aa"),
+      )
+      .build(b"aab" as &[u8]);
+    assert_eq!(parser.next_expecting(b"aa").unwrap().digested, 2);
+    assert!(parser.next_expecting(b"b").is_none());
+    assert_eq!(parser.instant.rest(), b"b");
+  }
+
+  #[test]
+  fn parser_next_expecting_units() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Token {
+      Num,
+      Plus,
+    }
+    impl crate::digest::Unit for Token {}
+
+    let tokens = [Token::Num, Token::Plus, Token::Num];
+    let token = |t: Token| crate::combinator::token_by(move |u: &Token| *u == t).when_expected([t]);
+    let mut parser = Parser::builder()
+      .entry(token(Token::Num) | token(Token::Plus))
+      .build(&tokens[..]);
+    // the units are compared, not the (empty) bytes
+    assert!(parser.next_expecting(&[Token::Plus]).is_none());
+    assert_eq!(parser.instant.digested(), 0);
+    assert_eq!(parser.next_expecting(&[Token::Num]).unwrap().digested, 1);
+    assert!(parser.next_expecting(&[Token::Num]).is_none());
+    assert_eq!(parser.next_expecting(&[Token::Plus]).unwrap().digested, 1);
+    assert_eq!(parser.next().unwrap().digested, 1);
+    assert!(parser.is_done());
+  }
+
+  #[test]
+  fn parser_next_verbose() {
+    contextual!((), Expectations);
+
+    // a json-ish object with flat values
+    let value = || {
+      (json_number().bind(()) | json_string().bind(()) | eat("true") | eat("false") | eat("null"))
+        .expect("value")
+    };
+    let ws = || whitespaces().optional();
+    let member =
+      || json_string().bind(()).expect("string") + ws() + eat(':').expect("':'") + ws() + value();
+    let object = || {
+      eat('{').expect("'{'")
+        + ws()
+        + (member() * (..)).sep(ws() + ',' + ws())
+        + ws()
+        + eat('}').expect("'}'")
+    };
+    let build = |text| {
+      Parser::builder()
+        .heap(Expectations::new())
+        .entry(object())
+        .build(text)
+    };
+
+    // missing value
+    let mut parser = build(r#"{"a":}"#);
+    let err = parser.next_verbose().unwrap_err();
+    assert_eq!(
+      err,
+      ParseError {
+        offset: 5,
+        expected: vec!["value"]
+      }
+    );
+    assert_eq!(err.to_string(), "expected value at offset 5");
+    assert_eq!(parser.instant.digested(), 0);
+
+    // labels at the same offset are merged
+    let err = build(r#"{"a": 1 "b": 2}"#).next_verbose().unwrap_err();
+    assert_eq!(err.offset, 8);
+    assert_eq!(err.expected, ["'}'"]);
+    let err = build(r#"{"a" 1}"#).next_verbose().unwrap_err();
+    assert_eq!(err.offset, 5);
+    assert_eq!(err.expected, ["':'"]);
+    let err = build(r#"{1}"#).next_verbose().unwrap_err();
+    assert_eq!(err.offset, 1);
+    assert_eq!(err.expected, ["string", "'}'"]);
+    let err = build("[]").next_verbose().unwrap_err();
+    assert_eq!(err.to_string(), "expected '{' at offset 0");
+
+    // accepted
+    let mut parser = build(r#"{"a": 1, "b": null}{}"#);
+    assert_eq!(parser.next_verbose().unwrap().digested, 19);
+    assert_eq!(parser.next_verbose().unwrap().digested, 2);
+    // the expectations are cleared before each execution
+    let err = parser.next_verbose().unwrap_err();
+    assert_eq!(err.offset, 21);
+    assert_eq!(err.expected, ["'{'"]);
+
+    // no labels
+    let mut parser = Parser::builder()
+      .heap(Expectations::new())
+      .entry(eat('a'))
+      .build("ab");
+    parser.next();
+    assert_eq!(
+      parser.next_verbose().unwrap_err(),
+      ParseError {
+        offset: 1,
+        expected: vec![]
+      }
+    );
+  }
+
+  #[test]
+  fn parser_furthest_failure() {
+    contextual!((), Expectations);
+
+    // the left branch fails deep, the right branch fails shallow
+    let entry = || {
+      (eat("let").track() + eat(' ').track() + eat('x').track() + eat(';').track())
+        | eat("const").track()
+    };
+    let build = |text| {
+      Parser::builder()
+        .heap(Expectations::new())
+        .entry(entry())
+        .build(text)
+    };
+
+    let mut parser = build("let x,");
+    assert_eq!(parser.next_verbose().unwrap_err().offset, 5);
+    assert_eq!(parser.furthest_failure(), Some(5));
+    assert_eq!(parser.instant.digested(), 0);
+
+    // rejected branches of accepted outputs are also recorded
+    let mut parser = build("constconst!");
+    assert_eq!(parser.next_verbose().unwrap().digested, 5);
+    assert_eq!(parser.furthest_failure(), Some(0));
+    // the record is cleared before each execution
+    assert_eq!(parser.next_verbose().unwrap().digested, 5);
+    assert_eq!(parser.furthest_failure(), Some(5));
+    assert!(parser.next_verbose().is_err());
+    assert_eq!(parser.furthest_failure(), Some(10));
+    // with `next`, the record is kept until cleared
+    let mut parser = build("constlet x,");
+    assert_eq!(parser.next().unwrap().digested, 5);
+    assert!(parser.next().is_none());
+    assert_eq!(parser.furthest_failure(), Some(10));
+    parser.heap.clear();
+    assert_eq!(parser.furthest_failure(), None);
+
+    // nothing tracked
+    let mut parser = Parser::builder()
+      .heap(Expectations::new())
+      .entry(eat('a'))
+      .build("b");
+    assert!(parser.next().is_none());
+    assert_eq!(parser.furthest_failure(), None);
+  }
+
+  #[test]
+  fn parser_collect_ranges() {
+    contextual!((), ());
+
+    let mut parser = Parser::builder()
+      .entry(eat('a').bind('a') | eat("bb").bind('b'))
+      .build("abbac");
+    assert_eq!(
+      parser.collect_ranges(),
+      [(0..1, 'a'), (1..3, 'b'), (3..4, 'a')]
+    );
+    // stop at the rejection
+    assert_eq!(parser.instant.digested(), 4);
+    assert_eq!(parser.collect_ranges(), []);
+
+    // ranges are relative to the start of the text
+    let mut parser = Parser::builder().entry(take(1)).build("abc");
+    parser.next();
+    assert_eq!(parser.collect_ranges(), [(1..2, ()), (2..3, ())]);
+  }
 }
@@ -1,5 +1,6 @@
-use super::{Instant, Parser};
-use crate::action::Action;
+use super::{validate::validate, Instant, NamedEntries, Parser, ValidationReport};
+use crate::{action::Action, digest::Digest, instant::DigestError};
+use std::{ops::RangeFrom, slice::SliceIndex};
 
 /// A builder for [`Parser`].
 /// # Examples
@@ -99,7 +100,7 @@ impl<T, State, Heap> Builder<T, State, Heap> {
 
   /// Build a [`Parser`] with the given text.
   #[inline]
-  pub fn build<Text: ?Sized>(self, text: &Text) -> Parser<T>
+  pub fn build<Text: ?Sized>(self, text: &Text) -> Parser<'_, T>
   where
     T: Action<Text = Text, State = State, Heap = Heap>,
   {
@@ -110,12 +111,177 @@ impl<T, State, Heap> Builder<T, State, Heap> {
       instant: Instant::new(text),
     }
   }
+
+  /// Build a [`Parser`] with the given text, starting at the byte offset `start`.
+  ///
+  /// [`Instant::digested`] starts at `start` and [`Instant::rest`] is the suffix from `start`,
+  /// so ranges (e.g. from [`Combinator::range`](crate::combinator::Combinator::range))
+  /// are absolute in the whole text without copying or re-offsetting.
+  /// To stop at an `end` offset, pass `&text[..end]` as the text.
+  ///
+  /// Return a [`DigestError`] if `start` is out of range or not a valid boundary
+  /// (e.g. not a char boundary for [`str`]).
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let text = "let a = 1;";
+  /// let mut parser = Parser::builder()
+  ///   .entry(eat("a").range())
+  ///   .build_at(&text[..5], 4)
+  ///   .unwrap();
+  /// assert_eq!(parser.instant.rest(), "a");
+  /// assert_eq!(parser.next().unwrap().value.range, 4..5);
+  /// ```
+  #[inline]
+  pub fn build_at<Text: ?Sized + Digest>(
+    self,
+    text: &Text,
+    start: usize,
+  ) -> Result<Parser<'_, T>, DigestError>
+  where
+    T: Action<Text = Text, State = State, Heap = Heap>,
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    Ok(Parser {
+      state: self.state,
+      heap: self.heap,
+      entry: self.entry,
+      instant: Instant::new_at(text, start)?,
+    })
+  }
+
+  /// Execute the entry action against each probe input before building the parser,
+  /// and check the invariants which are required by [`Action`]:
+  ///
+  /// - The digested length is no greater than the length of the rest.
+  /// - The digested length is a valid boundary, e.g. a char boundary for [`str`].
+  /// - The entry action doesn't panic.
+  ///
+  /// Each probe is parsed like [`Parser::next`] from the start,
+  /// until the entry action rejects or accepts with zero digested.
+  /// [`Parser::state`] and [`Parser::heap`] are cloned for each probe,
+  /// so the builder is not modified.
+  ///
+  /// This is a no-op in release builds (without `debug_assertions`),
+  /// use [`Self::validate_always_with`] to always validate.
+  ///
+  /// Return a [`ValidationReport`] if any probe fails.
+  /// Panics are caught, but the panic messages are still printed by the panic hook.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::{eat, wrap}, parser::Parser};
+  ///
+  /// let parser = Parser::builder()
+  ///   .entry(eat("a") | "b")
+  ///   .validate_with(&["ab", "", "c"])
+  ///   .unwrap()
+  ///   .build("ab");
+  ///
+  /// // a buggy closure which may digest more than the rest
+  /// let entry = wrap(|input| {
+  ///   let digested = if input.instant.rest().starts_with('x') { 2 } else { 0 };
+  ///   Some(unsafe { input.instant.accept_unchecked(digested) })
+  /// });
+  /// let report = Parser::builder()
+  ///   .entry(entry)
+  ///   .validate_always_with(&["xy", "x"])
+  ///   .unwrap_err();
+  /// assert_eq!(report.failures.len(), 1);
+  /// assert_eq!(report.failures[0].probe, 1);
+  /// ```
+  #[inline]
+  pub fn validate_with<Text: ?Sized + Digest>(
+    self,
+    probes: &[&Text],
+  ) -> Result<Self, ValidationReport>
+  where
+    T: Action<Text = Text, State = State, Heap = Heap>,
+    State: Clone,
+    Heap: Clone,
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    if cfg!(debug_assertions) {
+      self.validate_always_with(probes)
+    } else {
+      Ok(self)
+    }
+  }
+
+  /// Like [`Self::validate_with`], but also validate in release builds.
+  #[inline]
+  pub fn validate_always_with<Text: ?Sized + Digest>(
+    self,
+    probes: &[&Text],
+  ) -> Result<Self, ValidationReport>
+  where
+    T: Action<Text = Text, State = State, Heap = Heap>,
+    State: Clone,
+    Heap: Clone,
+    RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  {
+    validate(&self.entry, &self.state, &self.heap, probes).map(|_| self)
+  }
+}
+
+impl<State, Heap> Builder<(), State, Heap> {
+  /// Register the first named entry, which is the default entry.
+  /// Call this again to register more entries.
+  ///
+  /// All entries must share the same `Text`, `State`, `Heap` and `Value`.
+  /// The entries are boxed, so each execution costs a virtual call.
+  /// See [`NamedEntries`] for more information.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder()
+  ///   .entry_named("expr", eat("1") + "+" + "1")
+  ///   .entry_named("statement", eat("1+1;"))
+  ///   .build("1+1;");
+  /// assert_eq!(parser.next_from("statement").unwrap().unwrap().digested, 4);
+  /// ```
+  #[allow(clippy::type_complexity)]
+  #[inline]
+  pub fn entry_named<'a, Entry: Action<State = State, Heap = Heap> + 'a>(
+    self,
+    name: &'static str,
+    entry: Entry,
+  ) -> Builder<NamedEntries<'a, Entry::Text, State, Heap, Entry::Value>, State, Heap> {
+    Builder {
+      entry: NamedEntries::new(name, entry),
+      state: self.state,
+      heap: self.heap,
+    }
+  }
+}
+
+impl<'a, Text: ?Sized, State, Heap, Value>
+  Builder<NamedEntries<'a, Text, State, Heap, Value>, State, Heap>
+{
+  /// Register another named entry.
+  /// If the `name` is already registered, the entry will be replaced.
+  /// See [`NamedEntries`] for more information.
+  #[inline]
+  pub fn entry_named(
+    mut self,
+    name: &'static str,
+    entry: impl Action<Text = Text, State = State, Heap = Heap, Value = Value> + 'a,
+  ) -> Self {
+    self.entry.insert(name, entry);
+    self
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{combinator::eat, contextual};
+  use crate::{
+    action::{Input, Output},
+    combinator::eat,
+    contextual,
+    parser::{ProbeFailure, Violation},
+  };
 
   #[test]
   fn parser_builder_default() {
@@ -156,6 +322,143 @@ mod tests {
     assert_eq!(parser.state, 1);
   }
 
+  #[test]
+  fn parser_builder_build_at() {
+    let text = "a bb a bb";
+    let entry = || (eat("a") | "bb" | " ").range();
+
+    // same as the full parse restricted to the span
+    let full = Builder::default()
+      .entry(entry())
+      .build(text)
+      .map(|o| o.value.range)
+      .collect::<Vec<_>>();
+    let mut parser = Builder::default()
+      .entry(entry())
+      .build_at(&text[..6], 2)
+      .unwrap();
+    let span = parser.by_ref().map(|o| o.value.range).collect::<Vec<_>>();
+    assert_eq!(span, [2..4, 4..5, 5..6]);
+    assert_eq!(
+      span,
+      full
+        .into_iter()
+        .filter(|r| r.start >= 2 && r.end <= 6)
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(parser.instant.digested(), 6);
+
+    // start at the end
+    let parser = Builder::default().entry(entry()).build_at(text, 9).unwrap();
+    assert_eq!(parser.instant.rest(), "");
+    assert_eq!(parser.instant.digested(), 9);
+
+    // invalid start
+    assert_eq!(
+      Builder::default()
+        .entry(entry())
+        .build_at(text, 10)
+        .unwrap_err(),
+      DigestError::OutOfRange { n: 10, rest: 9 }
+    );
+    assert_eq!(
+      Builder::default()
+        .entry(eat("好"))
+        .build_at("好好", 4)
+        .unwrap_err(),
+      DigestError::InvalidBoundary { n: 4 }
+    );
+  }
+
+  #[test]
+  fn parser_builder_validate_with() {
+    contextual!(i32, ());
+
+    // valid, the state of the builder is not modified
+    let parser = Builder::default()
+      .state(0)
+      .entry((eat('a') | "好").then(|input| *input.state += 1))
+      .validate_always_with(&["aa好", "", "b"])
+      .unwrap()
+      .build("a");
+    assert_eq!(parser.state, 0);
+
+    // a buggy closure which digests more than the rest, caught as a panic by `wrap`
+    let report = Builder::default()
+      .state(0)
+      .entry(wrap(|input| {
+        let digested = if input.instant.rest().starts_with('x') {
+          2
+        } else {
+          0
+        };
+        Some(unsafe { input.instant.accept_unchecked(digested) })
+      }))
+      .validate_always_with(&["xxxy", "xx", "xxx"])
+      .unwrap_err();
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].probe, 2);
+    assert_eq!(report.failures[0].offset, 2);
+    assert!(matches!(report.failures[0].violation, Violation::Panic(_)));
+    assert!(report
+      .to_string()
+      .starts_with("1 probe(s) failed\n  probe #2 at offset 2: the entry action panicked: "));
+  }
+
+  #[test]
+  fn parser_builder_validate_digest() {
+    // an action which doesn't check the digested length
+    struct Buggy(usize);
+    unsafe impl Action for Buggy {
+      type Text = str;
+      type State = ();
+      type Heap = ();
+      type Value = ();
+      fn exec(&self, _: Input<&Instant<&str>, &mut (), &mut ()>) -> Option<Output<()>> {
+        Some(Output {
+          value: (),
+          digested: self.0,
+        })
+      }
+    }
+
+    let result = Builder::default()
+      .entry(Buggy(3))
+      .validate_with(&["abcdef", "ab", "a好"])
+      .map(|_| ());
+    if !cfg!(debug_assertions) {
+      // no-op in release builds
+      assert!(result.is_ok());
+      return;
+    }
+    let report = result.unwrap_err();
+    assert_eq!(
+      report.failures,
+      [
+        // the rest is empty after 2 accepted outputs
+        ProbeFailure {
+          probe: 0,
+          offset: 6,
+          violation: Violation::Digest(DigestError::OutOfRange { n: 3, rest: 0 })
+        },
+        ProbeFailure {
+          probe: 1,
+          offset: 0,
+          violation: Violation::Digest(DigestError::OutOfRange { n: 3, rest: 2 })
+        },
+        ProbeFailure {
+          probe: 2,
+          offset: 0,
+          violation: Violation::Digest(DigestError::InvalidBoundary { n: 3 })
+        }
+      ]
+    );
+    assert_eq!(
+      report.failures[1].to_string(),
+      "probe #1 at offset 0: the entry action accepted but can't digest 3 from the rest with length 2"
+    );
+  }
+
   #[test]
   fn re_use_entry_with_ref() {
     let entry = eat("hello ") + "world";
@@ -0,0 +1,261 @@
+use super::Parser;
+use crate::{
+  action::{Action, BoxedAction, Input, Output, Rejection},
+  digest::Digest,
+  instant::Instant,
+};
+use std::{
+  fmt::{self, Debug, Display},
+  ops::RangeFrom,
+  slice::SliceIndex,
+};
+
+/// Multiple named entry actions sharing the same `Text`, `State`, `Heap` and `Value`.
+///
+/// When used as [`Parser::entry`], the default entry is executed by [`Parser::next`],
+/// other entries can be executed by [`Parser::next_from`].
+/// The first registered entry is the default one,
+/// use [`Parser::set_default_entry`] to change it.
+///
+/// Build this with [`Builder::entry_named`](super::Builder::entry_named).
+/// # Examples
+/// ```
+/// use whitehole::{combinator::{eat, next}, parser::Parser};
+///
+/// let mut parser = Parser::builder()
+///   .entry_named("word", next(|c| c.is_ascii_alphabetic()) * (1..))
+///   .entry_named("char", next(|_| true))
+///   .build("ab");
+/// assert_eq!(parser.next_from("char").unwrap().unwrap().digested, 1);
+/// // the default entry is "word"
+/// assert_eq!(parser.next().unwrap().digested, 1);
+/// ```
+pub struct NamedEntries<'a, Text: ?Sized, State, Heap, Value> {
+  #[allow(clippy::type_complexity)]
+  entries: Vec<(&'static str, BoxedAction<'a, Text, State, Heap, Value>)>,
+  default: usize,
+}
+
+impl<Text: ?Sized, State, Heap, Value> Debug for NamedEntries<'_, Text, State, Heap, Value> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("NamedEntries")
+      .field("names", &self.names().collect::<Vec<_>>())
+      .field("default", &self.default_name())
+      .finish()
+  }
+}
+
+impl<'a, Text: ?Sized, State, Heap, Value> NamedEntries<'a, Text, State, Heap, Value> {
+  /// Create a new instance with the first (default) entry.
+  #[inline]
+  pub fn new(
+    name: &'static str,
+    entry: impl Action<Text = Text, State = State, Heap = Heap, Value = Value> + 'a,
+  ) -> Self {
+    Self {
+      entries: vec![(name, Box::new(entry))],
+      default: 0,
+    }
+  }
+
+  /// Register an entry with the `name`.
+  /// If the `name` is already registered, the entry will be replaced.
+  pub fn insert(
+    &mut self,
+    name: &'static str,
+    entry: impl Action<Text = Text, State = State, Heap = Heap, Value = Value> + 'a,
+  ) {
+    match self.position(name) {
+      Ok(i) => self.entries[i].1 = Box::new(entry),
+      Err(_) => self.entries.push((name, Box::new(entry))),
+    }
+  }
+
+  /// The names of the entries, in the registration order.
+  #[inline]
+  pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+    self.entries.iter().map(|(name, _)| *name)
+  }
+
+  /// The name of the default entry.
+  #[inline]
+  pub fn default_name(&self) -> &'static str {
+    self.entries[self.default].0
+  }
+
+  /// Get the entry by the `name`.
+  #[inline]
+  pub fn get(
+    &self,
+    name: &str,
+  ) -> Result<&BoxedAction<'a, Text, State, Heap, Value>, UnknownEntryError> {
+    self.position(name).map(|i| &self.entries[i].1)
+  }
+
+  /// Set the default entry by the `name`.
+  /// The default entry is unchanged if the `name` is unknown.
+  #[inline]
+  pub fn set_default(&mut self, name: &str) -> Result<(), UnknownEntryError> {
+    self.default = self.position(name)?;
+    Ok(())
+  }
+
+  fn position(&self, name: &str) -> Result<usize, UnknownEntryError> {
+    self
+      .entries
+      .iter()
+      .position(|(n, _)| *n == name)
+      .ok_or_else(|| UnknownEntryError {
+        name: name.to_string(),
+      })
+  }
+}
+
+unsafe impl<Text: ?Sized, State, Heap, Value> Action
+  for NamedEntries<'_, Text, State, Heap, Value>
+{
+  type Text = Text;
+  type State = State;
+  type Heap = Heap;
+  type Value = Value;
+
+  #[inline]
+  fn exec(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Option<Output<Self::Value>> {
+    self.entries[self.default].1.exec(input)
+  }
+
+  #[inline]
+  fn exec_cut(
+    &self,
+    input: Input<&Instant<&Self::Text>, &mut Self::State, &mut Self::Heap>,
+  ) -> Result<Output<Self::Value>, Rejection> {
+    self.entries[self.default].1.exec_cut(input)
+  }
+}
+
+/// The error returned when an entry name is not registered in [`NamedEntries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEntryError {
+  /// The unknown name.
+  pub name: String,
+}
+
+impl Display for UnknownEntryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown entry: {:?}", self.name)
+  }
+}
+
+impl std::error::Error for UnknownEntryError {}
+
+impl<Text: ?Sized + Digest, State, Heap, Value>
+  Parser<'_, NamedEntries<'_, Text, State, Heap, Value>>
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  /// Like [`Self::next`], but execute the entry with the `name`
+  /// instead of the default one.
+  ///
+  /// Return an [`UnknownEntryError`] if the `name` is not registered,
+  /// otherwise return the output of the entry, or [`None`] if the entry rejects.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder()
+  ///   .entry_named("a", eat('a'))
+  ///   .entry_named("b", eat('b'))
+  ///   .build("ba");
+  /// assert_eq!(parser.next_from("b").unwrap().unwrap().digested, 1);
+  /// assert_eq!(parser.next_from("c").unwrap_err().to_string(), "unknown entry: \"c\"");
+  /// ```
+  #[inline]
+  pub fn next_from(&mut self, name: &str) -> Result<Option<Output<Value>>, UnknownEntryError> {
+    let output = self.entry.get(name)?.exec(Input {
+      instant: &self.instant,
+      state: &mut self.state,
+      heap: &mut self.heap,
+    });
+    if let Some(output) = &output {
+      unsafe { self.instant.digest_unchecked(output.digested) };
+    }
+    Ok(output)
+  }
+
+  /// Set the default entry which is executed by [`Self::next`].
+  /// See [`NamedEntries::set_default`].
+  #[inline]
+  pub fn set_default_entry(&mut self, name: &str) -> Result<(), UnknownEntryError> {
+    self.entry.set_default(name)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{eat, next};
+
+  #[test]
+  fn parser_named_entries() {
+    let builder = || {
+      Parser::builder()
+        .entry_named("word", next(|c| c.is_ascii_alphabetic()) * (1..))
+        .entry_named("char", next(|_| true))
+    };
+
+    // different digesting behavior on the same text
+    let mut parser = builder().build("ab cd");
+    assert_eq!(parser.next().unwrap().digested, 2);
+    let mut parser = builder().build("ab cd");
+    assert_eq!(parser.next_from("char").unwrap().unwrap().digested, 1);
+    assert_eq!(parser.next_from("word").unwrap().unwrap().digested, 1);
+    assert!(parser.next_from("word").unwrap().is_none());
+    assert_eq!(parser.instant.digested(), 2);
+
+    // switch the default entry mid-iteration
+    let mut parser = builder().build("ab cd");
+    assert_eq!(parser.next().unwrap().digested, 2);
+    assert!(parser.next().is_none());
+    parser.set_default_entry("char").unwrap();
+    assert_eq!(parser.next().unwrap().digested, 1);
+    parser.set_default_entry("word").unwrap();
+    assert_eq!(parser.next().unwrap().digested, 2);
+    assert!(parser.is_done());
+
+    // unknown name
+    let mut parser = builder().build("ab");
+    assert_eq!(
+      parser.next_from("expr"),
+      Err(UnknownEntryError {
+        name: "expr".into()
+      })
+    );
+    assert_eq!(
+      parser.set_default_entry("expr").unwrap_err().to_string(),
+      "unknown entry: \"expr\""
+    );
+    // the default entry is unchanged
+    assert_eq!(parser.entry.default_name(), "word");
+    assert_eq!(parser.instant.digested(), 0);
+  }
+
+  #[test]
+  fn named_entries_insert() {
+    let mut entries = NamedEntries::new("a", eat('a'));
+    entries.insert("b", eat('b'));
+    entries.insert("a", eat("aa"));
+    assert_eq!(entries.names().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(entries.default_name(), "a");
+    assert!(entries.get("c").is_err());
+
+    let mut parser = Parser::builder().entry(entries).build("aab");
+    assert_eq!(parser.next().unwrap().digested, 2);
+    assert_eq!(
+      format!("{:?}", parser.entry),
+      r#"NamedEntries { names: ["a", "b"], default: "a" }"#
+    );
+  }
+}
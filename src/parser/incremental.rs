@@ -0,0 +1,288 @@
+use super::Parser;
+use crate::{
+  action::{Action, Output},
+  digest::Digest,
+  instant::Instant,
+};
+use std::{
+  fmt::Debug,
+  ops::{Deref, DerefMut, RangeFrom},
+  slice::SliceIndex,
+};
+
+/// A [`Parser`] which records output boundaries to re-parse after text edits.
+/// See [`Parser::record_boundaries`].
+///
+/// This can be used as a [`Parser`] via [`Deref`] and [`DerefMut`].
+/// Boundaries are recorded in [`Self::next`],
+/// digesting from outside of the parser won't be recorded.
+pub struct IncrementalParser<'text, T: Action> {
+  parser: Parser<'text, T>,
+  /// The digested offset and the state after each output,
+  /// starting with the position where the recording started.
+  boundaries: Vec<(usize, T::State)>,
+}
+
+impl<'text, T: Action> Debug for IncrementalParser<'text, T>
+where
+  Parser<'text, T>: Debug,
+  T::State: Debug,
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("IncrementalParser")
+      .field("parser", &self.parser)
+      .field("boundaries", &self.boundaries)
+      .finish()
+  }
+}
+
+impl<'text, T: Action> Deref for IncrementalParser<'text, T> {
+  type Target = Parser<'text, T>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.parser
+  }
+}
+
+impl<T: Action> DerefMut for IncrementalParser<'_, T> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.parser
+  }
+}
+
+impl<'text, T: Action<State: Clone>> Parser<'text, T> {
+  /// Consume self, return an [`IncrementalParser`] which records the digested offset
+  /// and a clone of [`Self::state`] after each yielded output,
+  /// so that it can resume from the middle of the text after an edit
+  /// via [`IncrementalParser::apply_edit`].
+  ///
+  /// The current position is recorded as the first boundary.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::{eat, next}, parser::Parser};
+  ///
+  /// let entry = || (next(|c| c.is_ascii_alphabetic()) * (1..)) | eat(' ');
+  /// let mut parser = Parser::builder().entry(entry()).build("ab cd ef").record_boundaries();
+  /// assert_eq!(parser.by_ref().count(), 5);
+  ///
+  /// // replace "cd" with "xyz"
+  /// let mut parser = parser.apply_edit(3, "ab xyz ef");
+  /// // resume after "ab", the space before the edit is re-yielded
+  /// assert_eq!(parser.instant.digested(), 2);
+  /// let rest = parser.map(|o| o.digested).collect::<Vec<_>>();
+  /// assert_eq!(rest, [1, 3, 1, 2]);
+  /// ```
+  #[inline]
+  pub fn record_boundaries(self) -> IncrementalParser<'text, T> {
+    IncrementalParser {
+      boundaries: vec![(self.instant.digested(), self.state.clone())],
+      parser: self,
+    }
+  }
+}
+
+impl<'text, T: Action<State: Clone>> IncrementalParser<'text, T> {
+  /// The recorded output boundaries as digested offsets, in ascending order.
+  /// The first one is the position where the recording started.
+  #[inline]
+  pub fn boundaries(&self) -> impl Iterator<Item = usize> + '_ {
+    self.boundaries.iter().map(|(offset, _)| *offset)
+  }
+
+  /// Consume self, return the inner [`Parser`].
+  #[inline]
+  pub fn into_parser(self) -> Parser<'text, T> {
+    self.parser
+  }
+
+  /// Consume self, return a new instance with the edited `text`,
+  /// rewound to the last recorded boundary before `edit_start`
+  /// (or the first boundary if there is none).
+  ///
+  /// `edit_start` is the byte offset of the first changed byte,
+  /// i.e. the text before it must be unchanged.
+  /// The output ending at `edit_start` is not reused,
+  /// since it may be extended by the inserted text.
+  ///
+  /// [`Parser::instant`] and [`Parser::state`] are restored to the boundary,
+  /// boundaries after it are discarded.
+  /// [`Parser::heap`] won't change.
+  ///
+  /// If the entry action looks ahead beyond its digested bytes,
+  /// an earlier output may be affected by the edit,
+  /// in this case pass a smaller `edit_start` to be safe.
+  /// # Panics
+  /// Panics if the rewound position is not a valid position of the new `text`,
+  /// which means the text before `edit_start` is changed.
+  #[inline]
+  pub fn apply_edit(mut self, edit_start: usize, text: &T::Text) -> IncrementalParser<'_, T>
+  where
+    T::Text: Digest,
+    RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  {
+    // the first boundary is always kept
+    let keep = self.boundaries[1..]
+      .iter()
+      .take_while(|(offset, _)| *offset < edit_start)
+      .count()
+      + 1;
+    self.boundaries.truncate(keep);
+    let (offset, state) = self.boundaries[keep - 1].clone();
+
+    let mut instant = Instant::new(text);
+    instant
+      .digest(offset)
+      .expect("the text before the edit should be unchanged");
+    IncrementalParser {
+      parser: Parser {
+        entry: self.parser.entry,
+        heap: self.parser.heap,
+        state,
+        instant,
+      },
+      boundaries: self.boundaries,
+    }
+  }
+}
+
+impl<T: Action<Text: Digest, State: Clone>> Iterator for IncrementalParser<'_, T>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Item = Output<T::Value>;
+
+  /// Try to yield the next [`Output`] and digest it,
+  /// then record the boundary.
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let output = self.parser.next()?;
+    self
+      .boundaries
+      .push((self.parser.instant.digested(), self.parser.state.clone()));
+    Some(output)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{eat, next},
+    contextual,
+  };
+
+  fn tokens(parser: impl Iterator<Item = Output<usize>>) -> Vec<usize> {
+    parser.map(|o| o.value).collect()
+  }
+
+  #[test]
+  fn parser_apply_edit() {
+    // numbers separated by commas, the value is the length of each token
+    let entry =
+      || (next(|c| c.is_ascii_digit()) * (1..)).select(|a| a.content().len()) | eat(',').bind(0);
+    let full = |text| tokens(Parser::builder().entry(entry()).build(text));
+
+    let text = "1,22,333,4,5";
+    let mut parser = Parser::builder()
+      .entry(entry())
+      .build(text)
+      .record_boundaries();
+    assert_eq!(parser.by_ref().count(), 9);
+    assert_eq!(
+      parser.boundaries().collect::<Vec<_>>(),
+      [0, 1, 2, 4, 5, 8, 9, 10, 11, 12]
+    );
+
+    // replace "333" with "9"
+    let edited = "1,22,9,4,5";
+    let parser = parser.apply_edit(5, edited);
+    assert_eq!(parser.instant.digested(), 4);
+    assert_eq!(parser.boundaries().collect::<Vec<_>>(), [0, 1, 2, 4]);
+    // only the suffix tokens are re-yielded
+    let suffix = tokens(parser);
+    assert_eq!(suffix, [0, 1, 0, 1, 0, 1]);
+    // prefix + suffix matches a full re-parse
+    let mut all = full(text)[..3].to_vec();
+    all.extend(suffix);
+    assert_eq!(all, full(edited));
+
+    // insert at the end of a token, the token is re-yielded
+    let mut parser = Parser::builder()
+      .entry(entry())
+      .build(text)
+      .record_boundaries();
+    parser.by_ref().for_each(drop);
+    let parser = parser.apply_edit(4, "1,2277,333,4,5");
+    assert_eq!(parser.instant.digested(), 2);
+    assert_eq!(tokens(parser), [4, 0, 3, 0, 1, 0, 1]);
+
+    // edit at the start
+    let mut parser = Parser::builder()
+      .entry(entry())
+      .build(text)
+      .record_boundaries();
+    parser.by_ref().for_each(drop);
+    let parser = parser.apply_edit(0, "9");
+    assert_eq!(parser.instant.digested(), 0);
+    assert_eq!(tokens(parser), [1]);
+
+    // edit after the last boundary
+    let mut parser = Parser::builder()
+      .entry(entry())
+      .build("1,a")
+      .record_boundaries();
+    assert_eq!(parser.by_ref().count(), 2);
+    let parser = parser.apply_edit(2, "1,2");
+    assert_eq!(parser.instant.digested(), 1);
+    assert_eq!(tokens(parser), [0, 1]);
+  }
+
+  #[test]
+  fn parser_apply_edit_state() {
+    contextual!(usize, ());
+
+    // count tokens in the state
+    let mut parser = Parser::builder()
+      .state(0)
+      .entry(next(|_| true).then(|a| *a.state += 1))
+      .build("abcd")
+      .record_boundaries();
+    assert_eq!(parser.by_ref().count(), 4);
+    assert_eq!(parser.state, 4);
+
+    let mut parser = parser.apply_edit(2, "abXYZ");
+    assert_eq!(parser.state, 1);
+    assert_eq!(parser.by_ref().count(), 4);
+    assert_eq!(parser.state, 5);
+    assert_eq!(parser.into_parser().instant.digested(), 5);
+  }
+
+  #[test]
+  fn parser_record_boundaries_from_middle() {
+    let mut parser = Parser::builder().entry(eat('a')).build("aaa");
+    parser.next();
+    let mut parser = parser.record_boundaries();
+    parser.next();
+    assert_eq!(parser.boundaries().collect::<Vec<_>>(), [1, 2]);
+    // edits before the first boundary rewind to the first boundary
+    let parser = parser.apply_edit(0, "aaaa");
+    assert_eq!(parser.instant.digested(), 1);
+    // debug
+    let _ = format!("{:?}", parser);
+    assert_eq!(parser.count(), 3);
+  }
+
+  #[test]
+  #[should_panic]
+  fn parser_apply_edit_invalid() {
+    let mut parser = Parser::builder()
+      .entry(eat('a'))
+      .build("aaa")
+      .record_boundaries();
+    parser.by_ref().for_each(drop);
+    // the new text is shorter than the rewound position
+    let _ = parser.apply_edit(3, "a");
+  }
+}
@@ -0,0 +1,392 @@
+// this module only uses the `idx` helpers, which are safe with the `safe` feature
+#![cfg_attr(all(feature = "safe", not(test)), deny(unsafe_code))]
+
+use super::{Builder, Parser};
+use crate::{
+  action::{Action, Input, Output},
+  digest::Digest,
+  idx,
+  instant::{DigestError, Instant},
+};
+use std::{ops::RangeFrom, slice::SliceIndex, sync::Arc};
+
+/// The snapshot of an [`OwnedParser`].
+///
+/// This can be created by [`OwnedParser::snapshot`]
+/// and used by [`OwnedParser::restore`].
+/// Unlike [`Snapshot`](super::Snapshot), this doesn't borrow the text,
+/// so it can outlive a borrow of the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedSnapshot<State> {
+  /// See [`Parser::state`](crate::parser::Parser::state).
+  /// You can modify this if needed.
+  pub state: State,
+
+  digested: usize,
+}
+
+impl<State> OwnedSnapshot<State> {
+  /// See [`Instant::digested`].
+  #[inline]
+  pub const fn digested(&self) -> usize {
+    self.digested
+  }
+}
+
+/// A parser which owns its text, e.g. an [`Arc<str>`] or a [`String`].
+///
+/// [`Parser`](super::Parser) borrows the text, which makes it hard to store it in a struct
+/// or move it into another thread together with its input.
+/// This stores the text and the digested length instead of an [`Instant`],
+/// and reconstructs the [`Instant`] on demand with [`Self::instant`].
+///
+/// This is [`Send`] if the entry action, `State`, `Heap` and the owned text are [`Send`]
+/// (e.g. [`Arc<str>`] instead of [`Rc<str>`](std::rc::Rc)).
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use whitehole::{combinator::eat, parser::Parser};
+///
+/// let text: Arc<str> = "123123".into();
+/// let mut parser = Parser::builder().entry(eat("123")).build_owned(text);
+/// let handle = std::thread::spawn(move || {
+///   assert_eq!(parser.by_ref().count(), 2);
+///   parser.instant().digested()
+/// });
+/// assert_eq!(handle.join().unwrap(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OwnedParser<T: Action, Owned = Arc<<T as Action>::Text>> {
+  /// See [`Parser::state`](crate::parser::Parser::state).
+  pub state: T::State,
+
+  /// See [`Parser::heap`](crate::parser::Parser::heap).
+  pub heap: T::Heap,
+
+  /// The entry action.
+  pub entry: T,
+
+  text: Owned,
+  digested: usize,
+}
+
+impl<T: Action, Owned: AsRef<T::Text>> OwnedParser<T, Owned> {
+  /// Create a new instance which starts at the beginning of the `text`.
+  #[inline]
+  pub const fn new(entry: T, state: T::State, heap: T::Heap, text: Owned) -> Self {
+    Self {
+      state,
+      heap,
+      entry,
+      text,
+      digested: 0,
+    }
+  }
+
+  /// The owned text.
+  #[inline]
+  pub const fn text(&self) -> &Owned {
+    &self.text
+  }
+
+  /// Consume self, return the owned text, [`Self::state`] and [`Self::heap`].
+  #[inline]
+  pub fn into_parts(self) -> (Owned, T::State, T::Heap) {
+    (self.text, self.state, self.heap)
+  }
+}
+
+impl<T: Action<Text: Digest>, Owned: AsRef<T::Text>> OwnedParser<T, Owned>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  /// The current progress over the owned text.
+  /// # Panics
+  /// Panics if the progress is invalid for the owned text,
+  /// i.e. [`AsRef::as_ref`] of the owned text doesn't return the same text every time.
+  #[inline]
+  pub fn instant(&self) -> Instant<&T::Text> {
+    instant_at(&self.text, self.digested).expect("the owned text should not change")
+  }
+
+  /// Try to yield the next [`Output`] without updating the progress and [`Self::state`].
+  /// [`Self::state`] will be cloned and returned.
+  /// See [`Parser::peek`](super::Parser::peek).
+  #[inline]
+  pub fn peek(&mut self) -> (Option<Output<T::Value>>, T::State)
+  where
+    T::State: Clone,
+  {
+    let mut tmp_state = self.state.clone();
+    let Ok(instant) = instant_at(&self.text, self.digested) else {
+      return (None, tmp_state);
+    };
+    (
+      self.entry.exec(Input {
+        instant: &instant,
+        state: &mut tmp_state,
+        heap: &mut self.heap,
+      }),
+      tmp_state,
+    )
+  }
+
+  /// Like [`Self::peek`], but [`Self::heap`] is also cloned,
+  /// so the parser is not changed at all.
+  /// See [`Parser::peek_isolated`](super::Parser::peek_isolated).
+  #[inline]
+  pub fn peek_isolated(&self) -> (Option<Output<T::Value>>, T::State, T::Heap)
+  where
+    T::State: Clone,
+    T::Heap: Clone,
+  {
+    let mut tmp_state = self.state.clone();
+    let mut tmp_heap = self.heap.clone();
+    let Ok(instant) = instant_at(&self.text, self.digested) else {
+      return (None, tmp_state, tmp_heap);
+    };
+    let output = self.entry.exec(Input {
+      instant: &instant,
+      state: &mut tmp_state,
+      heap: &mut tmp_heap,
+    });
+    (output, tmp_state, tmp_heap)
+  }
+
+  /// Take a snapshot of the current [`Self::state`] and the progress.
+  #[inline]
+  pub fn snapshot(&self) -> OwnedSnapshot<T::State>
+  where
+    T::State: Clone,
+  {
+    OwnedSnapshot {
+      state: self.state.clone(),
+      digested: self.digested,
+    }
+  }
+
+  /// Restore [`Self::state`] and the progress from an [`OwnedSnapshot`].
+  ///
+  /// Return a [`DigestError`] and keep self unchanged
+  /// if the snapshot's progress is invalid for the owned text,
+  /// e.g. the snapshot is taken from another parser.
+  #[inline]
+  pub fn restore(&mut self, snapshot: OwnedSnapshot<T::State>) -> Result<(), DigestError> {
+    Instant::new_at(self.text.as_ref(), snapshot.digested)?;
+    self.state = snapshot.state;
+    self.digested = snapshot.digested;
+    Ok(())
+  }
+}
+
+/// Reconstruct the [`Instant`] from the owned text and the digested length.
+///
+/// `digested` is only updated by accepted outputs or validated snapshots,
+/// but [`AsRef::as_ref`] is not guaranteed to return the same text every time,
+/// so this is checked.
+#[inline]
+fn instant_at<Text: ?Sized + Digest>(
+  text: &impl AsRef<Text>,
+  digested: usize,
+) -> Result<Instant<&Text>, DigestError>
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  Instant::new_at(text.as_ref(), digested)
+}
+
+impl<T: Action<Text: Digest>, Owned: AsRef<T::Text>> Iterator for OwnedParser<T, Owned>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Item = Output<T::Value>;
+
+  /// See [`Parser::next`](super::Parser::next).
+  /// Reject if the progress is invalid for the owned text, see [`OwnedParser::instant`].
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let instant = instant_at(&self.text, self.digested).ok()?;
+    let output = self.entry.exec(Input {
+      instant: &instant,
+      state: &mut self.state,
+      heap: &mut self.heap,
+    })?;
+    debug_assert!(instant.rest().validate(output.digested));
+    // SAFETY: the output is inside the text, whose length is a valid usize
+    self.digested = idx::unchecked!(idx::add(self.digested, output.digested));
+    Some(output)
+  }
+
+  /// Always return `(0, None)`. See [`Parser::size_hint`](super::Parser::size_hint).
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, None)
+  }
+}
+
+impl<T, State, Heap> Builder<T, State, Heap> {
+  /// Build an [`OwnedParser`] which owns the `text`,
+  /// e.g. an [`Arc<str>`], an [`Rc<str>`](std::rc::Rc) or a [`String`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("1")).build_owned(String::from("11"));
+  /// assert_eq!(parser.by_ref().count(), 2);
+  /// ```
+  #[inline]
+  pub fn build_owned<Owned: AsRef<T::Text>>(self, text: Owned) -> OwnedParser<T, Owned>
+  where
+    T: Action<State = State, Heap = Heap>,
+  {
+    let Parser {
+      entry, state, heap, ..
+    } = self.build(text.as_ref());
+    OwnedParser::new(entry, state, heap, text)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::contextual;
+  use std::{cell::Cell, rc::Rc};
+
+  fn assert_send<T: Send>(_: &T) {}
+
+  #[test]
+  fn owned_parser_thread() {
+    contextual!(usize, Vec<usize>);
+
+    let text: Arc<str> = "a,bb,ccc".into();
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(vec![])
+      .entry(
+        (next(|c| c.is_ascii_alphabetic()) * (1..)).then(|accepted| {
+          *accepted.state += 1;
+          accepted.heap.push(accepted.output().digested);
+        }) | eat(','),
+      )
+      .build_owned(text.clone());
+    assert_send(&parser);
+
+    // partially parsed on this thread
+    assert_eq!(parser.next().unwrap().digested, 1);
+    let handle = std::thread::spawn(move || {
+      // finish parsing on another thread
+      assert_eq!(parser.next().unwrap().digested, 1);
+      // the isolated peek doesn't change the heap
+      let (output, _, heap) = parser.peek_isolated();
+      assert_eq!(output.unwrap().digested, 2);
+      assert_eq!(heap, [1, 2]);
+      assert_eq!(parser.heap, [1]);
+      let rest = parser.by_ref().count();
+      assert!(parser.instant().rest().is_empty());
+      (rest, parser.into_parts())
+    });
+    let (rest, (owned, state, heap)) = handle.join().unwrap();
+    assert_eq!(rest, 3);
+    assert!(Arc::ptr_eq(&owned, &text));
+    assert_eq!(state, 3);
+    assert_eq!(heap, [1, 2, 3]);
+  }
+
+  /// An owned text whose `as_ref` returns "好好" or "a" depending on a shared flag.
+  struct Unstable(Rc<Cell<bool>>);
+
+  impl AsRef<str> for Unstable {
+    fn as_ref(&self) -> &str {
+      if self.0.get() {
+        "好好"
+      } else {
+        "a"
+      }
+    }
+  }
+
+  #[test]
+  fn owned_parser_unstable_text() {
+    let long = Rc::new(Cell::new(true));
+    let mut parser = Parser::builder()
+      .entry(crate::combinator::eat("好"))
+      .build_owned(Unstable(long.clone()));
+    assert_eq!(parser.next().unwrap().digested, 3);
+
+    // the progress is invalid for "a", reject instead of slicing out of bounds
+    long.set(false);
+    assert!(parser.next().is_none());
+    assert!(parser.peek().0.is_none());
+    assert!(parser.peek_isolated().0.is_none());
+
+    long.set(true);
+    assert_eq!(parser.instant().rest(), "好");
+    assert_eq!(parser.next().unwrap().digested, 3);
+  }
+
+  #[test]
+  #[should_panic(expected = "the owned text should not change")]
+  fn owned_parser_unstable_text_instant() {
+    let long = Rc::new(Cell::new(true));
+    let mut parser = Parser::builder()
+      .entry(crate::combinator::eat("好"))
+      .build_owned(Unstable(long.clone()));
+    parser.next();
+    long.set(false);
+    parser.instant();
+  }
+
+  #[test]
+  fn owned_parser_snapshot() {
+    contextual!(usize, ());
+
+    let mut parser = Parser::builder()
+      .state(0)
+      .entry(eat("好").then(|accepted| *accepted.state += 1))
+      .build_owned(Rc::<str>::from("好好好"));
+
+    let snapshot = parser.snapshot();
+    assert_eq!(parser.next().unwrap().digested, 3);
+    let middle = parser.snapshot();
+    assert_eq!(middle.digested(), 3);
+    assert_eq!(parser.by_ref().count(), 2);
+    assert_eq!(parser.state, 3);
+    assert_eq!(parser.instant().digested(), 9);
+
+    parser.restore(middle.clone()).unwrap();
+    assert_eq!(parser.state, 1);
+    assert_eq!(parser.instant().rest(), "好好");
+    parser.restore(snapshot).unwrap();
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.instant().digested(), 0);
+
+    // peek doesn't update the progress
+    let (output, state) = parser.peek();
+    assert_eq!(output.unwrap().digested, 3);
+    assert_eq!(state, 1);
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.instant().digested(), 0);
+
+    // invalid snapshots are rejected
+    let mut other = Parser::builder()
+      .state(0)
+      .entry(eat("好").then(|accepted| *accepted.state += 1))
+      .build_owned("a");
+    assert_eq!(
+      other.restore(middle),
+      Err(DigestError::OutOfRange { n: 3, rest: 1 })
+    );
+    let mut other = Parser::builder()
+      .state(0)
+      .entry(eat("a").then(|accepted| *accepted.state += 1))
+      .build_owned(String::from("a好"));
+    other.next();
+    let bad = other.snapshot();
+    assert_eq!(
+      parser.restore(bad),
+      Err(DigestError::InvalidBoundary { n: 1 })
+    );
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.instant().digested(), 0);
+  }
+}
@@ -0,0 +1,222 @@
+use super::Parser;
+use crate::{
+  action::{Action, Input, Output},
+  digest::Digest,
+  instant::Instant,
+};
+use std::{fmt::Debug, ops::RangeFrom, slice::SliceIndex};
+
+/// An iterator to peek outputs of a [`Parser`] without updating
+/// [`Parser::instant`] and [`Parser::state`].
+///
+/// This is created by [`Parser::peek_iter`].
+/// It advances a shadow [`Instant`] and a cloned `State`,
+/// so the parser is unchanged when this is dropped.
+///
+/// [`Parser::heap`] is shared with the parser and not restored,
+/// so actions shouldn't mutate the heap if you don't want the peeking to be observable.
+pub struct PeekIter<'parser, 'text, T: Action> {
+  parser: &'parser mut Parser<'text, T>,
+  instant: Instant<&'text T::Text>,
+  state: T::State,
+}
+
+impl<'text, T: Action> Debug for PeekIter<'_, 'text, T>
+where
+  Parser<'text, T>: Debug,
+  T::Text: Debug,
+  T::State: Debug,
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("PeekIter")
+      .field("parser", &self.parser)
+      .field("instant", &self.instant)
+      .field("state", &self.state)
+      .finish()
+  }
+}
+
+impl<'text, T: Action> PeekIter<'_, 'text, T> {
+  /// The shadow progress after the peeked outputs.
+  #[inline]
+  pub const fn instant(&self) -> &Instant<&'text T::Text> {
+    &self.instant
+  }
+
+  /// The shadow state after the peeked outputs.
+  #[inline]
+  pub const fn state(&self) -> &T::State {
+    &self.state
+  }
+
+  /// Consume self and take the shadow state.
+  #[inline]
+  pub fn into_state(self) -> T::State {
+    self.state
+  }
+}
+
+impl<T: Action<Text: Digest>> Iterator for PeekIter<'_, '_, T>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Item = Output<T::Value>;
+
+  /// Try to yield the next [`Output`] and digest it from the shadow [`Instant`].
+  /// Return [`None`] if the entry action rejects.
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .parser
+      .entry
+      .exec(Input {
+        instant: &self.instant,
+        state: &mut self.state,
+        heap: &mut self.parser.heap,
+      })
+      .inspect(|output| unsafe { self.instant.digest_unchecked(output.digested) })
+  }
+}
+
+impl<'text, T: Action<Text: Digest, State: Clone>> Parser<'text, T>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  /// Create an iterator to lazily peek outputs
+  /// without updating [`Self::instant`] and [`Self::state`].
+  /// [`Self::state`] will be cloned once.
+  ///
+  /// [`Self::heap`] is not restored, see [`PeekIter`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("a") | eat("b")).build("abc");
+  /// // LL(2)
+  /// let is_ab = parser.peek_iter().map(|o| o.digested).take(2).sum::<usize>() == 2;
+  /// assert!(is_ab);
+  /// assert_eq!(parser.instant.digested(), 0);
+  /// ```
+  #[inline]
+  pub fn peek_iter(&mut self) -> PeekIter<'_, 'text, T> {
+    PeekIter {
+      instant: self.instant.clone(),
+      state: self.state.clone(),
+      parser: self,
+    }
+  }
+
+  /// Try to yield at most `n` [`Output`]s
+  /// without updating [`Self::instant`] and [`Self::state`].
+  /// Stop early if the entry action rejects.
+  ///
+  /// [`Self::state`] will be cloned once,
+  /// the state after the peeked outputs will be returned.
+  /// [`Self::heap`] is not restored, see [`PeekIter`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("a")).build("aab");
+  /// let (outputs, _state) = parser.peek_n(3);
+  /// assert_eq!(outputs.len(), 2);
+  /// assert_eq!(parser.instant.digested(), 0);
+  /// ```
+  #[inline]
+  pub fn peek_n(&mut self, n: usize) -> (Vec<Output<T::Value>>, T::State) {
+    let mut iter = self.peek_iter();
+    let outputs = iter.by_ref().take(n).collect();
+    (outputs, iter.into_state())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{eat, next},
+    contextual,
+  };
+
+  #[test]
+  fn parser_peek_n() {
+    let factory = || {
+      Parser::builder()
+        .entry(
+          (next(|c| c.is_ascii_digit()) * (1..)).select(|a| a.content().len()) | eat(',').bind(0),
+        )
+        .build("1,22,333")
+    };
+
+    let mut parser = factory();
+    let (peeked, _) = parser.peek_n(3);
+    assert_eq!(parser.instant.digested(), 0);
+    let nexted = (&mut parser).take(3).collect::<Vec<_>>();
+    assert_eq!(peeked, nexted);
+    assert_eq!(
+      peeked
+        .iter()
+        .map(|o| (o.value, o.digested))
+        .collect::<Vec<_>>(),
+      vec![(1, 1), (0, 1), (2, 2)]
+    );
+
+    // peek past the end of input
+    let (peeked, _) = parser.peek_n(10);
+    assert_eq!(peeked.len(), 2);
+    assert_eq!(parser.instant.digested(), 4);
+    assert_eq!(parser.by_ref().count(), 2);
+    let (peeked, _) = parser.peek_n(1);
+    assert!(peeked.is_empty());
+
+    // n = 0
+    let mut parser = factory();
+    assert!(parser.peek_n(0).0.is_empty());
+  }
+
+  #[test]
+  fn parser_peek_n_state() {
+    contextual!(usize, usize);
+
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(0)
+      .entry(eat('a').then(|a| *a.state += 1))
+      .build("aaa");
+    let (peeked, state) = parser.peek_n(2);
+    assert_eq!(peeked.len(), 2);
+    assert_eq!(state, 2);
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.instant.digested(), 0);
+  }
+
+  #[test]
+  fn parser_peek_iter() {
+    contextual!(usize, Vec<usize>);
+
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(vec![])
+      .entry(eat('a').then(|a| {
+        *a.state += 1;
+        a.heap.push(a.start());
+      }))
+      .build("aab");
+
+    let mut iter = parser.peek_iter();
+    assert_eq!(iter.next().unwrap().digested, 1);
+    assert_eq!(iter.instant().digested(), 1);
+    assert_eq!(*iter.state(), 1);
+    assert_eq!(iter.by_ref().count(), 1);
+    assert_eq!(iter.instant().rest(), "b");
+    assert_eq!(iter.into_state(), 2);
+
+    // instant and state are unchanged, but the heap is not restored
+    assert_eq!(parser.instant.digested(), 0);
+    assert_eq!(parser.state, 0);
+    assert_eq!(parser.heap, vec![0, 1]);
+
+    // debug
+    let _ = format!("{:?}", parser.peek_iter());
+  }
+}
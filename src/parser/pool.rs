@@ -0,0 +1,216 @@
+use super::{Instant, Parser};
+use crate::action::Action;
+use std::{
+  mem::ManuallyDrop,
+  ops::{Deref, DerefMut},
+  sync::{Mutex, PoisonError},
+};
+
+/// A pool of [`Parser::heap`]s to reuse heap allocations across many small parses.
+///
+/// The entry action is shared by all parsers (as a reference),
+/// [`Parser::state`] is defaulted for each parser,
+/// and [`Parser::heap`] is taken from the pool if there is an idle one,
+/// otherwise a new one will be created by [`Default`].
+/// When the [`PooledParser`] is dropped, the heap will be reset by the `reset` closure
+/// and returned to the pool, unless the pool is full.
+///
+/// The pool is [`Sync`] if the entry action, the `reset` closure and the heap are thread-safe,
+/// so it can be shared across threads (e.g. behind an `Arc` in a server).
+/// # Examples
+/// ```
+/// use whitehole::{combinator::contextual, parser::ParserPool};
+///
+/// contextual!((), Vec<usize>);
+///
+/// # fn main() {
+/// let entry = eat('a').then(|accepted| accepted.heap.push(accepted.start()));
+/// let pool = ParserPool::new(entry, 16, |heap: &mut Vec<usize>| heap.clear());
+///
+/// let mut parser = pool.get("aa");
+/// for _ in &mut *parser {}
+/// assert_eq!(parser.heap, vec![0, 1]);
+/// drop(parser);
+///
+/// // the heap is reused and reset
+/// assert_eq!(pool.idle(), 1);
+/// let parser = pool.get("a");
+/// assert!(parser.heap.is_empty());
+/// # }
+/// ```
+pub struct ParserPool<T: Action, R> {
+  entry: T,
+  reset: R,
+  capacity: usize,
+  heaps: Mutex<Vec<T::Heap>>,
+}
+
+impl<T: Action, R: Fn(&mut T::Heap)> ParserPool<T, R> {
+  /// Create a new pool with the entry action.
+  /// At most `capacity` idle heaps will be kept,
+  /// extra heaps will be dropped when returned.
+  /// Heaps will be reset by `reset` before being returned to the pool.
+  #[inline]
+  pub const fn new(entry: T, capacity: usize, reset: R) -> Self {
+    Self {
+      entry,
+      reset,
+      capacity,
+      heaps: Mutex::new(Vec::new()),
+    }
+  }
+
+  /// The entry action.
+  #[inline]
+  pub const fn entry(&self) -> &T {
+    &self.entry
+  }
+
+  /// The max number of idle heaps.
+  #[inline]
+  pub const fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  /// The number of idle heaps in the pool.
+  #[inline]
+  pub fn idle(&self) -> usize {
+    self.lock().len()
+  }
+
+  /// Get a parser with the `text`.
+  /// The heap will be taken from the pool if there is an idle one,
+  /// otherwise a new one will be created.
+  pub fn get<'text>(&self, text: &'text T::Text) -> PooledParser<'_, 'text, T, R>
+  where
+    T::State: Default,
+    T::Heap: Default,
+  {
+    let heap = self.lock().pop().unwrap_or_default();
+    PooledParser {
+      pool: self,
+      parser: ManuallyDrop::new(Parser {
+        state: T::State::default(),
+        heap,
+        instant: Instant::new(text),
+        entry: &self.entry,
+      }),
+    }
+  }
+
+  #[inline]
+  fn lock(&self) -> std::sync::MutexGuard<'_, Vec<T::Heap>> {
+    // heaps are always reset before being returned, so the poisoned data is still valid
+    self.heaps.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+
+  fn recycle(&self, mut heap: T::Heap) {
+    (self.reset)(&mut heap);
+    let mut heaps = self.lock();
+    if heaps.len() < self.capacity {
+      heaps.push(heap);
+    }
+  }
+}
+
+/// A [`Parser`] from a [`ParserPool`].
+/// The [`Parser::heap`] will be returned to the pool when this is dropped.
+///
+/// This can be used as a [`Parser`] via [`Deref`] and [`DerefMut`].
+pub struct PooledParser<'pool, 'text, T: Action, R: Fn(&mut T::Heap)> {
+  pool: &'pool ParserPool<T, R>,
+  parser: ManuallyDrop<Parser<'text, &'pool T>>,
+}
+
+impl<'pool, 'text, T: Action, R: Fn(&mut T::Heap)> Deref for PooledParser<'pool, 'text, T, R> {
+  type Target = Parser<'text, &'pool T>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.parser
+  }
+}
+
+impl<T: Action, R: Fn(&mut T::Heap)> DerefMut for PooledParser<'_, '_, T, R> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.parser
+  }
+}
+
+impl<T: Action, R: Fn(&mut T::Heap)> Drop for PooledParser<'_, '_, T, R> {
+  fn drop(&mut self) {
+    // SAFETY: the parser is never used after being taken
+    let parser = unsafe { ManuallyDrop::take(&mut self.parser) };
+    self.pool.recycle(parser.heap);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::contextual;
+
+  contextual!((), Vec<usize>);
+
+  type Reset = fn(&mut Vec<usize>);
+
+  fn pool() -> ParserPool<impl Action<Text = str, State = (), Heap = Vec<usize>>, Reset> {
+    let entry = eat('a').then(|accepted| accepted.heap.push(accepted.start()));
+    ParserPool::new(entry, 2, |heap: &mut Vec<usize>| heap.clear())
+  }
+
+  #[test]
+  fn parser_pool_reuse_heap() {
+    let pool = pool();
+    assert_eq!(pool.capacity(), 2);
+    assert_eq!(pool.idle(), 0);
+
+    let mut parser = pool.get("aaa");
+    assert_eq!(parser.by_ref().count(), 3);
+    assert_eq!(parser.heap, vec![0, 1, 2]);
+    let ptr = parser.heap.as_ptr();
+    drop(parser);
+    assert_eq!(pool.idle(), 1);
+
+    // the same allocation is reused and reset
+    let mut parser = pool.get("a");
+    assert_eq!(parser.heap.as_ptr(), ptr);
+    assert!(parser.heap.is_empty());
+    assert!(parser.heap.capacity() >= 3);
+    assert_eq!(pool.idle(), 0);
+    parser.next();
+    assert_eq!(parser.heap, vec![0]);
+  }
+
+  #[test]
+  fn parser_pool_overflow() {
+    let pool = pool();
+    // more parsers than the capacity, extra heaps are created and dropped
+    let parsers: Vec<_> = (0..4).map(|_| pool.get("a")).collect();
+    assert_eq!(pool.idle(), 0);
+    drop(parsers);
+    assert_eq!(pool.idle(), 2);
+  }
+
+  #[test]
+  fn parser_pool_concurrent() {
+    let pool = pool();
+    std::thread::scope(|s| {
+      for i in 0..8 {
+        let pool = &pool;
+        s.spawn(move || {
+          for n in 0..100 {
+            let text = "a".repeat((i + n) % 5);
+            let mut parser = pool.get(&text);
+            // no data leaks from the previous parse
+            assert!(parser.heap.is_empty());
+            for _ in &mut *parser {}
+            assert_eq!(parser.heap, (0..text.len()).collect::<Vec<_>>());
+          }
+        });
+      }
+    });
+    assert!(pool.idle() <= pool.capacity());
+  }
+}
@@ -0,0 +1,339 @@
+use super::{Builder, Parser};
+use crate::{
+  action::{Action, Output},
+  digest::Digest,
+};
+use std::{
+  ops::{Deref, DerefMut, RangeFrom},
+  slice::SliceIndex,
+};
+
+/// Throttled progress reporting.
+/// See [`Builder::progress`].
+///
+/// The progress is calculated from [`Instant::digested`](crate::instant::Instant::digested),
+/// so bytes digested from outside of the parser (e.g. in error recovery) are also counted.
+#[derive(Clone, Copy)]
+pub struct Progress<F> {
+  every_n_bytes: usize,
+  callback: F,
+  /// The digested bytes in the last callback.
+  reported: usize,
+  /// Whether the final callback is invoked.
+  done: bool,
+}
+
+impl<F> std::fmt::Debug for Progress<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Progress")
+      .field("every_n_bytes", &self.every_n_bytes)
+      .field("reported", &self.reported)
+      .field("done", &self.done)
+      .finish()
+  }
+}
+
+impl<F: FnMut(usize, usize)> Progress<F> {
+  /// Create a new instance.
+  /// The `callback` will be invoked with `(digested, total)` in bytes
+  /// when at least `every_n_bytes` are newly digested since the last callback,
+  /// and once more when the whole text is digested.
+  #[inline]
+  pub const fn new(every_n_bytes: usize, callback: F) -> Self {
+    Self {
+      every_n_bytes,
+      callback,
+      reported: 0,
+      done: false,
+    }
+  }
+
+  /// The digested bytes in the last callback.
+  #[inline]
+  pub const fn reported(&self) -> usize {
+    self.reported
+  }
+
+  /// Whether the final callback (with `digested == total`) is invoked.
+  #[inline]
+  pub const fn done(&self) -> bool {
+    self.done
+  }
+
+  /// Check the progress and invoke the callback if needed.
+  pub fn update(&mut self, digested: usize, total: usize) {
+    if self.done {
+      return;
+    }
+
+    if digested >= total {
+      self.done = true;
+      self.reported = digested;
+      (self.callback)(digested, total);
+      return;
+    }
+
+    if digested < self.reported {
+      // the progress is rewound (e.g. a snapshot is restored), count from there
+      self.reported = digested;
+      return;
+    }
+
+    // at least 1 byte should be digested since the last callback
+    if digested > self.reported && digested - self.reported >= self.every_n_bytes {
+      self.reported = digested;
+      (self.callback)(digested, total);
+    }
+  }
+}
+
+/// A [`Builder`] with a progress callback.
+/// See [`Builder::progress`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressBuilder<T, State, Heap, F> {
+  builder: Builder<T, State, Heap>,
+  progress: Progress<F>,
+}
+
+impl<T, State, Heap> Builder<T, State, Heap> {
+  /// Report the parsing progress via the `callback` with `(digested, total)` in bytes,
+  /// where `total` is the length of the whole text.
+  ///
+  /// The `callback` will be invoked at most once per `every_n_bytes` of newly digested bytes
+  /// (counted across outputs, not per output),
+  /// and once more when the whole text is digested.
+  /// If the parser stalls before the end, there will be no final callback.
+  ///
+  /// This should be the last option before [`ProgressBuilder::build`].
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut reports = vec![];
+  /// let mut parser = Parser::builder()
+  ///   .entry(eat("abc"))
+  ///   .progress(5, |digested, total| reports.push(digested * 100 / total))
+  ///   .build("abcabcabc");
+  /// for _ in &mut parser {}
+  /// assert_eq!(reports, [66, 100]);
+  /// ```
+  #[inline]
+  pub fn progress<F: FnMut(usize, usize)>(
+    self,
+    every_n_bytes: usize,
+    callback: F,
+  ) -> ProgressBuilder<T, State, Heap, F> {
+    ProgressBuilder {
+      builder: self,
+      progress: Progress::new(every_n_bytes, callback),
+    }
+  }
+}
+
+impl<T, State, Heap, F: FnMut(usize, usize)> ProgressBuilder<T, State, Heap, F> {
+  /// Build a [`ProgressParser`] with the given text.
+  #[inline]
+  pub fn build<Text: ?Sized>(self, text: &Text) -> ProgressParser<'_, T, F>
+  where
+    T: Action<Text = Text, State = State, Heap = Heap>,
+  {
+    ProgressParser {
+      parser: self.builder.build(text),
+      progress: self.progress,
+    }
+  }
+}
+
+/// A [`Parser`] which reports the parsing progress.
+/// See [`Builder::progress`].
+///
+/// This can be used as a [`Parser`] via [`Deref`] and [`DerefMut`].
+/// The progress is checked in [`Self::next`],
+/// call [`Self::report`] after digesting from outside of the parser
+/// if you are not going to call [`Self::next`] again.
+pub struct ProgressParser<'text, T: Action, F> {
+  parser: Parser<'text, T>,
+  progress: Progress<F>,
+}
+
+impl<'text, T: Action, F> Deref for ProgressParser<'text, T, F> {
+  type Target = Parser<'text, T>;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.parser
+  }
+}
+
+impl<T: Action, F> DerefMut for ProgressParser<'_, T, F> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.parser
+  }
+}
+
+impl<'text, T: Action<Text: Digest>, F: FnMut(usize, usize)> ProgressParser<'text, T, F> {
+  /// The length of the whole text in bytes (see [`Digest::len`]).
+  #[inline]
+  pub fn total(&self) -> usize {
+    self.parser.instant.text().len()
+  }
+
+  /// The digested percentage from `0.0` to `100.0`.
+  /// An empty text is treated as `100.0`.
+  #[inline]
+  pub fn percentage(&self) -> f64 {
+    let total = self.total();
+    if total == 0 {
+      100.0
+    } else {
+      self.parser.instant.digested() as f64 * 100.0 / total as f64
+    }
+  }
+
+  /// Check the progress and invoke the callback if needed.
+  ///
+  /// This is called by [`Self::next`] automatically.
+  /// Call this after digesting from outside of the parser
+  /// if you are not going to call [`Self::next`] again.
+  #[inline]
+  pub fn report(&mut self) {
+    let total = self.total();
+    self.progress.update(self.parser.instant.digested(), total);
+  }
+
+  /// Consume self, return the inner [`Parser`].
+  #[inline]
+  pub fn into_parser(self) -> Parser<'text, T> {
+    self.parser
+  }
+}
+
+impl<T: Action<Text: Digest>, F: FnMut(usize, usize)> Iterator for ProgressParser<'_, T, F>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  type Item = Output<T::Value>;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    let output = self.parser.next();
+    self.report();
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::combinator::{eat, take};
+
+  #[test]
+  fn parser_progress_cadence() {
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("abc"))
+      .progress(10, |digested, total| reports.push((digested, total)))
+      .build("abcabcabcabcabcabcabcabcabcabc");
+    assert_eq!(parser.total(), 30);
+    assert_eq!(parser.by_ref().count(), 10);
+    assert_eq!(parser.percentage(), 100.0);
+    // throttled across outputs, plus a final call
+    assert_eq!(reports, [(12, 30), (24, 30), (30, 30)]);
+  }
+
+  #[test]
+  fn parser_progress_every_output() {
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("a") | eat("bb"))
+      .progress(0, |digested, _| reports.push(digested))
+      .build("abba");
+    for _ in &mut parser {}
+    // no duplicated callback on rejection after the end
+    parser.next();
+    assert_eq!(reports, [1, 3, 4]);
+  }
+
+  #[test]
+  fn parser_progress_final() {
+    // empty text
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("a"))
+      .progress(10, |digested, total| reports.push((digested, total)))
+      .build("");
+    assert!(parser.next().is_none());
+    assert_eq!(parser.percentage(), 100.0);
+    assert_eq!(reports, [(0, 0)]);
+
+    // the final callback is invoked even if the threshold is not reached
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(take(1))
+      .progress(100, |digested, total| reports.push((digested, total)))
+      .build("abc");
+    for _ in &mut parser {}
+    assert_eq!(reports, [(3, 3)]);
+  }
+
+  #[test]
+  fn parser_progress_stall() {
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("ab"))
+      .progress(3, |digested, total| reports.push((digested, total)))
+      .build("ababXabab");
+    for _ in &mut parser {}
+    // stalled mid-file, no final callback
+    assert_eq!(parser.instant.digested(), 4);
+    assert_eq!(parser.percentage(), 4.0 * 100.0 / 9.0);
+
+    // external digestion is counted
+    unsafe { parser.instant.digest_unchecked(1) };
+    parser.report();
+    for _ in &mut parser {}
+    assert_eq!(parser.into_parser().instant.digested(), 9);
+    assert_eq!(reports, [(4, 9), (7, 9), (9, 9)]);
+  }
+
+  #[test]
+  fn parser_progress_external_digestion_to_end() {
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("a"))
+      .progress(10, |digested, total| reports.push((digested, total)))
+      .build("aXX");
+    for _ in &mut parser {}
+    unsafe { parser.instant.digest_unchecked(2) };
+    parser.report();
+    assert_eq!(reports, [(3, 3)]);
+  }
+
+  #[test]
+  fn parser_progress_rewind() {
+    let mut reports = vec![];
+    let mut parser = Parser::builder()
+      .entry(eat("a"))
+      .progress(2, |digested, _| reports.push(digested))
+      .build("aaaaa");
+    let snapshot = parser.snapshot();
+    parser.next();
+    parser.next();
+    parser.restore(snapshot);
+    parser.report();
+    for _ in &mut parser {}
+    assert_eq!(reports, [2, 2, 4, 5]);
+  }
+
+  #[test]
+  fn progress_debug() {
+    let progress = Progress::new(1, |_, _| {});
+    assert_eq!(
+      format!("{:?}", progress),
+      "Progress { every_n_bytes: 1, reported: 0, done: false }"
+    );
+    assert_eq!(progress.reported(), 0);
+    assert!(!progress.done());
+  }
+}
@@ -0,0 +1,383 @@
+use super::Builder;
+use crate::{
+  action::{Action, Input, Output, Rejection},
+  idx,
+  instant::Instant,
+};
+
+/// The result of [`StreamingParser::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Streamed<Value> {
+  /// The entry action accepted and the output is digested.
+  Output(Output<Value>),
+  /// The result may change with more input.
+  /// Call [`StreamingParser::feed`] or [`StreamingParser::end`] then retry.
+  Incomplete,
+  /// The entry action rejected, and more input won't change the result.
+  Rejected,
+}
+
+impl<Value> Streamed<Value> {
+  /// Return the output if the entry action accepted.
+  #[inline]
+  pub fn output(self) -> Option<Output<Value>> {
+    match self {
+      Self::Output(output) => Some(output),
+      _ => None,
+    }
+  }
+
+  /// Return `true` if more input is needed.
+  #[inline]
+  pub const fn is_incomplete(&self) -> bool {
+    matches!(self, Self::Incomplete)
+  }
+}
+
+/// A parser for text that arrives in chunks, e.g. from a socket.
+///
+/// Feed chunks with [`Self::feed`] and call [`Self::end`] when there is no more input.
+/// The fed text is kept in an internal buffer until it is digested,
+/// and the digested prefix is dropped on the next [`Self::feed`],
+/// so the buffer doesn't grow with the whole input.
+/// [`Instant::digested`] and ranges are still counted from the start of the first chunk
+/// and never rewind, see [`Self::instant`].
+///
+/// Before [`Self::end`], [`Streamed::Incomplete`] is returned and nothing is digested
+/// if the output reaches the end of the buffer,
+/// or the entry action rejects with [`Rejection::Incomplete`] (e.g. [`eat`](crate::combinator::eat)
+/// with a prefix of the pattern, propagated through `|` and `+`),
+/// or the entry action rejects at the end of the buffer,
+/// so you can retry after feeding more input.
+/// Other rejections are final and [`Streamed::Rejected`] is returned.
+/// Actions that look ahead beyond what they digest should be careful with this.
+///
+/// [`Self::state`] is cloned before each try and only committed on [`Streamed::Output`].
+/// [`Self::heap`] is not restored, so actions shouldn't mutate the heap before they are sure to accept.
+/// # Examples
+/// ```
+/// use whitehole::{combinator::eat, parser::{Parser, Streamed}};
+///
+/// let mut parser = Parser::builder().entry(eat("true")).build_streaming();
+/// parser.feed("tr");
+/// assert_eq!(parser.poll(), Streamed::Incomplete);
+/// parser.feed("uetr");
+/// assert_eq!(parser.poll().output().unwrap().digested, 4);
+/// assert_eq!(parser.poll(), Streamed::Incomplete);
+/// parser.end();
+/// assert_eq!(parser.poll(), Streamed::Rejected);
+/// assert_eq!(parser.instant().rest(), "tr");
+/// assert_eq!(parser.digested(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingParser<T: Action<Text = str>> {
+  /// See [`Parser::state`](crate::parser::Parser::state).
+  pub state: T::State,
+
+  /// See [`Parser::heap`](crate::parser::Parser::heap).
+  pub heap: T::Heap,
+
+  /// The entry action.
+  pub entry: T,
+
+  buffer: String,
+  /// How many bytes are dropped from the start of the buffer.
+  base: usize,
+  /// How many bytes of the buffer are digested.
+  digested: usize,
+  ended: bool,
+}
+
+impl<T: Action<Text = str>> StreamingParser<T> {
+  /// Create a new instance with an empty buffer.
+  #[inline]
+  pub const fn new(entry: T, state: T::State, heap: T::Heap) -> Self {
+    Self {
+      state,
+      heap,
+      entry,
+      buffer: String::new(),
+      base: 0,
+      digested: 0,
+      ended: false,
+    }
+  }
+
+  /// Append more text to the buffer.
+  ///
+  /// The digested prefix of the buffer is dropped first,
+  /// so offsets in [`Self::instant`] are shifted.
+  /// [`Self::digested`] is not affected.
+  #[inline]
+  pub fn feed(&mut self, more: &str) {
+    debug_assert!(!self.ended, "feed after end");
+    self.compact();
+    self.buffer.push_str(more);
+  }
+
+  /// Drop the digested prefix of the buffer.
+  #[inline]
+  fn compact(&mut self) {
+    if self.digested == 0 {
+      return;
+    }
+    self.buffer.drain(..self.digested);
+    // SAFETY: the dropped prefix is inside the buffer, whose length is a valid usize
+    self.base = idx::unchecked!(idx::add(self.base, self.digested));
+    self.digested = 0;
+  }
+
+  /// Mark the end of input.
+  /// After this, results are always final.
+  #[inline]
+  pub fn end(&mut self) {
+    self.ended = true;
+  }
+
+  /// Whether [`Self::end`] is called.
+  #[inline]
+  pub const fn is_ended(&self) -> bool {
+    self.ended
+  }
+
+  /// How many bytes are digested, counted from the start of the first chunk.
+  /// This never rewinds.
+  ///
+  /// This is the same as [`Instant::digested`] of [`Self::instant`].
+  #[inline]
+  pub const fn digested(&self) -> usize {
+    // SAFETY: the sum is the length of the fed text, which is a valid usize
+    idx::unchecked!(idx::add(self.base, self.digested))
+  }
+
+  /// The current progress over the buffered text.
+  ///
+  /// [`Instant::digested`] and ranges (e.g. [`Combinator::range`](crate::combinator::Combinator::range))
+  /// are counted from the start of the first chunk,
+  /// but [`Instant::text`] only contains the text which is not dropped by [`Self::feed`].
+  #[inline]
+  pub fn instant(&self) -> Instant<&str> {
+    // SAFETY: `digested` is always a valid digested length of the buffer
+    // since only the digested prefix is dropped
+    unsafe {
+      Instant::new_with_base(self.buffer.as_str(), self.base).to_digested_unchecked(self.digested)
+    }
+  }
+
+  /// Try to yield the next [`Output`] and digest it.
+  ///
+  /// If [`Streamed::Incomplete`] is returned,
+  /// [`Self::state`] and the progress are unchanged
+  /// so you can retry after [`Self::feed`] or [`Self::end`].
+  pub fn poll(&mut self) -> Streamed<T::Value>
+  where
+    T::State: Clone,
+  {
+    // SAFETY: see `Self::instant`
+    let instant = unsafe {
+      Instant::new_with_base(self.buffer.as_str(), self.base).to_digested_unchecked(self.digested)
+    };
+    let mut state = self.state.clone();
+    let output = match self.entry.exec_cut(Input {
+      instant: &instant,
+      state: &mut state,
+      heap: &mut self.heap,
+    }) {
+      Ok(output) => output,
+      Err(_) if self.ended => return Streamed::Rejected,
+      // the rejection may be caused by the end of the buffer
+      Err(Rejection::Incomplete) => return Streamed::Incomplete,
+      Err(_) if instant.rest().is_empty() => return Streamed::Incomplete,
+      Err(_) => return Streamed::Rejected,
+    };
+
+    if !self.ended && output.digested == instant.rest().len() {
+      // the output may be extended by more input
+      return Streamed::Incomplete;
+    }
+
+    self.state = state;
+    // SAFETY: the output is inside the buffer, whose length is a valid usize
+    self.digested = idx::unchecked!(idx::add(self.digested, output.digested));
+    Streamed::Output(output)
+  }
+}
+
+impl<T, State, Heap> Builder<T, State, Heap> {
+  /// Build a [`StreamingParser`] with an empty buffer.
+  /// # Examples
+  /// ```
+  /// use whitehole::{combinator::eat, parser::Parser};
+  ///
+  /// let mut parser = Parser::builder().entry(eat("true")).build_streaming();
+  /// parser.feed("true");
+  /// ```
+  #[inline]
+  pub fn build_streaming(self) -> StreamingParser<T>
+  where
+    T: Action<Text = str, State = State, Heap = Heap>,
+  {
+    let parser = self.build("");
+    StreamingParser::new(parser.entry, parser.state, parser.heap)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{eat, next},
+    contextual,
+    parser::Parser,
+  };
+
+  #[test]
+  fn streaming_token_across_chunks() {
+    let mut parser = Parser::builder().entry(eat("true")).build_streaming();
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.feed("tr");
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    assert_eq!(parser.instant().digested(), 0);
+    parser.feed("ue");
+    // the output reaches the end of the buffer
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.end();
+    assert_eq!(
+      parser.poll(),
+      Streamed::Output(Output {
+        value: (),
+        digested: 4
+      })
+    );
+    assert_eq!(parser.instant().digested(), 4);
+    assert_eq!(parser.digested(), 4);
+    assert_eq!(parser.poll(), Streamed::Rejected);
+    assert!(parser.is_ended());
+  }
+
+  #[test]
+  fn streaming_greedy() {
+    let mut parser = Parser::builder()
+      .entry((next(|c| c.is_ascii_digit()) * (1..)) | eat(','))
+      .build_streaming();
+    parser.feed("12");
+    assert!(parser.poll().is_incomplete());
+    parser.feed("3,4");
+    assert_eq!(parser.poll().output().unwrap().digested, 3);
+    assert_eq!(parser.poll().output().unwrap().digested, 1);
+    assert!(parser.poll().is_incomplete());
+    assert_eq!(parser.digested(), 4);
+    // the digested prefix is dropped, the digested length is stable across chunks
+    parser.feed("5,");
+    assert_eq!(parser.instant().text(), "45,");
+    assert_eq!(parser.instant().digested(), 4);
+    assert_eq!(parser.poll().output().unwrap().digested, 2);
+    assert_eq!(parser.instant().digested(), 6);
+    assert_eq!(parser.digested(), 6);
+    assert_eq!(parser.instant().rest(), ",");
+    assert!(parser.poll().is_incomplete());
+    parser.end();
+    assert_eq!(parser.poll().output().unwrap().digested, 1);
+    assert_eq!(parser.poll(), Streamed::Rejected);
+    assert_eq!(parser.instant().text(), "45,");
+    assert_eq!(parser.digested(), 7);
+  }
+
+  #[test]
+  fn streaming_compact() {
+    let mut parser = Parser::builder().entry(eat("ab")).build_streaming();
+    parser.feed("a");
+    for _ in 0..100 {
+      parser.feed("ba");
+      assert!(parser.poll().output().is_some());
+      assert!(parser.poll().is_incomplete());
+    }
+    // the digested prefix is dropped on each feed
+    assert_eq!(parser.instant().text(), "aba");
+    assert_eq!(parser.instant().rest(), "a");
+    assert_eq!(parser.digested(), 200);
+    parser.feed("b");
+    parser.end();
+    assert!(parser.poll().output().is_some());
+    assert_eq!(parser.digested(), 202);
+  }
+
+  #[test]
+  fn streaming_rejected() {
+    let mut parser = Parser::builder().entry(eat("true")).build_streaming();
+    parser.feed("t");
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    // rejected before the end of the buffer, more input won't help
+    parser.feed("x");
+    assert_eq!(parser.poll(), Streamed::Rejected);
+    assert_eq!(parser.instant().digested(), 0);
+
+    // rejected at the end of the buffer, but the entry is not an `eat`
+    let mut parser = Parser::builder()
+      .entry(next(|c| c.is_ascii_digit()))
+      .build_streaming();
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.feed("a");
+    assert_eq!(parser.poll(), Streamed::Rejected);
+
+    // after the end, all rejections are final
+    let mut parser = Parser::builder().entry(eat("true")).build_streaming();
+    parser.feed("tr");
+    parser.end();
+    assert_eq!(parser.poll(), Streamed::Rejected);
+  }
+
+  #[test]
+  fn streaming_incomplete_propagation() {
+    let mut parser = Parser::builder()
+      .entry(eat('{') + (eat("true") | eat("false")) + eat('}'))
+      .build_streaming();
+    parser.feed("{fa");
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.feed("lse");
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.feed("}{x}");
+    assert_eq!(parser.poll().output().unwrap().digested, 7);
+    assert_eq!(parser.poll(), Streamed::Rejected);
+
+    // incomplete input after a cut is not a cut
+    let mut parser = Parser::builder()
+      .entry((eat('[') + eat("ab").cut()) | eat("[x"))
+      .build_streaming();
+    parser.feed("[a");
+    assert_eq!(parser.poll(), Streamed::Incomplete);
+    parser.feed("c");
+    assert_eq!(parser.poll(), Streamed::Rejected);
+
+    // ranges are counted from the start of the first chunk
+    let mut parser = Parser::builder().entry(eat("ab").range()).build_streaming();
+    parser.feed("aba");
+    assert_eq!(parser.poll().output().unwrap().value.range, 0..2);
+    parser.feed("b!");
+    assert_eq!(parser.poll().output().unwrap().value.range, 2..4);
+  }
+
+  #[test]
+  fn streaming_state_rollback() {
+    contextual!(usize, usize);
+
+    let mut parser = Parser::builder()
+      .state(0)
+      .heap(0)
+      .entry(eat("ab").then(|accepted| {
+        *accepted.state += 1;
+      }))
+      .build_streaming();
+    parser.feed("ab");
+    assert!(parser.poll().is_incomplete());
+    assert_eq!(parser.state, 0);
+    parser.feed("ab");
+    assert!(parser.poll().output().is_some());
+    assert_eq!(parser.state, 1);
+    assert!(parser.poll().is_incomplete());
+    assert_eq!(parser.state, 1);
+    parser.end();
+    assert!(parser.poll().output().is_some());
+    assert_eq!(parser.state, 2);
+  }
+}
@@ -0,0 +1,140 @@
+use super::Instant;
+use crate::{
+  action::{Action, Input},
+  digest::Digest,
+  instant::DigestError,
+};
+use std::{
+  any::Any,
+  fmt::{self, Display},
+  ops::RangeFrom,
+  panic::{self, AssertUnwindSafe},
+  slice::SliceIndex,
+};
+
+/// An invariant violated by the entry action. See [`ProbeFailure`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+  /// The entry action accepted with a digested length which can't be digested,
+  /// e.g. greater than the length of the rest, or not a char boundary for [`str`].
+  Digest(DigestError),
+  /// The entry action panicked, with the panic message if it is a string.
+  Panic(String),
+}
+
+impl Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Digest(e) => write!(f, "the entry action accepted but {}", e),
+      Self::Panic(message) => write!(f, "the entry action panicked: {}", message),
+    }
+  }
+}
+
+/// A failure found by [`Builder::validate_with`](super::Builder::validate_with).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeFailure {
+  /// The index of the probe input.
+  pub probe: usize,
+  /// The digested bytes of the probe when the entry action was executed.
+  pub offset: usize,
+  /// The violated invariant.
+  pub violation: Violation,
+}
+
+impl Display for ProbeFailure {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "probe #{} at offset {}: {}",
+      self.probe, self.offset, self.violation
+    )
+  }
+}
+
+/// The error returned by [`Builder::validate_with`](super::Builder::validate_with).
+/// There is at most one failure for each probe input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+  /// The failures, ordered by [`ProbeFailure::probe`].
+  pub failures: Vec<ProbeFailure>,
+}
+
+impl Display for ValidationReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} probe(s) failed", self.failures.len())?;
+    for failure in &self.failures {
+      write!(f, "\n  {}", failure)?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for ValidationReport {}
+
+/// Format the payload of a panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+  match payload.downcast::<String>() {
+    Ok(message) => *message,
+    Err(payload) => payload
+      .downcast_ref::<&str>()
+      .map_or_else(|| "<non-string panic payload>".into(), |s| s.to_string()),
+  }
+}
+
+/// Execute `entry` on each probe like [`Parser::next`](super::Parser::next),
+/// until it rejects, accepts with zero digested, or violates an invariant.
+/// `state` and `heap` are cloned for each probe.
+pub(super) fn validate<Text: ?Sized + Digest, State: Clone, Heap: Clone>(
+  entry: &impl Action<Text = Text, State = State, Heap = Heap>,
+  state: &State,
+  heap: &Heap,
+  probes: &[&Text],
+) -> Result<(), ValidationReport>
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+{
+  let mut failures = Vec::new();
+
+  for (probe, text) in probes.iter().enumerate() {
+    let mut instant = Instant::new(*text);
+    let mut state = state.clone();
+    let mut heap = heap.clone();
+
+    let violation = loop {
+      // the state and heap might be torn after a panic, but they are dropped after this probe
+      let digested = panic::catch_unwind(AssertUnwindSafe(|| {
+        entry
+          .exec(Input {
+            instant: &instant,
+            state: &mut state,
+            heap: &mut heap,
+          })
+          .map(|output| output.digested)
+      }));
+      match digested {
+        Err(payload) => break Some(Violation::Panic(panic_message(payload))),
+        Ok(None | Some(0)) => break None,
+        Ok(Some(n)) => {
+          if let Err(e) = instant.digest(n) {
+            break Some(Violation::Digest(e));
+          }
+        }
+      }
+    };
+
+    if let Some(violation) = violation {
+      failures.push(ProbeFailure {
+        probe,
+        offset: instant.digested(),
+        violation,
+      });
+    }
+  }
+
+  if failures.is_empty() {
+    Ok(())
+  } else {
+    Err(ValidationReport { failures })
+  }
+}
@@ -0,0 +1,205 @@
+//! Convert byte offsets to line and column numbers.
+//!
+//! Byte offsets (e.g. from [`Combinator::range`](crate::combinator::Combinator::range)
+//! or [`Instant::digested`](crate::instant::Instant::digested)) are fine for machines,
+//! but diagnostics for humans need line and column numbers.
+//! Build a [`LineColLookup`] from the text once, then each lookup is `O(log n)`.
+//!
+//! ```
+//! use whitehole::{combinator::eat, parser::Parser, position::LineCol};
+//!
+//! let mut parser = Parser::builder().entry(eat("a\n")).build("a\na\nb");
+//! for _ in &mut parser {}
+//! let lookup = parser.line_col_lookup();
+//! assert_eq!(
+//!   lookup.line_col(parser.instant.digested()),
+//!   LineCol { line: 3, column: 1 }
+//! );
+//! ```
+
+use crate::digest::Digest;
+use std::fmt::{self, Display};
+
+/// A 1-based line and column number.
+///
+/// For `str`, the column is counted in [`char`]s.
+/// For `[u8]`, the column is counted in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineCol {
+  /// The 1-based line number.
+  pub line: usize,
+  /// The 1-based column number.
+  pub column: usize,
+}
+
+impl Display for LineCol {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}", self.line, self.column)
+  }
+}
+
+/// Precomputed line starts of a text to convert byte offsets to [`LineCol`].
+///
+/// Lines are separated by `\n`, so `\r\n` is also supported
+/// (the `\r` is the last char of the line).
+/// An offset at a `\n` belongs to the line ended by it.
+#[derive(Debug, Clone)]
+pub struct LineColLookup<'text, Text: ?Sized> {
+  text: &'text Text,
+  /// The byte offset of the start of each line. The first one is always 0.
+  line_starts: Vec<usize>,
+}
+
+impl<'text, Text: ?Sized + Digest + AsRef<[u8]>> LineColLookup<'text, Text> {
+  /// Create a new instance by scanning the text for `\n`.
+  pub fn new(text: &'text Text) -> Self {
+    let mut line_starts = vec![0];
+    line_starts.extend(
+      text
+        .as_ref()
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| **b == b'\n')
+        .map(|(i, _)| i + 1),
+    );
+    Self { text, line_starts }
+  }
+
+  /// The text.
+  #[inline]
+  pub const fn text(&self) -> &'text Text {
+    self.text
+  }
+
+  /// The number of lines. This is at least 1.
+  #[inline]
+  pub fn lines(&self) -> usize {
+    self.line_starts.len()
+  }
+
+  /// Return the 0-based line index and the byte offset of the line start.
+  #[inline]
+  fn line_start(&self, offset: usize) -> (usize, usize) {
+    debug_assert!(offset <= self.text.as_bytes().len());
+    // the first line start is 0, so the partition point is at least 1
+    let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+    (line, self.line_starts[line])
+  }
+}
+
+impl LineColLookup<'_, str> {
+  /// Convert a byte offset to [`LineCol`].
+  /// The column is counted in [`char`]s.
+  ///
+  /// The `offset` should not be greater than the length of the text.
+  /// # Examples
+  /// ```
+  /// # use whitehole::position::{LineCol, LineColLookup};
+  /// let lookup = LineColLookup::new("a\r\n好b");
+  /// assert_eq!(lookup.line_col(0), LineCol { line: 1, column: 1 });
+  /// assert_eq!(lookup.line_col(3), LineCol { line: 2, column: 1 });
+  /// assert_eq!(lookup.line_col(6), LineCol { line: 2, column: 2 });
+  /// ```
+  #[inline]
+  pub fn line_col(&self, offset: usize) -> LineCol {
+    let (line, start) = self.line_start(offset);
+    LineCol {
+      line: line + 1,
+      // count chars by skipping utf-8 continuation bytes
+      column: self.text.as_bytes()[start..offset]
+        .iter()
+        .filter(|b| (**b & 0xC0) != 0x80)
+        .count()
+        + 1,
+    }
+  }
+}
+
+impl LineColLookup<'_, [u8]> {
+  /// Convert a byte offset to [`LineCol`].
+  /// The column is counted in bytes.
+  ///
+  /// The `offset` should not be greater than the length of the text.
+  /// # Examples
+  /// ```
+  /// # use whitehole::position::{LineCol, LineColLookup};
+  /// let lookup = LineColLookup::new(b"a\nbc" as &[u8]);
+  /// assert_eq!(lookup.line_col(3), LineCol { line: 2, column: 2 });
+  /// ```
+  #[inline]
+  pub fn line_col(&self, offset: usize) -> LineCol {
+    let (line, start) = self.line_start(offset);
+    LineCol {
+      line: line + 1,
+      column: offset - start + 1,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn lc(line: usize, column: usize) -> LineCol {
+    LineCol { line, column }
+  }
+
+  #[test]
+  fn line_col_str() {
+    let lookup = LineColLookup::new("ab\ncd\r\n\nef");
+    assert_eq!(lookup.lines(), 4);
+    assert_eq!(lookup.text(), "ab\ncd\r\n\nef");
+    assert_eq!(lookup.line_col(0), lc(1, 1));
+    assert_eq!(lookup.line_col(1), lc(1, 2));
+    // at the newline
+    assert_eq!(lookup.line_col(2), lc(1, 3));
+    // after the newline
+    assert_eq!(lookup.line_col(3), lc(2, 1));
+    // \r\n
+    assert_eq!(lookup.line_col(5), lc(2, 3));
+    assert_eq!(lookup.line_col(6), lc(2, 4));
+    // empty line
+    assert_eq!(lookup.line_col(7), lc(3, 1));
+    assert_eq!(lookup.line_col(8), lc(4, 1));
+    // the end of the text
+    assert_eq!(lookup.line_col(10), lc(4, 3));
+
+    // ends with a newline
+    let lookup = LineColLookup::new("a\n");
+    assert_eq!(lookup.lines(), 2);
+    assert_eq!(lookup.line_col(2), lc(2, 1));
+
+    // empty text
+    let lookup = LineColLookup::new("");
+    assert_eq!(lookup.lines(), 1);
+    assert_eq!(lookup.line_col(0), lc(1, 1));
+  }
+
+  #[test]
+  fn line_col_multi_byte() {
+    // columns are counted in chars
+    let lookup = LineColLookup::new("好a\n好好\n");
+    assert_eq!(lookup.line_col(3), lc(1, 2));
+    assert_eq!(lookup.line_col(4), lc(1, 3));
+    assert_eq!(lookup.line_col(5), lc(2, 1));
+    assert_eq!(lookup.line_col(8), lc(2, 2));
+    assert_eq!(lookup.line_col(11), lc(2, 3));
+    assert_eq!(lookup.line_col(12), lc(3, 1));
+  }
+
+  #[test]
+  fn line_col_bytes() {
+    // columns are counted in bytes
+    let lookup = LineColLookup::new("好a\r\nb".as_bytes());
+    assert_eq!(lookup.lines(), 2);
+    assert_eq!(lookup.line_col(3), lc(1, 4));
+    assert_eq!(lookup.line_col(5), lc(1, 6));
+    assert_eq!(lookup.line_col(6), lc(2, 1));
+    assert_eq!(lookup.line_col(7), lc(2, 2));
+  }
+
+  #[test]
+  fn line_col_display() {
+    assert_eq!(lc(1, 2).to_string(), "1:2");
+  }
+}
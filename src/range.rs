@@ -1,10 +1,13 @@
 //! Utilities for working with byte ranges.
 
+use std::ops::{Deref, DerefMut};
+
 /// See [`WithRange::range`].
 pub type Range = std::ops::Range<usize>;
 
 /// Associate a data with a byte range.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WithRange<Data> {
   /// The original data.
   pub data: Data,
@@ -22,6 +25,55 @@ pub struct WithRange<Data> {
   pub range: Range,
 }
 
+/// A value with the byte range of the digested text.
+/// See [`Combinator::ranged`](crate::combinator::Combinator::ranged).
+///
+/// Unlike [`WithRange`], this implements [`Deref`] and [`DerefMut`]
+/// so the value can be accessed directly.
+/// # Example
+/// ```
+/// # use whitehole::range::Ranged;
+/// let value = Ranged {
+///   value: "abc".to_string(),
+///   range: 0..3,
+/// };
+/// assert_eq!(value.len(), 3);
+/// assert_eq!(value.range, 0..3);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ranged<Value> {
+  /// The original value.
+  pub value: Value,
+  /// The byte range of the digested text.
+  /// This can be used to index a string.
+  pub range: Range,
+}
+
+impl<Value> Ranged<Value> {
+  /// Consume self, return the original value.
+  #[inline]
+  pub fn into_value(self) -> Value {
+    self.value
+  }
+}
+
+impl<Value> Deref for Ranged<Value> {
+  type Target = Value;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.value
+  }
+}
+
+impl<Value> DerefMut for Ranged<Value> {
+  #[inline]
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.value
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -36,4 +88,33 @@ mod tests {
     // ensure the range can be used to index a string
     assert_eq!(&"0123456"[value.range], "01234");
   }
+
+  #[test]
+  fn test_ranged() {
+    let mut value = Ranged {
+      value: vec![1],
+      range: 0..5,
+    };
+    // deref
+    assert_eq!(value.len(), 1);
+    value.push(2);
+    assert_eq!(*value, [1, 2]);
+    assert_eq!(&"0123456"[value.range.clone()], "01234");
+    assert_eq!(value.into_value(), [1, 2]);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_ranged_serde() {
+    let value = Ranged {
+      value: "a".to_string(),
+      range: 1..2,
+    };
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"value":"a","range":{"start":1,"end":2}}"#);
+    assert_eq!(
+      serde_json::from_str::<Ranged<String>>(&json).unwrap(),
+      value
+    );
+  }
 }
@@ -0,0 +1,470 @@
+//! Match suffixes from right to left.
+//!
+//! Sometimes the interesting structure is at the end of the text
+//! (file extensions, unit suffixes, trailing comments, etc),
+//! and parsing from the left requires parsing everything before it.
+//!
+//! This module provides a small, self-contained set of reverse combinators
+//! which digest the text from the right edge of the [`RInstant`]:
+//! - [`reat`]: match a literal ending at the right edge.
+//! - [`rtake`]: take the last `n` chars.
+//! - [`rtake_while`]: take chars from the right while a predicate is satisfied.
+//!
+//! To match bytes, see the [`bytes`] module for the combinators with the same name.
+//!
+//! Reverse combinators can be composed with `+` and `|` like [`Combinator`](crate::combinator::Combinator)s.
+//! Operands of `+` are written in the reading order (left to right),
+//! but executed from right to left, e.g. in `reat("a") + reat("b")`,
+//! `reat("b")` is executed first and should match at the end of the text.
+//!
+//! Use [`RCombinator::match_suffix`] to execute the reverse combinator against a text.
+//! # Examples
+//! ```
+//! use whitehole::reverse::{reat, rtake_while};
+//!
+//! let ext = || reat('.') + rtake_while(|c| c.is_ascii_alphanumeric());
+//! let archive = ext().optional() + ext();
+//!
+//! let text = "backup-2024.01.tar.gz";
+//! let output = archive.match_suffix(text).unwrap();
+//! assert_eq!(&text[text.len() - output.digested..], ".tar.gz");
+//! ```
+
+pub mod bytes;
+mod provided;
+
+pub use provided::*;
+
+use crate::{
+  action::Output,
+  combinator::ops::add::Concat,
+  digest::Digest,
+  idx,
+  range::{Range, WithRange},
+};
+use std::{ops, ops::RangeTo, slice::SliceIndex};
+
+/// The parsing progress of reverse combinators.
+/// The undigested text is [`RInstant::rest`], which is shrinking from the right.
+#[derive(Debug, Clone, Copy)]
+pub struct RInstant<TextRef> {
+  text: TextRef,
+  end: usize,
+}
+
+impl<'text, Text: ?Sized> RInstant<&'text Text> {
+  /// The whole input text.
+  #[inline]
+  pub const fn text(&self) -> &'text Text {
+    self.text
+  }
+
+  /// The right edge of the undigested text, in bytes.
+  #[inline]
+  pub const fn end(&self) -> usize {
+    self.end
+  }
+
+  /// Digest `n` more bytes from the right.
+  /// # Safety
+  /// `n` should not be greater than [`Self::end`]
+  /// and `end - n` should be a valid boundary according to [`Digest::validate`].
+  #[inline]
+  pub const unsafe fn to_digested_unchecked(&self, n: usize) -> Self {
+    Self {
+      text: self.text,
+      end: idx::sub(self.end, n),
+    }
+  }
+}
+
+impl<'text, Text: ?Sized + Digest> RInstant<&'text Text> {
+  /// Create a new instance with the whole `text` undigested.
+  #[inline]
+  pub fn new(text: &'text Text) -> Self {
+    Self {
+      end: text.len(),
+      text,
+    }
+  }
+
+  /// How many bytes are digested from the right.
+  #[inline]
+  pub fn digested(&self) -> usize {
+    self.text.len() - self.end
+  }
+
+  /// The undigested text, i.e. `text[..end]`.
+  #[inline]
+  pub fn rest(&self) -> &'text Text
+  where
+    RangeTo<usize>: SliceIndex<Text, Output = Text>,
+  {
+    // SAFETY: `end` is always valid for `text`
+    idx::unchecked!(idx::slice_to(self.text, self.end))
+  }
+}
+
+/// The basic building block of reverse matching.
+/// See the [module level documentation](self) for more information.
+/// # Safety
+/// [`Output::digested`] is counted from the right edge of the [`RInstant`].
+/// It should not be greater than [`RInstant::end`],
+/// and `end - digested` should be a valid boundary according to [`Digest::validate`].
+pub unsafe trait RAction {
+  /// See [`RInstant::text`].
+  type Text: ?Sized;
+  /// See [`Output::value`].
+  type Value;
+
+  /// Try to digest some bytes from the right of [`RInstant::rest`].
+  /// Return [`None`] to reject.
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>>;
+}
+
+unsafe impl<T: RAction + ?Sized> RAction for &T {
+  type Text = T::Text;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    (**self).exec(instant)
+  }
+}
+
+/// Wrap an [`RAction`] to provide decorators and operator overloading.
+#[derive(Debug, Clone, Copy)]
+pub struct RCombinator<T> {
+  pub action: T,
+}
+
+impl<T> RCombinator<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(action: T) -> Self {
+    Self { action }
+  }
+}
+
+unsafe impl<T: RAction> RAction for RCombinator<T> {
+  type Text = T::Text;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    self.action.exec(instant)
+  }
+}
+
+impl<T: RAction<Text: Digest>> RCombinator<T> {
+  /// Execute against the end of the `text`.
+  /// [`Output::digested`] is the number of matched bytes from the end.
+  /// The rest of the text is never touched.
+  #[inline]
+  pub fn match_suffix(&self, text: &T::Text) -> Option<Output<T::Value>> {
+    self.action.exec(&RInstant::new(text))
+  }
+}
+
+/// See [`RCombinator::optional`].
+#[derive(Debug, Clone, Copy)]
+pub struct ROptional<T> {
+  action: T,
+}
+
+unsafe impl<T: RAction<Value: Default>> RAction for ROptional<T> {
+  type Text = T::Text;
+  type Value = T::Value;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    Some(self.action.exec(instant).unwrap_or_else(|| Output {
+      value: Default::default(),
+      digested: 0,
+    }))
+  }
+}
+
+/// See [`RCombinator::tuple`].
+#[derive(Debug, Clone, Copy)]
+pub struct RTuple<T> {
+  action: T,
+}
+
+unsafe impl<T: RAction> RAction for RTuple<T> {
+  type Text = T::Text;
+  type Value = (T::Value,);
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    self.action.exec(instant).map(|output| output.map(|v| (v,)))
+  }
+}
+
+/// See [`RCombinator::map`].
+#[derive(Clone, Copy)]
+pub struct RMap<T, F> {
+  action: T,
+  mapper: F,
+}
+
+impl<T: std::fmt::Debug, F> std::fmt::Debug for RMap<T, F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("RMap")
+      .field("action", &self.action)
+      .finish()
+  }
+}
+
+unsafe impl<NewValue, T: RAction, F: Fn(T::Value) -> NewValue> RAction for RMap<T, F> {
+  type Text = T::Text;
+  type Value = NewValue;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    self.action.exec(instant).map(|o| o.map(&self.mapper))
+  }
+}
+
+/// See [`RCombinator::range`].
+#[derive(Debug, Clone, Copy)]
+pub struct RRange<T> {
+  action: T,
+}
+
+unsafe impl<T: RAction> RAction for RRange<T> {
+  type Text = T::Text;
+  type Value = WithRange<T::Value>;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    self.action.exec(instant).map(|output| Output {
+      digested: output.digested,
+      value: WithRange {
+        // SAFETY: `digested` is not greater than `end`, guaranteed by `RAction`
+        range: idx::unchecked!(idx::sub(instant.end(), output.digested))..instant.end(),
+        data: output.value,
+      },
+    })
+  }
+}
+
+impl<T> RCombinator<T> {
+  /// Make the combinator optional.
+  /// The value will be [`Default::default`] with 0 bytes digested if rejected.
+  #[inline]
+  pub fn optional(self) -> RCombinator<ROptional<T>> {
+    RCombinator::new(ROptional {
+      action: self.action,
+    })
+  }
+
+  /// Convert the value with the `mapper`.
+  #[inline]
+  pub fn map<NewValue, F: Fn(T::Value) -> NewValue>(self, mapper: F) -> RCombinator<RMap<T, F>>
+  where
+    T: RAction,
+  {
+    RCombinator::new(RMap {
+      action: self.action,
+      mapper,
+    })
+  }
+
+  /// Set the value to a clone-able value.
+  #[inline]
+  pub fn bind<NewValue: Clone>(
+    self,
+    value: NewValue,
+  ) -> RCombinator<RMap<T, impl Fn(T::Value) -> NewValue>>
+  where
+    T: RAction,
+  {
+    self.map(move |_| value.clone())
+  }
+
+  /// Wrap the value in an one-element tuple, so it can be [`Concat`]-ed by `+`.
+  #[inline]
+  pub fn tuple(self) -> RCombinator<RTuple<T>> {
+    RCombinator::new(RTuple {
+      action: self.action,
+    })
+  }
+
+  /// Wrap the value in a [`WithRange`] with the byte range in the whole text.
+  #[inline]
+  pub fn range(self) -> RCombinator<RRange<T>> {
+    RCombinator::new(RRange {
+      action: self.action,
+    })
+  }
+}
+
+/// An [`RAction`] created by the `+` operator.
+/// The right-hand side is executed first.
+/// See the [module level documentation](self) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct RAdd<Lhs, Rhs> {
+  lhs: Lhs,
+  rhs: Rhs,
+}
+
+impl<Lhs, Rhs> ops::Add<RCombinator<Rhs>> for RCombinator<Lhs> {
+  type Output = RCombinator<RAdd<Lhs, Rhs>>;
+
+  #[inline]
+  fn add(self, rhs: RCombinator<Rhs>) -> Self::Output {
+    RCombinator::new(RAdd {
+      lhs: self.action,
+      rhs: rhs.action,
+    })
+  }
+}
+
+unsafe impl<Lhs: RAction<Value: Concat<Rhs::Value>>, Rhs: RAction<Text = Lhs::Text>> RAction
+  for RAdd<Lhs, Rhs>
+{
+  type Text = Lhs::Text;
+  type Value = <Lhs::Value as Concat<Rhs::Value>>::Output;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    let rhs = self.rhs.exec(instant)?;
+    let lhs = self
+      .lhs
+      .exec(&unsafe { instant.to_digested_unchecked(rhs.digested) })?;
+    Some(Output {
+      value: lhs.value.concat(rhs.value),
+      // SAFETY: both are inside the rest, whose length is a valid usize
+      digested: idx::unchecked!(idx::add(lhs.digested, rhs.digested)),
+    })
+  }
+}
+
+/// An [`RAction`] created by the `|` operator.
+/// See the [module level documentation](self) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct ROr<Lhs, Rhs> {
+  lhs: Lhs,
+  rhs: Rhs,
+}
+
+impl<Lhs, Rhs> ops::BitOr<RCombinator<Rhs>> for RCombinator<Lhs> {
+  type Output = RCombinator<ROr<Lhs, Rhs>>;
+
+  #[inline]
+  fn bitor(self, rhs: RCombinator<Rhs>) -> Self::Output {
+    RCombinator::new(ROr {
+      lhs: self.action,
+      rhs: rhs.action,
+    })
+  }
+}
+
+unsafe impl<Lhs: RAction, Rhs: RAction<Text = Lhs::Text, Value = Lhs::Value>> RAction
+  for ROr<Lhs, Rhs>
+{
+  type Text = Lhs::Text;
+  type Value = Lhs::Value;
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    self.lhs.exec(instant).or_else(|| self.rhs.exec(instant))
+  }
+}
+
+/// Return the byte range of the matched suffix in the `text`.
+#[inline]
+pub fn suffix_range<Text: ?Sized + Digest, Value>(text: &Text, output: &Output<Value>) -> Range {
+  let len = text.len();
+  len - output.digested..len
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum Unit {
+    Kg,
+    G,
+  }
+
+  #[test]
+  fn reverse_instant() {
+    let instant = RInstant::new("abc");
+    assert_eq!(instant.text(), "abc");
+    assert_eq!(instant.end(), 3);
+    assert_eq!(instant.digested(), 0);
+    assert_eq!(instant.rest(), "abc");
+    let instant = unsafe { instant.to_digested_unchecked(2) };
+    assert_eq!(instant.end(), 1);
+    assert_eq!(instant.digested(), 2);
+    assert_eq!(instant.rest(), "a");
+  }
+
+  #[test]
+  fn reverse_file_extensions() {
+    let ext = || reat('.') + rtake_while(|c| c.is_ascii_alphanumeric());
+    let archive = ext().optional() + ext();
+
+    for (text, expected) in [
+      ("foo.tar.gz", Some(".tar.gz")),
+      ("foo.gz", Some(".gz")),
+      ("a.b.c.tar.gz", Some(".tar.gz")),
+      ("好.tar.gz", Some(".tar.gz")),
+      ("foo", None),
+    ] {
+      assert_eq!(
+        archive
+          .match_suffix(text)
+          .map(|o| &text[suffix_range(text, &o)]),
+        expected
+      );
+    }
+
+    // literal alternatives
+    let known = reat(".tar.gz") | reat(".tgz") | reat(".zip");
+    assert_eq!(known.match_suffix("a.tgz").map(|o| o.digested), Some(4));
+    assert_eq!(known.match_suffix("a.rar").map(|o| o.digested), None);
+  }
+
+  #[test]
+  fn reverse_unit_suffix() {
+    let unit = (reat("kg").bind(Unit::Kg) | reat("g").bind(Unit::G)).tuple();
+    let number = rtake_while(|c| c.is_ascii_digit() || c == '.').range();
+    let quantity = number.map(|r| r.range).tuple() + unit;
+
+    let text = "weight: 12.5kg";
+    let output = quantity.match_suffix(text).unwrap();
+    let (range, unit) = output.value;
+    assert_eq!(&text[range], "12.5");
+    assert_eq!(unit, Unit::Kg);
+    assert_eq!(output.digested, 6);
+
+    let text = "300g";
+    let output = quantity.match_suffix(text).unwrap();
+    assert_eq!(output.value, (0..3, Unit::G));
+    assert_eq!(output.digested, 4);
+
+    // no unit
+    assert!(quantity.match_suffix("300").is_none());
+  }
+
+  #[test]
+  fn reverse_add_executes_rhs_first() {
+    // `rtake(1)` is executed after `reat("c")`, so it takes "b"
+    let c = rtake(1).range().tuple() + reat('c').range().tuple();
+    let output = c.match_suffix("abc").unwrap();
+    assert_eq!(output.value.0.range, 1..2);
+    assert_eq!(output.value.1.range, 2..3);
+    assert_eq!(output.digested, 2);
+  }
+
+  fn _reverse_debug_clone_copy() {
+    let c = (reat('a') + rtake(1)).optional() | rtake_while(|_| true).map(|_| ());
+    let _ = format!("{:?}", c);
+    let _c = c;
+    let _c = c.clone();
+  }
+}
@@ -0,0 +1,208 @@
+//! Reverse combinators for bytes.
+//! See the [parent module](super) for more information.
+
+use super::{RAction, RCombinator, RInstant};
+use crate::action::Output;
+
+/// See [`reat`].
+#[derive(Debug, Clone, Copy)]
+pub struct REat<T> {
+  pattern: T,
+}
+
+impl<T> REat<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self { pattern }
+  }
+}
+
+unsafe impl RAction for REat<u8> {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    (instant.rest().last() == Some(&self.pattern)).then_some(Output {
+      value: (),
+      digested: 1,
+    })
+  }
+}
+
+unsafe impl RAction for REat<&[u8]> {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant.rest().ends_with(self.pattern).then_some(Output {
+      value: (),
+      digested: self.pattern.len(),
+    })
+  }
+}
+
+unsafe impl<const N: usize> RAction for REat<&[u8; N]> {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant.rest().ends_with(self.pattern).then_some(Output {
+      value: (),
+      digested: N,
+    })
+  }
+}
+
+unsafe impl RAction for REat<Vec<u8>> {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant.rest().ends_with(&self.pattern).then_some(Output {
+      value: (),
+      digested: self.pattern.len(),
+    })
+  }
+}
+
+/// Returns a reverse combinator to match a pattern ending at the right edge.
+///
+/// The pattern can be a [`u8`], a `&[u8]`, a `&[u8; N]` or a `Vec<u8>`.
+/// # Examples
+/// ```
+/// # use whitehole::reverse::bytes::reat;
+/// assert_eq!(reat(b".gz").match_suffix(b"a.tar.gz").unwrap().digested, 3);
+/// ```
+#[inline]
+pub const fn reat<T>(pattern: T) -> RCombinator<REat<T>> {
+  RCombinator::new(REat::new(pattern))
+}
+
+/// See [`rtake`].
+#[derive(Debug, Clone, Copy)]
+pub struct RTake {
+  n: usize,
+}
+
+impl RTake {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+unsafe impl RAction for RTake {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    (self.n <= instant.end()).then_some(Output {
+      value: (),
+      digested: self.n,
+    })
+  }
+}
+
+/// Returns a reverse combinator to take the last `n` undigested bytes.
+///
+/// The combinator will reject if there are not enough bytes.
+/// # Examples
+/// ```
+/// # use whitehole::reverse::bytes::rtake;
+/// assert_eq!(rtake(2).match_suffix(b"abc").unwrap().digested, 2);
+/// ```
+#[inline]
+pub const fn rtake(n: usize) -> RCombinator<RTake> {
+  RCombinator::new(RTake::new(n))
+}
+
+/// See [`rtake_while`].
+#[derive(Clone, Copy)]
+pub struct RTakeWhile<F> {
+  predicate: F,
+}
+
+impl<F> RTakeWhile<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F) -> Self {
+    Self { predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for RTakeWhile<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("RTakeWhile").finish()
+  }
+}
+
+unsafe impl<F: Fn(u8) -> bool> RAction for RTakeWhile<F> {
+  type Text = [u8];
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    Some(Output {
+      value: (),
+      digested: instant
+        .rest()
+        .iter()
+        .rev()
+        .take_while(|b| (self.predicate)(**b))
+        .count(),
+    })
+  }
+}
+
+/// Returns a reverse combinator to take bytes from the right
+/// while the `predicate` is satisfied.
+///
+/// The combinator will always accept, maybe with 0 bytes digested.
+/// # Examples
+/// ```
+/// # use whitehole::reverse::bytes::rtake_while;
+/// let c = rtake_while(|b| b.is_ascii_digit());
+/// assert_eq!(c.match_suffix(b"abc123").unwrap().digested, 3);
+/// ```
+#[inline]
+pub const fn rtake_while<F: Fn(u8) -> bool>(predicate: F) -> RCombinator<RTakeWhile<F>> {
+  RCombinator::new(RTakeWhile::new(predicate))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn exec(action: impl RAction<Text = [u8], Value = ()>, text: &[u8]) -> Option<usize> {
+    action.exec(&RInstant::new(text)).map(|o| o.digested)
+  }
+
+  #[test]
+  fn reverse_bytes_eat() {
+    assert_eq!(exec(reat(b'c'), b"abc"), Some(1));
+    assert_eq!(exec(reat(b'a'), b"abc"), None);
+    assert_eq!(exec(reat(b"bc"), b"abc"), Some(2));
+    assert_eq!(exec(reat(b"bc".as_slice()), b"abc"), Some(2));
+    assert_eq!(exec(reat(b"bc".to_vec()), b"abc"), Some(2));
+    assert_eq!(exec(reat(b"abcd"), b"abc"), None);
+  }
+
+  #[test]
+  fn reverse_bytes_take() {
+    assert_eq!(exec(rtake(2), b"abc"), Some(2));
+    assert_eq!(exec(rtake(4), b"abc"), None);
+    assert_eq!(exec(rtake_while(|b| b == b'c'), b"abcc"), Some(2));
+    assert_eq!(exec(rtake_while(|b| b == b'x'), b"abc"), Some(0));
+
+    // composition
+    let c = reat(b'.') + rtake_while(|b| b.is_ascii_alphanumeric());
+    assert_eq!(c.match_suffix(b"a.tar.gz").map(|o| o.digested), Some(3));
+  }
+}
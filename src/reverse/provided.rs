@@ -0,0 +1,213 @@
+use super::{RAction, RCombinator, RInstant};
+use crate::action::Output;
+use crate::idx;
+
+/// See [`reat`].
+#[derive(Debug, Clone, Copy)]
+pub struct REat<T> {
+  pattern: T,
+}
+
+impl<T> REat<T> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(pattern: T) -> Self {
+    Self { pattern }
+  }
+}
+
+unsafe impl RAction for REat<char> {
+  type Text = str;
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant.rest().ends_with(self.pattern).then_some(Output {
+      value: (),
+      digested: self.pattern.len_utf8(),
+    })
+  }
+}
+
+unsafe impl RAction for REat<&str> {
+  type Text = str;
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant.rest().ends_with(self.pattern).then_some(Output {
+      value: (),
+      digested: self.pattern.len(),
+    })
+  }
+}
+
+unsafe impl RAction for REat<String> {
+  type Text = str;
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    instant
+      .rest()
+      .ends_with(self.pattern.as_str())
+      .then_some(Output {
+        value: (),
+        digested: self.pattern.len(),
+      })
+  }
+}
+
+/// Returns a reverse combinator to match a pattern ending at the right edge.
+///
+/// The pattern can be a [`char`], a `&str` or a [`String`].
+/// # Examples
+/// ```
+/// # use whitehole::reverse::reat;
+/// assert_eq!(reat(".gz").match_suffix("a.tar.gz").unwrap().digested, 3);
+/// ```
+#[inline]
+pub const fn reat<T>(pattern: T) -> RCombinator<REat<T>> {
+  RCombinator::new(REat::new(pattern))
+}
+
+/// See [`rtake`].
+#[derive(Debug, Clone, Copy)]
+pub struct RTake {
+  n: usize,
+}
+
+impl RTake {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(n: usize) -> Self {
+    Self { n }
+  }
+}
+
+unsafe impl RAction for RTake {
+  type Text = str;
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    let mut digested: usize = 0;
+    let mut chars = instant.rest().chars().rev();
+    for _ in 0..self.n {
+      // reject if there are not enough chars
+      // SAFETY: the digested chars are inside the rest, whose length is a valid usize
+      digested = idx::unchecked!(idx::add(digested, chars.next()?.len_utf8()));
+    }
+    Some(Output {
+      value: (),
+      digested,
+    })
+  }
+}
+
+/// Returns a reverse combinator to take the last `n` undigested [`char`]s.
+///
+/// The combinator will reject if there are not enough chars.
+/// # Examples
+/// ```
+/// # use whitehole::reverse::rtake;
+/// assert_eq!(rtake(1).match_suffix("a好").unwrap().digested, 3);
+/// ```
+#[inline]
+pub const fn rtake(n: usize) -> RCombinator<RTake> {
+  RCombinator::new(RTake::new(n))
+}
+
+/// See [`rtake_while`].
+#[derive(Clone, Copy)]
+pub struct RTakeWhile<F> {
+  predicate: F,
+}
+
+impl<F> RTakeWhile<F> {
+  /// Create a new instance.
+  #[inline]
+  pub const fn new(predicate: F) -> Self {
+    Self { predicate }
+  }
+}
+
+impl<F> std::fmt::Debug for RTakeWhile<F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("RTakeWhile").finish()
+  }
+}
+
+unsafe impl<F: Fn(char) -> bool> RAction for RTakeWhile<F> {
+  type Text = str;
+  type Value = ();
+
+  #[inline]
+  fn exec(&self, instant: &RInstant<&Self::Text>) -> Option<Output<Self::Value>> {
+    Some(Output {
+      value: (),
+      digested: instant
+        .rest()
+        .chars()
+        .rev()
+        .take_while(|c| (self.predicate)(*c))
+        .map(char::len_utf8)
+        .sum(),
+    })
+  }
+}
+
+/// Returns a reverse combinator to take [`char`]s from the right
+/// while the `predicate` is satisfied.
+///
+/// The combinator will always accept, maybe with 0 bytes digested.
+/// # Examples
+/// ```
+/// # use whitehole::reverse::rtake_while;
+/// let c = rtake_while(|c| c.is_ascii_digit());
+/// assert_eq!(c.match_suffix("abc123").unwrap().digested, 3);
+/// assert_eq!(c.match_suffix("abc").unwrap().digested, 0);
+/// ```
+#[inline]
+pub const fn rtake_while<F: Fn(char) -> bool>(predicate: F) -> RCombinator<RTakeWhile<F>> {
+  RCombinator::new(RTakeWhile::new(predicate))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn exec(action: impl RAction<Text = str, Value = ()>, text: &str) -> Option<usize> {
+    action.exec(&RInstant::new(text)).map(|o| o.digested)
+  }
+
+  #[test]
+  fn reverse_eat() {
+    assert_eq!(exec(reat('c'), "abc"), Some(1));
+    assert_eq!(exec(reat('好'), "a好"), Some(3));
+    assert_eq!(exec(reat('a'), "abc"), None);
+    assert_eq!(exec(reat("bc"), "abc"), Some(2));
+    assert_eq!(exec(reat("bc".to_string()), "abc"), Some(2));
+    assert_eq!(exec(reat("ab"), "abc"), None);
+    assert_eq!(exec(reat("abcd"), "abc"), None);
+    // only the rest is checked
+    let instant = unsafe { RInstant::new("abc").to_digested_unchecked(1) };
+    assert_eq!(reat('b').exec(&instant).map(|o| o.digested), Some(1));
+  }
+
+  #[test]
+  fn reverse_take() {
+    assert_eq!(exec(rtake(2), "abc"), Some(2));
+    assert_eq!(exec(rtake(3), "abc"), Some(3));
+    assert_eq!(exec(rtake(4), "abc"), None);
+    assert_eq!(exec(rtake(0), ""), Some(0));
+    assert_eq!(exec(rtake(2), "a好好"), Some(6));
+  }
+
+  #[test]
+  fn reverse_take_while() {
+    assert_eq!(exec(rtake_while(|c| c.is_ascii_digit()), "a12"), Some(2));
+    assert_eq!(exec(rtake_while(|c| c.is_ascii_digit()), "a"), Some(0));
+    assert_eq!(exec(rtake_while(|c| c == '好'), "a好好"), Some(6));
+  }
+}
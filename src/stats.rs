@@ -0,0 +1,128 @@
+//! Profile which branch of a `|` chain is accepted.
+//!
+//! The order of a `|` chain is fixed at compile time,
+//! but the distribution of the accepted branches depends on the actual workload.
+//! Since the first accepted branch wins, putting the hottest branches first
+//! (when the grammar allows) avoids executing the rejected branches.
+//!
+//! Counting is opt-in: mark branches with [`Combinator::counted`](crate::combinator::Combinator::counted)
+//! and use [`BranchStats`] as (or inside) the `Heap` of the parser.
+//! Un-marked combinators pay nothing.
+//!
+//! ```
+//! use whitehole::{
+//!   combinator::{contextual, Combinator},
+//!   parser::Parser,
+//!   stats::BranchStats,
+//! };
+//!
+//! contextual!((), BranchStats);
+//!
+//! # fn main() {
+//! let entry = eat(' ').counted(0)
+//!   | next(|c| c.is_ascii_digit()).counted(1)
+//!   | next(|c| c.is_ascii_alphabetic()).counted(2);
+//!
+//! let mut parser = Parser::builder()
+//!   .heap(BranchStats::new())
+//!   .entry(entry)
+//!   .build("a b c 1");
+//! for _ in &mut parser {}
+//!
+//! assert_eq!(parser.branch_stats().counts(), &[3, 1, 3]);
+//! // the most frequently accepted branches first
+//! assert_eq!(parser.branch_stats().hot_first(), vec![0, 2, 1]);
+//! # }
+//! ```
+//!
+//! To act on the stats, collect them on a representative workload,
+//! then reorder the `|` chain in the source code according to [`BranchStats::hot_first`].
+//! Only reorder branches that can't accept the same input,
+//! otherwise the result of the parser will change.
+//! Remove the counting when you are done since it has a small cost.
+
+/// Accept counts of branches marked by [`Combinator::counted`](crate::combinator::Combinator::counted),
+/// indexed by the branch index.
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchStats {
+  counts: Vec<usize>,
+}
+
+impl BranchStats {
+  /// Create a new instance with no branch counted.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { counts: Vec::new() }
+  }
+
+  /// Increase the accept count of the `branch`.
+  #[inline]
+  pub fn record(&mut self, branch: usize) {
+    if branch >= self.counts.len() {
+      self.counts.resize(branch + 1, 0);
+    }
+    self.counts[branch] += 1;
+  }
+
+  /// Accept counts indexed by the branch index.
+  /// Branches with a greater index than any recorded branch are omitted.
+  #[inline]
+  pub fn counts(&self) -> &[usize] {
+    &self.counts
+  }
+
+  /// The accept count of the `branch`.
+  #[inline]
+  pub fn count(&self, branch: usize) -> usize {
+    self.counts.get(branch).copied().unwrap_or(0)
+  }
+
+  /// The sum of all accept counts.
+  #[inline]
+  pub fn total(&self) -> usize {
+    self.counts.iter().sum()
+  }
+
+  /// Branch indices sorted by the accept count in descending order.
+  /// Branches with the same count keep their original order.
+  pub fn hot_first(&self) -> Vec<usize> {
+    let mut indices: Vec<_> = (0..self.counts.len()).collect();
+    indices.sort_by(|a, b| self.counts[*b].cmp(&self.counts[*a]));
+    indices
+  }
+
+  /// Reset all counts, e.g. between documents.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.counts.clear();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn branch_stats() {
+    let mut stats = BranchStats::new();
+    assert_eq!(stats.counts(), &[] as &[usize]);
+    assert_eq!(stats.count(1), 0);
+
+    stats.record(2);
+    stats.record(0);
+    stats.record(2);
+    assert_eq!(stats.counts(), &[1, 0, 2]);
+    assert_eq!(stats.count(2), 2);
+    assert_eq!(stats.count(3), 0);
+    assert_eq!(stats.total(), 3);
+    assert_eq!(stats.hot_first(), vec![2, 0, 1]);
+
+    // stable
+    stats.record(1);
+    assert_eq!(stats.hot_first(), vec![2, 0, 1]);
+
+    stats.clear();
+    assert_eq!(stats.total(), 0);
+  }
+}
@@ -0,0 +1,733 @@
+//! Utilities to test grammars.
+//!
+//! # Compare Grammars
+//!
+//! When refactoring a grammar (e.g. reordering an `|` chain),
+//! use [`compare`] or [`assert_equivalent!`](crate::assert_equivalent)
+//! to make sure the outputs are unchanged across a corpus.
+//!
+//! ```
+//! use whitehole::{assert_equivalent, combinator::{eat, next}};
+//!
+//! let before = eat("a") | eat("b") | eat("c");
+//! let after = next(|c| matches!(c, 'a'..='c'));
+//! assert_equivalent!(before, after, ["abc", "cab", "abx", ""]);
+//! ```
+//!
+//! # Tiling
+//!
+//! Use [`check_tiling`] or [`check_tiling_by`] to verify that outputs cover
+//! every byte of the text exactly once, without gaps or overlaps.
+//! This is useful for lexers where completeness matters.
+//!
+//! ```
+//! use whitehole::{combinator::{eat, next}, parser::Parser, testing::assert_tiling};
+//!
+//! let entry = eat(' ') | (next(|c| c.is_ascii_alphabetic()) * (1..));
+//! assert_tiling(Parser::builder().entry(entry).build("ab cd"));
+//! ```
+
+// this module only uses the `idx` helpers, which are safe with the `safe` feature
+#![cfg_attr(all(feature = "safe", not(test)), deny(unsafe_code))]
+
+use crate::{
+  action::{Action, Output},
+  combinator::{preview, FormatUndigested},
+  digest::Digest,
+  idx,
+  parser::Parser,
+  range::Range,
+};
+use std::{
+  fmt::{self, Debug, Display},
+  ops::{RangeFrom, RangeTo},
+  slice::SliceIndex,
+};
+
+/// The first difference between the outputs of two entries on a text.
+/// See [`compare`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+  /// The index of the text in the corpus.
+  pub text: usize,
+  /// The index of the output in the text.
+  pub index: usize,
+  /// The byte offset where the diverged outputs start.
+  pub offset: usize,
+  /// The output of the entry `a`, with the value formatted by [`Debug`].
+  /// [`None`] if the entry rejected.
+  pub a: Option<Output<String>>,
+  /// The output of the entry `b`, with the value formatted by [`Debug`].
+  /// [`None`] if the entry rejected.
+  pub b: Option<Output<String>>,
+  /// The undigested text at [`Self::offset`], formatted by [`Debug`]
+  /// and truncated by [`LOG_UNDIGESTED_MAX_LEN`](crate::combinator::LOG_UNDIGESTED_MAX_LEN).
+  pub preview: String,
+}
+
+impl Display for Divergence {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn output(f: &mut fmt::Formatter<'_>, output: &Option<Output<String>>) -> fmt::Result {
+      match output {
+        Some(output) => write!(
+          f,
+          "accepted {} with {} bytes digested",
+          output.value, output.digested
+        ),
+        None => f.write_str("rejected"),
+      }
+    }
+
+    write!(
+      f,
+      "text #{} output #{} at offset {}: a ",
+      self.text, self.index, self.offset
+    )?;
+    output(f, &self.a)?;
+    f.write_str(", b ")?;
+    output(f, &self.b)?;
+    write!(f, ", rest: {}", self.preview)
+  }
+}
+
+fn run<'text, Text, A, B>(
+  a: &A,
+  b: &B,
+  texts: impl IntoIterator<Item = &'text Text>,
+  eq: impl Fn(&A::Value, &B::Value) -> bool,
+  format_a: impl Fn(&A::Value) -> String,
+  format_b: impl Fn(&B::Value) -> String,
+) -> Vec<Divergence>
+where
+  Text: ?Sized + FormatUndigested + Debug + Digest + 'text,
+  A: Action<Text = Text, State: Default, Heap: Default>,
+  B: Action<Text = Text, State: Default, Heap: Default>,
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  let mut divergences = Vec::new();
+
+  for (i, text) in texts.into_iter().enumerate() {
+    let mut pa = Parser::builder()
+      .state(A::State::default())
+      .heap(A::Heap::default())
+      .entry(a)
+      .build(text);
+    let mut pb = Parser::builder()
+      .state(B::State::default())
+      .heap(B::Heap::default())
+      .entry(b)
+      .build(text);
+
+    for index in 0.. {
+      // the two parsers are always at the same offset here
+      let offset = pa.instant.digested();
+      let rest = pa.instant.rest();
+      match (pa.next(), pb.next()) {
+        (None, None) => break,
+        (Some(oa), Some(ob)) if oa.digested == ob.digested && eq(&oa.value, &ob.value) => {
+          if oa.digested == 0 {
+            // prevent infinite loops
+            break;
+          }
+        }
+        (oa, ob) => {
+          divergences.push(Divergence {
+            text: i,
+            index,
+            offset,
+            a: oa.map(|o| o.map(|v| format_a(&v))),
+            b: ob.map(|o| o.map(|v| format_b(&v))),
+            preview: preview(rest),
+          });
+          break;
+        }
+      }
+    }
+  }
+
+  divergences
+}
+
+/// Run both entries over each text and compare the sequences of [`Output`]s,
+/// return the first [`Divergence`] of each text.
+///
+/// The [`Output::digested`] and [`Output::value`] are compared,
+/// values are compared by [`PartialEq`].
+/// Use [`compare_by`] if the values differ intentionally.
+///
+/// Each entry is executed by a new [`Parser`] for each text,
+/// with the state and heap set to their [`Default`].
+/// The comparison of a text stops at the first divergence,
+/// when both entries reject,
+/// or when both entries accept with 0 bytes digested.
+///
+/// The text can be `str` or `[u8]`.
+/// # Examples
+/// ```
+/// use whitehole::{combinator::eat, testing::compare};
+///
+/// let a = eat("a") | eat("ab");
+/// let b = eat("ab") | eat("a");
+/// let divergences = compare(a, b, ["aa", "ab"]);
+/// assert_eq!(divergences.len(), 1);
+/// assert_eq!(divergences[0].text, 1);
+/// assert_eq!(divergences[0].offset, 0);
+/// ```
+pub fn compare<'text, Text, A, B>(
+  a: A,
+  b: B,
+  texts: impl IntoIterator<Item = &'text Text>,
+) -> Vec<Divergence>
+where
+  Text: ?Sized + FormatUndigested + Debug + Digest + 'text,
+  A: Action<Text = Text, State: Default, Heap: Default, Value: PartialEq<B::Value> + Debug>,
+  B: Action<Text = Text, State: Default, Heap: Default, Value: Debug>,
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  run(
+    &a,
+    &b,
+    texts,
+    |va, vb| va == vb,
+    |v| format!("{:?}", v),
+    |v| format!("{:?}", v),
+  )
+}
+
+/// Like [`compare`], but compare the values by the projections `fa` and `fb`.
+///
+/// This is useful when the values differ intentionally,
+/// e.g. a refactored grammar yields a different value type.
+/// The projected values are recorded in the [`Divergence`].
+/// # Examples
+/// ```
+/// use whitehole::{combinator::eat, testing::compare_by};
+///
+/// let a = eat("a").bind('a');
+/// let b = eat("a").bind("a");
+/// assert!(compare_by(a, b, ["aaa"], |c| c.to_string(), |s| s.to_string()).is_empty());
+/// ```
+pub fn compare_by<'text, Text, A, B, K>(
+  a: A,
+  b: B,
+  texts: impl IntoIterator<Item = &'text Text>,
+  fa: impl Fn(&A::Value) -> K,
+  fb: impl Fn(&B::Value) -> K,
+) -> Vec<Divergence>
+where
+  Text: ?Sized + FormatUndigested + Debug + Digest + 'text,
+  A: Action<Text = Text, State: Default, Heap: Default>,
+  B: Action<Text = Text, State: Default, Heap: Default>,
+  K: PartialEq + Debug,
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  run(
+    &a,
+    &b,
+    texts,
+    |va, vb| fa(va) == fb(vb),
+    |v| format!("{:?}", fa(v)),
+    |v| format!("{:?}", fb(v)),
+  )
+}
+
+/// Used by [`assert_equivalent`](crate::assert_equivalent).
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_no_divergence(divergences: Vec<Divergence>) {
+  if !divergences.is_empty() {
+    let lines = divergences
+      .iter()
+      .map(|d| d.to_string())
+      .collect::<Vec<_>>();
+    panic!("entries are not equivalent:\n{}", lines.join("\n"));
+  }
+}
+
+/// Assert two entries yield the same outputs over a corpus.
+/// Panic with all divergences if not.
+///
+/// - `assert_equivalent!(a, b, texts)` uses [`compare`](crate::testing::compare).
+/// - `assert_equivalent!(a, b, texts, fa, fb)` uses [`compare_by`](crate::testing::compare_by).
+/// # Examples
+/// ```
+/// use whitehole::{assert_equivalent, combinator::eat};
+///
+/// assert_equivalent!(eat("ab"), eat('a') + eat('b'), ["abab", "abc"]);
+/// assert_equivalent!(
+///   eat("a").bind(1u8),
+///   eat("a").bind(1u32),
+///   ["aa"],
+///   |v| *v as u32,
+///   |v| *v,
+/// );
+/// ```
+/// ```should_panic
+/// # use whitehole::{assert_equivalent, combinator::eat};
+/// assert_equivalent!(eat("ab"), eat('a'), ["ab"]);
+/// ```
+#[macro_export]
+macro_rules! assert_equivalent {
+  ($a:expr, $b:expr, $texts:expr $(,)?) => {
+    $crate::testing::__assert_no_divergence($crate::testing::compare($a, $b, $texts))
+  };
+  ($a:expr, $b:expr, $texts:expr, $fa:expr, $fb:expr $(,)?) => {
+    $crate::testing::__assert_no_divergence($crate::testing::compare_by($a, $b, $texts, $fa, $fb))
+  };
+}
+
+/// The first gap or overlap found by [`check_tiling`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TilingError {
+  /// Bytes in the range are not covered by any output.
+  Gap {
+    range: Range,
+    /// The text starting from the range, see [`Divergence::preview`].
+    preview: String,
+  },
+  /// Bytes in the range are covered by more than one output.
+  Overlap {
+    range: Range,
+    /// The text starting from the range, see [`Divergence::preview`].
+    preview: String,
+  },
+}
+
+impl TilingError {
+  /// The byte range of the gap or overlap.
+  #[inline]
+  pub const fn range(&self) -> &Range {
+    match self {
+      Self::Gap { range, .. } | Self::Overlap { range, .. } => range,
+    }
+  }
+}
+
+impl Display for TilingError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Gap { range, preview } => write!(f, "gap at {:?}, rest: {}", range, preview),
+      Self::Overlap { range, preview } => write!(f, "overlap at {:?}, rest: {}", range, preview),
+    }
+  }
+}
+
+fn tile<Text: ?Sized + FormatUndigested + Debug + Digest>(
+  text: &Text,
+  mut ranges: Vec<Range>,
+) -> Result<(), TilingError>
+where
+  RangeFrom<usize>: SliceIndex<Text, Output = Text>,
+  RangeTo<usize>: SliceIndex<Text, Output = Text>,
+{
+  let rest = |start: usize| {
+    text
+      .get(start..)
+      .map(preview)
+      .unwrap_or_else(|| "(invalid index)".into())
+  };
+
+  ranges.sort_by_key(|r| (r.start, r.end));
+
+  let mut covered = 0;
+  for range in ranges {
+    if range.start > covered {
+      return Err(TilingError::Gap {
+        range: covered..range.start,
+        preview: rest(covered),
+      });
+    }
+    if range.start < covered {
+      return Err(TilingError::Overlap {
+        range: range.start..covered.min(range.end),
+        preview: rest(range.start),
+      });
+    }
+    covered = range.end;
+  }
+
+  let len = text.len();
+  if covered < len {
+    return Err(TilingError::Gap {
+      range: covered..len,
+      preview: rest(covered),
+    });
+  }
+  // ranges beyond the end are overlapping nothing, but they are still wrong
+  if covered > len {
+    return Err(TilingError::Overlap {
+      range: len..covered,
+      preview: rest(len),
+    });
+  }
+  Ok(())
+}
+
+/// Iterate all outputs of the parser,
+/// check that the digested ranges are contiguous from `0` to the length of the text.
+///
+/// Since outputs of a [`Parser`] are always adjacent,
+/// this detects the parser stalling before the end of the text
+/// (e.g. a token forgot to consume a trailing quote so the next token can't be lexed).
+/// Use [`check_tiling_by`] to check ranges carried by the values.
+/// # Examples
+/// ```
+/// use whitehole::{combinator::{eat, next}, parser::Parser, testing::check_tiling};
+///
+/// let entry = (eat('"') + next(|c| c.is_ascii_alphabetic()) * (1..)) | eat(' ');
+/// let parser = Parser::builder().entry(entry).build("\"ab\" \"cd");
+/// assert_eq!(check_tiling(parser).unwrap_err().range(), &(3..8));
+/// ```
+pub fn check_tiling<T: Action<Text: FormatUndigested + Debug + Digest>>(
+  mut parser: Parser<'_, T>,
+) -> Result<(), TilingError>
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  let mut ranges = Vec::new();
+  loop {
+    let start = parser.instant.digested();
+    match parser.next() {
+      // prevent infinite loops
+      // SAFETY: the output is inside the text, whose length is a valid usize
+      Some(output) if output.digested != 0 => {
+        ranges.push(start..idx::unchecked!(idx::add(start, output.digested)))
+      }
+      _ => break,
+    }
+  }
+  tile(parser.instant.text(), ranges)
+}
+
+/// Iterate all outputs of the parser,
+/// check that the ranges returned by `range` from the values,
+/// together with the trivia ranges returned by `trivia` after the iteration,
+/// are contiguous from `0` to the length of the text without overlapping.
+///
+/// This is useful when the entry skips trivia (e.g. whitespaces and comments)
+/// and records the trivia ranges somewhere (e.g. in [`Parser::heap`]),
+/// while the values of the tokens carry their own ranges
+/// (e.g. by [`Combinator::range`](crate::combinator::Combinator::range)).
+/// The iteration stops at the first output with 0 bytes digested.
+/// # Examples
+/// ```
+/// use whitehole::{
+///   combinator::{contextual, Combinator},
+///   parser::Parser,
+///   range::Range,
+///   testing::check_tiling_by,
+/// };
+///
+/// contextual!((), Vec<Range>);
+///
+/// # fn main() {
+/// let ws = eat(' ').then(|a| a.heap.push(a.range())) * (..);
+/// let ident = next(|c| c.is_ascii_alphabetic()) * (1..);
+/// let entry = ws + ident.range().tuple();
+/// let parser = Parser::builder().heap(vec![]).entry(entry).build(" ab  c");
+/// assert!(check_tiling_by(parser, |(v,)| v.range.clone(), |p| p.heap.clone()).is_ok());
+/// # }
+/// ```
+pub fn check_tiling_by<'text, T: Action<Text: FormatUndigested + Debug + Digest>, I>(
+  mut parser: Parser<'text, T>,
+  range: impl Fn(&T::Value) -> Range,
+  trivia: impl FnOnce(&Parser<'text, T>) -> I,
+) -> Result<(), TilingError>
+where
+  I: IntoIterator<Item = Range>,
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  let mut ranges = Vec::new();
+  for output in &mut parser {
+    ranges.push(range(&output.value));
+    if output.digested == 0 {
+      // prevent infinite loops
+      break;
+    }
+  }
+  ranges.extend(trivia(&parser));
+  tile(parser.instant.text(), ranges)
+}
+
+/// Like [`check_tiling`], but panic with the [`TilingError`].
+#[track_caller]
+pub fn assert_tiling<T: Action<Text: FormatUndigested + Debug + Digest>>(parser: Parser<'_, T>)
+where
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  if let Err(e) = check_tiling(parser) {
+    panic!("outputs don't tile the text: {}", e);
+  }
+}
+
+/// Like [`check_tiling_by`], but panic with the [`TilingError`].
+#[track_caller]
+pub fn assert_tiling_by<'text, T: Action<Text: FormatUndigested + Debug + Digest>, I>(
+  parser: Parser<'text, T>,
+  range: impl Fn(&T::Value) -> Range,
+  trivia: impl FnOnce(&Parser<'text, T>) -> I,
+) where
+  I: IntoIterator<Item = Range>,
+  RangeFrom<usize>: SliceIndex<T::Text, Output = T::Text>,
+  RangeTo<usize>: SliceIndex<T::Text, Output = T::Text>,
+{
+  if let Err(e) = check_tiling_by(parser, range, trivia) {
+    panic!("outputs don't tile the text: {}", e);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    combinator::{bytes, eat, next, Combinator, LOG_UNDIGESTED_MAX_LEN},
+    contextual,
+  };
+
+  #[test]
+  fn compare_identical() {
+    let a = eat("a").bind(1) | eat("b").bind(2);
+    assert_eq!(compare(a, a, ["abba", "", "abc", "c"]), vec![]);
+
+    let b = next(|c| c == 'a').bind(1) | next(|c| c == 'b').bind(2);
+    assert_eq!(compare(a, b, ["abba", "", "abc", "c"]), vec![]);
+    assert_equivalent!(a, b, ["abba", "", "abc", "c"]);
+  }
+
+  #[test]
+  fn compare_seeded_difference() {
+    let a = eat("a") | eat("b") | eat("c");
+    // forget 'c'
+    let b = eat("a") | eat("b");
+    assert_eq!(
+      compare(a, b, ["abc", "ab", "cab", "abbaXc"]),
+      vec![
+        Divergence {
+          text: 0,
+          index: 2,
+          offset: 2,
+          a: Some(Output {
+            value: "()".into(),
+            digested: 1
+          }),
+          b: None,
+          preview: "\"c\"".into(),
+        },
+        Divergence {
+          text: 2,
+          index: 0,
+          offset: 0,
+          a: Some(Output {
+            value: "()".into(),
+            digested: 1
+          }),
+          b: None,
+          preview: "\"cab\"".into(),
+        },
+      ]
+    );
+
+    // different digested
+    let d = compare(eat("a"), eat("a") * (1..), ["aab"]);
+    assert_eq!(d.len(), 1);
+    assert_eq!(
+      d[0].to_string(),
+      "text #0 output #0 at offset 0: a accepted () with 1 bytes digested, b accepted () with 2 bytes digested, rest: \"aab\""
+    );
+
+    // different values
+    let d = compare(eat("a").bind(1), eat("a").bind(2), ["a"]);
+    assert_eq!(d[0].a.as_ref().unwrap().value, "1");
+    assert_eq!(d[0].b.as_ref().unwrap().value, "2");
+  }
+
+  #[test]
+  fn compare_projection() {
+    let a = eat("a").select(|a| a.start());
+    let b = eat("a").select(|a| a.start().to_string());
+    assert_eq!(
+      compare_by(a, b, ["aaa"], |v| v.to_string(), |v| v.clone()),
+      vec![]
+    );
+    assert_equivalent!(a, b, ["aaa"], |v| v.to_string(), |v| v.clone());
+
+    let d = compare_by(a, b, ["aaa"], |v| v.to_string(), |_| "0".to_string());
+    assert_eq!(d.len(), 1);
+    assert_eq!(d[0].index, 1);
+    assert_eq!(d[0].offset, 1);
+    assert_eq!(d[0].a.as_ref().unwrap().value, "\"1\"");
+    assert_eq!(d[0].b.as_ref().unwrap().value, "\"0\"");
+  }
+
+  #[test]
+  fn compare_bytes() {
+    let a = bytes::eat(b'a') | bytes::eat(b'b');
+    let b = bytes::eat(b"a") | bytes::eat(b'b');
+    assert_eq!(compare(a, b, [b"ab" as &[u8], b"ba"]), vec![]);
+
+    let d = compare(a, bytes::eat(b'a'), [b"ab" as &[u8]]);
+    assert_eq!(d[0].offset, 1);
+    assert_eq!(d[0].preview, "[98]");
+  }
+
+  #[test]
+  fn compare_zero_digested() {
+    // no infinite loop
+    let a = eat("a") * (..);
+    assert_equivalent!(a, a, ["aa", "b"]);
+  }
+
+  #[test]
+  fn compare_state_heap() {
+    contextual!(usize, usize);
+    // each parser starts with the default state
+    let a = eat("a")
+      .prepare(|input| *input.state += 1)
+      .reject(|accepted| *accepted.state > 2)
+      .then(|accepted| *accepted.heap += 1);
+    assert_equivalent!(a, a, ["aaa", "aaa"]);
+  }
+
+  #[test]
+  fn compare_preview_truncated() {
+    LOG_UNDIGESTED_MAX_LEN.set(2);
+    let d = compare(eat("a"), eat("b"), ["a好好"]);
+    assert_eq!(d[0].preview, "\"a好\" (truncated)");
+    LOG_UNDIGESTED_MAX_LEN.set(100);
+  }
+
+  #[test]
+  #[should_panic(expected = "entries are not equivalent:\ntext #0 output #0 at offset 0")]
+  fn assert_equivalent_panic() {
+    assert_equivalent!(eat("a"), eat("b"), ["a"]);
+  }
+
+  fn lexer(
+    string: Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>>,
+  ) -> Combinator<impl Action<Text = str, State = (), Heap = (), Value = ()>> {
+    eat('(') | eat(')') | eat(' ') | (next(|c| c.is_ascii_alphabetic()) * (1..)) | string
+  }
+
+  #[test]
+  fn tiling_complete() {
+    let string = eat('"') + next(|c| c != '"') * (..) + '"';
+    let text = "(a \"b c\" (d))";
+    assert_eq!(
+      check_tiling(Parser::builder().entry(lexer(string)).build(text)),
+      Ok(())
+    );
+    assert_tiling(Parser::builder().entry(lexer(string)).build(text));
+    assert_tiling(Parser::builder().entry(lexer(string)).build(""));
+  }
+
+  #[test]
+  fn tiling_gap() {
+    // forget to consume the trailing quote
+    let string = eat('"') + next(|c| c.is_ascii_alphabetic()) * (1..);
+    let text = "(a \"bc\" d)";
+    assert_eq!(
+      check_tiling(Parser::builder().entry(lexer(string)).build(text)),
+      Err(TilingError::Gap {
+        range: 6..10,
+        preview: "\"\\\" d)\"".into()
+      })
+    );
+
+    // zero-length outputs stop the iteration
+    let entry = eat('a') * (..);
+    assert_eq!(
+      check_tiling(Parser::builder().entry(entry).build("aab"))
+        .unwrap_err()
+        .range(),
+      &(2..3)
+    );
+
+    // bytes
+    let entry = bytes::eat(b'a');
+    assert_eq!(
+      check_tiling(Parser::builder().entry(entry).build(b"ab" as &[u8])),
+      Err(TilingError::Gap {
+        range: 1..2,
+        preview: "[98]".into()
+      })
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "outputs don't tile the text: gap at 1..2")]
+  fn assert_tiling_panic() {
+    assert_tiling(Parser::builder().entry(eat('a')).build("ab"));
+  }
+
+  #[test]
+  fn tiling_trivia() {
+    contextual!((), Vec<Range>);
+    let ws = || eat(' ').then(|a| a.heap.push(a.range())) * (..);
+    let ident = || next(|c| c.is_ascii_alphabetic()) * (1..);
+
+    let parser = || {
+      Parser::builder()
+        .heap(vec![])
+        .entry(ws() + ident().range().tuple())
+        .build(" ab  c")
+    };
+    assert_eq!(
+      check_tiling_by(parser(), |(v,)| v.range.clone(), |p| p.heap.clone()),
+      Ok(())
+    );
+    assert_tiling_by(parser(), |(v,)| v.range.clone(), |p| p.heap.clone());
+    // without trivia
+    assert_eq!(
+      check_tiling_by(parser(), |(v,)| v.range.clone(), |_| []),
+      Err(TilingError::Gap {
+        range: 0..1,
+        preview: "\" ab  c\"".into()
+      })
+    );
+
+    // trivia is not recorded
+    let entry = ws() + eat(',') + ident().range().tuple();
+    let parser = Parser::builder().heap(vec![]).entry(entry).build("a ,b");
+    assert_eq!(
+      check_tiling_by(parser, |(v,)| v.range.clone(), |p| p.heap.clone())
+        .unwrap_err()
+        .range(),
+      &(0..4)
+    );
+    let entry = ws() + eat(',') + ident().range().tuple();
+    let parser = Parser::builder().heap(vec![]).entry(entry).build(" ,b");
+    assert_eq!(
+      check_tiling_by(parser, |(v,)| v.range.clone(), |p| p.heap.clone())
+        .unwrap_err()
+        .range(),
+      &(1..2)
+    );
+  }
+
+  #[test]
+  fn tiling_overlap() {
+    let entry = next(|c| c.is_ascii_alphabetic()).select(|a| a.start()..a.end() + 1);
+    let parser = Parser::builder().entry(entry).build("abc");
+    assert_eq!(
+      check_tiling_by(parser, |v| v.clone(), |_| []),
+      Err(TilingError::Overlap {
+        range: 1..2,
+        preview: "\"bc\"".into()
+      })
+    );
+    let entry = next(|c| c.is_ascii_alphabetic()).select(|a| a.start()..a.end() + 1);
+    let parser = Parser::builder().entry(entry).build("a");
+    assert_eq!(
+      check_tiling_by(parser, |v| v.clone(), |_| [])
+        .unwrap_err()
+        .to_string(),
+      "overlap at 1..2, rest: \"\""
+    );
+  }
+}
@@ -0,0 +1,232 @@
+//! Utilities to build custom combinators.
+//!
+//! # Char Map
+//!
+//! [`CharMap`] maps [`char`]s to values, with a dense table for ASCII chars
+//! and a [`HashMap`] for the others.
+//! This is useful to build dispatch logic on the next char
+//! (e.g. in [`wrap`](crate::combinator::wrap)), since most grammars are ASCII-heavy.
+//!
+//! ```
+//! use whitehole::{combinator::wrap, parser::Parser, utils::CharMap};
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! enum Punct { LParen, RParen, Arrow }
+//!
+//! let map: CharMap<Punct> = [('(', Punct::LParen), (')', Punct::RParen), ('→', Punct::Arrow)]
+//!   .into_iter()
+//!   .collect();
+//! let entry = wrap(move |input| {
+//!   let c = input.instant.rest().chars().next()?;
+//!   let punct = *map.get(c)?;
+//!   input.instant.accept(c.len_utf8()).map(|o| o.map(|_| punct))
+//! });
+//!
+//! let parser = Parser::builder().entry(entry).build("(→)");
+//! assert_eq!(parser.map(|o| o.value).collect::<Vec<_>>(), [Punct::LParen, Punct::Arrow, Punct::RParen]);
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+/// A map from [`char`] to `V`.
+///
+/// ASCII chars are stored in a dense table indexed by the char,
+/// so [`Self::get`] for ASCII chars is one (bounds-checked) load without hashing.
+/// Other chars fall back to a [`HashMap`].
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone)]
+pub struct CharMap<V> {
+  ascii: Box<[Option<V>; 128]>,
+  ascii_len: usize,
+  others: HashMap<char, V>,
+}
+
+impl<V> CharMap<V> {
+  /// Create an empty map.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      ascii: Box::new(std::array::from_fn(|_| None)),
+      ascii_len: 0,
+      others: HashMap::new(),
+    }
+  }
+
+  /// Insert a value for `c`.
+  /// Return the old value if `c` is already in the map.
+  pub fn insert(&mut self, c: char, value: V) -> Option<V> {
+    if c.is_ascii() {
+      let old = self.ascii[c as usize].replace(value);
+      if old.is_none() {
+        self.ascii_len += 1;
+      }
+      old
+    } else {
+      self.others.insert(c, value)
+    }
+  }
+
+  /// Remove the value of `c` and return it.
+  pub fn remove(&mut self, c: char) -> Option<V> {
+    if c.is_ascii() {
+      let old = self.ascii[c as usize].take();
+      if old.is_some() {
+        self.ascii_len -= 1;
+      }
+      old
+    } else {
+      self.others.remove(&c)
+    }
+  }
+
+  /// Get the value of `c`.
+  #[inline]
+  pub fn get(&self, c: char) -> Option<&V> {
+    if c.is_ascii() {
+      self.ascii[c as usize].as_ref()
+    } else {
+      self.others.get(&c)
+    }
+  }
+
+  /// Get the value of `c`, or `default` if `c` is not in the map.
+  #[inline]
+  pub fn get_or<'a>(&'a self, c: char, default: &'a V) -> &'a V {
+    self.get(c).unwrap_or(default)
+  }
+
+  /// Return `true` if `c` is in the map.
+  #[inline]
+  pub fn contains_key(&self, c: char) -> bool {
+    self.get(c).is_some()
+  }
+
+  /// The number of chars in the map.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.ascii_len + self.others.len()
+  }
+
+  /// Return `true` if the map is empty.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Iterate over the entries.
+  /// ASCII chars are yielded first in ascending order, then the others in arbitrary order.
+  pub fn iter(&self) -> impl Iterator<Item = (char, &V)> {
+    self
+      .ascii
+      .iter()
+      .enumerate()
+      .filter_map(|(i, v)| v.as_ref().map(|v| (i as u8 as char, v)))
+      .chain(self.others.iter().map(|(c, v)| (*c, v)))
+  }
+}
+
+impl<V> Default for CharMap<V> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V: fmt::Debug> fmt::Debug for CharMap<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<V> Extend<(char, V)> for CharMap<V> {
+  fn extend<I: IntoIterator<Item = (char, V)>>(&mut self, iter: I) {
+    for (c, v) in iter {
+      self.insert(c, v);
+    }
+  }
+}
+
+impl<V> FromIterator<(char, V)> for CharMap<V> {
+  fn from_iter<I: IntoIterator<Item = (char, V)>>(iter: I) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn char_map_ascii() {
+    let mut map: CharMap<u8> = ('a'..='z').zip(0..).collect();
+    assert_eq!(map.len(), 26);
+    assert_eq!(map.get('a'), Some(&0));
+    assert_eq!(map.get('z'), Some(&25));
+    assert_eq!(map.get('A'), None);
+    assert_eq!(map.get('\0'), None);
+    assert_eq!(map.get('\x7f'), None);
+    assert_eq!(*map.get_or('b', &255), 1);
+    assert_eq!(*map.get_or('-', &255), 255);
+
+    // replace
+    assert_eq!(map.insert('a', 100), Some(0));
+    assert_eq!(map.len(), 26);
+    assert_eq!(map.get('a'), Some(&100));
+
+    // boundaries of the table
+    map.insert('\0', 1);
+    map.insert('\x7f', 2);
+    assert_eq!(map.len(), 28);
+    assert_eq!(map.get('\0'), Some(&1));
+    assert_eq!(map.get('\x7f'), Some(&2));
+
+    // remove
+    assert_eq!(map.remove('\0'), Some(1));
+    assert_eq!(map.remove('\0'), None);
+    assert_eq!(map.len(), 27);
+    assert!(!map.contains_key('\0'));
+
+    // iterate in ascending order
+    assert_eq!(
+      map.iter().take(3).collect::<Vec<_>>(),
+      [('a', &100), ('b', &1), ('c', &2)]
+    );
+    assert_eq!(map.iter().last(), Some(('\x7f', &2)));
+  }
+
+  #[test]
+  fn char_map_mixed() {
+    let mut map = CharMap::new();
+    assert!(map.is_empty());
+    map.extend([
+      ('a', "ascii"),
+      ('ж', "cyrillic"),
+      ('好', "cjk"),
+      ('😀', "emoji"),
+    ]);
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get('a'), Some(&"ascii"));
+    assert_eq!(map.get('ж'), Some(&"cyrillic"));
+    assert_eq!(map.get('好'), Some(&"cjk"));
+    assert_eq!(map.get('😀'), Some(&"emoji"));
+    // chars with the same low byte are not confused
+    assert_eq!(map.get('\u{0161}'), None);
+    assert_eq!(map.get('\u{0100}'), None);
+    assert_eq!(map.get(char::MAX), None);
+    assert_eq!(*map.get_or('и', &"none"), "none");
+
+    assert_eq!(map.insert('ж', "zhe"), Some("cyrillic"));
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.remove('ж'), Some("zhe"));
+    assert_eq!(map.len(), 3);
+    assert!(!map.contains_key('ж'));
+
+    let mut entries = map.iter().collect::<Vec<_>>();
+    assert_eq!(entries[0], ('a', &"ascii"));
+    entries.sort();
+    assert_eq!(entries, [('a', &"ascii"), ('好', &"cjk"), ('😀', &"emoji")]);
+    assert_eq!(format!("{:?}", CharMap::from_iter([('a', 1)])), "{'a': 1}");
+  }
+}
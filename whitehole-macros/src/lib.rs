@@ -0,0 +1,317 @@
+//! Derive macros for [whitehole](https://docs.rs/whitehole).
+//!
+//! Enable the `derive` feature of `whitehole` to use them,
+//! they are re-exported in `whitehole::combinator`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+  parse_macro_input, Data, DeriveInput, Expr, Fields, GenericArgument, LitStr, PathArguments, Type,
+};
+
+/// Derive `FromCombinator` for a struct with named fields,
+/// and an inherent `combinator()` function with the same combinator.
+///
+/// The fields are parsed in declaration order (like `+`),
+/// then the struct is built from the field values.
+/// Each field is parsed by:
+///
+/// - `#[parse(with = "expr")]`: the combinator returned by the expression.
+/// - Otherwise, the `FromCombinator` implementation of the field type.
+///
+/// Some field types are handled specially,
+/// the combinator above is used to parse the inner type `T`:
+///
+/// - `Option<T>`: the combinator is made optional by `Combinator::opt`.
+/// - `Vec<T>`: the combinator is repeated for 0 or more times by `* (..)`,
+///   use `#[parse(sep = ",")]` to set a separator.
+///
+/// The `State` and `Heap` of the combinator are always `()`.
+/// # Examples
+/// ```
+/// use whitehole::{
+///   combinator::{eat, next, preceded, FromCombinator},
+///   parser::Parser,
+/// };
+///
+/// #[derive(FromCombinator, Debug, PartialEq)]
+/// struct Header {
+///   #[parse(with = r#"(next(|c| c.is_ascii_alphabetic()) * (1..)).select(|a| a.content().to_string())"#)]
+///   name: String,
+///   #[parse(with = "preceded(':', u16::combinator())")]
+///   port: Option<u16>,
+///   #[parse(with = "preceded(' ', u8::combinator())", sep = ",")]
+///   flags: Vec<u8>,
+/// }
+///
+/// let header = Parser::builder().entry(Header::combinator()).build("localhost:80 1, 2").next().unwrap().value;
+/// assert_eq!(
+///   header,
+///   Header { name: "localhost".into(), port: Some(80), flags: vec![1, 2] }
+/// );
+/// ```
+/// Only structs with named fields are supported.
+/// ```compile_fail
+/// # use whitehole::combinator::FromCombinator;
+/// #[derive(FromCombinator)]
+/// struct Point(u8, u8);
+/// ```
+/// `sep` is only allowed on `Vec` fields.
+/// ```compile_fail
+/// # use whitehole::combinator::FromCombinator;
+/// #[derive(FromCombinator)]
+/// struct Point {
+///   #[parse(sep = ",")]
+///   x: u8,
+/// }
+/// ```
+#[proc_macro_derive(FromCombinator, attributes(parse))]
+pub fn derive_from_combinator(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand(input)
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
+}
+
+/// The options from `#[parse(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldOptions {
+  with: Option<Expr>,
+  sep: Option<LitStr>,
+}
+
+impl FieldOptions {
+  fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+    let mut options = Self::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("parse")) {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("with") {
+          if options.with.is_some() {
+            return Err(meta.error("duplicate `with` attribute"));
+          }
+          options.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+          Ok(())
+        } else if meta.path.is_ident("sep") {
+          if options.sep.is_some() {
+            return Err(meta.error("duplicate `sep` attribute"));
+          }
+          options.sep = Some(meta.value()?.parse()?);
+          Ok(())
+        } else {
+          Err(meta.error("unknown parse attribute, expected `with` or `sep`"))
+        }
+      })?;
+    }
+    Ok(options)
+  }
+}
+
+/// The shape of a field type.
+enum Shape<'a> {
+  Plain(&'a Type),
+  Option(&'a Type),
+  Vec(&'a Type),
+}
+
+impl<'a> Shape<'a> {
+  fn of(ty: &'a Type) -> Self {
+    if let Type::Path(path) = ty {
+      if let (None, Some(segment)) = (&path.qself, path.path.segments.last()) {
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+          if let (1, Some(GenericArgument::Type(inner))) = (args.args.len(), args.args.first()) {
+            if segment.ident == "Option" {
+              return Shape::Option(inner);
+            }
+            if segment.ident == "Vec" {
+              return Shape::Vec(inner);
+            }
+          }
+        }
+      }
+    }
+    Shape::Plain(ty)
+  }
+
+  /// The type to be parsed by the item combinator.
+  fn item(&self) -> &'a Type {
+    match self {
+      Shape::Plain(ty) | Shape::Option(ty) | Shape::Vec(ty) => ty,
+    }
+  }
+}
+
+/// Build the combinator expression of a field.
+fn field_combinator(field: &syn::Field) -> syn::Result<TokenStream2> {
+  let options = FieldOptions::from_attrs(&field.attrs)?;
+  let shape = Shape::of(&field.ty);
+
+  let item = shape.item();
+  let combinator = match &options.with {
+    Some(expr) => quote!((#expr)),
+    None => quote!(<#item as ::whitehole::combinator::FromCombinator>::combinator()),
+  };
+
+  Ok(match shape {
+    Shape::Plain(_) | Shape::Option(_) if options.sep.is_some() => {
+      return Err(syn::Error::new_spanned(
+        options.sep,
+        "`sep` is only allowed on `Vec` fields",
+      ))
+    }
+    Shape::Plain(_) => combinator,
+    Shape::Option(_) => quote!(#combinator.opt()),
+    Shape::Vec(inner) => {
+      let mut repeated = quote!((#combinator * (..)));
+      if let Some(sep) = &options.sep {
+        repeated = quote!(#repeated.sep(#sep));
+      }
+      quote!(#repeated.fold(
+        ::std::vec::Vec::new,
+        |mut acc: ::std::vec::Vec<#inner>, value: #inner| {
+          acc.push(value);
+          acc
+        },
+      ))
+    }
+  })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let fields = match &input.data {
+    Data::Struct(data) => match &data.fields {
+      Fields::Named(fields) => &fields.named,
+      _ => {
+        return Err(syn::Error::new_spanned(
+          &input.ident,
+          "`FromCombinator` can only be derived for structs with named fields",
+        ))
+      }
+    },
+    _ => {
+      return Err(syn::Error::new_spanned(
+        &input.ident,
+        "`FromCombinator` can only be derived for structs",
+      ))
+    }
+  };
+  if fields.is_empty() {
+    return Err(syn::Error::new_spanned(
+      &input.ident,
+      "`FromCombinator` requires at least one field",
+    ));
+  }
+
+  // chain the fields by `pair`, so the value is a nested tuple like `((a, b), c)`
+  let mut combinator = TokenStream2::new();
+  let mut pattern = TokenStream2::new();
+  for (i, field) in fields.iter().enumerate() {
+    let field_combinator = field_combinator(field)?;
+    let ident = &field.ident;
+    if i == 0 {
+      combinator = field_combinator;
+      pattern = quote!(#ident);
+    } else {
+      combinator = quote!(#combinator.pair(#field_combinator));
+      pattern = quote!((#pattern, #ident));
+    }
+  }
+  let idents = fields.iter().map(|field| &field.ident);
+
+  let name = &input.ident;
+  let vis = &input.vis;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let doc = format!("Returns a combinator to parse [`{}`] field by field.", name);
+  Ok(quote! {
+    impl #impl_generics #name #ty_generics #where_clause {
+      #[doc = #doc]
+      #vis fn combinator() -> ::whitehole::combinator::Combinator<
+        impl ::whitehole::action::Action<Text = str, State = (), Heap = (), Value = Self>,
+      > {
+        #combinator.map(|#pattern| Self { #(#idents),* })
+      }
+    }
+
+    impl #impl_generics ::whitehole::combinator::FromCombinator for #name #ty_generics #where_clause {
+      #[inline]
+      fn combinator() -> ::whitehole::combinator::Combinator<
+        impl ::whitehole::action::Action<Text = str, State = (), Heap = (), Value = Self>,
+      > {
+        #name::combinator()
+      }
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn error(input: &str) -> String {
+    expand(syn::parse_str(input).unwrap())
+      .unwrap_err()
+      .to_string()
+  }
+
+  #[test]
+  fn expand_errors() {
+    assert_eq!(
+      error("struct A(u8);"),
+      "`FromCombinator` can only be derived for structs with named fields"
+    );
+    assert_eq!(
+      error("struct A;"),
+      "`FromCombinator` can only be derived for structs with named fields"
+    );
+    assert_eq!(
+      error("enum A { B }"),
+      "`FromCombinator` can only be derived for structs"
+    );
+    assert_eq!(
+      error("struct A {}"),
+      "`FromCombinator` requires at least one field"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(foo = "x")] a: u8 }"#),
+      "unknown parse attribute, expected `with` or `sep`"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(with = "x", with = "y")] a: u8 }"#),
+      "duplicate `with` attribute"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(sep = ",")] #[parse(sep = ",")] a: Vec<u8> }"#),
+      "duplicate `sep` attribute"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(with = 1)] a: u8 }"#),
+      "expected string literal"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(with = "1 +")] a: u8 }"#),
+      "unexpected end of input, expected an expression"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(sep = ",")] a: u8 }"#),
+      "`sep` is only allowed on `Vec` fields"
+    );
+    assert_eq!(
+      error(r#"struct A { #[parse(sep = ",")] a: Option<u8> }"#),
+      "`sep` is only allowed on `Vec` fields"
+    );
+  }
+
+  #[test]
+  fn expand_ok() {
+    let output = expand(
+      syn::parse_str(r#"pub struct A { a: u8, #[parse(with = "x()")] b: Option<u8>, #[parse(sep = ",")] c: Vec<u8> }"#)
+        .unwrap(),
+    )
+    .unwrap()
+    .to_string();
+    assert!(output.contains("pub fn combinator"));
+    assert!(output.contains("(x ()) . opt ()"));
+    assert!(output.contains(". sep (\",\")"));
+    assert!(output.contains("| ((a , b) , c) | Self { a , b , c }"));
+  }
+}
@@ -0,0 +1,107 @@
+use whitehole::{
+  combinator::{eat, eat_range, next, preceded, FromCombinator},
+  parser::Parser,
+};
+
+#[derive(FromCombinator, Debug, PartialEq)]
+struct Version {
+  major: u8,
+  #[parse(with = "preceded('.', u8::combinator())")]
+  minor: u8,
+  #[parse(with = "preceded('.', u8::combinator())")]
+  patch: Option<u8>,
+}
+
+#[derive(FromCombinator, Debug, PartialEq)]
+struct Header {
+  #[parse(with = r#"(eat_range('A'..='Z') * (1..)).select(|a| a.content().to_string())"#)]
+  method: String,
+  #[parse(with = "preceded(' ', Version::combinator())")]
+  version: Version,
+  #[parse(with = "preceded(' ', bool::combinator())")]
+  keep_alive: bool,
+  #[parse(
+    with = "preceded(' ', next(|c| c.is_ascii_alphabetic()).select(|a| a.content().chars().next().unwrap()))",
+    sep = ","
+  )]
+  tags: Vec<char>,
+  #[parse(with = "eat(';')")]
+  end: (),
+}
+
+fn parse<T>(
+  c: whitehole::combinator::Combinator<
+    impl whitehole::action::Action<Text = str, State = (), Heap = (), Value = T>,
+  >,
+  text: &str,
+) -> Option<(T, usize)> {
+  Parser::builder()
+    .entry(c)
+    .build(text)
+    .next()
+    .map(|o| (o.value, o.digested))
+}
+
+#[test]
+fn derive_version() {
+  assert_eq!(
+    parse(Version::combinator(), "1.2.3"),
+    Some((
+      Version {
+        major: 1,
+        minor: 2,
+        patch: Some(3)
+      },
+      5
+    ))
+  );
+  // optional field
+  assert_eq!(
+    parse(Version::combinator(), "1.2"),
+    Some((
+      Version {
+        major: 1,
+        minor: 2,
+        patch: None
+      },
+      3
+    ))
+  );
+  assert_eq!(parse(Version::combinator(), "1"), None);
+  assert_eq!(parse(Version::combinator(), "256.0"), None);
+  // the trait is also implemented
+  assert_eq!(
+    parse(<Version as FromCombinator>::combinator(), "1.0").map(|(_, d)| d),
+    Some(3)
+  );
+}
+
+#[test]
+fn derive_header() {
+  assert_eq!(
+    parse(Header::combinator(), "GET 1.1 true a, b, c;"),
+    Some((
+      Header {
+        method: "GET".into(),
+        version: Version {
+          major: 1,
+          minor: 1,
+          patch: None
+        },
+        keep_alive: true,
+        tags: vec!['a', 'b', 'c'],
+        end: (),
+      },
+      21
+    ))
+  );
+  // empty vec
+  assert_eq!(
+    parse(Header::combinator(), "POST 2.0.1 false;").map(|(h, _)| h.tags),
+    Some(vec![])
+  );
+  // missing the last field
+  assert_eq!(parse(Header::combinator(), "GET 1.1 true a"), None);
+  // invalid field
+  assert_eq!(parse(Header::combinator(), "get 1.1 true;"), None);
+}